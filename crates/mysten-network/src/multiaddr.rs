@@ -2,10 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use eyre::{eyre, Result};
-use std::{
-    borrow::Cow,
-    net::{IpAddr, SocketAddr},
-};
+#[cfg(not(target_arch = "wasm32"))]
+use std::borrow::Cow;
+use std::net::{IpAddr, SocketAddr};
 
 pub use ::multiaddr::Error;
 pub use ::multiaddr::Protocol;
@@ -52,6 +51,7 @@ impl Multiaddr {
 
     /// Attempts to convert a multiaddr of the form `/[ip4,ip6,dns]/{}/udp/{port}` into an anemo
     /// address
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn to_anemo_address(&self) -> Result<anemo::types::Address, &'static str> {
         let mut iter = self.iter();
 
@@ -69,6 +69,20 @@ impl Multiaddr {
         }
     }
 
+    /// Checks that the multiaddr is of the form `/[ip4,ip6,dns]/{}/udp/{port}`, as
+    /// [Self::to_anemo_address] does, on every target including those anemo does not build for.
+    pub fn validate_anemo_address(&self) -> Result<(), &'static str> {
+        let mut iter = self.iter();
+
+        match (iter.next(), iter.next()) {
+            (
+                Some(Protocol::Ip4(_) | Protocol::Ip6(_) | Protocol::Dns(_)),
+                Some(Protocol::Udp(_)),
+            ) => Ok(()),
+            _ => Err("invalid address"),
+        }
+    }
+
     pub fn udp_multiaddr_to_listen_address(&self) -> Option<std::net::SocketAddr> {
         let mut iter = self.iter();
 
@@ -179,6 +193,7 @@ pub(crate) fn parse_tcp<'a, T: Iterator<Item = Protocol<'a>>>(protocols: &mut T)
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 pub(crate) fn parse_http_https<'a, T: Iterator<Item = Protocol<'a>>>(
     protocols: &mut T,
 ) -> Result<&'static str> {
@@ -192,6 +207,7 @@ pub(crate) fn parse_http_https<'a, T: Iterator<Item = Protocol<'a>>>(
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 pub(crate) fn parse_end<'a, T: Iterator<Item = Protocol<'a>>>(protocols: &mut T) -> Result<()> {
     if protocols.next().is_none() {
         Ok(())
@@ -201,6 +217,7 @@ pub(crate) fn parse_end<'a, T: Iterator<Item = Protocol<'a>>>(protocols: &mut T)
 }
 
 // Parse a full /dns/-/tcp/-/{http,https} address
+#[cfg(not(target_arch = "wasm32"))]
 pub(crate) fn parse_dns(address: &Multiaddr) -> Result<(Cow<'_, str>, u16, &'static str)> {
     let mut iter = address.iter();
 
@@ -218,6 +235,7 @@ pub(crate) fn parse_dns(address: &Multiaddr) -> Result<(Cow<'_, str>, u16, &'sta
 }
 
 // Parse a full /ip4/-/tcp/-/{http,https} address
+#[cfg(not(target_arch = "wasm32"))]
 pub(crate) fn parse_ip4(address: &Multiaddr) -> Result<(SocketAddr, &'static str)> {
     let mut iter = address.iter();
 
@@ -237,6 +255,7 @@ pub(crate) fn parse_ip4(address: &Multiaddr) -> Result<(SocketAddr, &'static str
 }
 
 // Parse a full /ip6/-/tcp/-/{http,https} address
+#[cfg(not(target_arch = "wasm32"))]
 pub(crate) fn parse_ip6(address: &Multiaddr) -> Result<(SocketAddr, &'static str)> {
     let mut iter = address.iter();
 
@@ -256,7 +275,7 @@ pub(crate) fn parse_ip6(address: &Multiaddr) -> Result<(SocketAddr, &'static str
 }
 
 // Parse a full /unix/-/{http,https} address
-#[cfg(unix)]
+#[cfg(all(unix, not(target_arch = "wasm32")))]
 pub(crate) fn parse_unix(address: &Multiaddr) -> Result<(Cow<'_, str>, &'static str)> {
     let mut iter = address.iter();
 
@@ -296,4 +315,20 @@ mod test {
         let multi_addr_dns = Multiaddr(multiaddr!(Dnsaddr("mysten.sui"), Tcp(10500u16)));
         let _ = to_socket_addr(&multi_addr_dns).expect_err("DNS is unsupported");
     }
+
+    #[test]
+    fn test_validate_anemo_address() {
+        let udp = Multiaddr(multiaddr!(Dns("mysten.sui"), Udp(10500u16)));
+        udp.validate_anemo_address()
+            .expect("dns/udp is an anemo address");
+        assert_eq!(
+            udp.validate_anemo_address().is_ok(),
+            udp.to_anemo_address().is_ok()
+        );
+
+        let tcp = Multiaddr(multiaddr!(Ip4([127, 0, 0, 1]), Tcp(10500u16)));
+        let _ = tcp
+            .validate_anemo_address()
+            .expect_err("tcp is not an anemo address");
+    }
 }
@@ -0,0 +1,130 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A pool of the byte buffers holding the temporary values of execution, e.g. the serialized
+//! arguments of Move calls and the contents of the objects they take, so that the transactions
+//! executed by a thread, e.g. those of a checkpoint during catch-up, reuse the buffers of the
+//! previous ones instead of allocating their own.
+
+use std::cell::RefCell;
+
+/// Buffers kept by the pool of a thread, beyond which recycled buffers are dropped.
+const MAX_POOLED_BUFFERS: usize = 1024;
+
+/// Buffers of a larger capacity are dropped rather than recycled, so that a few large values do
+/// not keep their memory allocated for the lifetime of the thread.
+const MAX_POOLED_CAPACITY: usize = 64 * 1024;
+
+thread_local! {
+    static POOL: RefCell<BufferPool> = RefCell::new(BufferPool::default());
+}
+
+#[derive(Default)]
+struct BufferPool {
+    buffers: Vec<Vec<u8>>,
+    stats: BufferPoolStats,
+}
+
+/// Usage of the pool of a thread since the stats were last taken.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BufferPoolStats {
+    /// Buffers taken from the pool.
+    pub hits: u64,
+    /// Buffers allocated because the pool was empty.
+    pub misses: u64,
+}
+
+/// Returns an empty buffer, reused from the pool if possible.
+pub fn take() -> Vec<u8> {
+    POOL.with(|pool| {
+        let mut pool = pool.borrow_mut();
+        match pool.buffers.pop() {
+            Some(buffer) => {
+                pool.stats.hits += 1;
+                buffer
+            }
+            None => {
+                pool.stats.misses += 1;
+                Vec::new()
+            }
+        }
+    })
+}
+
+/// Returns a buffer holding a copy of `bytes`, reused from the pool if possible.
+pub fn copy_from(bytes: &[u8]) -> Vec<u8> {
+    let mut buffer = take();
+    buffer.extend_from_slice(bytes);
+    buffer
+}
+
+/// Gives `buffer` back to the pool, once the value it holds is no longer needed.
+pub fn recycle(mut buffer: Vec<u8>) {
+    if buffer.capacity() == 0 || buffer.capacity() > MAX_POOLED_CAPACITY {
+        return;
+    }
+    buffer.clear();
+    POOL.with(|pool| {
+        let mut pool = pool.borrow_mut();
+        if pool.buffers.len() < MAX_POOLED_BUFFERS {
+            pool.buffers.push(buffer);
+        }
+    })
+}
+
+pub fn recycle_all(buffers: impl IntoIterator<Item = Vec<u8>>) {
+    buffers.into_iter().for_each(recycle)
+}
+
+/// Returns the usage of the pool of the current thread, and resets it.
+pub fn take_stats() -> BufferPoolStats {
+    POOL.with(|pool| std::mem::take(&mut pool.borrow_mut().stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Every test runs on its own thread, with its own pool.
+
+    #[test]
+    fn test_take_and_recycle() {
+        let buffer = copy_from(b"value");
+        assert_eq!(buffer, b"value");
+        let ptr = buffer.as_ptr();
+        recycle(buffer);
+
+        // The recycled buffer is reused, empty.
+        let buffer = take();
+        assert!(buffer.is_empty());
+        assert!(buffer.capacity() >= 5);
+        assert_eq!(buffer.as_ptr(), ptr);
+        assert_eq!(take_stats(), BufferPoolStats { hits: 1, misses: 1 });
+        assert_eq!(take_stats(), BufferPoolStats::default());
+
+        // The pool is empty again.
+        take();
+        assert_eq!(take_stats(), BufferPoolStats { hits: 0, misses: 1 });
+    }
+
+    #[test]
+    fn test_recycle_all() {
+        recycle_all((0..3).map(|i| vec![i]));
+        let buffers: Vec<_> = (0..4).map(|_| take()).collect();
+        assert!(buffers.iter().all(Vec::is_empty));
+        assert_eq!(take_stats(), BufferPoolStats { hits: 3, misses: 1 });
+    }
+
+    #[test]
+    fn test_recycle_unpooled_buffers() {
+        // Buffers without an allocation, or with a large one, are not kept.
+        recycle(Vec::new());
+        recycle(Vec::with_capacity(MAX_POOLED_CAPACITY + 1));
+        take();
+        assert_eq!(take_stats(), BufferPoolStats { hits: 0, misses: 1 });
+
+        // Nor are buffers beyond the capacity of the pool.
+        recycle_all((0..MAX_POOLED_BUFFERS + 1).map(|_| Vec::with_capacity(1)));
+        POOL.with(|pool| assert_eq!(pool.borrow().buffers.len(), MAX_POOLED_BUFFERS));
+    }
+}
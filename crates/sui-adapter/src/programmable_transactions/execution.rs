@@ -53,7 +53,7 @@ use sui_verifier::{
     INIT_FN_NAME,
 };
 
-use crate::{adapter::substitute_package_id, execution_mode::ExecutionMode};
+use crate::{adapter::substitute_package_id, buffer_pool, execution_mode::ExecutionMode};
 
 use super::{context::*, types::*};
 
@@ -135,7 +135,7 @@ fn execute_command<S: StorageView, Mode: ExecutionMode>(
             )]
         }
         Command::MakeMoveVec(tag_opt, args) => {
-            let mut res = vec![];
+            let mut res = buffer_pool::take();
             leb128::write::unsigned(&mut res, args.len() as u64).unwrap();
             let mut arg_iter = args.into_iter().enumerate();
             let (mut used_in_non_entry_move_call, elem_ty) = match tag_opt {
@@ -160,6 +160,7 @@ fn execute_command<S: StorageView, Mode: ExecutionMode>(
                 used_in_non_entry_move_call =
                     used_in_non_entry_move_call || value.was_used_in_non_entry_move_call();
                 value.write_bcs_bytes(&mut res);
+                value.recycle();
             }
             let ty = Type::Vector(Box::new(elem_ty));
             let abilities = context
@@ -719,16 +720,15 @@ fn vm_move_call<S: StorageView>(
         }
     }
     // script visibility checked manually for entry points
-    let mut result = context
-        .session
-        .execute_function_bypass_visibility(
-            module_id,
-            function,
-            type_arguments,
-            serialized_arguments,
-            context.gas_status.move_gas_status(),
-        )
-        .map_err(|e| context.convert_vm_error(e))?;
+    let result = context.session.execute_function_bypass_visibility(
+        module_id,
+        function,
+        type_arguments,
+        serialized_arguments.iter().map(Vec::as_slice).collect(),
+        context.gas_status.move_gas_status(),
+    );
+    buffer_pool::recycle_all(serialized_arguments);
+    let mut result = result.map_err(|e| context.convert_vm_error(e))?;
 
     // When this function is used during publishing, it
     // may be executed several times, with objects being
@@ -1199,11 +1199,9 @@ fn build_move_args<S: StorageView, Mode: ExecutionMode>(
             ));
         }
         check_param_type::<_, Mode>(context, idx, &value, non_ref_param_ty)?;
-        let bytes = {
-            let mut v = vec![];
-            value.write_bcs_bytes(&mut v);
-            v
-        };
+        let mut bytes = buffer_pool::take();
+        value.write_bcs_bytes(&mut bytes);
+        value.recycle();
         serialized_args.push(bytes);
     }
     Ok((tx_ctx_kind, by_mut_ref, serialized_args))
@@ -23,6 +23,7 @@ use sui_types::{
 };
 
 use super::{context::load_type, linkage_view::LinkageView};
+use crate::buffer_pool;
 
 sui_macros::checked_arithmetic! {
 
@@ -172,6 +173,14 @@ impl Value {
         }
     }
 
+    /// Gives the buffer holding the value back to the [buffer_pool].
+    pub fn recycle(self) {
+        match self {
+            Value::Object(obj_value) => obj_value.recycle(),
+            Value::Raw(_, bytes) => buffer_pool::recycle(bytes),
+        }
+    }
+
     pub fn was_used_in_non_entry_move_call(&self) -> bool {
         match self {
             Value::Object(obj) => obj.used_in_non_entry_move_call,
@@ -204,7 +213,7 @@ impl ObjectValue {
             };
             ObjectContents::Coin(coin)
         } else {
-            ObjectContents::Raw(contents.to_vec())
+            ObjectContents::Raw(buffer_pool::copy_from(contents))
         };
         let tag: StructTag = type_.into();
         let type_ = load_type(session, &TypeTag::Struct(Box::new(tag)))
@@ -268,6 +277,13 @@ impl ObjectValue {
             ObjectContents::Coin(coin) => buf.extend(coin.to_bcs_bytes()),
         }
     }
+
+    /// Gives the buffer holding the contents of the object back to the [buffer_pool].
+    pub fn recycle(self) {
+        if let ObjectContents::Raw(bytes) = self.contents {
+            buffer_pool::recycle(bytes)
+        }
+    }
 }
 
 pub trait TryFromValue: Sized {
@@ -0,0 +1,213 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context, Result};
+use bytes::Bytes;
+use mysten_metrics::spawn_monitored_task;
+use object_store::path::Path;
+use object_store::DynObjectStore;
+use serde::{Deserialize, Serialize};
+use sui_data_ingestion::{CheckpointReader, FileProgressStore, ProgressStore, ReaderOptions};
+use sui_storage::object_store::util::put;
+use sui_storage::object_store::ObjectStoreConfig;
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tokio::sync::{oneshot, watch};
+use tracing::info;
+
+use crate::tables::{to_parquet, AnalyticsTable, TableRows};
+
+pub mod tables;
+
+#[cfg(test)]
+#[path = "unit_tests/analytics_tests.rs"]
+mod analytics_tests;
+
+/// Name of the task in the progress file.
+const TASK_NAME: &str = "analytics";
+
+/// How exported files are partitioned in the output store.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FilePartitioning {
+    /// `<table>/epoch=<epoch>/<first checkpoint>_<last checkpoint>.parquet`
+    #[default]
+    Epoch,
+    /// `<table>/date=<yyyy-mm-dd>/<first checkpoint>_<last checkpoint>.parquet`, using the UTC
+    /// date of the checkpoint timestamp.
+    Day,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AnalyticsConfig {
+    /// Local directory of checkpoint files, as read by `sui-data-ingestion`.
+    pub checkpoint_dir: PathBuf,
+    /// Remote store to read the checkpoints missing from `checkpoint-dir`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_store: Option<ObjectStoreConfig>,
+    /// Store the Parquet files are written to, a local directory or cloud bucket.
+    pub output_store: ObjectStoreConfig,
+    /// File recording the next checkpoint to export.
+    pub progress_file: PathBuf,
+    #[serde(default)]
+    pub partitioning: FilePartitioning,
+    /// Files are cut every `max-checkpoints-per-file` checkpoints, and when the partition changes.
+    #[serde(default = "default_max_checkpoints_per_file")]
+    pub max_checkpoints_per_file: u64,
+}
+
+fn default_max_checkpoints_per_file() -> u64 {
+    10000
+}
+
+/// Buffers the rows of consecutive checkpoints of a partition and writes them to one Parquet
+/// file per table. File boundaries only depend on the checkpoints, so that files written again
+/// after a restart replace the previous ones instead of duplicating rows.
+pub struct AnalyticsExporter {
+    config: AnalyticsConfig,
+    output_store: Arc<DynObjectStore>,
+    progress_store: FileProgressStore,
+    rows: TableRows,
+    partition: Option<String>,
+    first_checkpoint: CheckpointSequenceNumber,
+    next_checkpoint: CheckpointSequenceNumber,
+}
+
+impl AnalyticsExporter {
+    pub async fn new(config: AnalyticsConfig) -> Result<Self> {
+        let output_store = config.output_store.make()?;
+        let mut progress_store = FileProgressStore::new(config.progress_file.clone());
+        let next_checkpoint = progress_store.load(TASK_NAME.to_string()).await?;
+        Ok(Self {
+            config,
+            output_store,
+            progress_store,
+            rows: TableRows::default(),
+            partition: None,
+            first_checkpoint: next_checkpoint,
+            next_checkpoint,
+        })
+    }
+
+    /// Export checkpoints until `exit_receiver` fires.
+    pub async fn run(mut self, mut exit_receiver: oneshot::Receiver<()>) -> Result<()> {
+        info!(
+            checkpoint = self.next_checkpoint,
+            "Starting analytics exporter"
+        );
+        let (reader, mut checkpoint_receiver, gc_sender, reader_exit_sender) =
+            CheckpointReader::initialize(
+                self.config.checkpoint_dir.clone(),
+                self.next_checkpoint,
+                self.config.remote_store.clone(),
+                ReaderOptions::default(),
+            )?;
+        let mut reader_handle = spawn_monitored_task!(reader.run());
+
+        loop {
+            tokio::select! {
+                _ = &mut exit_receiver => break,
+                result = &mut reader_handle => {
+                    result?.context("Checkpoint reader failed")?;
+                    bail!("Checkpoint reader stopped");
+                }
+                Some(checkpoint) = checkpoint_receiver.recv() => {
+                    self.process_checkpoint(checkpoint, &gc_sender).await?;
+                }
+            }
+        }
+        let _ = reader_exit_sender.send(());
+        Ok(())
+    }
+
+    async fn process_checkpoint(
+        &mut self,
+        checkpoint: CheckpointData,
+        gc_sender: &watch::Sender<CheckpointSequenceNumber>,
+    ) -> Result<()> {
+        let sequence_number = checkpoint.checkpoint_summary.sequence_number;
+        let partition = partition_path(self.config.partitioning, &checkpoint)?;
+        if self.partition.as_ref() != Some(&partition) {
+            self.flush(gc_sender).await?;
+            self.partition = Some(partition);
+        }
+        self.rows.append(&checkpoint);
+        self.next_checkpoint = sequence_number + 1;
+        if self.next_checkpoint % self.config.max_checkpoints_per_file.max(1) == 0 {
+            self.flush(gc_sender).await?;
+        }
+        Ok(())
+    }
+
+    /// Write the buffered rows and record the progress.
+    async fn flush(&mut self, gc_sender: &watch::Sender<CheckpointSequenceNumber>) -> Result<()> {
+        let (Some(partition), false) = (&self.partition, self.rows.is_empty()) else {
+            return Ok(());
+        };
+        let file_name = format!(
+            "{}_{}.parquet",
+            self.first_checkpoint,
+            self.next_checkpoint - 1
+        );
+        let rows = std::mem::take(&mut self.rows);
+        self.write_table(partition, &file_name, &rows.checkpoints)
+            .await?;
+        self.write_table(partition, &file_name, &rows.transactions)
+            .await?;
+        self.write_table(partition, &file_name, &rows.events)
+            .await?;
+        self.write_table(partition, &file_name, &rows.object_changes)
+            .await?;
+        info!(
+            partition,
+            first_checkpoint = self.first_checkpoint,
+            last_checkpoint = self.next_checkpoint - 1,
+            "Exported checkpoints"
+        );
+
+        self.progress_store
+            .save(TASK_NAME.to_string(), self.next_checkpoint)
+            .await?;
+        gc_sender.send_replace(self.next_checkpoint);
+        self.first_checkpoint = self.next_checkpoint;
+        Ok(())
+    }
+
+    async fn write_table<T: AnalyticsTable>(
+        &self,
+        partition: &str,
+        file_name: &str,
+        rows: &[T],
+    ) -> Result<()> {
+        let location = Path::from(format!("{}/{partition}/{file_name}", T::NAME));
+        put(
+            &location,
+            Bytes::from(to_parquet(rows)?),
+            self.output_store.clone(),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+/// Partition directory of a checkpoint, e.g. `epoch=12` or `date=2023-05-01`.
+pub fn partition_path(
+    partitioning: FilePartitioning,
+    checkpoint: &CheckpointData,
+) -> Result<String> {
+    let summary = &checkpoint.checkpoint_summary;
+    Ok(match partitioning {
+        FilePartitioning::Epoch => format!("epoch={}", summary.epoch),
+        FilePartitioning::Day => {
+            let timestamp = summary.timestamp_ms;
+            let date = chrono::NaiveDateTime::from_timestamp_millis(timestamp as i64)
+                .ok_or_else(|| anyhow!("Invalid checkpoint timestamp {timestamp}"))?
+                .date();
+            format!("date={}", date.format("%Y-%m-%d"))
+        }
+    })
+}
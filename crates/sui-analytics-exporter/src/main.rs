@@ -0,0 +1,46 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use tokio::sync::oneshot;
+use tracing::info;
+
+use sui_analytics_exporter::{AnalyticsConfig, AnalyticsExporter};
+
+#[derive(Parser, Debug)]
+#[clap(
+    name = "sui-analytics-exporter",
+    about = "Export checkpoints, transactions, events and object changes as Parquet files"
+)]
+struct Args {
+    /// Path of the yaml config of the exporter.
+    #[clap(long)]
+    config: PathBuf,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let _guard = telemetry_subscribers::TelemetryConfig::new()
+        .with_env()
+        .init();
+
+    let args = Args::parse();
+    let config: AnalyticsConfig = serde_yaml::from_reader(
+        std::fs::File::open(&args.config)
+            .with_context(|| format!("Failed to open config {}", args.config.display()))?,
+    )?;
+    info!("Parsed analytics exporter config: {:#?}", config);
+
+    let (exit_sender, exit_receiver) = oneshot::channel();
+    tokio::spawn(async move {
+        let _ = tokio::signal::ctrl_c().await;
+        let _ = exit_sender.send(());
+    });
+    AnalyticsExporter::new(config)
+        .await?
+        .run(exit_receiver)
+        .await
+}
@@ -0,0 +1,366 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use arrow_array::{ArrayRef, BinaryArray, BooleanArray, RecordBatch, StringArray, UInt64Array};
+use arrow_schema::{DataType, Field, Schema};
+use parquet::arrow::ArrowWriter;
+use sui_types::base_types::ObjectRef;
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::messages::{TransactionDataAPI, TransactionEffectsAPI};
+use sui_types::object::Owner;
+use sui_types::storage::{DeleteKind, WriteKind};
+
+/// A table exported as Parquet files, see the README for the documented schemas.
+pub trait AnalyticsTable: Sized {
+    const NAME: &'static str;
+
+    fn schema() -> Schema;
+
+    fn columns(rows: &[Self]) -> Vec<ArrayRef>;
+}
+
+/// Encode the rows as a Parquet file.
+pub fn to_parquet<T: AnalyticsTable>(rows: &[T]) -> Result<Vec<u8>> {
+    let schema = Arc::new(T::schema());
+    let batch = RecordBatch::try_new(schema.clone(), T::columns(rows))?;
+    let mut writer = ArrowWriter::try_new(vec![], schema, None)?;
+    writer.write(&batch)?;
+    Ok(writer.into_inner()?)
+}
+
+fn u64_column<T>(rows: &[T], f: impl Fn(&T) -> u64) -> ArrayRef {
+    Arc::new(UInt64Array::from_iter_values(rows.iter().map(f)))
+}
+
+fn string_column<T>(rows: &[T], f: impl Fn(&T) -> &str) -> ArrayRef {
+    Arc::new(StringArray::from_iter_values(rows.iter().map(f)))
+}
+
+fn optional_string_column<T>(rows: &[T], f: impl Fn(&T) -> Option<&str>) -> ArrayRef {
+    Arc::new(StringArray::from(rows.iter().map(f).collect::<Vec<_>>()))
+}
+
+pub struct CheckpointRow {
+    pub sequence_number: u64,
+    pub epoch: u64,
+    pub timestamp_ms: u64,
+    pub digest: String,
+    pub previous_digest: Option<String>,
+    pub network_total_transactions: u64,
+    pub transaction_count: u64,
+    pub end_of_epoch: bool,
+}
+
+impl AnalyticsTable for CheckpointRow {
+    const NAME: &'static str = "checkpoints";
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new("sequence_number", DataType::UInt64, false),
+            Field::new("epoch", DataType::UInt64, false),
+            Field::new("timestamp_ms", DataType::UInt64, false),
+            Field::new("digest", DataType::Utf8, false),
+            Field::new("previous_digest", DataType::Utf8, true),
+            Field::new("network_total_transactions", DataType::UInt64, false),
+            Field::new("transaction_count", DataType::UInt64, false),
+            Field::new("end_of_epoch", DataType::Boolean, false),
+        ])
+    }
+
+    fn columns(rows: &[Self]) -> Vec<ArrayRef> {
+        vec![
+            u64_column(rows, |r| r.sequence_number),
+            u64_column(rows, |r| r.epoch),
+            u64_column(rows, |r| r.timestamp_ms),
+            string_column(rows, |r| &r.digest),
+            optional_string_column(rows, |r| r.previous_digest.as_deref()),
+            u64_column(rows, |r| r.network_total_transactions),
+            u64_column(rows, |r| r.transaction_count),
+            Arc::new(BooleanArray::from(
+                rows.iter().map(|r| r.end_of_epoch).collect::<Vec<_>>(),
+            )),
+        ]
+    }
+}
+
+pub struct TransactionRow {
+    pub transaction_digest: String,
+    pub checkpoint: u64,
+    pub epoch: u64,
+    pub timestamp_ms: u64,
+    pub sender: String,
+    pub gas_budget: u64,
+    pub gas_price: u64,
+    pub success: bool,
+    pub computation_cost: u64,
+    pub storage_cost: u64,
+    pub storage_rebate: u64,
+    pub move_call_count: u64,
+    pub event_count: u64,
+}
+
+impl AnalyticsTable for TransactionRow {
+    const NAME: &'static str = "transactions";
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new("transaction_digest", DataType::Utf8, false),
+            Field::new("checkpoint", DataType::UInt64, false),
+            Field::new("epoch", DataType::UInt64, false),
+            Field::new("timestamp_ms", DataType::UInt64, false),
+            Field::new("sender", DataType::Utf8, false),
+            Field::new("gas_budget", DataType::UInt64, false),
+            Field::new("gas_price", DataType::UInt64, false),
+            Field::new("success", DataType::Boolean, false),
+            Field::new("computation_cost", DataType::UInt64, false),
+            Field::new("storage_cost", DataType::UInt64, false),
+            Field::new("storage_rebate", DataType::UInt64, false),
+            Field::new("move_call_count", DataType::UInt64, false),
+            Field::new("event_count", DataType::UInt64, false),
+        ])
+    }
+
+    fn columns(rows: &[Self]) -> Vec<ArrayRef> {
+        vec![
+            string_column(rows, |r| &r.transaction_digest),
+            u64_column(rows, |r| r.checkpoint),
+            u64_column(rows, |r| r.epoch),
+            u64_column(rows, |r| r.timestamp_ms),
+            string_column(rows, |r| &r.sender),
+            u64_column(rows, |r| r.gas_budget),
+            u64_column(rows, |r| r.gas_price),
+            Arc::new(BooleanArray::from(
+                rows.iter().map(|r| r.success).collect::<Vec<_>>(),
+            )),
+            u64_column(rows, |r| r.computation_cost),
+            u64_column(rows, |r| r.storage_cost),
+            u64_column(rows, |r| r.storage_rebate),
+            u64_column(rows, |r| r.move_call_count),
+            u64_column(rows, |r| r.event_count),
+        ]
+    }
+}
+
+pub struct EventRow {
+    pub transaction_digest: String,
+    pub event_index: u64,
+    pub checkpoint: u64,
+    pub epoch: u64,
+    pub timestamp_ms: u64,
+    pub package: String,
+    pub module: String,
+    pub sender: String,
+    pub event_type: String,
+    pub bcs: Vec<u8>,
+}
+
+impl AnalyticsTable for EventRow {
+    const NAME: &'static str = "events";
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new("transaction_digest", DataType::Utf8, false),
+            Field::new("event_index", DataType::UInt64, false),
+            Field::new("checkpoint", DataType::UInt64, false),
+            Field::new("epoch", DataType::UInt64, false),
+            Field::new("timestamp_ms", DataType::UInt64, false),
+            Field::new("package", DataType::Utf8, false),
+            Field::new("module", DataType::Utf8, false),
+            Field::new("sender", DataType::Utf8, false),
+            Field::new("event_type", DataType::Utf8, false),
+            Field::new("bcs", DataType::Binary, false),
+        ])
+    }
+
+    fn columns(rows: &[Self]) -> Vec<ArrayRef> {
+        vec![
+            string_column(rows, |r| &r.transaction_digest),
+            u64_column(rows, |r| r.event_index),
+            u64_column(rows, |r| r.checkpoint),
+            u64_column(rows, |r| r.epoch),
+            u64_column(rows, |r| r.timestamp_ms),
+            string_column(rows, |r| &r.package),
+            string_column(rows, |r| &r.module),
+            string_column(rows, |r| &r.sender),
+            string_column(rows, |r| &r.event_type),
+            Arc::new(BinaryArray::from_iter_values(rows.iter().map(|r| &r.bcs))),
+        ]
+    }
+}
+
+pub struct ObjectChangeRow {
+    pub object_id: String,
+    pub version: u64,
+    pub digest: String,
+    pub transaction_digest: String,
+    pub checkpoint: u64,
+    pub epoch: u64,
+    pub timestamp_ms: u64,
+    /// One of `created`, `mutated`, `unwrapped`, `deleted`, `wrapped`, `unwrapped_then_deleted`.
+    pub change: String,
+    /// One of `address`, `object`, `shared`, `immutable`, null for deleted and wrapped objects.
+    pub owner_type: Option<String>,
+    pub owner_address: Option<String>,
+    pub object_type: Option<String>,
+}
+
+impl AnalyticsTable for ObjectChangeRow {
+    const NAME: &'static str = "object_changes";
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new("object_id", DataType::Utf8, false),
+            Field::new("version", DataType::UInt64, false),
+            Field::new("digest", DataType::Utf8, false),
+            Field::new("transaction_digest", DataType::Utf8, false),
+            Field::new("checkpoint", DataType::UInt64, false),
+            Field::new("epoch", DataType::UInt64, false),
+            Field::new("timestamp_ms", DataType::UInt64, false),
+            Field::new("change", DataType::Utf8, false),
+            Field::new("owner_type", DataType::Utf8, true),
+            Field::new("owner_address", DataType::Utf8, true),
+            Field::new("object_type", DataType::Utf8, true),
+        ])
+    }
+
+    fn columns(rows: &[Self]) -> Vec<ArrayRef> {
+        vec![
+            string_column(rows, |r| &r.object_id),
+            u64_column(rows, |r| r.version),
+            string_column(rows, |r| &r.digest),
+            string_column(rows, |r| &r.transaction_digest),
+            u64_column(rows, |r| r.checkpoint),
+            u64_column(rows, |r| r.epoch),
+            u64_column(rows, |r| r.timestamp_ms),
+            string_column(rows, |r| &r.change),
+            optional_string_column(rows, |r| r.owner_type.as_deref()),
+            optional_string_column(rows, |r| r.owner_address.as_deref()),
+            optional_string_column(rows, |r| r.object_type.as_deref()),
+        ]
+    }
+}
+
+/// Rows of all tables, for a batch of checkpoints.
+#[derive(Default)]
+pub struct TableRows {
+    pub checkpoints: Vec<CheckpointRow>,
+    pub transactions: Vec<TransactionRow>,
+    pub events: Vec<EventRow>,
+    pub object_changes: Vec<ObjectChangeRow>,
+}
+
+impl TableRows {
+    pub fn is_empty(&self) -> bool {
+        self.checkpoints.is_empty()
+    }
+
+    pub fn append(&mut self, checkpoint: &CheckpointData) {
+        let summary = &checkpoint.checkpoint_summary;
+        let (sequence_number, epoch, timestamp_ms) =
+            (summary.sequence_number, summary.epoch, summary.timestamp_ms);
+        self.checkpoints.push(CheckpointRow {
+            sequence_number,
+            epoch,
+            timestamp_ms,
+            digest: summary.digest().to_string(),
+            previous_digest: summary.previous_digest.map(|d| d.to_string()),
+            network_total_transactions: summary.network_total_transactions,
+            transaction_count: checkpoint.transactions.len() as u64,
+            end_of_epoch: summary.end_of_epoch_data.is_some(),
+        });
+
+        for transaction in &checkpoint.transactions {
+            let data = transaction.transaction.data().transaction_data();
+            let effects = &transaction.effects;
+            let transaction_digest = effects.transaction_digest().to_string();
+            let events = transaction
+                .events
+                .as_ref()
+                .map(|events| events.data.as_slice())
+                .unwrap_or_default();
+            let gas_cost = effects.gas_cost_summary();
+            self.transactions.push(TransactionRow {
+                transaction_digest: transaction_digest.clone(),
+                checkpoint: sequence_number,
+                epoch,
+                timestamp_ms,
+                sender: data.sender().to_string(),
+                gas_budget: data.gas_budget(),
+                gas_price: data.gas_price(),
+                success: effects.status().is_ok(),
+                computation_cost: gas_cost.computation_cost,
+                storage_cost: gas_cost.storage_cost,
+                storage_rebate: gas_cost.storage_rebate,
+                move_call_count: data.move_calls().len() as u64,
+                event_count: events.len() as u64,
+            });
+
+            for (event_index, event) in events.iter().enumerate() {
+                self.events.push(EventRow {
+                    transaction_digest: transaction_digest.clone(),
+                    event_index: event_index as u64,
+                    checkpoint: sequence_number,
+                    epoch,
+                    timestamp_ms,
+                    package: event.package_id.to_string(),
+                    module: event.transaction_module.to_string(),
+                    sender: event.sender.to_string(),
+                    event_type: event.type_.to_string(),
+                    bcs: event.contents.clone(),
+                });
+            }
+
+            let object_change = |object_ref: &ObjectRef, change: &str, owner: Option<&Owner>| {
+                let (owner_type, owner_address) = match owner {
+                    Some(Owner::AddressOwner(address)) => (Some("address"), Some(address)),
+                    Some(Owner::ObjectOwner(address)) => (Some("object"), Some(address)),
+                    Some(Owner::Shared { .. }) => (Some("shared"), None),
+                    Some(Owner::Immutable) => (Some("immutable"), None),
+                    None => (None, None),
+                };
+                let object_type = owner.and_then(|_| {
+                    transaction
+                        .output_objects
+                        .iter()
+                        .find(|o| o.id() == object_ref.0)
+                        .and_then(|o| o.struct_tag())
+                        .map(|tag| tag.to_string())
+                });
+                ObjectChangeRow {
+                    object_id: object_ref.0.to_string(),
+                    version: object_ref.1.value(),
+                    digest: object_ref.2.to_string(),
+                    transaction_digest: transaction_digest.clone(),
+                    checkpoint: sequence_number,
+                    epoch,
+                    timestamp_ms,
+                    change: change.to_string(),
+                    owner_type: owner_type.map(str::to_string),
+                    owner_address: owner_address.map(|a| a.to_string()),
+                    object_type,
+                }
+            };
+            for (object_ref, owner, kind) in effects.all_changed_objects() {
+                let change = match kind {
+                    WriteKind::Create => "created",
+                    WriteKind::Mutate => "mutated",
+                    WriteKind::Unwrap => "unwrapped",
+                };
+                self.object_changes
+                    .push(object_change(object_ref, change, Some(owner)));
+            }
+            for (object_ref, kind) in effects.all_deleted() {
+                let change = match kind {
+                    DeleteKind::Normal => "deleted",
+                    DeleteKind::Wrap => "wrapped",
+                    DeleteKind::UnwrapThenDelete => "unwrapped_then_deleted",
+                };
+                self.object_changes
+                    .push(object_change(object_ref, change, None));
+            }
+        }
+    }
+}
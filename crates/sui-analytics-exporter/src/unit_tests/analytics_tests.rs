@@ -0,0 +1,99 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use arrow_array::{Array, StringArray, UInt64Array};
+use move_core_types::ident_str;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use sui_types::base_types::ObjectID;
+use sui_types::committee::Committee;
+use sui_types::event::Event;
+use sui_types::full_checkpoint_content::CheckpointTransaction;
+use sui_types::gas_coin::GasCoin;
+use sui_types::messages::{TransactionEffects, TransactionEvents};
+use sui_types::messages_checkpoint::CheckpointContents;
+use sui_types::utils::{create_fake_transaction, mock_certified_checkpoint};
+
+use super::*;
+use crate::tables::{EventRow, TransactionRow};
+
+fn checkpoint_with_transaction() -> CheckpointData {
+    let (committee, keys) = Committee::new_simple_test_committee();
+    let transaction = create_fake_transaction().into_inner();
+    let sender = transaction.data().transaction_data().sender();
+    let event = Event {
+        package_id: ObjectID::random(),
+        transaction_module: ident_str!("test").to_owned(),
+        sender,
+        type_: GasCoin::type_(),
+        contents: vec![1, 2, 3],
+    };
+    CheckpointData {
+        checkpoint_summary: mock_certified_checkpoint(keys.iter(), committee, 3),
+        checkpoint_contents: CheckpointContents::new_with_causally_ordered_transactions(vec![]),
+        transactions: vec![CheckpointTransaction {
+            effects: TransactionEffects::new_with_tx(&transaction),
+            transaction,
+            events: Some(TransactionEvents {
+                data: vec![event.clone(), event],
+            }),
+            input_objects: vec![],
+            output_objects: vec![],
+        }],
+    }
+}
+
+#[test]
+fn test_partition_path() {
+    let checkpoint = checkpoint_with_transaction();
+    assert_eq!(
+        partition_path(FilePartitioning::Epoch, &checkpoint).unwrap(),
+        "epoch=0"
+    );
+    assert_eq!(
+        partition_path(FilePartitioning::Day, &checkpoint).unwrap(),
+        "date=1970-01-01"
+    );
+}
+
+#[test]
+fn test_parquet_round_trip() {
+    let checkpoint = checkpoint_with_transaction();
+    let mut rows = TableRows::default();
+    rows.append(&checkpoint);
+    assert_eq!(rows.checkpoints.len(), 1);
+    assert_eq!(rows.transactions.len(), 1);
+    assert_eq!(rows.events.len(), 2);
+
+    let file = Bytes::from(to_parquet(&rows.events).unwrap());
+    let reader = ParquetRecordBatchReaderBuilder::try_new(file)
+        .unwrap()
+        .build()
+        .unwrap();
+    let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(batches.len(), 1);
+    let batch = &batches[0];
+    assert_eq!(batch.schema().fields(), EventRow::schema().fields());
+    assert_eq!(batch.num_rows(), 2);
+
+    let indexes = batch
+        .column(1)
+        .as_any()
+        .downcast_ref::<UInt64Array>()
+        .unwrap();
+    assert_eq!(indexes.values().to_vec(), vec![0, 1]);
+    let digests = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(
+        digests.value(0),
+        rows.transactions[0].transaction_digest.as_str()
+    );
+    assert!(!digests.is_null(1));
+
+    // Empty batches are still valid files, with the schema of the table.
+    let file = Bytes::from(to_parquet::<TransactionRow>(&[]).unwrap());
+    let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+    assert_eq!(builder.schema().fields(), TransactionRow::schema().fields());
+}
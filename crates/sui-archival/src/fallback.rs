@@ -0,0 +1,139 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::index::{ArchivedTransaction, TransactionIndex, TransactionIndexEntry};
+use crate::reader::{contents_files_overlapping, ArchiveReader};
+use crate::FileMetadata;
+use anyhow::Result;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use sui_storage::object_store::ObjectStoreConfig;
+use sui_types::base_types::{SuiAddress, TransactionDigest};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tokio::time::Instant;
+use tracing::info;
+
+/// Minimum interval between two reads of the manifest of the archive to index its new files.
+const MANIFEST_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ArchiveReaderConfig {
+    pub object_store_config: ObjectStoreConfig,
+    /// Address of the operator key signing the manifest of the archive.
+    pub signer: SuiAddress,
+}
+
+/// Reads from an archive the transactions a node does not hold, e.g. because it was restored from
+/// a snapshot. The index of the archive is loaded lazily: only the index file of the checkpoint
+/// of a transaction when it is known, and otherwise the index files written since the last miss,
+/// up to the last checkpoint of the archive and at most once per [MANIFEST_REFRESH_INTERVAL].
+/// The state is only locked to look up and update the index, never across reads of the archive.
+pub struct ArchiveFallback {
+    reader: ArchiveReader,
+    signer: SuiAddress,
+    state: Mutex<FallbackState>,
+}
+
+#[derive(Default)]
+struct FallbackState {
+    index: TransactionIndex,
+    /// The checkpoints `0..indexed_until` of the archive are all indexed.
+    indexed_until: CheckpointSequenceNumber,
+    last_refresh: Option<Instant>,
+}
+
+impl ArchiveFallback {
+    pub fn new(config: &ArchiveReaderConfig) -> Result<Self> {
+        Ok(Self {
+            reader: ArchiveReader::new(&config.object_store_config)?,
+            signer: config.signer,
+            state: Default::default(),
+        })
+    }
+
+    /// Reads the transaction of `digest` from the archive, from the contents file of `checkpoint`
+    /// if the node knows the checkpoint of the transaction. Errors reading the archive are
+    /// returned, `None` means that the archive does not hold the transaction.
+    pub async fn get_transaction(
+        &self,
+        digest: &TransactionDigest,
+        checkpoint: Option<CheckpointSequenceNumber>,
+    ) -> Result<Option<ArchivedTransaction>> {
+        let mut location = self.locate(digest);
+        if location.is_none() {
+            self.load_index(checkpoint).await?;
+            location = self.locate(digest);
+        }
+        let Some((file, entry)) = location else {
+            return Ok(None);
+        };
+        self.reader
+            .read_transaction(&file, &entry, digest)
+            .await
+            .map(Some)
+    }
+
+    fn locate(&self, digest: &TransactionDigest) -> Option<(FileMetadata, TransactionIndexEntry)> {
+        self.state
+            .lock()
+            .index
+            .get(digest)
+            .map(|(file, entry)| (file.clone(), entry.clone()))
+    }
+
+    async fn load_index(&self, checkpoint: Option<CheckpointSequenceNumber>) -> Result<()> {
+        {
+            let mut state = self.state.lock();
+            match checkpoint {
+                Some(checkpoint) if checkpoint < state.indexed_until => return Ok(()),
+                Some(_) => (),
+                None if state
+                    .last_refresh
+                    .map_or(false, |last| last.elapsed() < MANIFEST_REFRESH_INTERVAL) =>
+                {
+                    return Ok(())
+                }
+                // Claims the refresh, so that concurrent misses do not read the archive again.
+                None => state.last_refresh = Some(Instant::now()),
+            }
+        }
+        let manifest = self.reader.read_manifest(self.signer).await?;
+        manifest.verify_coverage()?;
+        let (range, contents_files) = {
+            let state = self.state.lock();
+            let range = match checkpoint {
+                Some(checkpoint) => checkpoint..checkpoint.saturating_add(1),
+                None => state.indexed_until..manifest.next_checkpoint_seq_num,
+            };
+            let contents_files: Vec<_> = contents_files_overlapping(&manifest, &range)
+                .filter(|file| !state.index.contains_file(file))
+                .cloned()
+                .collect();
+            (range, contents_files)
+        };
+        let files = contents_files.len();
+        let entries = self
+            .reader
+            .read_index_entries(&manifest, contents_files)
+            .await?;
+
+        let mut state = self.state.lock();
+        for (contents_file, entries) in entries {
+            if !state.index.contains_file(&contents_file) {
+                state.index.insert(contents_file, entries);
+            }
+        }
+        if checkpoint.is_none() {
+            state.indexed_until = state.indexed_until.max(range.end);
+        }
+        if files > 0 {
+            info!(
+                "Loaded the index of {files} archive files, {} archived transactions are indexed",
+                state.index.len()
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Index of the transactions of an archive by digest.
+//!
+//! The index file of a contents file lists, for each transaction of its checkpoints, the byte
+//! range of the record of the checkpoint in the contents file. A reader loads the index files of
+//! the checkpoints it needs once, and then reads any of their transactions with a single ranged
+//! read of the contents of its checkpoint, instead of scanning the contents files.
+
+use crate::{record_ranges, FileMetadata, FileType};
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use sui_types::base_types::TransactionDigest;
+use sui_types::messages::{Transaction, TransactionEffects};
+use sui_types::messages_checkpoint::{
+    CheckpointContentsDigest, CheckpointSequenceNumber, CheckpointSummary, FullCheckpointContents,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionIndexEntry {
+    pub transaction: TransactionDigest,
+    pub checkpoint: CheckpointSequenceNumber,
+    /// Offset of the BCS encoded contents of the checkpoint in the contents file.
+    pub offset: u64,
+    pub length: u32,
+}
+
+impl TransactionIndexEntry {
+    pub fn byte_range(&self) -> Range<usize> {
+        let start = self.offset as usize;
+        start..start + self.length as usize
+    }
+}
+
+/// Computes the index entries of the contents file of the checkpoints from `start`.
+pub fn index_contents_file(
+    start: CheckpointSequenceNumber,
+    bytes: &[u8],
+) -> Result<Vec<TransactionIndexEntry>> {
+    let mut entries = vec![];
+    for (checkpoint, range) in (start..).zip(record_ranges(FileType::CheckpointContents, bytes)?) {
+        let contents: FullCheckpointContents = bcs::from_bytes(&bytes[range.clone()])?;
+        for data in contents.iter() {
+            entries.push(TransactionIndexEntry {
+                transaction: *data.transaction.digest(),
+                checkpoint,
+                offset: range.start as u64,
+                length: range.len().try_into()?,
+            });
+        }
+    }
+    Ok(entries)
+}
+
+/// In memory index of the transactions of some contents files of an archive, see
+/// [crate::ArchiveReader::load_transaction_index].
+#[derive(Debug, Default)]
+pub struct TransactionIndex {
+    contents_files: Vec<FileMetadata>,
+    /// First checkpoint of each contents file indexed.
+    indexed_files: HashSet<CheckpointSequenceNumber>,
+    locations: HashMap<TransactionDigest, (usize, TransactionIndexEntry)>,
+}
+
+impl TransactionIndex {
+    pub fn insert(&mut self, contents_file: FileMetadata, entries: Vec<TransactionIndexEntry>) {
+        let file = self.contents_files.len();
+        self.indexed_files
+            .insert(contents_file.checkpoint_seq_range.start);
+        self.contents_files.push(contents_file);
+        self.locations.extend(
+            entries
+                .into_iter()
+                .map(|entry| (entry.transaction, (file, entry))),
+        );
+    }
+
+    /// The contents file holding the checkpoint of `transaction`, and the location of the
+    /// checkpoint in the file.
+    pub fn get(
+        &self,
+        transaction: &TransactionDigest,
+    ) -> Option<(&FileMetadata, &TransactionIndexEntry)> {
+        self.locations
+            .get(transaction)
+            .map(|(file, entry)| (&self.contents_files[*file], entry))
+    }
+
+    /// Whether the transactions of `contents_file` are indexed.
+    pub fn contains_file(&self, contents_file: &FileMetadata) -> bool {
+        self.indexed_files
+            .contains(&contents_file.checkpoint_seq_range.start)
+    }
+
+    /// The number of indexed transactions.
+    pub fn len(&self) -> usize {
+        self.locations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.locations.is_empty()
+    }
+}
+
+/// A transaction read from an archive. It must be checked with [ArchivedTransaction::verify]
+/// against the certified summary of its checkpoint before it is trusted.
+#[derive(Clone, Debug)]
+pub struct ArchivedTransaction {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub transaction: Transaction,
+    pub effects: TransactionEffects,
+    /// Digest of the contents of the checkpoint the transaction was read from, computed from the
+    /// transactions and effects of the checkpoint.
+    pub contents_digest: CheckpointContentsDigest,
+}
+
+impl ArchivedTransaction {
+    /// Checks that the transaction and its effects were read from the contents certified by
+    /// `summary`, the summary of the checkpoint of the transaction.
+    pub fn verify(&self, summary: &CheckpointSummary) -> Result<()> {
+        ensure!(
+            summary.sequence_number == self.checkpoint,
+            "transaction {} was read from checkpoint {}, not {}",
+            self.transaction.digest(),
+            self.checkpoint,
+            summary.sequence_number
+        );
+        ensure!(
+            summary.content_digest == self.contents_digest,
+            "the contents of checkpoint {} in the archive do not match its certified contents",
+            self.checkpoint
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,253 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Archive of the certified checkpoints of the network and their contents, written to any
+//! object store supported by [sui_storage::object_store] (S3, GCS, Azure or a local directory),
+//! from which a node can recover the history of the chain if the state sync peers have pruned it.
+//!
+//! An archive is made of:
+//! - `epoch_<epoch>/<first checkpoint>.sum` files, each holding the certified summaries of a range
+//!   of consecutive checkpoints of an epoch.
+//! - `epoch_<epoch>/<first checkpoint>.chk` files, holding the full contents of the checkpoints of
+//!   the summary file of the same name.
+//! - `epoch_<epoch>/<first checkpoint>.idx` files, indexing the transactions of the contents file
+//!   of the same name by digest, see [index].
+//! - A `MANIFEST` listing every file of the archive with its checksum, signed by the key of the
+//!   operator of the archive.
+//!
+//! Files start with a magic number identifying their type, followed by BCS encoded records, each
+//! prefixed by its length as a little endian `u32`. The manifest is only uploaded after the files
+//! it lists, so that a reader never sees a manifest referencing missing files.
+
+use anyhow::{anyhow, ensure, Context, Result};
+use bytes::{BufMut, Bytes, BytesMut};
+use fastcrypto::hash::HashFunction;
+use object_store::path::Path;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use shared_crypto::intent::{Intent, IntentMessage, IntentScope, PersonalMessage};
+use std::collections::HashSet;
+use std::ops::Range;
+use sui_types::base_types::{EpochId, SuiAddress};
+use sui_types::crypto::{DefaultHash, Signature, SuiKeyPair, SuiSignature};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+pub mod fallback;
+pub mod index;
+pub mod reader;
+pub mod writer;
+
+#[cfg(test)]
+#[path = "unit_tests/archive_tests.rs"]
+mod archive_tests;
+
+pub use fallback::{ArchiveFallback, ArchiveReaderConfig};
+pub use reader::{ArchiveReader, ArchiveVerification};
+pub use writer::{ArchiveWriter, ArchiveWriterConfig};
+
+pub const MANIFEST_FILENAME: &str = "MANIFEST";
+const SUMMARY_FILE_MAGIC: u32 = 0x0000_5355;
+const CONTENTS_FILE_MAGIC: u32 = 0x0000_4348;
+const INDEX_FILE_MAGIC: u32 = 0x0000_4944;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FileType {
+    /// Certified checkpoint summaries.
+    CheckpointSummary,
+    /// Full contents of checkpoints.
+    CheckpointContents,
+    /// Locations of the transactions of a contents file.
+    TransactionIndex,
+}
+
+impl FileType {
+    fn magic(&self) -> u32 {
+        match self {
+            FileType::CheckpointSummary => SUMMARY_FILE_MAGIC,
+            FileType::CheckpointContents => CONTENTS_FILE_MAGIC,
+            FileType::TransactionIndex => INDEX_FILE_MAGIC,
+        }
+    }
+
+    fn suffix(&self) -> &'static str {
+        match self {
+            FileType::CheckpointSummary => "sum",
+            FileType::CheckpointContents => "chk",
+            FileType::TransactionIndex => "idx",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileMetadata {
+    pub file_type: FileType,
+    pub epoch: EpochId,
+    pub checkpoint_seq_range: Range<CheckpointSequenceNumber>,
+    /// Blake2b256 digest of the content of the file.
+    pub checksum: [u8; 32],
+}
+
+impl FileMetadata {
+    pub fn file_path(&self) -> Path {
+        Path::from(format!(
+            "epoch_{}/{}.{}",
+            self.epoch,
+            self.checkpoint_seq_range.start,
+            self.file_type.suffix()
+        ))
+    }
+}
+
+/// Index of the files of an archive, which hold checkpoints `0..next_checkpoint_seq_num`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Summary, contents and index files, ordered by checkpoint range.
+    pub files: Vec<FileMetadata>,
+    pub next_checkpoint_seq_num: CheckpointSequenceNumber,
+}
+
+impl Manifest {
+    pub fn files_of_type(&self, file_type: FileType) -> impl Iterator<Item = &FileMetadata> {
+        self.files
+            .iter()
+            .filter(move |file| file.file_type == file_type)
+    }
+
+    /// Checks that the summary and contents files are two sequences of consecutive checkpoint
+    /// ranges covering the checkpoints of the archive, with a contents file for each summary file.
+    /// Index files are optional, but must index a contents file.
+    pub fn verify_coverage(&self) -> Result<()> {
+        let summaries: Vec<_> = self.files_of_type(FileType::CheckpointSummary).collect();
+        let contents: Vec<_> = self.files_of_type(FileType::CheckpointContents).collect();
+        ensure!(
+            summaries.len() == contents.len(),
+            "{} summary files but {} contents files",
+            summaries.len(),
+            contents.len()
+        );
+        let mut next = 0;
+        for (summary, contents) in summaries.into_iter().zip(contents) {
+            ensure!(
+                summary.checkpoint_seq_range.start == next,
+                "expected a file starting at checkpoint {next}, found {}",
+                summary.file_path()
+            );
+            ensure!(
+                !summary.checkpoint_seq_range.is_empty(),
+                "empty file {}",
+                summary.file_path()
+            );
+            ensure!(
+                summary.checkpoint_seq_range == contents.checkpoint_seq_range
+                    && summary.epoch == contents.epoch,
+                "contents file {} does not match summary file {}",
+                contents.file_path(),
+                summary.file_path()
+            );
+            next = summary.checkpoint_seq_range.end;
+        }
+        let indexed: HashSet<_> = self
+            .files_of_type(FileType::CheckpointContents)
+            .map(|file| (file.epoch, file.checkpoint_seq_range.clone()))
+            .collect();
+        for index in self.files_of_type(FileType::TransactionIndex) {
+            ensure!(
+                indexed.contains(&(index.epoch, index.checkpoint_seq_range.clone())),
+                "index file {} does not match a contents file",
+                index.file_path()
+            );
+        }
+        ensure!(
+            next == self.next_checkpoint_seq_num,
+            "files end at checkpoint {next}, the manifest at {}",
+            self.next_checkpoint_seq_num
+        );
+        Ok(())
+    }
+}
+
+/// A [Manifest] signed by the operator of the archive, so that a reader only trusts archives
+/// written by a known operator, even if they are served by a third party storage.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignedManifest {
+    pub manifest: Manifest,
+    pub signature: Signature,
+}
+
+impl SignedManifest {
+    pub fn new(manifest: Manifest, keypair: &SuiKeyPair) -> Self {
+        let signature = Signature::new_secure(&Self::intent_message(&manifest), keypair);
+        Self {
+            manifest,
+            signature,
+        }
+    }
+
+    /// Verifies the signature of the manifest by `signer` and returns the manifest.
+    pub fn verify(self, signer: SuiAddress) -> Result<Manifest> {
+        self.signature
+            .verify_secure(&Self::intent_message(&self.manifest), signer)
+            .map_err(|e| anyhow!("invalid signature of the archive manifest: {e}"))?;
+        Ok(self.manifest)
+    }
+
+    fn intent_message(manifest: &Manifest) -> IntentMessage<PersonalMessage> {
+        IntentMessage::new(
+            Intent::sui_app(IntentScope::PersonalMessage),
+            PersonalMessage {
+                message: bcs::to_bytes(manifest).expect("Manifest serialization should not fail"),
+            },
+        )
+    }
+}
+
+pub fn compute_checksum(bytes: &[u8]) -> [u8; 32] {
+    DefaultHash::digest(bytes).digest
+}
+
+/// Encodes `records` in a file of type `file_type`.
+pub fn encode_file<T: Serialize>(file_type: FileType, records: &[T]) -> Result<Bytes> {
+    let mut buf = BytesMut::new();
+    buf.put_u32_le(file_type.magic());
+    for record in records {
+        let bytes = bcs::to_bytes(record)?;
+        buf.put_u32_le(bytes.len().try_into()?);
+        buf.put_slice(&bytes);
+    }
+    Ok(buf.freeze())
+}
+
+/// Decodes the records of a file of type `file_type`.
+pub fn decode_file<T: DeserializeOwned>(file_type: FileType, bytes: &[u8]) -> Result<Vec<T>> {
+    record_ranges(file_type, bytes)?
+        .into_iter()
+        .map(|range| Ok(bcs::from_bytes(&bytes[range])?))
+        .collect()
+}
+
+/// The byte ranges of the BCS encoded records of a file of type `file_type`.
+pub fn record_ranges(file_type: FileType, bytes: &[u8]) -> Result<Vec<Range<usize>>> {
+    let mut ranges = vec![];
+    let (magic, mut rest) = split(bytes, 4)?;
+    ensure!(
+        u32::from_le_bytes(magic.try_into()?) == file_type.magic(),
+        "not a {file_type:?} file"
+    );
+    while !rest.is_empty() {
+        let (len, tail) = split(rest, 4)?;
+        let (record, tail) = split(tail, u32::from_le_bytes(len.try_into()?) as usize)?;
+        let start = bytes.len() - tail.len() - record.len();
+        ranges.push(start..start + record.len());
+        rest = tail;
+    }
+    Ok(ranges)
+}
+
+fn split(bytes: &[u8], len: usize) -> Result<(&[u8], &[u8])> {
+    ensure!(bytes.len() >= len, "truncated file");
+    Ok(bytes.split_at(len))
+}
+
+pub(crate) fn decode_manifest(bytes: &[u8]) -> Result<SignedManifest> {
+    bcs::from_bytes(bytes).context("invalid archive manifest")
+}
@@ -0,0 +1,355 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::index::{
+    index_contents_file, ArchivedTransaction, TransactionIndex, TransactionIndexEntry,
+};
+use crate::{
+    compute_checksum, decode_file, decode_manifest, FileMetadata, FileType, Manifest,
+    MANIFEST_FILENAME,
+};
+use anyhow::{anyhow, ensure, Context, Result};
+use bytes::Bytes;
+use object_store::path::Path;
+use object_store::DynObjectStore;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+use std::sync::Arc;
+use sui_light_client::CommitteeTracker;
+use sui_storage::object_store::ObjectStoreConfig;
+use sui_types::base_types::{EpochId, SuiAddress, TransactionDigest};
+use sui_types::committee::Committee;
+use sui_types::messages::TransactionEffectsAPI;
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointSequenceNumber, FullCheckpointContents,
+};
+use tracing::info;
+
+/// Summary of the checkpoints checked by [ArchiveReader::verify].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ArchiveVerification {
+    /// Number of summary, contents and index files read.
+    pub files: usize,
+    /// Number of checkpoints whose contents were verified.
+    pub checkpoints: u64,
+    pub transactions: u64,
+    /// Epoch of the last verified checkpoint.
+    pub epoch: EpochId,
+}
+
+impl Display for ArchiveVerification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Verified {} checkpoints with {} transactions in {} files, up to epoch {}",
+            self.checkpoints, self.transactions, self.files, self.epoch
+        )
+    }
+}
+
+pub struct ArchiveReader {
+    remote_store: Arc<DynObjectStore>,
+}
+
+impl ArchiveReader {
+    pub fn new(config: &ObjectStoreConfig) -> Result<Self> {
+        Ok(Self {
+            remote_store: config.make()?,
+        })
+    }
+
+    /// Reads the manifest of the archive, which must be signed by `signer`.
+    pub async fn read_manifest(&self, signer: SuiAddress) -> Result<Manifest> {
+        let bytes = self
+            .remote_store
+            .get(&Path::from(MANIFEST_FILENAME))
+            .await?
+            .bytes()
+            .await?;
+        decode_manifest(&bytes)?.verify(signer)
+    }
+
+    pub async fn read_summaries(
+        &self,
+        file: &FileMetadata,
+    ) -> Result<Vec<CertifiedCheckpointSummary>> {
+        let bytes = self.read_file(file, FileType::CheckpointSummary).await?;
+        decode_checkpoints(file, &bytes)
+    }
+
+    pub async fn read_contents(&self, file: &FileMetadata) -> Result<Vec<FullCheckpointContents>> {
+        let bytes = self.read_file(file, FileType::CheckpointContents).await?;
+        decode_checkpoints(file, &bytes)
+    }
+
+    pub async fn read_index(&self, file: &FileMetadata) -> Result<Vec<TransactionIndexEntry>> {
+        let bytes = self.read_file(file, FileType::TransactionIndex).await?;
+        decode_file(FileType::TransactionIndex, &bytes)
+            .with_context(|| format!("{}", file.file_path()))
+    }
+
+    /// Downloads a file and checks it against its checksum in the manifest.
+    async fn read_file(&self, file: &FileMetadata, file_type: FileType) -> Result<Bytes> {
+        let path = file.file_path();
+        ensure!(
+            file.file_type == file_type,
+            "{path} is not a {file_type:?} file"
+        );
+        let bytes = self.remote_store.get(&path).await?.bytes().await?;
+        ensure!(
+            compute_checksum(&bytes) == file.checksum,
+            "checksum mismatch of {path}"
+        );
+        Ok(bytes)
+    }
+
+    /// Verifies the checkpoints of `range` end to end, so that operators can rely on the archive
+    /// to recover them:
+    /// - the files of the manifest cover the archive, and match their checksums.
+    /// - the summaries from genesis form a chain of consecutive checkpoints certified by the
+    ///   committee of their epoch, starting from `genesis_committee`.
+    /// - the contents of the checkpoints of `range` are the ones of their certified summary, and
+    ///   their index files locate their transactions.
+    pub async fn verify(
+        &self,
+        manifest: &Manifest,
+        genesis_committee: Committee,
+        range: Range<CheckpointSequenceNumber>,
+    ) -> Result<ArchiveVerification> {
+        manifest.verify_coverage()?;
+        ensure!(
+            range.end <= manifest.next_checkpoint_seq_num,
+            "the archive only holds checkpoints up to {}",
+            manifest.next_checkpoint_seq_num
+        );
+        let mut tracker = CommitteeTracker::new(genesis_committee);
+        let mut verification = ArchiveVerification::default();
+        let mut previous_digest = None;
+        let index_files = index_files(manifest);
+        let contents_files = manifest.files_of_type(FileType::CheckpointContents);
+        for (summary_file, contents_file) in manifest
+            .files_of_type(FileType::CheckpointSummary)
+            .zip(contents_files)
+            .take_while(|(file, _)| file.checkpoint_seq_range.start < range.end)
+        {
+            let summaries = self.read_summaries(summary_file).await?;
+            verification.files += 1;
+            for (sequence_number, summary) in
+                summary_file.checkpoint_seq_range.clone().zip(&summaries)
+            {
+                ensure!(
+                    summary.sequence_number == sequence_number
+                        && summary.epoch == summary_file.epoch,
+                    "{} holds checkpoint {} of epoch {}",
+                    summary_file.file_path(),
+                    summary.sequence_number,
+                    summary.epoch
+                );
+                ensure!(
+                    summary.previous_digest == previous_digest,
+                    "checkpoint {sequence_number} does not follow the previous checkpoint"
+                );
+                previous_digest = Some(*summary.digest());
+                if summary.end_of_epoch_data.is_some() {
+                    tracker.advance_epoch(summary.clone())?;
+                } else {
+                    tracker.verify_checkpoint(summary.clone())?;
+                }
+            }
+
+            if summary_file.checkpoint_seq_range.end <= range.start {
+                continue;
+            }
+            let bytes = self
+                .read_file(contents_file, FileType::CheckpointContents)
+                .await?;
+            let contents: Vec<FullCheckpointContents> = decode_checkpoints(contents_file, &bytes)?;
+            verification.files += 1;
+            if let Some(index_file) = index_files.get(&file_key(contents_file)) {
+                let start = contents_file.checkpoint_seq_range.start;
+                ensure!(
+                    self.read_index(index_file).await? == index_contents_file(start, &bytes)?,
+                    "index file {} does not match its contents file",
+                    index_file.file_path()
+                );
+                verification.files += 1;
+            }
+            for (summary, contents) in summaries.iter().zip(&contents) {
+                if !range.contains(&summary.sequence_number) {
+                    continue;
+                }
+                contents
+                    .verify_digests(summary.content_digest)
+                    .with_context(|| format!("checkpoint {}", summary.sequence_number))?;
+                verification.checkpoints += 1;
+                verification.transactions += contents.iter().count() as u64;
+                verification.epoch = summary.epoch;
+            }
+            info!(
+                "Verified checkpoints {:?}",
+                summary_file.checkpoint_seq_range
+            );
+        }
+        Ok(verification)
+    }
+
+    /// Loads the index of the transactions of the checkpoints of `range`, from the index files
+    /// of the contents files overlapping it. Contents files written without an index file are
+    /// indexed from their contents.
+    pub async fn load_transaction_index(
+        &self,
+        manifest: &Manifest,
+        range: Range<CheckpointSequenceNumber>,
+    ) -> Result<TransactionIndex> {
+        let mut index = TransactionIndex::default();
+        self.extend_transaction_index(&mut index, manifest, range)
+            .await?;
+        Ok(index)
+    }
+
+    /// Adds to `index` the transactions of the contents files overlapping `range` that it does not
+    /// index yet, so that the index of an archive is loaded as its checkpoints are needed.
+    pub async fn extend_transaction_index(
+        &self,
+        index: &mut TransactionIndex,
+        manifest: &Manifest,
+        range: Range<CheckpointSequenceNumber>,
+    ) -> Result<()> {
+        manifest.verify_coverage()?;
+        let contents_files: Vec<_> = contents_files_overlapping(manifest, &range)
+            .filter(|file| !index.contains_file(file))
+            .cloned()
+            .collect();
+        let files = contents_files.len();
+        for (contents_file, entries) in self.read_index_entries(manifest, contents_files).await? {
+            index.insert(contents_file, entries);
+        }
+        if files > 0 {
+            info!(
+                "Loaded the index of {files} archive files, {} archived transactions are indexed",
+                index.len()
+            );
+        }
+        Ok(())
+    }
+
+    /// Reads the index entries of `contents_files` of `manifest`, from their index files, or from
+    /// their contents when they were written without an index file.
+    pub async fn read_index_entries(
+        &self,
+        manifest: &Manifest,
+        contents_files: Vec<FileMetadata>,
+    ) -> Result<Vec<(FileMetadata, Vec<TransactionIndexEntry>)>> {
+        let index_files = index_files(manifest);
+        let mut entries = Vec::with_capacity(contents_files.len());
+        for contents_file in contents_files {
+            let file_entries = match index_files.get(&file_key(&contents_file)) {
+                Some(index_file) => self.read_index(index_file).await?,
+                None => index_contents_file(
+                    contents_file.checkpoint_seq_range.start,
+                    &self
+                        .read_file(&contents_file, FileType::CheckpointContents)
+                        .await?,
+                )?,
+            };
+            entries.push((contents_file, file_entries));
+        }
+        Ok(entries)
+    }
+
+    /// Reads a transaction and its effects with a ranged read of the contents of its checkpoint
+    /// in `index`, see [Self::read_transaction].
+    pub async fn get_transaction(
+        &self,
+        index: &TransactionIndex,
+        digest: &TransactionDigest,
+    ) -> Result<Option<ArchivedTransaction>> {
+        let Some((file, entry)) = index.get(digest) else {
+            return Ok(None);
+        };
+        self.read_transaction(file, entry, digest).await.map(Some)
+    }
+
+    /// Reads a transaction and its effects with a ranged read of the contents of its checkpoint at
+    /// `entry` in the contents file `file`. The checksum of the contents file is not checked, but
+    /// the transaction is the one of `digest`, and the effects are the effects of the transaction.
+    /// The digest of the contents of the checkpoint is computed from the transactions and effects
+    /// read, to check them against the certified checkpoint with [ArchivedTransaction::verify].
+    pub async fn read_transaction(
+        &self,
+        file: &FileMetadata,
+        entry: &TransactionIndexEntry,
+        digest: &TransactionDigest,
+    ) -> Result<ArchivedTransaction> {
+        let bytes = self
+            .remote_store
+            .get_range(&file.file_path(), entry.byte_range())
+            .await?;
+        let contents: FullCheckpointContents = bcs::from_bytes(&bytes)
+            .with_context(|| format!("checkpoint {} in {}", entry.checkpoint, file.file_path()))?;
+        let data = contents
+            .iter()
+            .find(|data| data.transaction.digest() == digest)
+            .ok_or_else(|| {
+                anyhow!(
+                    "transaction {digest} is not in checkpoint {}",
+                    entry.checkpoint
+                )
+            })?;
+        ensure!(
+            data.effects.transaction_digest() == digest,
+            "the effects of transaction {digest} are the effects of {}",
+            data.effects.transaction_digest()
+        );
+        Ok(ArchivedTransaction {
+            checkpoint: entry.checkpoint,
+            transaction: data.transaction.clone(),
+            effects: data.effects.clone(),
+            contents_digest: *contents.checkpoint_contents().digest(),
+        })
+    }
+}
+
+/// The contents files of `manifest` holding checkpoints of `range`.
+pub fn contents_files_overlapping<'a>(
+    manifest: &'a Manifest,
+    range: &'a Range<CheckpointSequenceNumber>,
+) -> impl Iterator<Item = &'a FileMetadata> {
+    manifest
+        .files_of_type(FileType::CheckpointContents)
+        .filter(move |file| {
+            file.checkpoint_seq_range.start < range.end
+                && range.start < file.checkpoint_seq_range.end
+        })
+}
+
+fn file_key(file: &FileMetadata) -> (EpochId, Range<CheckpointSequenceNumber>) {
+    (file.epoch, file.checkpoint_seq_range.clone())
+}
+
+fn index_files(
+    manifest: &Manifest,
+) -> HashMap<(EpochId, Range<CheckpointSequenceNumber>), &FileMetadata> {
+    manifest
+        .files_of_type(FileType::TransactionIndex)
+        .map(|file| (file_key(file), file))
+        .collect()
+}
+
+/// Decodes the checkpoints of a summary or contents file.
+fn decode_checkpoints<T: serde::de::DeserializeOwned>(
+    file: &FileMetadata,
+    bytes: &[u8],
+) -> Result<Vec<T>> {
+    let records: Vec<T> =
+        decode_file(file.file_type, bytes).with_context(|| format!("{}", file.file_path()))?;
+    ensure!(
+        records.len() as u64 == file.checkpoint_seq_range.end - file.checkpoint_seq_range.start,
+        "{} holds {} checkpoints, expected {:?}",
+        file.file_path(),
+        records.len(),
+        file.checkpoint_seq_range
+    );
+    Ok(records)
+}
@@ -0,0 +1,425 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::fallback::{ArchiveFallback, ArchiveReaderConfig};
+use crate::reader::{ArchiveReader, ArchiveVerification};
+use crate::writer::{ArchiveWriter, ArchiveWriterConfig};
+use crate::{FileType, Manifest};
+use fastcrypto::traits::KeyPair;
+use std::path::Path;
+use sui_storage::object_store::{ObjectStoreConfig, ObjectStoreType};
+use sui_types::base_types::{ExecutionData, SuiAddress, TransactionDigest};
+use sui_types::committee::{Committee, ProtocolVersion};
+use sui_types::crypto::{get_key_pair, AccountKeyPair, AuthorityKeyPair, SuiKeyPair};
+use sui_types::gas::GasCostSummary;
+use sui_types::messages::{
+    TransactionEffects, TransactionEffectsAPI, TransactionEffectsV1, VerifiedTransaction,
+};
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointSummary, EndOfEpochData, FullCheckpointContents,
+    SignedCheckpointSummary, VerifiedCheckpoint, VerifiedCheckpointContents,
+};
+use sui_types::storage::SharedInMemoryStore;
+
+/// Builds a chain of checkpoints with one transaction each, signed by the committees of their
+/// epoch.
+struct ChainBuilder {
+    store: SharedInMemoryStore,
+    committees: Vec<(Committee, Vec<AuthorityKeyPair>)>,
+    previous: Option<VerifiedCheckpoint>,
+    transactions: Vec<TransactionDigest>,
+}
+
+impl ChainBuilder {
+    fn new() -> Self {
+        let (genesis_committee, genesis_keys) = Committee::new_simple_test_committee();
+        let (next_committee, next_keys) = Committee::new_simple_test_committee_of_size(5);
+        let next_committee = Committee::new(1, next_committee.voting_rights.into_iter().collect());
+        Self {
+            store: SharedInMemoryStore::default(),
+            committees: vec![
+                (genesis_committee, genesis_keys),
+                (next_committee, next_keys),
+            ],
+            previous: None,
+            transactions: vec![],
+        }
+    }
+
+    fn genesis_committee(&self) -> Committee {
+        self.committees[0].0.clone()
+    }
+
+    fn add_checkpoint(&mut self, end_of_epoch: bool) {
+        let (sequence_number, epoch, previous_digest) = match &self.previous {
+            Some(previous) => (
+                previous.sequence_number + 1,
+                previous.epoch + previous.end_of_epoch_data.is_some() as u64,
+                Some(*previous.digest()),
+            ),
+            None => (0, 0, None),
+        };
+        let (committee, keys) = &self.committees[epoch as usize];
+        let transaction =
+            VerifiedTransaction::new_consensus_commit_prologue(epoch, sequence_number, 0);
+        self.transactions.push(*transaction.digest());
+        let effects = TransactionEffects::V1(TransactionEffectsV1 {
+            transaction_digest: *transaction.digest(),
+            ..Default::default()
+        });
+        let contents =
+            FullCheckpointContents::new_with_causally_ordered_transactions([ExecutionData::new(
+                transaction.into_inner(),
+                effects,
+            )]);
+        let end_of_epoch_data = end_of_epoch.then(|| EndOfEpochData {
+            next_epoch_committee: self.committees[epoch as usize + 1].0.voting_rights.clone(),
+            next_epoch_protocol_version: ProtocolVersion::MIN,
+            epoch_commitments: vec![],
+        });
+        let summary = CheckpointSummary::new(
+            epoch,
+            sequence_number,
+            sequence_number + 1,
+            &contents.checkpoint_contents(),
+            previous_digest,
+            GasCostSummary::default(),
+            end_of_epoch_data,
+            0,
+        );
+        let sign_infos: Vec<_> = keys
+            .iter()
+            .map(|k| SignedCheckpointSummary::sign(epoch, &summary, k, k.public().into()))
+            .collect();
+        let checkpoint = VerifiedCheckpoint::new_unchecked(
+            CertifiedCheckpointSummary::new(summary, sign_infos, committee).unwrap(),
+        );
+
+        let mut store = self.store.inner_mut();
+        if sequence_number == 0 {
+            store.insert_genesis_state(
+                checkpoint.clone(),
+                VerifiedCheckpointContents::new_unchecked(contents),
+                committee.clone(),
+            );
+        } else {
+            store.insert_checkpoint(checkpoint.clone());
+            store.insert_checkpoint_contents(VerifiedCheckpointContents::new_unchecked(contents));
+            store.update_highest_synced_checkpoint(&checkpoint);
+        }
+        self.previous = Some(checkpoint);
+    }
+}
+
+fn object_store_config(path: &Path) -> ObjectStoreConfig {
+    ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(path.to_path_buf()),
+        ..Default::default()
+    }
+}
+
+fn ranges(manifest: &Manifest, file_type: FileType) -> Vec<(u64, std::ops::Range<u64>)> {
+    manifest
+        .files_of_type(file_type)
+        .map(|file| (file.epoch, file.checkpoint_seq_range.clone()))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_write_and_verify_archive() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let config = ArchiveWriterConfig {
+        object_store_config: object_store_config(dir.path()),
+        checkpoints_per_file: 2,
+    };
+    let mut chain = ChainBuilder::new();
+    for end_of_epoch in [false, false, true, false] {
+        chain.add_checkpoint(end_of_epoch);
+    }
+    let (_, keypair): (_, AccountKeyPair) = get_key_pair();
+    let writer = ArchiveWriter::new(&config, chain.store.clone(), SuiKeyPair::Ed25519(keypair))?;
+    let signer = writer.signer();
+
+    // Files end at the end of each epoch.
+    let manifest = writer.archive_synced_checkpoints().await?;
+    assert_eq!(manifest.next_checkpoint_seq_num, 4);
+    assert_eq!(
+        ranges(&manifest, FileType::CheckpointSummary),
+        vec![(0, 0..2), (0, 2..3), (1, 3..4)]
+    );
+    assert_eq!(
+        ranges(&manifest, FileType::CheckpointContents),
+        ranges(&manifest, FileType::CheckpointSummary)
+    );
+
+    // The next run appends the new checkpoints.
+    chain.add_checkpoint(false);
+    chain.add_checkpoint(false);
+    let manifest = writer.archive_synced_checkpoints().await?;
+    assert_eq!(
+        ranges(&manifest, FileType::CheckpointSummary),
+        vec![(0, 0..2), (0, 2..3), (1, 3..4), (1, 4..6)]
+    );
+
+    let reader = ArchiveReader::new(&config.object_store_config)?;
+    assert_eq!(reader.read_manifest(signer).await?, manifest);
+    assert!(reader
+        .read_manifest(SuiAddress::random_for_testing_only())
+        .await
+        .is_err());
+    assert_eq!(
+        reader
+            .verify(&manifest, chain.genesis_committee(), 0..6)
+            .await?,
+        ArchiveVerification {
+            files: 12,
+            checkpoints: 6,
+            transactions: 6,
+            epoch: 1,
+        }
+    );
+    // Summaries are verified from genesis, contents only in the range.
+    assert_eq!(
+        reader
+            .verify(&manifest, chain.genesis_committee(), 3..4)
+            .await?,
+        ArchiveVerification {
+            files: 5,
+            checkpoints: 1,
+            transactions: 1,
+            epoch: 1,
+        }
+    );
+    assert!(reader
+        .verify(&manifest, chain.genesis_committee(), 0..7)
+        .await
+        .is_err());
+    let (other_committee, _) = Committee::new_simple_test_committee();
+    assert!(reader
+        .verify(&manifest, other_committee, 0..6)
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_verify_corrupted_archive() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let config = ArchiveWriterConfig {
+        object_store_config: object_store_config(dir.path()),
+        checkpoints_per_file: 2,
+    };
+    let mut chain = ChainBuilder::new();
+    for _ in 0..4 {
+        chain.add_checkpoint(false);
+    }
+    let (_, keypair): (_, AccountKeyPair) = get_key_pair();
+    let writer = ArchiveWriter::new(&config, chain.store.clone(), SuiKeyPair::Ed25519(keypair))?;
+    let manifest = writer.archive_synced_checkpoints().await?;
+    let reader = ArchiveReader::new(&config.object_store_config)?;
+    reader
+        .verify(&manifest, chain.genesis_committee(), 0..4)
+        .await?;
+
+    // A corrupted contents file fails its checksum, the previous checkpoints are still valid.
+    let file = manifest
+        .files_of_type(FileType::CheckpointContents)
+        .last()
+        .unwrap();
+    let path = dir.path().join(file.file_path().to_string());
+    let mut bytes = std::fs::read(&path)?;
+    *bytes.last_mut().unwrap() ^= 1;
+    std::fs::write(&path, bytes)?;
+    let error = reader
+        .verify(&manifest, chain.genesis_committee(), 0..4)
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("checksum mismatch"), "{error}");
+    reader
+        .verify(&manifest, chain.genesis_committee(), 0..2)
+        .await?;
+
+    // A manifest missing a file does not cover the archive.
+    let mut truncated = manifest.clone();
+    truncated.files.remove(0);
+    assert!(reader
+        .verify(&truncated, chain.genesis_committee(), 0..2)
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_transaction() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let config = ArchiveWriterConfig {
+        object_store_config: object_store_config(dir.path()),
+        checkpoints_per_file: 2,
+    };
+    let mut chain = ChainBuilder::new();
+    for end_of_epoch in [false, true, false, false, false] {
+        chain.add_checkpoint(end_of_epoch);
+    }
+    let (_, keypair): (_, AccountKeyPair) = get_key_pair();
+    let writer = ArchiveWriter::new(&config, chain.store.clone(), SuiKeyPair::Ed25519(keypair))?;
+    let manifest = writer.archive_synced_checkpoints().await?;
+    assert_eq!(
+        ranges(&manifest, FileType::TransactionIndex),
+        ranges(&manifest, FileType::CheckpointContents)
+    );
+
+    let reader = ArchiveReader::new(&config.object_store_config)?;
+    let index = reader
+        .load_transaction_index(&manifest, 0..manifest.next_checkpoint_seq_num)
+        .await?;
+    assert_eq!(index.len(), chain.transactions.len());
+    let summaries: Vec<_> = (0..chain.transactions.len() as u64)
+        .map(|seq| {
+            chain
+                .store
+                .inner()
+                .get_checkpoint_by_sequence_number(seq)
+                .unwrap()
+                .clone()
+        })
+        .collect();
+    for (checkpoint, digest) in chain.transactions.iter().enumerate() {
+        let transaction = reader.get_transaction(&index, digest).await?.unwrap();
+        assert_eq!(transaction.checkpoint, checkpoint as u64);
+        assert_eq!(transaction.transaction.digest(), digest);
+        assert_eq!(transaction.effects.transaction_digest(), digest);
+        transaction.verify(summaries[checkpoint].data())?;
+        // A transaction only matches the certified contents of its own checkpoint.
+        let other = (checkpoint + 1) % summaries.len();
+        assert!(transaction.verify(summaries[other].data()).is_err());
+        let mut forged = transaction.clone();
+        forged.checkpoint = other as u64;
+        assert!(forged.verify(summaries[other].data()).is_err());
+    }
+    assert!(reader
+        .get_transaction(&index, &TransactionDigest::random())
+        .await?
+        .is_none());
+
+    // Contents files of archives written without index files are indexed from their contents.
+    let mut unindexed = manifest.clone();
+    unindexed
+        .files
+        .retain(|file| file.file_type != FileType::TransactionIndex);
+    let index = reader
+        .load_transaction_index(&unindexed, 0..unindexed.next_checkpoint_seq_num)
+        .await?;
+    assert_eq!(index.len(), chain.transactions.len());
+    let digest = chain.transactions.last().unwrap();
+    assert_eq!(
+        reader
+            .get_transaction(&index, digest)
+            .await?
+            .unwrap()
+            .checkpoint,
+        4
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_load_transaction_index_per_range() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let config = ArchiveWriterConfig {
+        object_store_config: object_store_config(dir.path()),
+        checkpoints_per_file: 2,
+    };
+    let mut chain = ChainBuilder::new();
+    for end_of_epoch in [false, true, false, false, false] {
+        chain.add_checkpoint(end_of_epoch);
+    }
+    let (_, keypair): (_, AccountKeyPair) = get_key_pair();
+    let writer = ArchiveWriter::new(&config, chain.store.clone(), SuiKeyPair::Ed25519(keypair))?;
+    let manifest = writer.archive_synced_checkpoints().await?;
+
+    // Only the file of checkpoints 2..4 is indexed.
+    let reader = ArchiveReader::new(&config.object_store_config)?;
+    let mut index = reader.load_transaction_index(&manifest, 3..4).await?;
+    assert_eq!(index.len(), 2);
+    assert!(reader
+        .get_transaction(&index, &chain.transactions[0])
+        .await?
+        .is_none());
+    assert_eq!(
+        reader
+            .get_transaction(&index, &chain.transactions[2])
+            .await?
+            .unwrap()
+            .checkpoint,
+        2
+    );
+
+    // Extending the index only adds the files it does not hold.
+    reader
+        .extend_transaction_index(&mut index, &manifest, 0..4)
+        .await?;
+    assert_eq!(index.len(), 4);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_archive_fallback() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let config = ArchiveWriterConfig {
+        object_store_config: object_store_config(dir.path()),
+        checkpoints_per_file: 2,
+    };
+    let mut chain = ChainBuilder::new();
+    for end_of_epoch in [false, true, false] {
+        chain.add_checkpoint(end_of_epoch);
+    }
+    let (_, keypair): (_, AccountKeyPair) = get_key_pair();
+    let writer = ArchiveWriter::new(&config, chain.store.clone(), SuiKeyPair::Ed25519(keypair))?;
+    writer.archive_synced_checkpoints().await?;
+
+    let fallback = ArchiveFallback::new(&ArchiveReaderConfig {
+        object_store_config: config.object_store_config.clone(),
+        signer: writer.signer(),
+    })?;
+    // The checkpoint of the transaction only loads the index of its file.
+    let transaction = fallback
+        .get_transaction(&chain.transactions[2], Some(2))
+        .await?
+        .unwrap();
+    assert_eq!(transaction.checkpoint, 2);
+    for (checkpoint, digest) in chain.transactions.iter().enumerate() {
+        let transaction = fallback.get_transaction(digest, None).await?.unwrap();
+        assert_eq!(transaction.checkpoint, checkpoint as u64);
+    }
+    assert!(fallback
+        .get_transaction(&TransactionDigest::random(), None)
+        .await?
+        .is_none());
+
+    // Checkpoints archived after the last read of the manifest are only indexed once the
+    // manifest is read again.
+    chain.add_checkpoint(false);
+    writer.archive_synced_checkpoints().await?;
+    let digest = chain.transactions.last().unwrap();
+    assert!(fallback.get_transaction(digest, None).await?.is_none());
+    assert_eq!(
+        fallback
+            .get_transaction(digest, Some(3))
+            .await?
+            .unwrap()
+            .checkpoint,
+        3
+    );
+
+    // Errors reading the archive are not reported as missing transactions.
+    let fallback = ArchiveFallback::new(&ArchiveReaderConfig {
+        object_store_config: config.object_store_config.clone(),
+        signer: SuiAddress::random_for_testing_only(),
+    })?;
+    assert!(fallback
+        .get_transaction(&TransactionDigest::random(), None)
+        .await
+        .is_err());
+    Ok(())
+}
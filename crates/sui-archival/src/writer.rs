@@ -0,0 +1,206 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::index::index_contents_file;
+use crate::{
+    compute_checksum, decode_manifest, encode_file, FileMetadata, FileType, Manifest,
+    SignedManifest, MANIFEST_FILENAME,
+};
+use anyhow::{anyhow, ensure, Result};
+use bytes::Bytes;
+use object_store::path::Path;
+use object_store::DynObjectStore;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::Duration;
+use sui_storage::object_store::util::put;
+use sui_storage::object_store::ObjectStoreConfig;
+use sui_types::base_types::{EpochId, SuiAddress};
+use sui_types::crypto::SuiKeyPair;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::storage::ReadStore;
+use tokio::sync::oneshot;
+use tracing::{error, info};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ArchiveWriterConfig {
+    pub object_store_config: ObjectStoreConfig,
+    /// Maximum number of checkpoints in a file. Files also end at the last checkpoint of each
+    /// epoch.
+    #[serde(default = "default_checkpoints_per_file")]
+    pub checkpoints_per_file: u64,
+}
+
+fn default_checkpoints_per_file() -> u64 {
+    10_000
+}
+
+/// Appends the checkpoints synced in `store` to an archive.
+pub struct ArchiveWriter<S> {
+    store: S,
+    remote_store: Arc<DynObjectStore>,
+    keypair: SuiKeyPair,
+    checkpoints_per_file: u64,
+}
+
+impl<S> ArchiveWriter<S>
+where
+    S: ReadStore,
+    S::Error: std::error::Error + Send + Sync + 'static,
+{
+    /// Creates a writer signing the manifest of the archive with `keypair`.
+    pub fn new(config: &ArchiveWriterConfig, store: S, keypair: SuiKeyPair) -> Result<Self> {
+        ensure!(
+            config.checkpoints_per_file > 0,
+            "checkpoints-per-file must be positive"
+        );
+        Ok(Self {
+            store,
+            remote_store: config.object_store_config.make()?,
+            keypair,
+            checkpoints_per_file: config.checkpoints_per_file,
+        })
+    }
+
+    /// The address of the key signing the manifest, which readers of the archive must trust.
+    pub fn signer(&self) -> SuiAddress {
+        (&self.keypair.public()).into()
+    }
+
+    /// Reads the manifest of the archive, or returns an empty manifest for a new archive.
+    pub async fn read_manifest(&self) -> Result<Manifest> {
+        match self.remote_store.get(&Path::from(MANIFEST_FILENAME)).await {
+            Ok(result) => decode_manifest(&result.bytes().await?)?.verify(self.signer()),
+            Err(object_store::Error::NotFound { .. }) => Ok(Manifest::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Archives the checkpoints synced since the last call, and returns the updated manifest.
+    /// The manifest is uploaded after each set of summary, contents and index files, so that an
+    /// interrupted run resumes from the last complete file.
+    pub async fn archive_synced_checkpoints(&self) -> Result<Manifest> {
+        let mut manifest = self.read_manifest().await?;
+        let highest_synced = *self
+            .store
+            .get_highest_synced_checkpoint()?
+            .sequence_number();
+        while manifest.next_checkpoint_seq_num <= highest_synced {
+            let [summary_file, contents_file, index_file] = self
+                .write_files(manifest.next_checkpoint_seq_num, highest_synced)
+                .await?;
+            manifest.next_checkpoint_seq_num = summary_file.checkpoint_seq_range.end;
+            info!(
+                epoch = summary_file.epoch,
+                "Archived checkpoints {:?}", summary_file.checkpoint_seq_range
+            );
+            manifest.files.push(summary_file);
+            manifest.files.push(contents_file);
+            manifest.files.push(index_file);
+            let signed = SignedManifest::new(manifest.clone(), &self.keypair);
+            put(
+                &Path::from(MANIFEST_FILENAME),
+                Bytes::from(bcs::to_bytes(&signed)?),
+                self.remote_store.clone(),
+            )
+            .await?;
+        }
+        Ok(manifest)
+    }
+
+    /// Writes the files of the checkpoints from `start`, up to the end of the epoch of `start`,
+    /// `highest_synced` or the maximum size of a file.
+    async fn write_files(
+        &self,
+        start: CheckpointSequenceNumber,
+        highest_synced: CheckpointSequenceNumber,
+    ) -> Result<[FileMetadata; 3]> {
+        let mut summaries = vec![];
+        let mut contents = vec![];
+        let mut next = start;
+        while next <= highest_synced && next - start < self.checkpoints_per_file {
+            let checkpoint = self
+                .store
+                .get_checkpoint_by_sequence_number(next)?
+                .ok_or_else(|| anyhow!("missing checkpoint {next}"))?;
+            let full_contents = self
+                .store
+                .get_full_checkpoint_contents(&checkpoint.content_digest)?
+                .ok_or_else(|| anyhow!("missing contents of checkpoint {next}"))?;
+            let end_of_epoch = checkpoint.end_of_epoch_data.is_some();
+            summaries.push(checkpoint.into_inner());
+            contents.push(full_contents);
+            next += 1;
+            if end_of_epoch {
+                break;
+            }
+        }
+        let epoch = summaries[0].epoch;
+        ensure!(
+            summaries.iter().all(|summary| summary.epoch == epoch),
+            "checkpoints {start}..{next} span several epochs"
+        );
+        let summaries = encode_file(FileType::CheckpointSummary, &summaries)?;
+        let contents = encode_file(FileType::CheckpointContents, &contents)?;
+        let index = encode_file(
+            FileType::TransactionIndex,
+            &index_contents_file(start, &contents)?,
+        )?;
+        let mut files = vec![];
+        for (file_type, bytes) in [
+            (FileType::CheckpointSummary, summaries),
+            (FileType::CheckpointContents, contents),
+            (FileType::TransactionIndex, index),
+        ] {
+            files.push(
+                self.write_file(file_type, epoch, start..next, bytes)
+                    .await?,
+            );
+        }
+        Ok(files.try_into().expect("three files are written"))
+    }
+
+    async fn write_file(
+        &self,
+        file_type: FileType,
+        epoch: EpochId,
+        checkpoint_seq_range: Range<CheckpointSequenceNumber>,
+        bytes: Bytes,
+    ) -> Result<FileMetadata> {
+        let file = FileMetadata {
+            file_type,
+            epoch,
+            checkpoint_seq_range,
+            checksum: compute_checksum(&bytes),
+        };
+        put(&file.file_path(), bytes, self.remote_store.clone()).await?;
+        Ok(file)
+    }
+}
+
+impl<S> ArchiveWriter<S>
+where
+    S: ReadStore + Send + Sync + 'static,
+    S::Error: std::error::Error + Send + Sync + 'static,
+{
+    /// Archives the synced checkpoints every `interval`, until the returned sender is dropped.
+    pub fn start(self, interval: Duration) -> oneshot::Sender<()> {
+        let (sender, mut recv) = oneshot::channel();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        if let Err(e) = self.archive_synced_checkpoints().await {
+                            error!("Failed to archive checkpoints: {e:?}");
+                        }
+                    }
+                    _ = &mut recv => break,
+                }
+            }
+        });
+        sender
+    }
+}
@@ -4,10 +4,9 @@
 #[cfg(msim)]
 mod test {
 
-    use rand::{distributions::uniform::SampleRange, thread_rng, Rng};
     use std::str::FromStr;
-    use std::sync::{Arc, Mutex};
-    use std::time::{Duration, Instant};
+    use std::sync::Arc;
+    use std::time::Duration;
     use sui_benchmark::bank::BenchmarkBank;
     use sui_benchmark::system_state_observer::SystemStateObserver;
     use sui_benchmark::workloads::adversarial::AdversarialPayloadCfg;
@@ -20,19 +19,15 @@ mod test {
     use sui_config::{AUTHORITIES_DB_NAME, SUI_KEYSTORE_FILENAME};
     use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
     use sui_core::checkpoints::CheckpointStore;
-    use sui_macros::{register_fail_point_async, register_fail_points, sim_test};
+    use sui_macros::sim_test;
     use sui_simulator::{configs::*, SimConfig};
     use sui_types::messages_checkpoint::VerifiedCheckpoint;
     use test_utils::messages::get_sui_gas_object_with_wallet_context;
     use test_utils::network::{TestCluster, TestClusterBuilder};
-    use tracing::{error, info};
+    use test_utils::sim::{delay_on, CrashInjector, NARWHAL_FAIL_POINTS, STORAGE_FAIL_POINTS};
+    use tracing::info;
     use typed_store::traits::Map;
 
-    struct DeadValidator {
-        node_id: sui_simulator::task::NodeId,
-        dead_until: std::time::Instant,
-    }
-
     fn test_config() -> SimConfig {
         env_config(
             uniform_latency_ms(10..20),
@@ -98,109 +93,18 @@ mod test {
         test_simulated_load(test_cluster, 120).await;
     }
 
-    fn handle_failpoint(
-        dead_validator: Arc<Mutex<Option<DeadValidator>>>,
-        client_node: sui_simulator::task::NodeId,
-        probability: f64,
-    ) {
-        let mut dead_validator = dead_validator.lock().unwrap();
-        let cur_node = sui_simulator::current_simnode_id();
-
-        // never kill the client node (which is running the test)
-        if cur_node == client_node {
-            return;
-        }
-
-        // do not fail multiple nodes at a time.
-        if let Some(dead) = &*dead_validator {
-            if dead.node_id != cur_node && dead.dead_until > Instant::now() {
-                return;
-            }
-        }
-
-        // otherwise, possibly fail the current node
-        let mut rng = thread_rng();
-        if rng.gen_range(0.0..1.0) < probability {
-            error!("Matched probability threshold for failpoint. Failing...");
-            let restart_after = Duration::from_millis(rng.gen_range(10000..20000));
-
-            *dead_validator = Some(DeadValidator {
-                node_id: cur_node,
-                dead_until: Instant::now() + restart_after,
-            });
-
-            // must manually release lock before calling kill_current_node, which panics
-            // and would poison the lock.
-            drop(dead_validator);
-
-            sui_simulator::task::kill_current_node(Some(restart_after));
-        }
-    }
-
-    async fn delay_failpoint<R>(range_ms: R, probability: f64)
-    where
-        R: SampleRange<u64>,
-    {
-        let duration = {
-            let mut rng = thread_rng();
-            if rng.gen_range(0.0..1.0) < probability {
-                info!("Matched probability threshold for delay failpoint. Delaying...");
-                Some(Duration::from_millis(rng.gen_range(range_ms)))
-            } else {
-                None
-            }
-        };
-        if let Some(duration) = duration {
-            tokio::time::sleep(duration).await;
-        }
-    }
-
     #[sim_test(config = "test_config()")]
     async fn test_simulated_load_reconfig_with_crashes_and_delays() {
         sui_protocol_config::ProtocolConfig::poison_get_for_min_version();
         let test_cluster = build_test_cluster(4, 1000).await;
 
-        let dead_validator_orig: Arc<Mutex<Option<DeadValidator>>> = Default::default();
-
-        let dead_validator = dead_validator_orig.clone();
-        let client_node = sui_simulator::current_simnode_id();
-        register_fail_points(
-            &[
-                "batch-write-before",
-                "batch-write-after",
-                "put-cf-before",
-                "put-cf-after",
-                "delete-cf-before",
-                "delete-cf-after",
-                "transaction-commit",
-                "highest-executed-checkpoint",
-            ],
-            move || {
-                handle_failpoint(dead_validator.clone(), client_node, 0.02);
-            },
-        );
-
-        let dead_validator = dead_validator_orig.clone();
-        register_fail_point_async("crash", move || {
-            let dead_validator = dead_validator.clone();
-            async move {
-                handle_failpoint(dead_validator.clone(), client_node, 0.01);
-            }
-        });
+        let crash_injector = CrashInjector::new();
+        crash_injector.crash_on(STORAGE_FAIL_POINTS, 0.02);
+        crash_injector.crash_on_async("crash", 0.01);
 
         // Narwhal fail points.
-        let dead_validator = dead_validator_orig.clone();
-        register_fail_points(
-            &[
-                "narwhal-rpc-response",
-                "narwhal-store-before-write",
-                "narwhal-store-after-write",
-            ],
-            move || {
-                handle_failpoint(dead_validator.clone(), client_node, 0.001);
-            },
-        );
-        register_fail_point_async("narwhal-delay", || delay_failpoint(10..20, 0.001));
+        crash_injector.crash_on(NARWHAL_FAIL_POINTS, 0.001);
+        delay_on("narwhal-delay", 10..20, 0.001);
 
         test_simulated_load(test_cluster, 120).await;
     }
@@ -210,11 +114,7 @@ mod test {
         sui_protocol_config::ProtocolConfig::poison_get_for_min_version();
         let test_cluster = build_test_cluster(4, 10000).await;
 
-        let dead_validator: Arc<Mutex<Option<DeadValidator>>> = Default::default();
-        let client_node = sui_simulator::current_simnode_id();
-        register_fail_points(&["before-open-new-epoch-store"], move || {
-            handle_failpoint(dead_validator.clone(), client_node, 1.0);
-        });
+        CrashInjector::new().crash_on(&["before-open-new-epoch-store"], 1.0);
         test_simulated_load(test_cluster, 120).await;
     }
 
@@ -308,6 +308,8 @@ pub async fn new_wallet_context_from_cluster(
         }],
         active_address: Some(address),
         active_env: Some("localnet".to_string()),
+        aliases: Default::default(),
+        watch_only: Default::default(),
     }
     .persisted(&wallet_config_path)
     .save()
@@ -3,8 +3,8 @@
 
 use crate::genesis::{TokenAllocation, TokenDistributionScheduleBuilder};
 use crate::node::{
-    default_end_of_epoch_broadcast_channel_capacity, AuthorityKeyPairWithPath, DBCheckpointConfig,
-    KeyPairWithPath,
+    default_end_of_epoch_broadcast_channel_capacity, default_shutdown_timeout_secs,
+    AuthorityKeyPairWithPath, DBCheckpointConfig, HealthCheckConfig, KeyPairWithPath,
 };
 use crate::{
     genesis,
@@ -24,7 +24,7 @@ use std::{
     path::{Path, PathBuf},
     sync::Arc,
 };
-use sui_protocol_config::SupportedProtocolVersions;
+use sui_protocol_config::{ProtocolConfig, SupportedProtocolVersions};
 use sui_types::base_types::{AuthorityName, SuiAddress};
 use sui_types::committee::{Committee, ProtocolVersion};
 use sui_types::crypto::{
@@ -209,7 +209,13 @@ impl<R> ConfigBuilder<R> {
 
 impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
     //TODO right now we always randomize ports, we may want to have a default port configuration
-    pub fn build(mut self) -> NetworkConfig {
+    pub fn build(self) -> NetworkConfig {
+        self.try_build().unwrap()
+    }
+
+    /// Like [Self::build], but returns the errors of invalid genesis configs, e.g. packages that
+    /// cannot be loaded, instead of panicking.
+    pub fn try_build(mut self) -> Result<NetworkConfig, anyhow::Error> {
         let committee = self.committee.take().unwrap();
 
         let mut rng = self.rng.take().unwrap();
@@ -320,7 +326,7 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
         self,
         mut rng: R,
         validators: Vec<ValidatorConfigInfo>,
-    ) -> NetworkConfig {
+    ) -> Result<NetworkConfig, anyhow::Error> {
         let validator_set = validators
             .iter()
             .enumerate()
@@ -373,8 +379,7 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
 
         initial_accounts_config.parameters.protocol_version = self.protocol_version;
 
-        let (account_keys, allocations) =
-            initial_accounts_config.generate_accounts(&mut rng).unwrap();
+        let (account_keys, allocations) = initial_accounts_config.generate_accounts(&mut rng)?;
 
         let token_distribution_schedule = {
             let mut builder = TokenDistributionScheduleBuilder::new();
@@ -396,21 +401,37 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
         };
 
         let genesis = {
+            let protocol_config = ProtocolConfig::get_for_version(
+                initial_accounts_config.parameters.protocol_version,
+            );
+            let packages = initial_accounts_config.load_packages()?;
             let mut builder = genesis::Builder::new()
                 .with_parameters(initial_accounts_config.parameters)
                 .add_objects(self.additional_objects);
 
+            for object in &initial_accounts_config.objects {
+                builder = builder.add_object(object.to_object(&packages, &protocol_config)?);
+            }
+            for package in packages {
+                builder = builder.add_move_package(package);
+            }
+            for coin in initial_accounts_config.coins {
+                builder = builder.add_coin(coin);
+            }
+
             for (validator, proof_of_possession) in validator_set {
                 builder = builder.add_validator(validator, proof_of_possession);
             }
 
             builder = builder.with_token_distribution_schedule(token_distribution_schedule);
 
+            // Builds the genesis before it is signed, to return the errors of invalid inputs.
+            builder.try_build_unsigned_genesis_checkpoint()?;
             for validator in &validators {
                 builder = builder.add_validator_signature(&validator.genesis_info.key_pair);
             }
 
-            builder.build()
+            builder.try_build()?
         };
 
         let validator_configs = validators
@@ -513,13 +534,22 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
                     db_checkpoint_config: self.db_checkpoint_config.clone(),
                     indirect_objects_threshold: usize::MAX,
                     expensive_safety_check_config: Default::default(),
+                    health_check_config: HealthCheckConfig::default(),
+                    shutdown_timeout_secs: default_shutdown_timeout_secs(),
+                    rpc_limits: Default::default(),
+                    remote_signer_config: None,
+                    validator_selection: Default::default(),
+                    db_compaction_config: None,
+                    transaction_policy: Default::default(),
+                    archive_config: None,
+                    archive_reader_config: None,
                 }
             })
             .collect();
-        NetworkConfig {
+        Ok(NetworkConfig {
             validator_configs,
             genesis,
             account_keys,
-        }
+        })
     }
 }
@@ -7,8 +7,11 @@ use camino::Utf8Path;
 use fastcrypto::encoding::{Base64, Encoding};
 use fastcrypto::hash::HashFunction;
 use fastcrypto::traits::KeyPair;
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::file_format::StructHandle;
 use move_binary_format::CompiledModule;
 use move_core_types::ident_str;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_with::serde_as;
 use shared_crypto::intent::{Intent, IntentMessage, IntentScope};
@@ -19,9 +22,11 @@ use sui_adapter::adapter::MoveVM;
 use sui_adapter::{adapter, execution_mode, programmable_transactions};
 use sui_framework::BuiltInFramework;
 use sui_protocol_config::ProtocolConfig;
+use sui_types::balance::Supply;
 use sui_types::base_types::{ExecutionDigests, TransactionDigest};
 use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
 use sui_types::clock::Clock;
+use sui_types::coin::{Coin, CoinMetadata, TreasuryCap};
 use sui_types::committee::CommitteeWithNetworkMetadata;
 use sui_types::crypto::{
     verify_proof_of_possession, AuthorityPublicKey, AuthoritySignInfoTrait, DefaultHash,
@@ -32,8 +37,9 @@ use sui_types::crypto::{
 };
 use sui_types::epoch_data::EpochData;
 use sui_types::gas::SuiGasStatus;
-use sui_types::gas_coin::{GasCoin, TOTAL_SUPPLY_MIST};
+use sui_types::gas_coin::{GasCoin, GAS, TOTAL_SUPPLY_MIST};
 use sui_types::governance::StakedSui;
+use sui_types::id::UID;
 use sui_types::in_memory_storage::InMemoryStorage;
 use sui_types::message_envelope::Message;
 use sui_types::messages::{
@@ -44,8 +50,9 @@ use sui_types::messages_checkpoint::{
     CertifiedCheckpointSummary, CheckpointContents, CheckpointSummary, VerifiedCheckpoint,
 };
 use sui_types::multiaddr::Multiaddr;
-use sui_types::object::Owner;
+use sui_types::object::{MoveObject, Owner};
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::sui_serde::SuiStructTag;
 use sui_types::sui_system_state::{
     get_sui_system_state, get_sui_system_state_wrapper, SuiSystemState, SuiSystemStateTrait,
     SuiSystemStateWrapper, SuiValidatorGenesis,
@@ -604,10 +611,278 @@ impl Default for GenesisCeremonyParameters {
     }
 }
 
+/// A Move package published at genesis, at the address its modules were compiled with, after the
+/// system packages. Its `init` functions are run by the genesis transaction, and the objects they
+/// create are owned by the zero address.
+#[derive(Clone, Debug)]
+pub struct GenesisPackage {
+    pub modules: Vec<CompiledModule>,
+    /// System packages or packages published at genesis before this one.
+    pub dependencies: Vec<ObjectID>,
+}
+
+impl GenesisPackage {
+    pub fn new(modules: Vec<CompiledModule>, dependencies: Vec<ObjectID>) -> Self {
+        Self {
+            modules,
+            dependencies,
+        }
+    }
+
+    /// The address of the modules of the package, where the package is published.
+    pub fn id(&self) -> ObjectID {
+        self.modules
+            .first()
+            .map(|module| ObjectID::from(*module.self_id().address()))
+            .unwrap_or(ObjectID::ZERO)
+    }
+
+    fn module_bytes(&self) -> Vec<Vec<u8>> {
+        self.modules
+            .iter()
+            .map(|module| {
+                let mut bytes = vec![];
+                module.serialize(&mut bytes).unwrap();
+                bytes
+            })
+            .collect()
+    }
+
+    fn validate(&self, published: &[ObjectID]) -> Result<(), anyhow::Error> {
+        let id = self.id();
+        if id == ObjectID::ZERO {
+            bail!("genesis packages must be compiled with a non-zero address");
+        }
+        if sui_types::is_system_package(id) || published.contains(&id) {
+            bail!("package {id} is published twice");
+        }
+        if self
+            .modules
+            .iter()
+            .any(|module| ObjectID::from(*module.self_id().address()) != id)
+        {
+            bail!("the modules of package {id} have different addresses");
+        }
+        for dependency in &self.dependencies {
+            if !sui_types::is_system_package(*dependency) && !published.contains(dependency) {
+                bail!("dependency {dependency} of package {id} is not published before it");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A custom coin minted at genesis, with its `CoinMetadata` and `TreasuryCap`, so that private
+/// networks can start with the coins of their packages instead of minting them after genesis.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenesisCoin {
+    /// The one-time witness type of the coin, e.g. `0x42::my_coin::MY_COIN`, defined by a system
+    /// package or a package published at genesis.
+    #[serde_as(as = "SuiStructTag")]
+    pub coin_type: StructTag,
+    pub decimals: u8,
+    pub name: String,
+    pub symbol: String,
+    pub description: String,
+    #[serde(default)]
+    pub icon_url: Option<String>,
+    pub allocations: Vec<CoinAllocation>,
+    /// Owner of the `TreasuryCap` of the coin. The cap is frozen if unset, which fixes the supply
+    /// of the coin to its allocations.
+    #[serde(default)]
+    pub treasury_cap_owner: Option<SuiAddress>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoinAllocation {
+    pub recipient_address: SuiAddress,
+    pub amount: u64,
+}
+
+impl GenesisCoin {
+    /// The coins of the allocations, followed by the metadata and the treasury cap of the coin.
+    /// The ids of the objects are derived from the config of the coin, so that the same config
+    /// always results in the same genesis.
+    pub fn to_objects(&self, protocol_config: &ProtocolConfig) -> Result<Vec<Object>> {
+        let mut hasher = DefaultHash::default();
+        hasher.update(b"sui-genesis-coin");
+        hasher.update(&bcs::to_bytes(self)?);
+        let digest = TransactionDigest::new(hasher.finalize().into());
+        let mut ids = (0..).map(|creation_num| ObjectID::derive_id(digest, creation_num));
+
+        let coin_type = TypeTag::Struct(Box::new(self.coin_type.clone()));
+        let mut objects = vec![];
+        for allocation in &self.allocations {
+            objects.push(Object::new_move(
+                MoveObject::new_coin(
+                    Coin::type_(coin_type.clone()).into(),
+                    SequenceNumber::new(),
+                    ids.next().unwrap(),
+                    allocation.amount,
+                ),
+                Owner::AddressOwner(allocation.recipient_address),
+                TransactionDigest::genesis(),
+            ));
+        }
+
+        let metadata = CoinMetadata {
+            id: UID::new(ids.next().unwrap()),
+            decimals: self.decimals,
+            name: self.name.clone(),
+            symbol: self.symbol.clone(),
+            description: self.description.clone(),
+            icon_url: self.icon_url.clone(),
+        };
+        objects.push(new_genesis_move_object(
+            CoinMetadata::type_(self.coin_type.clone()),
+            /* has_public_transfer */ true,
+            bcs::to_bytes(&metadata)?,
+            Owner::Immutable,
+            protocol_config,
+        )?);
+
+        let total_supply = self
+            .allocations
+            .iter()
+            .try_fold(0u64, |total, allocation| {
+                total.checked_add(allocation.amount)
+            })
+            .with_context(|| format!("the supply of {} overflows", self.coin_type))?;
+        let treasury_cap = TreasuryCap {
+            id: UID::new(ids.next().unwrap()),
+            total_supply: Supply {
+                value: total_supply,
+            },
+        };
+        objects.push(new_genesis_move_object(
+            TreasuryCap::type_(self.coin_type.clone()),
+            /* has_public_transfer */ true,
+            bcs::to_bytes(&treasury_cap)?,
+            self.treasury_cap_owner
+                .map(Owner::AddressOwner)
+                .unwrap_or(Owner::Immutable),
+            protocol_config,
+        )?);
+        Ok(objects)
+    }
+}
+
+/// An arbitrary Move object created at genesis, e.g. the configuration object of a package
+/// published at genesis.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenesisMoveObject {
+    pub id: ObjectID,
+    #[serde_as(as = "SuiStructTag")]
+    pub object_type: StructTag,
+    /// BCS encoding of the fields of the object following its `id`.
+    #[serde_as(as = "Base64")]
+    pub contents: Vec<u8>,
+    pub owner: Owner,
+}
+
+impl GenesisMoveObject {
+    /// Creates the object, which can be transferred by any module if its type has `store`. The
+    /// type must be defined by a system package or by one of `packages`.
+    pub fn to_object(
+        &self,
+        packages: &[GenesisPackage],
+        protocol_config: &ProtocolConfig,
+    ) -> Result<Object> {
+        let modules: Vec<_> = BuiltInFramework::iter_system_packages()
+            .flat_map(|package| package.modules())
+            .chain(packages.iter().flat_map(|package| package.modules.clone()))
+            .collect();
+        let handle = find_struct_handle(&self.object_type, &modules)?;
+        if !handle.abilities.has_key() {
+            bail!("{} is not an object type", self.object_type);
+        }
+        let has_public_transfer = struct_has_store(&self.object_type, &modules)?;
+
+        let mut contents = self.id.to_vec();
+        contents.extend_from_slice(&self.contents);
+        new_genesis_move_object(
+            self.object_type.clone(),
+            has_public_transfer,
+            contents,
+            self.owner,
+            protocol_config,
+        )
+    }
+}
+
+fn find_struct_handle<'a>(
+    type_: &StructTag,
+    modules: &'a [CompiledModule],
+) -> Result<&'a StructHandle> {
+    let module_id = type_.module_id();
+    modules
+        .iter()
+        .filter(|module| module.self_id() == module_id)
+        .find_map(|module| {
+            let definition = module.find_struct_def_by_name(&type_.name)?;
+            Some(module.struct_handle_at(definition.struct_handle))
+        })
+        .with_context(|| format!("type {type_} is not defined at genesis"))
+}
+
+/// Whether an instance of the struct has `store`: the struct must declare it, and its type
+/// arguments for non-phantom parameters must have it as well.
+fn struct_has_store(type_: &StructTag, modules: &[CompiledModule]) -> Result<bool> {
+    let handle = find_struct_handle(type_, modules)?;
+    if !handle.abilities.has_store() {
+        return Ok(false);
+    }
+    for (parameter, type_arg) in handle.type_parameters.iter().zip(&type_.type_params) {
+        if !parameter.is_phantom && !type_has_store(type_arg, modules)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+fn type_has_store(type_: &TypeTag, modules: &[CompiledModule]) -> Result<bool> {
+    match type_ {
+        TypeTag::Signer => Ok(false),
+        TypeTag::Vector(element) => type_has_store(element, modules),
+        TypeTag::Struct(type_) => struct_has_store(type_, modules),
+        _ => Ok(true),
+    }
+}
+
+fn new_genesis_move_object(
+    type_: StructTag,
+    has_public_transfer: bool,
+    contents: Vec<u8>,
+    owner: Owner,
+    protocol_config: &ProtocolConfig,
+) -> Result<Object> {
+    // Safe because genesis objects are checked by the genesis transaction, like the objects added
+    // with `Builder::add_object`.
+    let object = unsafe {
+        MoveObject::new_from_execution(
+            type_.into(),
+            has_public_transfer,
+            SequenceNumber::new(),
+            contents,
+            protocol_config,
+        )
+    }
+    .map_err(|e| anyhow::anyhow!("invalid genesis object: {e:?}"))?;
+    Ok(Object::new_move(
+        object,
+        owner,
+        TransactionDigest::genesis(),
+    ))
+}
+
 pub struct Builder {
     parameters: GenesisCeremonyParameters,
     token_distribution_schedule: Option<TokenDistributionSchedule>,
     objects: BTreeMap<ObjectID, Object>,
+    packages: Vec<GenesisPackage>,
+    coins: Vec<GenesisCoin>,
     validators: BTreeMap<AuthorityPublicKeyBytes, GenesisValidatorInfo>,
     // Validator signatures over checkpoint
     signatures: BTreeMap<AuthorityPublicKeyBytes, AuthoritySignInfo>,
@@ -626,6 +901,8 @@ impl Builder {
             parameters: Default::default(),
             token_distribution_schedule: None,
             objects: Default::default(),
+            packages: Default::default(),
+            coins: Default::default(),
             validators: Default::default(),
             signatures: Default::default(),
             built_genesis: None,
@@ -662,6 +939,17 @@ impl Builder {
         self
     }
 
+    /// Publishes a package at genesis, after the system packages and the packages added before it.
+    pub fn add_move_package(mut self, package: GenesisPackage) -> Self {
+        self.packages.push(package);
+        self
+    }
+
+    pub fn add_coin(mut self, coin: GenesisCoin) -> Self {
+        self.coins.push(coin);
+        self
+    }
+
     pub fn add_validator(
         mut self,
         validator: ValidatorInfo,
@@ -712,14 +1000,24 @@ impl Builder {
     }
 
     pub fn build_unsigned_genesis_checkpoint(&mut self) -> UnsignedGenesis {
+        self.try_build_unsigned_genesis_checkpoint().unwrap()
+    }
+
+    /// Like [Self::build_unsigned_genesis_checkpoint], but returns the errors of invalid inputs,
+    /// e.g. genesis packages that cannot be published, instead of panicking.
+    pub fn try_build_unsigned_genesis_checkpoint(&mut self) -> Result<UnsignedGenesis> {
         if let Some(built_genesis) = &self.built_genesis {
-            return built_genesis.clone();
+            return Ok(built_genesis.clone());
         }
 
         // Verify that all input data is valid
-        self.validate().unwrap();
+        self.validate()?;
 
-        let objects = self.objects.clone().into_values().collect::<Vec<_>>();
+        let protocol_config = ProtocolConfig::get_for_version(self.parameters.protocol_version);
+        let mut objects = self.objects.clone().into_values().collect::<Vec<_>>();
+        for coin in &self.coins {
+            objects.extend(coin.to_objects(&protocol_config)?);
+        }
         let validators = self.validators.clone().into_values().collect::<Vec<_>>();
 
         let token_distribution_schedule =
@@ -735,12 +1033,13 @@ impl Builder {
             &self.parameters,
             &token_distribution_schedule,
             &validators,
+            &self.packages,
             &objects,
-        ));
+        )?);
 
         self.token_distribution_schedule = Some(token_distribution_schedule);
 
-        self.built_genesis.clone().unwrap()
+        Ok(self.built_genesis.clone().unwrap())
     }
 
     fn committee(objects: &[Object]) -> Committee {
@@ -753,7 +1052,12 @@ impl Builder {
         self.parameters.protocol_version
     }
 
-    pub fn build(mut self) -> Genesis {
+    pub fn build(self) -> Genesis {
+        self.try_build().unwrap()
+    }
+
+    /// Like [Self::build], but returns the errors of invalid inputs instead of panicking.
+    pub fn try_build(mut self) -> Result<Genesis> {
         let UnsignedGenesis {
             checkpoint,
             checkpoint_contents,
@@ -761,7 +1065,7 @@ impl Builder {
             effects,
             events,
             objects,
-        } = self.build_unsigned_genesis_checkpoint();
+        } = self.try_build_unsigned_genesis_checkpoint()?;
 
         let committee = Self::committee(&objects);
 
@@ -773,7 +1077,7 @@ impl Builder {
                 .map(|(_, s)| s)
                 .collect();
 
-            CertifiedCheckpointSummary::new(checkpoint, signatures, &committee).unwrap()
+            CertifiedCheckpointSummary::new(checkpoint, signatures, &committee)?
         };
 
         let genesis = Genesis {
@@ -786,9 +1090,9 @@ impl Builder {
         };
 
         // Verify that all on-chain state was properly created
-        self.validate().unwrap();
+        self.validate()?;
 
-        genesis
+        Ok(genesis)
     }
 
     /// Validates the entire state of the build, no matter what the internal state is (input
@@ -801,10 +1105,34 @@ impl Builder {
 
     /// Runs through validation checks on the input values present in the builder
     fn validate_inputs(&self) -> Result<(), anyhow::Error> {
-        if !self.parameters.allow_insertion_of_extra_objects && !self.objects.is_empty() {
+        if !self.parameters.allow_insertion_of_extra_objects
+            && !(self.objects.is_empty() && self.packages.is_empty() && self.coins.is_empty())
+        {
             bail!("extra objects are disallowed");
         }
 
+        let mut published = vec![];
+        for package in &self.packages {
+            package.validate(&published)?;
+            published.push(package.id());
+        }
+        let modules: Vec<_> = BuiltInFramework::iter_system_packages()
+            .flat_map(|package| package.modules())
+            .chain(
+                self.packages
+                    .iter()
+                    .flat_map(|package| package.modules.clone()),
+            )
+            .collect();
+        for coin in &self.coins {
+            // SUI is only minted by the token distribution schedule, within its fixed supply.
+            if coin.coin_type == GAS::type_() {
+                bail!("SUI cannot be minted as a genesis coin");
+            }
+            find_struct_handle(&coin.coin_type, &modules)
+                .with_context(|| format!("invalid genesis coin {}", coin.coin_type))?;
+        }
+
         for validator in self.validators.values() {
             validator.validate().with_context(|| {
                 format!(
@@ -1116,10 +1444,55 @@ impl Builder {
             signatures.insert(sigs.authority, sigs);
         }
 
+        // Load the packages, coins and objects added to genesis
+        let mut packages = vec![];
+        let packages_dir = path.join(GENESIS_BUILDER_PACKAGES_DIR);
+        if packages_dir.exists() {
+            let mut entries = packages_dir
+                .read_dir_utf8()?
+                .map(|entry| Ok(entry?.path().to_owned()))
+                .collect::<Result<Vec<_>, std::io::Error>>()?;
+            // Package files are named after their position, to be published in the same order.
+            entries.sort();
+            for path in entries {
+                let (module_bytes, dependencies): (Vec<Vec<u8>>, Vec<ObjectID>) =
+                    bcs::from_bytes(&fs::read(&path)?)
+                        .with_context(|| format!("unable to load genesis package {path}"))?;
+                let modules = module_bytes
+                    .iter()
+                    .map(|bytes| CompiledModule::deserialize(bytes))
+                    .collect::<Result<_, _>>()
+                    .with_context(|| format!("invalid module in genesis package {path}"))?;
+                packages.push(GenesisPackage::new(modules, dependencies));
+            }
+        }
+
+        let coins_file = path.join(GENESIS_BUILDER_COINS_FILE);
+        let coins = if coins_file.exists() {
+            serde_yaml::from_slice(&fs::read(coins_file)?)
+                .context("unable to deserialize genesis coins")?
+        } else {
+            vec![]
+        };
+
+        let mut objects = BTreeMap::new();
+        let objects_dir = path.join(GENESIS_BUILDER_OBJECTS_DIR);
+        if objects_dir.exists() {
+            for entry in objects_dir.read_dir_utf8()? {
+                let entry = entry?;
+                let path = entry.path();
+                let object: Object = bcs::from_bytes(&fs::read(path)?)
+                    .with_context(|| format!("unable to load genesis object {path}"))?;
+                objects.insert(object.id(), object);
+            }
+        }
+
         let mut builder = Self {
             parameters,
             token_distribution_schedule,
-            objects: Default::default(),
+            objects,
+            packages,
+            coins,
             validators: committee,
             signatures,
             built_genesis: None, // Leave this as none, will build and compare below
@@ -1168,6 +1541,33 @@ impl Builder {
             )?)?;
         }
 
+        // Write the packages, coins and objects added to genesis
+        if !self.packages.is_empty() {
+            let packages_dir = path.join(GENESIS_BUILDER_PACKAGES_DIR);
+            fs::create_dir_all(&packages_dir)?;
+            for (i, package) in self.packages.iter().enumerate() {
+                fs::write(
+                    packages_dir.join(format!("{i:04}-{}", package.id())),
+                    bcs::to_bytes(&(package.module_bytes(), &package.dependencies))?,
+                )?;
+            }
+        }
+
+        if !self.coins.is_empty() {
+            fs::write(
+                path.join(GENESIS_BUILDER_COINS_FILE),
+                serde_yaml::to_vec(&self.coins)?,
+            )?;
+        }
+
+        if !self.objects.is_empty() {
+            let objects_dir = path.join(GENESIS_BUILDER_OBJECTS_DIR);
+            fs::create_dir_all(&objects_dir)?;
+            for (id, object) in &self.objects {
+                fs::write(objects_dir.join(id.to_string()), bcs::to_bytes(object)?)?;
+            }
+        }
+
         // Write Signatures
         let signature_dir = path.join(GENESIS_BUILDER_SIGNATURE_DIR);
         std::fs::create_dir_all(&signature_dir)?;
@@ -1209,6 +1609,7 @@ fn create_genesis_context(
     genesis_chain_parameters: &GenesisChainParameters,
     genesis_validators: &[GenesisValidatorMetadata],
     token_distribution_schedule: &TokenDistributionSchedule,
+    packages: &[GenesisPackage],
 ) -> TxContext {
     let mut hasher = DefaultHash::default();
     hasher.update(b"sui-genesis");
@@ -1218,6 +1619,9 @@ fn create_genesis_context(
     for system_package in BuiltInFramework::iter_system_packages() {
         hasher.update(&bcs::to_bytes(system_package.bytes()).unwrap());
     }
+    for package in packages {
+        hasher.update(&bcs::to_bytes(&package.module_bytes()).unwrap());
+    }
 
     let hash = hasher.finalize();
     let genesis_transaction_digest = TransactionDigest::new(hash.into());
@@ -1233,10 +1637,12 @@ fn build_unsigned_genesis_data(
     parameters: &GenesisCeremonyParameters,
     token_distribution_schedule: &TokenDistributionSchedule,
     validators: &[GenesisValidatorInfo],
+    packages: &[GenesisPackage],
     objects: &[Object],
-) -> UnsignedGenesis {
-    if !parameters.allow_insertion_of_extra_objects && !objects.is_empty() {
-        panic!("insertion of extra objects at genesis time is prohibited due to 'allow_insertion_of_extra_objects' parameter");
+) -> Result<UnsignedGenesis> {
+    if !parameters.allow_insertion_of_extra_objects && !(objects.is_empty() && packages.is_empty())
+    {
+        bail!("insertion of extra objects at genesis time is prohibited due to 'allow_insertion_of_extra_objects' parameter");
     }
 
     let genesis_chain_parameters = parameters.to_genesis_chain_parameters();
@@ -1258,15 +1664,17 @@ fn build_unsigned_genesis_data(
         &genesis_chain_parameters,
         &genesis_validators,
         token_distribution_schedule,
+        packages,
     );
 
     let objects = create_genesis_objects(
         &mut genesis_ctx,
+        packages,
         objects,
         &genesis_validators,
         &genesis_chain_parameters,
         token_distribution_schedule,
-    );
+    )?;
 
     let protocol_config = ProtocolConfig::get_for_version(parameters.protocol_version);
 
@@ -1275,14 +1683,14 @@ fn build_unsigned_genesis_data(
     let (checkpoint, checkpoint_contents) =
         create_genesis_checkpoint(parameters, &genesis_transaction, &genesis_effects);
 
-    UnsignedGenesis {
+    Ok(UnsignedGenesis {
         checkpoint,
         checkpoint_contents,
         transaction: genesis_transaction,
         effects: genesis_effects,
         events: genesis_events,
         objects,
-    }
+    })
 }
 
 fn create_genesis_checkpoint(
@@ -1407,11 +1815,12 @@ fn create_genesis_transaction(
 
 fn create_genesis_objects(
     genesis_ctx: &mut TxContext,
+    packages: &[GenesisPackage],
     input_objects: &[Object],
     validators: &[GenesisValidatorMetadata],
     parameters: &GenesisChainParameters,
     token_distribution_schedule: &TokenDistributionSchedule,
-) -> Vec<Object> {
+) -> Result<Vec<Object>> {
     let mut store = InMemoryStorage::new(Vec::new());
     let protocol_config =
         ProtocolConfig::get_for_version(ProtocolVersion::new(parameters.protocol_version));
@@ -1438,6 +1847,18 @@ fn create_genesis_objects(
         .unwrap();
     }
 
+    for package in packages {
+        process_package(
+            &mut store,
+            &move_vm,
+            genesis_ctx,
+            &package.modules,
+            package.dependencies.clone(),
+            &protocol_config,
+        )
+        .with_context(|| format!("unable to publish genesis package {}", package.id()))?;
+    }
+
     for object in input_objects {
         store.insert_object(object.to_owned());
     }
@@ -1452,7 +1873,7 @@ fn create_genesis_objects(
     )
     .unwrap();
 
-    store.into_inner().into_values().collect()
+    Ok(store.into_inner().into_values().collect())
 }
 
 fn process_package(
@@ -1630,6 +2051,9 @@ const GENESIS_BUILDER_PARAMETERS_FILE: &str = "parameters";
 const GENESIS_BUILDER_TOKEN_DISTRIBUTION_SCHEDULE_FILE: &str = "token-distribution-schedule";
 const GENESIS_BUILDER_SIGNATURE_DIR: &str = "signatures";
 const GENESIS_BUILDER_UNSIGNED_GENESIS_FILE: &str = "unsigned-genesis";
+const GENESIS_BUILDER_PACKAGES_DIR: &str = "packages";
+const GENESIS_BUILDER_COINS_FILE: &str = "coins";
+const GENESIS_BUILDER_OBJECTS_DIR: &str = "objects";
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -1824,6 +2248,7 @@ mod test {
     use super::*;
     use crate::{utils, ValidatorInfo};
     use fastcrypto::traits::KeyPair;
+    use move_core_types::identifier::Identifier;
     use sui_types::crypto::{
         generate_proof_of_possession, get_key_pair_from_rng, AccountKeyPair, AuthorityKeyPair,
         NetworkKeyPair,
@@ -1859,6 +2284,173 @@ mod test {
         assert_eq!(genesis, from_s);
     }
 
+    fn test_coin(package: &str) -> GenesisCoin {
+        GenesisCoin {
+            coin_type: sui_types::parse_sui_struct_tag(&format!("{package}::usdc::USDC")).unwrap(),
+            decimals: 6,
+            name: "USD Coin".into(),
+            symbol: "USDC".into(),
+            description: String::new(),
+            icon_url: None,
+            allocations: vec![
+                CoinAllocation {
+                    recipient_address: SuiAddress::random_for_testing_only(),
+                    amount: 100,
+                },
+                CoinAllocation {
+                    recipient_address: SuiAddress::random_for_testing_only(),
+                    amount: 20,
+                },
+            ],
+            treasury_cap_owner: None,
+        }
+    }
+
+    #[test]
+    fn genesis_coin_objects() {
+        let protocol_config = ProtocolConfig::get_for_max_version();
+        let coin = test_coin("0x42");
+        let objects = coin.to_objects(&protocol_config).unwrap();
+        assert_eq!(objects, coin.to_objects(&protocol_config).unwrap());
+        assert_eq!(objects.len(), 4);
+
+        for (object, allocation) in objects.iter().zip(&coin.allocations) {
+            assert_eq!(
+                object.owner,
+                Owner::AddressOwner(allocation.recipient_address)
+            );
+            assert_eq!(
+                Coin::extract_balance_if_coin(object).unwrap(),
+                Some(allocation.amount)
+            );
+        }
+        let metadata = CoinMetadata::try_from(&objects[2]).unwrap();
+        assert_eq!(metadata.symbol, "USDC");
+        assert_eq!(objects[2].owner, Owner::Immutable);
+
+        let treasury_cap = &objects[3];
+        assert_eq!(treasury_cap.owner, Owner::Immutable);
+        let treasury_cap =
+            TreasuryCap::from_bcs_bytes(treasury_cap.data.try_as_move().unwrap().contents())
+                .unwrap();
+        assert_eq!(treasury_cap.total_supply.value, 120);
+    }
+
+    #[test]
+    fn genesis_coin_validation() {
+        // The type of the coin must be defined by a package published at genesis.
+        let builder = Builder::new().add_coin(test_coin("0x42"));
+        assert!(builder.validate().is_err());
+        let builder = Builder::new()
+            .add_move_package(test_coin_package(0x42))
+            .add_coin(test_coin("0x42"));
+        builder.validate().unwrap();
+        let builder = Builder::new().add_coin(test_coin("0x2"));
+        assert!(builder.validate().is_err());
+
+        // SUI is not minted outside of its fixed supply.
+        let mut sui = test_coin("0x2");
+        sui.coin_type = GAS::type_();
+        assert!(Builder::new().add_coin(sui).validate().is_err());
+
+        // Coins are extra objects.
+        let parameters = GenesisCeremonyParameters {
+            allow_insertion_of_extra_objects: false,
+            ..Default::default()
+        };
+        let error = Builder::new()
+            .with_parameters(parameters)
+            .add_coin(test_coin("0x42"))
+            .validate()
+            .unwrap_err();
+        assert!(error.to_string().contains("extra objects"), "{error}");
+    }
+
+    #[test]
+    fn genesis_package_publish_failure_is_an_error() {
+        // The package does not pass the bytecode verifier, as its function has no code.
+        let mut package = test_coin_package(0x42);
+        let function = &mut package.modules[0].function_defs[0];
+        function.code.as_mut().unwrap().code.clear();
+
+        let mut builder = Builder::new().add_move_package(package);
+        let error = builder.try_build_unsigned_genesis_checkpoint().unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("unable to publish genesis package"),
+            "{error}"
+        );
+    }
+
+    fn test_package(address: u8) -> GenesisPackage {
+        let mut module = move_binary_format::file_format::empty_module();
+        module.address_identifiers[0] = ObjectID::from_single_byte(address).into();
+        GenesisPackage::new(vec![module], vec![SUI_FRAMEWORK_ADDRESS.into()])
+    }
+
+    /// A package defining the type `usdc::USDC` of [test_coin].
+    fn test_coin_package(address: u8) -> GenesisPackage {
+        let mut module = move_binary_format::file_format::basic_test_module();
+        module.address_identifiers[0] = ObjectID::from_single_byte(address).into();
+        module.identifiers[0] = Identifier::new("usdc").unwrap();
+        let name = module.struct_handles[0].name;
+        module.identifiers[name.0 as usize] = Identifier::new("USDC").unwrap();
+        GenesisPackage::new(vec![module], vec![SUI_FRAMEWORK_ADDRESS.into()])
+    }
+
+    #[test]
+    fn genesis_packages_roundtrip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        // More packages than digits in the names of their files, and in the reverse order of
+        // their ids, to check that they are loaded in the order they were added.
+        let packages: Vec<_> = (0x42..0x50).rev().map(test_package).collect();
+        let coin = test_coin("0x42");
+        let mut builder = Builder::new().add_coin(coin.clone());
+        for package in &packages {
+            builder = builder.add_move_package(package.clone());
+        }
+        builder.save(dir.path()).unwrap();
+
+        let loaded = Builder::load(dir.path()).unwrap();
+        assert_eq!(loaded.packages.len(), packages.len());
+        for (loaded, package) in loaded.packages.iter().zip(&packages) {
+            assert_eq!(loaded.modules, package.modules);
+            assert_eq!(loaded.dependencies, package.dependencies);
+        }
+        assert_eq!(loaded.coins, vec![coin]);
+    }
+
+    #[test]
+    fn genesis_move_object_public_transfer() {
+        let protocol_config = ProtocolConfig::get_for_max_version();
+        let object = |object_type: &str, contents: Vec<u8>| GenesisMoveObject {
+            id: ObjectID::random(),
+            object_type: sui_types::parse_sui_struct_tag(object_type).unwrap(),
+            contents,
+            owner: Owner::Immutable,
+        };
+        let has_public_transfer = |object: GenesisMoveObject| {
+            let object = object.to_object(&[], &protocol_config).unwrap();
+            object.data.try_as_move().unwrap().has_public_transfer()
+        };
+
+        // `Coin<SUI>` has `store`, `Clock` does not.
+        let coin = object(
+            "0x2::coin::Coin<0x2::sui::SUI>",
+            100u64.to_le_bytes().to_vec(),
+        );
+        assert!(has_public_transfer(coin));
+        let clock = object("0x2::clock::Clock", 0u64.to_le_bytes().to_vec());
+        assert!(!has_public_transfer(clock));
+
+        // Types without `key`, or not defined at genesis, are rejected.
+        let balance = object("0x2::balance::Balance<0x2::sui::SUI>", vec![]);
+        assert!(balance.to_object(&[], &protocol_config).is_err());
+        let undefined = object("0x42::m::S", vec![]);
+        assert!(undefined.to_object(&[], &protocol_config).is_err());
+    }
+
     #[test]
     #[cfg_attr(msim, ignore)]
     fn ceremony() {
@@ -1,10 +1,13 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::fs;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use fastcrypto::traits::KeyPair;
+use move_binary_format::CompiledModule;
 use rand::{rngs::StdRng, SeedableRng};
 use serde::{Deserialize, Serialize};
 use sui_types::multiaddr::Multiaddr;
@@ -15,7 +18,9 @@ use sui_types::crypto::{
     get_key_pair_from_rng, AccountKeyPair, AuthorityKeyPair, NetworkKeyPair, SuiKeyPair,
 };
 
-use crate::genesis::{GenesisCeremonyParameters, TokenAllocation};
+use crate::genesis::{
+    GenesisCeremonyParameters, GenesisCoin, GenesisMoveObject, GenesisPackage, TokenAllocation,
+};
 use crate::node::DEFAULT_GRPC_CONCURRENCY_LIMIT;
 use crate::Config;
 use crate::{utils, DEFAULT_COMMISSION_RATE, DEFAULT_GAS_PRICE};
@@ -36,6 +41,14 @@ pub struct GenesisConfig {
     pub grpc_load_shed: Option<bool>,
     pub grpc_concurrency_limit: Option<usize>,
     pub accounts: Vec<AccountConfig>,
+    /// Packages published at genesis, in order. Requires `allow_insertion_of_extra_objects`, like
+    /// the coins and objects below.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub packages: Vec<GenesisPackageConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub coins: Vec<GenesisCoin>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub objects: Vec<GenesisMoveObject>,
 }
 
 impl Config for GenesisConfig {}
@@ -74,6 +87,42 @@ impl GenesisConfig {
 
         Ok((keys, allocations))
     }
+
+    pub fn load_packages(&self) -> Result<Vec<GenesisPackage>> {
+        self.packages.iter().map(|package| package.load()).collect()
+    }
+}
+
+/// A package published at genesis, see [GenesisPackage].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GenesisPackageConfig {
+    /// Directory of the compiled modules of the package, e.g. `build/<package>/bytecode_modules`
+    /// after a `sui move build` with the address of the package set in its manifest.
+    pub path: PathBuf,
+    #[serde(default)]
+    pub dependencies: Vec<ObjectID>,
+}
+
+impl GenesisPackageConfig {
+    pub fn load(&self) -> Result<GenesisPackage> {
+        let mut paths = fs::read_dir(&self.path)
+            .with_context(|| format!("unable to read package {}", self.path.display()))?
+            .map(|entry| Ok(entry?.path()))
+            .collect::<Result<Vec<_>>>()?;
+        paths.retain(|path| path.extension().map_or(false, |ext| ext == "mv"));
+        paths.sort();
+        if paths.is_empty() {
+            bail!("no compiled modules in {}", self.path.display());
+        }
+        let modules = paths
+            .iter()
+            .map(|path| {
+                CompiledModule::deserialize(&fs::read(path)?)
+                    .with_context(|| format!("invalid module {}", path.display()))
+            })
+            .collect::<Result<_>>()?;
+        Ok(GenesisPackage::new(modules, self.dependencies.clone()))
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -327,6 +376,9 @@ impl GenesisConfig {
             grpc_load_shed: None,
             grpc_concurrency_limit: None,
             accounts: vec![account_config],
+            packages: vec![],
+            coins: vec![],
+            objects: vec![],
         }
     }
 
@@ -358,6 +410,9 @@ impl Default for GenesisConfig {
             grpc_load_shed: None,
             grpc_concurrency_limit: Some(DEFAULT_GRPC_CONCURRENCY_LIMIT),
             accounts: vec![],
+            packages: vec![],
+            coins: vec![],
+            objects: vec![],
         }
     }
 }
@@ -4,20 +4,25 @@
 use crate::genesis;
 use crate::p2p::P2pConfig;
 use crate::Config;
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
+use move_core_types::identifier::{IdentStr, Identifier};
 use narwhal_config::Parameters as ConsensusParameters;
 use once_cell::sync::OnceCell;
 use rand::rngs::OsRng;
 use serde::{Deserialize, Serialize};
-use serde_with::serde_as;
+use serde_with::{serde_as, DisplayFromStr};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
 use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::Arc;
 use std::usize;
+use sui_archival::{ArchiveReaderConfig, ArchiveWriterConfig};
 use sui_keys::keypair_file::{read_authority_keypair_from_file, read_keypair_from_file};
 use sui_protocol_config::SupportedProtocolVersions;
 use sui_storage::object_store::ObjectStoreConfig;
-use sui_types::base_types::SuiAddress;
+use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::crypto::AuthorityPublicKeyBytes;
 use sui_types::crypto::KeypairTraits;
 use sui_types::crypto::NetworkKeyPair;
@@ -99,25 +104,75 @@ pub struct NodeConfig {
 
     #[serde(default)]
     pub expensive_safety_check_config: ExpensiveSafetyCheckConfig,
+
+    #[serde(default)]
+    pub health_check_config: HealthCheckConfig,
+
+    /// How long the node waits for in-flight requests and executions to finish when shutting
+    /// down on SIGTERM, before flushing its databases and exiting.
+    ///
+    /// If unspecified, this will default to `30` seconds.
+    #[serde(default = "default_shutdown_timeout_secs")]
+    pub shutdown_timeout_secs: u64,
+
+    #[serde(default)]
+    pub rpc_limits: RpcLimitsConfig,
+
+    /// External signing service holding the validator keys, e.g. in an HSM or KMS. When set,
+    /// transaction votes, checkpoint signatures and consensus messages are signed by the remote
+    /// signer, and the config must not set the protocol and account keys.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_signer_config: Option<RemoteSignerConfig>,
+
+    /// How the quorum driver of fullnodes orders the validators it submits transactions and
+    /// certificates to.
+    #[serde(default, skip_serializing_if = "ValidatorSelectionConfig::is_default")]
+    pub validator_selection: ValidatorSelectionConfig,
+
+    /// Daily compaction of tables of the perpetual store in an off-peak window.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub db_compaction_config: Option<DbCompactionConfig>,
+
+    /// Rules of fullnodes refusing some of the transactions submitted to them.
+    #[serde(default, skip_serializing_if = "TransactionPolicyConfig::is_empty")]
+    pub transaction_policy: TransactionPolicyConfig,
+
+    /// Archive of the checkpoints synced by the node, written to an object store.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_config: Option<ArchiveConfig>,
+
+    /// Archive from which fullnodes serve the transactions they do not hold.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_reader_config: Option<ArchiveReaderConfig>,
 }
 
 fn default_authority_store_pruning_config() -> AuthorityStorePruningConfig {
     AuthorityStorePruningConfig::default()
 }
 
+pub fn default_shutdown_timeout_secs() -> u64 {
+    30
+}
+
 fn default_grpc_address() -> Multiaddr {
     "/ip4/0.0.0.0/tcp/8080".parse().unwrap()
 }
 fn default_authority_key_pair() -> AuthorityKeyPairWithPath {
-    AuthorityKeyPairWithPath::new(get_key_pair_from_rng::<AuthorityKeyPair, _>(&mut OsRng).1)
+    AuthorityKeyPairWithPath {
+        generated: true,
+        ..AuthorityKeyPairWithPath::new(get_key_pair_from_rng::<AuthorityKeyPair, _>(&mut OsRng).1)
+    }
 }
 
 fn default_key_pair() -> KeyPairWithPath {
-    KeyPairWithPath::new(
-        get_key_pair_from_rng::<AccountKeyPair, _>(&mut OsRng)
-            .1
-            .into(),
-    )
+    KeyPairWithPath {
+        generated: true,
+        ..KeyPairWithPath::new(
+            get_key_pair_from_rng::<AccountKeyPair, _>(&mut OsRng)
+                .1
+                .into(),
+        )
+    }
 }
 
 fn default_metrics_address() -> SocketAddr {
@@ -182,6 +237,12 @@ impl NodeConfig {
         self.protocol_key_pair().public().into()
     }
 
+    /// Whether the config sets the protocol or the account key of the validator. The network and
+    /// worker keys are not checked, as they are always held by the node for its TLS connections.
+    pub fn holds_validator_keys(&self) -> bool {
+        !self.protocol_key_pair.generated || !self.account_key_pair.generated
+    }
+
     pub fn db_path(&self) -> PathBuf {
         self.db_path.join("live")
     }
@@ -258,6 +319,209 @@ pub struct CheckpointExecutorConfig {
     pub local_execution_timeout_sec: u64,
 }
 
+/// Thresholds of the readiness check served on the admin interface.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HealthCheckConfig {
+    /// The node is not ready when its highest executed checkpoint is more than this many
+    /// checkpoints behind the highest checkpoint known from its peers, or verified by itself.
+    ///
+    /// If unspecified, this will default to `100`.
+    #[serde(default = "default_max_checkpoint_lag")]
+    pub max_checkpoint_lag: u64,
+}
+
+fn default_max_checkpoint_lag() -> u64 {
+    100
+}
+
+impl Default for HealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            max_checkpoint_lag: default_max_checkpoint_lag(),
+        }
+    }
+}
+
+/// Limits of the JSON-RPC server of fullnodes.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RpcLimitsConfig {
+    /// Requests received while this many requests are being served are rejected with
+    /// `429 Too Many Requests`.
+    ///
+    /// If unspecified, the number of concurrent requests is not limited.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_requests: Option<usize>,
+
+    /// Concurrent requests of `sui_executeTransactionBlock` and `sui_dryRunTransactionBlock`
+    /// served in addition to `max-concurrent-requests`, so that read queries using up that limit
+    /// cannot delay the submission of transactions.
+    ///
+    /// If unspecified, these methods share `max-concurrent-requests` with the other methods.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_lane_size: Option<usize>,
+
+    /// Pages of results larger than this many bytes of JSON, e.g. of `suix_getOwnedObjects`, are
+    /// truncated, and point to the next page with their cursor.
+    ///
+    /// If unspecified, the size of the pages is only limited by the number of items requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_response_size: Option<usize>,
+
+    /// Maximum number of concurrent requests of the given methods, e.g. to keep expensive
+    /// methods like `suix_queryTransactionBlocks` from starving the others. The requests beyond
+    /// the limit of their method wait for the requests of the method being served.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub method_concurrency_limits: BTreeMap<String, usize>,
+
+    /// How long requests wait for the concurrency limit of their method before being rejected
+    /// with `429 Too Many Requests`.
+    ///
+    /// If unspecified, requests wait until they are served.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method_queue_timeout_ms: Option<u64>,
+}
+
+/// Rules of a fullnode refusing the transactions submitted to it, e.g. to comply with the
+/// regulations it operates under. Refused transactions are never sent to validators.
+#[serde_as]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TransactionPolicyConfig {
+    /// Transactions sent by these addresses are refused.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub deny_senders: BTreeSet<SuiAddress>,
+
+    /// Transactions calling these Move functions are refused.
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny_move_calls: Vec<MoveCallPattern>,
+
+    /// Allowlist-only mode: when set, only the transactions sent by these addresses are
+    /// accepted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_senders: Option<BTreeSet<SuiAddress>>,
+
+    /// Allowlist-only mode: when set, only the transactions calling nothing but these Move
+    /// functions are accepted.
+    #[serde_as(as = "Option<Vec<DisplayFromStr>>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow_move_calls: Option<Vec<MoveCallPattern>>,
+}
+
+impl TransactionPolicyConfig {
+    /// Whether the policy has no rules, and accepts every transaction.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Move functions matched by a rule, written `package`, `package::module` or
+/// `package::module::function`, e.g. `0x2::coin` matches every function of the `coin` module of
+/// the framework.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MoveCallPattern {
+    pub package: ObjectID,
+    pub module: Option<Identifier>,
+    pub function: Option<Identifier>,
+}
+
+impl MoveCallPattern {
+    pub fn matches(&self, package: &ObjectID, module: &IdentStr, function: &IdentStr) -> bool {
+        self.package == *package
+            && self
+                .module
+                .as_ref()
+                .map_or(true, |m| m.as_ident_str() == module)
+            && self
+                .function
+                .as_ref()
+                .map_or(true, |f| f.as_ident_str() == function)
+    }
+}
+
+impl FromStr for MoveCallPattern {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = s.split("::");
+        let package = ObjectID::from_str(parts.next().unwrap_or_default())
+            .map_err(|e| anyhow!("Invalid package in Move call pattern {s}: {e}"))?;
+        let identifier = |part: Option<&str>| {
+            part.map(|p| {
+                Identifier::new(p).map_err(|e| anyhow!("Invalid Move call pattern {s}: {e}"))
+            })
+            .transpose()
+        };
+        let module = identifier(parts.next())?;
+        let function = identifier(parts.next())?;
+        if parts.next().is_some() {
+            bail!("Invalid Move call pattern {s}, expected package::module::function");
+        }
+        Ok(Self {
+            package,
+            module,
+            function,
+        })
+    }
+}
+
+impl fmt::Display for MoveCallPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.package.to_hex_literal())?;
+        for identifier in [&self.module, &self.function].into_iter().flatten() {
+            write!(f, "::{identifier}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Order in which the quorum driver contacts the validators. Validators contacted first answer
+/// first, which matters most for the requests sent to one validator at a time.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case", tag = "strategy")]
+pub enum ValidatorSelectionConfig {
+    /// Random order, weighted by the stake of the validators.
+    #[default]
+    StakeWeighted,
+    /// Validators with the lowest observed latency first, then the validators without latency
+    /// observations in random order weighted by stake.
+    LatencyAware,
+    /// The given validators first, e.g. the validators in the region of the fullnode, in random
+    /// order weighted by stake, then the other validators.
+    Preferred {
+        validators: Vec<AuthorityPublicKeyBytes>,
+    },
+    /// The given validators in the given order, then the other validators in random order
+    /// weighted by stake.
+    Ordered {
+        validators: Vec<AuthorityPublicKeyBytes>,
+    },
+}
+
+impl ValidatorSelectionConfig {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Schedule of manual compactions of the tables of the perpetual store, so that the compactions
+/// of the tables with the most writes run off-peak instead of colliding with checkpoint execution.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DbCompactionConfig {
+    /// Tables of the perpetual store to compact, e.g. `objects`.
+    pub tables: Vec<String>,
+    /// Hour of the day, from 0 to 23 UTC, at which the tables are compacted.
+    pub off_peak_hour_utc: u8,
+    /// Disables the automatic compactions of the tables, which are then only compacted by the
+    /// schedule and the admin server. Writes stall when a table has too many uncompacted files,
+    /// so this only suits tables with few writes between two scheduled compactions.
+    #[serde(default)]
+    pub disable_auto_compactions: bool,
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct ExpensiveSafetyCheckConfig {
@@ -343,7 +607,7 @@ impl Default for CheckpointExecutorConfig {
     }
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct AuthorityStorePruningConfig {
     pub num_latest_epoch_dbs_to_retain: usize,
@@ -354,6 +618,14 @@ pub struct AuthorityStorePruningConfig {
     pub max_checkpoints_in_batch: usize,
     pub max_transactions_in_batch: usize,
     pub use_range_deletion: bool,
+    /// Objects whose historical versions are never pruned, regardless of
+    /// `num_epochs_to_retain`. The versions pruned before an object is added are not restored.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub retained_objects: Vec<ObjectID>,
+    /// Packages whose objects, i.e. the objects whose type is defined in one of the packages,
+    /// never have their historical versions pruned.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub retained_packages: Vec<ObjectID>,
 }
 
 impl Default for AuthorityStorePruningConfig {
@@ -366,6 +638,8 @@ impl Default for AuthorityStorePruningConfig {
             max_checkpoints_in_batch: 200,
             max_transactions_in_batch: 1000,
             use_range_deletion: true,
+            retained_objects: vec![],
+            retained_packages: vec![],
         }
     }
 }
@@ -380,6 +654,8 @@ impl AuthorityStorePruningConfig {
             max_checkpoints_in_batch: 200,
             max_transactions_in_batch: 1000,
             use_range_deletion: true,
+            retained_objects: vec![],
+            retained_packages: vec![],
         }
     }
     pub fn fullnode_config() -> Self {
@@ -391,11 +667,13 @@ impl AuthorityStorePruningConfig {
             max_checkpoints_in_batch: 200,
             max_transactions_in_batch: 1000,
             use_range_deletion: true,
+            retained_objects: vec![],
+            retained_packages: vec![],
         }
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct MetricsConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -404,6 +682,34 @@ pub struct MetricsConfig {
     pub push_url: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RemoteSignerConfig {
+    /// Address of the signing service implementing the `RemoteSigner` gRPC API. The network and
+    /// worker keys remain in the config, as the node needs them for its TLS connections. Requests
+    /// are signed with the network key, which the signing service must allow.
+    pub address: Multiaddr,
+    /// Protocol public key of the validator, which the signing service must hold.
+    pub protocol_public_key: AuthorityPublicKeyBytes,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ArchiveConfig {
+    #[serde(flatten)]
+    pub writer_config: ArchiveWriterConfig,
+    /// Key of the operator of the archive, signing its manifest. Readers of the archive trust
+    /// the address of this key.
+    pub operator_key_pair: KeyPairWithPath,
+    /// Interval between two uploads of the checkpoints synced since the last one.
+    #[serde(default = "default_archive_interval_secs")]
+    pub archive_interval_secs: u64,
+}
+
+fn default_archive_interval_secs() -> u64 {
+    600
+}
+
 #[derive(Default, Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct DBCheckpointConfig {
@@ -547,6 +853,10 @@ pub struct KeyPairWithPath {
 
     #[serde(skip)]
     keypair: OnceCell<Arc<SuiKeyPair>>,
+
+    /// Whether the keypair was generated because the config does not set it.
+    #[serde(skip)]
+    generated: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Eq)]
@@ -572,6 +882,7 @@ impl KeyPairWithPath {
         Self {
             location: KeyPairLocation::InPlace { value: arc_kp },
             keypair: cell,
+            generated: false,
         }
     }
 
@@ -585,6 +896,7 @@ impl KeyPairWithPath {
         Self {
             location: KeyPairLocation::File { path },
             keypair: cell,
+            generated: false,
         }
     }
 
@@ -613,6 +925,10 @@ pub struct AuthorityKeyPairWithPath {
 
     #[serde(skip)]
     keypair: OnceCell<Arc<AuthorityKeyPair>>,
+
+    /// Whether the keypair was generated because the config does not set it.
+    #[serde(skip)]
+    generated: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Eq)]
@@ -633,6 +949,7 @@ impl AuthorityKeyPairWithPath {
         Self {
             location: AuthorityKeyPairLocation::InPlace { value: arc_kp },
             keypair: cell,
+            generated: false,
         }
     }
 
@@ -647,6 +964,7 @@ impl AuthorityKeyPairWithPath {
         Self {
             location: AuthorityKeyPairLocation::File { path },
             keypair: cell,
+            generated: false,
         }
     }
 
@@ -676,7 +994,7 @@ mod tests {
     use sui_keys::keypair_file::{write_authority_keypair_to_file, write_keypair_to_file};
     use sui_types::crypto::{get_key_pair_from_rng, AuthorityKeyPair, NetworkKeyPair, SuiKeyPair};
 
-    use super::Genesis;
+    use super::{ArchiveConfig, Genesis};
     use crate::NodeConfig;
 
     #[test]
@@ -739,6 +1057,24 @@ mod tests {
         let _template: NodeConfig = serde_yaml::from_str(TEMPLATE).unwrap();
     }
 
+    #[test]
+    fn load_archive_config() {
+        let config: ArchiveConfig = serde_yaml::from_str(
+            "object-store-config:\n  \
+               object-store: File\n  \
+               directory: archive\n\
+             operator-key-pair:\n  \
+               path: operator.key\n",
+        )
+        .unwrap();
+        assert_eq!(config.writer_config.checkpoints_per_file, 10_000);
+        assert_eq!(config.archive_interval_secs, 600);
+        assert_eq!(
+            config.writer_config.object_store_config.directory,
+            Some(PathBuf::from("archive"))
+        );
+    }
+
     #[test]
     fn load_key_pairs_to_node_config() {
         let protocol_key_pair: AuthorityKeyPair =
@@ -20,6 +20,12 @@ pub struct P2pConfig {
     /// connection is established with these nodes.
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub seed_peers: Vec<SeedPeer>,
+    /// Peers allowed to send requests to this node, whose requests from any other peer are
+    /// rejected.
+    ///
+    /// If empty, the requests of every peer are allowed.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub allowlisted_peers: Vec<anemo::PeerId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub anemo_config: Option<anemo::Config>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -44,6 +50,7 @@ impl Default for P2pConfig {
             listen_address: default_listen_address(),
             external_address: Default::default(),
             seed_peers: Default::default(),
+            allowlisted_peers: Default::default(),
             anemo_config: Default::default(),
             state_sync: None,
             discovery: None,
@@ -61,7 +68,7 @@ impl P2pConfig {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct SeedPeer {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -3,8 +3,8 @@
 
 use crate::node::AuthorityStorePruningConfig;
 use crate::node::{
-    default_end_of_epoch_broadcast_channel_capacity, AuthorityKeyPairWithPath, DBCheckpointConfig,
-    KeyPairWithPath,
+    default_end_of_epoch_broadcast_channel_capacity, default_shutdown_timeout_secs,
+    AuthorityKeyPairWithPath, DBCheckpointConfig, HealthCheckConfig, KeyPairWithPath,
 };
 use crate::p2p::{P2pConfig, SeedPeer};
 use crate::{
@@ -287,6 +287,15 @@ impl<'a> FullnodeConfigBuilder<'a> {
             indirect_objects_threshold: usize::MAX,
             // Copy the expensive safety check config from the first validator config.
             expensive_safety_check_config: validator_config.expensive_safety_check_config.clone(),
+            health_check_config: HealthCheckConfig::default(),
+            shutdown_timeout_secs: default_shutdown_timeout_secs(),
+            rpc_limits: Default::default(),
+            remote_signer_config: None,
+            validator_selection: Default::default(),
+            db_compaction_config: None,
+            transaction_policy: Default::default(),
+            archive_config: None,
+            archive_reader_config: None,
         })
     }
 }
@@ -2,10 +2,14 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
-use std::{collections::HashMap, fs, pin::Pin, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    pin::Pin,
+    sync::Arc,
+};
 
 use anyhow::anyhow;
 use arc_swap::{ArcSwap, Guard};
@@ -29,6 +33,7 @@ use serde::Serialize;
 use tap::TapFallible;
 use tokio::sync::mpsc::unbounded_channel;
 use tokio::sync::oneshot;
+use tokio::sync::watch;
 use tokio_retry::strategy::{jitter, ExponentialBackoff};
 use tracing::{debug, error, info, instrument, trace, warn, Instrument};
 
@@ -41,15 +46,16 @@ use narwhal_config::{
 };
 use shared_crypto::intent::{Intent, IntentScope};
 use sui_adapter::execution_engine;
-use sui_adapter::{adapter, execution_mode};
+use sui_adapter::{adapter, buffer_pool, execution_mode};
 use sui_config::genesis::Genesis;
 use sui_config::node::{
     AuthorityStorePruningConfig, DBCheckpointConfig, ExpensiveSafetyCheckConfig,
 };
 use sui_framework::BuiltInFramework;
 use sui_json_rpc_types::{
-    Checkpoint, DevInspectResults, DryRunTransactionBlockResponse, EventFilter, SuiEvent,
-    SuiMoveValue, SuiObjectDataFilter, SuiTransactionBlockData, SuiTransactionBlockEvents,
+    Checkpoint, DevInspectResults, DryRunTransactionBlockResponse, EventFilter, ObjectChange,
+    OwnedObjectChange, SuiEvent, SuiMoveValue, SuiObjectDataFilter, SuiObjectLockStatus,
+    SuiTransactionBlockData, SuiTransactionBlockEvents, SuiTransactionBlockStatus,
 };
 use sui_macros::{fail_point, fail_point_async, nondeterministic};
 use sui_protocol_config::SupportedProtocolVersions;
@@ -57,8 +63,8 @@ use sui_storage::indexes::ObjectIndexChanges;
 use sui_storage::IndexStore;
 use sui_types::committee::{EpochId, ProtocolVersion};
 use sui_types::crypto::{
-    default_hash, AggregateAuthoritySignature, AuthorityKeyPair, AuthoritySignInfo, NetworkKeyPair,
-    Signer,
+    default_hash, AggregateAuthoritySignature, AuthorityKeyPair, AuthoritySignInfo,
+    AuthoritySigner, NetworkKeyPair,
 };
 use sui_types::digests::TransactionEventsDigest;
 use sui_types::dynamic_field::{DynamicFieldInfo, DynamicFieldName, DynamicFieldType, Field};
@@ -72,22 +78,25 @@ use sui_types::messages_checkpoint::{
 };
 use sui_types::messages_checkpoint::{CheckpointRequest, CheckpointResponse};
 use sui_types::object::{MoveObject, Owner, PastObjectRead, OBJECT_START_VERSION};
+use sui_types::object_proof::ObjectProof;
 use sui_types::query::TransactionFilter;
-use sui_types::storage::{ObjectKey, ObjectStore, WriteKind};
+use sui_types::storage::{DeleteKind, ObjectKey, ObjectStore, WriteKind};
 use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemStateTrait;
 use sui_types::sui_system_state::SuiSystemState;
 use sui_types::sui_system_state::SuiSystemStateTrait;
 pub use sui_types::temporary_store::TemporaryStore;
 use sui_types::temporary_store::{InnerTemporaryStore, TemporaryModuleResolver};
+use sui_types::transaction_proof::TransactionProof;
 use sui_types::{
     base_types::*,
+    clock::Clock,
     committee::Committee,
-    crypto::AuthoritySignature,
     error::{SuiError, SuiResult},
     fp_ensure,
+    id::UID,
     messages::*,
     object::{Object, ObjectFormatOptions, ObjectRead},
-    SUI_SYSTEM_ADDRESS,
+    SUI_CLOCK_OBJECT_ID, SUI_SYSTEM_ADDRESS,
 };
 use typed_store::Map;
 
@@ -132,6 +141,8 @@ mod batch_verification_tests;
 pub mod authority_per_epoch_store;
 pub mod authority_per_epoch_store_pruner;
 
+pub mod authority_store_compaction;
+pub mod authority_store_integrity;
 pub mod authority_store_pruner;
 pub mod authority_store_tables;
 pub mod authority_store_types;
@@ -185,6 +196,9 @@ pub struct AuthorityMetrics {
     pub(crate) execution_driver_executed_transactions: IntCounter,
     pub(crate) execution_driver_dispatch_queue: IntGauge,
 
+    execution_buffer_pool_hits: IntCounter,
+    execution_buffer_pool_misses: IntCounter,
+
     pub(crate) skipped_consensus_txns: IntCounter,
     pub(crate) skipped_consensus_txns_cache_hit: IntCounter,
 
@@ -369,6 +383,18 @@ impl AuthorityMetrics {
                 registry,
             )
             .unwrap(),
+            execution_buffer_pool_hits: register_int_counter_with_registry!(
+                "execution_buffer_pool_hits",
+                "Number of execution buffers reused from the pool of the executing thread",
+                registry,
+            )
+            .unwrap(),
+            execution_buffer_pool_misses: register_int_counter_with_registry!(
+                "execution_buffer_pool_misses",
+                "Number of execution buffers allocated because the pool of the executing thread was empty",
+                registry,
+            )
+            .unwrap(),
             execution_driver_dispatch_queue: register_int_gauge_with_registry!(
                 "execution_driver_dispatch_queue",
                 "Number of transaction pending in execution driver dispatch queue",
@@ -453,13 +479,15 @@ impl AuthorityMetrics {
     }
 }
 
-/// a Trait object for `Signer` that is:
+/// a Trait object for `AuthoritySigner` that is:
 /// - Pin, i.e. confined to one place in memory (we don't want to copy private keys).
 /// - Sync, i.e. can be safely shared between threads.
+/// - Fallible, i.e. a vote or signature is not produced when the signer fails.
 ///
-/// Typically instantiated with Box::pin(keypair) where keypair is a `KeyPair`
+/// Typically instantiated with Box::pin(keypair) where keypair is a `KeyPair`, or with
+/// `validator_signer::authority_signer` when the key is held by a remote signer.
 ///
-pub type StableSyncAuthoritySigner = Pin<Arc<dyn Signer<AuthoritySignature> + Send + Sync>>;
+pub type StableSyncAuthoritySigner = Pin<Arc<dyn AuthoritySigner>>;
 
 pub struct AuthorityState {
     // Fixed size, static, identity of the authority
@@ -490,6 +518,8 @@ pub struct AuthorityState {
     pub metrics: Arc<AuthorityMetrics>,
     _objects_pruner: AuthorityStorePruner,
     _authority_per_epoch_pruner: AuthorityPerEpochStorePruner,
+    /// Config of the pruners, which can be updated while the node is running.
+    pruning_config: watch::Sender<AuthorityStorePruningConfig>,
 
     /// Take db checkpoints af different dbs
     db_checkpoint_config: DBCheckpointConfig,
@@ -513,6 +543,11 @@ impl AuthorityState {
         !self.is_validator(epoch_store)
     }
 
+    /// Update the config of the object and epoch db pruners, which apply it from their next run.
+    pub fn update_pruning_config(&self, config: AuthorityStorePruningConfig) {
+        self.pruning_config.send_replace(config);
+    }
+
     pub fn committee_store(&self) -> &Arc<CommitteeStore> {
         &self.committee_store
     }
@@ -555,12 +590,12 @@ impl AuthorityState {
 
         let owned_objects = input_objects.filter_owned_objects();
 
-        let signed_transaction = VerifiedSignedTransaction::new(
+        let signed_transaction = VerifiedSignedTransaction::try_new(
             epoch_store.epoch(),
             transaction,
             self.name,
             &*self.secret,
-        );
+        )?;
 
         // Check and write locks, to signed transaction, into the database
         // The call to self.set_transaction_lock checks the lock is not conflicting,
@@ -988,6 +1023,13 @@ impl AuthorityState {
                 &epoch_store.epoch_start_config().epoch_data(),
                 epoch_store.protocol_config(),
             );
+        let pool_stats = buffer_pool::take_stats();
+        self.metrics
+            .execution_buffer_pool_hits
+            .inc_by(pool_stats.hits);
+        self.metrics
+            .execution_buffer_pool_misses
+            .inc_by(pool_stats.misses);
 
         Ok((inner_temp_store, effects))
     }
@@ -1002,6 +1044,8 @@ impl AuthorityState {
             .certificate_executed(digest, epoch_store)
     }
 
+    /// Executes `transaction` without committing it, and returns the store of the execution
+    /// along with its effects, e.g. to compute the balance and object changes of the transaction.
     pub async fn dry_exec_transaction(
         &self,
         transaction: TransactionData,
@@ -1009,7 +1053,7 @@ impl AuthorityState {
     ) -> Result<
         (
             DryRunTransactionBlockResponse,
-            BTreeMap<ObjectID, (ObjectRef, Object, WriteKind)>,
+            InnerTemporaryStore,
             TransactionEffects,
         ),
         anyhow::Error,
@@ -1117,18 +1161,40 @@ impl AuthorityState {
                 object_changes,
                 balance_changes,
             },
-            inner_temp_store.written,
+            inner_temp_store,
             effects,
         ))
     }
 
-    /// The object ID for gas can be any object ID, even for an uncreated object
+    /// The object ID for gas can be any object ID, even for an uncreated object.
+    /// `clock_timestamp_ms` overrides the timestamp of the `Clock` object during the call.
     pub async fn dev_inspect_transaction_block(
         &self,
         sender: SuiAddress,
         transaction_kind: TransactionKind,
         gas_price: Option<u64>,
+        clock_timestamp_ms: Option<u64>,
     ) -> Result<DevInspectResults, anyhow::Error> {
+        let (results, _, _) = self
+            .dev_inspect_transaction_block_with_store(
+                sender,
+                transaction_kind,
+                gas_price,
+                clock_timestamp_ms,
+            )
+            .await?;
+        Ok(results)
+    }
+
+    /// Same as [Self::dev_inspect_transaction_block], also returning the store of the execution
+    /// and its effects, like [Self::dry_exec_transaction].
+    pub async fn dev_inspect_transaction_block_with_store(
+        &self,
+        sender: SuiAddress,
+        transaction_kind: TransactionKind,
+        gas_price: Option<u64>,
+        clock_timestamp_ms: Option<u64>,
+    ) -> Result<(DevInspectResults, InnerTemporaryStore, TransactionEffects), anyhow::Error> {
         let epoch_store = self.load_epoch_store_one_call_per_task();
         if !self.is_fullnode(&epoch_store) {
             return Err(anyhow!("dev-inspect is only supported on fullnodes"));
@@ -1163,6 +1229,27 @@ impl AuthorityState {
             gas_object,
         )
         .await?;
+        let input_objects = match clock_timestamp_ms {
+            Some(timestamp_ms) => {
+                let mut objects = input_objects.into_objects();
+                for (_, object) in &mut objects {
+                    if object.id() != SUI_CLOCK_OBJECT_ID {
+                        continue;
+                    }
+                    let clock = Clock {
+                        id: UID::new(SUI_CLOCK_OBJECT_ID),
+                        timestamp_ms,
+                    };
+                    object
+                        .data
+                        .try_as_move_mut()
+                        .ok_or_else(|| anyhow!("The clock object is not a Move object"))?
+                        .update_contents(bcs::to_bytes(&clock)?, protocol_config)?;
+                }
+                InputObjects::new(objects)
+            }
+            None => input_objects,
+        };
         let shared_object_refs = input_objects.filter_shared_objects();
 
         let gas_budget = max_tx_gas;
@@ -1210,12 +1297,13 @@ impl AuthorityState {
         let module_cache =
             TemporaryModuleResolver::new(&inner_temp_store, epoch_store.module_cache().clone());
 
-        DevInspectResults::new(
-            effects,
+        let results = DevInspectResults::new(
+            effects.clone(),
             inner_temp_store.events.clone(),
             execution_result,
             &module_cache,
-        )
+        )?;
+        Ok((results, inner_temp_store, effects))
     }
 
     pub fn is_tx_already_executed(&self, digest: &TransactionDigest) -> SuiResult<bool> {
@@ -1490,11 +1578,146 @@ impl AuthorityState {
                 self.metrics
                     .post_processing_total_events_emitted
                     .inc_by(events.data.len() as u64);
+
+                let subscriptions = self.event_handler.owned_object_change_subscriptions().await;
+                if !subscriptions.is_empty() {
+                    let data = &certificate.data().intent_message().value;
+                    match self.get_owned_object_changes(data, effects, &subscriptions) {
+                        Ok(changes) => {
+                            self.event_handler
+                                .process_owned_object_changes(changes)
+                                .await
+                        }
+                        Err(e) => warn!(
+                            ?tx_digest,
+                            "Post processing - Couldn't compute owned object changes for tx: {e}"
+                        ),
+                    }
+                }
             }
         };
         Ok(())
     }
 
+    /// Returns the changes of the objects owned by one of the `subscriptions` before or after the
+    /// transaction of `data`. Only the objects owned by a subscribed address are read.
+    fn get_owned_object_changes(
+        &self,
+        data: &TransactionData,
+        effects: &TransactionEffects,
+        subscriptions: &HashSet<SuiAddress>,
+    ) -> SuiResult<Vec<OwnedObjectChange>> {
+        let sender = data.sender();
+        // The objects owned by an address before the transaction are inputs of the transaction,
+        // owned by one of its signers.
+        let signer_subscribed = data
+            .signers()
+            .iter()
+            .any(|signer| subscriptions.contains(signer));
+        let subscribed = |owner: &Owner| match owner {
+            Owner::AddressOwner(address) => subscriptions.contains(address),
+            _ => false,
+        };
+        let transaction_digest = *effects.transaction_digest();
+        let previous_versions: HashMap<_, _> =
+            effects.modified_at_versions().iter().cloned().collect();
+        let previous_object = |object_id: &ObjectID| match previous_versions.get(object_id) {
+            Some(version) => self.database.get_object_by_key(object_id, *version),
+            None => Ok(None),
+        };
+        let mut changes = vec![];
+        let mut notify = |owner: &Owner, change: &ObjectChange| {
+            if let Owner::AddressOwner(address) = owner {
+                if subscriptions.contains(address) {
+                    changes.push(OwnedObjectChange {
+                        address: *address,
+                        transaction_digest,
+                        change: change.clone(),
+                    });
+                }
+            }
+        };
+
+        for ((object_id, version, digest), owner, kind) in effects.all_changed_objects() {
+            let previously_owned = signer_subscribed && previous_versions.contains_key(object_id);
+            if !subscribed(owner) && !previously_owned {
+                continue;
+            }
+            let Some(object_type) = self
+                .database
+                .get_object_by_key(object_id, *version)?
+                .and_then(|o| o.struct_tag()) else {
+                continue;
+            };
+            let previous = previous_object(object_id)?;
+            match (kind, previous) {
+                (WriteKind::Mutate, Some(previous)) if previous.owner == *owner => {
+                    let change = ObjectChange::Mutated {
+                        sender,
+                        owner: *owner,
+                        object_type,
+                        object_id: *object_id,
+                        version: *version,
+                        previous_version: previous.version(),
+                        digest: *digest,
+                    };
+                    notify(owner, &change);
+                }
+                (WriteKind::Mutate, Some(previous)) => {
+                    let change = ObjectChange::Transferred {
+                        sender,
+                        recipient: *owner,
+                        object_type,
+                        object_id: *object_id,
+                        version: *version,
+                        digest: *digest,
+                    };
+                    notify(&previous.owner, &change);
+                    notify(owner, &change);
+                }
+                _ => {
+                    let change = ObjectChange::Created {
+                        sender,
+                        owner: *owner,
+                        object_type,
+                        object_id: *object_id,
+                        version: *version,
+                        digest: *digest,
+                    };
+                    notify(owner, &change);
+                }
+            }
+        }
+
+        for ((object_id, version, _), kind) in effects.all_deleted() {
+            if !signer_subscribed {
+                continue;
+            }
+            let Some(previous) = previous_object(object_id)? else {
+                continue;
+            };
+            let Some(object_type) = previous.struct_tag() else {
+                continue;
+            };
+            let change = match kind {
+                DeleteKind::Wrap => ObjectChange::Wrapped {
+                    sender,
+                    object_type,
+                    object_id: *object_id,
+                    version: *version,
+                },
+                DeleteKind::Normal | DeleteKind::UnwrapThenDelete => ObjectChange::Deleted {
+                    sender,
+                    object_type,
+                    object_id: *object_id,
+                    version: *version,
+                },
+            };
+            notify(&previous.owner, &change);
+        }
+        Ok(changes)
+    }
+
     pub fn unixtime_now_ms() -> u64 {
         let ts_ms = Utc::now().timestamp_millis();
         u64::try_from(ts_ms).expect("Travelling in time machine")
@@ -1643,13 +1866,16 @@ impl AuthorityState {
         ));
         let (tx_execution_shutdown, rx_execution_shutdown) = oneshot::channel();
 
-        let _authority_per_epoch_pruner =
-            AuthorityPerEpochStorePruner::new(epoch_store.get_parent_path(), &pruning_config);
+        let (pruning_config, _) = watch::channel(pruning_config);
+        let _authority_per_epoch_pruner = AuthorityPerEpochStorePruner::new(
+            epoch_store.get_parent_path(),
+            pruning_config.subscribe(),
+        );
         let _objects_pruner = AuthorityStorePruner::new(
             store.perpetual_tables.clone(),
             checkpoint_store.clone(),
             store.objects_lock_table.clone(),
-            pruning_config,
+            pruning_config.subscribe(),
             epoch_store.epoch_start_state().epoch_duration_ms(),
             prometheus_registry,
         );
@@ -1667,6 +1893,7 @@ impl AuthorityState {
             metrics,
             _objects_pruner,
             _authority_per_epoch_pruner,
+            pruning_config,
             db_checkpoint_config: db_checkpoint_config.clone(),
             expensive_safety_check_config,
         });
@@ -2032,6 +2259,35 @@ impl AuthorityState {
         Ok(checkpoint)
     }
 
+    /// The status of the transaction block of `digest` on this node, None if the node does not
+    /// have it.
+    pub fn get_transaction_block_status(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<Option<SuiTransactionBlockStatus>> {
+        if let Some((_, checkpoint)) = self.database.get_transaction_checkpoint(digest)? {
+            let highest_executed = self
+                .checkpoint_store
+                .get_highest_executed_checkpoint_seq_number()?;
+            return Ok(Some(
+                if highest_executed.map_or(false, |highest| highest >= checkpoint) {
+                    SuiTransactionBlockStatus::Finalized { checkpoint }
+                } else {
+                    SuiTransactionBlockStatus::InCertifiedCheckpoint { checkpoint }
+                },
+            ));
+        }
+        if let Some(effects) = self.database.get_executed_effects(digest)? {
+            return Ok(Some(SuiTransactionBlockStatus::ExecutedLocally {
+                effects_digest: effects.digest(),
+            }));
+        }
+        Ok(self
+            .database
+            .get_transaction_block(digest)?
+            .map(|_| SuiTransactionBlockStatus::Seen))
+    }
+
     pub fn get_object_read(&self, object_id: &ObjectID) -> Result<ObjectRead, SuiError> {
         match self.database.get_object_or_tombstone(*object_id)? {
             None => Ok(ObjectRead::NotExists(*object_id)),
@@ -2356,6 +2612,11 @@ impl AuthorityState {
             .ok_or(SuiError::TransactionEventsNotFound { digest: *digest })
     }
 
+    /// Whether the node indexes the transactions it executes, to serve the extended API.
+    pub fn has_indexes(&self) -> bool {
+        self.indexes.is_some()
+    }
+
     fn get_indexes(&self) -> SuiResult<Arc<IndexStore>> {
         match &self.indexes {
             Some(i) => Ok(i.clone()),
@@ -2786,12 +3047,12 @@ impl AuthorityState {
                     epoch=?epoch_store.epoch(),
                     "Re-signing the effects with the current epoch"
                 );
-                SignedTransactionEffects::new(
+                SignedTransactionEffects::try_new(
                     epoch_store.epoch(),
                     effects,
                     &*self.secret,
                     self.name,
-                )
+                )?
             }
         };
         Ok(VerifiedSignedTransactionEffects::new_unchecked(
@@ -2853,13 +3114,13 @@ impl AuthorityState {
         let tx_digest = certificate.digest();
         // Only need to sign effects if we are a validator.
         let effects_sig = if self.is_validator(epoch_store) {
-            Some(AuthoritySignInfo::new(
+            Some(AuthoritySignInfo::try_new(
                 epoch_store.epoch(),
                 effects,
                 Intent::sui_app(IntentScope::TransactionEffects),
                 self.name,
                 &*self.secret,
-            ))
+            )?)
         } else {
             None
         };
@@ -2947,6 +3208,139 @@ impl AuthorityState {
         Ok(tx_option)
     }
 
+    /// The lock of an owned object version in the current epoch. Only validators lock objects,
+    /// when signing transactions: on fullnodes, the versions which were not consumed yet are
+    /// always reported as unlocked.
+    pub fn get_object_lock_status(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<SuiObjectLockStatus> {
+        let object = self
+            .database
+            .get_object_by_key(&object_id, version)?
+            .ok_or_else(|| {
+                SuiError::from(UserInputError::ObjectNotFound {
+                    object_id,
+                    version: Some(version),
+                })
+            })?;
+        if !object.is_address_owned() {
+            return Ok(SuiObjectLockStatus::NotOwned);
+        }
+        let epoch = self.load_epoch_store_one_call_per_task().epoch();
+        Ok(
+            match self
+                .database
+                .get_lock(object.compute_object_reference(), epoch)?
+            {
+                ObjectLockStatus::Initialized => SuiObjectLockStatus::Unlocked,
+                ObjectLockStatus::LockedToTx { locked_by_tx } => SuiObjectLockStatus::Locked {
+                    transaction_digest: locked_by_tx.tx_digest,
+                    epoch: locked_by_tx.epoch,
+                },
+                ObjectLockStatus::LockedAtDifferentVersion { locked_ref } => {
+                    SuiObjectLockStatus::Consumed {
+                        latest_version: locked_ref.1,
+                    }
+                }
+            },
+        )
+    }
+
+    /// The proof that an object version is part of the state at the checkpoint including the
+    /// transaction which wrote it. Only object versions which are still stored by the node, of
+    /// transactions which are checkpointed already, can be proved.
+    pub fn get_object_proof(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<ObjectProof> {
+        let object = self
+            .database
+            .get_object_by_key(&object_id, version)?
+            .ok_or_else(|| {
+                SuiError::from(UserInputError::ObjectNotFound {
+                    object_id,
+                    version: Some(version),
+                })
+            })?;
+        let digest = object.previous_transaction;
+        let effects = self
+            .database
+            .get_executed_effects(&digest)?
+            .ok_or(SuiError::TransactionNotFound { digest })?;
+        let (_, sequence_number) = self
+            .database
+            .get_transaction_checkpoint(&digest)?
+            .ok_or_else(|| SuiError::GenericAuthorityError {
+                error: format!("Transaction {digest} is not included in a checkpoint yet"),
+            })?;
+        let checkpoint = self
+            .checkpoint_store
+            .get_checkpoint_by_sequence_number(sequence_number)?
+            .ok_or_else(|| {
+                SuiError::StorageMissingFieldError(format!(
+                    "Certified checkpoint {sequence_number} is missing"
+                ))
+            })?
+            .into_inner();
+        let contents = self
+            .checkpoint_store
+            .get_checkpoint_contents(&checkpoint.content_digest)?
+            .ok_or_else(|| {
+                SuiError::StorageMissingFieldError(format!(
+                    "Contents of checkpoint {sequence_number} are missing"
+                ))
+            })?;
+        Ok(ObjectProof {
+            checkpoint,
+            contents,
+            effects,
+            object,
+        })
+    }
+
+    /// The proof that a transaction was executed with its effects and events, from the
+    /// checkpoint including it. Only transactions which are checkpointed already can be proved.
+    pub fn get_transaction_proof(&self, digest: TransactionDigest) -> SuiResult<TransactionProof> {
+        let transaction = self
+            .database
+            .get_transaction_block(&digest)?
+            .ok_or(SuiError::TransactionNotFound { digest })?
+            .into_inner();
+        let effects = self
+            .database
+            .get_executed_effects(&digest)?
+            .ok_or(SuiError::TransactionNotFound { digest })?;
+        let events = effects
+            .events_digest()
+            .map(|digest| self.get_transaction_events(digest))
+            .transpose()?;
+        let checkpoint = self
+            .get_transaction_checkpoint(&digest)?
+            .ok_or_else(|| SuiError::GenericAuthorityError {
+                error: format!("Transaction {digest} is not included in a checkpoint yet"),
+            })?
+            .into_inner();
+        let contents = self
+            .checkpoint_store
+            .get_checkpoint_contents(&checkpoint.content_digest)?
+            .ok_or_else(|| {
+                SuiError::StorageMissingFieldError(format!(
+                    "Contents of checkpoint {} are missing",
+                    checkpoint.sequence_number
+                ))
+            })?;
+        Ok(TransactionProof {
+            checkpoint,
+            contents,
+            transaction,
+            effects,
+            events,
+        })
+    }
+
     pub async fn get_objects(
         &self,
         _objects: &[ObjectID],
@@ -313,6 +313,14 @@ impl AuthorityEpochTables {
         parent_path.join(format!("{}{}", EPOCH_DB_PREFIX, epoch))
     }
 
+    fn flush(&self) -> SuiResult {
+        let tables = Self::describe_tables();
+        Ok(self
+            .signed_transactions
+            .rocksdb
+            .flush_cfs(tables.keys().map(String::as_str))?)
+    }
+
     fn load_reconfig_state(&self) -> SuiResult<ReconfigState> {
         let state = self
             .reconfig_state
@@ -415,6 +423,11 @@ impl AuthorityPerEpochStore {
         self.parent_path.clone()
     }
 
+    /// Flush the tables of the epoch to disk, e.g. before shutting down.
+    pub fn flush(&self) -> SuiResult {
+        self.tables.flush()
+    }
+
     /// Returns `&Arc<EpochStartConfiguration>`
     /// User can treat this `Arc` as `&EpochStartConfiguration`, or clone the Arc to pass as owned object
     pub fn epoch_start_config(&self) -> &Arc<EpochStartConfiguration> {
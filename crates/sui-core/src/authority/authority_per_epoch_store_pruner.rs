@@ -6,7 +6,7 @@ use std::fs;
 use std::path::PathBuf;
 use std::time::Duration;
 use sui_config::node::AuthorityStorePruningConfig;
-use tokio::sync::oneshot;
+use tokio::sync::{oneshot, watch};
 use tracing::log::{error, info};
 use typed_store::rocks::safe_drop_db;
 
@@ -15,26 +15,38 @@ pub struct AuthorityPerEpochStorePruner {
 }
 
 impl AuthorityPerEpochStorePruner {
-    pub fn new(parent_path: PathBuf, config: &AuthorityStorePruningConfig) -> Self {
+    pub fn new(
+        parent_path: PathBuf,
+        mut config_receiver: watch::Receiver<AuthorityStorePruningConfig>,
+    ) -> Self {
         let (_cancel_handle, mut recv) = tokio::sync::oneshot::channel();
-        let num_latest_epoch_dbs_to_retain = config.num_latest_epoch_dbs_to_retain;
-        if num_latest_epoch_dbs_to_retain == 0 || num_latest_epoch_dbs_to_retain == usize::MAX {
-            info!("Skipping pruning of epoch tables as we want to retain all versions");
-            return Self { _cancel_handle };
-        }
-        let mut prune_interval =
-            tokio::time::interval(Duration::from_secs(config.epoch_db_pruning_period_secs));
         tokio::task::spawn(async move {
+            // Restarts pruning with the new config whenever it is updated.
             loop {
-                tokio::select! {
-                    _ = prune_interval.tick() => {
-                        info!("Starting pruning of epoch tables");
-                        match Self::prune_old_directories(&parent_path, num_latest_epoch_dbs_to_retain) {
-                            Ok(pruned_count) => info!("Finished pruning old epoch databases. Pruned {} dbs", pruned_count),
-                            Err(err) => error!("Error while removing old epoch databases {:?}", err),
+                let config = config_receiver.borrow_and_update().clone();
+                let num_latest_epoch_dbs_to_retain = config.num_latest_epoch_dbs_to_retain;
+                let mut prune_interval = if num_latest_epoch_dbs_to_retain == 0
+                    || num_latest_epoch_dbs_to_retain == usize::MAX
+                {
+                    info!("Skipping pruning of epoch tables as we want to retain all versions");
+                    None
+                } else {
+                    Some(tokio::time::interval(Duration::from_secs(
+                        config.epoch_db_pruning_period_secs,
+                    )))
+                };
+                loop {
+                    tokio::select! {
+                        _ = async { prune_interval.as_mut().unwrap().tick().await }, if prune_interval.is_some() => {
+                            info!("Starting pruning of epoch tables");
+                            match Self::prune_old_directories(&parent_path, num_latest_epoch_dbs_to_retain) {
+                                Ok(pruned_count) => info!("Finished pruning old epoch databases. Pruned {} dbs", pruned_count),
+                                Err(err) => error!("Error while removing old epoch databases {:?}", err),
+                            }
                         }
+                        Ok(()) = config_receiver.changed() => break,
+                        _ = &mut recv => return,
                     }
-                    _ = &mut recv => break,
                 }
             }
         });
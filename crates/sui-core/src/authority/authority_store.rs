@@ -28,6 +28,7 @@ use sui_types::object::Owner;
 use sui_types::storage::{
     get_module_by_id, BackingPackageStore, ChildObjectResolver, DeleteKind, ObjectKey, ObjectStore,
 };
+use sui_types::sui_serde::VersionedFormat;
 use sui_types::sui_system_state::get_sui_system_state;
 use sui_types::{base_types::SequenceNumber, fp_bail, fp_ensure, storage::ParentSync};
 use typed_store::rocks::{DBBatch, TypedStoreError};
@@ -216,6 +217,26 @@ impl AuthorityStore {
         self.perpetual_tables.get_recovery_epoch_at_restart()
     }
 
+    /// Compacts the table `table_name` of the perpetual tables, see
+    /// [AuthorityPerpetualTables::compact_table].
+    pub fn compact_perpetual_table(&self, table_name: &str) -> SuiResult {
+        self.perpetual_tables.compact_table(table_name)
+    }
+
+    pub fn set_perpetual_table_auto_compactions(
+        &self,
+        table_name: &str,
+        enabled: bool,
+    ) -> SuiResult {
+        self.perpetual_tables
+            .set_auto_compactions(table_name, enabled)
+    }
+
+    pub fn set_perpetual_tables_compaction_throttle(&self, max_background_jobs: u32) -> SuiResult {
+        self.perpetual_tables
+            .set_compaction_throttle(max_background_jobs)
+    }
+
     pub fn get_effects(
         &self,
         effects_digest: &TransactionEffectsDigest,
@@ -371,6 +392,11 @@ impl AuthorityStore {
             .collect())
     }
 
+    /// Flush the perpetual tables to disk, e.g. before shutting down.
+    pub fn flush(&self) -> SuiResult {
+        self.perpetual_tables.flush()
+    }
+
     /// Returns true if there are no objects in the database
     pub fn database_is_empty(&self) -> SuiResult<bool> {
         self.perpetual_tables.database_is_empty()
@@ -942,7 +968,7 @@ impl AuthorityStore {
                 .into());
             }
             // Safe to unwrap as it is checked above
-            let lock = lock.unwrap().map(|l| l.migrate().into_inner());
+            let lock = lock.unwrap().map(|l| l.into_inner());
 
             if let Some(LockDetails {
                 epoch: previous_epoch,
@@ -1008,7 +1034,7 @@ impl AuthorityStore {
             {
                 match lock_info {
                     Some(lock_info) => {
-                        let lock_info = lock_info.migrate().into_inner();
+                        let lock_info = lock_info.into_inner();
                         match Ord::cmp(&lock_info.epoch, &epoch_id) {
                             // If the object was locked in a previous epoch, we can say that it's
                             // no longer locked and is considered as just Initialized.
@@ -1621,40 +1647,6 @@ pub enum ObjectLockStatus {
     LockedAtDifferentVersion { locked_ref: ObjectRef },
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub enum LockDetailsWrapper {
-    V1(LockDetailsV1),
-}
-
-impl LockDetailsWrapper {
-    pub fn migrate(self) -> Self {
-        // TODO: when there are multiple versions, we must iteratively migrate from version N to
-        // N+1 until we arrive at the latest version
-        self
-    }
-
-    // Always returns the most recent version. Older versions are migrated to the latest version at
-    // read time, so there is never a need to access older versions.
-    pub fn inner(&self) -> &LockDetails {
-        match self {
-            Self::V1(v1) => v1,
-
-            // can remove #[allow] when there are multiple versions
-            #[allow(unreachable_patterns)]
-            _ => panic!("lock details should have been migrated to latest version at read time"),
-        }
-    }
-    pub fn into_inner(self) -> LockDetails {
-        match self {
-            Self::V1(v1) => v1,
-
-            // can remove #[allow] when there are multiple versions
-            #[allow(unreachable_patterns)]
-            _ => panic!("lock details should have been migrated to latest version at read time"),
-        }
-    }
-}
-
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LockDetailsV1 {
     pub epoch: EpochId,
@@ -1663,11 +1655,9 @@ pub struct LockDetailsV1 {
 
 pub type LockDetails = LockDetailsV1;
 
-impl From<LockDetails> for LockDetailsWrapper {
-    fn from(details: LockDetails) -> Self {
-        // always use latest version.
-        LockDetailsWrapper::V1(details)
-    }
+// Version 0 is stored as the `V1` variant of the enum lock details used to be wrapped in.
+impl VersionedFormat for LockDetailsV1 {
+    const FORMAT_VERSION: u8 = 0;
 }
 
 /// A potential input to a transaction.
@@ -0,0 +1,112 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::ensure;
+use sui_config::node::DbCompactionConfig;
+use tokio::sync::oneshot;
+use tokio::time::Instant;
+use tracing::{error, info};
+
+use super::authority_store::AuthorityStore;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Compacts tables of the perpetual store daily at the off-peak hour of a [DbCompactionConfig].
+pub struct AuthorityStoreCompactionScheduler {
+    _cancel_handle: oneshot::Sender<()>,
+}
+
+impl AuthorityStoreCompactionScheduler {
+    pub fn new(store: Arc<AuthorityStore>, config: DbCompactionConfig) -> anyhow::Result<Self> {
+        ensure!(
+            config.off_peak_hour_utc < 24,
+            "Invalid off-peak compaction hour {}",
+            config.off_peak_hour_utc
+        );
+        // Also checks that the tables exist.
+        for table in &config.tables {
+            store.set_perpetual_table_auto_compactions(table, !config.disable_auto_compactions)?;
+        }
+
+        let (_cancel_handle, mut recv) = oneshot::channel();
+        tokio::task::spawn(async move {
+            info!(
+                "Starting db compaction schedule of {:?} at {}:00 UTC",
+                config.tables, config.off_peak_hour_utc
+            );
+            loop {
+                let delay = duration_until_hour_utc(SystemTime::now(), config.off_peak_hour_utc);
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {
+                        for table in &config.tables {
+                            Self::compact(store.clone(), table.clone()).await;
+                        }
+                    }
+                    _ = &mut recv => break,
+                }
+            }
+        });
+        Ok(Self { _cancel_handle })
+    }
+
+    async fn compact(store: Arc<AuthorityStore>, table: String) {
+        info!("Starting scheduled compaction of {table}");
+        let start = Instant::now();
+        let result = {
+            let table = table.clone();
+            tokio::task::spawn_blocking(move || store.compact_perpetual_table(&table)).await
+        };
+        match result {
+            Ok(Ok(())) => info!(
+                "Finished scheduled compaction of {table} in {:?}",
+                start.elapsed()
+            ),
+            Ok(Err(err)) => error!("Failed to compact {table}: {err:?}"),
+            Err(err) => error!("Compaction of {table} panicked: {err:?}"),
+        }
+    }
+}
+
+/// The time from `now` until the next time the hour of the day is `hour` UTC.
+fn duration_until_hour_utc(now: SystemTime, hour: u8) -> Duration {
+    let seconds_in_day = now
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()
+        % SECONDS_PER_DAY;
+    let target = hour as u64 * 60 * 60;
+    let delay = (target + SECONDS_PER_DAY - seconds_in_day) % SECONDS_PER_DAY;
+    // Right after a compaction, wait for the next day.
+    Duration::from_secs(if delay == 0 { SECONDS_PER_DAY } else { delay })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duration_until_hour_utc() {
+        let day = |days: u64, seconds: u64| {
+            UNIX_EPOCH + Duration::from_secs(days * SECONDS_PER_DAY + seconds)
+        };
+        assert_eq!(
+            duration_until_hour_utc(day(10, 0), 3),
+            Duration::from_secs(3 * 60 * 60)
+        );
+        assert_eq!(
+            duration_until_hour_utc(day(10, 4 * 60 * 60), 3),
+            Duration::from_secs(23 * 60 * 60)
+        );
+        assert_eq!(
+            duration_until_hour_utc(day(10, 3 * 60 * 60), 3),
+            Duration::from_secs(SECONDS_PER_DAY)
+        );
+        assert_eq!(
+            duration_until_hour_utc(day(10, 3 * 60 * 60 - 1), 3),
+            Duration::from_secs(1)
+        );
+    }
+}
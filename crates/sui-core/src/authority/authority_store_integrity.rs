@@ -0,0 +1,333 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks of the invariants between the tables of the db of a node, e.g. after a crash or after
+//! restoring a db checkpoint taken at an epoch boundary.
+
+use std::fmt::{Display, Formatter};
+
+use sui_storage::IndexStore;
+use sui_types::base_types::{ObjectID, ObjectInfo, ObjectRef, SuiAddress, TransactionDigest};
+use sui_types::digests::TransactionEffectsDigest;
+use sui_types::error::SuiResult;
+use sui_types::messages::TransactionEffectsAPI;
+use sui_types::object::Owner;
+use sui_types::storage::{ObjectKey, ObjectStore};
+use typed_store::traits::Map;
+
+use crate::authority::authority_store_tables::AuthorityPerpetualTables;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DbIntegrityViolation {
+    /// The transaction is marked as executed, but its effects are missing.
+    MissingExecutedEffects {
+        transaction: TransactionDigest,
+        effects: TransactionEffectsDigest,
+    },
+    /// The effects are stored, but the transaction they are the effects of is missing.
+    MissingTransaction {
+        transaction: TransactionDigest,
+        effects: TransactionEffectsDigest,
+    },
+    /// The lock of an owned object references an object version missing from the object store.
+    MissingLockedObject { object_ref: ObjectRef },
+    /// The owner index references an object version which is not the live version of the object,
+    /// or is not owned by the indexed owner.
+    DanglingOwnerIndexEntry {
+        owner: SuiAddress,
+        object_id: ObjectID,
+        indexed: ObjectRef,
+        live: Option<ObjectRef>,
+    },
+}
+
+impl DbIntegrityViolation {
+    /// Whether [repair_db_integrity] can fix the violation. Missing transactions and effects can
+    /// only be recovered by syncing them again from the network.
+    pub fn is_repairable(&self) -> bool {
+        matches!(
+            self,
+            Self::MissingLockedObject { .. } | Self::DanglingOwnerIndexEntry { .. }
+        )
+    }
+}
+
+impl Display for DbIntegrityViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingExecutedEffects {
+                transaction,
+                effects,
+            } => write!(
+                f,
+                "effects {effects} of executed transaction {transaction} are missing"
+            ),
+            Self::MissingTransaction {
+                transaction,
+                effects,
+            } => write!(
+                f,
+                "transaction {transaction} of effects {effects} is missing"
+            ),
+            Self::MissingLockedObject { object_ref } => {
+                write!(f, "locked object {object_ref:?} is missing")
+            }
+            Self::DanglingOwnerIndexEntry {
+                owner,
+                object_id,
+                indexed,
+                live,
+            } => write!(
+                f,
+                "owner index entry ({owner}, {object_id}) references {indexed:?}, the live object is {live:?}"
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct DbIntegrityReport {
+    pub executed_transactions: usize,
+    pub effects: usize,
+    pub locks: usize,
+    pub owner_index_entries: usize,
+    pub violations: Vec<DbIntegrityViolation>,
+}
+
+impl DbIntegrityReport {
+    pub fn is_consistent(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Scans the tables of the db for entries referencing data missing from other tables. The
+/// owner index is only checked if `index_store` is set, i.e. on fullnodes.
+pub fn verify_db_integrity(
+    perpetual_tables: &AuthorityPerpetualTables,
+    index_store: Option<&IndexStore>,
+) -> SuiResult<DbIntegrityReport> {
+    let mut report = DbIntegrityReport::default();
+
+    for (transaction, effects) in perpetual_tables.executed_effects.iter() {
+        report.executed_transactions += 1;
+        if !perpetual_tables.effects.contains_key(&effects)? {
+            report
+                .violations
+                .push(DbIntegrityViolation::MissingExecutedEffects {
+                    transaction,
+                    effects,
+                });
+        }
+    }
+
+    for (digest, effects) in perpetual_tables.effects.iter() {
+        report.effects += 1;
+        let transaction = *effects.transaction_digest();
+        if !perpetual_tables.transactions.contains_key(&transaction)? {
+            report
+                .violations
+                .push(DbIntegrityViolation::MissingTransaction {
+                    transaction,
+                    effects: digest,
+                });
+        }
+    }
+
+    for (object_ref, _) in perpetual_tables.owned_object_transaction_locks.iter() {
+        report.locks += 1;
+        let object = match perpetual_tables
+            .objects
+            .get(&ObjectKey(object_ref.0, object_ref.1))?
+        {
+            Some(store_object) => perpetual_tables.object(store_object)?,
+            None => None,
+        };
+        if object.map(|o| o.compute_object_reference()) != Some(object_ref) {
+            report
+                .violations
+                .push(DbIntegrityViolation::MissingLockedObject { object_ref });
+        }
+    }
+
+    if let Some(index_store) = index_store {
+        for ((owner, object_id), info) in index_store.owner_index_iter() {
+            report.owner_index_entries += 1;
+            let live = perpetual_tables.get_object(&object_id)?;
+            let indexed = (info.object_id, info.version, info.digest);
+            let is_live = live.as_ref().map_or(false, |object| {
+                object.compute_object_reference() == indexed
+                    && object.owner == Owner::AddressOwner(owner)
+                    && info.owner == object.owner
+            });
+            if !is_live {
+                report
+                    .violations
+                    .push(DbIntegrityViolation::DanglingOwnerIndexEntry {
+                        owner,
+                        object_id,
+                        indexed,
+                        live: live.map(|o| o.compute_object_reference()),
+                    });
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Repairs the repairable `violations` found by [verify_db_integrity]: the locks of missing
+/// objects are removed, and the dangling owner index entries are replaced by the entry of the
+/// live object if it is owned by an address. Returns the number of repaired violations.
+pub fn repair_db_integrity(
+    perpetual_tables: &AuthorityPerpetualTables,
+    index_store: Option<&IndexStore>,
+    violations: &[DbIntegrityViolation],
+) -> SuiResult<usize> {
+    let mut missing_locks = vec![];
+    let mut deleted_owners = vec![];
+    let mut new_owners = vec![];
+    for violation in violations {
+        match violation {
+            DbIntegrityViolation::MissingLockedObject { object_ref } => {
+                missing_locks.push(*object_ref)
+            }
+            DbIntegrityViolation::DanglingOwnerIndexEntry {
+                owner, object_id, ..
+            } if index_store.is_some() => {
+                deleted_owners.push((*owner, *object_id));
+                if let Some(object) = perpetual_tables.get_object(object_id)? {
+                    if let Owner::AddressOwner(address) = object.owner {
+                        new_owners.push((
+                            (address, *object_id),
+                            ObjectInfo::new(&object.compute_object_reference(), &object),
+                        ));
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    let repaired = missing_locks.len() + deleted_owners.len();
+    let mut batch = perpetual_tables.owned_object_transaction_locks.batch();
+    batch.delete_batch(
+        &perpetual_tables.owned_object_transaction_locks,
+        missing_locks,
+    )?;
+    batch.write()?;
+    if let Some(index_store) = index_store {
+        index_store.repair_owner_index(deleted_owners, new_owners)?;
+    }
+    Ok(repaired)
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_types::base_types::SequenceNumber;
+    use sui_types::message_envelope::Message;
+    use sui_types::messages::TransactionEffects;
+    use sui_types::object::Object;
+
+    use super::*;
+    use crate::authority::authority_store_types::get_store_object_pair;
+
+    fn insert_object(tables: &AuthorityPerpetualTables, object: &Object) {
+        tables
+            .objects
+            .insert(
+                &ObjectKey(object.id(), object.version()),
+                &get_store_object_pair(object.clone(), 0).0,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_verify_and_repair_db_integrity() {
+        let tables =
+            AuthorityPerpetualTables::open(&tempfile::tempdir().unwrap().into_path(), None);
+        let index_store = IndexStore::new(tempfile::tempdir().unwrap().into_path());
+
+        let owner = SuiAddress::random_for_testing_only();
+        let object = Object::with_id_owner_for_testing(ObjectID::random(), owner);
+        insert_object(&tables, &object);
+        let object_ref = object.compute_object_reference();
+        tables
+            .owned_object_transaction_locks
+            .insert(&object_ref, &None)
+            .unwrap();
+        index_store
+            .repair_owner_index(
+                vec![],
+                vec![((owner, object.id()), ObjectInfo::new(&object_ref, &object))],
+            )
+            .unwrap();
+        let report = verify_db_integrity(&tables, Some(&index_store)).unwrap();
+        assert!(report.is_consistent(), "{:?}", report.violations);
+        assert_eq!((report.locks, report.owner_index_entries), (1, 1));
+
+        // Executed transaction without effects, and effects without transaction.
+        let transaction = TransactionDigest::random();
+        let effects = TransactionEffectsDigest::random();
+        tables
+            .executed_effects
+            .insert(&transaction, &effects)
+            .unwrap();
+        let stored_effects = TransactionEffects::default();
+        tables
+            .effects
+            .insert(&stored_effects.digest(), &stored_effects)
+            .unwrap();
+
+        // Lock of an object version which was never written.
+        let missing = Object::with_id_owner_for_testing(ObjectID::random(), owner);
+        tables
+            .owned_object_transaction_locks
+            .insert(&missing.compute_object_reference(), &None)
+            .unwrap();
+
+        // The object was transferred, but the owner index was not updated.
+        let new_owner = SuiAddress::random_for_testing_only();
+        let mut transferred = object.clone();
+        transferred.owner = Owner::AddressOwner(new_owner);
+        transferred
+            .data
+            .try_as_move_mut()
+            .unwrap()
+            .increment_version_to(SequenceNumber::from_u64(object.version().value() + 1));
+        insert_object(&tables, &transferred);
+
+        let report = verify_db_integrity(&tables, Some(&index_store)).unwrap();
+        assert_eq!(
+            report.violations,
+            vec![
+                DbIntegrityViolation::MissingExecutedEffects {
+                    transaction,
+                    effects
+                },
+                DbIntegrityViolation::MissingTransaction {
+                    transaction: *stored_effects.transaction_digest(),
+                    effects: stored_effects.digest(),
+                },
+                DbIntegrityViolation::MissingLockedObject {
+                    object_ref: missing.compute_object_reference()
+                },
+                DbIntegrityViolation::DanglingOwnerIndexEntry {
+                    owner,
+                    object_id: object.id(),
+                    indexed: object_ref,
+                    live: Some(transferred.compute_object_reference()),
+                },
+            ]
+        );
+
+        assert_eq!(
+            repair_db_integrity(&tables, Some(&index_store), &report.violations).unwrap(),
+            2
+        );
+        let report = verify_db_integrity(&tables, Some(&index_store)).unwrap();
+        assert!(report.violations.iter().all(|v| !v.is_repairable()));
+        assert_eq!(report.violations.len(), 2);
+        let owners: Vec<_> = index_store.owner_index_iter().map(|(key, _)| key).collect();
+        assert_eq!(owners, vec![(new_owner, object.id())]);
+    }
+}
@@ -1,7 +1,9 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::authority::authority_store_types::{ObjectContentDigest, StoreData, StoreObject};
+use crate::authority::authority_store_types::{
+    ObjectContentDigest, StoreData, StoreObject, StoreObjectWrapper,
+};
 use crate::checkpoints::CheckpointStore;
 use mysten_metrics::monitored_scope;
 use prometheus::{
@@ -9,10 +11,11 @@ use prometheus::{
     Registry,
 };
 use std::cmp::{max, min};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::{sync::Arc, time::Duration};
 use sui_config::node::AuthorityStorePruningConfig;
 use sui_storage::mutex_table::RwLockTable;
+use sui_types::error::SuiResult;
 use sui_types::messages::{TransactionEffects, TransactionEffectsAPI};
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::{
@@ -20,8 +23,9 @@ use sui_types::{
     storage::ObjectKey,
 };
 use tokio::sync::oneshot::{self, Sender};
+use tokio::sync::watch;
 use tokio::time::Instant;
-use tracing::log::{debug, error};
+use tracing::log::{debug, error, info};
 use typed_store::Map;
 
 use super::authority_store_tables::AuthorityPerpetualTables;
@@ -55,6 +59,48 @@ impl AuthorityStorePruningMetrics {
     }
 }
 
+/// The objects whose historical versions are never pruned, from
+/// [AuthorityStorePruningConfig::retained_objects] and
+/// [AuthorityStorePruningConfig::retained_packages].
+#[derive(Debug, Default)]
+struct RetainedObjects {
+    objects: HashSet<ObjectID>,
+    packages: HashSet<ObjectID>,
+}
+
+impl RetainedObjects {
+    fn new(config: &AuthorityStorePruningConfig) -> Self {
+        Self {
+            objects: config.retained_objects.iter().copied().collect(),
+            packages: config.retained_packages.iter().copied().collect(),
+        }
+    }
+
+    /// Whether the version of `object_id` stored as `object` must be retained. The type of an
+    /// object never changes, so either all its versions are retained or none.
+    fn retains(
+        &self,
+        perpetual_db: &AuthorityPerpetualTables,
+        object_id: &ObjectID,
+        object: &StoreObjectWrapper,
+    ) -> SuiResult<bool> {
+        if self.objects.contains(object_id) {
+            return Ok(true);
+        }
+        if self.packages.is_empty() {
+            return Ok(false);
+        }
+        Ok(perpetual_db
+            .object(object.clone())?
+            .and_then(|object| {
+                object
+                    .type_()
+                    .map(|type_| self.packages.contains(&ObjectID::from(type_.address())))
+            })
+            .unwrap_or(false))
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 enum DeletionMethod {
     RangeDelete,
@@ -69,46 +115,50 @@ impl AuthorityStorePruner {
         objects_lock_table: &Arc<RwLockTable<ObjectContentDigest>>,
         checkpoint_number: CheckpointSequenceNumber,
         deletion_method: DeletionMethod,
+        retained_objects: &RetainedObjects,
         metrics: Arc<AuthorityStorePruningMetrics>,
     ) -> anyhow::Result<()> {
         let _scope = monitored_scope("ObjectsLivePruner");
         let mut wb = perpetual_db.objects.batch();
 
-        let mut object_keys_to_prune = vec![];
+        let mut object_keys = vec![];
         for effects in &transaction_effects {
             for (object_id, seq_number) in effects.modified_at_versions() {
-                object_keys_to_prune.push(ObjectKey(*object_id, *seq_number));
+                object_keys.push(ObjectKey(*object_id, *seq_number));
             }
         }
-        metrics
-            .num_pruned_objects
-            .inc_by(object_keys_to_prune.len() as u64);
+        let objects = perpetual_db.objects.multi_get(object_keys.iter())?;
+        let mut object_keys_to_prune = vec![];
         let mut indirect_objects: HashMap<_, i64> = HashMap::new();
-        for object in perpetual_db
-            .objects
-            .multi_get(object_keys_to_prune.iter())?
-            .into_iter()
-            .flatten()
-        {
-            if let StoreObject::Value(obj) = object.into_inner() {
-                if let StoreData::IndirectObject(indirect_object) = obj.data {
-                    *indirect_objects.entry(indirect_object.digest).or_default() -= 1;
+        for (object_key, object) in object_keys.into_iter().zip(objects) {
+            if let Some(object) = object {
+                if retained_objects.retains(perpetual_db, &object_key.0, &object)? {
+                    continue;
+                }
+                if let StoreObject::Value(obj) = object.into_inner() {
+                    if let StoreData::IndirectObject(indirect_object) = obj.data {
+                        *indirect_objects.entry(indirect_object.digest).or_default() -= 1;
+                    }
                 }
             }
+            object_keys_to_prune.push(object_key);
         }
+        metrics
+            .num_pruned_objects
+            .inc_by(object_keys_to_prune.len() as u64);
 
         match deletion_method {
             DeletionMethod::RangeDelete => {
+                // Retention applies to all the versions of an object, the ranges never cover
+                // retained versions.
                 let mut updates: HashMap<ObjectID, (VersionNumber, VersionNumber)> = HashMap::new();
-                for effects in transaction_effects {
-                    for (object_id, seq_number) in effects.modified_at_versions() {
-                        updates
-                            .entry(*object_id)
-                            .and_modify(|range| {
-                                *range = (min(range.0, *seq_number), max(range.1, *seq_number))
-                            })
-                            .or_insert((*seq_number, *seq_number));
-                    }
+                for ObjectKey(object_id, seq_number) in object_keys_to_prune {
+                    updates
+                        .entry(object_id)
+                        .and_modify(|range| {
+                            *range = (min(range.0, seq_number), max(range.1, seq_number))
+                        })
+                        .or_insert((seq_number, seq_number));
                 }
                 for (object_id, (min_version, max_version)) in updates {
                     let start_range = ObjectKey(object_id, min_version);
@@ -141,9 +191,10 @@ impl AuthorityStorePruner {
         perpetual_db: &Arc<AuthorityPerpetualTables>,
         checkpoint_store: &Arc<CheckpointStore>,
         objects_lock_table: &Arc<RwLockTable<ObjectContentDigest>>,
-        config: AuthorityStorePruningConfig,
+        config: &AuthorityStorePruningConfig,
         metrics: Arc<AuthorityStorePruningMetrics>,
     ) -> anyhow::Result<()> {
+        let retained_objects = RetainedObjects::new(config);
         let deletion_method = if config.use_range_deletion {
             DeletionMethod::RangeDelete
         } else {
@@ -206,6 +257,7 @@ impl AuthorityStorePruner {
                     objects_lock_table,
                     checkpoint_number,
                     deletion_method,
+                    &retained_objects,
                     metrics.clone(),
                 )
                 .await?;
@@ -220,6 +272,7 @@ impl AuthorityStorePruner {
                 objects_lock_table,
                 checkpoint_number,
                 deletion_method,
+                &retained_objects,
                 metrics.clone(),
             )
             .await?;
@@ -231,8 +284,22 @@ impl AuthorityStorePruner {
         Ok(())
     }
 
+    fn pruning_interval(
+        config: &AuthorityStorePruningConfig,
+        epoch_duration_ms: u64,
+    ) -> tokio::time::Interval {
+        let tick_duration = if config.num_epochs_to_retain > 0 {
+            Duration::from_millis(epoch_duration_ms / 2)
+        } else {
+            Duration::from_secs(config.pruning_run_delay_seconds.unwrap_or(60))
+        };
+
+        let pruning_initial_delay = min(tick_duration, Duration::from_secs(300));
+        tokio::time::interval_at(Instant::now() + pruning_initial_delay, tick_duration)
+    }
+
     fn setup_objects_pruning(
-        config: AuthorityStorePruningConfig,
+        mut config_receiver: watch::Receiver<AuthorityStorePruningConfig>,
         epoch_duration_ms: u64,
         perpetual_db: Arc<AuthorityPerpetualTables>,
         checkpoint_store: Arc<CheckpointStore>,
@@ -240,39 +307,41 @@ impl AuthorityStorePruner {
         metrics: Arc<AuthorityStorePruningMetrics>,
     ) -> Sender<()> {
         let (sender, mut recv) = tokio::sync::oneshot::channel();
+        let mut config = config_receiver.borrow_and_update().clone();
         debug!(
             "Starting object pruning service with num_epochs_to_retain={}",
             config.num_epochs_to_retain
         );
-        let tick_duration = if config.num_epochs_to_retain > 0 {
-            Duration::from_millis(epoch_duration_ms / 2)
-        } else {
-            Duration::from_secs(config.pruning_run_delay_seconds.unwrap_or(60))
-        };
-
-        let pruning_initial_delay = min(tick_duration, Duration::from_secs(300));
-        let mut prune_interval =
-            tokio::time::interval_at(Instant::now() + pruning_initial_delay, tick_duration);
+        let mut prune_interval = Self::pruning_interval(&config, epoch_duration_ms);
 
         tokio::task::spawn(async move {
             loop {
                 tokio::select! {
                     _ = prune_interval.tick(), if config.num_epochs_to_retain != u64::MAX => {
-                        if let Err(err) = Self::prune_objects_for_eligible_epochs(&perpetual_db, &checkpoint_store, &objects_lock_table, config, metrics.clone()).await {
+                        if let Err(err) = Self::prune_objects_for_eligible_epochs(&perpetual_db, &checkpoint_store, &objects_lock_table, &config, metrics.clone()).await {
                             error!("Failed to prune objects: {:?}", err);
                         }
                     },
+                    Ok(()) = config_receiver.changed() => {
+                        config = config_receiver.borrow_and_update().clone();
+                        info!(
+                            "Updated object pruning config, num_epochs_to_retain={}",
+                            config.num_epochs_to_retain
+                        );
+                        prune_interval = Self::pruning_interval(&config, epoch_duration_ms);
+                    }
                     _ = &mut recv => break,
                 }
             }
         });
         sender
     }
+
     pub fn new(
         perpetual_db: Arc<AuthorityPerpetualTables>,
         checkpoint_store: Arc<CheckpointStore>,
         objects_lock_table: Arc<RwLockTable<ObjectContentDigest>>,
-        pruning_config: AuthorityStorePruningConfig,
+        pruning_config: watch::Receiver<AuthorityStorePruningConfig>,
         epoch_duration_ms: u64,
         registry: &Registry,
     ) -> Self {
@@ -314,12 +383,13 @@ mod tests {
         base_types::{ObjectID, SequenceNumber},
         object::Object,
         storage::ObjectKey,
+        SUI_FRAMEWORK_OBJECT_ID,
     };
     use typed_store::rocks::util::reference_count_merge_operator;
     use typed_store::rocks::{DBMap, MetricConf, ReadWriteOptions};
     use typed_store::Map;
 
-    use super::AuthorityStorePruner;
+    use super::{AuthorityStorePruner, RetainedObjects};
 
     fn get_keys_after_pruning(path: &Path) -> anyhow::Result<HashSet<ObjectKey>> {
         let perpetual_db_path = path.join(Path::new("perpetual"));
@@ -476,6 +546,7 @@ mod tests {
                 &lock_table(),
                 0,
                 deletion_method,
+                &RetainedObjects::default(),
                 metrics,
             )
             .await
@@ -504,6 +575,50 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn test_pruning_retained_objects() {
+        let path = tempfile::tempdir().unwrap().into_path();
+        let db = Arc::new(AuthorityPerpetualTables::open(&path, None));
+        let (to_keep, to_delete) = generate_test_data(db.clone(), 3, 1, 10).unwrap();
+        let retained_id = to_delete[0].0;
+        let prune = |retained_objects: RetainedObjects| {
+            let mut effects = TransactionEffects::default();
+            *effects.modified_at_versions_mut_for_testing() =
+                to_delete.iter().map(|o| (o.0, o.1)).collect();
+            let db = db.clone();
+            async move {
+                AuthorityStorePruner::prune_effects(
+                    vec![effects],
+                    &db,
+                    &lock_table(),
+                    0,
+                    DeletionMethod::PointDelete,
+                    &retained_objects,
+                    AuthorityStorePruningMetrics::new(&Registry::default()),
+                )
+                .await
+                .unwrap();
+            }
+        };
+
+        // All the test objects are gas coins, whose type is defined in the framework.
+        prune(RetainedObjects {
+            objects: HashSet::new(),
+            packages: HashSet::from([SUI_FRAMEWORK_OBJECT_ID]),
+        })
+        .await;
+        assert_eq!(db.objects.keys().count(), to_keep.len() + to_delete.len());
+
+        prune(RetainedObjects {
+            objects: HashSet::from([retained_id]),
+            packages: HashSet::new(),
+        })
+        .await;
+        let mut expected: HashSet<_> = to_keep.into_iter().collect();
+        expected.extend(to_delete.iter().filter(|key| key.0 == retained_id));
+        assert_eq!(db.objects.keys().collect::<HashSet<_>>(), expected);
+    }
+
     #[tokio::test]
     async fn test_ref_count_pruning() {
         let path = tempfile::tempdir().unwrap().into_path();
@@ -566,6 +681,7 @@ mod tests {
             &lock_table(),
             0,
             DeletionMethod::RangeDelete,
+            &RetainedObjects::default(),
             metrics,
         )
         .await;
@@ -603,6 +719,7 @@ mod tests {
             &lock_table(),
             0,
             DeletionMethod::RangeDelete,
+            &RetainedObjects::default(),
             metrics,
         )
         .await?;
@@ -638,6 +755,7 @@ mod tests {
             &lock_table(),
             0,
             DeletionMethod::RangeDelete,
+            &RetainedObjects::default(),
             metrics,
         )
         .await?;
@@ -2,18 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
-use crate::authority::authority_store::LockDetailsWrapper;
+use crate::authority::authority_store::LockDetails;
 use rocksdb::Options;
 use std::path::Path;
 use sui_types::accumulator::Accumulator;
 use sui_types::base_types::SequenceNumber;
 use sui_types::digests::TransactionEventsDigest;
 use sui_types::storage::ObjectStore;
+use sui_types::sui_serde::VersionedEnvelope;
 use typed_store::metrics::SamplingInterval;
 use typed_store::rocks::util::{empty_compaction_filter, reference_count_merge_operator};
 use typed_store::rocks::{
     optimized_for_high_throughput_options, read_size_from_env, DBBatch, DBMap, DBOptions,
-    MetricConf, ReadWriteOptions,
+    MetricConf, ReadWriteOptions, TypedStoreError,
 };
 use typed_store::traits::{Map, TableSummary, TypedStoreDebug};
 
@@ -60,7 +61,8 @@ pub struct AuthorityPerpetualTables {
     /// the lock once it is set. After a certificate for this object is processed it can be
     /// forgotten.
     #[default_options_override_fn = "owned_object_transaction_locks_table_default_config"]
-    pub(crate) owned_object_transaction_locks: DBMap<ObjectRef, Option<LockDetailsWrapper>>,
+    pub(crate) owned_object_transaction_locks:
+        DBMap<ObjectRef, Option<VersionedEnvelope<LockDetails>>>,
 
     /// This is a map between the transaction digest and the corresponding transaction that's known to be
     /// executable. This means that it may have been executed locally, or it may have been synced through
@@ -247,6 +249,15 @@ impl AuthorityPerpetualTables {
         Ok(())
     }
 
+    /// Flush all tables to disk, see [typed_store::rocks::RocksDB::flush_cfs].
+    pub fn flush(&self) -> SuiResult {
+        let tables = Self::describe_tables();
+        Ok(self
+            .objects
+            .rocksdb
+            .flush_cfs(tables.keys().map(String::as_str))?)
+    }
+
     pub fn database_is_empty(&self) -> SuiResult<bool> {
         Ok(self
             .objects
@@ -264,6 +275,45 @@ impl AuthorityPerpetualTables {
         }
     }
 
+    /// Compacts the whole table `table_name`. Blocks until the compaction is done.
+    pub fn compact_table(&self, table_name: &str) -> SuiResult {
+        let cf = self.table_cf(table_name)?;
+        self.objects
+            .rocksdb
+            .compact_range_cf(&cf, None::<Vec<u8>>, None);
+        Ok(())
+    }
+
+    /// Enables or disables the automatic compactions of the table `table_name`.
+    pub fn set_auto_compactions(&self, table_name: &str, enabled: bool) -> SuiResult {
+        let cf = self.table_cf(table_name)?;
+        let disabled = if enabled { "false" } else { "true" };
+        self.objects
+            .rocksdb
+            .set_options_cf(&cf, &[("disable_auto_compactions", disabled)])
+            .map_err(|e| SuiError::StorageError(TypedStoreError::RocksDBError(e.into_string())))
+    }
+
+    /// Limits the number of concurrent background compactions and flushes of the perpetual tables,
+    /// to leave more CPU and disk bandwidth to the execution of transactions.
+    pub fn set_compaction_throttle(&self, max_background_jobs: u32) -> SuiResult {
+        if max_background_jobs == 0 {
+            return Err(SuiError::StorageError(TypedStoreError::RocksDBError(
+                "max_background_jobs must be positive".to_string(),
+            )));
+        }
+        self.objects
+            .rocksdb
+            .set_options(&[("max_background_jobs", &max_background_jobs.to_string())])
+            .map_err(|e| SuiError::StorageError(TypedStoreError::RocksDBError(e.into_string())))
+    }
+
+    fn table_cf(&self, table_name: &str) -> SuiResult<Arc<rocksdb::BoundColumnFamily<'_>>> {
+        self.objects.rocksdb.cf_handle(table_name).ok_or_else(|| {
+            SuiError::StorageError(TypedStoreError::UnregisteredColumn(table_name.to_string()))
+        })
+    }
+
     pub fn checkpoint_db(&self, path: &Path) -> SuiResult {
         // This checkpoints the entire db and not just objects table
         self.objects
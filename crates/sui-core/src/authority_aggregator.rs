@@ -47,6 +47,9 @@ use tokio::time::{sleep, timeout};
 
 use crate::authority::AuthorityStore;
 use crate::epoch::committee_store::CommitteeStore;
+use crate::quorum_driver::validator_selection::{
+    StakeWeightedSelection, ValidatorSelectionStrategy,
+};
 use crate::stake_aggregator::{InsertResult, MultiStakeAggregator, StakeAggregator};
 
 pub const DEFAULT_RETRIES: usize = 4;
@@ -326,6 +329,8 @@ pub struct AuthorityAggregator<A> {
     pub timeouts: TimeoutConfig,
     /// Store here for clone during re-config.
     pub committee_store: Arc<CommitteeStore>,
+    /// Order in which the authorities are contacted.
+    pub validator_selection: Arc<dyn ValidatorSelectionStrategy>,
 }
 
 impl<A> AuthorityAggregator<A> {
@@ -372,6 +377,7 @@ impl<A> AuthorityAggregator<A> {
             safe_client_metrics_base,
             timeouts,
             committee_store,
+            validator_selection: Arc::new(StakeWeightedSelection),
         }
     }
 
@@ -402,6 +408,7 @@ impl<A> AuthorityAggregator<A> {
             safe_client_metrics_base,
             timeouts: Default::default(),
             committee_store,
+            validator_selection: Arc::new(StakeWeightedSelection),
         }
     }
 
@@ -468,9 +475,35 @@ impl<A> AuthorityAggregator<A> {
             timeouts: self.timeouts.clone(),
             safe_client_metrics_base: self.safe_client_metrics_base.clone(),
             committee_store: self.committee_store.clone(),
+            validator_selection: self.validator_selection.clone(),
         })
     }
 
+    pub fn with_validator_selection(
+        mut self,
+        validator_selection: Arc<dyn ValidatorSelectionStrategy>,
+    ) -> Self {
+        self.validator_selection = validator_selection;
+        self
+    }
+
+    /// Order in which the authorities are contacted: the `preferences` first, then the other
+    /// authorities, each group in the order of the validator selection strategy.
+    fn order_authorities(
+        &self,
+        preferences: Option<&BTreeSet<AuthorityName>>,
+        restrict_to: Option<&BTreeSet<AuthorityName>>,
+    ) -> Vec<AuthorityName> {
+        let (mut preferred, rest): (Vec<_>, Vec<_>) = self
+            .validator_selection
+            .order_validators(&self.committee)
+            .into_iter()
+            .filter(|name| restrict_to.map_or(true, |restrict_to| restrict_to.contains(name)))
+            .partition(|name| preferences.map_or(false, |preferences| preferences.contains(name)));
+        preferred.extend(rest);
+        preferred
+    }
+
     pub fn get_client(&self, name: &AuthorityName) -> Option<&SafeClient<A>> {
         self.authority_clients.get(name)
     }
@@ -612,7 +645,8 @@ where
             Result<V, SuiError>,
         ) -> BoxFuture<'a, ReduceOutput<R, S>>,
     {
-        let authorities_shuffled = self.committee.shuffle_by_stake(authority_preferences, None);
+        let authorities_shuffled = self.order_authorities(authority_preferences, None);
+        let validator_selection = &self.validator_selection;
 
         // First, execute in parallel for each authority FMap.
         let mut responses: futures::stream::FuturesUnordered<_> = authorities_shuffled
@@ -621,12 +655,16 @@ where
                 let client = &self.authority_clients[name];
                 let execute = map_each_authority.clone();
                 monitored_future!(async move {
-                    (
-                        *name,
-                        execute(*name, client)
-                            .instrument(tracing::trace_span!("quorum_map_auth", authority =? name.concise()))
-                            .await,
-                    )
+                    let start = tokio::time::Instant::now();
+                    let result = execute(*name, client)
+                        .instrument(
+                            tracing::trace_span!("quorum_map_auth", authority =? name.concise()),
+                        )
+                        .await;
+                    if result.is_ok() {
+                        validator_selection.report_latency(name, start.elapsed());
+                    }
+                    (*name, result)
                 })
             })
             .collect();
@@ -685,7 +723,7 @@ where
         let start = tokio::time::Instant::now();
         let mut delay = Duration::from_secs(1);
         loop {
-            let authorities_shuffled = self.committee.shuffle_by_stake(preferences, restrict_to);
+            let authorities_shuffled = self.order_authorities(preferences, restrict_to);
             let mut authorities_shuffled = authorities_shuffled.iter();
 
             type RequestResult<S> = Result<Result<S, SuiError>, tokio::time::error::Elapsed>;
@@ -699,10 +737,16 @@ where
 
             let start_req = |name: AuthorityName, client: SafeClient<A>| {
                 let map_each_authority = map_each_authority.clone();
+                let validator_selection = self.validator_selection.clone();
                 Box::pin(monitored_future!(async move {
-                    trace!(name=?name.concise(), now = ?tokio::time::Instant::now() - start, "new request");
+                    let request_start = tokio::time::Instant::now();
+                    trace!(name=?name.concise(), now = ?request_start - start, "new request");
                     let map = map_each_authority(name, client);
-                    Event::Request(name, timeout(timeout_each_authority, map).await)
+                    let result = timeout(timeout_each_authority, map).await;
+                    if let Ok(Ok(_)) = &result {
+                        validator_selection.report_latency(&name, request_start.elapsed());
+                    }
+                    Event::Request(name, result)
                 }))
             };
 
@@ -14,6 +14,7 @@ use sui_types::messages_checkpoint::{CheckpointRequest, CheckpointResponse};
 use sui_types::multiaddr::Multiaddr;
 use sui_types::sui_system_state::SuiSystemState;
 use sui_types::{error::SuiError, messages::*};
+use telemetry_subscribers::trace_context::{current_traceparent, TRACEPARENT_HEADER};
 
 use sui_network::tonic::transport::Channel;
 
@@ -86,6 +87,16 @@ impl NetworkAuthorityClient {
     }
 }
 
+/// Request carrying the `traceparent` of the current span, so that validators continue the trace
+/// of the transaction.
+fn traced_request<T>(message: T) -> tonic::Request<T> {
+    let mut request = tonic::Request::new(message);
+    if let Some(value) = current_traceparent().and_then(|v| v.parse().ok()) {
+        request.metadata_mut().insert(TRACEPARENT_HEADER, value);
+    }
+    request
+}
+
 #[async_trait]
 impl AuthorityAPI for NetworkAuthorityClient {
     /// Initiate a new transfer to a Sui or Primary account.
@@ -94,7 +105,7 @@ impl AuthorityAPI for NetworkAuthorityClient {
         transaction: Transaction,
     ) -> Result<HandleTransactionResponse, SuiError> {
         self.client()
-            .transaction(transaction)
+            .transaction(traced_request(transaction))
             .await
             .map(tonic::Response::into_inner)
             .map_err(Into::into)
@@ -106,7 +117,7 @@ impl AuthorityAPI for NetworkAuthorityClient {
         certificate: CertifiedTransaction,
     ) -> Result<HandleCertificateResponse, SuiError> {
         self.client()
-            .handle_certificate(certificate)
+            .handle_certificate(traced_request(certificate))
             .await
             .map(tonic::Response::into_inner)
             .map_err(Into::into)
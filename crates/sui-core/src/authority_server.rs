@@ -22,8 +22,11 @@ use sui_types::{
     messages_checkpoint::{CheckpointRequest, CheckpointResponse},
 };
 use tap::TapFallible;
+use telemetry_subscribers::trace_context::{
+    set_remote_parent, TraceParent, TRACEPARENT_HEADER, TRACESTATE_HEADER,
+};
 use tokio::task::JoinHandle;
-use tracing::{error_span, info, Instrument};
+use tracing::{error_span, field, info, Instrument, Span};
 
 use crate::consensus_adapter::{ConnectionMonitorStatusForTests, LazyNarwhalClient};
 use crate::{
@@ -311,6 +314,7 @@ impl ValidatorService {
         request: tonic::Request<Transaction>,
         metrics: Arc<ValidatorServiceMetrics>,
     ) -> Result<tonic::Response<HandleTransactionResponse>, tonic::Status> {
+        let trace_parent = trace_parent(&request);
         let transaction = request.into_inner();
         let epoch_store = state.load_epoch_store_one_call_per_task();
 
@@ -353,7 +357,14 @@ impl ValidatorService {
         let tx_digest = transaction.digest();
 
         // Enable Trace Propagation across spans/processes using tx_digest
-        let span = error_span!("validator_state_process_tx", ?tx_digest);
+        let span = error_span!(
+            "validator_state_process_tx",
+            ?tx_digest,
+            trace_id = field::Empty
+        );
+        if let Some((traceparent, tracestate)) = &trace_parent {
+            set_remote_parent(&span, traceparent, tracestate.as_deref());
+        }
 
         let info = state
             .handle_transaction(&epoch_store, transaction)
@@ -496,6 +507,29 @@ impl ValidatorService {
     }
 }
 
+/// The `traceparent` and `tracestate` sent by the client, e.g. the quorum driver of a fullnode.
+fn trace_parent<T>(request: &tonic::Request<T>) -> Option<(TraceParent, Option<String>)> {
+    let metadata = request.metadata();
+    let traceparent = metadata
+        .get(TRACEPARENT_HEADER)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()?;
+    let tracestate = metadata
+        .get(TRACESTATE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    Some((traceparent, tracestate))
+}
+
+/// Continue the trace of the client in `span`, if the request has a `traceparent`.
+fn set_request_trace_parent<T>(span: &Span, request: &tonic::Request<T>) {
+    if let Some((traceparent, tracestate)) = trace_parent(request) {
+        set_remote_parent(span, &traceparent, tracestate.as_deref());
+    }
+}
+
 #[async_trait]
 impl Validator for ValidatorService {
     async fn transaction(
@@ -529,7 +563,8 @@ impl Validator for ValidatorService {
         // processing in the event that the client connection is dropped.
         let metrics = self.metrics.clone();
         spawn_monitored_task!(async move {
-            let span = error_span!("submit_certificate", tx_digest = ?request.get_ref().digest());
+            let span = error_span!("submit_certificate", tx_digest = ?request.get_ref().digest(), trace_id = field::Empty);
+            set_request_trace_parent(&span, &request);
             Self::handle_certificate(state, consensus_adapter, request, metrics, false)
                 .instrument(span)
                 .await
@@ -550,7 +585,8 @@ impl Validator for ValidatorService {
         // processing in the event that the client connection is dropped.
         let metrics = self.metrics.clone();
         spawn_monitored_task!(async move {
-            let span = error_span!("handle_certificate", tx_digest = ?request.get_ref().digest());
+            let span = error_span!("handle_certificate", tx_digest = ?request.get_ref().digest(), trace_id = field::Empty);
+            set_request_trace_parent(&span, &request);
             Self::handle_certificate(state, consensus_adapter, request, metrics, true)
                 .instrument(span)
                 .await
@@ -15,8 +15,12 @@
 //! highest_executed_checkpoint, which is guaranteed to be updated sequentially in order,
 //! despite checkpoints themselves potentially being executed nonsequentially and in parallel.
 //! CheckpointExecutor parallelizes checkpoints of the same epoch as much as possible.
-//! CheckpointExecutor enforces the invariant that if `run` returns successfully, we have reached the
-//! end of epoch. This allows us to use it as a signal for reconfig.
+//! CheckpointExecutor enforces the invariant that if `run_epoch` returns
+//! [StopReason::EpochComplete], we have reached the end of epoch. This allows us to use it as a
+//! signal for reconfig.
+//!
+//! When the node shuts down, CheckpointExecutor stops scheduling checkpoints and waits for the
+//! checkpoints being executed to finish, so that the watermark is up to date on restart.
 
 use std::{
     collections::HashMap,
@@ -40,7 +44,10 @@ use sui_types::{
 use sui_types::{error::SuiResult, messages::TransactionDataAPI};
 use tap::{TapFallible, TapOptional};
 use tokio::{
-    sync::broadcast::{self, error::RecvError},
+    sync::{
+        broadcast::{self, error::RecvError},
+        watch,
+    },
     task::JoinHandle,
     time::timeout,
 };
@@ -62,8 +69,18 @@ pub(crate) mod tests;
 
 type CheckpointExecutionBuffer = FuturesOrdered<JoinHandle<VerifiedCheckpoint>>;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// All checkpoints of the epoch were executed.
+    EpochComplete,
+    /// The node is shutting down. The checkpoints scheduled before the shutdown were executed.
+    Shutdown,
+}
+
 pub struct CheckpointExecutor {
     mailbox: broadcast::Receiver<VerifiedCheckpoint>,
+    /// Set to true when the node is shutting down.
+    shutdown: watch::Receiver<bool>,
     checkpoint_store: Arc<CheckpointStore>,
     authority_store: Arc<AuthorityStore>,
     tx_manager: Arc<TransactionManager>,
@@ -75,6 +92,7 @@ pub struct CheckpointExecutor {
 impl CheckpointExecutor {
     pub fn new(
         mailbox: broadcast::Receiver<VerifiedCheckpoint>,
+        shutdown: watch::Receiver<bool>,
         checkpoint_store: Arc<CheckpointStore>,
         authority_store: Arc<AuthorityStore>,
         tx_manager: Arc<TransactionManager>,
@@ -84,6 +102,7 @@ impl CheckpointExecutor {
     ) -> Self {
         Self {
             mailbox,
+            shutdown,
             checkpoint_store,
             authority_store,
             tx_manager,
@@ -100,8 +119,11 @@ impl CheckpointExecutor {
         tx_manager: Arc<TransactionManager>,
         accumulator: Arc<StateAccumulator>,
     ) -> Self {
+        // The sender is dropped, so the executor never shuts down.
+        let (_, shutdown) = watch::channel(false);
         Self {
             mailbox,
+            shutdown,
             checkpoint_store,
             authority_store,
             tx_manager,
@@ -111,10 +133,11 @@ impl CheckpointExecutor {
         }
     }
 
-    /// Ensure that all checkpoints in the current epoch will be executed.
+    /// Ensure that all checkpoints in the current epoch will be executed, unless the node shuts
+    /// down first.
     /// We don't technically need &mut on self, but passing it to make sure only one instance is
     /// running at one time.
-    pub async fn run_epoch(&mut self, epoch_store: Arc<AuthorityPerEpochStore>) {
+    pub async fn run_epoch(&mut self, epoch_store: Arc<AuthorityPerEpochStore>) -> StopReason {
         debug!(
             "Checkpoint executor running for epoch {}",
             epoch_store.epoch(),
@@ -158,7 +181,11 @@ impl CheckpointExecutor {
                     "Pending checkpoint execution buffer should be empty after processing last checkpoint of epoch",
                 );
                 fail_point_async!("crash");
-                return;
+                return StopReason::EpochComplete;
+            }
+            if *self.shutdown.borrow() {
+                self.finish_pending_checkpoints(pending).await;
+                return StopReason::Shutdown;
             }
             self.schedule_synced_checkpoints(
                 &mut pending,
@@ -213,11 +240,24 @@ impl CheckpointExecutor {
                     Err(RecvError::Closed) => {
                         panic!("Checkpoint Execution Sender (StateSync) closed channel unexpectedly");
                     }
-                }
+                },
+                // The shutdown flag is checked at the start of the loop.
+                _ = wait_for_shutdown(&mut self.shutdown) => {}
             }
         }
     }
 
+    /// Wait for the checkpoints being executed, and ratchet the watermark accordingly.
+    async fn finish_pending_checkpoints(&self, mut pending: CheckpointExecutionBuffer) {
+        info!(
+            "Shutting down checkpoint executor, waiting for {} checkpoints being executed",
+            pending.len()
+        );
+        while let Some(Ok(checkpoint)) = pending.next().await {
+            self.process_executed_checkpoint(&checkpoint);
+        }
+    }
+
     pub fn set_inconsistent_state(&self, is_inconsistent_state: bool) {
         self.metrics
             .accumulator_inconsistent_state
@@ -853,3 +893,12 @@ fn finalize_checkpoint(
     accumulator.accumulate_checkpoint(effects, checkpoint_sequence, epoch_store)?;
     Ok(())
 }
+
+/// Returns when `shutdown` is set, or never if its sender was dropped.
+async fn wait_for_shutdown(shutdown: &mut watch::Receiver<bool>) {
+    while !*shutdown.borrow() {
+        if shutdown.changed().await.is_err() {
+            futures::future::pending::<()>().await;
+        }
+    }
+}
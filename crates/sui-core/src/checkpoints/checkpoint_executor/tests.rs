@@ -55,7 +55,7 @@ pub async fn test_checkpoint_executor_crash_recovery() {
     let epoch_store = state.epoch_store_for_testing().clone();
     let executor_handle =
         spawn_monitored_task!(async move { executor.run_epoch(epoch_store).await });
-    tokio::time::sleep(Duration::from_secs(5)).await;
+    wait_for_highest_executed(&checkpoint_store, buffer_size as u64 - 1).await;
 
     // ensure we executed all synced checkpoints
     let highest_executed = checkpoint_store
@@ -89,7 +89,7 @@ pub async fn test_checkpoint_executor_crash_recovery() {
     let epoch_store = state.epoch_store_for_testing().clone();
     let executor_handle =
         spawn_monitored_task!(async move { executor.run_epoch(epoch_store).await });
-    tokio::time::sleep(Duration::from_secs(5)).await;
+    wait_for_highest_executed(&checkpoint_store, buffer_size as u64 - 1).await;
 
     let highest_executed = checkpoint_store
         .get_highest_executed_checkpoint_seq_number()
@@ -100,6 +100,61 @@ pub async fn test_checkpoint_executor_crash_recovery() {
     executor_handle.abort();
 }
 
+/// Test that checkpoint executor stops when the node shuts down, after ratcheting the watermark of
+/// the checkpoints it executed.
+#[tokio::test]
+pub async fn test_checkpoint_executor_shutdown() {
+    let buffer_size = num_cpus::get() * 2;
+    let tempdir = tempdir().unwrap();
+    let checkpoint_store = CheckpointStore::new(tempdir.path());
+
+    let (state, _, accumulator, checkpoint_sender, committee): (
+        Arc<AuthorityState>,
+        CheckpointExecutor,
+        Arc<StateAccumulator>,
+        Sender<VerifiedCheckpoint>,
+        CommitteeFixture,
+    ) = init_executor_test(buffer_size, checkpoint_store.clone()).await;
+
+    let (shutdown_sender, shutdown_receiver) = watch::channel(false);
+    let mut executor = CheckpointExecutor::new(
+        checkpoint_sender.subscribe(),
+        shutdown_receiver,
+        checkpoint_store.clone(),
+        state.database.clone(),
+        state.transaction_manager().clone(),
+        accumulator.clone(),
+        CheckpointExecutorConfig::default(),
+        &Registry::new(),
+    );
+
+    let _ = sync_new_checkpoints(
+        &checkpoint_store,
+        &checkpoint_sender,
+        buffer_size,
+        None,
+        &committee,
+    );
+
+    let epoch_store = state.epoch_store_for_testing().clone();
+    let executor_handle =
+        spawn_monitored_task!(async move { executor.run_epoch(epoch_store).await });
+    wait_for_highest_executed(&checkpoint_store, buffer_size as u64 - 1).await;
+
+    shutdown_sender.send(true).unwrap();
+    let stop_reason = timeout(Duration::from_secs(5), executor_handle)
+        .await
+        .expect("Checkpoint executor should stop on shutdown")
+        .unwrap();
+    assert_eq!(stop_reason, StopReason::Shutdown);
+
+    let highest_executed = checkpoint_store
+        .get_highest_executed_checkpoint_seq_number()
+        .unwrap()
+        .expect("Expected highest executed to not be None");
+    assert_eq!(highest_executed, buffer_size as u64 - 1);
+}
+
 /// Test that checkpoint execution correctly signals end of epoch after
 /// receiving last checkpoint of epoch, then resumes executing cehckpoints
 /// from the next epoch if called after reconfig
@@ -412,6 +467,24 @@ async fn init_executor_test(
     (state, executor, accumulator, checkpoint_sender, committee)
 }
 
+/// Waits until the highest executed checkpoint watermark reaches `sequence_number`.
+async fn wait_for_highest_executed(
+    checkpoint_store: &CheckpointStore,
+    sequence_number: CheckpointSequenceNumber,
+) {
+    timeout(Duration::from_secs(10), async {
+        while checkpoint_store
+            .get_highest_executed_checkpoint_seq_number()
+            .unwrap()
+            < Some(sequence_number)
+        {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("Checkpoints should be executed in a timely manner");
+}
+
 /// Creates and simulates syncing of a new checkpoint by StateSync, i.e. new
 /// checkpoint is persisted, along with its contents, highest synced checkpoint
 /// watermark is updated, and message is broadcasted notifying of the newly synced
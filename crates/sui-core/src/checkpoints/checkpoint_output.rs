@@ -76,12 +76,12 @@ impl<T: SubmitToConsensus + ReconfigurationInitiator> CheckpointOutput
             .checkpoint_created(summary, contents, epoch_store)
             .await?;
 
-        let summary = SignedCheckpointSummary::new(
+        let summary = SignedCheckpointSummary::try_new(
             epoch_store.epoch(),
             summary.clone(),
             &*self.signer,
             self.authority,
-        );
+        )?;
 
         let message = CheckpointSignatureMessage { summary };
         let transaction = ConsensusTransaction::new_checkpoint_signature_message(message);
@@ -117,6 +117,14 @@ impl CheckpointStore {
         Self::get_read_only_handle(path.to_path_buf(), None, None, MetricConf::default())
     }
 
+    /// Flush all tables to disk, including the checkpoint watermarks.
+    pub fn flush(&self) -> Result<(), TypedStoreError> {
+        let tables = Self::describe_tables();
+        self.checkpoint_content
+            .rocksdb
+            .flush_cfs(tables.keys().map(String::as_str))
+    }
+
     pub fn insert_genesis_checkpoint(
         &self,
         checkpoint: VerifiedCheckpoint,
@@ -48,7 +48,7 @@ use sui_simulator::narwhal_network::connectivity::ConnectionStatus;
 use sui_types::base_types::AuthorityName;
 use sui_types::messages::ConsensusTransactionKind;
 use tokio::time::Duration;
-use tracing::{debug, info, warn};
+use tracing::{debug, info, warn, Instrument};
 
 #[cfg(test)]
 #[path = "unit_tests/consensus_tests.rs"]
@@ -502,10 +502,13 @@ impl ConsensusAdapter {
         transaction: ConsensusTransaction,
         epoch_store: &Arc<AuthorityPerEpochStore>,
     ) -> JoinHandle<()> {
+        // Keep the submission in the trace of the request handling the certificate, if any.
+        let span = tracing::error_span!("submit_to_consensus", key = ?transaction.key());
         // Reconfiguration lock is dropped when pending_consensus_transactions is persisted, before it is handled by consensus
         let async_stage = self
             .clone()
-            .submit_and_wait(transaction, epoch_store.clone());
+            .submit_and_wait(transaction, epoch_store.clone())
+            .instrument(span);
         // Number of this tasks is limited by `sequencing_certificate_inflight` limit
         let join_handle = spawn_monitored_task!(async_stage);
         join_handle
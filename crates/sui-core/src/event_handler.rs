@@ -1,11 +1,15 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashSet;
 use tokio_stream::Stream;
 use tracing::{error, instrument, trace};
 
-use sui_json_rpc_types::{EventFilter, SuiTransactionBlockEffects, SuiTransactionBlockEvents};
+use sui_json_rpc_types::{
+    EventFilter, OwnedObjectChange, SuiTransactionBlockEffects, SuiTransactionBlockEvents,
+};
 use sui_json_rpc_types::{SuiEvent, SuiTransactionBlockEffectsAPI};
+use sui_types::base_types::SuiAddress;
 use sui_types::error::SuiResult;
 
 use crate::streamer::Streamer;
@@ -18,6 +22,7 @@ pub const EVENT_DISPATCH_BUFFER_SIZE: usize = 1000;
 
 pub struct EventHandler {
     event_streamer: Streamer<SuiEvent, EventFilter>,
+    owned_object_change_streamer: Streamer<OwnedObjectChange, SuiAddress>,
 }
 
 impl Default for EventHandler {
@@ -25,6 +30,7 @@ impl Default for EventHandler {
         let streamer = Streamer::spawn(EVENT_DISPATCH_BUFFER_SIZE);
         Self {
             event_streamer: streamer,
+            owned_object_change_streamer: Streamer::spawn(EVENT_DISPATCH_BUFFER_SIZE),
         }
     }
 }
@@ -54,4 +60,29 @@ impl EventHandler {
     pub fn subscribe(&self, filter: EventFilter) -> impl Stream<Item = SuiEvent> {
         self.event_streamer.subscribe(filter)
     }
+
+    /// The addresses subscribed to their owned object changes, the only ones whose changes are
+    /// worth computing for the processed transactions.
+    pub async fn owned_object_change_subscriptions(&self) -> HashSet<SuiAddress> {
+        self.owned_object_change_streamer
+            .subscribed_filters()
+            .await
+            .into_iter()
+            .collect()
+    }
+
+    pub async fn process_owned_object_changes(&self, changes: Vec<OwnedObjectChange>) {
+        for change in changes {
+            if let Err(e) = self.owned_object_change_streamer.send(change).await {
+                error!(error =? e, "Failed to send owned object change to dispatch");
+            }
+        }
+    }
+
+    pub fn subscribe_owned_object_changes(
+        &self,
+        address: SuiAddress,
+    ) -> impl Stream<Item = OwnedObjectChange> {
+        self.owned_object_change_streamer.subscribe(address)
+    }
 }
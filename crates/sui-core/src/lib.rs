@@ -32,6 +32,8 @@ pub mod test_utils;
 pub mod transaction_input_checker;
 mod transaction_manager;
 pub mod transaction_orchestrator;
+pub mod transaction_policy;
+pub mod validator_signer;
 
 #[cfg(test)]
 #[path = "unit_tests/move_package_publish_tests.rs"]
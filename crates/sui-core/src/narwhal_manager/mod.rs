@@ -8,6 +8,7 @@ pub mod narwhal_manager_tests;
 use fastcrypto::traits::KeyPair;
 use mysten_metrics::RegistryService;
 use narwhal_config::{Committee, Epoch, Parameters, WorkerCache, WorkerId};
+use narwhal_crypto::ProtocolSigner;
 use narwhal_executor::ExecutionState;
 use narwhal_network::client::NetworkClient;
 use narwhal_node::primary_node::PrimaryNode;
@@ -18,7 +19,7 @@ use prometheus::{register_int_gauge_with_registry, IntGauge, Registry};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
-use sui_types::crypto::{AuthorityKeyPair, NetworkKeyPair};
+use sui_types::crypto::NetworkKeyPair;
 use tokio::sync::Mutex;
 
 #[derive(PartialEq)]
@@ -28,7 +29,7 @@ enum Running {
 }
 
 pub struct NarwhalConfiguration {
-    pub primary_keypair: AuthorityKeyPair,
+    pub primary_signer: ProtocolSigner,
     pub network_keypair: NetworkKeyPair,
     pub worker_ids_and_keypairs: Vec<(WorkerId, NetworkKeyPair)>,
 
@@ -76,7 +77,7 @@ impl NarwhalManagerMetrics {
 }
 
 pub struct NarwhalManager {
-    primary_keypair: AuthorityKeyPair,
+    primary_signer: ProtocolSigner,
     network_keypair: NetworkKeyPair,
     worker_ids_and_keypairs: Vec<(WorkerId, NetworkKeyPair)>,
     primary_node: PrimaryNode,
@@ -106,7 +107,7 @@ impl NarwhalManager {
         Self {
             primary_node,
             worker_nodes,
-            primary_keypair: config.primary_keypair,
+            primary_signer: config.primary_signer,
             network_keypair: config.network_keypair,
             worker_ids_and_keypairs: config.worker_ids_and_keypairs,
             storage_base_path: config.storage_base_path,
@@ -145,7 +146,7 @@ impl NarwhalManager {
         // Create a new client.
         let network_client = NetworkClient::new_from_keypair(&self.network_keypair);
 
-        let name = self.primary_keypair.public().clone();
+        let name = self.primary_signer.public().clone();
 
         tracing::info!("Starting up Narwhal for epoch {}", committee.epoch());
 
@@ -156,7 +157,7 @@ impl NarwhalManager {
             match self
                 .primary_node
                 .start(
-                    self.primary_keypair.copy(),
+                    self.primary_signer.clone(),
                     self.network_keypair.copy(),
                     committee.clone(),
                     worker_cache.clone(),
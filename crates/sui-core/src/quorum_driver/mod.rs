@@ -5,6 +5,7 @@ mod metrics;
 pub use metrics::*;
 
 pub mod reconfig_observer;
+pub mod validator_selection;
 
 use arc_swap::ArcSwap;
 use std::collections::{BTreeMap, BTreeSet};
@@ -52,6 +53,9 @@ pub struct QuorumDriverTask {
     pub tx_cert: Option<VerifiedCertificate>,
     pub retry_times: u8,
     pub next_retry_after: Instant,
+    /// Span of the request submitting the transaction. Tasks are processed in their own tokio
+    /// task, under this span to keep them in the trace of the request.
+    pub span: tracing::Span,
 }
 
 impl Debug for QuorumDriverTask {
@@ -160,6 +164,7 @@ impl<A> QuorumDriver<A> {
             tx_cert,
             retry_times: old_retry_times + 1,
             next_retry_after,
+            span: tracing::Span::current(),
         })
         .await
     }
@@ -215,6 +220,7 @@ where
             tx_cert: None,
             retry_times: 0,
             next_retry_after: Instant::now(),
+            span: tracing::Span::current(),
         })
         .await?;
         Ok(ticket)
@@ -238,6 +244,7 @@ where
             tx_cert: None,
             retry_times: 0,
             next_retry_after: Instant::now(),
+            span: tracing::Span::current(),
         })
         .await
     }
@@ -757,11 +764,9 @@ where
             quorum_driver.notify(&transaction, &Err(qd_error), old_retry_times + 1);
         } else {
             debug!(?tx_digest, "Failed to {action} - Retrying");
-            spawn_monitored_task!(quorum_driver.enqueue_again_maybe(
-                transaction.clone(),
-                tx_cert,
-                old_retry_times
-            ));
+            spawn_monitored_task!(quorum_driver
+                .enqueue_again_maybe(transaction.clone(), tx_cert, old_retry_times)
+                .in_current_span());
         }
     }
 
@@ -784,7 +789,8 @@ where
             }
             metrics.current_requests_in_flight.dec();
             let qd = quorum_driver.clone();
-            spawn_monitored_task!(QuorumDriverHandler::process_task(qd, task));
+            let span = task.span.clone();
+            spawn_monitored_task!(QuorumDriverHandler::process_task(qd, task).instrument(span));
         }
     }
 }
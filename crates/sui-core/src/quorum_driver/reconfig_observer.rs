@@ -15,6 +15,7 @@ use crate::{
     safe_client::SafeClientMetricsBase,
 };
 
+use super::validator_selection::{StakeWeightedSelection, ValidatorSelectionStrategy};
 use super::QuorumDriver;
 
 #[async_trait]
@@ -31,6 +32,7 @@ pub struct OnsiteReconfigObserver {
     committee_store: Arc<CommitteeStore>,
     safe_client_metrics_base: SafeClientMetricsBase,
     auth_agg_metrics: AuthAggMetrics,
+    validator_selection: Arc<dyn ValidatorSelectionStrategy>,
 }
 
 impl OnsiteReconfigObserver {
@@ -47,9 +49,19 @@ impl OnsiteReconfigObserver {
             committee_store,
             safe_client_metrics_base,
             auth_agg_metrics,
+            validator_selection: Arc::new(StakeWeightedSelection),
         }
     }
 
+    /// The validator selection strategy of the authority aggregators of the new committees.
+    pub fn with_validator_selection(
+        mut self,
+        validator_selection: Arc<dyn ValidatorSelectionStrategy>,
+    ) -> Self {
+        self.validator_selection = validator_selection;
+        self
+    }
+
     async fn create_authority_aggregator_from_system_state(
         &self,
     ) -> AuthorityAggregator<NetworkAuthorityClient> {
@@ -65,6 +77,7 @@ impl OnsiteReconfigObserver {
                 e
             )
         })
+        .with_validator_selection(self.validator_selection.clone())
     }
 }
 
@@ -77,6 +90,7 @@ impl ReconfigObserver<NetworkAuthorityClient> for OnsiteReconfigObserver {
             committee_store: self.committee_store.clone(),
             safe_client_metrics_base: self.safe_client_metrics_base.clone(),
             auth_agg_metrics: self.auth_agg_metrics.clone(),
+            validator_selection: self.validator_selection.clone(),
         })
     }
 
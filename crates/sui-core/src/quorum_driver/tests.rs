@@ -2,14 +2,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::quorum_driver::reconfig_observer::DummyReconfigObserver;
+use crate::quorum_driver::validator_selection::{
+    LatencyAwareSelection, OrderedSelection, PreferredValidatorsSelection,
+    ValidatorSelectionStrategy,
+};
 use crate::quorum_driver::{AuthorityAggregator, QuorumDriverHandlerBuilder};
 use crate::test_authority_clients::LocalAuthorityClient;
 use crate::test_utils::make_transfer_sui_transaction;
 use crate::{quorum_driver::QuorumDriverMetrics, test_utils::init_local_authorities};
 use mysten_common::sync::notify_read::{NotifyRead, Registration};
+use std::collections::BTreeSet;
 use std::sync::Arc;
 use std::time::Duration;
-use sui_types::base_types::SuiAddress;
+use sui_types::base_types::{AuthorityName, SuiAddress};
+use sui_types::committee::Committee;
 use sui_types::crypto::{deterministic_random_account_key, get_key_pair, AccountKeyPair};
 use sui_types::messages::{TransactionEffectsAPI, VerifiedTransaction};
 use sui_types::object::{generate_test_gas_objects, Object};
@@ -427,3 +433,43 @@ async fn test_quorum_driver_object_locked() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[test]
+fn test_validator_selection_strategies() {
+    let (committee, _) = Committee::new_simple_test_committee();
+    let names: Vec<AuthorityName> = committee.names().cloned().collect();
+    let sorted = |order: Vec<AuthorityName>| order.into_iter().collect::<BTreeSet<_>>();
+
+    // Operator-provided order, unknown validators and duplicates are skipped.
+    let (other_committee, _) = Committee::new_simple_test_committee_of_size(1);
+    let unknown = *other_committee.names().next().unwrap();
+    let ordered = OrderedSelection::new(vec![names[2], unknown, names[0], names[2]]);
+    let order = ordered.order_validators(&committee);
+    assert_eq!(order.len(), names.len());
+    assert_eq!(&order[..2], &[names[2], names[0]]);
+    assert_eq!(sorted(order), sorted(names.clone()));
+
+    let preferred = PreferredValidatorsSelection::new([names[1], names[3]].into_iter().collect());
+    let order = preferred.order_validators(&committee);
+    assert_eq!(
+        sorted(order[..2].to_vec()),
+        sorted(vec![names[1], names[3]])
+    );
+    assert_eq!(sorted(order), sorted(names.clone()));
+
+    // Validators without latency observations come last.
+    let latency_aware = LatencyAwareSelection::default();
+    latency_aware.report_latency(&names[3], Duration::from_millis(100));
+    latency_aware.report_latency(&names[1], Duration::from_millis(300));
+    latency_aware.report_latency(&names[2], Duration::from_millis(200));
+    let order = latency_aware.order_validators(&committee);
+    assert_eq!(order, vec![names[3], names[2], names[1], names[0]]);
+
+    // The latency is a moving average of the observations.
+    for _ in 0..10 {
+        latency_aware.report_latency(&names[1], Duration::from_millis(10));
+    }
+    assert!(latency_aware.latency(&names[1]).unwrap() < Duration::from_millis(100));
+    let order = latency_aware.order_validators(&committee);
+    assert_eq!(order, vec![names[1], names[3], names[2], names[0]]);
+}
@@ -0,0 +1,142 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use parking_lot::RwLock;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+use sui_config::node::ValidatorSelectionConfig;
+use sui_types::base_types::AuthorityName;
+use sui_types::committee::Committee;
+
+/// Weight of a new latency observation in the moving average of the latency of a validator.
+const LATENCY_DECAY_FACTOR: f64 = 0.2;
+
+/// Order in which the `AuthorityAggregator` of the quorum driver contacts the validators of the
+/// committee. Requests sent to one validator at a time go to the first validators of the order,
+/// and requests broadcast to all validators are sent in this order.
+pub trait ValidatorSelectionStrategy: Send + Sync + Debug {
+    /// Orders the validators of `committee`. The order must contain each validator of the
+    /// committee exactly once.
+    fn order_validators(&self, committee: &Committee) -> Vec<AuthorityName>;
+
+    /// Called with the latency of each successful request to a validator.
+    fn report_latency(&self, _name: &AuthorityName, _latency: Duration) {}
+}
+
+pub fn validator_selection_from_config(
+    config: &ValidatorSelectionConfig,
+) -> Arc<dyn ValidatorSelectionStrategy> {
+    match config {
+        ValidatorSelectionConfig::StakeWeighted => Arc::new(StakeWeightedSelection),
+        ValidatorSelectionConfig::LatencyAware => Arc::new(LatencyAwareSelection::default()),
+        ValidatorSelectionConfig::Preferred { validators } => Arc::new(
+            PreferredValidatorsSelection::new(validators.iter().cloned().collect()),
+        ),
+        ValidatorSelectionConfig::Ordered { validators } => {
+            Arc::new(OrderedSelection::new(validators.clone()))
+        }
+    }
+}
+
+/// Random order weighted by stake, so that the load is spread across the validators in
+/// proportion to their stake.
+#[derive(Debug, Default)]
+pub struct StakeWeightedSelection;
+
+impl ValidatorSelectionStrategy for StakeWeightedSelection {
+    fn order_validators(&self, committee: &Committee) -> Vec<AuthorityName> {
+        committee.shuffle_by_stake(None, None)
+    }
+}
+
+/// Validators with the lowest moving average of the latency first. Validators without latency
+/// observations, e.g. validators which joined the committee at this epoch, come last in random
+/// order weighted by stake.
+#[derive(Debug, Default)]
+pub struct LatencyAwareSelection {
+    latencies: RwLock<HashMap<AuthorityName, Duration>>,
+}
+
+impl LatencyAwareSelection {
+    pub fn latency(&self, name: &AuthorityName) -> Option<Duration> {
+        self.latencies.read().get(name).copied()
+    }
+}
+
+impl ValidatorSelectionStrategy for LatencyAwareSelection {
+    fn order_validators(&self, committee: &Committee) -> Vec<AuthorityName> {
+        let latencies = self.latencies.read();
+        let (mut measured, unmeasured): (Vec<_>, Vec<_>) = committee
+            .shuffle_by_stake(None, None)
+            .into_iter()
+            .partition(|name| latencies.contains_key(name));
+        measured.sort_by_key(|name| latencies[name]);
+        measured.extend(unmeasured);
+        measured
+    }
+
+    fn report_latency(&self, name: &AuthorityName, latency: Duration) {
+        self.latencies
+            .write()
+            .entry(*name)
+            .and_modify(|average| {
+                *average = average.mul_f64(1.0 - LATENCY_DECAY_FACTOR)
+                    + latency.mul_f64(LATENCY_DECAY_FACTOR)
+            })
+            .or_insert(latency);
+    }
+}
+
+/// The preferred validators first, e.g. the validators in the region of the fullnode, then the
+/// other validators, each group in random order weighted by stake.
+#[derive(Debug)]
+pub struct PreferredValidatorsSelection {
+    preferred: BTreeSet<AuthorityName>,
+}
+
+impl PreferredValidatorsSelection {
+    pub fn new(preferred: BTreeSet<AuthorityName>) -> Self {
+        Self { preferred }
+    }
+}
+
+impl ValidatorSelectionStrategy for PreferredValidatorsSelection {
+    fn order_validators(&self, committee: &Committee) -> Vec<AuthorityName> {
+        committee.shuffle_by_stake(Some(&self.preferred), None)
+    }
+}
+
+/// The validators in the order given by the operator, then the validators missing from the
+/// order in random order weighted by stake. Validators of the order which are not in the
+/// committee are skipped.
+#[derive(Debug)]
+pub struct OrderedSelection {
+    order: Vec<AuthorityName>,
+}
+
+impl OrderedSelection {
+    pub fn new(order: Vec<AuthorityName>) -> Self {
+        Self { order }
+    }
+}
+
+impl ValidatorSelectionStrategy for OrderedSelection {
+    fn order_validators(&self, committee: &Committee) -> Vec<AuthorityName> {
+        let mut ordered = Vec::with_capacity(committee.num_members());
+        let mut seen = BTreeSet::new();
+        for name in &self.order {
+            if committee.authority_exists(name) && seen.insert(*name) {
+                ordered.push(*name);
+            }
+        }
+        ordered.extend(
+            committee
+                .shuffle_by_stake(None, None)
+                .into_iter()
+                .filter(|name| !seen.contains(name)),
+        );
+        ordered
+    }
+}
@@ -17,14 +17,17 @@ use std::sync::Arc;
 
 use fastcrypto::hash::MultisetHash;
 use sui_types::accumulator::Accumulator;
-use sui_types::error::SuiResult;
+use sui_types::error::{SuiError, SuiResult};
 use sui_types::messages::{TransactionEffects, TransactionEffectsAPI};
-use sui_types::messages_checkpoint::{CheckpointSequenceNumber, ECMHLiveObjectSetDigest};
+use sui_types::messages_checkpoint::{
+    CheckpointCommitment, CheckpointSequenceNumber, ECMHLiveObjectSetDigest,
+};
 use typed_store::rocks::TypedStoreError;
 
 use crate::authority::authority_per_epoch_store::AuthorityPerEpochStore;
-use crate::authority::authority_store_tables::LiveObject;
+use crate::authority::authority_store_tables::{AuthorityPerpetualTables, LiveObject};
 use crate::authority::AuthorityStore;
+use crate::checkpoints::CheckpointStore;
 
 pub struct StateAccumulator {
     authority_store: Arc<AuthorityStore>,
@@ -336,3 +339,227 @@ impl StateAccumulator {
             .into())
     }
 }
+
+/// State of an object in the live object set, as written by the last effects touching it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LiveObjectEntry {
+    Normal(SequenceNumber, ObjectDigest),
+    Wrapped(SequenceNumber),
+}
+
+/// The live object set replayed from a stream of effects, independently of the objects table
+/// and of the accumulators persisted by the node. Replaying the effects of all the checkpoints
+/// since genesis yields the live object set committed to by the last checkpoint of an epoch.
+#[derive(Debug, Default)]
+pub struct LiveObjectSetReplay {
+    objects: HashMap<ObjectID, LiveObjectEntry>,
+}
+
+impl LiveObjectSetReplay {
+    /// Applies the effects of one transaction. Effects must be applied in causal order.
+    pub fn apply_effects(&mut self, effects: &TransactionEffects) {
+        for (id, _) in effects.modified_at_versions() {
+            self.objects.remove(id);
+        }
+        for ((id, version, digest), _) in effects
+            .created()
+            .iter()
+            .chain(effects.mutated())
+            .chain(effects.unwrapped())
+        {
+            self.objects
+                .insert(*id, LiveObjectEntry::Normal(*version, *digest));
+        }
+        for (id, version, _) in effects.wrapped() {
+            self.objects.insert(*id, LiveObjectEntry::Wrapped(*version));
+        }
+        for (id, _, _) in effects
+            .deleted()
+            .iter()
+            .chain(effects.unwrapped_then_deleted())
+        {
+            self.objects.remove(id);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// The versions and digests of the objects of the set which are not wrapped.
+    pub fn objects(&self) -> impl Iterator<Item = (ObjectID, SequenceNumber, ObjectDigest)> + '_ {
+        self.objects.iter().filter_map(|(id, entry)| match entry {
+            LiveObjectEntry::Normal(version, digest) => Some((*id, *version, *digest)),
+            LiveObjectEntry::Wrapped(_) => None,
+        })
+    }
+
+    /// The accumulator of the set, as computed by
+    /// [StateAccumulator::accumulate_live_object_set].
+    pub fn accumulate(&self) -> Accumulator {
+        let mut acc = Accumulator::default();
+        for (id, entry) in &self.objects {
+            match entry {
+                LiveObjectEntry::Normal(_, digest) => acc.insert(*digest),
+                LiveObjectEntry::Wrapped(version) => acc.insert(
+                    bcs::to_bytes(&WrappedObject::new(*id, *version))
+                        .expect("Failed to serialize WrappedObject"),
+                ),
+            }
+        }
+        acc
+    }
+}
+
+impl FromIterator<LiveObject> for LiveObjectSetReplay {
+    fn from_iter<T: IntoIterator<Item = LiveObject>>(iter: T) -> Self {
+        let objects = iter
+            .into_iter()
+            .map(|live_object| match live_object {
+                LiveObject::Normal(object) => {
+                    let (id, version, digest) = object.compute_object_reference();
+                    (id, LiveObjectEntry::Normal(version, digest))
+                }
+                LiveObject::Wrapped(key) => (key.0, LiveObjectEntry::Wrapped(key.1)),
+            })
+            .collect();
+        Self { objects }
+    }
+}
+
+/// An object version of the replayed live object set whose stored contents do not have the
+/// digest recorded by the effects which wrote it.
+#[derive(Debug)]
+pub struct ObjectDigestMismatch {
+    pub object_id: ObjectID,
+    pub version: SequenceNumber,
+    /// The digest recorded by the effects.
+    pub expected: ObjectDigest,
+    /// The digest of the stored object, `None` if the object version is not stored, e.g. after
+    /// it was pruned.
+    pub actual: Option<ObjectDigest>,
+}
+
+#[derive(Debug)]
+pub struct EpochAccumulatorAudit {
+    pub epoch: EpochId,
+    pub last_checkpoint: CheckpointSequenceNumber,
+    pub live_objects: usize,
+    /// Digest of the live object set replayed from the effects of the checkpoints.
+    pub replayed: ECMHLiveObjectSetDigest,
+    /// Commitment of the last checkpoint of the epoch, `None` if the protocol version of the
+    /// epoch did not commit to the root state digest.
+    pub committed: Option<ECMHLiveObjectSetDigest>,
+    /// Root state digest accumulated by the node for the epoch, if any.
+    pub local: Option<ECMHLiveObjectSetDigest>,
+    pub mismatches: Vec<ObjectDigestMismatch>,
+}
+
+impl EpochAccumulatorAudit {
+    pub fn is_consistent(&self) -> bool {
+        self.mismatches.is_empty()
+            && self
+                .committed
+                .as_ref()
+                .map_or(true, |c| c == &self.replayed)
+            && self.local.as_ref().map_or(true, |l| l == &self.replayed)
+    }
+}
+
+/// Recomputes the state accumulator at the end of `epoch` by replaying the effects of all the
+/// checkpoints since genesis, and compares it with the commitment of the last checkpoint of the
+/// epoch and with the root state hash of the node. The digests of the stored versions of the
+/// replayed live objects are checked against the ones recorded by the effects, to locate the
+/// objects causing a mismatch. Requires the effects of all the checkpoints, i.e. a node which
+/// did not prune transactions.
+pub fn audit_epoch_accumulator(
+    perpetual_tables: &AuthorityPerpetualTables,
+    checkpoint_store: &CheckpointStore,
+    epoch: EpochId,
+) -> SuiResult<EpochAccumulatorAudit> {
+    let last_checkpoint = checkpoint_store
+        .get_epoch_last_checkpoint(epoch)?
+        .ok_or_else(|| {
+            SuiError::StorageMissingFieldError(format!(
+                "Last checkpoint of epoch {epoch} is not known"
+            ))
+        })?;
+
+    let mut live_set = LiveObjectSetReplay::default();
+    for sequence_number in 0..=*last_checkpoint.sequence_number() {
+        let checkpoint = checkpoint_store
+            .get_checkpoint_by_sequence_number(sequence_number)?
+            .ok_or_else(|| {
+                SuiError::StorageMissingFieldError(format!(
+                    "Checkpoint {sequence_number} is missing"
+                ))
+            })?;
+        let contents = checkpoint_store
+            .get_checkpoint_contents(&checkpoint.content_digest)?
+            .ok_or_else(|| {
+                SuiError::StorageMissingFieldError(format!(
+                    "Contents of checkpoint {sequence_number} are missing"
+                ))
+            })?;
+        let digests: Vec<_> = contents.iter().map(|digests| digests.effects).collect();
+        for (digest, effects) in digests
+            .iter()
+            .zip(perpetual_tables.effects.multi_get(&digests)?)
+        {
+            let effects = effects.ok_or_else(|| {
+                SuiError::StorageMissingFieldError(format!(
+                    "Effects {digest} of checkpoint {sequence_number} are missing"
+                ))
+            })?;
+            live_set.apply_effects(&effects);
+        }
+    }
+
+    let committed = last_checkpoint.end_of_epoch_data.as_ref().and_then(|data| {
+        data.epoch_commitments
+            .iter()
+            .find_map(|commitment| match commitment {
+                CheckpointCommitment::ECMHLiveObjectSetDigest(digest) => Some(digest.clone()),
+            })
+    });
+    let local = perpetual_tables
+        .root_state_hash_by_epoch
+        .get(&epoch)?
+        .map(|(_, acc)| acc.digest().into());
+
+    let mut mismatches = vec![];
+    for (object_id, version, expected) in live_set.objects() {
+        let actual = match perpetual_tables
+            .objects
+            .get(&ObjectKey(object_id, version))?
+        {
+            Some(stored) => perpetual_tables
+                .object(stored)?
+                .map(|object| object.compute_object_reference().2),
+            None => None,
+        };
+        if actual != Some(expected) {
+            mismatches.push(ObjectDigestMismatch {
+                object_id,
+                version,
+                expected,
+                actual,
+            });
+        }
+    }
+    mismatches.sort_by_key(|mismatch| (mismatch.object_id, mismatch.version));
+
+    Ok(EpochAccumulatorAudit {
+        epoch,
+        last_checkpoint: *last_checkpoint.sequence_number(),
+        live_objects: live_set.len(),
+        replayed: live_set.accumulate().digest().into(),
+        committed,
+        local,
+        mismatches,
+    })
+}
@@ -77,6 +77,16 @@ where
         ReceiverStream::new(rx)
     }
 
+    /// The filters of the current subscribers.
+    pub async fn subscribed_filters(&self) -> Vec<F> {
+        self.subscribers
+            .read()
+            .await
+            .values()
+            .map(|(_, filter)| filter.clone())
+            .collect()
+    }
+
     pub async fn send(&self, data: T) -> Result<(), SuiError> {
         self.streamer_queue
             .send(data)
@@ -552,6 +552,11 @@ impl TransactionManager {
         inner.pending_certificates.len() + inner.executing_certificates.len()
     }
 
+    // Returns the number of certificates being executed by the execution driver right now.
+    pub fn executing_certificates_len(&self) -> usize {
+        self.inner.read().executing_certificates.len()
+    }
+
     // Reconfigures the TransactionManager for a new epoch. Existing transactions will be dropped
     // because they are no longer relevant and may be incorrect in the new epoch.
     pub(crate) fn reconfigure(&self, new_epoch: EpochId) {
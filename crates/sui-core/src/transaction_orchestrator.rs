@@ -10,8 +10,10 @@ use crate::authority::AuthorityState;
 use crate::authority_aggregator::{AuthAggMetrics, AuthorityAggregator};
 use crate::authority_client::{AuthorityAPI, NetworkAuthorityClient};
 use crate::quorum_driver::reconfig_observer::{OnsiteReconfigObserver, ReconfigObserver};
+use crate::quorum_driver::validator_selection::validator_selection_from_config;
 use crate::quorum_driver::{QuorumDriverHandler, QuorumDriverHandlerBuilder, QuorumDriverMetrics};
 use crate::safe_client::SafeClientMetricsBase;
+use crate::transaction_policy::TransactionPolicy;
 use mysten_common::sync::notify_read::{NotifyRead, Registration};
 use mysten_metrics::histogram::{Histogram, HistogramTimerGuard, HistogramVec};
 use mysten_metrics::spawn_monitored_task;
@@ -23,6 +25,7 @@ use prometheus::{
 use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
+use sui_config::node::{TransactionPolicyConfig, ValidatorSelectionConfig};
 use sui_storage::write_path_pending_tx_log::WritePathPendingTransactionLog;
 use sui_types::base_types::TransactionDigest;
 use sui_types::error::{SuiError, SuiResult};
@@ -56,6 +59,7 @@ pub struct TransactiondOrchestrator<A> {
     pending_tx_log: Arc<WritePathPendingTransactionLog>,
     notifier: Arc<NotifyRead<TransactionDigest, QuorumDriverResult>>,
     metrics: Arc<TransactionOrchestratorMetrics>,
+    transaction_policy: TransactionPolicy,
 }
 
 impl TransactiondOrchestrator<NetworkAuthorityClient> {
@@ -64,15 +68,19 @@ impl TransactiondOrchestrator<NetworkAuthorityClient> {
         reconfig_channel: Receiver<SuiSystemState>,
         parent_path: &Path,
         prometheus_registry: &Registry,
+        validator_selection: &ValidatorSelectionConfig,
+        transaction_policy: &TransactionPolicyConfig,
     ) -> anyhow::Result<Self> {
         let safe_client_metrics_base = SafeClientMetricsBase::new(prometheus_registry);
         let auth_agg_metrics = AuthAggMetrics::new(prometheus_registry);
+        let validator_selection = validator_selection_from_config(validator_selection);
         let validators = AuthorityAggregator::new_from_local_system_state(
             &validator_state.db(),
             validator_state.committee_store(),
             safe_client_metrics_base.clone(),
             auth_agg_metrics.clone(),
-        )?;
+        )?
+        .with_validator_selection(validator_selection.clone());
 
         let observer = OnsiteReconfigObserver::new(
             reconfig_channel,
@@ -80,13 +88,15 @@ impl TransactiondOrchestrator<NetworkAuthorityClient> {
             validator_state.clone_committee_store(),
             safe_client_metrics_base,
             auth_agg_metrics,
-        );
+        )
+        .with_validator_selection(validator_selection);
         Ok(TransactiondOrchestrator::new(
             Arc::new(validators),
             validator_state,
             parent_path,
             prometheus_registry,
             observer,
+            TransactionPolicy::new(transaction_policy.clone(), prometheus_registry),
         )
         .await)
     }
@@ -103,6 +113,7 @@ where
         parent_path: &Path,
         prometheus_registry: &Registry,
         reconfig_observer: OnsiteReconfigObserver,
+        transaction_policy: TransactionPolicy,
     ) -> Self {
         let notifier = Arc::new(NotifyRead::new());
         let quorum_driver_handler = Arc::new(
@@ -142,6 +153,7 @@ where
             pending_tx_log,
             notifier,
             metrics,
+            transaction_policy,
         }
     }
 
@@ -183,6 +195,7 @@ where
             .transaction
             .verify()
             .map_err(QuorumDriverError::InvalidUserSignature)?;
+        self.transaction_policy.check(&transaction)?;
         let (_in_flight_metrics_guards, good_response_metrics) = self.update_metrics(&transaction);
         let tx_digest = *transaction.digest();
         debug!(?tx_digest, "TO Received transaction execution request.");
@@ -382,6 +395,10 @@ where
         }
     }
 
+    pub fn transaction_policy(&self) -> &TransactionPolicy {
+        &self.transaction_policy
+    }
+
     pub fn quorum_driver(&self) -> &Arc<QuorumDriverHandler<A>> {
         &self.quorum_driver_handler
     }
@@ -0,0 +1,113 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rules of a fullnode refusing some of the transactions submitted to it, configured by its
+//! operator with [TransactionPolicyConfig] and reloadable while the node is running.
+
+use arc_swap::ArcSwap;
+use prometheus::{register_int_counter_vec_with_registry, IntCounterVec, Registry};
+use std::sync::Arc;
+use sui_config::node::TransactionPolicyConfig;
+use sui_types::messages::{TransactionData, TransactionDataAPI, VerifiedTransaction};
+use sui_types::quorum_driver_types::QuorumDriverError;
+use tracing::{info, warn};
+
+pub struct TransactionPolicy {
+    config: ArcSwap<TransactionPolicyConfig>,
+    metrics: TransactionPolicyMetrics,
+}
+
+impl TransactionPolicy {
+    pub fn new(config: TransactionPolicyConfig, registry: &Registry) -> Self {
+        Self {
+            config: ArcSwap::from_pointee(config),
+            metrics: TransactionPolicyMetrics::new(registry),
+        }
+    }
+
+    pub fn config(&self) -> Arc<TransactionPolicyConfig> {
+        self.config.load_full()
+    }
+
+    /// Applies `config` to the transactions submitted from now on.
+    pub fn update_config(&self, config: TransactionPolicyConfig) {
+        info!(?config, "Updating transaction policy");
+        self.config.store(Arc::new(config));
+    }
+
+    /// Returns an error if a rule of the policy refuses `transaction`.
+    pub fn check(&self, transaction: &VerifiedTransaction) -> Result<(), QuorumDriverError> {
+        let config = self.config.load();
+        let data = transaction.data().transaction_data();
+        let Some((rule, reason)) = violated_rule(&config, data) else {
+            return Ok(());
+        };
+        warn!(
+            tx_digest = ?transaction.digest(),
+            sender = ?data.sender(),
+            rule,
+            "Transaction refused by policy: {reason}"
+        );
+        self.metrics
+            .refused_transactions
+            .with_label_values(&[rule])
+            .inc();
+        Err(QuorumDriverError::TransactionDeniedByPolicy { reason })
+    }
+}
+
+/// Returns the name of the first rule of `config` refusing the transaction of `data`, and why.
+fn violated_rule(
+    config: &TransactionPolicyConfig,
+    data: &TransactionData,
+) -> Option<(&'static str, String)> {
+    let sender = data.sender();
+    if config.deny_senders.contains(&sender) {
+        return Some(("deny_senders", format!("sender {sender} is denied")));
+    }
+    if let Some(allow_senders) = &config.allow_senders {
+        if !allow_senders.contains(&sender) {
+            return Some(("allow_senders", format!("sender {sender} is not allowed")));
+        }
+    }
+    for (package, module, function) in data.move_calls() {
+        let call = format!("{}::{module}::{function}", package.to_hex_literal());
+        if config
+            .deny_move_calls
+            .iter()
+            .any(|pattern| pattern.matches(package, module, function))
+        {
+            return Some(("deny_move_calls", format!("calls to {call} are denied")));
+        }
+        if let Some(allow_move_calls) = &config.allow_move_calls {
+            if !allow_move_calls
+                .iter()
+                .any(|pattern| pattern.matches(package, module, function))
+            {
+                return Some((
+                    "allow_move_calls",
+                    format!("calls to {call} are not allowed"),
+                ));
+            }
+        }
+    }
+    None
+}
+
+struct TransactionPolicyMetrics {
+    refused_transactions: IntCounterVec,
+}
+
+impl TransactionPolicyMetrics {
+    fn new(registry: &Registry) -> Self {
+        Self {
+            refused_transactions: register_int_counter_vec_with_registry!(
+                "tx_policy_refused_transactions",
+                "Number of transactions submitted to this fullnode refused by its transaction policy, by rule",
+                &["rule"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
@@ -55,7 +55,7 @@ use sui_types::{SUI_CLOCK_OBJECT_ID, SUI_CLOCK_OBJECT_SHARED_VERSION};
 use crate::authority::move_integration_tests::build_and_publish_test_package_with_upgrade_cap;
 use crate::consensus_handler::SequencedConsensusTransaction;
 use crate::epoch::epoch_metrics::EpochMetrics;
-use crate::state_accumulator::StateAccumulator;
+use crate::state_accumulator::{LiveObjectSetReplay, StateAccumulator};
 use crate::{
     authority_client::{AuthorityAPI, NetworkAuthorityClient},
     authority_server::AuthorityServer,
@@ -569,7 +569,7 @@ async fn test_dev_inspect_dynamic_field() {
     };
     let kind = TransactionKind::programmable(pt);
     let DevInspectResults { error, .. } = fullnode
-        .dev_inspect_transaction_block(sender, kind, Some(1))
+        .dev_inspect_transaction_block(sender, kind, Some(1), None)
         .await
         .unwrap();
     // produces an error
@@ -811,7 +811,7 @@ async fn test_dev_inspect_gas_coin_argument() {
     };
     let kind = TransactionKind::programmable(pt);
     let results = fullnode
-        .dev_inspect_transaction_block(sender, kind, Some(1))
+        .dev_inspect_transaction_block(sender, kind, Some(1), None)
         .await
         .unwrap()
         .results
@@ -873,7 +873,7 @@ async fn test_dev_inspect_uses_unbound_object() {
     let kind = TransactionKind::programmable(pt);
 
     let result = fullnode
-        .dev_inspect_transaction_block(sender, kind, Some(1))
+        .dev_inspect_transaction_block(sender, kind, Some(1), None)
         .await;
     let Err(err) = result else { panic!() };
     assert!(err.to_string().contains("ObjectNotFound"));
@@ -1013,7 +1013,7 @@ async fn test_dry_run_dev_inspect_dynamic_field_too_new() {
     let kind = TransactionKind::programmable(pt.clone());
     // dev inspect
     let DevInspectResults { effects, .. } = fullnode
-        .dev_inspect_transaction_block(sender, kind, Some(1))
+        .dev_inspect_transaction_block(sender, kind, Some(1), None)
         .await
         .unwrap();
     assert_eq!(effects.deleted().len(), 1);
@@ -1071,7 +1071,7 @@ async fn test_dry_run_dev_inspect_max_gas_version() {
     let kind = TransactionKind::programmable(pt.clone());
     // dev inspect
     let DevInspectResults { effects, .. } = fullnode
-        .dev_inspect_transaction_block(sender, kind, Some(1))
+        .dev_inspect_transaction_block(sender, kind, Some(1), None)
         .await
         .unwrap();
     assert_eq!(effects.status(), &SuiExecutionStatus::Success);
@@ -1669,9 +1669,10 @@ pub async fn send_and_confirm_transaction_(
     // we unfortunately don't get a very descriptive error message, but we can at least see that something went wrong inside the VM
     //
     // We also check the incremental effects of the transaction on the live object set against StateAccumulator
-    // for testing and regression detection
+    // for testing and regression detection, and against the live object set replayed from the effects
     let state_acc = StateAccumulator::new(authority.database.clone());
     let mut state = state_acc.accumulate_live_object_set();
+    let mut replay: LiveObjectSetReplay = authority.database.iter_live_object_set().collect();
     let result = authority.try_execute_for_test(&certificate).await?;
     let state_after = state_acc.accumulate_live_object_set();
     let effects_acc = state_acc.accumulate_effects(
@@ -1679,8 +1680,10 @@ pub async fn send_and_confirm_transaction_(
         epoch_store.protocol_config(),
     );
     state.union(&effects_acc);
+    replay.apply_effects(result.inner().data());
 
     assert_eq!(state_after.digest(), state.digest());
+    assert_eq!(state_after.digest(), replay.accumulate().digest());
 
     if let Some(fullnode) = fullnode {
         fullnode.try_execute_for_test(&certificate).await?;
@@ -2071,6 +2074,77 @@ async fn test_handle_transfer_transaction_double_spend() {
     assert_eq!(signed_transaction, double_spend_signed_transaction);
 }
 
+#[tokio::test]
+async fn test_object_lock_status() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    let epoch_store = authority_state.load_epoch_store_one_call_per_task();
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let transfer_transaction = init_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+    );
+
+    assert_eq!(
+        authority_state
+            .get_object_lock_status(object_id, object.version())
+            .unwrap(),
+        SuiObjectLockStatus::Unlocked
+    );
+
+    authority_state
+        .handle_transaction(&epoch_store, transfer_transaction.clone())
+        .await
+        .unwrap();
+    let locked = SuiObjectLockStatus::Locked {
+        transaction_digest: *transfer_transaction.digest(),
+        epoch: epoch_store.epoch(),
+    };
+    assert_eq!(
+        authority_state
+            .get_object_lock_status(object_id, object.version())
+            .unwrap(),
+        locked
+    );
+    assert_eq!(
+        authority_state
+            .get_object_lock_status(gas_object_id, gas_object.version())
+            .unwrap(),
+        locked
+    );
+
+    send_and_confirm_transaction(&authority_state, transfer_transaction)
+        .await
+        .unwrap();
+    let SuiObjectLockStatus::Consumed { latest_version } = authority_state
+        .get_object_lock_status(object_id, object.version())
+        .unwrap() else {
+        panic!("The object version should be consumed");
+    };
+    assert!(latest_version > object.version());
+
+    // Versions which never existed have no lock.
+    assert!(authority_state
+        .get_object_lock_status(object_id, SequenceNumber::MAX)
+        .is_err());
+}
+
 #[tokio::test]
 async fn test_handle_transfer_sui_with_amount_insufficient_gas() {
     let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
@@ -4172,12 +4246,13 @@ fn init_certified_transaction(
     transaction: VerifiedTransaction,
     authority_state: &AuthorityState,
 ) -> VerifiedCertificate {
-    let vote = VerifiedSignedTransaction::new(
+    let vote = VerifiedSignedTransaction::try_new(
         0,
         transaction.clone(),
         authority_state.name,
         &*authority_state.secret,
-    );
+    )
+    .unwrap();
     let epoch_store = authority_state.epoch_store_for_testing();
     CertifiedTransaction::new(
         transaction.into_message(),
@@ -4533,7 +4608,7 @@ pub async fn call_dev_inspect(
     ));
     let kind = TransactionKind::programmable(builder.finish());
     authority
-        .dev_inspect_transaction_block(*sender, kind, Some(1))
+        .dev_inspect_transaction_block(*sender, kind, Some(1), None)
         .await
 }
 
@@ -5099,7 +5174,7 @@ async fn test_for_inc_201_dev_inspect() {
     ));
     let kind = TransactionKind::programmable(builder.finish());
     let DevInspectResults { events, .. } = fullnode
-        .dev_inspect_transaction_block(sender, kind, Some(1))
+        .dev_inspect_transaction_block(sender, kind, Some(1), None)
         .await
         .unwrap();
 
@@ -5111,6 +5186,50 @@ async fn test_for_inc_201_dev_inspect() {
     assert_eq!(json!({"foo":"bar"}), events.data[0].parsed_json);
 }
 
+#[tokio::test]
+async fn test_dev_inspect_clock_timestamp_override() {
+    let (sender, _sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (_, fullnode, _) =
+        init_state_with_ids_and_object_basics_with_fullnode(vec![(sender, gas_object_id)]).await;
+
+    let timestamp_ms = |clock_timestamp_ms| {
+        let fullnode = fullnode.clone();
+        async move {
+            let mut builder = ProgrammableTransactionBuilder::new();
+            builder
+                .move_call(
+                    SUI_FRAMEWORK_OBJECT_ID,
+                    ident_str!("clock").to_owned(),
+                    ident_str!("timestamp_ms").to_owned(),
+                    vec![],
+                    vec![CallArg::Object(ObjectArg::SharedObject {
+                        id: SUI_CLOCK_OBJECT_ID,
+                        initial_shared_version: SUI_CLOCK_OBJECT_SHARED_VERSION,
+                        mutable: false,
+                    })],
+                )
+                .unwrap();
+            let kind = TransactionKind::programmable(builder.finish());
+            let DevInspectResults { results, .. } = fullnode
+                .dev_inspect_transaction_block(sender, kind, Some(1), clock_timestamp_ms)
+                .await
+                .unwrap();
+            let (bytes, _) = &results.unwrap()[0].return_values[0];
+            bcs::from_bytes::<u64>(bytes).unwrap()
+        }
+    };
+
+    // The clock of the genesis is at 0, unless overridden.
+    assert_eq!(timestamp_ms(None).await, 0);
+    assert_eq!(
+        timestamp_ms(Some(1_700_000_000_000)).await,
+        1_700_000_000_000
+    );
+    // The override only applies to the call.
+    assert_eq!(timestamp_ms(None).await, 0);
+}
+
 #[tokio::test]
 async fn test_for_inc_201_dry_run() {
     use sui_framework_build::compiled_package::BuildConfig;
@@ -0,0 +1,227 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use shared_crypto::intent::IntentMessage;
+use sui_network::api::RemoteSignerServer;
+use sui_types::crypto::{get_key_pair, AccountKeyPair};
+
+fn local_signer() -> LocalValidatorSigner {
+    let (_, protocol_key_pair): (_, AuthorityKeyPair) = get_key_pair();
+    let (_, account_key_pair): (_, AccountKeyPair) = get_key_pair();
+    let (_, network_key_pair): (_, NetworkKeyPair) = get_key_pair();
+    LocalValidatorSigner::new(
+        protocol_key_pair,
+        SuiKeyPair::Ed25519(account_key_pair),
+        network_key_pair,
+    )
+}
+
+/// A signer whose keys are unavailable.
+struct FailingSigner(LocalValidatorSigner);
+
+impl ValidatorSigner for FailingSigner {
+    fn protocol_public_key(&self) -> AuthorityPublicKeyBytes {
+        self.0.protocol_public_key()
+    }
+
+    fn account_public_key(&self) -> PublicKey {
+        self.0.account_public_key()
+    }
+
+    fn network_public_key(&self) -> NetworkPublicKey {
+        self.0.network_public_key()
+    }
+
+    fn sign_protocol(&self, _msg: &[u8]) -> Result<AuthoritySignature, signature::Error> {
+        Err(signature::Error::new())
+    }
+
+    fn sign_account(&self, _msg: &[u8]) -> Result<Signature, signature::Error> {
+        Err(signature::Error::new())
+    }
+
+    fn sign_network(&self, _msg: &[u8]) -> Result<Ed25519Signature, signature::Error> {
+        Err(signature::Error::new())
+    }
+}
+
+/// The bytes of `intent` messages signed by validators.
+fn intent_message(intent: Intent) -> Vec<u8> {
+    bcs::to_bytes(&IntentMessage::new(intent, "hello")).unwrap()
+}
+
+/// Starts a signing service for `signer`, allowing `client_key`, and returns its address.
+async fn serve_remote_signer(
+    signer: Arc<dyn ValidatorSigner>,
+    client_key: &NetworkKeyPair,
+) -> Multiaddr {
+    let service = RemoteSignerService::new(signer, [client_key.public().clone()]);
+    let mut server = mysten_network::config::Config::new()
+        .server_builder()
+        .add_service(RemoteSignerServer::new(service))
+        .bind(&"/ip4/127.0.0.1/tcp/0/http".parse().unwrap())
+        .await
+        .unwrap();
+    let address = server.local_addr().to_owned();
+    tokio::spawn(server.serve());
+    address
+}
+
+async fn start_remote_signer(signer: Arc<dyn ValidatorSigner>) -> RemoteValidatorSigner {
+    let (_, client_key): (_, NetworkKeyPair) = get_key_pair();
+    let protocol_public_key = signer.protocol_public_key();
+    let address = serve_remote_signer(signer, &client_key).await;
+    RemoteValidatorSigner::connect(&address, client_key, protocol_public_key)
+        .await
+        .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_remote_signer_matches_local_signer() {
+    let local = Arc::new(local_signer());
+    let remote = start_remote_signer(local.clone()).await;
+    assert_eq!(remote.protocol_public_key(), local.protocol_public_key());
+    assert_eq!(remote.account_public_key(), local.account_public_key());
+    assert_eq!(remote.network_public_key(), local.network_public_key());
+
+    // All schemes sign deterministically, so remote signatures match local ones.
+    let msg = &intent_message(Intent::sui_app(IntentScope::CheckpointSummary));
+    assert_eq!(
+        remote.sign_protocol(msg).unwrap(),
+        local.sign_protocol(msg).unwrap()
+    );
+    let transaction = &intent_message(Intent::sui_transaction());
+    assert_eq!(
+        remote.sign_account(transaction).unwrap(),
+        local.sign_account(transaction).unwrap()
+    );
+
+    let remote = Arc::new(remote);
+    let signer = authority_signer(remote.clone());
+    assert_eq!(
+        signer.try_sign(msg).unwrap(),
+        local.sign_protocol(msg).unwrap()
+    );
+    let header = &intent_message(Intent::narwhal_app(IntentScope::HeaderDigest));
+    let signer = consensus_signer(remote).unwrap();
+    assert_eq!(signer.public(), local.protocol_key_pair.public());
+    assert_eq!(signer.sign(header), local.sign_protocol(header).unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_remote_signer_only_signs_allowed_intents() {
+    let remote = start_remote_signer(Arc::new(local_signer())).await;
+    // The protocol key does not sign transactions, nor arbitrary bytes.
+    assert!(remote
+        .sign_protocol(&intent_message(Intent::sui_transaction()))
+        .is_err());
+    assert!(remote.sign_protocol(b"hello").is_err());
+    assert!(remote
+        .sign_protocol(&intent_message(Intent::sui_app(
+            IntentScope::ProofOfPossession
+        )))
+        .is_err());
+    // The account key does not sign checkpoints, and the network key signs no message.
+    assert!(remote
+        .sign_account(&intent_message(Intent::sui_app(
+            IntentScope::CheckpointSummary
+        )))
+        .is_err());
+    assert!(remote
+        .sign_network(&intent_message(Intent::sui_transaction()))
+        .is_err());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_remote_signer_authenticates_clients() {
+    let local: Arc<dyn ValidatorSigner> = Arc::new(local_signer());
+    let protocol_public_key = local.protocol_public_key();
+    let (_, allowed_key): (_, NetworkKeyPair) = get_key_pair();
+    let address = serve_remote_signer(local.clone(), &allowed_key).await;
+    let msg = &intent_message(Intent::sui_app(IntentScope::TransactionEffects));
+
+    // Requests of a client that is not allowed are refused.
+    let (_, other_key): (_, NetworkKeyPair) = get_key_pair();
+    let remote = RemoteValidatorSigner::connect(&address, other_key, protocol_public_key)
+        .await
+        .unwrap();
+    assert!(remote.sign_protocol(msg).is_err());
+
+    // Requests not signed by the client key are refused.
+    let (_, forger_key): (_, NetworkKeyPair) = get_key_pair();
+    let forged_signature: Ed25519Signature = forger_key.sign(msg);
+    let request = SignRequest {
+        key: ValidatorKeyKind::Protocol,
+        message: msg.clone(),
+        client_public_key: allowed_key.public().as_ref().to_vec(),
+        client_signature: forged_signature.as_ref().to_vec(),
+    };
+    let service = RemoteSignerService::new(local.clone(), [allowed_key.public().clone()]);
+    assert_eq!(
+        service.authorize(&request).unwrap_err().code(),
+        tonic::Code::Unauthenticated
+    );
+
+    // The node does not connect to a signer holding another protocol key.
+    let (_, other_protocol_key): (_, AuthorityKeyPair) = get_key_pair();
+    assert!(RemoteValidatorSigner::connect(
+        &address,
+        allowed_key.copy(),
+        other_protocol_key.public().into()
+    )
+    .await
+    .is_err());
+
+    let remote = RemoteValidatorSigner::connect(&address, allowed_key, protocol_public_key)
+        .await
+        .unwrap();
+    assert_eq!(
+        remote.sign_protocol(msg).unwrap(),
+        local.sign_protocol(msg).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_remote_signer_on_current_thread_runtime() {
+    // The signing service runs on its own runtime, as it would run on another host, so that
+    // signing can block the only thread of the test runtime.
+    let server_runtime = tokio::runtime::Runtime::new().unwrap();
+    let local = Arc::new(local_signer());
+    let remote = server_runtime
+        .spawn(start_remote_signer(local.clone()))
+        .await
+        .unwrap();
+
+    let msg = &intent_message(Intent::sui_app(IntentScope::TransactionEffects));
+    let signer = authority_signer(Arc::new(remote));
+    assert_eq!(
+        signer.try_sign(msg).unwrap(),
+        local.sign_protocol(msg).unwrap()
+    );
+    server_runtime.shutdown_background();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_signing_failure_is_an_error() {
+    let failing = Arc::new(FailingSigner(local_signer()));
+    let msg = &intent_message(Intent::sui_app(IntentScope::TransactionEffects));
+    assert!(matches!(
+        authority_signer(failing.clone()).try_sign(msg),
+        Err(SuiError::ValidatorSigningFailed { .. })
+    ));
+
+    let remote = start_remote_signer(failing).await;
+    assert!(remote.sign_protocol(msg).is_err());
+    assert!(matches!(
+        authority_signer(Arc::new(remote)).try_sign(msg),
+        Err(SuiError::ValidatorSigningFailed { .. })
+    ));
+}
+
+#[test]
+#[should_panic(expected = "Failed to sign consensus message")]
+fn test_consensus_signing_failure_panics() {
+    let failing = Arc::new(FailingSigner(local_signer()));
+    consensus_signer(failing).unwrap().sign(b"hello");
+}
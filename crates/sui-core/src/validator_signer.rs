@@ -0,0 +1,493 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use fastcrypto::ed25519::Ed25519Signature;
+use fastcrypto::traits::{KeyPair, ToFromBytes, VerifyingKey};
+use narwhal_crypto::ProtocolSigner;
+use shared_crypto::intent::{AppId, Intent, IntentScope, INTENT_PREFIX_LENGTH};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use sui_network::api::{RemoteSigner, RemoteSignerClient};
+use sui_network::tonic;
+use sui_network::tonic::transport::Channel;
+use sui_types::crypto::{
+    AuthorityKeyPair, AuthorityPublicKey, AuthorityPublicKeyBytes, AuthoritySignature,
+    AuthoritySigner, NetworkKeyPair, NetworkPublicKey, PublicKey, Signature, SignatureScheme,
+    Signer, SuiKeyPair,
+};
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::multiaddr::Multiaddr;
+use sui_types::remote_signer::{
+    PublicKeyRequest, PublicKeyResponse, SignRequest, SignResponse, ValidatorKeyKind,
+};
+use tokio::runtime::{Handle, Runtime, RuntimeFlavor};
+use tokio::task::{JoinError, JoinHandle};
+use tracing::warn;
+
+use crate::authority::StableSyncAuthoritySigner;
+
+#[cfg(test)]
+#[path = "unit_tests/validator_signer_tests.rs"]
+mod validator_signer_tests;
+
+/// Signs messages with the protocol, account and network keys of a validator.
+///
+/// Implementations either hold the keys in memory ([LocalValidatorSigner]) or delegate to an
+/// external signing service ([RemoteValidatorSigner]) so that keys can live in an HSM or KMS.
+pub trait ValidatorSigner: Send + Sync {
+    fn protocol_public_key(&self) -> AuthorityPublicKeyBytes;
+
+    fn account_public_key(&self) -> PublicKey;
+
+    fn network_public_key(&self) -> NetworkPublicKey;
+
+    fn sign_protocol(&self, msg: &[u8]) -> Result<AuthoritySignature, signature::Error>;
+
+    fn sign_account(&self, msg: &[u8]) -> Result<Signature, signature::Error>;
+
+    fn sign_network(&self, msg: &[u8]) -> Result<Ed25519Signature, signature::Error>;
+}
+
+/// Returns the protocol key signer used by `AuthorityState` for votes and checkpoint signatures.
+/// A signing error is returned to the caller, which skips the vote or retries the signature.
+pub fn authority_signer(signer: Arc<dyn ValidatorSigner>) -> StableSyncAuthoritySigner {
+    Arc::pin(ProtocolKeySigner(signer))
+}
+
+struct ProtocolKeySigner(Arc<dyn ValidatorSigner>);
+
+impl AuthoritySigner for ProtocolKeySigner {
+    fn try_sign(&self, msg: &[u8]) -> SuiResult<AuthoritySignature> {
+        self.0
+            .sign_protocol(msg)
+            .map_err(|e| SuiError::ValidatorSigningFailed {
+                error: e.to_string(),
+            })
+    }
+}
+
+/// Returns the protocol key signer used by consensus for headers and votes.
+///
+/// Consensus cannot make progress without signing, and its signing is infallible, so the node
+/// panics once the signer gives up, e.g. after the retries of a [RemoteValidatorSigner].
+pub fn consensus_signer(signer: Arc<dyn ValidatorSigner>) -> anyhow::Result<ProtocolSigner> {
+    let public = AuthorityPublicKey::try_from(signer.protocol_public_key())
+        .map_err(|e| anyhow!("Invalid validator protocol public key: {e}"))?;
+    Ok(ProtocolSigner::new(
+        public,
+        Arc::new(ConsensusKeySigner(signer)),
+    ))
+}
+
+struct ConsensusKeySigner(Arc<dyn ValidatorSigner>);
+
+impl Signer<AuthoritySignature> for ConsensusKeySigner {
+    fn sign(&self, msg: &[u8]) -> AuthoritySignature {
+        self.0.sign_protocol(msg).unwrap_or_else(|e| {
+            let error = SuiError::ValidatorSigningFailed {
+                error: e.to_string(),
+            };
+            panic!("Failed to sign consensus message: {error}")
+        })
+    }
+}
+
+/// A [ValidatorSigner] holding the keys in memory, e.g. as loaded from the node key files.
+pub struct LocalValidatorSigner {
+    protocol_key_pair: AuthorityKeyPair,
+    account_key_pair: SuiKeyPair,
+    network_key_pair: NetworkKeyPair,
+}
+
+impl LocalValidatorSigner {
+    pub fn new(
+        protocol_key_pair: AuthorityKeyPair,
+        account_key_pair: SuiKeyPair,
+        network_key_pair: NetworkKeyPair,
+    ) -> Self {
+        Self {
+            protocol_key_pair,
+            account_key_pair,
+            network_key_pair,
+        }
+    }
+}
+
+impl ValidatorSigner for LocalValidatorSigner {
+    fn protocol_public_key(&self) -> AuthorityPublicKeyBytes {
+        self.protocol_key_pair.public().into()
+    }
+
+    fn account_public_key(&self) -> PublicKey {
+        self.account_key_pair.public()
+    }
+
+    fn network_public_key(&self) -> NetworkPublicKey {
+        self.network_key_pair.public().clone()
+    }
+
+    fn sign_protocol(&self, msg: &[u8]) -> Result<AuthoritySignature, signature::Error> {
+        Ok(self.protocol_key_pair.sign(msg))
+    }
+
+    fn sign_account(&self, msg: &[u8]) -> Result<Signature, signature::Error> {
+        Ok(self.account_key_pair.sign(msg))
+    }
+
+    fn sign_network(&self, msg: &[u8]) -> Result<Ed25519Signature, signature::Error> {
+        Ok(self.network_key_pair.sign(msg))
+    }
+}
+
+/// Time given to the remote signer to answer a signing request.
+const REMOTE_SIGN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Number of times a signing request is sent to the remote signer before giving up.
+const MAX_REMOTE_SIGN_ATTEMPTS: usize = 5;
+
+/// Initial and maximum delays between attempts of sending a signing request.
+const REMOTE_SIGN_RETRY_DELAY: Duration = Duration::from_millis(100);
+const MAX_REMOTE_SIGN_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// A [ValidatorSigner] backed by a remote signing service speaking the `RemoteSigner` gRPC API.
+///
+/// Requests are authenticated with the network key of the node, which the service must allow,
+/// and the protocol key of the service must be the one pinned in the config of the node. Public
+/// keys are fetched once when connecting. As signing is synchronous in the authority, the
+/// requests run on a runtime dedicated to the signer while the calling thread waits for their
+/// response, in place on the worker threads of a multi-thread tokio runtime. A request is bounded
+/// by a timeout per attempt and a limited number of attempts, after which signing fails.
+#[derive(Clone)]
+pub struct RemoteValidatorSigner {
+    runtime: Arc<SignerRuntime>,
+    client: RemoteSignerClient<Channel>,
+    client_key: Arc<NetworkKeyPair>,
+    protocol_public_key: AuthorityPublicKeyBytes,
+    account_public_key: PublicKey,
+    network_public_key: NetworkPublicKey,
+}
+
+/// The runtime of the requests of a [RemoteValidatorSigner]. It is shut down in the background
+/// when dropped, as a runtime cannot be dropped from an async context.
+struct SignerRuntime(Option<Runtime>);
+
+impl SignerRuntime {
+    fn runtime(&self) -> &Runtime {
+        self.0.as_ref().expect("Runtime is only taken when dropped")
+    }
+}
+
+impl Drop for SignerRuntime {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.0.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+impl RemoteValidatorSigner {
+    /// Connects to the remote signer at `address`, authenticating with `client_key`, and checks
+    /// that it holds the protocol key `expected_protocol_key`.
+    pub async fn connect(
+        address: &Multiaddr,
+        client_key: NetworkKeyPair,
+        expected_protocol_key: AuthorityPublicKeyBytes,
+    ) -> anyhow::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("remote-signer")
+            .enable_all()
+            .build()?;
+        let runtime = Arc::new(SignerRuntime(Some(runtime)));
+        // The channel is created on the signer runtime, so that its connection is driven by it.
+        runtime
+            .runtime()
+            .spawn(Self::connect_on(
+                runtime.clone(),
+                address.clone(),
+                client_key,
+                expected_protocol_key,
+            ))
+            .await?
+    }
+
+    async fn connect_on(
+        runtime: Arc<SignerRuntime>,
+        address: Multiaddr,
+        client_key: NetworkKeyPair,
+        expected_protocol_key: AuthorityPublicKeyBytes,
+    ) -> anyhow::Result<Self> {
+        let channel = mysten_network::client::connect(&address)
+            .await
+            .map_err(|err| anyhow!(err.to_string()))?;
+        let mut client = RemoteSignerClient::new(channel);
+
+        let protocol = fetch_public_key(&mut client, ValidatorKeyKind::Protocol).await?;
+        let protocol_public_key = AuthorityPublicKeyBytes::from_bytes(&protocol)
+            .map_err(|e| anyhow!("Invalid protocol public key from remote signer: {e}"))?;
+        if protocol_public_key != expected_protocol_key {
+            return Err(anyhow!(
+                "The remote signer holds protocol key {protocol_public_key}, expected \
+                 {expected_protocol_key}"
+            ));
+        }
+
+        let account = fetch_public_key(&mut client, ValidatorKeyKind::Account).await?;
+        let (flag, key) = account
+            .split_first()
+            .ok_or_else(|| anyhow!("Empty account public key from remote signer"))?;
+        let account_public_key =
+            PublicKey::try_from_bytes(SignatureScheme::from_flag_byte(flag)?, key)
+                .map_err(|e| anyhow!("Invalid account public key from remote signer: {e}"))?;
+
+        let network = fetch_public_key(&mut client, ValidatorKeyKind::Network).await?;
+        let network_public_key = NetworkPublicKey::from_bytes(&network)
+            .map_err(|e| anyhow!("Invalid network public key from remote signer: {e}"))?;
+
+        Ok(Self {
+            runtime,
+            client,
+            client_key: Arc::new(client_key),
+            protocol_public_key,
+            account_public_key,
+            network_public_key,
+        })
+    }
+
+    fn sign_remote(&self, key: ValidatorKeyKind, msg: &[u8]) -> Result<Vec<u8>, signature::Error> {
+        let mut client = self.client.clone();
+        let client_signature: Ed25519Signature = self
+            .client_key
+            .sign(&SignRequest::client_signing_bytes(key, msg));
+        let request = SignRequest {
+            key,
+            message: msg.to_vec(),
+            client_public_key: self.client_key.public().as_ref().to_vec(),
+            client_signature: client_signature.as_ref().to_vec(),
+        };
+        let request = self.runtime.runtime().spawn(async move {
+            let mut delay = REMOTE_SIGN_RETRY_DELAY;
+            let mut attempt = 1;
+            loop {
+                let response = client.sign(request.clone());
+                let error = match tokio::time::timeout(REMOTE_SIGN_TIMEOUT, response).await {
+                    Ok(Ok(response)) => return Ok(response.into_inner().signature),
+                    // The signer refuses the request, so sending it again is bound to fail.
+                    Ok(Err(status)) if is_refusal(status.code()) => {
+                        return Err(format!("Remote signer refused the request: {status}"));
+                    }
+                    Ok(Err(status)) => status.to_string(),
+                    Err(_) => format!("no response after {REMOTE_SIGN_TIMEOUT:?}"),
+                };
+                if attempt == MAX_REMOTE_SIGN_ATTEMPTS {
+                    return Err(format!(
+                        "Remote signing failed after {attempt} attempts: {error}"
+                    ));
+                }
+                warn!(
+                    ?key,
+                    attempt, "Remote signing failed, retrying in {delay:?}: {error}"
+                );
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(MAX_REMOTE_SIGN_RETRY_DELAY);
+                attempt += 1;
+            }
+        });
+        wait_for_request(request)
+            .map_err(signature::Error::from_source)?
+            .map_err(signature::Error::from_source)
+    }
+}
+
+/// Whether the signer refuses a request, rather than failing to sign it.
+fn is_refusal(code: tonic::Code) -> bool {
+    matches!(
+        code,
+        tonic::Code::Unauthenticated | tonic::Code::PermissionDenied | tonic::Code::InvalidArgument
+    )
+}
+
+/// Waits for a request running on the runtime of a [RemoteValidatorSigner]. A worker thread of a
+/// multi-thread runtime waits in place, so that its other tasks move to the other workers, while
+/// the only thread of a current-thread runtime cannot but block until the request is done.
+fn wait_for_request<T>(request: JoinHandle<T>) -> Result<T, JoinError> {
+    let wait = || futures::executor::block_on(request);
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(wait)
+        }
+        _ => wait(),
+    }
+}
+
+async fn fetch_public_key(
+    client: &mut RemoteSignerClient<Channel>,
+    key: ValidatorKeyKind,
+) -> anyhow::Result<Vec<u8>> {
+    Ok(client
+        .public_key(PublicKeyRequest { key })
+        .await
+        .map_err(|e| anyhow!("Failed to fetch {key:?} public key from remote signer: {e}"))?
+        .into_inner()
+        .public_key)
+}
+
+impl ValidatorSigner for RemoteValidatorSigner {
+    fn protocol_public_key(&self) -> AuthorityPublicKeyBytes {
+        self.protocol_public_key
+    }
+
+    fn account_public_key(&self) -> PublicKey {
+        self.account_public_key.clone()
+    }
+
+    fn network_public_key(&self) -> NetworkPublicKey {
+        self.network_public_key.clone()
+    }
+
+    fn sign_protocol(&self, msg: &[u8]) -> Result<AuthoritySignature, signature::Error> {
+        let bytes = self.sign_remote(ValidatorKeyKind::Protocol, msg)?;
+        AuthoritySignature::from_bytes(&bytes).map_err(signature::Error::from_source)
+    }
+
+    fn sign_account(&self, msg: &[u8]) -> Result<Signature, signature::Error> {
+        let bytes = self.sign_remote(ValidatorKeyKind::Account, msg)?;
+        Signature::from_bytes(&bytes).map_err(signature::Error::from_source)
+    }
+
+    fn sign_network(&self, msg: &[u8]) -> Result<Ed25519Signature, signature::Error> {
+        let bytes = self.sign_remote(ValidatorKeyKind::Network, msg)?;
+        Ed25519Signature::from_bytes(&bytes).map_err(signature::Error::from_source)
+    }
+}
+
+/// Serves the `RemoteSigner` gRPC API from any [ValidatorSigner]. Signing services can wrap
+/// their HSM or KMS client in a [ValidatorSigner] and expose it to validators with this.
+///
+/// Only requests signed by the network key of an allowed node are served, and each key only
+/// signs the messages of the intents a validator signs with it, see [is_allowed_intent].
+pub struct RemoteSignerService {
+    signer: Arc<dyn ValidatorSigner>,
+    /// Network keys of the nodes allowed to request signatures.
+    allowed_clients: HashSet<NetworkPublicKey>,
+}
+
+impl RemoteSignerService {
+    pub fn new(
+        signer: Arc<dyn ValidatorSigner>,
+        allowed_clients: impl IntoIterator<Item = NetworkPublicKey>,
+    ) -> Self {
+        Self {
+            signer,
+            allowed_clients: allowed_clients.into_iter().collect(),
+        }
+    }
+
+    /// Checks that the request is signed by an allowed client, and that its message is one the
+    /// requested key signs.
+    fn authorize(&self, request: &SignRequest) -> Result<(), tonic::Status> {
+        let client = NetworkPublicKey::from_bytes(&request.client_public_key)
+            .map_err(|_| tonic::Status::unauthenticated("Invalid client public key"))?;
+        if !self.allowed_clients.contains(&client) {
+            return Err(tonic::Status::permission_denied(format!(
+                "Client {client:?} is not allowed to request signatures"
+            )));
+        }
+        let signature = Ed25519Signature::from_bytes(&request.client_signature)
+            .map_err(|_| tonic::Status::unauthenticated("Invalid client signature"))?;
+        client
+            .verify(
+                &SignRequest::client_signing_bytes(request.key, &request.message),
+                &signature,
+            )
+            .map_err(|_| tonic::Status::unauthenticated("Invalid client signature"))?;
+
+        let intent = message_intent(&request.message).ok_or_else(|| {
+            tonic::Status::invalid_argument("The message does not start with an intent")
+        })?;
+        if !is_allowed_intent(request.key, &intent) {
+            return Err(tonic::Status::permission_denied(format!(
+                "The {:?} key does not sign messages of intent {intent:?}",
+                request.key
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// The intent prefixing a message, as every message signed by a validator key is the BCS
+/// encoding of an intent message, possibly followed by the epoch.
+fn message_intent(message: &[u8]) -> Option<Intent> {
+    let prefix = message.get(..INTENT_PREFIX_LENGTH)?;
+    Some(Intent {
+        scope: prefix[0].try_into().ok()?,
+        version: prefix[1].try_into().ok()?,
+        app_id: prefix[2].try_into().ok()?,
+    })
+}
+
+/// Whether a validator signs messages of `intent` with `key` while running: the protocol key
+/// signs effects, checkpoints, transaction votes and consensus headers, and the account key
+/// signs transactions. Proofs of possession are only signed when registering a validator, and
+/// the network key only signs the TLS handshakes of the node, with the key in its config.
+fn is_allowed_intent(key: ValidatorKeyKind, intent: &Intent) -> bool {
+    match (key, intent.app_id) {
+        (ValidatorKeyKind::Protocol, AppId::Sui) => matches!(
+            intent.scope,
+            IntentScope::TransactionEffects
+                | IntentScope::CheckpointSummary
+                | IntentScope::SenderSignedTransaction
+        ),
+        (ValidatorKeyKind::Protocol, AppId::Narwhal) => intent.scope == IntentScope::HeaderDigest,
+        (ValidatorKeyKind::Account, AppId::Sui) => intent.scope == IntentScope::TransactionData,
+        _ => false,
+    }
+}
+
+#[async_trait]
+impl RemoteSigner for RemoteSignerService {
+    async fn sign(
+        &self,
+        request: tonic::Request<SignRequest>,
+    ) -> Result<tonic::Response<SignResponse>, tonic::Status> {
+        let request = request.into_inner();
+        self.authorize(&request)?;
+        let SignRequest { key, message, .. } = request;
+        let signature = match key {
+            ValidatorKeyKind::Protocol => self
+                .signer
+                .sign_protocol(&message)
+                .map(|sig| sig.as_ref().to_vec()),
+            ValidatorKeyKind::Account => self
+                .signer
+                .sign_account(&message)
+                .map(|sig| sig.as_ref().to_vec()),
+            ValidatorKeyKind::Network => self
+                .signer
+                .sign_network(&message)
+                .map(|sig| sig.as_ref().to_vec()),
+        }
+        .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        Ok(tonic::Response::new(SignResponse { signature }))
+    }
+
+    async fn public_key(
+        &self,
+        request: tonic::Request<PublicKeyRequest>,
+    ) -> Result<tonic::Response<PublicKeyResponse>, tonic::Status> {
+        let public_key = match request.into_inner().key {
+            ValidatorKeyKind::Protocol => self.signer.protocol_public_key().as_ref().to_vec(),
+            ValidatorKeyKind::Account => {
+                let pk = self.signer.account_public_key();
+                let mut bytes = vec![pk.flag()];
+                bytes.extend_from_slice(pk.as_ref());
+                bytes
+            }
+            ValidatorKeyKind::Network => self.signer.network_public_key().as_ref().to_vec(),
+        };
+        Ok(tonic::Response::new(PublicKeyResponse { public_key }))
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+
+use anyhow::{bail, Context, Result};
+use mysten_metrics::spawn_monitored_task;
+use sui_storage::object_store::ObjectStoreConfig;
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::progress_store::ProgressStore;
+use crate::reader::{CheckpointReader, ReaderOptions};
+use crate::worker_pool::{Worker, WorkerPool};
+
+#[cfg(test)]
+#[path = "unit_tests/executor_tests.rs"]
+mod executor_tests;
+
+/// Number of checkpoints buffered for each worker pool.
+const MAX_CHECKPOINTS_IN_PROGRESS: usize = 10000;
+
+/// Watermark of each task when the executor stopped.
+pub type ExecutorProgress = BTreeMap<String, CheckpointSequenceNumber>;
+
+/// Feeds the checkpoints of a [CheckpointReader] to the registered worker pools and persists
+/// their progress.
+pub struct IndexerExecutor<P> {
+    pools: Vec<Pin<Box<dyn Future<Output = ()> + Send>>>,
+    pool_senders: Vec<mpsc::Sender<CheckpointData>>,
+    progress_store: P,
+    watermarks: ExecutorProgress,
+    pool_progress_sender: mpsc::UnboundedSender<(String, CheckpointSequenceNumber)>,
+    pool_progress_receiver: mpsc::UnboundedReceiver<(String, CheckpointSequenceNumber)>,
+}
+
+impl<P: ProgressStore> IndexerExecutor<P> {
+    pub fn new(progress_store: P) -> Self {
+        let (pool_progress_sender, pool_progress_receiver) = mpsc::unbounded_channel();
+        Self {
+            pools: vec![],
+            pool_senders: vec![],
+            progress_store,
+            watermarks: BTreeMap::new(),
+            pool_progress_sender,
+            pool_progress_receiver,
+        }
+    }
+
+    /// Register a worker pool, which resumes from the watermark persisted for its task name.
+    pub async fn register<W: Worker + 'static>(&mut self, pool: WorkerPool<W>) -> Result<()> {
+        let watermark = self.progress_store.load(pool.task_name.clone()).await?;
+        let (sender, receiver) = mpsc::channel(MAX_CHECKPOINTS_IN_PROGRESS);
+        self.watermarks.insert(pool.task_name.clone(), watermark);
+        self.pools.push(Box::pin(pool.run(
+            watermark,
+            receiver,
+            self.pool_progress_sender.clone(),
+        )));
+        self.pool_senders.push(sender);
+        Ok(())
+    }
+
+    /// Process checkpoints, read from the local directory at `path` or from `remote_store`, until
+    /// `exit_receiver` fires. Returns the watermark of each task, or an error if the reader stops
+    /// before.
+    pub async fn run(
+        mut self,
+        path: PathBuf,
+        remote_store: Option<ObjectStoreConfig>,
+        reader_options: ReaderOptions,
+        mut exit_receiver: oneshot::Receiver<()>,
+    ) -> Result<ExecutorProgress> {
+        let mut reader_watermark = self.min_watermark();
+        let (reader, mut checkpoint_receiver, gc_sender, reader_exit_sender) =
+            CheckpointReader::initialize(path, reader_watermark, remote_store, reader_options)?;
+        let mut reader_handle = spawn_monitored_task!(reader.run());
+        for pool in std::mem::take(&mut self.pools) {
+            spawn_monitored_task!(pool);
+        }
+
+        loop {
+            tokio::select! {
+                _ = &mut exit_receiver => break,
+                result = &mut reader_handle => {
+                    result?.context("Checkpoint reader failed")?;
+                    bail!("Checkpoint reader stopped");
+                }
+                Some((task_name, watermark)) = self.pool_progress_receiver.recv() => {
+                    self.progress_store.save(task_name.clone(), watermark).await?;
+                    self.watermarks.insert(task_name, watermark);
+                    let min_watermark = self.min_watermark();
+                    if min_watermark > reader_watermark {
+                        gc_sender.send_replace(min_watermark);
+                        reader_watermark = min_watermark;
+                    }
+                }
+                Some(checkpoint) = checkpoint_receiver.recv() => {
+                    for sender in &self.pool_senders {
+                        sender.send(checkpoint.clone()).await?;
+                    }
+                }
+            }
+        }
+        let _ = reader_exit_sender.send(());
+        Ok(self.watermarks)
+    }
+
+    /// The checkpoint from which the slowest task has to resume.
+    fn min_watermark(&self) -> CheckpointSequenceNumber {
+        self.watermarks.values().min().copied().unwrap_or_default()
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures::future::try_join_all;
+use rdkafka::config::ClientConfig;
+use rdkafka::message::{Header, OwnedHeaders};
+use rdkafka::producer::{FutureProducer, FutureRecord};
+
+use super::{ExportedMessage, MessageSink};
+
+/// Header holding the dedup key of a message.
+const DEDUP_KEY_HEADER: &str = "sui-dedup-key";
+
+/// How long a message may wait in the producer queue when it is full.
+const QUEUE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// [MessageSink] publishing to Kafka, using the partition key as the record key. Enable
+/// `enable.idempotence` in the producer config to get rid of duplicates caused by producer
+/// retries, duplicates caused by checkpoint retries carry the same dedup key header.
+pub struct KafkaSink {
+    producer: FutureProducer,
+}
+
+impl KafkaSink {
+    pub fn new(config: &ClientConfig) -> Result<Self> {
+        Ok(Self {
+            producer: config.create()?,
+        })
+    }
+}
+
+#[async_trait]
+impl MessageSink for KafkaSink {
+    async fn publish(&self, messages: Vec<ExportedMessage>) -> Result<()> {
+        try_join_all(messages.iter().map(|message| async move {
+            let headers = OwnedHeaders::new().insert(Header {
+                key: DEDUP_KEY_HEADER,
+                value: Some(message.dedup_key.as_str()),
+            });
+            let record = FutureRecord::to(&message.topic)
+                .key(&message.partition_key)
+                .payload(&message.payload)
+                .headers(headers);
+            self.producer
+                .send(record, QUEUE_TIMEOUT)
+                .await
+                .map_err(|(e, _)| anyhow!("Failed to publish to {}: {e}", message.topic))
+        }))
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! [Worker] publishing the transactions, effects and events of checkpoints to a message broker.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sui_types::full_checkpoint_content::{CheckpointData, CheckpointTransaction};
+use sui_types::messages::{TransactionDataAPI, TransactionEffectsAPI};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+use crate::worker_pool::Worker;
+
+#[cfg(feature = "kafka")]
+mod kafka;
+#[cfg(feature = "nats")]
+mod nats;
+
+#[cfg(feature = "kafka")]
+pub use kafka::KafkaSink;
+#[cfg(feature = "nats")]
+pub use nats::NatsSink;
+
+#[cfg(test)]
+#[path = "../unit_tests/exporter_tests.rs"]
+mod exporter_tests;
+
+/// How messages are assigned a partition key, which brokers use to order and shard messages.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Partitioning {
+    /// All messages of a transaction are keyed by its sender.
+    Sender,
+    /// Events are keyed by the package emitting them, transactions and effects by the package of
+    /// their first Move call, or by their sender if they do not call Move.
+    Package,
+    /// All messages are keyed by their checkpoint, which preserves the order of the chain.
+    #[default]
+    Checkpoint,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Encoding {
+    #[default]
+    Bcs,
+    Json,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct ExporterConfig {
+    pub transactions_topic: String,
+    pub effects_topic: String,
+    pub events_topic: String,
+    pub partitioning: Partitioning,
+    pub encoding: Encoding,
+}
+
+impl Default for ExporterConfig {
+    fn default() -> Self {
+        Self {
+            transactions_topic: "sui.transactions".to_string(),
+            effects_topic: "sui.effects".to_string(),
+            events_topic: "sui.events".to_string(),
+            partitioning: Partitioning::default(),
+            encoding: Encoding::default(),
+        }
+    }
+}
+
+/// A message to publish to a broker.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExportedMessage {
+    pub topic: String,
+    pub partition_key: String,
+    /// Key identifying the message, identical when a checkpoint is processed again, so that
+    /// brokers or consumers can drop the duplicates of at-least-once delivery.
+    pub dedup_key: String,
+    pub payload: Vec<u8>,
+}
+
+/// A message broker, e.g. Kafka or NATS.
+#[async_trait]
+pub trait MessageSink: Send + Sync {
+    /// Publish all messages, returning once the broker acknowledged them.
+    async fn publish(&self, messages: Vec<ExportedMessage>) -> Result<()>;
+}
+
+pub struct ExporterWorker<S> {
+    config: ExporterConfig,
+    sink: S,
+}
+
+impl<S: MessageSink> ExporterWorker<S> {
+    pub fn new(config: ExporterConfig, sink: S) -> Self {
+        Self { config, sink }
+    }
+
+    pub fn messages(&self, checkpoint: &CheckpointData) -> Result<Vec<ExportedMessage>> {
+        let sequence_number = checkpoint.checkpoint_summary.sequence_number;
+        let mut messages = vec![];
+        for transaction in &checkpoint.transactions {
+            let digest = transaction.effects.transaction_digest();
+            let key = self.transaction_partition_key(sequence_number, transaction);
+            messages.push(ExportedMessage {
+                topic: self.config.transactions_topic.clone(),
+                partition_key: key.clone(),
+                dedup_key: format!("transaction:{digest}"),
+                payload: self.encode(&transaction.transaction)?,
+            });
+            messages.push(ExportedMessage {
+                topic: self.config.effects_topic.clone(),
+                partition_key: key.clone(),
+                dedup_key: format!("effects:{digest}"),
+                payload: self.encode(&transaction.effects)?,
+            });
+            for (index, event) in transaction.events.iter().flat_map(|e| &e.data).enumerate() {
+                let partition_key = match self.config.partitioning {
+                    Partitioning::Package => event.package_id.to_string(),
+                    _ => key.clone(),
+                };
+                messages.push(ExportedMessage {
+                    topic: self.config.events_topic.clone(),
+                    partition_key,
+                    dedup_key: format!("event:{digest}:{index}"),
+                    payload: self.encode(event)?,
+                });
+            }
+        }
+        Ok(messages)
+    }
+
+    fn transaction_partition_key(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+        transaction: &CheckpointTransaction,
+    ) -> String {
+        let data = transaction.transaction.data().transaction_data();
+        match self.config.partitioning {
+            Partitioning::Sender => data.sender().to_string(),
+            Partitioning::Package => data.move_calls().first().map_or_else(
+                || data.sender().to_string(),
+                |(package, _, _)| package.to_string(),
+            ),
+            Partitioning::Checkpoint => sequence_number.to_string(),
+        }
+    }
+
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>> {
+        Ok(match self.config.encoding {
+            Encoding::Bcs => bcs::to_bytes(value)?,
+            Encoding::Json => serde_json::to_vec(value)?,
+        })
+    }
+}
+
+#[async_trait]
+impl<S: MessageSink> Worker for ExporterWorker<S> {
+    async fn process_checkpoint(&self, checkpoint: CheckpointData) -> Result<()> {
+        let messages = self.messages(&checkpoint)?;
+        self.sink.publish(messages).await
+    }
+}
@@ -0,0 +1,50 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::future::IntoFuture;
+
+use anyhow::{anyhow, Result};
+use async_nats::header::{HeaderMap, NATS_MESSAGE_ID};
+use async_nats::jetstream;
+use async_trait::async_trait;
+use futures::future::try_join_all;
+
+use super::{ExportedMessage, MessageSink};
+
+/// [MessageSink] publishing to NATS JetStream on the `<topic>.<partition key>` subject. The
+/// dedup key is sent as the message ID, so that JetStream drops the duplicates received within
+/// the duplicate window of the stream.
+pub struct NatsSink {
+    context: jetstream::Context,
+}
+
+impl NatsSink {
+    pub async fn connect(url: &str) -> Result<Self> {
+        let client = async_nats::connect(url).await?;
+        Ok(Self {
+            context: jetstream::new(client),
+        })
+    }
+}
+
+#[async_trait]
+impl MessageSink for NatsSink {
+    async fn publish(&self, messages: Vec<ExportedMessage>) -> Result<()> {
+        let mut acks = Vec::with_capacity(messages.len());
+        for message in messages {
+            let mut headers = HeaderMap::new();
+            headers.insert(NATS_MESSAGE_ID, message.dedup_key.as_str());
+            let subject = format!("{}.{}", message.topic, message.partition_key);
+            let ack = self
+                .context
+                .publish_with_headers(subject, headers, message.payload.into())
+                .await
+                .map_err(|e| anyhow!("Failed to publish to {}: {e}", message.topic))?;
+            acks.push(ack.into_future());
+        }
+        try_join_all(acks)
+            .await
+            .map_err(|e| anyhow!("Failed to publish to NATS: {e}"))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Framework to build custom indexers over checkpoint data.
+//!
+//! Checkpoints are read in order from a local directory of BCS encoded [CheckpointData] files
+//! named `<sequence number>.chk`, falling back to a remote object store (e.g. S3) with the same
+//! layout. Each registered [WorkerPool] runs a [Worker] over every checkpoint, and its progress is
+//! persisted in a [ProgressStore] once all checkpoints up to a watermark are processed. Failed
+//! checkpoints are retried, and processing resumes from the persisted watermark on restart, so
+//! that every checkpoint is delivered at least once to each worker.
+//!
+//! A minimal indexer only needs to implement [Worker]:
+//!
+//! ```ignore
+//! struct CountingWorker;
+//!
+//! #[async_trait]
+//! impl Worker for CountingWorker {
+//!     async fn process_checkpoint(&self, checkpoint: CheckpointData) -> Result<()> {
+//!         println!("{} transactions", checkpoint.transactions.len());
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let (exit_sender, exit_receiver) = oneshot::channel();
+//! let progress_store = FileProgressStore::new(PathBuf::from("progress.json"));
+//! let mut executor = IndexerExecutor::new(progress_store);
+//! executor
+//!     .register(WorkerPool::new(CountingWorker, "counting".to_string(), 4))
+//!     .await?;
+//! executor
+//!     .run(PathBuf::from("checkpoints"), None, ReaderOptions::default(), exit_receiver)
+//!     .await?;
+//! ```
+//!
+//! [CheckpointData]: sui_types::full_checkpoint_content::CheckpointData
+
+mod executor;
+pub mod exporter;
+mod progress_store;
+mod reader;
+mod worker_pool;
+
+pub use executor::{ExecutorProgress, IndexerExecutor};
+pub use progress_store::{FileProgressStore, ProgressStore};
+pub use reader::{CheckpointReader, ReaderOptions};
+pub use worker_pool::{Worker, WorkerPool};
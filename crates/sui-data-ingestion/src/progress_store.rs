@@ -0,0 +1,64 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+/// Persists the watermark of each task, i.e. the number of the next checkpoint it has to process.
+#[async_trait]
+pub trait ProgressStore: Send {
+    /// Watermark of the task, 0 if the task never reported progress.
+    async fn load(&mut self, task_name: String) -> Result<CheckpointSequenceNumber>;
+
+    async fn save(
+        &mut self,
+        task_name: String,
+        checkpoint_number: CheckpointSequenceNumber,
+    ) -> Result<()>;
+}
+
+/// [ProgressStore] keeping the watermarks of all tasks in a local json file.
+pub struct FileProgressStore {
+    path: PathBuf,
+}
+
+impl FileProgressStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    fn read(&self) -> Result<BTreeMap<String, CheckpointSequenceNumber>> {
+        if !self.path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        let content = fs::read(&self.path)
+            .with_context(|| format!("Failed to read progress file {}", self.path.display()))?;
+        Ok(serde_json::from_slice(&content)?)
+    }
+}
+
+#[async_trait]
+impl ProgressStore for FileProgressStore {
+    async fn load(&mut self, task_name: String) -> Result<CheckpointSequenceNumber> {
+        Ok(self.read()?.get(&task_name).copied().unwrap_or_default())
+    }
+
+    async fn save(
+        &mut self,
+        task_name: String,
+        checkpoint_number: CheckpointSequenceNumber,
+    ) -> Result<()> {
+        let mut progress = self.read()?;
+        progress.insert(task_name, checkpoint_number);
+        // Write to a temporary file first so that a crash cannot leave a truncated file behind.
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_vec(&progress)?)?;
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
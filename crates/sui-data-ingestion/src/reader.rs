@@ -0,0 +1,196 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use object_store::path::Path as ObjectPath;
+use object_store::DynObjectStore;
+use sui_storage::object_store::ObjectStoreConfig;
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tokio::sync::{mpsc, oneshot, watch};
+use tracing::{debug, info, warn};
+
+/// Extension of the checkpoint files, which are named `<sequence number>.chk`.
+const CHECKPOINT_FILE_SUFFIX: &str = "chk";
+
+#[derive(Clone, Debug)]
+pub struct ReaderOptions {
+    /// How often the local directory and the remote store are polled for new checkpoints.
+    pub tick_interval: Duration,
+    /// Maximum number of checkpoints read in one poll.
+    pub batch_size: usize,
+}
+
+impl Default for ReaderOptions {
+    fn default() -> Self {
+        Self {
+            tick_interval: Duration::from_millis(100),
+            batch_size: 100,
+        }
+    }
+}
+
+/// Reads checkpoints in order, from the local directory if present there and from the remote
+/// store otherwise, and deletes the local files of the checkpoints processed by all tasks.
+pub struct CheckpointReader {
+    path: PathBuf,
+    remote_store: Option<Arc<DynObjectStore>>,
+    current_checkpoint_number: CheckpointSequenceNumber,
+    checkpoint_sender: mpsc::Sender<CheckpointData>,
+    processed_receiver: watch::Receiver<CheckpointSequenceNumber>,
+    exit_receiver: oneshot::Receiver<()>,
+    options: ReaderOptions,
+}
+
+impl CheckpointReader {
+    /// Create a reader starting at `starting_checkpoint_number`. Returns the reader, together with
+    /// the receiver of the checkpoints it reads, the sender to report the watermark under which
+    /// local files can be deleted, and the sender to stop the reader. Reporting the watermark
+    /// never blocks, so that the reader and its consumer cannot wait on each other.
+    pub fn initialize(
+        path: PathBuf,
+        starting_checkpoint_number: CheckpointSequenceNumber,
+        remote_store: Option<ObjectStoreConfig>,
+        options: ReaderOptions,
+    ) -> Result<(
+        Self,
+        mpsc::Receiver<CheckpointData>,
+        watch::Sender<CheckpointSequenceNumber>,
+        oneshot::Sender<()>,
+    )> {
+        let remote_store = remote_store.map(|config| config.make()).transpose()?;
+        let (checkpoint_sender, checkpoint_receiver) = mpsc::channel(options.batch_size.max(1));
+        let (processed_sender, processed_receiver) = watch::channel(starting_checkpoint_number);
+        let (exit_sender, exit_receiver) = oneshot::channel();
+        let reader = Self {
+            path,
+            remote_store,
+            current_checkpoint_number: starting_checkpoint_number,
+            checkpoint_sender,
+            processed_receiver,
+            exit_receiver,
+            options,
+        };
+        Ok((reader, checkpoint_receiver, processed_sender, exit_sender))
+    }
+
+    pub async fn run(mut self) -> Result<()> {
+        info!(
+            path = ?self.path,
+            checkpoint = self.current_checkpoint_number,
+            "Starting checkpoint reader"
+        );
+        let mut interval = tokio::time::interval(self.options.tick_interval);
+        loop {
+            tokio::select! {
+                _ = &mut self.exit_receiver => break,
+                Ok(()) = self.processed_receiver.changed() => {
+                    let watermark = *self.processed_receiver.borrow();
+                    self.gc_processed_files(watermark)?;
+                }
+                _ = interval.tick() => {
+                    if !self.sync().await? {
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Send the next available checkpoints, returns false if the receiver was dropped.
+    async fn sync(&mut self) -> Result<bool> {
+        let mut checkpoints = self.read_local_files()?;
+        if checkpoints.is_empty() {
+            checkpoints = self.read_remote_files().await;
+        }
+        for checkpoint in checkpoints {
+            let sequence_number = checkpoint.checkpoint_summary.sequence_number;
+            if self.checkpoint_sender.send(checkpoint).await.is_err() {
+                return Ok(false);
+            }
+            self.current_checkpoint_number = sequence_number + 1;
+        }
+        Ok(true)
+    }
+
+    fn read_local_files(&self) -> Result<Vec<CheckpointData>> {
+        let mut files = vec![];
+        for entry in fs::read_dir(&self.path)? {
+            let path = entry?.path();
+            if let Some(sequence_number) = checkpoint_number_from_file_path(&path) {
+                if sequence_number >= self.current_checkpoint_number {
+                    files.push((sequence_number, path));
+                }
+            }
+        }
+        files.sort();
+
+        let mut checkpoints = vec![];
+        for (expected, (sequence_number, path)) in
+            (self.current_checkpoint_number..).zip(files.into_iter().take(self.options.batch_size))
+        {
+            // Stop at the first missing checkpoint to deliver checkpoints in order.
+            if sequence_number != expected {
+                break;
+            }
+            checkpoints.push(bcs::from_bytes(&fs::read(&path)?)?);
+        }
+        Ok(checkpoints)
+    }
+
+    async fn read_remote_files(&self) -> Vec<CheckpointData> {
+        let Some(remote_store) = &self.remote_store else {
+            return vec![];
+        };
+        let mut checkpoints = vec![];
+        for sequence_number in (self.current_checkpoint_number..).take(self.options.batch_size) {
+            let location = ObjectPath::from(format!("{sequence_number}.{CHECKPOINT_FILE_SUFFIX}"));
+            let bytes = match remote_store.get(&location).await {
+                Ok(result) => result.bytes().await,
+                Err(e) => Err(e),
+            };
+            match bytes {
+                Ok(bytes) => match bcs::from_bytes(&bytes) {
+                    Ok(checkpoint) => checkpoints.push(checkpoint),
+                    Err(e) => {
+                        warn!(sequence_number, "Failed to decode remote checkpoint: {e}");
+                        break;
+                    }
+                },
+                Err(object_store::Error::NotFound { .. }) => break,
+                Err(e) => {
+                    warn!(sequence_number, "Failed to fetch remote checkpoint: {e}");
+                    break;
+                }
+            }
+        }
+        checkpoints
+    }
+
+    /// Delete the local files of the checkpoints under the watermark.
+    fn gc_processed_files(&self, watermark: CheckpointSequenceNumber) -> Result<()> {
+        debug!(watermark, "Deleting processed checkpoint files");
+        for entry in fs::read_dir(&self.path)? {
+            let path = entry?.path();
+            if let Some(sequence_number) = checkpoint_number_from_file_path(&path) {
+                if sequence_number < watermark {
+                    fs::remove_file(path)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn checkpoint_number_from_file_path(path: &Path) -> Option<CheckpointSequenceNumber> {
+    if path.extension()? != CHECKPOINT_FILE_SUFFIX {
+        return None;
+    }
+    path.file_stem()?.to_str()?.parse().ok()
+}
@@ -0,0 +1,130 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use sui_types::committee::Committee;
+use sui_types::messages_checkpoint::CheckpointContents;
+use sui_types::utils::mock_certified_checkpoint;
+
+use super::*;
+use crate::progress_store::FileProgressStore;
+
+const NUM_CHECKPOINTS: u64 = 5;
+
+/// Records the number of times each checkpoint was processed, failing the first attempt of
+/// checkpoint 2.
+struct TestWorker {
+    attempts: Mutex<BTreeMap<CheckpointSequenceNumber, u64>>,
+    done_sender: mpsc::UnboundedSender<CheckpointSequenceNumber>,
+}
+
+#[async_trait]
+impl Worker for TestWorker {
+    async fn process_checkpoint(&self, checkpoint: CheckpointData) -> Result<()> {
+        let sequence_number = checkpoint.checkpoint_summary.sequence_number;
+        let attempts = {
+            let mut attempts = self.attempts.lock().unwrap();
+            let count = attempts.entry(sequence_number).or_default();
+            *count += 1;
+            *count
+        };
+        if sequence_number == 2 && attempts == 1 {
+            return Err(anyhow!("Transient failure"));
+        }
+        self.done_sender.send(sequence_number).unwrap();
+        Ok(())
+    }
+}
+
+fn write_checkpoints(path: &Path) {
+    let (committee, keys) = Committee::new_simple_test_committee();
+    for sequence_number in 0..NUM_CHECKPOINTS {
+        let checkpoint = CheckpointData {
+            checkpoint_summary: mock_certified_checkpoint(
+                keys.iter(),
+                committee.clone(),
+                sequence_number,
+            ),
+            checkpoint_contents: CheckpointContents::new_with_causally_ordered_transactions(vec![]),
+            transactions: vec![],
+        };
+        fs::write(
+            path.join(format!("{sequence_number}.chk")),
+            bcs::to_bytes(&checkpoint).unwrap(),
+        )
+        .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_executor_processes_all_checkpoints() {
+    let checkpoints_dir = tempfile::tempdir().unwrap();
+    let progress_dir = tempfile::tempdir().unwrap();
+    write_checkpoints(checkpoints_dir.path());
+
+    let (done_sender, mut done_receiver) = mpsc::unbounded_channel();
+    let progress_path = progress_dir.path().join("progress.json");
+    let mut executor = IndexerExecutor::new(FileProgressStore::new(progress_path.clone()));
+    executor
+        .register(WorkerPool::new(
+            TestWorker {
+                attempts: Mutex::new(BTreeMap::new()),
+                done_sender,
+            },
+            "test".to_string(),
+            2,
+        ))
+        .await
+        .unwrap();
+
+    let (exit_sender, exit_receiver) = oneshot::channel();
+    let handle = tokio::spawn(executor.run(
+        checkpoints_dir.path().to_path_buf(),
+        None,
+        ReaderOptions::default(),
+        exit_receiver,
+    ));
+
+    let mut done = vec![];
+    while done.len() < NUM_CHECKPOINTS as usize {
+        done.push(done_receiver.recv().await.unwrap());
+    }
+    done.sort();
+    assert_eq!(done, (0..NUM_CHECKPOINTS).collect::<Vec<_>>());
+
+    // Wait for the progress of the last checkpoint to be persisted.
+    let mut progress_store = FileProgressStore::new(progress_path);
+    while progress_store.load("test".to_string()).await.unwrap() < NUM_CHECKPOINTS {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    exit_sender.send(()).unwrap();
+    let progress = handle.await.unwrap().unwrap();
+    assert_eq!(progress.get("test"), Some(&NUM_CHECKPOINTS));
+}
+
+#[tokio::test]
+async fn test_executor_fails_when_reader_fails() {
+    let progress_dir = tempfile::tempdir().unwrap();
+    let executor = IndexerExecutor::new(FileProgressStore::new(
+        progress_dir.path().join("progress.json"),
+    ));
+
+    // The reader fails to list the missing checkpoints directory.
+    let (_exit_sender, exit_receiver) = oneshot::channel();
+    let result = executor
+        .run(
+            progress_dir.path().join("missing"),
+            None,
+            ReaderOptions::default(),
+            exit_receiver,
+        )
+        .await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,106 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::ident_str;
+use sui_types::base_types::ObjectID;
+use sui_types::committee::Committee;
+use sui_types::event::Event;
+use sui_types::gas_coin::GasCoin;
+use sui_types::messages::{TransactionEffects, TransactionEvents};
+use sui_types::messages_checkpoint::CheckpointContents;
+use sui_types::utils::{create_fake_transaction, mock_certified_checkpoint};
+
+use super::*;
+
+struct NoopSink;
+
+#[async_trait]
+impl MessageSink for NoopSink {
+    async fn publish(&self, _messages: Vec<ExportedMessage>) -> Result<()> {
+        Ok(())
+    }
+}
+
+fn checkpoint_with_transaction(package_id: ObjectID) -> CheckpointData {
+    let (committee, keys) = Committee::new_simple_test_committee();
+    let transaction = create_fake_transaction().into_inner();
+    let sender = transaction.data().transaction_data().sender();
+    let event = Event {
+        package_id,
+        transaction_module: ident_str!("test").to_owned(),
+        sender,
+        type_: GasCoin::type_(),
+        contents: vec![],
+    };
+    CheckpointData {
+        checkpoint_summary: mock_certified_checkpoint(keys.iter(), committee, 7),
+        checkpoint_contents: CheckpointContents::new_with_causally_ordered_transactions(vec![]),
+        transactions: vec![CheckpointTransaction {
+            effects: TransactionEffects::new_with_tx(&transaction),
+            transaction,
+            events: Some(TransactionEvents {
+                data: vec![event.clone(), event],
+            }),
+            input_objects: vec![],
+            output_objects: vec![],
+        }],
+    }
+}
+
+#[test]
+fn test_exported_messages() {
+    let package_id = ObjectID::random();
+    let checkpoint = checkpoint_with_transaction(package_id);
+    let transaction = &checkpoint.transactions[0];
+    let digest = *transaction.effects.transaction_digest();
+    let sender = transaction.transaction.data().transaction_data().sender();
+
+    let worker = ExporterWorker::new(ExporterConfig::default(), NoopSink);
+    let messages = worker.messages(&checkpoint).unwrap();
+    assert_eq!(
+        messages
+            .iter()
+            .map(|m| (m.topic.as_str(), m.dedup_key.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("sui.transactions", format!("transaction:{digest}")),
+            ("sui.effects", format!("effects:{digest}")),
+            ("sui.events", format!("event:{digest}:0")),
+            ("sui.events", format!("event:{digest}:1")),
+        ]
+    );
+    assert!(messages.iter().all(|m| m.partition_key == "7"));
+    assert_eq!(
+        messages[0].payload,
+        bcs::to_bytes(&transaction.transaction).unwrap()
+    );
+    // Processing the checkpoint again produces the same dedup keys.
+    assert_eq!(worker.messages(&checkpoint).unwrap(), messages);
+
+    let config = ExporterConfig {
+        partitioning: Partitioning::Package,
+        ..Default::default()
+    };
+    let messages = ExporterWorker::new(config, NoopSink)
+        .messages(&checkpoint)
+        .unwrap();
+    // The transaction does not call Move, it is keyed by its sender.
+    assert_eq!(messages[0].partition_key, sender.to_string());
+    assert_eq!(messages[2].partition_key, package_id.to_string());
+
+    let config = ExporterConfig {
+        partitioning: Partitioning::Sender,
+        encoding: Encoding::Json,
+        ..Default::default()
+    };
+    let messages = ExporterWorker::new(config, NoopSink)
+        .messages(&checkpoint)
+        .unwrap();
+    assert!(messages
+        .iter()
+        .all(|m| m.partition_key == sender.to_string()));
+    assert_eq!(
+        messages[1].payload,
+        serde_json::to_vec(&transaction.effects).unwrap()
+    );
+}
@@ -0,0 +1,117 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use backoff::ExponentialBackoff;
+use futures::stream::FuturesUnordered;
+use futures::StreamExt;
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+/// User defined processing of checkpoints.
+///
+/// A checkpoint may be passed to the worker more than once, e.g. when processing failed or the
+/// indexer restarted before persisting its progress, so processing must be idempotent.
+#[async_trait]
+pub trait Worker: Send + Sync {
+    async fn process_checkpoint(&self, checkpoint: CheckpointData) -> Result<()>;
+}
+
+/// Runs a [Worker] over checkpoints, processing up to `concurrency` checkpoints at a time.
+pub struct WorkerPool<W: Worker> {
+    pub task_name: String,
+    concurrency: usize,
+    worker: Arc<W>,
+}
+
+impl<W: Worker + 'static> WorkerPool<W> {
+    pub fn new(worker: W, task_name: String, concurrency: usize) -> Self {
+        Self {
+            task_name,
+            concurrency: concurrency.max(1),
+            worker: Arc::new(worker),
+        }
+    }
+
+    /// Process the checkpoints received from `checkpoint_receiver`, starting at `watermark`, and
+    /// report the new watermark to `progress_sender` each time it advances.
+    pub async fn run(
+        self,
+        watermark: CheckpointSequenceNumber,
+        mut checkpoint_receiver: mpsc::Receiver<CheckpointData>,
+        progress_sender: mpsc::UnboundedSender<(String, CheckpointSequenceNumber)>,
+    ) {
+        info!(
+            task_name = self.task_name,
+            watermark, "Starting indexing worker pool"
+        );
+        let mut in_flight = FuturesUnordered::new();
+        let mut processed = BTreeSet::new();
+        let mut current = watermark;
+        loop {
+            tokio::select! {
+                Some(sequence_number) = in_flight.next() => {
+                    processed.insert(sequence_number);
+                    let previous = current;
+                    while processed.remove(&current) {
+                        current += 1;
+                    }
+                    if current > previous
+                        && progress_sender
+                            .send((self.task_name.clone(), current))
+                            .is_err()
+                    {
+                        break;
+                    }
+                }
+                checkpoint = checkpoint_receiver.recv(), if in_flight.len() < self.concurrency => {
+                    let Some(checkpoint) = checkpoint else {
+                        break;
+                    };
+                    if checkpoint.checkpoint_summary.sequence_number < watermark {
+                        continue;
+                    }
+                    in_flight.push(process(
+                        self.task_name.clone(),
+                        self.worker.clone(),
+                        checkpoint,
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Process a checkpoint, retrying until the worker succeeds.
+async fn process<W: Worker>(
+    task_name: String,
+    worker: Arc<W>,
+    checkpoint: CheckpointData,
+) -> CheckpointSequenceNumber {
+    let sequence_number = checkpoint.checkpoint_summary.sequence_number;
+    let backoff = ExponentialBackoff {
+        max_elapsed_time: None,
+        ..Default::default()
+    };
+    backoff::future::retry(backoff, || async {
+        worker
+            .process_checkpoint(checkpoint.clone())
+            .await
+            .map_err(|e| {
+                warn!(
+                    task_name,
+                    sequence_number, "Failed to process checkpoint, retrying: {e}"
+                );
+                backoff::Error::transient(e)
+            })
+    })
+    .await
+    .expect("Checkpoint processing is retried until it succeeds");
+    sequence_number
+}
@@ -726,7 +726,7 @@ mod tests {
         // Now we transfer one gas out
         let res = SuiClientCommands::PayAllSui {
             input_coins: vec![*bad_gas.id()],
-            recipient: SuiAddress::random_for_testing_only(),
+            recipient: SuiAddress::random_for_testing_only().into(),
             gas_budget: 2_000_000,
         }
         .execute(faucet.wallet_mut())
@@ -914,7 +914,7 @@ mod tests {
     async fn get_current_gases(address: SuiAddress, context: &mut WalletContext) -> Vec<GasCoin> {
         // Get the latest list of gas
         let results = SuiClientCommands::Gas {
-            address: Some(address),
+            address: Some(address.into()),
         }
         .execute(context)
         .await
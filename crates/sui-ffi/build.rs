@@ -0,0 +1,20 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::{Path, PathBuf};
+
+/// Generates the C header of the crate in the build directory. The header is also checked in for
+/// the wallets linking the library without building it, and a test checks that it is up to date.
+fn main() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))
+        .expect("cbindgen.toml should be a valid cbindgen config");
+    cbindgen::generate_with_config(crate_dir, config)
+        .expect("Unable to generate C bindings")
+        .write_to_file(out_dir.join("sui_ffi.h"));
+
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=src");
+}
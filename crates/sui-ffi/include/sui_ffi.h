@@ -0,0 +1,111 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#ifndef SUI_FFI_H
+#define SUI_FFI_H
+
+// Generated by cbindgen from crates/sui-ffi, do not edit.
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Length of the addresses written by [sui_address_from_key].
+#define SUI_FFI_ADDRESS_LENGTH 32
+
+typedef enum SuiStatus {
+  SUI_STATUS_OK = 0,
+  SUI_STATUS_NULL_POINTER = 1,
+  SUI_STATUS_INVALID_SCHEME = 2,
+  SUI_STATUS_INVALID_KEY = 3,
+  SUI_STATUS_INVALID_MNEMONIC = 4,
+  SUI_STATUS_INVALID_TRANSACTION_DATA = 5,
+  SUI_STATUS_INVALID_JSON = 6,
+  SUI_STATUS_INVALID_UTF8 = 7,
+  SUI_STATUS_KEY_GENERATION_FAILED = 8,
+  SUI_STATUS_PANIC = 9,
+} SuiStatus;
+
+// Bytes allocated by the library and owned by the caller, to free with [sui_buffer_free].
+typedef struct SuiBuffer {
+  uint8_t *data;
+  uintptr_t len;
+} SuiBuffer;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Frees the bytes of `buffer`, which must have been returned by this library. The bytes are
+// zeroed first, as buffers may hold private keys or mnemonics.
+//
+// # Safety
+//
+// `buffer` must not be used afterwards.
+void sui_buffer_free(struct SuiBuffer buffer);
+
+// Generates a key of the signature scheme of flag `scheme`, from a new 12 words mnemonic, and
+// writes it to `key_out` and the mnemonic to `mnemonic_out`, unless the latter is null.
+//
+// # Safety
+//
+// `key_out` and `mnemonic_out` must be null or valid for writes.
+enum SuiStatus sui_generate_key(uint8_t scheme,
+                                struct SuiBuffer *key_out,
+                                struct SuiBuffer *mnemonic_out);
+
+// Recovers the key of the signature scheme of flag `scheme` derived from the English mnemonic
+// `mnemonic` at the default derivation path, as the CLI does, and writes it to `key_out`.
+//
+// # Safety
+//
+// `mnemonic` must be a NUL terminated string and `key_out` valid for writes.
+enum SuiStatus sui_key_from_mnemonic(uint8_t scheme,
+                                     const char *mnemonic,
+                                     struct SuiBuffer *key_out);
+
+// Writes the [SUI_FFI_ADDRESS_LENGTH] bytes of the address of the key `key` to `address_out`.
+//
+// # Safety
+//
+// `key` must be valid for `key_len` bytes of reads and `address_out` for
+// [SUI_FFI_ADDRESS_LENGTH] bytes of writes.
+enum SuiStatus sui_address_from_key(const uint8_t *key, uintptr_t key_len, uint8_t *address_out);
+
+// Signs the transaction data of BCS serialization `tx_data` with the key `key`, and writes the
+// serialized signature, `flag || signature || public key`, to `signature_out`.
+//
+// # Safety
+//
+// `key` and `tx_data` must be valid for `key_len` and `tx_data_len` bytes of reads, and
+// `signature_out` for writes.
+enum SuiStatus sui_sign_transaction_data(const uint8_t *key,
+                                         uintptr_t key_len,
+                                         const uint8_t *tx_data,
+                                         uintptr_t tx_data_len,
+                                         struct SuiBuffer *signature_out);
+
+// Encodes the transaction data `json`, in the JSON format of its Rust type, to BCS, and writes
+// it to `tx_data_out`.
+//
+// # Safety
+//
+// `json` must be a NUL terminated string and `tx_data_out` valid for writes.
+enum SuiStatus sui_transaction_data_from_json(const char *json, struct SuiBuffer *tx_data_out);
+
+// Decodes the transaction data of BCS serialization `tx_data`, and writes it to `json_out` as
+// UTF-8 JSON, without a NUL terminator.
+//
+// # Safety
+//
+// `tx_data` must be valid for `tx_data_len` bytes of reads and `json_out` for writes.
+enum SuiStatus sui_transaction_data_to_json(const uint8_t *tx_data,
+                                            uintptr_t tx_data_len,
+                                            struct SuiBuffer *json_out);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif // SUI_FFI_H
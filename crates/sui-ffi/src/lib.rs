@@ -0,0 +1,263 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A C ABI over the key generation, address derivation, signing and BCS encoding of Sui, so that
+//! wallets written in other languages, such as Swift and Kotlin, use the same implementations as
+//! the Rust SDK. The header `include/sui_ffi.h` is generated by cbindgen when the crate is built,
+//! and checked in.
+//!
+//! Every function returns a [SuiStatus] and writes its results through out pointers. Keys are
+//! serialized as `flag || private key`, as in keystores. The bytes returned in a [SuiBuffer] are
+//! owned by the caller, who frees them with [sui_buffer_free].
+
+use std::ffi::{c_char, CStr};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use bip39::{Language, Mnemonic, Seed};
+use shared_crypto::intent::{Intent, IntentMessage};
+use sui_keys::key_derive::{derive_key_pair_from_path, generate_new_key};
+use sui_types::base_types::{SuiAddress, SUI_ADDRESS_LENGTH};
+use sui_types::crypto::{Signature, SignatureScheme, SuiKeyPair};
+use sui_types::messages::TransactionData;
+use zeroize::Zeroize;
+
+/// Length of the addresses written by [sui_address_from_key].
+pub const SUI_FFI_ADDRESS_LENGTH: usize = 32;
+
+// A literal above, for cbindgen to write in the header.
+const _: () = assert!(SUI_FFI_ADDRESS_LENGTH == SUI_ADDRESS_LENGTH);
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuiStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidScheme = 2,
+    InvalidKey = 3,
+    InvalidMnemonic = 4,
+    InvalidTransactionData = 5,
+    InvalidJson = 6,
+    InvalidUtf8 = 7,
+    KeyGenerationFailed = 8,
+    Panic = 9,
+}
+
+/// Bytes allocated by the library and owned by the caller, to free with [sui_buffer_free].
+#[repr(C)]
+#[derive(Debug)]
+pub struct SuiBuffer {
+    pub data: *mut u8,
+    pub len: usize,
+}
+
+impl SuiBuffer {
+    fn from_vec(bytes: Vec<u8>) -> Self {
+        let bytes = Box::leak(bytes.into_boxed_slice());
+        Self {
+            data: bytes.as_mut_ptr(),
+            len: bytes.len(),
+        }
+    }
+}
+
+/// Frees the bytes of `buffer`, which must have been returned by this library. The bytes are
+/// zeroed first, as buffers may hold private keys or mnemonics.
+///
+/// # Safety
+///
+/// `buffer` must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn sui_buffer_free(buffer: SuiBuffer) {
+    if !buffer.data.is_null() {
+        let mut bytes = Box::from_raw(std::ptr::slice_from_raw_parts_mut(buffer.data, buffer.len));
+        bytes.zeroize();
+    }
+}
+
+/// Generates a key of the signature scheme of flag `scheme`, from a new 12 words mnemonic, and
+/// writes it to `key_out` and the mnemonic to `mnemonic_out`, unless the latter is null.
+///
+/// # Safety
+///
+/// `key_out` and `mnemonic_out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn sui_generate_key(
+    scheme: u8,
+    key_out: *mut SuiBuffer,
+    mnemonic_out: *mut SuiBuffer,
+) -> SuiStatus {
+    ffi(|| {
+        let scheme = signature_scheme(scheme)?;
+        if key_out.is_null() {
+            return Err(SuiStatus::NullPointer);
+        }
+        let (_, key, _, mnemonic) =
+            generate_new_key(scheme, None, None).map_err(|_| SuiStatus::KeyGenerationFailed)?;
+        write(key_out, key.to_bytes())?;
+        if !mnemonic_out.is_null() {
+            write(mnemonic_out, mnemonic.into_bytes())?;
+        }
+        Ok(())
+    })
+}
+
+/// Recovers the key of the signature scheme of flag `scheme` derived from the English mnemonic
+/// `mnemonic` at the default derivation path, as the CLI does, and writes it to `key_out`.
+///
+/// # Safety
+///
+/// `mnemonic` must be a NUL terminated string and `key_out` valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn sui_key_from_mnemonic(
+    scheme: u8,
+    mnemonic: *const c_char,
+    key_out: *mut SuiBuffer,
+) -> SuiStatus {
+    ffi(|| {
+        let scheme = signature_scheme(scheme)?;
+        let phrase = string(mnemonic)?;
+        let mnemonic = Mnemonic::from_phrase(phrase, Language::English)
+            .map_err(|_| SuiStatus::InvalidMnemonic)?;
+        let (_, key) =
+            derive_key_pair_from_path(Seed::new(&mnemonic, "").as_bytes(), None, &scheme)
+                .map_err(|_| SuiStatus::KeyGenerationFailed)?;
+        write(key_out, key.to_bytes())
+    })
+}
+
+/// Writes the [SUI_FFI_ADDRESS_LENGTH] bytes of the address of the key `key` to `address_out`.
+///
+/// # Safety
+///
+/// `key` must be valid for `key_len` bytes of reads and `address_out` for
+/// [SUI_FFI_ADDRESS_LENGTH] bytes of writes.
+#[no_mangle]
+pub unsafe extern "C" fn sui_address_from_key(
+    key: *const u8,
+    key_len: usize,
+    address_out: *mut u8,
+) -> SuiStatus {
+    ffi(|| {
+        if address_out.is_null() {
+            return Err(SuiStatus::NullPointer);
+        }
+        let key = key_pair(bytes(key, key_len)?)?;
+        let address = SuiAddress::from(&key.public()).to_inner();
+        std::ptr::copy_nonoverlapping(address.as_ptr(), address_out, SUI_ADDRESS_LENGTH);
+        Ok(())
+    })
+}
+
+/// Signs the transaction data of BCS serialization `tx_data` with the key `key`, and writes the
+/// serialized signature, `flag || signature || public key`, to `signature_out`.
+///
+/// # Safety
+///
+/// `key` and `tx_data` must be valid for `key_len` and `tx_data_len` bytes of reads, and
+/// `signature_out` for writes.
+#[no_mangle]
+pub unsafe extern "C" fn sui_sign_transaction_data(
+    key: *const u8,
+    key_len: usize,
+    tx_data: *const u8,
+    tx_data_len: usize,
+    signature_out: *mut SuiBuffer,
+) -> SuiStatus {
+    ffi(|| {
+        let key = key_pair(bytes(key, key_len)?)?;
+        let tx_data: TransactionData = bcs::from_bytes(bytes(tx_data, tx_data_len)?)
+            .map_err(|_| SuiStatus::InvalidTransactionData)?;
+        let signature = Signature::new_secure(
+            &IntentMessage::new(Intent::sui_transaction(), tx_data),
+            &key,
+        );
+        write(signature_out, signature.as_ref().to_vec())
+    })
+}
+
+/// Encodes the transaction data `json`, in the JSON format of its Rust type, to BCS, and writes
+/// it to `tx_data_out`.
+///
+/// # Safety
+///
+/// `json` must be a NUL terminated string and `tx_data_out` valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn sui_transaction_data_from_json(
+    json: *const c_char,
+    tx_data_out: *mut SuiBuffer,
+) -> SuiStatus {
+    ffi(|| {
+        let tx_data: TransactionData =
+            serde_json::from_str(string(json)?).map_err(|_| SuiStatus::InvalidJson)?;
+        let tx_data = bcs::to_bytes(&tx_data).map_err(|_| SuiStatus::InvalidTransactionData)?;
+        write(tx_data_out, tx_data)
+    })
+}
+
+/// Decodes the transaction data of BCS serialization `tx_data`, and writes it to `json_out` as
+/// UTF-8 JSON, without a NUL terminator.
+///
+/// # Safety
+///
+/// `tx_data` must be valid for `tx_data_len` bytes of reads and `json_out` for writes.
+#[no_mangle]
+pub unsafe extern "C" fn sui_transaction_data_to_json(
+    tx_data: *const u8,
+    tx_data_len: usize,
+    json_out: *mut SuiBuffer,
+) -> SuiStatus {
+    ffi(|| {
+        let tx_data: TransactionData = bcs::from_bytes(bytes(tx_data, tx_data_len)?)
+            .map_err(|_| SuiStatus::InvalidTransactionData)?;
+        let json = serde_json::to_vec(&tx_data).map_err(|_| SuiStatus::InvalidJson)?;
+        write(json_out, json)
+    })
+}
+
+/// Runs `f`, so that panics do not unwind into the caller.
+fn ffi(f: impl FnOnce() -> Result<(), SuiStatus>) -> SuiStatus {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => SuiStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => SuiStatus::Panic,
+    }
+}
+
+unsafe fn bytes<'a>(data: *const u8, len: usize) -> Result<&'a [u8], SuiStatus> {
+    if data.is_null() {
+        return Err(SuiStatus::NullPointer);
+    }
+    Ok(std::slice::from_raw_parts(data, len))
+}
+
+unsafe fn string<'a>(string: *const c_char) -> Result<&'a str, SuiStatus> {
+    if string.is_null() {
+        return Err(SuiStatus::NullPointer);
+    }
+    CStr::from_ptr(string)
+        .to_str()
+        .map_err(|_| SuiStatus::InvalidUtf8)
+}
+
+unsafe fn write(out: *mut SuiBuffer, bytes: Vec<u8>) -> Result<(), SuiStatus> {
+    if out.is_null() {
+        return Err(SuiStatus::NullPointer);
+    }
+    out.write(SuiBuffer::from_vec(bytes));
+    Ok(())
+}
+
+fn signature_scheme(flag: u8) -> Result<SignatureScheme, SuiStatus> {
+    match SignatureScheme::from_flag_byte(&flag) {
+        Ok(
+            scheme @ (SignatureScheme::ED25519
+            | SignatureScheme::Secp256k1
+            | SignatureScheme::Secp256r1),
+        ) => Ok(scheme),
+        _ => Err(SuiStatus::InvalidScheme),
+    }
+}
+
+fn key_pair(bytes: &[u8]) -> Result<SuiKeyPair, SuiStatus> {
+    SuiKeyPair::from_bytes(bytes).map_err(|_| SuiStatus::InvalidKey)
+}
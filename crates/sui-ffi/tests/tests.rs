@@ -0,0 +1,150 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::ffi::CString;
+use std::ptr;
+
+use shared_crypto::intent::{Intent, IntentMessage};
+use sui_ffi::*;
+use sui_types::base_types::{random_object_ref, SuiAddress};
+use sui_types::crypto::{Signature, SuiKeyPair, SuiSignature, ToFromBytes};
+use sui_types::messages::TransactionData;
+
+const ED25519: u8 = 0x00;
+
+fn empty_buffer() -> SuiBuffer {
+    SuiBuffer {
+        data: ptr::null_mut(),
+        len: 0,
+    }
+}
+
+/// Copies the bytes of `buffer` and frees it.
+unsafe fn take(buffer: SuiBuffer) -> Vec<u8> {
+    let bytes = std::slice::from_raw_parts(buffer.data, buffer.len).to_vec();
+    sui_buffer_free(buffer);
+    bytes
+}
+
+#[test]
+fn test_generate_key_and_recover_it_from_mnemonic() {
+    unsafe {
+        let (mut key, mut mnemonic) = (empty_buffer(), empty_buffer());
+        assert_eq!(
+            sui_generate_key(ED25519, &mut key, &mut mnemonic),
+            SuiStatus::Ok
+        );
+        let key = take(key);
+        let mnemonic = CString::new(take(mnemonic)).unwrap();
+
+        let mut recovered = empty_buffer();
+        assert_eq!(
+            sui_key_from_mnemonic(ED25519, mnemonic.as_ptr(), &mut recovered),
+            SuiStatus::Ok
+        );
+        assert_eq!(take(recovered), key);
+
+        let mut address = [0; SUI_FFI_ADDRESS_LENGTH];
+        assert_eq!(
+            sui_address_from_key(key.as_ptr(), key.len(), address.as_mut_ptr()),
+            SuiStatus::Ok
+        );
+        let key_pair = SuiKeyPair::from_bytes(&key).unwrap();
+        assert_eq!(address, SuiAddress::from(&key_pair.public()).to_inner());
+    }
+}
+
+#[test]
+fn test_sign_transaction_data() {
+    unsafe {
+        let mut key = empty_buffer();
+        assert_eq!(
+            sui_generate_key(ED25519, &mut key, ptr::null_mut()),
+            SuiStatus::Ok
+        );
+        let key = take(key);
+        let sender = SuiAddress::from(&SuiKeyPair::from_bytes(&key).unwrap().public());
+        let tx_data = TransactionData::new_transfer_sui_with_dummy_gas_price(
+            SuiAddress::random_for_testing_only(),
+            sender,
+            Some(1000),
+            random_object_ref(),
+            10000,
+        );
+        let tx_bytes = bcs::to_bytes(&tx_data).unwrap();
+
+        let mut signature = empty_buffer();
+        assert_eq!(
+            sui_sign_transaction_data(
+                key.as_ptr(),
+                key.len(),
+                tx_bytes.as_ptr(),
+                tx_bytes.len(),
+                &mut signature,
+            ),
+            SuiStatus::Ok
+        );
+        let signature = Signature::from_bytes(&take(signature)).unwrap();
+        signature
+            .verify_secure(
+                &IntentMessage::new(Intent::sui_transaction(), tx_data),
+                sender,
+            )
+            .unwrap();
+
+        assert_eq!(
+            sui_sign_transaction_data(
+                key.as_ptr(),
+                key.len(),
+                tx_bytes.as_ptr(),
+                tx_bytes.len() - 1,
+                &mut empty_buffer(),
+            ),
+            SuiStatus::InvalidTransactionData
+        );
+    }
+}
+
+#[test]
+fn test_transaction_data_json_round_trip() {
+    unsafe {
+        let tx_data = TransactionData::new_transfer_sui_with_dummy_gas_price(
+            SuiAddress::random_for_testing_only(),
+            SuiAddress::random_for_testing_only(),
+            None,
+            random_object_ref(),
+            10000,
+        );
+        let tx_bytes = bcs::to_bytes(&tx_data).unwrap();
+
+        let mut json = empty_buffer();
+        assert_eq!(
+            sui_transaction_data_to_json(tx_bytes.as_ptr(), tx_bytes.len(), &mut json),
+            SuiStatus::Ok
+        );
+        let json = CString::new(take(json)).unwrap();
+
+        let mut encoded = empty_buffer();
+        assert_eq!(
+            sui_transaction_data_from_json(json.as_ptr(), &mut encoded),
+            SuiStatus::Ok
+        );
+        assert_eq!(take(encoded), tx_bytes);
+
+        assert_eq!(
+            sui_transaction_data_from_json(ptr::null(), &mut empty_buffer()),
+            SuiStatus::NullPointer
+        );
+    }
+}
+
+#[test]
+fn test_checked_in_header_is_up_to_date() {
+    let generated = include_str!(concat!(env!("OUT_DIR"), "/sui_ffi.h"));
+    let checked_in = include_str!("../include/sui_ffi.h");
+    assert!(
+        generated == checked_in,
+        "include/sui_ffi.h is out of date, update it from {}/sui_ffi.h",
+        env!("OUT_DIR")
+    );
+}
@@ -88,7 +88,9 @@ impl BuildConfig {
             .any(|(_, name, _)| matches!(name, AttributeName_::Known(KnownAttribute::Testing(_))))
     }
 
-    fn fn_info(
+    /// Whether each function of the modules of `units` is a test, as needed by
+    /// [verify_modules].
+    pub fn fn_info(
         units: &[CompiledUnitEnum<AnnotatedCompiledModule, AnnotatedCompiledScript>],
     ) -> FnInfoMap {
         let mut fn_info_map = BTreeMap::new();
@@ -189,14 +191,7 @@ pub fn build_from_resolution_graph(
     };
     let compiled_modules = package.root_modules_map();
     if run_bytecode_verifier {
-        for m in compiled_modules.iter_modules() {
-            move_bytecode_verifier::verify_module(m).map_err(|err| {
-                SuiError::ModuleVerificationFailure {
-                    error: err.to_string(),
-                }
-            })?;
-            sui_bytecode_verifier::verify_module(m, &fn_info)?;
-        }
+        verify_modules(compiled_modules.iter_modules(), &fn_info)?;
         // TODO(https://github.com/MystenLabs/sui/issues/69): Run Move linker
     }
     Ok(CompiledPackage {
@@ -207,6 +202,22 @@ pub fn build_from_resolution_graph(
     })
 }
 
+/// Runs the Move and Sui bytecode verifiers on `modules`.
+pub fn verify_modules<'a>(
+    modules: impl IntoIterator<Item = &'a CompiledModule>,
+    fn_info: &FnInfoMap,
+) -> SuiResult {
+    for m in modules {
+        move_bytecode_verifier::verify_module(m).map_err(|err| {
+            SuiError::ModuleVerificationFailure {
+                error: err.to_string(),
+            }
+        })?;
+        sui_bytecode_verifier::verify_module(m, fn_info)?;
+    }
+    Ok(())
+}
+
 impl CompiledPackage {
     /// Return all of the bytecode modules in this package (not including direct or transitive deps)
     /// Note: these are not topologically sorted by dependency--use `get_dependency_sorted_modules` to produce a list of modules suitable
@@ -84,9 +84,15 @@ fn create_checkpoint(sequence_number: i64) -> TemporaryCheckpointStore {
             deleted_objects: vec![],
         }],
         addresses: vec![],
+        address_summaries: vec![],
+        address_packages: vec![],
+        address_gas_usage: vec![],
         packages: vec![],
         input_objects: vec![],
         move_calls: vec![],
+        move_call_stats: vec![],
+        move_call_callers: vec![],
+        shared_object_gas_prices: vec![],
         recipients: vec![],
     }
 }
@@ -0,0 +1,8 @@
+-- Named watermarks of indexer clients, the last checkpoint processed by each consumer.
+CREATE TABLE checkpoint_bookmarks
+(
+    name                 VARCHAR(255)  PRIMARY KEY,
+    checkpoint           BIGINT        NOT NULL,
+    -- number of milliseconds from the Unix epoch
+    updated_at_ms        BIGINT        NOT NULL
+);
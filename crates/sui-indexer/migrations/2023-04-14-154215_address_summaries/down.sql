@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS address_packages;
+DROP TABLE IF EXISTS address_summaries;
@@ -0,0 +1,25 @@
+-- Activity of every address, the sender or a recipient of the transactions it is involved in,
+-- updated with every indexed checkpoint.
+CREATE TABLE address_summaries
+(
+    address                  address       PRIMARY KEY,
+    first_seen_checkpoint    BIGINT        NOT NULL,
+    first_seen_tx            base58digest  NOT NULL,
+    first_seen_time          BIGINT        NOT NULL,
+    last_active_checkpoint   BIGINT        NOT NULL,
+    last_active_tx           base58digest  NOT NULL,
+    last_active_time         BIGINT        NOT NULL,
+    transaction_count        BIGINT        NOT NULL,
+    -- net gas used by the transactions sent by the address, negative when their storage
+    -- rebates exceed their costs.
+    gas_spent                BIGINT        NOT NULL
+);
+
+-- The distinct packages called by the transactions sent by every address.
+CREATE TABLE address_packages
+(
+    address                  address       NOT NULL,
+    package                  address       NOT NULL,
+    first_call_checkpoint    BIGINT        NOT NULL,
+    CONSTRAINT address_packages_pk PRIMARY KEY (address, package)
+);
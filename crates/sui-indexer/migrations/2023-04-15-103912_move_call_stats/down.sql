@@ -0,0 +1,4 @@
+DROP TRIGGER IF EXISTS move_call_caller_added ON move_call_callers;
+DROP FUNCTION IF EXISTS move_call_caller_added_func();
+DROP TABLE IF EXISTS move_call_callers;
+DROP TABLE IF EXISTS move_call_stats;
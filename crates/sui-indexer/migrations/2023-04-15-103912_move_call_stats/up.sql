@@ -0,0 +1,49 @@
+-- Statistics of the calls of every Move function in every epoch, updated with every indexed
+-- checkpoint.
+CREATE TABLE move_call_stats
+(
+    epoch                    BIGINT        NOT NULL,
+    move_package             address       NOT NULL,
+    move_module              TEXT          NOT NULL,
+    move_function            TEXT          NOT NULL,
+    call_count               BIGINT        NOT NULL,
+    -- calls in transactions which failed to execute.
+    failed_call_count        BIGINT        NOT NULL,
+    -- number of distinct senders of the calls, maintained by the trigger on move_call_callers.
+    unique_callers           BIGINT        NOT NULL DEFAULT 0,
+    -- gas used by the transactions calling the function, once per transaction.
+    total_gas                BIGINT        NOT NULL,
+    CONSTRAINT move_call_stats_pk PRIMARY KEY (epoch, move_package, move_module, move_function)
+);
+CREATE INDEX move_call_stats_epoch_call_count ON move_call_stats (epoch, call_count);
+
+-- The distinct senders of the calls of every Move function in every epoch.
+CREATE TABLE move_call_callers
+(
+    epoch                    BIGINT        NOT NULL,
+    move_package             address       NOT NULL,
+    move_module              TEXT          NOT NULL,
+    move_function            TEXT          NOT NULL,
+    sender                   address       NOT NULL,
+    CONSTRAINT move_call_callers_pk PRIMARY KEY (epoch, move_package, move_module, move_function, sender)
+);
+
+CREATE OR REPLACE FUNCTION move_call_caller_added_func() RETURNS TRIGGER AS
+$body$
+BEGIN
+    UPDATE move_call_stats
+    SET unique_callers = unique_callers + 1
+    WHERE epoch = NEW.epoch
+      AND move_package = NEW.move_package
+      AND move_module = NEW.move_module
+      AND move_function = NEW.move_function;
+    RETURN NEW;
+END;
+$body$
+    LANGUAGE plpgsql;
+
+CREATE TRIGGER move_call_caller_added
+    AFTER INSERT
+    ON move_call_callers
+    FOR EACH ROW
+EXECUTE PROCEDURE move_call_caller_added_func();
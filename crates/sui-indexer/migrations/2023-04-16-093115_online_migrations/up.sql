@@ -0,0 +1,13 @@
+-- Progress of the online migrations of the indexer tables, see `src/migration.rs`.
+CREATE TABLE online_migrations
+(
+    name                 VARCHAR(255)  PRIMARY KEY,
+    table_name           VARCHAR(255)  NOT NULL,
+    -- `backfilling` or `complete`
+    phase                VARCHAR(255)  NOT NULL,
+    -- key of the last row copied to the shadow table
+    backfill_cursor      BIGINT,
+    rows_copied          BIGINT        NOT NULL,
+    -- number of milliseconds from the Unix epoch
+    updated_at_ms        BIGINT        NOT NULL
+);
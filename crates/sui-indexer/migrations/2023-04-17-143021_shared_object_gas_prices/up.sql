@@ -0,0 +1,10 @@
+-- The gas prices paid by the transactions using shared objects, which wait in consensus for
+-- their turn and are ordered by gas price, to recommend gas prices from the recent ones.
+CREATE TABLE shared_object_gas_prices
+(
+    transaction_digest          base58digest  PRIMARY KEY,
+    checkpoint_sequence_number  BIGINT        NOT NULL,
+    -- gas price from transaction data, not the reference gas price
+    gas_price                   BIGINT        NOT NULL
+);
+CREATE INDEX shared_object_gas_prices_checkpoint_sequence_number ON shared_object_gas_prices (checkpoint_sequence_number);
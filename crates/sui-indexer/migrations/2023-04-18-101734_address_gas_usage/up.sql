@@ -0,0 +1,13 @@
+-- Gas fees paid and storage rebates received by every address in every epoch, as the gas owner
+-- of the transactions, updated with every indexed checkpoint.
+CREATE TABLE address_gas_usage
+(
+    address                     address       NOT NULL,
+    epoch                       BIGINT        NOT NULL,
+    transaction_count           BIGINT        NOT NULL,
+    computation_cost            BIGINT        NOT NULL,
+    storage_cost                BIGINT        NOT NULL,
+    storage_rebate              BIGINT        NOT NULL,
+    non_refundable_storage_fee  BIGINT        NOT NULL,
+    CONSTRAINT address_gas_usage_pk PRIMARY KEY (address, epoch)
+);
@@ -6,27 +6,72 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::RpcModule;
 
 use sui_json_rpc::api::{
-    validate_limit, ExtendedApiServer, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS,
+    validate_limit, ExtendedApiServer, QUERY_MAX_RESULT_LIMIT, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS,
     QUERY_MAX_RESULT_LIMIT_OBJECTS,
 };
 use sui_json_rpc::SuiRpcModule;
 use sui_json_rpc_types::{
-    CheckpointedObjectID, EpochInfo, EpochPage, MoveCallMetrics, NetworkMetrics, Page,
+    AddressGasUsagePage, AddressSummary, CheckpointBookmark, CheckpointedObjectID, EpochCommittee,
+    EpochInfo, EpochPage, GasPriceRecommendation, MoveCallMetrics, MoveCallStats,
+    MoveCallStatsOrder, NetworkMetrics, ObjectOwnershipHistoryPage, ObjectsSnapshotRange, Page,
     QueryObjectsPage, SuiObjectDataFilter, SuiObjectResponse, SuiObjectResponseQuery,
 };
 use sui_open_rpc::Module;
+use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
 use sui_types::sui_serde::BigInt;
 
 use crate::errors::IndexerError;
 use crate::store::IndexerStore;
 
+/// Maximum length of the name of a checkpoint bookmark, the size of the column of the table.
+const MAX_CHECKPOINT_BOOKMARK_NAME_LENGTH: usize = 255;
+/// Number of last checkpoints the gas price recommendations are computed from by default.
+const DEFAULT_GAS_PRICE_CHECKPOINTS: usize = 100;
+
 pub(crate) struct ExtendedApi<S> {
     state: S,
+    /// Number of checkpoints the consistent checkpoint of the objects trails the latest indexed
+    /// checkpoint by.
+    objects_snapshot_lag: u64,
+    /// Number of checkpoints before the consistent checkpoint the cursors of object queries can
+    /// read the objects at, unlimited if `None`.
+    objects_snapshot_window: Option<u64>,
+    /// Token the clients setting checkpoint bookmarks have to provide, bookmarks cannot be set if
+    /// `None`.
+    checkpoint_bookmark_auth_token: Option<String>,
 }
 
 impl<S: IndexerStore> ExtendedApi<S> {
-    pub fn new(state: S) -> Self {
-        Self { state }
+    pub fn new(
+        state: S,
+        objects_snapshot_lag: u64,
+        objects_snapshot_window: Option<u64>,
+        checkpoint_bookmark_auth_token: Option<String>,
+    ) -> Self {
+        Self {
+            state,
+            objects_snapshot_lag,
+            objects_snapshot_window,
+            checkpoint_bookmark_auth_token,
+        }
+    }
+
+    async fn objects_snapshot_range(&self) -> Result<ObjectsSnapshotRange, IndexerError> {
+        // -1 if no checkpoint is indexed yet.
+        let latest_checkpoint = self
+            .state
+            .get_latest_checkpoint_sequence_number()
+            .await?
+            .max(0) as u64;
+        let consistent_checkpoint = latest_checkpoint.saturating_sub(self.objects_snapshot_lag);
+        let earliest_checkpoint = self
+            .objects_snapshot_window
+            .map_or(0, |window| consistent_checkpoint.saturating_sub(window));
+        Ok(ObjectsSnapshotRange {
+            consistent_checkpoint,
+            earliest_checkpoint,
+            latest_checkpoint,
+        })
     }
 
     async fn query_objects_internal(
@@ -37,14 +82,21 @@ impl<S: IndexerStore> ExtendedApi<S> {
     ) -> Result<QueryObjectsPage, IndexerError> {
         let limit = validate_limit(limit, QUERY_MAX_RESULT_LIMIT_OBJECTS)?;
 
+        let range = self.objects_snapshot_range().await?;
         let at_checkpoint = if let Some(CheckpointedObjectID {
             at_checkpoint: Some(cp),
             ..
         }) = cursor
         {
+            if cp < range.earliest_checkpoint || cp > range.latest_checkpoint {
+                return Err(IndexerError::InvalidArgumentError(format!(
+                    "Objects cannot be read as of checkpoint {cp} of the cursor, the snapshot range is {} to {}, restart the query without a cursor",
+                    range.earliest_checkpoint, range.latest_checkpoint
+                )));
+            }
             cp
         } else {
-            self.state.get_latest_checkpoint_sequence_number().await? as u64
+            range.consistent_checkpoint
         };
 
         let object_cursor = cursor.as_ref().map(|c| c.object_id);
@@ -80,8 +132,64 @@ impl<S: IndexerStore> ExtendedApi<S> {
             data,
             next_cursor,
             has_next_page,
+            truncated_due_to_limit: false,
         })
     }
+
+    async fn set_checkpoint_bookmark_internal(
+        &self,
+        name: String,
+        checkpoint: u64,
+        expected_checkpoint: Option<u64>,
+        auth_token: String,
+    ) -> Result<CheckpointBookmark, IndexerError> {
+        match &self.checkpoint_bookmark_auth_token {
+            None => {
+                return Err(IndexerError::NotSupportedError(
+                    "Checkpoint bookmarks cannot be set on this indexer".to_string(),
+                ))
+            }
+            Some(token) if *token != auth_token => {
+                return Err(IndexerError::InvalidArgumentError(
+                    "Invalid checkpoint bookmark auth token".to_string(),
+                ))
+            }
+            Some(_) => (),
+        }
+        if name.is_empty() || name.len() > MAX_CHECKPOINT_BOOKMARK_NAME_LENGTH {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Checkpoint bookmark name must have 1 to {MAX_CHECKPOINT_BOOKMARK_NAME_LENGTH} characters"
+            )));
+        }
+        let latest_checkpoint = self.state.get_latest_checkpoint_sequence_number().await?;
+        if checkpoint as i64 > latest_checkpoint {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Checkpoint {checkpoint} is not indexed yet, the latest indexed checkpoint is {latest_checkpoint}"
+            )));
+        }
+
+        match self
+            .state
+            .compare_and_set_checkpoint_bookmark(&name, checkpoint, expected_checkpoint)
+            .await?
+        {
+            Some(bookmark) => Ok(bookmark),
+            None => {
+                let current = self
+                    .state
+                    .get_checkpoint_bookmark(&name)
+                    .await?
+                    .map(|bookmark| bookmark.checkpoint.to_string())
+                    .unwrap_or_else(|| "not set".to_string());
+                Err(IndexerError::InvalidArgumentError(format!(
+                    "Checkpoint bookmark {name} was expected to be at {}, but is {current}",
+                    expected_checkpoint
+                        .map(|c| c.to_string())
+                        .unwrap_or_else(|| "not set".to_string())
+                )))
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -108,6 +216,7 @@ impl<S: IndexerStore + Sync + Send + 'static> ExtendedApiServer for ExtendedApi<
             data: epochs,
             next_cursor: next_cursor.map(|id| id.into()),
             has_next_page,
+            truncated_due_to_limit: false,
         })
     }
 
@@ -115,6 +224,28 @@ impl<S: IndexerStore + Sync + Send + 'static> ExtendedApiServer for ExtendedApi<
         Ok(self.state.get_current_epoch().await?)
     }
 
+    async fn get_committee_history(
+        &self,
+        from_epoch: BigInt<u64>,
+        to_epoch: BigInt<u64>,
+    ) -> RpcResult<Vec<EpochCommittee>> {
+        let (from_epoch, to_epoch) = (*from_epoch, *to_epoch);
+        if from_epoch > to_epoch {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "From epoch {from_epoch} is after to epoch {to_epoch}"
+            ))
+            .into());
+        }
+        validate_limit(
+            Some(((to_epoch - from_epoch) as usize).saturating_add(1)),
+            QUERY_MAX_RESULT_LIMIT_CHECKPOINTS,
+        )?;
+        Ok(self
+            .state
+            .get_committee_history(from_epoch, to_epoch)
+            .await?)
+    }
+
     async fn query_objects(
         &self,
         query: SuiObjectResponseQuery,
@@ -126,6 +257,93 @@ impl<S: IndexerStore + Sync + Send + 'static> ExtendedApiServer for ExtendedApi<
             .await?)
     }
 
+    async fn get_objects_snapshot_range(&self) -> RpcResult<ObjectsSnapshotRange> {
+        Ok(self.objects_snapshot_range().await?)
+    }
+
+    async fn get_object_ownership_history(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<BigInt<u64>>,
+        limit: Option<BigInt<u64>>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<ObjectOwnershipHistoryPage> {
+        let limit = validate_limit(limit.map(|l| *l as usize), QUERY_MAX_RESULT_LIMIT)?;
+        let mut changes = self
+            .state
+            .get_object_ownership_history(
+                object_id,
+                cursor.map(|c| SequenceNumber::from_u64(*c)),
+                limit + 1,
+                descending_order,
+            )
+            .await?;
+
+        let has_next_page = changes.len() > limit;
+        changes.truncate(limit);
+        let next_cursor = changes.last().map(|c| c.version.value().into());
+        Ok(Page {
+            data: changes,
+            next_cursor,
+            has_next_page,
+            truncated_due_to_limit: false,
+        })
+    }
+
+    async fn get_address_summary(&self, address: SuiAddress) -> RpcResult<Option<AddressSummary>> {
+        Ok(self.state.get_address_summary(address).await?)
+    }
+
+    async fn get_address_gas_usage(
+        &self,
+        address: SuiAddress,
+        from_epoch: Option<BigInt<u64>>,
+        to_epoch: Option<BigInt<u64>>,
+        cursor: Option<BigInt<u64>>,
+        limit: Option<BigInt<u64>>,
+    ) -> RpcResult<AddressGasUsagePage> {
+        let limit = validate_limit(limit.map(|l| *l as usize), QUERY_MAX_RESULT_LIMIT)?;
+        // The next page starts after the epoch of the cursor.
+        let from_epoch = from_epoch
+            .map(|e| *e)
+            .max(cursor.map(|c| c.saturating_add(1)));
+        let mut usage = self
+            .state
+            .get_address_gas_usage(address, from_epoch, to_epoch.map(|e| *e), limit + 1)
+            .await?;
+
+        let has_next_page = usage.len() > limit;
+        usage.truncate(limit);
+        let next_cursor = usage.last().map(|u| u.epoch.into());
+        Ok(Page {
+            data: usage,
+            next_cursor,
+            has_next_page,
+            truncated_due_to_limit: false,
+        })
+    }
+
+    async fn get_checkpoint_bookmark(&self, name: String) -> RpcResult<Option<CheckpointBookmark>> {
+        Ok(self.state.get_checkpoint_bookmark(&name).await?)
+    }
+
+    async fn set_checkpoint_bookmark(
+        &self,
+        name: String,
+        checkpoint: BigInt<u64>,
+        expected_checkpoint: Option<BigInt<u64>>,
+        auth_token: String,
+    ) -> RpcResult<CheckpointBookmark> {
+        Ok(self
+            .set_checkpoint_bookmark_internal(
+                name,
+                *checkpoint,
+                expected_checkpoint.map(|c| *c),
+                auth_token,
+            )
+            .await?)
+    }
+
     async fn get_network_metrics(&self) -> RpcResult<NetworkMetrics> {
         Ok(self.state.get_network_metrics().await?)
     }
@@ -133,6 +351,31 @@ impl<S: IndexerStore + Sync + Send + 'static> ExtendedApiServer for ExtendedApi<
     async fn get_move_call_metrics(&self) -> RpcResult<MoveCallMetrics> {
         Ok(self.state.get_move_call_metrics().await?)
     }
+
+    async fn get_move_call_stats(
+        &self,
+        epoch: BigInt<u64>,
+        package: Option<ObjectID>,
+        order: Option<MoveCallStatsOrder>,
+        limit: Option<BigInt<u64>>,
+    ) -> RpcResult<Vec<MoveCallStats>> {
+        let limit = validate_limit(limit.map(|l| *l as usize), QUERY_MAX_RESULT_LIMIT)?;
+        Ok(self
+            .state
+            .get_move_call_stats(*epoch, package, order.unwrap_or_default(), limit)
+            .await?)
+    }
+
+    async fn get_gas_price_recommendation(
+        &self,
+        checkpoints: Option<BigInt<u64>>,
+    ) -> RpcResult<GasPriceRecommendation> {
+        let checkpoints = validate_limit(
+            Some(checkpoints.map_or(DEFAULT_GAS_PRICE_CHECKPOINTS, |c| *c as usize)),
+            QUERY_MAX_RESULT_LIMIT,
+        )?;
+        Ok(self.state.get_gas_price_recommendation(checkpoints).await?)
+    }
 }
 
 impl<S> SuiRpcModule for ExtendedApi<S>
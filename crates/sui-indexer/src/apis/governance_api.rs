@@ -10,8 +10,10 @@ use sui_json_rpc::api::{GovernanceReadApiClient, GovernanceReadApiServer};
 use sui_json_rpc::SuiRpcModule;
 use sui_json_rpc_types::DelegatedStake;
 use sui_json_rpc_types::SuiCommittee;
+use sui_json_rpc_types::{ValidatorEpochOutcomePage, ValidatorReport};
 use sui_open_rpc::Module;
 use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::event::EventID;
 use sui_types::sui_serde::BigInt;
 use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
 
@@ -47,6 +49,22 @@ impl GovernanceReadApiServer for GovernanceReadApi {
         self.fullnode.get_latest_sui_system_state().await
     }
 
+    async fn get_validator_reports(&self) -> RpcResult<Vec<ValidatorReport>> {
+        self.fullnode.get_validator_reports().await
+    }
+
+    async fn get_validator_epoch_outcomes(
+        &self,
+        validator: Option<SuiAddress>,
+        cursor: Option<EventID>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<ValidatorEpochOutcomePage> {
+        self.fullnode
+            .get_validator_epoch_outcomes(validator, cursor, limit, descending_order)
+            .await
+    }
+
     async fn get_reference_gas_price(&self) -> RpcResult<BigInt<u64>> {
         self.fullnode.get_reference_gas_price().await
     }
@@ -9,6 +9,7 @@ use futures::executor::block_on;
 use futures::future::join_all;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::http_client::HttpClient;
+use jsonrpsee::types::error::CallError;
 use jsonrpsee::types::SubscriptionResult;
 use jsonrpsee::{RpcModule, SubscriptionSink};
 
@@ -243,6 +244,7 @@ impl<S: IndexerStore> IndexerApi<S> {
             data: sui_tx_resp_vec,
             next_cursor,
             has_next_page,
+            truncated_due_to_limit: false,
         })
     }
 
@@ -292,6 +294,7 @@ impl<S: IndexerStore> IndexerApi<S> {
             data,
             next_cursor,
             has_next_page,
+            truncated_due_to_limit: false,
         })
     }
 }
@@ -368,6 +371,46 @@ where
         ))?)
     }
 
+    fn subscribe_owned_object_changes(
+        &self,
+        mut sink: SubscriptionSink,
+        _address: SuiAddress,
+    ) -> SubscriptionResult {
+        // The previous owners of the objects changed by the transactions of a checkpoint are not
+        // known to the checkpoint handler.
+        sink.reject(CallError::Failed(anyhow!(
+            "Owned object change subscriptions are only served by fullnodes"
+        )))?;
+        Ok(())
+    }
+
+    fn subscribe_transaction_status(
+        &self,
+        mut sink: SubscriptionSink,
+        _digest: TransactionDigest,
+    ) -> SubscriptionResult {
+        // Transactions are only known to the indexer once their checkpoint is indexed.
+        sink.reject(CallError::Failed(anyhow!(
+            "Transaction status subscriptions are only served by fullnodes"
+        )))?;
+        Ok(())
+    }
+
+    fn export_dynamic_fields(
+        &self,
+        mut sink: SubscriptionSink,
+        _parent_object_id: ObjectID,
+        _cursor: Option<ObjectID>,
+        _page_size: Option<usize>,
+    ) -> SubscriptionResult {
+        // The dynamic fields are not indexed, forwarding the pages of the fullnode would need a
+        // websocket connection to it.
+        sink.reject(CallError::Failed(anyhow!(
+            "Dynamic field export is only served by fullnodes"
+        )))?;
+        Ok(())
+    }
+
     fn get_dynamic_fields(
         &self,
         parent_object_id: ObjectID,
@@ -390,7 +433,15 @@ where
             .await
     }
 
-    fn subscribe_event(&self, sink: SubscriptionSink, filter: EventFilter) -> SubscriptionResult {
+    fn subscribe_event(
+        &self,
+        mut sink: SubscriptionSink,
+        filter: EventFilter,
+    ) -> SubscriptionResult {
+        if let Err(e) = filter.validate() {
+            sink.reject(CallError::InvalidParams(e))?;
+            return Ok(());
+        }
         spawn_subscription(sink, self.event_handler.subscribe(filter));
         Ok(())
     }
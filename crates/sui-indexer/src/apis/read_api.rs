@@ -11,9 +11,10 @@ use jsonrpsee::RpcModule;
 use sui_json_rpc::api::{ReadApiClient, ReadApiServer};
 use sui_json_rpc::SuiRpcModule;
 use sui_json_rpc_types::{
-    Checkpoint, CheckpointId, CheckpointPage, SuiEvent, SuiGetPastObjectRequest,
-    SuiObjectDataOptions, SuiObjectResponse, SuiPastObjectResponse, SuiTransactionBlockResponse,
-    SuiTransactionBlockResponseOptions,
+    Checkpoint, CheckpointId, CheckpointPage, SuiCheckpointCertificate, SuiEvent,
+    SuiGetPastObjectRequest, SuiObjectDataOptions, SuiObjectLockStatus, SuiObjectProof,
+    SuiObjectResponse, SuiObjectVersionsDiff, SuiPastObjectResponse, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions, SuiTransactionProof,
 };
 use sui_open_rpc::Module;
 use sui_types::base_types::{ObjectID, SequenceNumber};
@@ -211,6 +212,42 @@ where
         )
     }
 
+    async fn diff_object_versions(
+        &self,
+        object_id: ObjectID,
+        from_version: SequenceNumber,
+        to_version: SequenceNumber,
+    ) -> RpcResult<SuiObjectVersionsDiff> {
+        self.fullnode
+            .diff_object_versions(object_id, from_version, to_version)
+            .await
+    }
+
+    fn get_object_lock_status(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> RpcResult<SuiObjectLockStatus> {
+        // Locks are only known by the nodes processing transactions.
+        block_on(self.fullnode.get_object_lock_status(object_id, version))
+    }
+
+    fn get_object_proof(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> RpcResult<SuiObjectProof> {
+        block_on(self.fullnode.get_object_proof(object_id, version))
+    }
+
+    fn get_transaction_proof(&self, digest: TransactionDigest) -> RpcResult<SuiTransactionProof> {
+        block_on(self.fullnode.get_transaction_proof(digest))
+    }
+
+    fn get_checkpoint_certificate(&self, id: CheckpointId) -> RpcResult<SuiCheckpointCertificate> {
+        block_on(self.fullnode.get_checkpoint_certificate(id))
+    }
+
     async fn get_latest_checkpoint_sequence_number(&self) -> RpcResult<BigInt<u64>> {
         if !self
             .migrated_methods
@@ -10,8 +10,8 @@ use jsonrpsee::RpcModule;
 use sui_json_rpc::api::{WriteApiClient, WriteApiServer};
 use sui_json_rpc::SuiRpcModule;
 use sui_json_rpc_types::{
-    DevInspectResults, DryRunTransactionBlockResponse, SuiTransactionBlockEffectsAPI,
-    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    DevInspectResults, DryRunTransactionBlockResponse, GasBudgetEstimate, GasBudgetSafetyMargin,
+    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
 use sui_open_rpc::Module;
 use sui_types::base_types::SuiAddress;
@@ -54,7 +54,9 @@ where
         options: Option<SuiTransactionBlockResponseOptions>,
         request_type: Option<ExecuteTransactionRequestType>,
     ) -> RpcResult<SuiTransactionBlockResponse> {
-        let fast_path_options = SuiTransactionBlockResponseOptions::full_content();
+        let options = options.unwrap_or_default();
+        let mut fast_path_options = SuiTransactionBlockResponseOptions::full_content();
+        fast_path_options.show_effects_certificate = options.show_effects_certificate;
         let sui_transaction_response = self
             .fullnode
             .execute_transaction_block(tx_bytes, signatures, Some(fast_path_options), request_type)
@@ -82,7 +84,7 @@ where
 
         Ok(SuiTransactionBlockResponseWithOptions {
             response: sui_transaction_response,
-            options: options.unwrap_or_default(),
+            options,
         }
         .into())
     }
@@ -93,9 +95,16 @@ where
         tx_bytes: Base64,
         gas_price: Option<BigInt<u64>>,
         epoch: Option<BigInt<u64>>,
+        clock_timestamp_ms: Option<BigInt<u64>>,
     ) -> RpcResult<DevInspectResults> {
         self.fullnode
-            .dev_inspect_transaction_block(sender_address, tx_bytes, gas_price, epoch)
+            .dev_inspect_transaction_block(
+                sender_address,
+                tx_bytes,
+                gas_price,
+                epoch,
+                clock_timestamp_ms,
+            )
             .await
     }
 
@@ -105,6 +114,16 @@ where
     ) -> RpcResult<DryRunTransactionBlockResponse> {
         self.fullnode.dry_run_transaction_block(tx_bytes).await
     }
+
+    async fn estimate_gas_budget(
+        &self,
+        tx_bytes: Base64,
+        safety_margin: Option<GasBudgetSafetyMargin>,
+    ) -> RpcResult<GasBudgetEstimate> {
+        self.fullnode
+            .estimate_gas_budget(tx_bytes, safety_margin)
+            .await
+    }
 }
 
 impl<S> SuiRpcModule for WriteApi<S>
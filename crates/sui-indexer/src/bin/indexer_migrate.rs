@@ -0,0 +1,73 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::{Parser, Subcommand};
+use tracing::error;
+
+use sui_indexer::errors::IndexerError;
+use sui_indexer::migration::{self, DEFAULT_BACKFILL_BATCH_SIZE, ONLINE_MIGRATIONS};
+use sui_indexer::{get_pg_pool_connection, new_pg_connection_pool};
+
+#[derive(Parser, Clone, Debug)]
+#[clap(
+    name = "Sui indexer migrate",
+    about = "Migrates the schema of the indexer database while the indexer is running",
+    rename_all = "kebab-case"
+)]
+struct MigrateConfig {
+    #[clap(long)]
+    db_url: String,
+    #[clap(subcommand)]
+    command: MigrateCommand,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+#[clap(rename_all = "kebab-case")]
+enum MigrateCommand {
+    /// Lists the migrations, and whether they are applied.
+    Status,
+    /// Lists the steps applying the pending migrations.
+    Plan,
+    /// Applies the pending migrations, resuming an interrupted backfill.
+    Apply {
+        /// Number of rows copied by each transaction of a backfill.
+        #[clap(long, default_value_t = DEFAULT_BACKFILL_BATCH_SIZE)]
+        batch_size: i64,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<(), IndexerError> {
+    // NOTE: this is to print out tracing like info, warn & error.
+    let _guard = telemetry_subscribers::TelemetryConfig::new()
+        .with_env()
+        .init();
+
+    let config = MigrateConfig::parse();
+    let (blocking_cp, _) = new_pg_connection_pool(&config.db_url).await.map_err(|e| {
+        error!(
+            "Failed creating Postgres connection pool with error {:?}",
+            e
+        );
+        e
+    })?;
+    let mut conn = get_pg_pool_connection(&blocking_cp)?;
+    match config.command {
+        MigrateCommand::Status => {
+            print!("{}", migration::status(&mut conn, ONLINE_MIGRATIONS)?);
+        }
+        MigrateCommand::Plan => {
+            let steps = migration::plan(&mut conn, ONLINE_MIGRATIONS)?;
+            if steps.is_empty() {
+                println!("The database is up to date.");
+            }
+            for (i, step) in steps.iter().enumerate() {
+                println!("{}. {step}", i + 1);
+            }
+        }
+        MigrateCommand::Apply { batch_size } => {
+            migration::apply(&mut conn, ONLINE_MIGRATIONS, batch_size)?;
+        }
+    }
+    Ok(())
+}
@@ -41,6 +41,9 @@ pub enum IndexerError {
     #[error("Indexer failed to reset PostgresDB with error: `{0}`")]
     PostgresResetError(String),
 
+    #[error("Indexer failed to migrate PostgresDB with error: `{0}`")]
+    PostgresMigrationError(String),
+
     #[error("Indexer failed to commit changes to PostgresDB with error: `{0}`")]
     PostgresWriteError(String),
 
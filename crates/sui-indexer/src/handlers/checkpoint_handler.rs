@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 
 use fastcrypto::traits::ToFromBytes;
@@ -34,10 +34,14 @@ use sui_types::SUI_SYSTEM_ADDRESS;
 
 use crate::errors::IndexerError;
 use crate::metrics::IndexerCheckpointHandlerMetrics;
+use crate::models::addresses::{AddressPackage, DBAddressGasUsage, DBAddressSummary};
 use crate::models::checkpoints::Checkpoint;
 use crate::models::epoch::{DBEpochInfo, SystemEpochInfoEvent};
+use crate::models::gas_prices::SharedObjectGasPrice;
+use crate::models::move_call_stats::{DBMoveCallStats, MoveCallCaller};
 use crate::models::objects::{DeletedObject, Object, ObjectStatus};
 use crate::models::packages::Package;
+use crate::models::transaction_index::MoveCall;
 use crate::models::transactions::Transaction;
 use crate::store::{
     CheckpointData, IndexerStore, TemporaryCheckpointStore, TemporaryEpochStore,
@@ -454,10 +458,21 @@ where
             .into_iter()
             .flatten()
             .collect::<Vec<_>>();
-        let move_calls = transactions
+        let move_calls: Vec<MoveCall> = transactions
             .iter()
             .flat_map(|tx| tx.get_move_calls(checkpoint.epoch, checkpoint.sequence_number))
             .collect();
+        let (move_call_stats, move_call_callers) =
+            Self::index_move_call_stats(checkpoint.epoch, checkpoint.sequence_number, transactions);
+        let shared_object_gas_prices = transactions
+            .iter()
+            .filter(|tx| !tx.effects.shared_objects().is_empty())
+            .map(|tx| SharedObjectGasPrice {
+                transaction_digest: tx.digest.to_string(),
+                checkpoint_sequence_number: checkpoint.sequence_number as i64,
+                gas_price: tx.transaction.data.gas_data().price as i64,
+            })
+            .collect();
         let recipients = transactions
             .iter()
             .flat_map(|tx| tx.get_recipients(checkpoint.epoch, checkpoint.sequence_number))
@@ -468,6 +483,13 @@ where
             .iter()
             .flat_map(|tx| tx.get_addresses(checkpoint.epoch, checkpoint.sequence_number))
             .collect();
+        let (address_summaries, address_packages) = Self::index_address_summaries(
+            checkpoint.epoch,
+            checkpoint.sequence_number,
+            transactions,
+            &move_calls,
+        );
+        let address_gas_usage = Self::index_address_gas_usage(checkpoint.epoch, transactions);
 
         // Index epoch
         let epoch_index = if checkpoint.epoch == 0 && checkpoint.sequence_number == 0 {
@@ -585,15 +607,166 @@ where
                 events,
                 objects_changes,
                 addresses,
+                address_summaries,
+                address_packages,
+                address_gas_usage,
                 packages,
                 input_objects,
                 move_calls,
+                move_call_stats,
+                move_call_callers,
+                shared_object_gas_prices,
                 recipients,
             },
             epoch_index,
         ))
     }
 
+    /// Rolls up the activity of the addresses involved in the transactions of a checkpoint, as
+    /// sender or recipient, and the packages called by the senders.
+    fn index_address_summaries(
+        epoch: EpochId,
+        checkpoint: CheckpointSequenceNumber,
+        transactions: &[CheckpointTransactionBlockResponse],
+        move_calls: &[MoveCall],
+    ) -> (Vec<DBAddressSummary>, Vec<AddressPackage>) {
+        let mut summaries: BTreeMap<String, DBAddressSummary> = BTreeMap::new();
+        for tx in transactions {
+            let sender = tx.transaction.data.sender().to_string();
+            let digest = tx.digest.to_string();
+            let time = tx.timestamp_ms as i64;
+            let mut involved: BTreeSet<_> = tx
+                .get_recipients(epoch, checkpoint)
+                .into_iter()
+                .map(|r| r.recipient)
+                .collect();
+            involved.insert(sender.clone());
+            for address in involved {
+                let gas_spent = if address == sender {
+                    tx.effects.gas_cost_summary().net_gas_usage()
+                } else {
+                    0
+                };
+                summaries
+                    .entry(address.clone())
+                    .and_modify(|summary| {
+                        summary.last_active_tx = digest.clone();
+                        summary.last_active_time = time;
+                        summary.transaction_count += 1;
+                        summary.gas_spent += gas_spent;
+                    })
+                    .or_insert_with(|| DBAddressSummary {
+                        address,
+                        first_seen_checkpoint: checkpoint as i64,
+                        first_seen_tx: digest.clone(),
+                        first_seen_time: time,
+                        last_active_checkpoint: checkpoint as i64,
+                        last_active_tx: digest.clone(),
+                        last_active_time: time,
+                        transaction_count: 1,
+                        gas_spent,
+                    });
+            }
+        }
+
+        let packages: BTreeSet<_> = move_calls
+            .iter()
+            .map(|call| (call.sender.clone(), call.move_package.clone()))
+            .collect();
+        let packages = packages
+            .into_iter()
+            .map(|(address, package)| AddressPackage {
+                address,
+                package,
+                first_call_checkpoint: checkpoint as i64,
+            })
+            .collect();
+        (summaries.into_values().collect(), packages)
+    }
+
+    /// Rolls up the gas paid by the gas owners of the transactions of a checkpoint.
+    fn index_address_gas_usage(
+        epoch: EpochId,
+        transactions: &[CheckpointTransactionBlockResponse],
+    ) -> Vec<DBAddressGasUsage> {
+        let mut usage: BTreeMap<String, DBAddressGasUsage> = BTreeMap::new();
+        for tx in transactions {
+            let address = tx.transaction.data.gas_data().owner.to_string();
+            let gas = tx.effects.gas_cost_summary();
+            let entry = usage
+                .entry(address.clone())
+                .or_insert_with(|| DBAddressGasUsage {
+                    address,
+                    epoch: epoch as i64,
+                    transaction_count: 0,
+                    computation_cost: 0,
+                    storage_cost: 0,
+                    storage_rebate: 0,
+                    non_refundable_storage_fee: 0,
+                });
+            entry.transaction_count += 1;
+            entry.computation_cost += gas.computation_cost as i64;
+            entry.storage_cost += gas.storage_cost as i64;
+            entry.storage_rebate += gas.storage_rebate as i64;
+            entry.non_refundable_storage_fee += gas.non_refundable_storage_fee as i64;
+        }
+        usage.into_values().collect()
+    }
+
+    /// Rolls up the calls of every Move function in the transactions of a checkpoint, and their
+    /// senders.
+    fn index_move_call_stats(
+        epoch: EpochId,
+        checkpoint: CheckpointSequenceNumber,
+        transactions: &[CheckpointTransactionBlockResponse],
+    ) -> (Vec<DBMoveCallStats>, Vec<MoveCallCaller>) {
+        let mut stats: BTreeMap<(String, String, String), DBMoveCallStats> = BTreeMap::new();
+        let mut callers = BTreeSet::new();
+        for tx in transactions {
+            let failed = !tx.effects.status().is_ok();
+            let gas_used = tx.effects.gas_cost_summary().gas_used() as i64;
+            let mut called = BTreeSet::new();
+            for call in tx.get_move_calls(epoch, checkpoint) {
+                let function = (call.move_package, call.move_module, call.move_function);
+                let entry = stats
+                    .entry(function.clone())
+                    .or_insert_with(|| DBMoveCallStats {
+                        epoch: epoch as i64,
+                        move_package: function.0.clone(),
+                        move_module: function.1.clone(),
+                        move_function: function.2.clone(),
+                        call_count: 0,
+                        failed_call_count: 0,
+                        unique_callers: 0,
+                        total_gas: 0,
+                    });
+                entry.call_count += 1;
+                if failed {
+                    entry.failed_call_count += 1;
+                }
+                // The gas of a transaction is counted once for every function it calls.
+                if called.insert(function.clone()) {
+                    entry.total_gas += gas_used;
+                }
+                callers.insert((function, call.sender));
+            }
+        }
+
+        let callers = callers
+            .into_iter()
+            .map(
+                |((move_package, move_module, move_function), sender)| MoveCallCaller {
+                    epoch: epoch as i64,
+                    move_package,
+                    move_module,
+                    move_function,
+                    sender,
+                },
+            )
+            .collect();
+        (stats.into_values().collect(), callers)
+    }
+
     fn index_packages(
         transactions: &[CheckpointTransactionBlockResponse],
         changed_objects: &[(ObjectStatus, SuiObjectData)],
@@ -7,7 +7,10 @@ use tracing::{error, info};
 use sui_indexer::errors::IndexerError;
 use sui_indexer::store::PgIndexerStore;
 use sui_indexer::utils::reset_database;
-use sui_indexer::{get_pg_pool_connection, new_pg_connection_pool, Indexer, IndexerConfig};
+use sui_indexer::{
+    get_pg_pool_connection, new_async_pg_connection_pool, new_pg_connection_pool, Indexer,
+    IndexerConfig,
+};
 use sui_node::metrics::start_prometheus_server;
 
 #[tokio::main]
@@ -58,7 +61,14 @@ async fn main() -> Result<(), IndexerError> {
             IndexerError::PostgresResetError(db_err_msg)
         })?;
     }
-    let store = PgIndexerStore::new(async_cp, blocking_cp).await;
+    let replicas = indexer_config
+        .db_replica_urls
+        .iter()
+        .map(|url| new_async_pg_connection_pool(url))
+        .collect::<Result<Vec<_>, _>>()?;
+    let store = PgIndexerStore::new(async_cp, blocking_cp)
+        .await
+        .with_read_replicas(replicas, indexer_config.max_replica_checkpoint_lag);
 
     Indexer::start(&indexer_config, &registry, store).await
 }
@@ -0,0 +1,550 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Migrations of the schema of the indexer database, applied while the indexer is running.
+//!
+//! The schema migrations under `migrations` are run by diesel in a transaction each, which
+//! holds the locks of the tables it changes until it commits. They are meant for changes which
+//! do not rewrite tables, like creating a table or adding a nullable column. A change which
+//! rewrites a table is an [OnlineMigration] instead, applied in three steps while the indexer
+//! keeps writing to the table:
+//!
+//! 1. a shadow table is created with the new schema, and a trigger mirrors the writes to the
+//!    table into the shadow table;
+//! 2. the rows of the table are copied into the shadow table in batches, each in its own
+//!    transaction, so that the backfill can be interrupted and resumed;
+//! 3. the shadow table replaces the table, in a transaction locking the table only for the time
+//!    of the renames.
+//!
+//! The progress of the online migrations is recorded in the `online_migrations` table, and
+//! reported by `sui-indexer-migrate status`.
+
+use std::fmt::{self, Display, Formatter};
+
+use diesel::connection::SimpleConnection;
+use diesel::migration::{Migration, MigrationName, MigrationSource};
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use diesel::sql_types::{BigInt, Bool, Nullable, Text};
+use diesel::QueryableByName;
+use diesel_migrations::MigrationHarness;
+use tracing::info;
+
+use crate::errors::IndexerError;
+use crate::models::online_migrations::{DBOnlineMigration, PHASE_BACKFILLING, PHASE_COMPLETE};
+use crate::schema::online_migrations;
+use crate::utils::MIGRATIONS;
+
+/// The online migrations of the indexer tables, applied in this order once the schema
+/// migrations are run. A migration is added here along with the change of the definition of
+/// its table in `schema.rs`, which describes the table once migrated.
+pub const ONLINE_MIGRATIONS: &[OnlineMigration] = &[];
+
+/// Number of rows copied into the shadow table by each transaction of a backfill.
+pub const DEFAULT_BACKFILL_BATCH_SIZE: i64 = 10_000;
+
+/// Number of times a batch of a backfill is retried when it conflicts with a write of the
+/// indexer.
+const MAX_BATCH_RETRIES: usize = 10;
+
+/// Key of the advisory lock held while migrations are applied, so that two processes do not
+/// apply them at the same time.
+const MIGRATION_LOCK_KEY: i64 = 0x5355_4d49_4752;
+
+/// A change of the schema of `table` which rewrites it, applied while the indexer writes to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OnlineMigration {
+    /// Unique name of the migration, under which its progress is recorded.
+    pub name: &'static str,
+    pub table: &'static str,
+    /// A `BIGINT` column of both schemas, unique in the table. The rows are copied in its
+    /// order, and the sequence generating it, if it is a `BIGSERIAL`, is handed over to the
+    /// migrated table.
+    pub key: &'static str,
+    /// Statements creating the shadow table with the new schema and its indexes, `{shadow}`
+    /// standing for the name of the shadow table. The key must be the primary key of the shadow
+    /// table, and the names of its indexes must start with `{shadow}` to be renamed after the
+    /// table at the cutover.
+    pub create_shadow: &'static str,
+    /// The columns of the shadow table, and the expressions computing them from the columns of
+    /// a row of the table.
+    pub columns: &'static [(&'static str, &'static str)],
+}
+
+impl OnlineMigration {
+    fn shadow(&self) -> String {
+        format!("{}__shadow", self.table)
+    }
+
+    fn mirror(&self) -> String {
+        format!("{}_mirror", self.shadow())
+    }
+
+    fn column_names(&self) -> String {
+        self.columns
+            .iter()
+            .map(|(column, _)| *column)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn column_expressions(&self) -> String {
+        self.columns
+            .iter()
+            .map(|(_, expression)| *expression)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Creates the shadow table, and the trigger mirroring the writes to the table into it,
+    /// from the rows of the table as written.
+    fn create_shadow_sql(&self) -> String {
+        let (table, key, shadow, mirror) = (self.table, self.key, self.shadow(), self.mirror());
+        let updates = self
+            .columns
+            .iter()
+            .map(|(column, _)| format!("{column} = EXCLUDED.{column}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "{create_shadow};
+            CREATE FUNCTION {mirror}() RETURNS TRIGGER AS $$
+            BEGIN
+                IF TG_OP <> 'INSERT' THEN
+                    DELETE FROM {shadow} WHERE {key} = OLD.{key};
+                END IF;
+                IF TG_OP <> 'DELETE' THEN
+                    INSERT INTO {shadow} ({columns})
+                    SELECT {expressions} FROM {table} WHERE {key} = NEW.{key}
+                    ON CONFLICT ({key}) DO UPDATE SET {updates};
+                END IF;
+                RETURN NULL;
+            END;
+            $$ LANGUAGE plpgsql;
+            CREATE TRIGGER {mirror} AFTER INSERT OR UPDATE OR DELETE ON {table}
+            FOR EACH ROW EXECUTE PROCEDURE {mirror}();",
+            create_shadow = self.create_shadow.replace("{shadow}", &shadow),
+            columns = self.column_names(),
+            expressions = self.column_expressions(),
+        )
+    }
+
+    /// Copies the rows of the table following the key bound to `$1` into the shadow table.
+    /// Rows already mirrored by the trigger are left as they are, being at least as recent as
+    /// the rows read by the batch.
+    fn backfill_sql(&self, batch_size: i64) -> String {
+        let (table, key, shadow) = (self.table, self.key, self.shadow());
+        format!(
+            "WITH batch AS (
+                SELECT * FROM {table} WHERE {key} > $1 ORDER BY {key} LIMIT {batch_size}
+            ), copied AS (
+                INSERT INTO {shadow} ({columns})
+                SELECT {expressions} FROM batch
+                ON CONFLICT ({key}) DO NOTHING
+                RETURNING 1
+            )
+            SELECT (SELECT MAX({key}) FROM batch) AS last_key,
+                (SELECT COUNT(*) FROM copied) AS rows_copied",
+            columns = self.column_names(),
+            expressions = self.column_expressions(),
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OnlineMigrationPhase {
+    Pending,
+    /// The shadow table mirrors the writes to the table, and the rows of the table up to the
+    /// key `cursor` are copied into it.
+    Backfilling {
+        cursor: Option<i64>,
+        rows_copied: i64,
+    },
+    Complete,
+}
+
+impl From<&DBOnlineMigration> for OnlineMigrationPhase {
+    fn from(migration: &DBOnlineMigration) -> Self {
+        if migration.phase == PHASE_COMPLETE {
+            Self::Complete
+        } else {
+            Self::Backfilling {
+                cursor: migration.backfill_cursor,
+                rows_copied: migration.rows_copied,
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MigrationStatus {
+    /// The schema migrations, and whether they are applied.
+    pub schema_migrations: Vec<(String, bool)>,
+    pub online_migrations: Vec<(OnlineMigration, OnlineMigrationPhase)>,
+}
+
+impl Display for MigrationStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Schema migrations:")?;
+        for (name, applied) in &self.schema_migrations {
+            let status = if *applied { "applied" } else { "pending" };
+            writeln!(f, "  [{status}] {name}")?;
+        }
+        writeln!(f, "Online migrations:")?;
+        if self.online_migrations.is_empty() {
+            writeln!(f, "  none")?;
+        }
+        for (migration, phase) in &self.online_migrations {
+            let (name, table) = (migration.name, migration.table);
+            match phase {
+                OnlineMigrationPhase::Pending => writeln!(f, "  [pending] {name} of {table}")?,
+                OnlineMigrationPhase::Backfilling {
+                    cursor,
+                    rows_copied,
+                } => writeln!(
+                    f,
+                    "  [backfilling] {name} of {table}: {rows_copied} rows copied, up to {} {}",
+                    migration.key,
+                    cursor.map_or_else(|| "none".to_string(), |cursor| cursor.to_string())
+                )?,
+                OnlineMigrationPhase::Complete => writeln!(f, "  [complete] {name} of {table}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MigrationStep {
+    /// Runs the pending schema migrations.
+    SchemaMigrations(Vec<String>),
+    CreateShadowTable(OnlineMigration),
+    /// Copies the rows of the table following the key into the shadow table.
+    Backfill(OnlineMigration, Option<i64>),
+    CutOver(OnlineMigration),
+}
+
+impl Display for MigrationStep {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SchemaMigrations(names) => {
+                write!(f, "Run the schema migrations {}", names.join(", "))
+            }
+            Self::CreateShadowTable(migration) => write!(
+                f,
+                "Create the shadow table {} of {}, mirroring the writes to {}, for {}",
+                migration.shadow(),
+                migration.table,
+                migration.table,
+                migration.name
+            ),
+            Self::Backfill(migration, cursor) => write!(
+                f,
+                "Copy the rows of {} into {}{}, for {}",
+                migration.table,
+                migration.shadow(),
+                cursor.map_or_else(String::new, |cursor| format!(
+                    " following {} {cursor}",
+                    migration.key
+                )),
+                migration.name
+            ),
+            Self::CutOver(migration) => write!(
+                f,
+                "Replace {} with {}, for {}",
+                migration.table,
+                migration.shadow(),
+                migration.name
+            ),
+        }
+    }
+}
+
+#[derive(QueryableByName, Debug)]
+struct Present {
+    #[diesel(sql_type = Bool)]
+    present: bool,
+}
+
+#[derive(QueryableByName, Debug)]
+struct Locked {
+    #[diesel(sql_type = Bool)]
+    locked: bool,
+}
+
+#[derive(QueryableByName, Debug)]
+struct BackfillBatch {
+    #[diesel(sql_type = Nullable<BigInt>)]
+    last_key: Option<i64>,
+    #[diesel(sql_type = BigInt)]
+    rows_copied: i64,
+}
+
+#[derive(QueryableByName, Debug)]
+struct SerialSequence {
+    #[diesel(sql_type = Nullable<Text>)]
+    sequence: Option<String>,
+}
+
+#[derive(QueryableByName, Debug)]
+struct IndexName {
+    #[diesel(sql_type = Text)]
+    indexname: String,
+}
+
+fn migration_error(e: impl Display) -> IndexerError {
+    IndexerError::PostgresMigrationError(e.to_string())
+}
+
+pub fn status(
+    conn: &mut PgConnection,
+    migrations: &[OnlineMigration],
+) -> Result<MigrationStatus, IndexerError> {
+    let applied = conn.applied_migrations().map_err(migration_error)?;
+    let schema_migrations = MigrationSource::<Pg>::migrations(&MIGRATIONS)
+        .map_err(migration_error)?
+        .iter()
+        .map(|migration| {
+            let name = migration.name();
+            (name.to_string(), applied.contains(&name.version()))
+        })
+        .collect();
+
+    // The table is created by a schema migration, which may still be pending.
+    let states =
+        if diesel::sql_query("SELECT to_regclass('online_migrations') IS NOT NULL AS present")
+            .get_result::<Present>(conn)?
+            .present
+        {
+            online_migrations::table.load::<DBOnlineMigration>(conn)?
+        } else {
+            vec![]
+        };
+    let online_migrations = migrations
+        .iter()
+        .map(|migration| {
+            let phase = states
+                .iter()
+                .find(|state| state.name == migration.name)
+                .map_or(OnlineMigrationPhase::Pending, OnlineMigrationPhase::from);
+            (*migration, phase)
+        })
+        .collect();
+    Ok(MigrationStatus {
+        schema_migrations,
+        online_migrations,
+    })
+}
+
+/// Returns the steps applying the pending migrations, in order.
+pub fn plan(
+    conn: &mut PgConnection,
+    migrations: &[OnlineMigration],
+) -> Result<Vec<MigrationStep>, IndexerError> {
+    let status = status(conn, migrations)?;
+    let mut steps = vec![];
+    let pending: Vec<_> = status
+        .schema_migrations
+        .into_iter()
+        .filter_map(|(name, applied)| (!applied).then_some(name))
+        .collect();
+    if !pending.is_empty() {
+        steps.push(MigrationStep::SchemaMigrations(pending));
+    }
+    for (migration, phase) in status.online_migrations {
+        match phase {
+            OnlineMigrationPhase::Pending => {
+                steps.push(MigrationStep::CreateShadowTable(migration));
+                steps.push(MigrationStep::Backfill(migration, None));
+                steps.push(MigrationStep::CutOver(migration));
+            }
+            OnlineMigrationPhase::Backfilling { cursor, .. } => {
+                steps.push(MigrationStep::Backfill(migration, cursor));
+                steps.push(MigrationStep::CutOver(migration));
+            }
+            OnlineMigrationPhase::Complete => {}
+        }
+    }
+    Ok(steps)
+}
+
+/// Applies the pending migrations. It is safe to run while the indexer is running, and to
+/// resume after an interruption.
+pub fn apply(
+    conn: &mut PgConnection,
+    migrations: &[OnlineMigration],
+    batch_size: i64,
+) -> Result<(), IndexerError> {
+    let locked = diesel::sql_query("SELECT pg_try_advisory_lock($1) AS locked")
+        .bind::<BigInt, _>(MIGRATION_LOCK_KEY)
+        .get_result::<Locked>(conn)?
+        .locked;
+    if !locked {
+        return Err(IndexerError::PostgresMigrationError(
+            "Migrations are being applied by another process".to_string(),
+        ));
+    }
+    let result = plan(conn, migrations).and_then(|steps| {
+        steps
+            .iter()
+            .try_for_each(|step| apply_step(conn, step, batch_size))
+    });
+    diesel::sql_query("SELECT pg_advisory_unlock($1)")
+        .bind::<BigInt, _>(MIGRATION_LOCK_KEY)
+        .execute(conn)?;
+    result
+}
+
+pub fn apply_step(
+    conn: &mut PgConnection,
+    step: &MigrationStep,
+    batch_size: i64,
+) -> Result<(), IndexerError> {
+    info!("{step}");
+    match step {
+        MigrationStep::SchemaMigrations(_) => {
+            conn.run_pending_migrations(MIGRATIONS)
+                .map_err(migration_error)?;
+            Ok(())
+        }
+        MigrationStep::CreateShadowTable(migration) => create_shadow_table(conn, migration),
+        MigrationStep::Backfill(migration, cursor) => {
+            backfill(conn, migration, *cursor, batch_size)
+        }
+        MigrationStep::CutOver(migration) => cut_over(conn, migration),
+    }
+}
+
+fn create_shadow_table(
+    conn: &mut PgConnection,
+    migration: &OnlineMigration,
+) -> Result<(), IndexerError> {
+    conn.transaction::<_, IndexerError, _>(|conn| {
+        conn.batch_execute(&migration.create_shadow_sql())?;
+        diesel::insert_into(online_migrations::table)
+            .values(DBOnlineMigration {
+                name: migration.name.to_string(),
+                table_name: migration.table.to_string(),
+                phase: PHASE_BACKFILLING.to_string(),
+                backfill_cursor: None,
+                rows_copied: 0,
+                updated_at_ms: chrono::Utc::now().timestamp_millis(),
+            })
+            .execute(conn)?;
+        Ok(())
+    })
+}
+
+fn backfill(
+    conn: &mut PgConnection,
+    migration: &OnlineMigration,
+    mut cursor: Option<i64>,
+    batch_size: i64,
+) -> Result<(), IndexerError> {
+    let sql = migration.backfill_sql(batch_size);
+    while let Some(last_key) = copy_batch(conn, migration, &sql, cursor)? {
+        info!(
+            "Backfilled {} of {} up to {last_key}",
+            migration.table, migration.name
+        );
+        cursor = Some(last_key);
+    }
+    Ok(())
+}
+
+/// Copies the batch of rows following `cursor`, and records the progress of the backfill, in
+/// a serializable transaction so that it cannot race with a write mirrored by the trigger.
+/// Returns the key of the last row copied, or `None` once the table is copied.
+fn copy_batch(
+    conn: &mut PgConnection,
+    migration: &OnlineMigration,
+    sql: &str,
+    cursor: Option<i64>,
+) -> Result<Option<i64>, IndexerError> {
+    let mut retries = 0;
+    loop {
+        let result = conn
+            .build_transaction()
+            .serializable()
+            .read_write()
+            .run(|conn| {
+                let batch = diesel::sql_query(sql)
+                    .bind::<BigInt, _>(cursor.unwrap_or(i64::MIN))
+                    .get_result::<BackfillBatch>(conn)?;
+                if batch.last_key.is_some() {
+                    diesel::update(online_migrations::table.find(migration.name))
+                        .set((
+                            online_migrations::backfill_cursor.eq(batch.last_key),
+                            online_migrations::rows_copied
+                                .eq(online_migrations::rows_copied + batch.rows_copied),
+                            online_migrations::updated_at_ms
+                                .eq(chrono::Utc::now().timestamp_millis()),
+                        ))
+                        .execute(conn)?;
+                }
+                Ok::<_, DieselError>(batch.last_key)
+            });
+        match result {
+            Err(DieselError::DatabaseError(DatabaseErrorKind::SerializationFailure, _))
+                if retries < MAX_BATCH_RETRIES =>
+            {
+                retries += 1;
+            }
+            result => return Ok(result?),
+        }
+    }
+}
+
+/// Replaces the table with the shadow table, which holds all its rows, handing over the
+/// sequence of the key and the names of the indexes.
+fn cut_over(conn: &mut PgConnection, migration: &OnlineMigration) -> Result<(), IndexerError> {
+    let (table, key, shadow, mirror) = (
+        migration.table,
+        migration.key,
+        migration.shadow(),
+        migration.mirror(),
+    );
+    conn.transaction::<_, IndexerError, _>(|conn| {
+        conn.batch_execute(&format!(
+            "LOCK TABLE {table} IN ACCESS EXCLUSIVE MODE;
+            DROP TRIGGER {mirror} ON {table};
+            DROP FUNCTION {mirror}();"
+        ))?;
+        let sequence = diesel::sql_query("SELECT pg_get_serial_sequence($1, $2) AS sequence")
+            .bind::<Text, _>(table)
+            .bind::<Text, _>(key)
+            .get_result::<SerialSequence>(conn)?
+            .sequence;
+        if let Some(sequence) = sequence {
+            conn.batch_execute(&format!(
+                "ALTER SEQUENCE {sequence} OWNED BY {shadow}.{key};
+                ALTER TABLE {shadow} ALTER COLUMN {key} SET DEFAULT nextval('{sequence}');"
+            ))?;
+        }
+        conn.batch_execute(&format!(
+            "DROP TABLE {table};
+            ALTER TABLE {shadow} RENAME TO {table};"
+        ))?;
+        let indexes = diesel::sql_query(
+            "SELECT indexname FROM pg_indexes WHERE schemaname = current_schema() AND tablename = $1",
+        )
+        .bind::<Text, _>(table)
+        .load::<IndexName>(conn)?;
+        for index in indexes {
+            if let Some(suffix) = index.indexname.strip_prefix(&shadow) {
+                conn.batch_execute(&format!(
+                    "ALTER INDEX {} RENAME TO {table}{suffix}",
+                    index.indexname
+                ))?;
+            }
+        }
+        diesel::update(online_migrations::table.find(migration.name))
+            .set((
+                online_migrations::phase.eq(PHASE_COMPLETE),
+                online_migrations::updated_at_ms.eq(chrono::Utc::now().timestamp_millis()),
+            ))
+            .execute(conn)?;
+        Ok(())
+    })
+}
@@ -1,9 +1,16 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::str::FromStr;
+
 use diesel::prelude::*;
 
-use crate::schema::addresses;
+use sui_json_rpc_types::{AddressGasUsage, AddressSummary};
+use sui_types::base_types::SuiAddress;
+use sui_types::digests::TransactionDigest;
+
+use crate::errors::IndexerError;
+use crate::schema::{address_gas_usage, address_packages, address_summaries, addresses};
 
 #[derive(Queryable, Insertable, Debug)]
 #[diesel(table_name = addresses, primary_key(account_address))]
@@ -12,3 +19,79 @@ pub struct Address {
     pub first_appearance_tx: String,
     pub first_appearance_time: i64,
 }
+
+/// The activity of an address, in the transactions of a checkpoint when it is indexed, and in
+/// all the indexed transactions once it is committed.
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = address_summaries, primary_key(address))]
+pub struct DBAddressSummary {
+    pub address: String,
+    pub first_seen_checkpoint: i64,
+    pub first_seen_tx: String,
+    pub first_seen_time: i64,
+    pub last_active_checkpoint: i64,
+    pub last_active_tx: String,
+    pub last_active_time: i64,
+    pub transaction_count: i64,
+    pub gas_spent: i64,
+}
+
+impl DBAddressSummary {
+    pub fn into_address_summary(
+        self,
+        packages_touched: i64,
+    ) -> Result<AddressSummary, IndexerError> {
+        Ok(AddressSummary {
+            address: SuiAddress::from_str(&self.address)?,
+            first_seen_checkpoint: self.first_seen_checkpoint as u64,
+            first_seen_timestamp_ms: self.first_seen_time as u64,
+            first_transaction: TransactionDigest::from_str(&self.first_seen_tx)?,
+            last_active_checkpoint: self.last_active_checkpoint as u64,
+            last_active_timestamp_ms: self.last_active_time as u64,
+            last_transaction: TransactionDigest::from_str(&self.last_active_tx)?,
+            transaction_count: self.transaction_count as u64,
+            gas_spent: self.gas_spent,
+            packages_touched: packages_touched as u64,
+        })
+    }
+}
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = address_packages, primary_key(address, package))]
+pub struct AddressPackage {
+    pub address: String,
+    pub package: String,
+    pub first_call_checkpoint: i64,
+}
+
+/// The gas paid by an address as the gas owner of transactions in an epoch, in the transactions
+/// of a checkpoint when it is indexed, and in all the indexed transactions of the epoch once it is
+/// committed.
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = address_gas_usage, primary_key(address, epoch))]
+pub struct DBAddressGasUsage {
+    pub address: String,
+    pub epoch: i64,
+    pub transaction_count: i64,
+    pub computation_cost: i64,
+    pub storage_cost: i64,
+    pub storage_rebate: i64,
+    pub non_refundable_storage_fee: i64,
+}
+
+impl TryFrom<DBAddressGasUsage> for AddressGasUsage {
+    type Error = IndexerError;
+
+    fn try_from(usage: DBAddressGasUsage) -> Result<Self, Self::Error> {
+        Ok(AddressGasUsage {
+            address: SuiAddress::from_str(&usage.address)?,
+            epoch: usage.epoch as u64,
+            transaction_count: usage.transaction_count as u64,
+            computation_cost: usage.computation_cost as u64,
+            storage_cost: usage.storage_cost as u64,
+            storage_rebate: usage.storage_rebate as u64,
+            non_refundable_storage_fee: usage.non_refundable_storage_fee as u64,
+            net_gas_cost: usage.computation_cost + usage.storage_cost - usage.storage_rebate,
+        })
+    }
+}
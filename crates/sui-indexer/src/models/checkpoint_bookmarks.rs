@@ -0,0 +1,26 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+
+use sui_json_rpc_types::CheckpointBookmark;
+
+use crate::schema::checkpoint_bookmarks;
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = checkpoint_bookmarks)]
+pub struct DBCheckpointBookmark {
+    pub name: String,
+    pub checkpoint: i64,
+    pub updated_at_ms: i64,
+}
+
+impl From<DBCheckpointBookmark> for CheckpointBookmark {
+    fn from(bookmark: DBCheckpointBookmark) -> Self {
+        CheckpointBookmark {
+            name: bookmark.name,
+            checkpoint: bookmark.checkpoint as u64,
+            updated_at_ms: bookmark.updated_at_ms as u64,
+        }
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Nullable};
+
+use sui_json_rpc_types::GasPriceRecommendation;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+use crate::schema::shared_object_gas_prices;
+
+/// The gas price paid by a transaction using shared objects.
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = shared_object_gas_prices)]
+pub struct SharedObjectGasPrice {
+    pub transaction_digest: String,
+    pub checkpoint_sequence_number: i64,
+    pub gas_price: i64,
+}
+
+/// The percentiles of the gas prices paid by the transactions using shared objects in a range
+/// of checkpoints, None if there are no such transactions.
+#[derive(QueryableByName, Debug, Clone)]
+pub struct DBGasPricePercentiles {
+    #[diesel(sql_type = BigInt)]
+    pub transactions: i64,
+    #[diesel(sql_type = Nullable<BigInt>)]
+    pub p50: Option<i64>,
+    #[diesel(sql_type = Nullable<BigInt>)]
+    pub p90: Option<i64>,
+}
+
+impl DBGasPricePercentiles {
+    pub fn to_recommendation(
+        &self,
+        reference_gas_price: u64,
+        from_checkpoint: CheckpointSequenceNumber,
+        to_checkpoint: CheckpointSequenceNumber,
+    ) -> GasPriceRecommendation {
+        let at_least_reference =
+            |price: Option<i64>| (price.unwrap_or_default() as u64).max(reference_gas_price);
+        GasPriceRecommendation {
+            reference_gas_price,
+            p50_gas_price: at_least_reference(self.p50),
+            p90_gas_price: at_least_reference(self.p90),
+            from_checkpoint,
+            to_checkpoint,
+            transactions: self.transactions as u64,
+        }
+    }
+}
@@ -2,11 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod addresses;
+pub mod checkpoint_bookmarks;
 pub mod checkpoints;
 pub mod epoch;
 pub mod events;
+pub mod gas_prices;
+pub mod move_call_stats;
 pub mod network_metrics;
 pub mod objects;
+pub mod online_migrations;
 pub mod owners;
 pub mod packages;
 pub mod system_state;
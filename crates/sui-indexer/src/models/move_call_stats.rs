@@ -0,0 +1,65 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use diesel::prelude::*;
+use move_core_types::identifier::Identifier;
+
+use sui_json_rpc_types::{MoveCallStats, MoveFunctionName};
+use sui_types::base_types::ObjectID;
+
+use crate::errors::IndexerError;
+use crate::schema::{move_call_callers, move_call_stats};
+
+/// The statistics of the calls of a Move function in an epoch, in the transactions of a
+/// checkpoint when it is indexed, and in all the indexed transactions of the epoch once it is
+/// committed.
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = move_call_stats)]
+pub struct DBMoveCallStats {
+    pub epoch: i64,
+    pub move_package: String,
+    pub move_module: String,
+    pub move_function: String,
+    pub call_count: i64,
+    pub failed_call_count: i64,
+    // Maintained by the database, from the callers of the function.
+    pub unique_callers: i64,
+    pub total_gas: i64,
+}
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = move_call_callers)]
+pub struct MoveCallCaller {
+    pub epoch: i64,
+    pub move_package: String,
+    pub move_module: String,
+    pub move_function: String,
+    pub sender: String,
+}
+
+impl TryFrom<DBMoveCallStats> for MoveCallStats {
+    type Error = IndexerError;
+
+    fn try_from(stats: DBMoveCallStats) -> Result<Self, Self::Error> {
+        let failure_rate = if stats.call_count > 0 {
+            stats.failed_call_count as f64 / stats.call_count as f64
+        } else {
+            0.0
+        };
+        Ok(MoveCallStats {
+            epoch: stats.epoch as u64,
+            function: MoveFunctionName {
+                package: ObjectID::from_str(&stats.move_package)?,
+                module: Identifier::from_str(&stats.move_module)?,
+                function: Identifier::from_str(&stats.move_function)?,
+            },
+            call_count: stats.call_count as u64,
+            failed_call_count: stats.failed_call_count as u64,
+            failure_rate,
+            unique_callers: stats.unique_callers as u64,
+            total_gas: stats.total_gas as u64,
+        })
+    }
+}
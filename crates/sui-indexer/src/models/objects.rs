@@ -322,6 +322,34 @@ pub fn owner_to_owner_info(owner: &Owner) -> (OwnerType, Option<String>, Option<
     }
 }
 
+pub fn owner_from_owner_info(
+    owner_type: OwnerType,
+    owner_address: Option<String>,
+    initial_shared_version: Option<i64>,
+) -> Result<Owner, IndexerError> {
+    let address = |owner_address: Option<String>| {
+        owner_address
+            .ok_or_else(|| {
+                IndexerError::PostgresReadError("Owner address should not be empty.".to_string())
+            })
+            .and_then(|address| Ok(SuiAddress::from_str(&address)?))
+    };
+    Ok(match owner_type {
+        OwnerType::AddressOwner => Owner::AddressOwner(address(owner_address)?),
+        OwnerType::ObjectOwner => Owner::ObjectOwner(address(owner_address)?),
+        OwnerType::Shared => Owner::Shared {
+            initial_shared_version: SequenceNumber::from_u64(initial_shared_version.ok_or_else(
+                || {
+                    IndexerError::PostgresReadError(
+                        "Shared version should not be empty.".to_string(),
+                    )
+                },
+            )? as u64),
+        },
+        OwnerType::Immutable => Owner::Immutable,
+    })
+}
+
 pub fn compose_object_bulk_insert_update_query(objects: &[Object]) -> String {
     let insert_query = compose_object_bulk_insert_query(objects)
         .as_str()
@@ -0,0 +1,20 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+
+use crate::schema::online_migrations;
+
+pub const PHASE_BACKFILLING: &str = "backfilling";
+pub const PHASE_COMPLETE: &str = "complete";
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = online_migrations)]
+pub struct DBOnlineMigration {
+    pub name: String,
+    pub table_name: String,
+    pub phase: String,
+    pub backfill_cursor: Option<i64>,
+    pub rows_copied: i64,
+    pub updated_at_ms: i64,
+}
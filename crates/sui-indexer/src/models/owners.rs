@@ -1,10 +1,18 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::models::objects::ObjectStatus;
+use std::str::FromStr;
+
+use crate::errors::IndexerError;
+use crate::models::objects::{owner_from_owner_info, ObjectStatus};
+use crate::schema::sql_types::OwnerType as SqlOwnerType;
 use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Nullable, VarChar};
 use diesel_derive_enum::DbEnum;
 use serde::{Deserialize, Serialize};
+use sui_json_rpc_types::ObjectOwnershipChange;
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::digests::TransactionDigest;
 
 #[derive(Queryable, Debug, Clone)]
 #[diesel(table_name = owner)]
@@ -42,3 +50,79 @@ pub struct OwnerHistory {
     pub object_digest: String,
     pub object_status: ObjectStatus,
 }
+
+/// An owner transition of an object, including its first owner when it is created, read from
+/// the versions of the object in `objects_history`.
+#[derive(QueryableByName, Debug, Clone)]
+pub struct DBObjectOwnershipChange {
+    #[diesel(sql_type = VarChar)]
+    pub object_id: String,
+    #[diesel(sql_type = BigInt)]
+    pub version: i64,
+    // -1 for changes from fast path, until indexed from the checkpoint.
+    #[diesel(sql_type = BigInt)]
+    pub checkpoint: i64,
+    #[diesel(sql_type = VarChar)]
+    pub transaction_digest: String,
+    #[diesel(sql_type = Nullable<SqlOwnerType>)]
+    pub old_owner_type: Option<OwnerType>,
+    #[diesel(sql_type = Nullable<VarChar>)]
+    pub old_owner_address: Option<String>,
+    #[diesel(sql_type = SqlOwnerType)]
+    pub new_owner_type: OwnerType,
+    #[diesel(sql_type = Nullable<VarChar>)]
+    pub new_owner_address: Option<String>,
+    #[diesel(sql_type = Nullable<BigInt>)]
+    pub initial_shared_version: Option<i64>,
+}
+
+/// Query of the owner transitions of the object `$1` after the version `$2`, or before it in
+/// descending order, limited to `$3` transitions. Each version of the object is compared to the
+/// previous one, from the checkpoint if it is indexed from both the fast path and the checkpoint.
+/// Versions of deleted or wrapped objects have no owner and are skipped.
+pub fn compose_object_ownership_history_query(descending_order: bool) -> String {
+    let (cmp, order) = if descending_order {
+        ("<", "DESC")
+    } else {
+        (">", "ASC")
+    };
+    format!(
+        "SELECT object_id, version, checkpoint, transaction_digest, old_owner_type, \
+         old_owner_address, new_owner_type, new_owner_address, initial_shared_version \
+         FROM (SELECT object_id, version, checkpoint, previous_transaction AS transaction_digest, \
+         LAG(owner_type) OVER (ORDER BY version) AS old_owner_type, \
+         LAG(owner_address) OVER (ORDER BY version) AS old_owner_address, \
+         owner_type AS new_owner_type, owner_address AS new_owner_address, \
+         initial_shared_version \
+         FROM (SELECT DISTINCT ON (version) * FROM objects_history \
+         WHERE object_id = $1 \
+         AND object_status NOT IN ('deleted', 'wrapped', 'unwrapped_then_deleted') \
+         ORDER BY version, checkpoint DESC) versions) changes \
+         WHERE (old_owner_type, old_owner_address) IS DISTINCT FROM \
+         (new_owner_type, new_owner_address) \
+         AND version {cmp} $2 ORDER BY version {order} LIMIT $3"
+    )
+}
+
+impl TryFrom<DBObjectOwnershipChange> for ObjectOwnershipChange {
+    type Error = IndexerError;
+
+    fn try_from(change: DBObjectOwnershipChange) -> Result<Self, Self::Error> {
+        let previous_owner = change
+            .old_owner_type
+            .map(|owner_type| owner_from_owner_info(owner_type, change.old_owner_address, None))
+            .transpose()?;
+        Ok(ObjectOwnershipChange {
+            object_id: ObjectID::from_str(&change.object_id)?,
+            version: SequenceNumber::from_u64(change.version as u64),
+            transaction_digest: TransactionDigest::from_str(&change.transaction_digest)?,
+            checkpoint: (change.checkpoint >= 0).then_some(change.checkpoint as u64),
+            previous_owner,
+            new_owner: owner_from_owner_info(
+                change.new_owner_type,
+                change.new_owner_address,
+                change.initial_shared_version,
+            )?,
+        })
+    }
+}
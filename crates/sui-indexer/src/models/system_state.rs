@@ -5,6 +5,7 @@ use std::str::FromStr;
 
 use diesel::{Insertable, Queryable};
 
+use sui_json_rpc_types::CommitteeMember;
 use sui_types::base_types::{EpochId, ObjectID, SuiAddress};
 use sui_types::sui_system_state::sui_system_state_summary::{
     SuiSystemStateSummary, SuiValidatorSummary,
@@ -162,6 +163,20 @@ impl From<(EpochId, SuiValidatorSummary)> for DBValidatorSummary {
     }
 }
 
+impl TryFrom<DBValidatorSummary> for CommitteeMember {
+    type Error = IndexerError;
+    fn try_from(db: DBValidatorSummary) -> Result<CommitteeMember, Self::Error> {
+        Ok(CommitteeMember {
+            sui_address: SuiAddress::from_str(&db.sui_address)?,
+            name: db.name,
+            voting_power: db.voting_power as u64,
+            commission_rate: db.commission_rate as u64,
+            stake: db.staking_pool_sui_balance as u64,
+            gas_price: db.gas_price as u64,
+        })
+    }
+}
+
 impl TryFrom<DBValidatorSummary> for SuiValidatorSummary {
     type Error = IndexerError;
     fn try_from(db: DBValidatorSummary) -> Result<SuiValidatorSummary, Self::Error> {
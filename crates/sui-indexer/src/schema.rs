@@ -16,6 +16,40 @@ pub mod sql_types {
     pub struct OwnerType;
 }
 
+diesel::table! {
+    address_gas_usage (address, epoch) {
+        address -> Varchar,
+        epoch -> Int8,
+        transaction_count -> Int8,
+        computation_cost -> Int8,
+        storage_cost -> Int8,
+        storage_rebate -> Int8,
+        non_refundable_storage_fee -> Int8,
+    }
+}
+
+diesel::table! {
+    address_packages (address, package) {
+        address -> Varchar,
+        package -> Varchar,
+        first_call_checkpoint -> Int8,
+    }
+}
+
+diesel::table! {
+    address_summaries (address) {
+        address -> Varchar,
+        first_seen_checkpoint -> Int8,
+        first_seen_tx -> Varchar,
+        first_seen_time -> Int8,
+        last_active_checkpoint -> Int8,
+        last_active_tx -> Varchar,
+        last_active_time -> Int8,
+        transaction_count -> Int8,
+        gas_spent -> Int8,
+    }
+}
+
 diesel::table! {
     addresses (account_address) {
         account_address -> Varchar,
@@ -33,6 +67,14 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    checkpoint_bookmarks (name) {
+        name -> Varchar,
+        checkpoint -> Int8,
+        updated_at_ms -> Int8,
+    }
+}
+
 diesel::table! {
     checkpoints (sequence_number) {
         sequence_number -> Int8,
@@ -104,6 +146,29 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    move_call_callers (epoch, move_package, move_module, move_function, sender) {
+        epoch -> Int8,
+        move_package -> Varchar,
+        move_module -> Text,
+        move_function -> Text,
+        sender -> Varchar,
+    }
+}
+
+diesel::table! {
+    move_call_stats (epoch, move_package, move_module, move_function) {
+        epoch -> Int8,
+        move_package -> Varchar,
+        move_module -> Text,
+        move_function -> Text,
+        call_count -> Int8,
+        failed_call_count -> Int8,
+        unique_callers -> Int8,
+        total_gas -> Int8,
+    }
+}
+
 diesel::table! {
     move_calls (id) {
         id -> Int8,
@@ -167,6 +232,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    online_migrations (name) {
+        name -> Varchar,
+        table_name -> Varchar,
+        phase -> Varchar,
+        backfill_cursor -> Nullable<Int8>,
+        rows_copied -> Int8,
+        updated_at_ms -> Int8,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::BcsBytes;
@@ -190,6 +266,14 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    shared_object_gas_prices (transaction_digest) {
+        transaction_digest -> Varchar,
+        checkpoint_sequence_number -> Int8,
+        gas_price -> Int8,
+    }
+}
+
 diesel::table! {
     system_states (epoch) {
         epoch -> Int8,
@@ -296,17 +380,25 @@ diesel::table! {
 }
 
 diesel::allow_tables_to_appear_in_same_query!(
+    address_gas_usage,
+    address_packages,
+    address_summaries,
     addresses,
     at_risk_validators,
+    checkpoint_bookmarks,
     checkpoints,
     epochs,
     events,
     input_objects,
+    move_call_callers,
+    move_call_stats,
     move_calls,
     objects,
     objects_history,
+    online_migrations,
     packages,
     recipients,
+    shared_object_gas_prices,
     system_states,
     transactions,
     validators,
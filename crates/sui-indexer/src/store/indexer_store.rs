@@ -4,11 +4,12 @@
 use async_trait::async_trait;
 
 use sui_json_rpc_types::{
-    Checkpoint as RpcCheckpoint, CheckpointId, EpochInfo, EventFilter, EventPage, MoveCallMetrics,
-    NetworkMetrics, SuiObjectData, SuiObjectDataFilter, SuiTransactionBlockResponse,
-    SuiTransactionBlockResponseOptions,
+    AddressGasUsage, AddressSummary, Checkpoint as RpcCheckpoint, CheckpointBookmark, CheckpointId,
+    EpochCommittee, EpochInfo, EventFilter, EventPage, GasPriceRecommendation, MoveCallMetrics,
+    MoveCallStats, MoveCallStatsOrder, NetworkMetrics, ObjectOwnershipChange, SuiObjectData,
+    SuiObjectDataFilter, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
-use sui_types::base_types::{EpochId, ObjectID, SequenceNumber};
+use sui_types::base_types::{EpochId, ObjectID, SequenceNumber, SuiAddress};
 use sui_types::digests::CheckpointDigest;
 use sui_types::error::SuiError;
 use sui_types::event::EventID;
@@ -17,10 +18,12 @@ use sui_types::object::ObjectRead;
 use sui_types::storage::ObjectStore;
 
 use crate::errors::IndexerError;
-use crate::models::addresses::Address;
+use crate::models::addresses::{Address, AddressPackage, DBAddressGasUsage, DBAddressSummary};
 use crate::models::checkpoints::Checkpoint;
 use crate::models::epoch::DBEpochInfo;
 use crate::models::events::Event;
+use crate::models::gas_prices::SharedObjectGasPrice;
+use crate::models::move_call_stats::{DBMoveCallStats, MoveCallCaller};
 use crate::models::objects::{DeletedObject, Object, ObjectStatus};
 use crate::models::packages::Package;
 use crate::models::system_state::{DBSystemStateSummary, DBValidatorSummary};
@@ -69,6 +72,42 @@ pub trait IndexerStore {
         limit: usize,
     ) -> Result<Vec<ObjectRead>, IndexerError>;
 
+    async fn get_object_ownership_history(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<SequenceNumber>,
+        limit: usize,
+        descending_order: Option<bool>,
+    ) -> Result<Vec<ObjectOwnershipChange>, IndexerError>;
+
+    async fn get_address_summary(
+        &self,
+        address: SuiAddress,
+    ) -> Result<Option<AddressSummary>, IndexerError>;
+
+    async fn get_address_gas_usage(
+        &self,
+        address: SuiAddress,
+        from_epoch: Option<EpochId>,
+        to_epoch: Option<EpochId>,
+        limit: usize,
+    ) -> Result<Vec<AddressGasUsage>, IndexerError>;
+
+    async fn get_checkpoint_bookmark(
+        &self,
+        name: &str,
+    ) -> Result<Option<CheckpointBookmark>, IndexerError>;
+
+    /// Sets the checkpoint of the bookmark `name` only if its current checkpoint is `expected`,
+    /// or creates the bookmark if `expected` is None and it does not exist yet. Returns the
+    /// bookmark if it was set, None otherwise.
+    async fn compare_and_set_checkpoint_bookmark(
+        &self,
+        name: &str,
+        checkpoint: CheckpointSequenceNumber,
+        expected: Option<CheckpointSequenceNumber>,
+    ) -> Result<Option<CheckpointBookmark>, IndexerError>;
+
     async fn get_total_transaction_number_from_checkpoints(&self) -> Result<i64, IndexerError>;
 
     // TODO: combine all get_transaction* methods
@@ -179,6 +218,21 @@ pub trait IndexerStore {
     async fn get_network_metrics(&self) -> Result<NetworkMetrics, IndexerError>;
     async fn get_move_call_metrics(&self) -> Result<MoveCallMetrics, IndexerError>;
 
+    async fn get_move_call_stats(
+        &self,
+        epoch: EpochId,
+        package: Option<ObjectID>,
+        order: MoveCallStatsOrder,
+        limit: usize,
+    ) -> Result<Vec<MoveCallStats>, IndexerError>;
+
+    /// The gas prices to offer for transactions, from the transactions using shared objects in
+    /// the last `checkpoints` indexed checkpoints.
+    async fn get_gas_price_recommendation(
+        &self,
+        checkpoints: usize,
+    ) -> Result<GasPriceRecommendation, IndexerError>;
+
     async fn persist_fast_path(
         &self,
         tx: Transaction,
@@ -199,6 +253,13 @@ pub trait IndexerStore {
 
     async fn get_current_epoch(&self) -> Result<EpochInfo, IndexerError>;
 
+    /// The committees of the indexed epochs from `from_epoch` to `to_epoch` included.
+    async fn get_committee_history(
+        &self,
+        from_epoch: EpochId,
+        to_epoch: EpochId,
+    ) -> Result<Vec<EpochCommittee>, IndexerError>;
+
     fn module_cache(&self) -> &Self::ModuleCache;
 }
 
@@ -233,9 +294,21 @@ pub struct TemporaryCheckpointStore {
     pub events: Vec<Event>,
     pub objects_changes: Vec<TransactionObjectChanges>,
     pub addresses: Vec<Address>,
+    /// The activity of the addresses in the transactions of the checkpoint, added to their
+    /// summaries when the checkpoint is committed.
+    pub address_summaries: Vec<DBAddressSummary>,
+    pub address_packages: Vec<AddressPackage>,
+    /// The gas paid by the addresses in the checkpoint, added to their usage in the epoch when
+    /// the checkpoint is committed.
+    pub address_gas_usage: Vec<DBAddressGasUsage>,
     pub packages: Vec<Package>,
     pub input_objects: Vec<InputObject>,
     pub move_calls: Vec<MoveCall>,
+    /// The statistics of the Move calls of the checkpoint, added to the statistics of the epoch
+    /// when the checkpoint is committed.
+    pub move_call_stats: Vec<DBMoveCallStats>,
+    pub move_call_callers: Vec<MoveCallCaller>,
+    pub shared_object_gas_prices: Vec<SharedObjectGasPrice>,
     pub recipients: Vec<Recipient>,
 }
 
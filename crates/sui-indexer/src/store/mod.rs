@@ -8,6 +8,7 @@ mod indexer_store;
 mod module_resolver;
 mod pg_indexer_store;
 mod query;
+mod read_replicas;
 
 mod diesel_marco {
     macro_rules! read_only_blocking {
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -25,8 +25,10 @@ use tracing::info;
 
 use sui_json_rpc::{ObjectProvider, ObjectProviderCache};
 use sui_json_rpc_types::{
-    CheckpointId, EpochInfo, EventFilter, EventPage, MoveCallMetrics, MoveFunctionName,
-    NetworkMetrics, SuiEvent, SuiObjectDataFilter,
+    AddressGasUsage, AddressSummary, CheckpointBookmark, CheckpointId, CommitteeMember,
+    EpochCommittee, EpochInfo, EventFilter, EventPage, GasPriceRecommendation, MoveCallMetrics,
+    MoveCallStats, MoveCallStatsOrder, MoveFunctionName, NetworkMetrics, ObjectOwnershipChange,
+    SuiEvent, SuiObjectDataFilter,
 };
 use sui_json_rpc_types::{
     SuiTransactionBlock, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI,
@@ -44,21 +46,32 @@ use sui_types::messages_checkpoint::{
 use sui_types::object::ObjectRead;
 
 use crate::errors::{Context, IndexerError};
+use crate::models::addresses::{DBAddressGasUsage, DBAddressSummary};
+use crate::models::checkpoint_bookmarks::DBCheckpointBookmark;
 use crate::models::checkpoints::Checkpoint;
 use crate::models::epoch::DBEpochInfo;
 use crate::models::events::Event;
+use crate::models::gas_prices::DBGasPricePercentiles;
+use crate::models::move_call_stats::DBMoveCallStats;
 use crate::models::network_metrics::{DBMoveCallMetrics, DBNetworkMetrics};
 use crate::models::objects::{
     compose_object_bulk_insert_update_query, group_and_sort_objects, Object,
 };
+use crate::models::owners::{compose_object_ownership_history_query, DBObjectOwnershipChange};
 use crate::models::system_state::DBValidatorSummary;
 use crate::models::transactions::Transaction;
 use crate::schema::{
-    addresses, checkpoints, checkpoints::dsl as checkpoints_dsl, epochs, epochs::dsl as epochs_dsl,
-    events, input_objects, input_objects::dsl as input_objects_dsl, move_calls,
-    move_calls::dsl as move_calls_dsl, objects, objects::dsl as objects_dsl, objects_history,
-    packages, recipients, recipients::dsl as recipients_dsl, system_states, transactions,
-    transactions::dsl as transactions_dsl, validators,
+    address_gas_usage, address_gas_usage::dsl as address_gas_usage_dsl, address_packages,
+    address_packages::dsl as address_packages_dsl, address_summaries,
+    address_summaries::dsl as address_summaries_dsl, addresses, checkpoint_bookmarks,
+    checkpoint_bookmarks::dsl as checkpoint_bookmarks_dsl, checkpoints,
+    checkpoints::dsl as checkpoints_dsl, epochs, epochs::dsl as epochs_dsl, events, input_objects,
+    input_objects::dsl as input_objects_dsl, move_call_callers, move_call_stats,
+    move_call_stats::dsl as move_call_stats_dsl, move_calls, move_calls::dsl as move_calls_dsl,
+    objects, objects::dsl as objects_dsl, objects_history, packages, recipients,
+    recipients::dsl as recipients_dsl, shared_object_gas_prices, system_states,
+    system_states::dsl as system_states_dsl, transactions, transactions::dsl as transactions_dsl,
+    validators,
 };
 use crate::store::diesel_marco::{
     read_only, read_only_blocking, transactional, transactional_blocking,
@@ -66,6 +79,7 @@ use crate::store::diesel_marco::{
 use crate::store::indexer_store::TemporaryCheckpointStore;
 use crate::store::module_resolver::IndexerModuleResolver;
 use crate::store::query::DBFilter;
+use crate::store::read_replicas::ReadReplicas;
 use crate::store::TransactionObjectChanges;
 use crate::store::{IndexerStore, TemporaryEpochStore};
 use crate::utils::{get_balance_changes_from_effect, get_object_changes};
@@ -95,6 +109,7 @@ struct TempDigestTable {
 #[derive(Clone)]
 pub struct PgIndexerStore {
     cp: AsyncPgConnectionPool,
+    replicas: ReadReplicas,
     partition_manager: PartitionManager,
     module_cache: Arc<SyncModuleCache<IndexerModuleResolver>>,
 }
@@ -106,39 +121,58 @@ impl PgIndexerStore {
         )));
         PgIndexerStore {
             cp: cp.clone(),
+            replicas: ReadReplicas::new(cp, vec![], 0),
             partition_manager: PartitionManager::new(blocking_cp).await.unwrap(),
             module_cache,
         }
     }
 
+    /// Serves the reads of the store from `replicas` of its database, skipping the replicas
+    /// lagging the database by more than `max_lag` checkpoints.
+    pub fn with_read_replicas(
+        mut self,
+        replicas: Vec<AsyncPgConnectionPool>,
+        max_lag: u64,
+    ) -> Self {
+        self.replicas = ReadReplicas::new(self.cp.clone(), replicas, max_lag);
+        self.replicas.spawn_lag_monitor();
+        self
+    }
+
+    fn reader(&self) -> &AsyncPgConnectionPool {
+        self.replicas.reader()
+    }
+
     pub async fn get_sui_types_object(
         &self,
         object_id: &ObjectID,
         version: &SequenceNumber,
     ) -> Result<sui_types::object::Object, IndexerError> {
-        let pg_object = read_only!(&self.cp, |conn| objects_history::dsl::objects_history
-            .select((
-                objects_history::epoch,
-                objects_history::checkpoint,
-                objects_history::object_id,
-                objects_history::version,
-                objects_history::object_digest,
-                objects_history::owner_type,
-                objects_history::owner_address,
-                objects_history::initial_shared_version,
-                objects_history::previous_transaction,
-                objects_history::object_type,
-                objects_history::object_status,
-                objects_history::has_public_transfer,
-                objects_history::storage_rebate,
-                objects_history::bcs,
-            ))
-            .filter(objects_history::object_id.eq(object_id.to_string()))
-            .filter(objects_history::version.eq(version.value() as i64))
-            // pick data from checkpoint if available
-            .order(objects_history::checkpoint.desc())
-            .first::<Object>(conn)
-            .scope_boxed())
+        let pg_object = read_only!(self.reader(), |conn| {
+            objects_history::dsl::objects_history
+                .select((
+                    objects_history::epoch,
+                    objects_history::checkpoint,
+                    objects_history::object_id,
+                    objects_history::version,
+                    objects_history::object_digest,
+                    objects_history::owner_type,
+                    objects_history::owner_address,
+                    objects_history::initial_shared_version,
+                    objects_history::previous_transaction,
+                    objects_history::object_type,
+                    objects_history::object_status,
+                    objects_history::has_public_transfer,
+                    objects_history::storage_rebate,
+                    objects_history::bcs,
+                ))
+                .filter(objects_history::object_id.eq(object_id.to_string()))
+                .filter(objects_history::version.eq(version.value() as i64))
+                // pick data from checkpoint if available
+                .order(objects_history::checkpoint.desc())
+                .first::<Object>(conn)
+                .scope_boxed()
+        })
         .context("Failed reading Object from PostgresDB");
         match pg_object {
             Ok(pg_object) => {
@@ -154,7 +188,7 @@ impl PgIndexerStore {
         id: &ObjectID,
         version: &SequenceNumber,
     ) -> Result<Option<sui_types::object::Object>, IndexerError> {
-        let pg_object = read_only!(&self.cp, |conn| async {
+        let pg_object = read_only!(self.reader(), |conn| async {
             objects_history::dsl::objects_history
                 .select((
                     objects_history::epoch,
@@ -199,6 +233,7 @@ impl IndexerStore for PgIndexerStore {
     type ModuleCache = SyncModuleCache<IndexerModuleResolver>;
 
     async fn get_latest_checkpoint_sequence_number(&self) -> Result<i64, IndexerError> {
+        // Read from the database rather than a replica, the checkpoint handler resumes from it.
         read_only!(&self.cp, |conn| async {
             checkpoints_dsl::checkpoints
                 .select(max(checkpoints::sequence_number))
@@ -215,7 +250,7 @@ impl IndexerStore for PgIndexerStore {
         &self,
         id: CheckpointId,
     ) -> Result<sui_json_rpc_types::Checkpoint, IndexerError> {
-        read_only!(&self.cp, |conn| async {
+        read_only!(self.reader(), |conn| async {
             let cp: Checkpoint = match id {
                 CheckpointId::SequenceNumber(seq) => {
                     checkpoints_dsl::checkpoints
@@ -303,16 +338,18 @@ impl IndexerStore for PgIndexerStore {
         &self,
         digest: CheckpointDigest,
     ) -> Result<CheckpointSequenceNumber, IndexerError> {
-        Ok(read_only!(&self.cp, |conn| checkpoints_dsl::checkpoints
-            .select(checkpoints::sequence_number)
-            .filter(checkpoints::checkpoint_digest.eq(digest.base58_encode()))
-            .first::<i64>(conn)
-            .scope_boxed())
-        .context("Failed reading checkpoint seq number from PostgresDB")? as u64)
+        Ok(
+            read_only!(self.reader(), |conn| checkpoints_dsl::checkpoints
+                .select(checkpoints::sequence_number)
+                .filter(checkpoints::checkpoint_digest.eq(digest.base58_encode()))
+                .first::<i64>(conn)
+                .scope_boxed())
+            .context("Failed reading checkpoint seq number from PostgresDB")? as u64,
+        )
     }
 
     async fn get_event(&self, id: EventID) -> Result<Event, IndexerError> {
-        read_only!(&self.cp, |conn| events::table
+        read_only!(self.reader(), |conn| events::table
             .filter(events::dsl::transaction_digest.eq(id.tx_digest.base58_encode()))
             .filter(events::dsl::event_sequence.eq(id.event_seq as i64))
             .first::<Event>(conn)
@@ -381,7 +418,7 @@ impl IndexerStore for PgIndexerStore {
                 None
             };
 
-        let events_vec: Vec<Event> = read_only!(&self.cp, |conn| {
+        let events_vec: Vec<Event> = read_only!(self.reader(), |conn| {
             if let Some(pg_cursor) = pg_cursor {
                 if descending_order {
                     boxed_query = boxed_query.filter(events::dsl::id.lt(pg_cursor));
@@ -411,11 +448,12 @@ impl IndexerStore for PgIndexerStore {
             data: sui_event_vec,
             next_cursor,
             has_next_page,
+            truncated_due_to_limit: false,
         })
     }
 
     async fn get_total_transaction_number_from_checkpoints(&self) -> Result<i64, IndexerError> {
-        let checkpoint: Checkpoint = read_only!(&self.cp, |conn| {
+        let checkpoint: Checkpoint = read_only!(self.reader(), |conn| {
             checkpoints_dsl::checkpoints
                 .order(checkpoints_dsl::network_total_transactions.desc())
                 .first::<Checkpoint>(conn)
@@ -429,7 +467,7 @@ impl IndexerStore for PgIndexerStore {
         &self,
         tx_digest: &str,
     ) -> Result<Transaction, IndexerError> {
-        read_only!(&self.cp, |conn| {
+        read_only!(self.reader(), |conn| {
             transactions_dsl::transactions
                 .filter(transactions_dsl::transaction_digest.eq(tx_digest))
                 .first::<Transaction>(conn)
@@ -524,6 +562,7 @@ impl IndexerStore for PgIndexerStore {
             object_changes,
             balance_changes,
             errors: vec![],
+            effects_certificate: None,
         })
     }
 
@@ -531,7 +570,7 @@ impl IndexerStore for PgIndexerStore {
         &self,
         tx_digests: &[String],
     ) -> Result<Vec<Transaction>, IndexerError> {
-        read_only!(&self.cp, |conn| {
+        read_only!(self.reader(), |conn| {
             transactions_dsl::transactions
                 .filter(transactions_dsl::transaction_digest.eq_any(tx_digests))
                 .load::<Transaction>(conn)
@@ -547,7 +586,7 @@ impl IndexerStore for PgIndexerStore {
         tx_digest: Option<String>,
         is_descending: bool,
     ) -> Result<Option<i64>, IndexerError> {
-        read_only!(&self.cp, |conn| async {
+        read_only!(self.reader(), |conn| async {
             if let Some(digest) = &tx_digest {
                 let mut boxed_query = transactions_dsl::transactions
                     .filter(transactions_dsl::transaction_digest.eq(digest))
@@ -576,7 +615,7 @@ impl IndexerStore for PgIndexerStore {
         version: Option<SequenceNumber>,
     ) -> Result<ObjectRead, IndexerError> {
         // MUSTFIX (jian): add display field error support on implementation
-        let object = read_only!(&self.cp, |conn| async {
+        let object = read_only!(self.reader(), |conn| async {
             if let Some(version) = version {
                 objects_history::dsl::objects_history
                     .select((
@@ -624,7 +663,7 @@ impl IndexerStore for PgIndexerStore {
         cursor: Option<ObjectID>,
         limit: usize,
     ) -> Result<Vec<ObjectRead>, IndexerError> {
-        let objects = read_only!(&self.cp, |conn| {
+        let objects = read_only!(self.reader(), |conn| {
             let columns = vec![
                 "epoch",
                 "checkpoint",
@@ -677,7 +716,7 @@ impl IndexerStore for PgIndexerStore {
             "bcs",
         ];
 
-        let objects = read_only!(&self.cp, |conn| diesel::sql_query(
+        let objects = read_only!(self.reader(), |conn| diesel::sql_query(
             filter.to_latest_objects_sql(cursor, limit, columns)
         )
         .get_results::<Object>(conn)
@@ -689,12 +728,155 @@ impl IndexerStore for PgIndexerStore {
             .collect()
     }
 
+    async fn get_object_ownership_history(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<SequenceNumber>,
+        limit: usize,
+        descending_order: Option<bool>,
+    ) -> Result<Vec<ObjectOwnershipChange>, IndexerError> {
+        let is_descending = descending_order.unwrap_or_default();
+        let version = cursor
+            .map(|v| v.value() as i64)
+            .unwrap_or(if is_descending { i64::MAX } else { -1 });
+        let query = compose_object_ownership_history_query(is_descending);
+
+        let changes: Vec<DBObjectOwnershipChange> = read_only!(self.reader(), |conn| {
+            diesel::sql_query(query)
+                .bind::<VarChar, _>(object_id.to_string())
+                .bind::<BigInt, _>(version)
+                .bind::<BigInt, _>(limit as i64)
+                .get_results(conn)
+                .scope_boxed()
+        })
+        .context(&format!(
+            "Failed reading ownership history of object {object_id} in PostgresDB"
+        ))?;
+        changes.into_iter().map(|c| c.try_into()).collect()
+    }
+
+    async fn get_address_summary(
+        &self,
+        address: SuiAddress,
+    ) -> Result<Option<AddressSummary>, IndexerError> {
+        let address = address.to_string();
+        let summary: Option<(DBAddressSummary, i64)> = read_only!(self.reader(), |conn| async {
+            let Some(summary) = address_summaries_dsl::address_summaries
+                .filter(address_summaries::address.eq(&address))
+                .first::<DBAddressSummary>(conn)
+                .await
+                .optional()? else {
+                return Ok(None);
+            };
+            let packages_touched = address_packages_dsl::address_packages
+                .filter(address_packages::address.eq(&address))
+                .count()
+                .get_result::<i64>(conn)
+                .await?;
+            Ok::<_, diesel::result::Error>(Some((summary, packages_touched)))
+        }
+        .scope_boxed())
+        .context(&format!(
+            "Failed reading summary of address {address} from PostgresDB"
+        ))?;
+        summary
+            .map(|(summary, packages_touched)| summary.into_address_summary(packages_touched))
+            .transpose()
+    }
+
+    async fn get_address_gas_usage(
+        &self,
+        address: SuiAddress,
+        from_epoch: Option<EpochId>,
+        to_epoch: Option<EpochId>,
+        limit: usize,
+    ) -> Result<Vec<AddressGasUsage>, IndexerError> {
+        let mut query = address_gas_usage_dsl::address_gas_usage
+            .filter(address_gas_usage::address.eq(address.to_string()))
+            .into_boxed();
+        if let Some(from_epoch) = from_epoch {
+            query = query.filter(address_gas_usage::epoch.ge(from_epoch as i64));
+        }
+        if let Some(to_epoch) = to_epoch {
+            query = query.filter(address_gas_usage::epoch.le(to_epoch as i64));
+        }
+
+        let usage: Vec<DBAddressGasUsage> = read_only!(self.reader(), |conn| query
+            .order_by(address_gas_usage::epoch.asc())
+            .limit(limit as i64)
+            .load(conn)
+            .scope_boxed())
+        .context(&format!(
+            "Failed reading gas usage of address {address} from PostgresDB"
+        ))?;
+        usage.into_iter().map(|u| u.try_into()).collect()
+    }
+
+    async fn get_checkpoint_bookmark(
+        &self,
+        name: &str,
+    ) -> Result<Option<CheckpointBookmark>, IndexerError> {
+        let bookmark: Option<DBCheckpointBookmark> = read_only!(self.reader(), |conn| async {
+            checkpoint_bookmarks_dsl::checkpoint_bookmarks
+                .filter(checkpoint_bookmarks::name.eq(name))
+                .first::<DBCheckpointBookmark>(conn)
+                .await
+                .optional()
+        }
+        .scope_boxed())
+        .context(&format!(
+            "Failed reading checkpoint bookmark {name} from PostgresDB"
+        ))?;
+        Ok(bookmark.map(CheckpointBookmark::from))
+    }
+
+    async fn compare_and_set_checkpoint_bookmark(
+        &self,
+        name: &str,
+        checkpoint: CheckpointSequenceNumber,
+        expected: Option<CheckpointSequenceNumber>,
+    ) -> Result<Option<CheckpointBookmark>, IndexerError> {
+        let bookmark = DBCheckpointBookmark {
+            name: name.to_string(),
+            checkpoint: checkpoint as i64,
+            updated_at_ms: chrono::Utc::now().timestamp_millis(),
+        };
+        let updated = transactional!(&self.cp, |conn| async {
+            match expected {
+                None => {
+                    diesel::insert_into(checkpoint_bookmarks::table)
+                        .values(&bookmark)
+                        .on_conflict_do_nothing()
+                        .execute(conn)
+                        .await
+                }
+                Some(expected) => {
+                    diesel::update(checkpoint_bookmarks::table)
+                        .filter(checkpoint_bookmarks::name.eq(name))
+                        .filter(checkpoint_bookmarks::checkpoint.eq(expected as i64))
+                        .set((
+                            checkpoint_bookmarks::checkpoint.eq(bookmark.checkpoint),
+                            checkpoint_bookmarks::updated_at_ms.eq(bookmark.updated_at_ms),
+                        ))
+                        .execute(conn)
+                        .await
+                }
+            }
+            .map_err(IndexerError::from)
+        }
+        .scope_boxed())
+        .context(&format!(
+            "Failed writing checkpoint bookmark {name} to PostgresDB"
+        ))?;
+        Ok((updated > 0).then(|| bookmark.into()))
+    }
+
     async fn get_move_call_sequence_by_digest(
         &self,
         tx_digest: Option<String>,
         is_descending: bool,
     ) -> Result<Option<i64>, IndexerError> {
-        read_only!(&self.cp, |conn| async {
+        read_only!(self.reader(), |conn| async {
             if let Some(digest) = &tx_digest {
                 let mut boxed_query = move_calls_dsl::move_calls
                     .filter(move_calls_dsl::transaction_digest.eq(digest))
@@ -722,7 +904,7 @@ impl IndexerStore for PgIndexerStore {
         tx_digest: Option<String>,
         is_descending: bool,
     ) -> Result<Option<i64>, IndexerError> {
-        read_only!(&self.cp, |conn| async {
+        read_only!(self.reader(), |conn| async {
             if let Some(digest) = &tx_digest {
                 let mut boxed_query = input_objects_dsl::input_objects
                     .filter(input_objects_dsl::transaction_digest.eq(digest))
@@ -750,7 +932,7 @@ impl IndexerStore for PgIndexerStore {
         tx_digest: Option<String>,
         is_descending: bool,
     ) -> Result<Option<i64>, IndexerError> {
-        read_only!(&self.cp, |conn| async {
+        read_only!(self.reader(), |conn| async {
             if let Some(digest) = &tx_digest {
                 let mut boxed_query = recipients_dsl::recipients
                     .filter(recipients_dsl::transaction_digest.eq(digest))
@@ -779,7 +961,7 @@ impl IndexerStore for PgIndexerStore {
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        read_only!(&self.cp, |conn| {
+        read_only!(self.reader(), |conn| {
             let mut boxed_query = transactions_dsl::transactions.into_boxed();
             if let Some(start_sequence) = start_sequence {
                 if is_descending {
@@ -810,7 +992,7 @@ impl IndexerStore for PgIndexerStore {
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        read_only!(&self.cp, |conn| {
+        read_only!(self.reader(), |conn| {
             let mut boxed_query = transactions_dsl::transactions
                 .filter(transactions_dsl::checkpoint_sequence_number.eq(checkpoint_sequence_number))
                 .into_boxed();
@@ -842,7 +1024,7 @@ impl IndexerStore for PgIndexerStore {
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        read_only!(&self.cp, |conn| {
+        read_only!(self.reader(), |conn| {
             let mut boxed_query = transactions_dsl::transactions
                 .filter(transactions_dsl::transaction_kind.eq(kind.clone()))
                 .into_boxed();
@@ -875,7 +1057,7 @@ impl IndexerStore for PgIndexerStore {
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        read_only!(&self.cp, |conn| {
+        read_only!(self.reader(), |conn| {
             let mut boxed_query = transactions_dsl::transactions
                 .filter(transactions_dsl::mutated.contains(vec![Some(object_id.clone())]))
                 .or_filter(transactions_dsl::created.contains(vec![Some(object_id.clone())]))
@@ -909,7 +1091,7 @@ impl IndexerStore for PgIndexerStore {
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        read_only!(&self.cp, |conn| {
+        read_only!(self.reader(), |conn| {
             let mut boxed_query = transactions_dsl::transactions
                 .filter(transactions_dsl::sender.eq(sender_address.clone()))
                 .into_boxed();
@@ -969,7 +1151,7 @@ impl IndexerStore for PgIndexerStore {
             if is_descending { "DESC" } else { "ASC" },
             limit
         );
-        let tx_digests: Vec<String> = read_only!(&self.cp, |conn| diesel::sql_query(sql_query).load(conn).scope_boxed())
+        let tx_digests: Vec<String> = read_only!(self.reader(), |conn| diesel::sql_query(sql_query).load(conn).scope_boxed())
                 .context(&format!("Failed reading transaction digests by input object ID {object_id} and version {version:?} with start_sequence {start_sequence:?} and limit {limit}"))?
                 .into_iter()
                 .map(|table: TempDigestTable| table.digest_name)
@@ -1017,7 +1199,7 @@ impl IndexerStore for PgIndexerStore {
             if is_descending { "DESC" } else { "ASC" },
             limit
         );
-        let tx_digests: Vec<String> = read_only!(&self.cp, |conn| diesel::sql_query(sql_query).load(conn).scope_boxed())
+        let tx_digests: Vec<String> = read_only!(self.reader(), |conn| diesel::sql_query(sql_query).load(conn).scope_boxed())
                 .context(&format!(
                         "Failed reading transaction digests with package_name {} module_name {:?} and function_name {:?} and start_sequence {:?} and limit {}",
                         package_name, module_name, function_name, start_sequence, limit))?
@@ -1060,7 +1242,7 @@ impl IndexerStore for PgIndexerStore {
             if is_descending { "DESC" } else { "ASC" },
             limit
         );
-        let tx_digests: Vec<String> = read_only!(&self.cp, |conn| diesel::sql_query(sql_query).load(conn).scope_boxed())
+        let tx_digests: Vec<String> = read_only!(self.reader(), |conn| diesel::sql_query(sql_query).load(conn).scope_boxed())
                 .context(&format!("Failed reading transaction digests by recipient address {to} with start_sequence {start_sequence:?} and limit {limit}"))?
                 .into_iter()
                 .map(|table: TempDigestTable| table.digest_name)
@@ -1069,11 +1251,11 @@ impl IndexerStore for PgIndexerStore {
     }
 
     async fn get_network_metrics(&self) -> Result<NetworkMetrics, IndexerError> {
-        get_network_metrics_cached(&self.cp).await
+        get_network_metrics_cached(self.reader()).await
     }
 
     async fn get_move_call_metrics(&self) -> Result<MoveCallMetrics, IndexerError> {
-        let metrics = read_only!(&self.cp, |conn| {
+        let metrics = read_only!(self.reader(), |conn| {
             diesel::sql_query("SELECT * FROM epoch_move_call_metrics;")
                 .get_results::<DBMoveCallMetrics>(conn)
                 .scope_boxed()
@@ -1107,6 +1289,84 @@ impl IndexerStore for PgIndexerStore {
         })
     }
 
+    async fn get_move_call_stats(
+        &self,
+        epoch: EpochId,
+        package: Option<ObjectID>,
+        order: MoveCallStatsOrder,
+        limit: usize,
+    ) -> Result<Vec<MoveCallStats>, IndexerError> {
+        let mut query = move_call_stats_dsl::move_call_stats
+            .filter(move_call_stats::epoch.eq(epoch as i64))
+            .into_boxed();
+        if let Some(package) = package {
+            query = query.filter(move_call_stats::move_package.eq(package.to_string()));
+        }
+        query = match order {
+            MoveCallStatsOrder::CallCount => query.order_by(move_call_stats::call_count.desc()),
+            MoveCallStatsOrder::UniqueCallers => {
+                query.order_by(move_call_stats::unique_callers.desc())
+            }
+            MoveCallStatsOrder::TotalGas => query.order_by(move_call_stats::total_gas.desc()),
+            MoveCallStatsOrder::FailureRate => query.order_by(
+                diesel::dsl::sql::<diesel::sql_types::Double>(
+                    "failed_call_count::FLOAT8 / GREATEST(call_count, 1)",
+                )
+                .desc(),
+            ),
+        };
+
+        let stats: Vec<DBMoveCallStats> = read_only!(self.reader(), |conn| query
+            .then_order_by(move_call_stats::call_count.desc())
+            .limit(limit as i64)
+            .load(conn)
+            .scope_boxed())
+        .context(&format!(
+            "Failed reading move call stats of epoch {epoch} from PostgresDB"
+        ))?;
+        stats.into_iter().map(|s| s.try_into()).collect()
+    }
+
+    async fn get_gas_price_recommendation(
+        &self,
+        checkpoints: usize,
+    ) -> Result<GasPriceRecommendation, IndexerError> {
+        let reference_gas_price: i64 = read_only!(self.reader(), |conn| async {
+            system_states_dsl::system_states
+                .select(system_states::reference_gas_price)
+                .order_by(system_states::epoch.desc())
+                .first::<i64>(conn)
+                .await
+        }
+        .scope_boxed())
+        .context("Failed reading the reference gas price from PostgresDB")?;
+
+        let to_checkpoint = self.get_latest_checkpoint_sequence_number().await?.max(0);
+        let from_checkpoint = (to_checkpoint - checkpoints as i64 + 1).max(0);
+        let percentiles: DBGasPricePercentiles = read_only!(self.reader(), |conn| {
+            diesel::sql_query(
+                "SELECT COUNT(*) AS transactions, \
+                 percentile_disc(0.5) WITHIN GROUP (ORDER BY gas_price) AS p50, \
+                 percentile_disc(0.9) WITHIN GROUP (ORDER BY gas_price) AS p90 \
+                 FROM shared_object_gas_prices \
+                 WHERE checkpoint_sequence_number BETWEEN $1 AND $2",
+            )
+            .bind::<diesel::sql_types::BigInt, _>(from_checkpoint)
+            .bind::<diesel::sql_types::BigInt, _>(to_checkpoint)
+            .get_result::<DBGasPricePercentiles>(conn)
+            .scope_boxed()
+        })
+        .context(&format!(
+            "Failed reading the gas prices of checkpoints {from_checkpoint} to {to_checkpoint} from PostgresDB"
+        ))?;
+
+        Ok(percentiles.to_recommendation(
+            reference_gas_price as u64,
+            from_checkpoint as u64,
+            to_checkpoint as u64,
+        ))
+    }
+
     async fn persist_fast_path(
         &self,
         tx: Transaction,
@@ -1147,9 +1407,15 @@ impl IndexerStore for PgIndexerStore {
             events,
             objects_changes: tx_object_changes,
             addresses,
+            address_summaries,
+            address_packages,
+            address_gas_usage,
             packages,
             input_objects,
             move_calls,
+            move_call_stats,
+            move_call_callers,
+            shared_object_gas_prices,
             recipients,
         } = data;
 
@@ -1211,6 +1477,67 @@ impl IndexerStore for PgIndexerStore {
                     .context("Failed writing addresses to PostgresDB")?;
             }
 
+            // Add the activity of the addresses in the checkpoint to their summaries
+            for summaries_chunk in address_summaries.chunks(PG_COMMIT_CHUNK_SIZE) {
+                diesel::insert_into(address_summaries::table)
+                    .values(summaries_chunk)
+                    .on_conflict(address_summaries::address)
+                    .do_update()
+                    .set((
+                        address_summaries::last_active_checkpoint
+                            .eq(excluded(address_summaries::last_active_checkpoint)),
+                        address_summaries::last_active_tx
+                            .eq(excluded(address_summaries::last_active_tx)),
+                        address_summaries::last_active_time
+                            .eq(excluded(address_summaries::last_active_time)),
+                        address_summaries::transaction_count
+                            .eq(address_summaries::transaction_count
+                                + excluded(address_summaries::transaction_count)),
+                        address_summaries::gas_spent
+                            .eq(address_summaries::gas_spent
+                                + excluded(address_summaries::gas_spent)),
+                    ))
+                    .execute(conn)
+                    .await
+                    .map_err(IndexerError::from)
+                    .context("Failed writing address summaries to PostgresDB")?;
+            }
+            for packages_chunk in address_packages.chunks(PG_COMMIT_CHUNK_SIZE) {
+                diesel::insert_into(address_packages::table)
+                    .values(packages_chunk)
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+                    .await
+                    .map_err(IndexerError::from)
+                    .context("Failed writing address packages to PostgresDB")?;
+            }
+
+            // Add the gas paid by the addresses in the checkpoint to their usage in the epoch
+            for usage_chunk in address_gas_usage.chunks(PG_COMMIT_CHUNK_SIZE) {
+                diesel::insert_into(address_gas_usage::table)
+                    .values(usage_chunk)
+                    .on_conflict((address_gas_usage::address, address_gas_usage::epoch))
+                    .do_update()
+                    .set((
+                        address_gas_usage::transaction_count
+                            .eq(address_gas_usage::transaction_count
+                                + excluded(address_gas_usage::transaction_count)),
+                        address_gas_usage::computation_cost.eq(address_gas_usage::computation_cost
+                            + excluded(address_gas_usage::computation_cost)),
+                        address_gas_usage::storage_cost.eq(address_gas_usage::storage_cost
+                            + excluded(address_gas_usage::storage_cost)),
+                        address_gas_usage::storage_rebate.eq(address_gas_usage::storage_rebate
+                            + excluded(address_gas_usage::storage_rebate)),
+                        address_gas_usage::non_refundable_storage_fee
+                            .eq(address_gas_usage::non_refundable_storage_fee
+                                + excluded(address_gas_usage::non_refundable_storage_fee)),
+                    ))
+                    .execute(conn)
+                    .await
+                    .map_err(IndexerError::from)
+                    .context("Failed writing address gas usage to PostgresDB")?;
+            }
+
             // Commit indexed packages
             for packages_chunk in packages.chunks(PG_COMMIT_CHUNK_SIZE) {
                 diesel::insert_into(packages::table)
@@ -1233,6 +1560,56 @@ impl IndexerStore for PgIndexerStore {
                     .context("Failed writing move_calls to PostgresDB")?;
             }
 
+            // Add the move calls of the checkpoint to the statistics of the epoch, the callers
+            // are committed after the statistics as they update their number of unique callers.
+            for stats_chunk in move_call_stats.chunks(PG_COMMIT_CHUNK_SIZE) {
+                diesel::insert_into(move_call_stats::table)
+                    .values(stats_chunk)
+                    .on_conflict((
+                        move_call_stats::epoch,
+                        move_call_stats::move_package,
+                        move_call_stats::move_module,
+                        move_call_stats::move_function,
+                    ))
+                    .do_update()
+                    .set(
+                        (
+                            move_call_stats::call_count
+                                .eq(move_call_stats::call_count
+                                    + excluded(move_call_stats::call_count)),
+                            move_call_stats::failed_call_count
+                                .eq(move_call_stats::failed_call_count
+                                    + excluded(move_call_stats::failed_call_count)),
+                            move_call_stats::total_gas
+                                .eq(move_call_stats::total_gas
+                                    + excluded(move_call_stats::total_gas)),
+                        ),
+                    )
+                    .execute(conn)
+                    .await
+                    .map_err(IndexerError::from)
+                    .context("Failed writing move call stats to PostgresDB")?;
+            }
+            for callers_chunk in move_call_callers.chunks(PG_COMMIT_CHUNK_SIZE) {
+                diesel::insert_into(move_call_callers::table)
+                    .values(callers_chunk)
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+                    .await
+                    .map_err(IndexerError::from)
+                    .context("Failed writing move call callers to PostgresDB")?;
+            }
+
+            for gas_prices_chunk in shared_object_gas_prices.chunks(PG_COMMIT_CHUNK_SIZE) {
+                diesel::insert_into(shared_object_gas_prices::table)
+                    .values(gas_prices_chunk)
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+                    .await
+                    .map_err(IndexerError::from)
+                    .context("Failed writing shared object gas prices to PostgresDB")?;
+            }
+
             // Commit indexed input objects
             for input_objects_chunk in input_objects.chunks(PG_COMMIT_CHUNK_SIZE) {
                 diesel::insert_into(input_objects::table)
@@ -1370,7 +1747,7 @@ WHERE e1.epoch = e2.epoch
                 .order_by(epochs::epoch.asc());
         }
 
-        let epoch_info: Vec<DBEpochInfo> = read_only!(&self.cp, |conn| query
+        let epoch_info: Vec<DBEpochInfo> = read_only!(self.reader(), |conn| query
             .limit(limit as i64)
             .load(conn)
             .scope_boxed())
@@ -1382,7 +1759,7 @@ WHERE e1.epoch = e2.epoch
         })?;
 
         let validators: Vec<DBValidatorSummary> =
-            read_only!(&self.cp, |conn| validators::dsl::validators
+            read_only!(self.reader(), |conn| validators::dsl::validators
                 .filter(validators::epoch.gt(id))
                 .load(conn)
                 .scope_boxed())
@@ -1411,7 +1788,7 @@ WHERE e1.epoch = e2.epoch
     }
 
     async fn get_current_epoch(&self) -> Result<EpochInfo, IndexerError> {
-        let epoch_info: DBEpochInfo = read_only!(&self.cp, |conn| async {
+        let epoch_info: DBEpochInfo = read_only!(self.reader(), |conn| async {
             epochs::dsl::epochs
                 .order_by(epochs::epoch.desc())
                 .first::<DBEpochInfo>(conn)
@@ -1420,7 +1797,7 @@ WHERE e1.epoch = e2.epoch
         .scope_boxed())
         .context("Failed reading latest epoch")?;
 
-        let validators: Vec<DBValidatorSummary> = read_only!(&self.cp, |conn| async {
+        let validators: Vec<DBValidatorSummary> = read_only!(self.reader(), |conn| async {
             validators::dsl::validators
                 .filter(validators::epoch.eq(epoch_info.epoch))
                 .load(conn)
@@ -1431,6 +1808,57 @@ WHERE e1.epoch = e2.epoch
 
         epoch_info.to_epoch_info(validators)
     }
+
+    async fn get_committee_history(
+        &self,
+        from_epoch: EpochId,
+        to_epoch: EpochId,
+    ) -> Result<Vec<EpochCommittee>, IndexerError> {
+        // The committee of the previous epoch is needed for the changes of the first epoch.
+        let validators: Vec<DBValidatorSummary> = read_only!(self.reader(), |conn| async {
+            validators::dsl::validators
+                .filter(validators::epoch.ge(from_epoch.saturating_sub(1) as i64))
+                .filter(validators::epoch.le(to_epoch as i64))
+                .order_by((validators::epoch.asc(), validators::sui_address.asc()))
+                .load(conn)
+                .await
+        }
+        .scope_boxed())
+        .context(&format!(
+            "Failed reading validators of epochs {from_epoch} to {to_epoch} from PostgresDB"
+        ))?;
+
+        let mut committees = validators.into_iter().try_fold(
+            BTreeMap::<EpochId, Vec<CommitteeMember>>::new(),
+            |mut acc, v| {
+                acc.entry(v.epoch as u64).or_default().push(v.try_into()?);
+                Ok::<_, IndexerError>(acc)
+            },
+        )?;
+        let mut previous: BTreeSet<SuiAddress> = if from_epoch == 0 {
+            BTreeSet::new()
+        } else {
+            committees
+                .remove(&(from_epoch - 1))
+                .unwrap_or_default()
+                .iter()
+                .map(|v| v.sui_address)
+                .collect()
+        };
+
+        let mut history = Vec::with_capacity(committees.len());
+        for (epoch, validators) in committees {
+            let addresses: BTreeSet<_> = validators.iter().map(|v| v.sui_address).collect();
+            history.push(EpochCommittee {
+                epoch,
+                validators,
+                joined: addresses.difference(&previous).copied().collect(),
+                left: previous.difference(&addresses).copied().collect(),
+            });
+            previous = addresses;
+        }
+        Ok(history)
+    }
 }
 
 async fn persist_transaction_object_changes(
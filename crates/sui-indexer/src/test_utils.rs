@@ -10,7 +10,7 @@ use sui_json_rpc_types::SuiTransactionBlockResponse;
 use crate::errors::IndexerError;
 use crate::store::PgIndexerStore;
 use crate::utils::reset_database;
-use crate::{new_pg_connection_pool, Indexer, IndexerConfig};
+use crate::{new_async_pg_connection_pool, new_pg_connection_pool, Indexer, IndexerConfig};
 
 /// Spawns an indexer thread with provided Postgres DB url
 pub async fn start_test_indexer(
@@ -27,7 +27,14 @@ pub async fn start_test_indexer(
             true,
         )?;
     }
-    let store = PgIndexerStore::new(async_pool, blocking_pool).await;
+    let replicas = config
+        .db_replica_urls
+        .iter()
+        .map(|url| new_async_pg_connection_pool(url))
+        .collect::<Result<Vec<_>, _>>()?;
+    let store = PgIndexerStore::new(async_pool, blocking_pool)
+        .await
+        .with_read_replicas(replicas, config.max_replica_checkpoint_lag);
 
     let registry = Registry::default();
     let store_clone = store.clone();
@@ -42,6 +42,7 @@ impl TryFrom<SuiTransactionBlockResponse> for FastPathTransactionBlockResponse {
             confirmed_local_execution,
             checkpoint: _,
             errors,
+            effects_certificate: _,
         } = response;
 
         let transaction = transaction.ok_or_else(|| {
@@ -124,6 +125,7 @@ impl TryFrom<SuiTransactionBlockResponse> for CheckpointTransactionBlockResponse
             confirmed_local_execution,
             checkpoint,
             errors,
+            effects_certificate: _,
         } = response;
 
         let transaction = transaction.ok_or_else(|| {
@@ -385,6 +387,10 @@ impl From<SuiTransactionBlockResponseWithOptions> for SuiTransactionBlockRespons
             confirmed_local_execution: response.confirmed_local_execution,
             checkpoint: response.checkpoint,
             errors: vec![],
+            effects_certificate: options
+                .show_effects_certificate
+                .then_some(response.effects_certificate)
+                .flatten(),
         }
     }
 }
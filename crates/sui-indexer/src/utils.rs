@@ -25,10 +25,11 @@ use sui_types::object::Owner;
 use sui_types::storage::{DeleteKind, WriteKind};
 
 use crate::errors::IndexerError;
+use crate::migration::{self, DEFAULT_BACKFILL_BATCH_SIZE, ONLINE_MIGRATIONS};
 use crate::types::CheckpointTransactionBlockResponse;
 use crate::PgPoolConnection;
 
-const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+pub(crate) const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
 
 /// Resets the database by reverting all migrations and reapplying them.
 ///
@@ -47,6 +48,9 @@ pub fn reset_database(conn: &mut PgPoolConnection, drop_all: bool) -> Result<(),
 
     conn.run_migrations(&MIGRATIONS.migrations().unwrap())
         .map_err(|e| anyhow!("Failed to run migrations {e}"))?;
+    // The tables are empty, the online migrations are applied at once.
+    migration::apply(conn, ONLINE_MIGRATIONS, DEFAULT_BACKFILL_BATCH_SIZE)
+        .map_err(|e| anyhow!("Failed to apply online migrations {e}"))?;
     info!("Reset database complete.");
     Ok(())
 }
@@ -7,7 +7,8 @@ pub mod pg_integration_test {
     use std::env;
     use std::str::FromStr;
 
-    use diesel::RunQueryDsl;
+    use diesel::sql_types::{BigInt, Text};
+    use diesel::{QueryableByName, RunQueryDsl};
     use futures::future::join_all;
     use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
     use move_core_types::ident_str;
@@ -19,6 +20,7 @@ pub mod pg_integration_test {
 
     use sui_config::SUI_KEYSTORE_FILENAME;
     use sui_indexer::errors::IndexerError;
+    use sui_indexer::migration::{self, MigrationStep, OnlineMigration, OnlineMigrationPhase};
     use sui_indexer::models::objects::{
         compose_object_bulk_insert_query, compose_object_bulk_insert_update_query,
         group_and_sort_objects, NamedBcsBytes, Object, ObjectStatus,
@@ -32,10 +34,11 @@ pub mod pg_integration_test {
     use sui_json_rpc::api::IndexerApiClient;
     use sui_json_rpc::api::{ReadApiClient, TransactionBuilderClient, WriteApiClient};
     use sui_json_rpc_types::{
-        CheckpointId, EventFilter, SuiMoveObject, SuiObjectData, SuiObjectDataFilter,
-        SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery, SuiParsedMoveObject,
-        SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
-        SuiTransactionBlockResponseQuery, TransactionBlockBytes,
+        CheckpointId, CheckpointedObjectID, EventFilter, MoveCallStatsOrder, SuiMoveObject,
+        SuiObjectData, SuiObjectDataFilter, SuiObjectDataOptions, SuiObjectResponse,
+        SuiObjectResponseQuery, SuiParsedMoveObject, SuiTransactionBlockResponse,
+        SuiTransactionBlockResponseOptions, SuiTransactionBlockResponseQuery,
+        TransactionBlockBytes,
     };
     use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
     use sui_types::base_types::{ObjectID, SuiAddress};
@@ -43,13 +46,17 @@ pub mod pg_integration_test {
     use sui_types::error::SuiObjectResponseError;
     use sui_types::gas_coin::GasCoin;
     use sui_types::messages::ExecuteTransactionRequestType;
-    use sui_types::object::ObjectFormatOptions;
+    use sui_types::object::{ObjectFormatOptions, Owner};
     use sui_types::query::TransactionFilter;
     use sui_types::utils::to_sender_signed_transaction;
     use test_utils::network::{TestCluster, TestClusterBuilder};
-    use test_utils::transaction::{create_devnet_nft, delete_devnet_nft, publish_nfts_package};
+    use test_utils::transaction::{
+        create_devnet_nft, delete_devnet_nft, increment_counter,
+        publish_basics_package_and_make_counter, publish_nfts_package,
+    };
 
     const WAIT_UNTIL_TIME_LIMIT: u64 = 60;
+    const CHECKPOINT_BOOKMARK_AUTH_TOKEN: &str = "bookmark-token";
 
     async fn get_owned_objects_for_address(
         indexer_rpc_client: &HttpClient,
@@ -869,6 +876,46 @@ pub mod pg_integration_test {
         drop(test_cluster);
     }
 
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_get_committee_history() -> Result<(), anyhow::Error> {
+        let (test_cluster, indexer_rpc_client, store, _handle) =
+            start_test_cluster(Some(10000)).await;
+        wait_until_next_checkpoint(&store).await;
+        wait_until_next_epoch(&store).await;
+
+        let history = indexer_rpc_client
+            .get_committee_history(0.into(), 1.into())
+            .await?;
+        assert_eq!(history.len(), 2);
+        let validators = test_cluster.swarm.validators().count();
+        assert_eq!(history[0].epoch, 0);
+        assert_eq!(history[0].validators.len(), validators);
+        assert_eq!(history[0].joined.len(), validators);
+        assert!(history[0].left.is_empty());
+        // The validator set does not change.
+        assert_eq!(history[1].epoch, 1);
+        assert_eq!(history[1].validators.len(), validators);
+        assert!(history[1].joined.is_empty());
+        assert!(history[1].left.is_empty());
+
+        let history = indexer_rpc_client
+            .get_committee_history(1.into(), 1.into())
+            .await?;
+        assert_eq!(history.len(), 1);
+        assert!(history[0].joined.is_empty());
+
+        assert!(indexer_rpc_client
+            .get_committee_history(1.into(), 0.into())
+            .await
+            .is_err());
+        assert!(indexer_rpc_client
+            .get_committee_history(0.into(), 1000.into())
+            .await
+            .is_err());
+        Ok(())
+    }
+
     #[tokio::test]
     #[timeout(60000)]
     async fn test_get_last_checkpoint_of_epoch() {
@@ -1255,6 +1302,419 @@ pub mod pg_integration_test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_get_object_ownership_history() -> Result<(), anyhow::Error> {
+        let (mut test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster(None).await;
+        wait_until_next_checkpoint(&store).await;
+        let (tx_response, sender, recipient, gas_objects) =
+            execute_simple_transfer(&mut test_cluster, &indexer_rpc_client).await?;
+        wait_until_transaction_synced_in_checkpoint(
+            &store,
+            tx_response.digest.base58_encode().as_str(),
+        )
+        .await;
+
+        // The transferred coin was created at genesis, owned by the sender.
+        let coin = *gas_objects.first().unwrap();
+        let history = indexer_rpc_client
+            .get_object_ownership_history(coin, None, None, None)
+            .await?;
+        assert!(!history.has_next_page);
+        assert_eq!(history.data.len(), 2);
+        let (created, transferred) = (&history.data[0], &history.data[1]);
+        assert_eq!(created.previous_owner, None);
+        assert_eq!(created.new_owner, Owner::AddressOwner(sender));
+        assert_eq!(created.checkpoint, Some(0));
+        assert_eq!(
+            transferred.previous_owner,
+            Some(Owner::AddressOwner(sender))
+        );
+        assert_eq!(transferred.new_owner, Owner::AddressOwner(recipient));
+        assert_eq!(transferred.transaction_digest, tx_response.digest);
+        assert!(transferred.checkpoint.is_some());
+
+        // The gas coin of the transfer is mutated, but stays owned by the sender.
+        let history = indexer_rpc_client
+            .get_object_ownership_history(*gas_objects.last().unwrap(), None, None, None)
+            .await?;
+        assert_eq!(history.data.len(), 1);
+        assert_eq!(history.data[0].previous_owner, None);
+
+        // One transition per page, latest first.
+        let page = indexer_rpc_client
+            .get_object_ownership_history(coin, None, Some(1.into()), Some(true))
+            .await?;
+        assert!(page.has_next_page);
+        assert_eq!(page.data, vec![transferred.clone()]);
+        let page = indexer_rpc_client
+            .get_object_ownership_history(coin, page.next_cursor, Some(1.into()), Some(true))
+            .await?;
+        assert_eq!(page.data, vec![created.clone()]);
+        let page = indexer_rpc_client
+            .get_object_ownership_history(coin, page.next_cursor, Some(1.into()), Some(true))
+            .await?;
+        assert!(page.data.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_address_summary() -> Result<(), anyhow::Error> {
+        let (mut test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster(None).await;
+        wait_until_next_checkpoint(&store).await;
+        let context = &mut test_cluster.wallet;
+        let (package_id, publish_digest) = publish_nfts_package(context, /* sender */ None).await;
+        wait_until_transaction_synced(&store, publish_digest.base58_encode().as_str()).await;
+        let (_, _, digest_one) = create_devnet_nft(context, package_id).await.unwrap();
+        wait_until_transaction_synced(&store, digest_one.base58_encode().as_str()).await;
+        let (sender, _, digest_two) = create_devnet_nft(context, package_id).await.unwrap();
+        wait_until_transaction_synced_in_checkpoint(&store, digest_two.base58_encode().as_str())
+            .await;
+
+        let summary = indexer_rpc_client
+            .get_address_summary(sender)
+            .await?
+            .unwrap();
+        assert_eq!(summary.address, sender);
+        // The sender received its gas coins at genesis.
+        assert_eq!(summary.first_seen_checkpoint, 0);
+        assert_eq!(summary.last_transaction, digest_two);
+        assert!(summary.transaction_count >= 3);
+        assert!(summary.last_active_checkpoint >= summary.first_seen_checkpoint);
+        // Both NFTs are minted by calls to the same package.
+        assert_eq!(summary.packages_touched, 1);
+
+        assert!(indexer_rpc_client
+            .get_address_summary(SuiAddress::random_for_testing_only())
+            .await?
+            .is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_address_gas_usage() -> Result<(), anyhow::Error> {
+        let (mut test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster(None).await;
+        wait_until_next_checkpoint(&store).await;
+        let context = &mut test_cluster.wallet;
+        let (package_id, publish_digest) = publish_nfts_package(context, /* sender */ None).await;
+        wait_until_transaction_synced(&store, publish_digest.base58_encode().as_str()).await;
+        let (sender, _, digest) = create_devnet_nft(context, package_id).await.unwrap();
+        wait_until_transaction_synced_in_checkpoint(&store, digest.base58_encode().as_str()).await;
+
+        let page = indexer_rpc_client
+            .get_address_gas_usage(sender, None, None, None, None)
+            .await?;
+        // All the transactions of the test run within the first epoch.
+        assert_eq!(page.data.len(), 1);
+        assert!(!page.has_next_page);
+        assert_eq!(page.next_cursor, Some(0.into()));
+        let usage = &page.data[0];
+        assert_eq!(usage.address, sender);
+        assert_eq!(usage.epoch, 0);
+        assert!(usage.transaction_count >= 2);
+        assert!(usage.computation_cost > 0);
+        assert_eq!(
+            usage.net_gas_cost,
+            usage.computation_cost as i64 + usage.storage_cost as i64 - usage.storage_rebate as i64
+        );
+
+        assert!(indexer_rpc_client
+            .get_address_gas_usage(sender, Some(1.into()), None, None, None)
+            .await?
+            .data
+            .is_empty());
+        // The page after the cursor of the last epoch is empty.
+        assert!(indexer_rpc_client
+            .get_address_gas_usage(sender, None, None, page.next_cursor, None)
+            .await?
+            .data
+            .is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_move_call_stats() -> Result<(), anyhow::Error> {
+        let (mut test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster(None).await;
+        wait_until_next_checkpoint(&store).await;
+        let context = &mut test_cluster.wallet;
+        let (package_id, publish_digest) = publish_nfts_package(context, /* sender */ None).await;
+        wait_until_transaction_synced(&store, publish_digest.base58_encode().as_str()).await;
+        let (_, _, digest_one) = create_devnet_nft(context, package_id).await.unwrap();
+        wait_until_transaction_synced(&store, digest_one.base58_encode().as_str()).await;
+        let (_, _, digest_two) = create_devnet_nft(context, package_id).await.unwrap();
+        wait_until_transaction_synced_in_checkpoint(&store, digest_two.base58_encode().as_str())
+            .await;
+
+        for order in [
+            None,
+            Some(MoveCallStatsOrder::UniqueCallers),
+            Some(MoveCallStatsOrder::FailureRate),
+        ] {
+            let stats = indexer_rpc_client
+                .get_move_call_stats(0.into(), Some(package_id), order, None)
+                .await?;
+            assert_eq!(stats.len(), 1);
+            let stats = &stats[0];
+            assert_eq!(stats.function.package, package_id);
+            assert_eq!(stats.function.module.as_str(), "devnet_nft");
+            assert_eq!(stats.function.function.as_str(), "mint");
+            assert_eq!(stats.call_count, 2);
+            assert_eq!(stats.failed_call_count, 0);
+            assert_eq!(stats.failure_rate, 0.0);
+            assert_eq!(stats.unique_callers, 1);
+            assert!(stats.total_gas > 0);
+        }
+
+        let stats = indexer_rpc_client
+            .get_move_call_stats(1000.into(), Some(package_id), None, None)
+            .await?;
+        assert!(stats.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_gas_price_recommendation() -> Result<(), anyhow::Error> {
+        let (mut test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster(None).await;
+        wait_until_next_checkpoint(&store).await;
+        let context = &mut test_cluster.wallet;
+        let sender = context.config.keystore.addresses().get(0).cloned().unwrap();
+        let (package_ref, counter_ref) =
+            publish_basics_package_and_make_counter(context, sender).await;
+        let response = increment_counter(context, sender, None, package_ref.0, counter_ref.0).await;
+        wait_until_transaction_synced_in_checkpoint(
+            &store,
+            response.digest.base58_encode().as_str(),
+        )
+        .await;
+
+        let recommendation = indexer_rpc_client
+            .get_gas_price_recommendation(None)
+            .await?;
+        assert!(recommendation.transactions >= 1);
+        assert!(recommendation.from_checkpoint <= recommendation.to_checkpoint);
+        // Every transaction of the test cluster pays the reference gas price.
+        assert_eq!(
+            recommendation.p50_gas_price,
+            recommendation.reference_gas_price
+        );
+        assert_eq!(
+            recommendation.p90_gas_price,
+            recommendation.reference_gas_price
+        );
+
+        assert!(indexer_rpc_client
+            .get_gas_price_recommendation(Some(0.into()))
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_objects_snapshot_range() -> Result<(), anyhow::Error> {
+        let (_test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster(None).await;
+        wait_until_next_checkpoint(&store).await;
+
+        let range = indexer_rpc_client.get_objects_snapshot_range().await?;
+        // No lag nor window is configured.
+        assert_eq!(range.consistent_checkpoint, range.latest_checkpoint);
+        assert_eq!(range.earliest_checkpoint, 0);
+
+        let coins = SuiObjectResponseQuery::new_with_filter(SuiObjectDataFilter::StructType(
+            parse_struct_tag("0x2::coin::Coin<0x2::sui::SUI>").unwrap(),
+        ));
+        let page = indexer_rpc_client
+            .query_objects(coins.clone(), None, Some(1.into()))
+            .await?;
+        let cursor = page.next_cursor.unwrap();
+        let at_checkpoint = cursor.at_checkpoint.unwrap();
+        assert!(at_checkpoint >= range.consistent_checkpoint);
+
+        // Cursors beyond the indexed checkpoints are rejected.
+        let latest_checkpoint = store.get_latest_checkpoint_sequence_number().await? as u64;
+        let cursor = CheckpointedObjectID {
+            object_id: cursor.object_id,
+            at_checkpoint: Some(latest_checkpoint + 1000),
+        };
+        assert!(indexer_rpc_client
+            .query_objects(coins, Some(cursor), None)
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_bookmarks() -> Result<(), anyhow::Error> {
+        let (_test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster(None).await;
+        wait_until_next_checkpoint(&store).await;
+        let cp = store.get_latest_checkpoint_sequence_number().await? as u64;
+        let name = "my-app-cursor".to_string();
+        let token = || CHECKPOINT_BOOKMARK_AUTH_TOKEN.to_string();
+
+        assert!(indexer_rpc_client
+            .get_checkpoint_bookmark(name.clone())
+            .await?
+            .is_none());
+        // Updating a bookmark which does not exist fails.
+        assert!(indexer_rpc_client
+            .set_checkpoint_bookmark(name.clone(), 0.into(), Some(0.into()), token())
+            .await
+            .is_err());
+
+        // Bookmarks cannot be set without the auth token of the indexer.
+        assert!(indexer_rpc_client
+            .set_checkpoint_bookmark(name.clone(), 0.into(), None, "wrong-token".to_string())
+            .await
+            .is_err());
+
+        let bookmark = indexer_rpc_client
+            .set_checkpoint_bookmark(name.clone(), 0.into(), None, token())
+            .await?;
+        assert_eq!(bookmark.name, name);
+        assert_eq!(bookmark.checkpoint, 0);
+        // Creating the bookmark again fails, as well as updating it from another checkpoint.
+        assert!(indexer_rpc_client
+            .set_checkpoint_bookmark(name.clone(), cp.into(), None, token())
+            .await
+            .is_err());
+        assert!(indexer_rpc_client
+            .set_checkpoint_bookmark(name.clone(), cp.into(), Some((cp + 1).into()), token())
+            .await
+            .is_err());
+        // Checkpoints which are not indexed cannot be bookmarked.
+        assert!(indexer_rpc_client
+            .set_checkpoint_bookmark(name.clone(), (cp + 1000).into(), Some(0.into()), token())
+            .await
+            .is_err());
+
+        let bookmark = indexer_rpc_client
+            .set_checkpoint_bookmark(name.clone(), cp.into(), Some(0.into()), token())
+            .await?;
+        assert_eq!(bookmark.checkpoint, cp);
+        assert_eq!(
+            indexer_rpc_client.get_checkpoint_bookmark(name).await?,
+            Some(bookmark)
+        );
+        Ok(())
+    }
+
+    #[derive(QueryableByName, Debug, PartialEq, Eq)]
+    struct MigratedRow {
+        #[diesel(sql_type = BigInt)]
+        id: i64,
+        #[diesel(sql_type = BigInt)]
+        value: i64,
+        #[diesel(sql_type = BigInt)]
+        doubled: i64,
+    }
+
+    #[derive(QueryableByName, Debug)]
+    struct IndexName {
+        #[diesel(sql_type = Text)]
+        indexname: String,
+    }
+
+    #[tokio::test]
+    async fn test_online_migration() -> Result<(), anyhow::Error> {
+        const MIGRATION: OnlineMigration = OnlineMigration {
+            name: "add_doubled_value",
+            table: "online_migration_test",
+            key: "id",
+            create_shadow: "CREATE TABLE {shadow} (id BIGINT PRIMARY KEY, value BIGINT NOT NULL, doubled BIGINT NOT NULL);
+                CREATE INDEX {shadow}_doubled ON {shadow} (doubled);",
+            columns: &[("id", "id"), ("value", "value"), ("doubled", "value * 2")],
+        };
+        // Helps clear/build the database
+        start_test_cluster(None).await;
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+        let (pg_connection_pool, _) = new_pg_connection_pool(&db_url).await?;
+        let mut conn = get_pg_pool_connection(&pg_connection_pool)?;
+
+        diesel::sql_query(
+            "CREATE TABLE online_migration_test (id BIGSERIAL PRIMARY KEY, value BIGINT NOT NULL)",
+        )
+        .execute(&mut conn)?;
+        diesel::sql_query(
+            "INSERT INTO online_migration_test (value) SELECT generate_series(1, 10)",
+        )
+        .execute(&mut conn)?;
+
+        let steps = migration::plan(&mut conn, &[MIGRATION])?;
+        assert_eq!(
+            steps,
+            vec![
+                MigrationStep::CreateShadowTable(MIGRATION),
+                MigrationStep::Backfill(MIGRATION, None),
+                MigrationStep::CutOver(MIGRATION),
+            ]
+        );
+        migration::apply_step(&mut conn, &steps[0], 3)?;
+        // The writes made during the migration are mirrored into the shadow table.
+        diesel::sql_query("INSERT INTO online_migration_test (value) VALUES (11)")
+            .execute(&mut conn)?;
+        diesel::sql_query("UPDATE online_migration_test SET value = 20 WHERE id = 1")
+            .execute(&mut conn)?;
+        diesel::sql_query("DELETE FROM online_migration_test WHERE id = 2").execute(&mut conn)?;
+
+        migration::apply_step(&mut conn, &steps[1], 3)?;
+        let status = migration::status(&mut conn, &[MIGRATION])?;
+        assert_eq!(
+            status.online_migrations,
+            vec![(
+                MIGRATION,
+                OnlineMigrationPhase::Backfilling {
+                    cursor: Some(11),
+                    // The rows mirrored by the trigger are not copied again.
+                    rows_copied: 8,
+                }
+            )]
+        );
+        assert_eq!(
+            migration::plan(&mut conn, &[MIGRATION])?,
+            vec![
+                MigrationStep::Backfill(MIGRATION, Some(11)),
+                MigrationStep::CutOver(MIGRATION),
+            ]
+        );
+
+        migration::apply(&mut conn, &[MIGRATION], 3)?;
+        assert!(migration::plan(&mut conn, &[MIGRATION])?.is_empty());
+        // The migrated table keeps generating its keys from the sequence of the table.
+        diesel::sql_query("INSERT INTO online_migration_test (value, doubled) VALUES (12, 24)")
+            .execute(&mut conn)?;
+        let rows: Vec<MigratedRow> =
+            diesel::sql_query("SELECT id, value, doubled FROM online_migration_test ORDER BY id")
+                .load(&mut conn)?;
+        let mut expected = vec![MigratedRow {
+            id: 1,
+            value: 20,
+            doubled: 40,
+        }];
+        expected.extend((3..=12).map(|id| MigratedRow {
+            id,
+            value: id,
+            doubled: id * 2,
+        }));
+        assert_eq!(rows, expected);
+
+        let indexes: Vec<String> = diesel::sql_query(
+            "SELECT indexname FROM pg_indexes WHERE tablename = 'online_migration_test' ORDER BY indexname",
+        )
+        .load::<IndexName>(&mut conn)?
+        .into_iter()
+        .map(|index| index.indexname)
+        .collect();
+        assert_eq!(
+            indexes,
+            vec![
+                "online_migration_test_doubled",
+                "online_migration_test_pkey"
+            ]
+        );
+        Ok(())
+    }
+
     async fn start_test_cluster(
         epoch_duration_ms: Option<u64>,
     ) -> (
@@ -1282,6 +1742,7 @@ pub mod pg_integration_test {
             db_url,
             rpc_client_url: test_cluster.rpc_url().to_string(),
             migrated_methods: IndexerConfig::all_implemented_methods(),
+            checkpoint_bookmark_auth_token: Some(CHECKPOINT_BOOKMARK_AUTH_TOKEN.to_string()),
             reset_db: true,
             ..Default::default()
         };
@@ -3,11 +3,13 @@
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 pub use balance_changes::*;
 pub use object_changes::*;
 pub use sui_checkpoint::*;
 pub use sui_coin::*;
+pub use sui_error_code::*;
 pub use sui_event::*;
 pub use sui_extended::*;
 pub use sui_governance::*;
@@ -25,6 +27,7 @@ mod balance_changes;
 mod object_changes;
 mod sui_checkpoint;
 mod sui_coin;
+mod sui_error_code;
 mod sui_event;
 mod sui_extended;
 mod sui_governance;
@@ -32,7 +35,24 @@ mod sui_move;
 mod sui_object;
 mod sui_transaction;
 
+/// Semantic version of the JSON Schema of the types, recorded by the `generate-json-rpc-spec`
+/// example of sui-open-rpc. The major version is bumped with breaking changes of the types, e.g.
+/// fields removed, renamed or made required, the minor version with additions, and the patch
+/// version with changes of the descriptions only.
+pub const RPC_TYPES_SCHEMA_VERSION: &str = "1.0.0";
+
 pub type DynamicFieldPage = Page<DynamicFieldInfo, ObjectID>;
+pub type DynamicFieldExportPage = Page<DynamicFieldEntry, ObjectID>;
+
+/// A dynamic field with its value, decoded from the field object, or the content of the child
+/// object for dynamic object fields.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DynamicFieldEntry {
+    pub field: DynamicFieldInfo,
+    pub value: Value,
+}
+
 /// `next_cursor` points to the last item in the page;
 /// Reading with `next_cursor` will start from the next item after `next_cursor` if
 /// `next_cursor` is `Some`, otherwise it will start from the first item.
@@ -42,4 +62,87 @@ pub struct Page<T, C> {
     pub data: Vec<T>,
     pub next_cursor: Option<C>,
     pub has_next_page: bool,
+    /// Whether the page holds fewer items than requested to keep the response within the size
+    /// limit of the server, in which case the next page starts after the last item returned.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated_due_to_limit: bool,
+}
+
+impl<T: Serialize, C> Page<T, C> {
+    /// Keeps the first items of the page whose JSON serialization fits in `max_size` bytes, and
+    /// at least one item so that reading the pages makes progress. If items are dropped, the
+    /// page is marked as truncated and its `next_cursor` is set by `cursor`, from the last item
+    /// kept and its index.
+    pub fn truncate_to_size(&mut self, max_size: usize, cursor: impl FnOnce(usize, &T) -> C) {
+        let mut budget = PageSizeBudget::new(max_size);
+        let len = self
+            .data
+            .iter()
+            .take_while(|item| budget.try_add(item))
+            .count();
+        if len >= self.data.len() {
+            return;
+        }
+        self.data.truncate(len);
+        self.mark_truncated(cursor(len - 1, &self.data[len - 1]));
+    }
+}
+
+impl<T, C> Page<T, C> {
+    /// Marks the page as truncated after its last item, whose cursor is `next_cursor`.
+    pub fn mark_truncated(&mut self, next_cursor: C) {
+        self.next_cursor = Some(next_cursor);
+        self.has_next_page = true;
+        self.truncated_due_to_limit = true;
+    }
+}
+
+/// Accumulates the JSON size of the items of a page while they are read, so that reading stops
+/// once the page is full. The first item always fits, so that reading the pages makes progress.
+pub struct PageSizeBudget {
+    max_size: usize,
+    counter: ByteCounter,
+    items: usize,
+    full: bool,
+}
+
+impl PageSizeBudget {
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            max_size,
+            counter: ByteCounter(0),
+            items: 0,
+            full: false,
+        }
+    }
+
+    /// Counts `item` in the page and returns whether it fits. Once an item does not fit, the
+    /// page is full and the following items do not fit either.
+    pub fn try_add<T: Serialize>(&mut self, item: &T) -> bool {
+        if self.full {
+            return false;
+        }
+        let written = serde_json::to_writer(&mut self.counter, item).is_ok();
+        if self.items == 0 || (written && self.counter.0 <= self.max_size) {
+            self.items += 1;
+            true
+        } else {
+            self.full = true;
+            false
+        }
+    }
+}
+
+/// Counts the bytes written to it.
+struct ByteCounter(usize);
+
+impl std::io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
 }
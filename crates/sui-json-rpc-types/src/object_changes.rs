@@ -5,11 +5,15 @@ use move_core_types::language_storage::StructTag;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
-use sui_types::base_types::{ObjectDigest, ObjectID, ObjectRef, SequenceNumber, SuiAddress};
+use sui_types::base_types::{
+    ObjectDigest, ObjectID, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest,
+};
 use sui_types::object::Owner;
 use sui_types::sui_serde::SequenceNumber as AsSequenceNumber;
 use sui_types::sui_serde::SuiStructTag;
 
+use crate::Filter;
+
 /// ObjectChange are derived from the object mutations in the TransactionEffect to provide richer object information.
 #[serde_as]
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
@@ -166,3 +170,19 @@ impl ObjectChange {
         }
     }
 }
+
+/// A change of an object owned by `address` before or after a transaction, pushed to the
+/// subscribers of the address. Objects transferred between two addresses are notified to both.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnedObjectChange {
+    pub address: SuiAddress,
+    pub transaction_digest: TransactionDigest,
+    pub change: ObjectChange,
+}
+
+impl Filter<OwnedObjectChange> for SuiAddress {
+    fn matches(&self, item: &OwnedObjectChange) -> bool {
+        *self == item.address
+    }
+}
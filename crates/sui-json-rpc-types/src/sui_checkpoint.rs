@@ -3,18 +3,19 @@
 
 use crate::Page;
 use fastcrypto::encoding::Base64;
+use roaring::RoaringBitmap;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use sui_types::base_types::TransactionDigest;
-use sui_types::committee::EpochId;
-use sui_types::crypto::AggregateAuthoritySignature;
+use sui_types::committee::{Committee, EpochId};
+use sui_types::crypto::{AggregateAuthoritySignature, AuthorityStrongQuorumSignInfo};
 use sui_types::digests::CheckpointDigest;
 use sui_types::gas::GasCostSummary;
 use sui_types::message_envelope::Message;
 use sui_types::messages_checkpoint::{
-    CheckpointCommitment, CheckpointContents, CheckpointSequenceNumber, CheckpointSummary,
-    CheckpointTimestamp, EndOfEpochData,
+    CertifiedCheckpointSummary, CheckpointCommitment, CheckpointContents, CheckpointSequenceNumber,
+    CheckpointSummary, CheckpointTimestamp, EndOfEpochData,
 };
 use sui_types::sui_serde::BigInt;
 pub type CheckpointPage = Page<Checkpoint, BigInt<u64>>;
@@ -132,3 +133,98 @@ impl From<CheckpointDigest> for CheckpointId {
         Self::Digest(digest)
     }
 }
+
+/// The signatures of a quorum of the validators of an epoch over a message, for clients to verify
+/// them against the committee of the epoch themselves.
+#[serde_as]
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename = "AuthorityQuorumSignInfo", rename_all = "camelCase")]
+pub struct SuiAuthorityQuorumSignInfo {
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub epoch: EpochId,
+    /// The aggregate BLS signature of the signers.
+    #[schemars(with = "Base64")]
+    pub signature: AggregateAuthoritySignature,
+    /// The indexes of the signers in the committee of the epoch, as a serialized roaring bitmap.
+    #[schemars(with = "Base64")]
+    #[serde_as(as = "Base64")]
+    pub signers_map: Vec<u8>,
+}
+
+impl From<&AuthorityStrongQuorumSignInfo> for SuiAuthorityQuorumSignInfo {
+    fn from(info: &AuthorityStrongQuorumSignInfo) -> Self {
+        let mut signers_map = vec![];
+        info.signers_map
+            .serialize_into(&mut signers_map)
+            .expect("serializing a bitmap into a vector cannot fail");
+        Self {
+            epoch: info.epoch,
+            signature: info.signature.clone(),
+            signers_map,
+        }
+    }
+}
+
+impl TryFrom<&SuiAuthorityQuorumSignInfo> for AuthorityStrongQuorumSignInfo {
+    type Error = anyhow::Error;
+
+    fn try_from(info: &SuiAuthorityQuorumSignInfo) -> Result<Self, Self::Error> {
+        Ok(Self {
+            epoch: info.epoch,
+            signature: info.signature.clone(),
+            signers_map: RoaringBitmap::deserialize_from(&info.signers_map[..])?,
+        })
+    }
+}
+
+/// A checkpoint summary with the signatures of the validators certifying it.
+#[serde_as]
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename = "CheckpointCertificate", rename_all = "camelCase")]
+pub struct SuiCheckpointCertificate {
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub sequence_number: CheckpointSequenceNumber,
+    pub digest: CheckpointDigest,
+    /// BCS bytes of the checkpoint summary signed by the validators.
+    #[schemars(with = "Base64")]
+    #[serde_as(as = "Base64")]
+    pub summary_bcs: Vec<u8>,
+    pub auth_sign_info: SuiAuthorityQuorumSignInfo,
+}
+
+impl SuiCheckpointCertificate {
+    pub fn new(checkpoint: &CertifiedCheckpointSummary) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            sequence_number: checkpoint.sequence_number,
+            digest: *checkpoint.digest(),
+            summary_bcs: bcs::to_bytes(checkpoint.data())?,
+            auth_sign_info: checkpoint.auth_sig().into(),
+        })
+    }
+
+    /// Verifies the signatures against the committee of the epoch of the checkpoint, which must
+    /// come from a trusted source, and that they certify the checkpoint of the certificate.
+    pub fn verify(
+        &self,
+        committee: &Committee,
+    ) -> Result<CertifiedCheckpointSummary, anyhow::Error> {
+        let summary: CheckpointSummary = bcs::from_bytes(&self.summary_bcs)?;
+        let checkpoint = CertifiedCheckpointSummary::new_from_data_and_sig(
+            summary,
+            (&self.auth_sign_info).try_into()?,
+        );
+        checkpoint.verify_signature(committee)?;
+        if checkpoint.sequence_number != self.sequence_number || *checkpoint.digest() != self.digest
+        {
+            return Err(anyhow::anyhow!(
+                "The certificate of checkpoint {} is for checkpoint {} with digest {}",
+                self.sequence_number,
+                checkpoint.sequence_number,
+                checkpoint.digest()
+            ));
+        }
+        Ok(checkpoint)
+    }
+}
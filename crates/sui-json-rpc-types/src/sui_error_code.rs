@@ -0,0 +1,98 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sui_types::error::{SuiError, SuiObjectResponseError, UserInputError};
+use sui_types::quorum_driver_types::QuorumDriverError;
+
+/// Stable, machine readable classification of a JSON-RPC error. It is sent in the `data` field
+/// of the JSON-RPC error object, so that clients can act on errors without matching messages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SuiRpcErrorCode {
+    /// The referenced object or package does not exist (yet) on this node.
+    ObjectNotFound,
+    /// The referenced object version has already been consumed.
+    ObjectVersionTooOld,
+    /// The referenced object version is newer than the latest version known to this node.
+    ObjectVersionTooHigh,
+    /// The gas budget or gas coin balance does not cover the transaction.
+    InsufficientGas,
+    /// The transaction was rejected by a node or network policy.
+    DeniedByPolicy,
+    /// The transaction was rejected because validators are overloaded.
+    Congestion,
+}
+
+/// The `data` field of JSON-RPC errors returned by Sui nodes.
+#[derive(Clone, Debug, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiRpcErrorData {
+    pub code: SuiRpcErrorCode,
+}
+
+impl SuiRpcErrorCode {
+    /// Whether the same request may succeed if retried later.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            SuiRpcErrorCode::ObjectNotFound
+                | SuiRpcErrorCode::ObjectVersionTooHigh
+                | SuiRpcErrorCode::Congestion
+        )
+    }
+
+    pub fn from_user_input_error(error: &UserInputError) -> Option<Self> {
+        match error {
+            UserInputError::ObjectNotFound { .. }
+            | UserInputError::DependentPackageNotFound { .. } => Some(Self::ObjectNotFound),
+            UserInputError::ObjectVersionUnavailableForConsumption {
+                provided_obj_ref,
+                current_version,
+            } => Some(if provided_obj_ref.1 < *current_version {
+                Self::ObjectVersionTooOld
+            } else {
+                Self::ObjectVersionTooHigh
+            }),
+            UserInputError::ObjectSequenceNumberTooHigh { .. } => Some(Self::ObjectVersionTooHigh),
+            UserInputError::ObjectDeleted { .. } => Some(Self::ObjectVersionTooOld),
+            UserInputError::GasBudgetTooLow { .. }
+            | UserInputError::GasBalanceTooLow { .. }
+            | UserInputError::InsufficientBalanceToCoverMinimalGas => Some(Self::InsufficientGas),
+            UserInputError::BlockedMoveFunction => Some(Self::DeniedByPolicy),
+            _ => None,
+        }
+    }
+
+    pub fn from_object_response_error(error: &SuiObjectResponseError) -> Option<Self> {
+        match error {
+            SuiObjectResponseError::NotExists { .. } => Some(Self::ObjectNotFound),
+            SuiObjectResponseError::Deleted { .. } => Some(Self::ObjectVersionTooOld),
+            _ => None,
+        }
+    }
+
+    pub fn from_sui_error(error: &SuiError) -> Option<Self> {
+        match error {
+            SuiError::UserInputError { error } => Self::from_user_input_error(error),
+            SuiError::SuiObjectResponseError { error } => Self::from_object_response_error(error),
+            e if e.is_overload() => Some(Self::Congestion),
+            _ => None,
+        }
+    }
+
+    pub fn from_quorum_driver_error(error: &QuorumDriverError) -> Option<Self> {
+        match error {
+            QuorumDriverError::SystemOverload { .. } => Some(Self::Congestion),
+            QuorumDriverError::TransactionDeniedByPolicy { .. } => Some(Self::DeniedByPolicy),
+            // Errors are grouped by stake, report the code of the most common one.
+            QuorumDriverError::NonRecoverableTransactionError { errors } => errors
+                .iter()
+                .max_by_key(|(_, stake, _)| *stake)
+                .and_then(|(error, _, _)| Self::from_sui_error(error)),
+            QuorumDriverError::QuorumDriverInternalError(error) => Self::from_sui_error(error),
+            _ => None,
+        }
+    }
+}
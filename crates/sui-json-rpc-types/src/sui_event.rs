@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::cmp::Ordering;
+
 use fastcrypto::encoding::Base58;
 use move_bytecode_utils::module_cache::GetModule;
 use move_core_types::identifier::Identifier;
@@ -136,10 +138,20 @@ pub enum EventFilter {
         #[serde_as(as = "SuiStructTag")]
         StructTag,
     ),
+    /// Return events with a json field, selected by a JSON pointer e.g. `/amount`, equal to
+    /// the given value.
     MoveEventField {
         path: String,
         value: Value,
     },
+    /// Return events with a json field, selected by a JSON pointer e.g. `/amount`, comparing to
+    /// the given value with the given operator. Integers are compared by value, whether they are
+    /// encoded as json numbers or as decimal strings.
+    MoveEventFieldComparison {
+        path: String,
+        operator: EventFieldOperator,
+        value: Value,
+    },
     /// Return events emitted in [start_time, end_time] interval
     #[serde(rename_all = "camelCase")]
     TimeRange {
@@ -157,15 +169,123 @@ pub enum EventFilter {
     Any(Vec<EventFilter>),
     And(Box<EventFilter>, Box<EventFilter>),
     Or(Box<EventFilter>, Box<EventFilter>),
+    Not(Box<EventFilter>),
 }
 
+/// Comparison operator of [EventFilter::MoveEventFieldComparison].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum EventFieldOperator {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl EventFieldOperator {
+    /// Compare `field` to `value`, returns false if the values cannot be ordered.
+    pub fn compare(&self, field: &Value, value: &Value) -> bool {
+        let ordering = match (as_integer(field), as_integer(value)) {
+            (Some(field), Some(value)) => Some(
+                field
+                    .len()
+                    .cmp(&value.len())
+                    .then_with(|| field.cmp(&value)),
+            ),
+            _ => match (field, value) {
+                (Value::String(field), Value::String(value)) => Some(field.cmp(value)),
+                (field, value) if field == value => Some(Ordering::Equal),
+                _ => None,
+            },
+        };
+        match (self, ordering) {
+            (EventFieldOperator::Ne, None) => true,
+            (_, None) => false,
+            (EventFieldOperator::Eq, Some(o)) => o.is_eq(),
+            (EventFieldOperator::Ne, Some(o)) => o.is_ne(),
+            (EventFieldOperator::Gt, Some(o)) => o.is_gt(),
+            (EventFieldOperator::Ge, Some(o)) => o.is_ge(),
+            (EventFieldOperator::Lt, Some(o)) => o.is_lt(),
+            (EventFieldOperator::Le, Some(o)) => o.is_le(),
+        }
+    }
+}
+
+/// Digits of a non negative integer, without leading zeros. Move integers larger than u32 are
+/// encoded as decimal strings in the json representation of events.
+fn as_integer(value: &Value) -> Option<String> {
+    match value {
+        Value::Number(n) => n.as_u64().map(|n| n.to_string()),
+        Value::String(s) if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) => {
+            let trimmed = s.trim_start_matches('0');
+            Some(if trimmed.is_empty() { "0" } else { trimmed }.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Maximum number of filters, including nested ones, of an event subscription filter.
+pub const MAX_EVENT_FILTER_SIZE: usize = 100;
+
 impl EventFilter {
+    /// Check that the filter can be evaluated by the server, i.e. that it is not too large and
+    /// that field paths are valid JSON pointers.
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        let size = self.size();
+        if size > MAX_EVENT_FILTER_SIZE {
+            anyhow::bail!(
+                "Event filter has {size} filters, exceeding the maximum of {MAX_EVENT_FILTER_SIZE}"
+            );
+        }
+        self.validate_paths()
+    }
+
+    fn size(&self) -> usize {
+        1 + match self {
+            EventFilter::All(filters) | EventFilter::Any(filters) => {
+                filters.iter().map(|f| f.size()).sum()
+            }
+            EventFilter::And(f1, f2) | EventFilter::Or(f1, f2) => f1.size() + f2.size(),
+            EventFilter::Not(f) => f.size(),
+            _ => 0,
+        }
+    }
+
+    fn validate_paths(&self) -> Result<(), anyhow::Error> {
+        match self {
+            EventFilter::MoveEventField { path, .. }
+            | EventFilter::MoveEventFieldComparison { path, .. } => {
+                if !path.is_empty() && !path.starts_with('/') {
+                    anyhow::bail!("Invalid event field path [{path}], expected a JSON pointer");
+                }
+            }
+            EventFilter::All(filters) | EventFilter::Any(filters) => {
+                for filter in filters {
+                    filter.validate_paths()?;
+                }
+            }
+            EventFilter::And(f1, f2) | EventFilter::Or(f1, f2) => {
+                f1.validate_paths()?;
+                f2.validate_paths()?;
+            }
+            EventFilter::Not(f) => f.validate_paths()?,
+            _ => {}
+        }
+        Ok(())
+    }
+
     fn try_matches(&self, item: &SuiEvent) -> SuiResult<bool> {
         Ok(match self {
             EventFilter::MoveEventType(event_type) => &item.type_ == event_type,
             EventFilter::MoveEventField { path, value } => {
                 matches!(item.parsed_json.pointer(path), Some(v) if v == value)
             }
+            EventFilter::MoveEventFieldComparison {
+                path,
+                operator,
+                value,
+            } => matches!(item.parsed_json.pointer(path), Some(v) if operator.compare(v, value)),
             EventFilter::Sender(sender) => &item.sender == sender,
             EventFilter::Package(object_id) => &item.package_id == object_id,
             EventFilter::MoveModule { package, module } => {
@@ -173,12 +293,9 @@ impl EventFilter {
             }
             EventFilter::All(filters) => filters.iter().all(|f| f.matches(item)),
             EventFilter::Any(filters) => filters.iter().any(|f| f.matches(item)),
-            EventFilter::And(f1, f2) => {
-                EventFilter::All(vec![*(*f1).clone(), *(*f2).clone()]).matches(item)
-            }
-            EventFilter::Or(f1, f2) => {
-                EventFilter::Any(vec![*(*f1).clone(), *(*f2).clone()]).matches(item)
-            }
+            EventFilter::And(f1, f2) => f1.matches(item) && f2.matches(item),
+            EventFilter::Or(f1, f2) => f1.matches(item) || f2.matches(item),
+            EventFilter::Not(f) => !f.matches(item),
             EventFilter::Transaction(digest) => digest == &item.id.tx_digest,
 
             EventFilter::TimeRange {
@@ -200,6 +317,9 @@ impl EventFilter {
     pub fn or(self, other_filter: EventFilter) -> Self {
         Self::Any(vec![self, other_filter])
     }
+    pub fn not(self) -> Self {
+        Self::Not(Box::new(self))
+    }
 }
 
 impl Filter<SuiEvent> for EventFilter {
@@ -8,14 +8,20 @@ use serde::Serialize;
 use serde_with::serde_as;
 use serde_with::DisplayFromStr;
 
-use sui_types::base_types::{EpochId, ObjectID};
+use sui_types::base_types::{EpochId, ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::Owner;
 use sui_types::sui_serde::BigInt;
+use sui_types::sui_serde::SequenceNumber as AsSequenceNumber;
 use sui_types::sui_system_state::sui_system_state_summary::SuiValidatorSummary;
 
 use crate::Page;
 
 pub type EpochPage = Page<EpochInfo, BigInt<u64>>;
+/// Owner transitions of an object, the cursor is the object version.
+pub type ObjectOwnershipHistoryPage = Page<ObjectOwnershipChange, BigInt<u64>>;
+/// Gas usage of an address in a range of epochs, the cursor is the epoch.
+pub type AddressGasUsagePage = Page<AddressGasUsage, BigInt<u64>>;
 
 #[serde_as]
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
@@ -144,3 +150,233 @@ pub struct MoveFunctionName {
     #[serde_as(as = "DisplayFromStr")]
     pub function: Identifier,
 }
+
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectOwnershipChange {
+    pub object_id: ObjectID,
+    /// the object version written by the transaction
+    #[schemars(with = "AsSequenceNumber")]
+    #[serde_as(as = "AsSequenceNumber")]
+    pub version: SequenceNumber,
+    pub transaction_digest: TransactionDigest,
+    /// the checkpoint of the transaction, none if it is not indexed from a checkpoint yet
+    #[schemars(with = "Option<BigInt<u64>>")]
+    #[serde_as(as = "Option<BigInt<u64>>")]
+    pub checkpoint: Option<CheckpointSequenceNumber>,
+    /// the owner before the transaction, none if the object was created by the transaction
+    pub previous_owner: Option<Owner>,
+    pub new_owner: Owner,
+}
+
+/// A named watermark of an indexer client, the last checkpoint processed by the consumer.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckpointBookmark {
+    pub name: String,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub checkpoint: CheckpointSequenceNumber,
+    /// the time of the last update of the bookmark, in milliseconds from the Unix epoch
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub updated_at_ms: u64,
+}
+
+/// The checkpoints at which objects can be queried consistently with `suix_queryObjects`.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectsSnapshotRange {
+    /// the checkpoint queries without a cursor read the objects at, the latest indexed checkpoint less the lag configured on the indexer
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub consistent_checkpoint: CheckpointSequenceNumber,
+    /// the earliest checkpoint the cursor of a query can read the objects at
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub earliest_checkpoint: CheckpointSequenceNumber,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub latest_checkpoint: CheckpointSequenceNumber,
+}
+
+/// The statistics of the calls of a Move function in an epoch.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveCallStats {
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub epoch: EpochId,
+    pub function: MoveFunctionName,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub call_count: u64,
+    /// the number of calls in transactions which failed to execute
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub failed_call_count: u64,
+    /// the share of the calls in transactions which failed to execute, from 0 to 1
+    pub failure_rate: f64,
+    /// the number of distinct senders of the transactions calling the function
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub unique_callers: u64,
+    /// the gas used by the transactions calling the function, counted once per transaction
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub total_gas: u64,
+}
+
+/// The statistic Move functions are ranked by, from the highest value.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MoveCallStatsOrder {
+    #[default]
+    CallCount,
+    UniqueCallers,
+    TotalGas,
+    FailureRate,
+}
+
+/// The activity of an address, in the transactions it sent or received objects from.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressSummary {
+    pub address: SuiAddress,
+    /// the checkpoint of the first transaction of the address
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub first_seen_checkpoint: CheckpointSequenceNumber,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub first_seen_timestamp_ms: u64,
+    pub first_transaction: TransactionDigest,
+    /// the checkpoint of the last transaction of the address
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub last_active_checkpoint: CheckpointSequenceNumber,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub last_active_timestamp_ms: u64,
+    pub last_transaction: TransactionDigest,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub transaction_count: u64,
+    /// the net gas used by the transactions sent by the address, negative if their storage rebates exceed their costs
+    #[schemars(with = "BigInt<i64>")]
+    #[serde_as(as = "BigInt<i64>")]
+    pub gas_spent: i64,
+    /// the number of distinct packages called by the transactions sent by the address
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub packages_touched: u64,
+}
+
+/// The gas paid by an address in an epoch, as the gas owner of the transactions, e.g. to
+/// reconcile its spending.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressGasUsage {
+    pub address: SuiAddress,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub epoch: EpochId,
+    /// the number of transactions the address paid the gas of
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub transaction_count: u64,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub computation_cost: u64,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub storage_cost: u64,
+    /// the storage rebates received for the objects deleted or mutated by the transactions
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub storage_rebate: u64,
+    /// the part of the storage costs which is never rebated
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub non_refundable_storage_fee: u64,
+    /// the computation and storage costs minus the storage rebates, negative if the rebates exceed the costs
+    #[schemars(with = "BigInt<i64>")]
+    #[serde_as(as = "BigInt<i64>")]
+    pub net_gas_cost: i64,
+}
+
+/// The committee of an epoch, with the changes of the validator set since the previous epoch.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochCommittee {
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub epoch: EpochId,
+    pub validators: Vec<CommitteeMember>,
+    /// validators which joined the committee at the start of the epoch, all of them for the genesis epoch
+    pub joined: Vec<SuiAddress>,
+    /// validators of the previous epoch which left the committee at the start of the epoch
+    pub left: Vec<SuiAddress>,
+}
+
+/// A validator of the committee of an epoch.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitteeMember {
+    pub sui_address: SuiAddress,
+    pub name: String,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub voting_power: u64,
+    /// the commission rate of the validator, in basis points
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub commission_rate: u64,
+    /// the SUI balance of the staking pool of the validator at the start of the epoch
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub stake: u64,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub gas_price: u64,
+}
+
+/// Gas prices to offer for transactions, from the gas prices paid by the transactions using
+/// shared objects in the last indexed checkpoints, which are ordered by gas price when
+/// congested. The percentiles are never below the reference gas price, and are the reference gas
+/// price when no such transactions were indexed.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GasPriceRecommendation {
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub reference_gas_price: u64,
+    /// the median gas price paid
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub p50_gas_price: u64,
+    /// the gas price paid by 90% of the transactions or less
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub p90_gas_price: u64,
+    /// the first checkpoint of the transactions
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub from_checkpoint: CheckpointSequenceNumber,
+    /// the last checkpoint of the transactions
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub to_checkpoint: CheckpointSequenceNumber,
+    /// the number of transactions using shared objects in the checkpoints
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub transactions: u64,
+}
@@ -6,7 +6,15 @@ use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use sui_types::base_types::{AuthorityName, EpochId, ObjectID, SuiAddress};
 use sui_types::committee::{Committee, StakeUnit};
+use sui_types::event::EventID;
 use sui_types::sui_serde::BigInt;
+use sui_types::sui_system_state::ValidatorEpochInfoEventV2;
+
+use crate::Page;
+
+/// Slashing outcomes of validators, the cursor is the ID of the last epoch change event scanned for
+/// the previous page.
+pub type ValidatorEpochOutcomePage = Page<ValidatorEpochOutcome, EventID>;
 
 /// RPC representation of the [Committee] type.
 #[serde_as]
@@ -72,3 +80,62 @@ pub struct Stake {
     #[serde(flatten)]
     pub status: StakeStatus,
 }
+
+/// Tallying rule reports of the current epoch against a validator.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorReport {
+    /// The reported validator.
+    pub reportee: SuiAddress,
+    /// The validators reporting it.
+    pub reporters: Vec<SuiAddress>,
+    /// Voting power of the reporters in the current committee.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub reporters_voting_power: StakeUnit,
+    /// Whether the reporters reach the quorum, in which case the staking rewards of the reported
+    /// validator are slashed at the end of the epoch.
+    pub slashing_pending: bool,
+}
+
+/// Outcome of the tallying rule for a validator at the end of an epoch.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorEpochOutcome {
+    /// The epoch the rewards were earned in.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub epoch: EpochId,
+    pub validator_address: SuiAddress,
+    /// The validators which reported the validator during the epoch.
+    pub reporters: Vec<SuiAddress>,
+    /// Whether the staking rewards of the validator were slashed.
+    pub slashed: bool,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub stake: u64,
+    /// Staking rewards of the pool of the validator, after slashing.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub pool_staking_reward: u64,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub storage_fund_staking_reward: u64,
+}
+
+impl From<ValidatorEpochInfoEventV2> for ValidatorEpochOutcome {
+    fn from(event: ValidatorEpochInfoEventV2) -> Self {
+        Self {
+            // The event is emitted with the epoch starting after the rewards are distributed.
+            epoch: event.epoch.saturating_sub(1),
+            validator_address: event.validator_address,
+            reporters: event.tallying_rule_reporters,
+            slashed: event.tallying_rule_global_score == 0,
+            stake: event.stake,
+            pool_staking_reward: event.pool_staking_reward,
+            storage_fund_staking_reward: event.storage_fund_staking_reward,
+        }
+    }
+}
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::cmp::Ordering;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::fmt::Write;
 use std::fmt::{Display, Formatter};
@@ -26,11 +26,13 @@ use sui_types::base_types::{
     ObjectDigest, ObjectID, ObjectInfo, ObjectRef, ObjectType, SequenceNumber, SuiAddress,
     TransactionDigest,
 };
+use sui_types::committee::{Committee, EpochId};
 use sui_types::error::{SuiObjectResponseError, UserInputError, UserInputResult};
 use sui_types::gas_coin::GasCoin;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::move_package::{MovePackage, TypeOrigin, UpgradeInfo};
 use sui_types::object::{Data, MoveObject, Object, ObjectFormatOptions, ObjectRead, Owner};
+use sui_types::object_proof::ObjectProof;
 use sui_types::sui_serde::BigInt;
 use sui_types::sui_serde::SequenceNumber as AsSequenceNumber;
 use sui_types::sui_serde::SuiStructTag;
@@ -1033,6 +1035,201 @@ impl SuiPastObjectResponse {
     }
 }
 
+/// The lock of an owned object version in the current epoch, as seen by one node. Validators lock
+/// the owned objects used by a transaction when signing it, and refuse to sign other transactions
+/// using the same object versions until the end of the epoch.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(tag = "status", rename = "ObjectLockStatus")]
+pub enum SuiObjectLockStatus {
+    /// The object is not owned by an address. Only address-owned objects are locked by
+    /// transactions, shared objects are sequenced by consensus.
+    NotOwned,
+    /// The object version is not locked, and can be used by a new transaction.
+    Unlocked,
+    /// The object version is locked to a transaction, which must be executed before it can be
+    /// used by another transaction.
+    #[serde(rename_all = "camelCase")]
+    Locked {
+        transaction_digest: TransactionDigest,
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "BigInt<u64>")]
+        epoch: EpochId,
+    },
+    /// The object version was used by an executed transaction, the object is now at a later
+    /// version.
+    #[serde(rename_all = "camelCase")]
+    Consumed {
+        #[schemars(with = "AsSequenceNumber")]
+        #[serde_as(as = "AsSequenceNumber")]
+        latest_version: SequenceNumber,
+    },
+}
+
+/// Proof that an object version is part of the state at a checkpoint, from the certified
+/// checkpoint including the transaction which wrote the object version.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename = "ObjectProof", rename_all = "camelCase")]
+pub struct SuiObjectProof {
+    pub object_ref: SuiObjectRef,
+    /// The epoch of the checkpoint, whose committee certified the checkpoint.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub epoch: EpochId,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub checkpoint: CheckpointSequenceNumber,
+    /// BCS bytes of the proof: the certified checkpoint summary, the checkpoint contents, the
+    /// effects of the transaction and the object.
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub proof_bcs: Vec<u8>,
+}
+
+impl SuiObjectProof {
+    pub fn new(proof: &ObjectProof) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            object_ref: proof.object.compute_object_reference().into(),
+            epoch: proof.checkpoint.epoch,
+            checkpoint: proof.checkpoint.sequence_number,
+            proof_bcs: bcs::to_bytes(proof)?,
+        })
+    }
+
+    pub fn proof(&self) -> Result<ObjectProof, anyhow::Error> {
+        Ok(bcs::from_bytes(&self.proof_bcs)?)
+    }
+
+    /// Verifies the proof against the committee of its epoch, which must come from a trusted
+    /// source, and that it proves the object version of the response.
+    pub fn verify(&self, committee: &Committee) -> Result<ObjectProof, anyhow::Error> {
+        let proof = self.proof()?;
+        let object_ref = proof.verify(committee)?;
+        if object_ref != self.object_ref.to_object_ref()
+            || proof.checkpoint.sequence_number != self.checkpoint
+        {
+            return Err(anyhow!(
+                "The proof of object {:?} is for object {object_ref:?} at checkpoint {}",
+                self.object_ref,
+                proof.checkpoint.sequence_number
+            ));
+        }
+        Ok(proof)
+    }
+}
+
+/// The fields which differ between two versions of a Move object.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename = "ObjectVersionsDiff", rename_all = "camelCase")]
+pub struct SuiObjectVersionsDiff {
+    pub object_id: ObjectID,
+    #[schemars(with = "String")]
+    #[serde_as(as = "SuiStructTag")]
+    pub object_type: StructTag,
+    #[schemars(with = "AsSequenceNumber")]
+    #[serde_as(as = "AsSequenceNumber")]
+    pub from_version: SequenceNumber,
+    #[schemars(with = "AsSequenceNumber")]
+    #[serde_as(as = "AsSequenceNumber")]
+    pub to_version: SequenceNumber,
+    /// The changed fields, ordered by path, and the elements of vectors by index.
+    pub changes: Vec<SuiObjectFieldChange>,
+}
+
+/// A change of a field of a Move object, at the `path` of the field in the JSON content of the
+/// object, e.g. `balance` or `inner.items[2]`.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename = "ObjectFieldChange", rename_all = "camelCase", tag = "type")]
+pub enum SuiObjectFieldChange {
+    /// The field only exists in the later version, e.g. an element appended to a vector.
+    Added { path: String, value: Value },
+    /// The field only exists in the earlier version.
+    Removed { path: String, value: Value },
+    /// The field has a different value in the later version.
+    Changed {
+        path: String,
+        from: Value,
+        to: Value,
+    },
+}
+
+impl SuiObjectVersionsDiff {
+    /// Compares the contents of the object at `from_version` and at `to_version`, down to the
+    /// fields of nested structs and the elements of vectors.
+    pub fn new(
+        object_id: ObjectID,
+        object_type: StructTag,
+        from_version: SequenceNumber,
+        from: SuiMoveStruct,
+        to_version: SequenceNumber,
+        to: SuiMoveStruct,
+    ) -> Self {
+        let mut changes = vec![];
+        diff_values(
+            String::new(),
+            from.to_json_value(),
+            to.to_json_value(),
+            &mut changes,
+        );
+        Self {
+            object_id,
+            object_type,
+            from_version,
+            to_version,
+            changes,
+        }
+    }
+}
+
+fn diff_values(path: String, from: Value, to: Value, changes: &mut Vec<SuiObjectFieldChange>) {
+    match (from, to) {
+        (Value::Object(mut from), Value::Object(mut to)) => {
+            let keys: BTreeSet<_> = from.keys().chain(to.keys()).cloned().collect();
+            for key in keys {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                match (from.remove(&key), to.remove(&key)) {
+                    (Some(from), Some(to)) => diff_values(path, from, to, changes),
+                    (Some(value), None) => {
+                        changes.push(SuiObjectFieldChange::Removed { path, value })
+                    }
+                    (None, Some(value)) => {
+                        changes.push(SuiObjectFieldChange::Added { path, value })
+                    }
+                    (None, None) => {}
+                }
+            }
+        }
+        (Value::Array(from), Value::Array(to)) => {
+            let mut from = from.into_iter();
+            let mut to = to.into_iter();
+            for index in 0.. {
+                let path = format!("{path}[{index}]");
+                match (from.next(), to.next()) {
+                    (Some(from), Some(to)) => diff_values(path, from, to, changes),
+                    (Some(value), None) => {
+                        changes.push(SuiObjectFieldChange::Removed { path, value })
+                    }
+                    (None, Some(value)) => {
+                        changes.push(SuiObjectFieldChange::Added { path, value })
+                    }
+                    (None, None) => break,
+                }
+            }
+        }
+        (from, to) => {
+            if from != to {
+                changes.push(SuiObjectFieldChange::Changed { path, from, to })
+            }
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Eq, PartialEq)]
 #[serde(rename = "MovePackage", rename_all = "camelCase")]
 pub struct SuiMovePackage {
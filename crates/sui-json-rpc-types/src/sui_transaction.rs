@@ -19,14 +19,17 @@ use sui_json::{primitive_type, SuiJsonValue};
 use sui_types::base_types::{
     EpochId, ObjectID, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest,
 };
-use sui_types::digests::{ObjectDigest, TransactionEventsDigest};
+use sui_types::committee::Committee;
+use sui_types::crypto::AuthorityStrongQuorumSignInfo;
+use sui_types::digests::{ObjectDigest, TransactionEffectsDigest, TransactionEventsDigest};
 use sui_types::error::{ExecutionError, SuiError};
 use sui_types::gas::GasCostSummary;
 use sui_types::messages::{
-    Argument, CallArg, Command, ExecuteTransactionRequestType, ExecutionStatus, GenesisObject,
-    InputObjectKind, ObjectArg, ProgrammableMoveCall, ProgrammableTransaction, SenderSignedData,
-    TransactionData, TransactionDataAPI, TransactionEffects, TransactionEffectsAPI,
-    TransactionEvents, TransactionKind, VersionedProtocolMessage,
+    Argument, CallArg, CertifiedTransactionEffects, Command, ExecuteTransactionRequestType,
+    ExecutionStatus, GenesisObject, InputObjectKind, ObjectArg, ProgrammableMoveCall,
+    ProgrammableTransaction, SenderSignedData, TransactionData, TransactionDataAPI,
+    TransactionEffects, TransactionEffectsAPI, TransactionEvents, TransactionKind,
+    VersionedProtocolMessage,
 };
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::move_package::disassemble_modules;
@@ -38,10 +41,11 @@ use sui_types::storage::{DeleteKind, WriteKind};
 use sui_types::sui_serde::{
     BigInt, SequenceNumber as AsSequenceNumber, SuiTypeTag as AsSuiTypeTag,
 };
+use sui_types::transaction_proof::TransactionProof;
 
 use crate::balance_changes::BalanceChange;
 use crate::object_changes::ObjectChange;
-use crate::{Page, SuiEvent, SuiMovePackage, SuiObjectRef};
+use crate::{Page, SuiAuthorityQuorumSignInfo, SuiEvent, SuiMovePackage, SuiObjectRef};
 
 // similar to EpochId of sui-types but BigInt
 pub type SuiEpochId = BigInt<u64>;
@@ -96,6 +100,10 @@ pub struct SuiTransactionBlockResponseOptions {
     pub show_object_changes: bool,
     /// Whether to show balance_changes. Default to be False
     pub show_balance_changes: bool,
+    /// Whether to show the effects with the signatures of the validators certifying them, only
+    /// when executing the transaction, as fullnodes do not keep the signatures. Default to be
+    /// False
+    pub show_effects_certificate: bool,
 }
 
 impl SuiTransactionBlockResponseOptions {
@@ -111,6 +119,8 @@ impl SuiTransactionBlockResponseOptions {
             show_events: true,
             show_object_changes: true,
             show_balance_changes: true,
+            // Only returned on execution, where it must be asked for explicitly.
+            show_effects_certificate: false,
         }
     }
 
@@ -144,6 +154,11 @@ impl SuiTransactionBlockResponseOptions {
         self
     }
 
+    pub fn with_effects_certificate(mut self) -> Self {
+        self.show_effects_certificate = true;
+        self
+    }
+
     /// default to return `WaitForEffectsCert` unless some options require
     /// local execution
     pub fn default_execution_request_type(&self) -> ExecuteTransactionRequestType {
@@ -211,6 +226,10 @@ pub struct SuiTransactionBlockResponse {
     pub checkpoint: Option<CheckpointSequenceNumber>,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub errors: Vec<String>,
+    /// The effects with the signatures of the validators certifying them, returned by the
+    /// transaction execution api only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effects_certificate: Option<SuiEffectsCertificate>,
 }
 
 impl SuiTransactionBlockResponse {
@@ -660,6 +679,97 @@ pub struct DryRunTransactionBlockResponse {
     pub input: SuiTransactionBlockData,
 }
 
+/// Safety margins, in percent of the dry run costs, added to the gas budget estimated by
+/// `sui_estimateGasBudget`.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GasBudgetSafetyMargin {
+    /// Margin on the computation cost.
+    pub computation_percent: u64,
+    /// Additional margin on the computation cost of transactions using shared objects. Their
+    /// execution is deferred until sequenced by consensus, and may run against other versions
+    /// of the shared objects than the dry run.
+    pub shared_object_percent: u64,
+    /// Margin on the storage cost, covering objects growing between the dry run and execution.
+    pub storage_percent: u64,
+}
+
+impl Default for GasBudgetSafetyMargin {
+    fn default() -> Self {
+        Self {
+            computation_percent: 10,
+            shared_object_percent: 20,
+            storage_percent: 10,
+        }
+    }
+}
+
+impl GasBudgetSafetyMargin {
+    /// Applies the margins to the gas used by a dry run. The storage rebate is not deducted
+    /// from the budget, as it is only credited after execution and depends on the objects
+    /// at execution time.
+    pub fn apply(&self, gas_used: GasCostSummary, uses_shared_objects: bool) -> GasBudgetBreakdown {
+        let percent_of = |cost: u64, percent: u64| {
+            let margin = (cost as u128 * percent as u128 + 99) / 100;
+            u64::try_from(margin).unwrap_or(u64::MAX)
+        };
+        GasBudgetBreakdown {
+            computation_margin: percent_of(gas_used.computation_cost, self.computation_percent),
+            shared_object_margin: if uses_shared_objects {
+                percent_of(gas_used.computation_cost, self.shared_object_percent)
+            } else {
+                0
+            },
+            storage_margin: percent_of(gas_used.storage_cost, self.storage_percent),
+            gas_used,
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GasBudgetBreakdown {
+    /// Gas used by the dry run of the transaction.
+    pub gas_used: GasCostSummary,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub computation_margin: u64,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub shared_object_margin: u64,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub storage_margin: u64,
+}
+
+impl GasBudgetBreakdown {
+    /// The budget covering the dry run costs and all margins.
+    pub fn gas_budget(&self) -> u64 {
+        self.gas_used
+            .computation_cost
+            .saturating_add(self.gas_used.storage_cost)
+            .saturating_add(self.computation_margin)
+            .saturating_add(self.shared_object_margin)
+            .saturating_add(self.storage_margin)
+    }
+}
+
+#[serde_as]
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GasBudgetEstimate {
+    /// The estimated gas budget, within the budget bounds of the current protocol version.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub gas_budget: u64,
+    /// The gas price the estimate is computed for.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub gas_price: u64,
+    pub breakdown: GasBudgetBreakdown,
+}
+
 #[derive(Eq, PartialEq, Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename = "TransactionBlockEvents", transparent)]
 pub struct SuiTransactionBlockEvents {
@@ -702,6 +812,10 @@ pub struct DevInspectResults {
     /// Execution error from executing the transactions
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Balance changes that likely would be generated if the transaction is actually run,
+    /// including the gas paid by the mock gas coin of the sender.
+    #[serde(default)]
+    pub balance_changes: Vec<BalanceChange>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -760,6 +874,7 @@ impl DevInspectResults {
             events: SuiTransactionBlockEvents::try_from(events, tx_digest, None, resolver)?,
             results,
             error,
+            balance_changes: vec![],
         })
     }
 }
@@ -1456,6 +1571,93 @@ impl TransactionBlockBytes {
     }
 }
 
+/// Proof that a transaction was executed with its effects and events, from the certified
+/// checkpoint including it.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename = "TransactionProof", rename_all = "camelCase")]
+pub struct SuiTransactionProof {
+    pub digest: TransactionDigest,
+    /// The epoch of the checkpoint, whose committee certified the checkpoint.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub epoch: EpochId,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub checkpoint: CheckpointSequenceNumber,
+    /// BCS bytes of the proof: the certified checkpoint summary, the checkpoint contents, the
+    /// signed transaction, its effects and its events.
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub proof_bcs: Vec<u8>,
+}
+
+impl SuiTransactionProof {
+    pub fn new(proof: &TransactionProof) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            digest: *proof.transaction.digest(),
+            epoch: proof.checkpoint.epoch,
+            checkpoint: proof.checkpoint.sequence_number,
+            proof_bcs: bcs::to_bytes(proof)?,
+        })
+    }
+
+    pub fn proof(&self) -> Result<TransactionProof, anyhow::Error> {
+        Ok(bcs::from_bytes(&self.proof_bcs)?)
+    }
+
+    /// Verifies the proof against the committee of its epoch, which must come from a trusted
+    /// source, and that it proves the transaction of the response.
+    pub fn verify(&self, committee: &Committee) -> Result<TransactionProof, anyhow::Error> {
+        let proof = self.proof()?;
+        let digest = proof.verify(committee)?;
+        if digest != self.digest || proof.checkpoint.sequence_number != self.checkpoint {
+            return Err(anyhow::anyhow!(
+                "The proof of transaction {} is for transaction {digest} at checkpoint {}",
+                self.digest,
+                proof.checkpoint.sequence_number
+            ));
+        }
+        Ok(proof)
+    }
+}
+
+/// Transaction effects with the signatures of the validators certifying them.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename = "EffectsCertificate", rename_all = "camelCase")]
+pub struct SuiEffectsCertificate {
+    /// BCS bytes of the transaction effects signed by the validators.
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub effects_bcs: Vec<u8>,
+    pub auth_sign_info: SuiAuthorityQuorumSignInfo,
+}
+
+impl SuiEffectsCertificate {
+    pub fn new(
+        effects: &TransactionEffects,
+        auth_sign_info: &AuthorityStrongQuorumSignInfo,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            effects_bcs: bcs::to_bytes(effects)?,
+            auth_sign_info: auth_sign_info.into(),
+        })
+    }
+
+    /// Verifies the signatures against the committee of the epoch of the effects, which must come
+    /// from a trusted source, and returns the effects certified.
+    pub fn verify(&self, committee: &Committee) -> Result<TransactionEffects, anyhow::Error> {
+        let effects: TransactionEffects = bcs::from_bytes(&self.effects_bcs)?;
+        let certificate = CertifiedTransactionEffects::new_from_data_and_sig(
+            effects,
+            (&self.auth_sign_info).try_into()?,
+        );
+        certificate.verify_signature(committee)?;
+        Ok(certificate.into_data())
+    }
+}
+
 #[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename = "OwnedObjectRef")]
 pub struct OwnedObjectRef {
@@ -1562,3 +1764,35 @@ pub enum SuiObjectArg {
         mutable: bool,
     },
 }
+
+/// The progress of a transaction block towards finality, as seen by one node. A transaction block
+/// only moves forward through the statuses, in the order of the variants.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(tag = "status", rename = "TransactionBlockStatus")]
+pub enum SuiTransactionBlockStatus {
+    /// The node has the transaction block, but has not executed it yet.
+    Seen,
+    /// The node has executed the transaction block, which is not in a checkpoint executed by the
+    /// node yet.
+    #[serde(rename_all = "camelCase")]
+    ExecutedLocally {
+        effects_digest: TransactionEffectsDigest,
+    },
+    /// The transaction block is part of a certified checkpoint, which the node has not finished
+    /// executing yet, e.g. while it executes the checkpoints before it.
+    #[serde(rename_all = "camelCase")]
+    InCertifiedCheckpoint {
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "BigInt<u64>")]
+        checkpoint: CheckpointSequenceNumber,
+    },
+    /// The node has executed the whole certified checkpoint including the transaction block, and
+    /// all the checkpoints before it.
+    #[serde(rename_all = "camelCase")]
+    Finalized {
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "BigInt<u64>")]
+        checkpoint: CheckpointSequenceNumber,
+    },
+}
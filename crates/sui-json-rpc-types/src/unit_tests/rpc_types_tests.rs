@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::BTreeMap;
 use std::str::FromStr;
 
 use anyhow::anyhow;
@@ -8,14 +9,23 @@ use move_core_types::ident_str;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{StructTag, TypeTag};
 use move_core_types::value::{MoveStruct, MoveValue};
+use serde_json::json;
 
 use sui_types::base_types::{ObjectDigest, SequenceNumber};
-use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::error::{SuiError, UserInputError};
+use sui_types::event::EventID;
+use sui_types::gas::GasCostSummary;
 use sui_types::gas_coin::GasCoin;
 use sui_types::object::{MoveObject, Owner};
 use sui_types::{parse_sui_struct_tag, MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
 
-use crate::{ObjectChange, SuiMoveStruct, SuiMoveValue};
+use crate::{
+    EventFieldOperator, EventFilter, Filter, GasBudgetSafetyMargin, ObjectChange, Page,
+    PageSizeBudget, SuiEvent, SuiMoveStruct, SuiMoveValue, SuiObjectFieldChange,
+    SuiObjectLockStatus, SuiObjectVersionsDiff, SuiRpcErrorCode, SuiRpcErrorData,
+    MAX_EVENT_FILTER_SIZE,
+};
 
 #[test]
 fn test_move_value_to_sui_coin() {
@@ -169,3 +179,287 @@ fn test_type_tag_struct_tag_devnet_inc_222() {
         assert_eq!(oc, deser);
     }
 }
+
+#[test]
+fn test_rpc_error_code_from_sui_error() {
+    let object_id = ObjectID::random();
+    let not_found = SuiError::from(UserInputError::ObjectNotFound {
+        object_id,
+        version: None,
+    });
+    assert_eq!(
+        SuiRpcErrorCode::from_sui_error(&not_found),
+        Some(SuiRpcErrorCode::ObjectNotFound)
+    );
+
+    let stale = UserInputError::ObjectVersionUnavailableForConsumption {
+        provided_obj_ref: (object_id, SequenceNumber::from(1), ObjectDigest::random()),
+        current_version: SequenceNumber::from(2),
+    };
+    assert_eq!(
+        SuiRpcErrorCode::from_user_input_error(&stale),
+        Some(SuiRpcErrorCode::ObjectVersionTooOld)
+    );
+
+    let overloaded = SuiError::TooManyTransactionsPendingConsensus;
+    assert_eq!(
+        SuiRpcErrorCode::from_sui_error(&overloaded),
+        Some(SuiRpcErrorCode::Congestion)
+    );
+    assert!(SuiRpcErrorCode::Congestion.is_retryable());
+
+    assert_eq!(
+        SuiRpcErrorCode::from_sui_error(&SuiError::UnexpectedOwnerType),
+        None
+    );
+}
+
+#[test]
+fn test_rpc_error_data_serialization() {
+    let data = SuiRpcErrorData {
+        code: SuiRpcErrorCode::InsufficientGas,
+    };
+    let json = serde_json::to_string(&data).unwrap();
+    assert_eq!(json, r#"{"code":"insufficientGas"}"#);
+    assert_eq!(data, serde_json::from_str(&json).unwrap());
+}
+
+#[test]
+fn test_gas_budget_safety_margin() {
+    let gas_used = GasCostSummary::new(1000, 2001, 500, 5);
+    let margin = GasBudgetSafetyMargin::default();
+
+    let breakdown = margin.apply(gas_used.clone(), false);
+    assert_eq!(breakdown.computation_margin, 100);
+    assert_eq!(breakdown.shared_object_margin, 0);
+    // Margins are rounded up.
+    assert_eq!(breakdown.storage_margin, 201);
+    // The storage rebate is not deducted from the budget.
+    assert_eq!(breakdown.gas_budget(), 1000 + 2001 + 100 + 201);
+
+    let breakdown = margin.apply(gas_used, true);
+    assert_eq!(breakdown.shared_object_margin, 200);
+    assert_eq!(breakdown.gas_budget(), 1000 + 2001 + 100 + 200 + 201);
+
+    let breakdown = margin.apply(GasCostSummary::new(u64::MAX, u64::MAX, 0, 0), true);
+    assert_eq!(breakdown.gas_budget(), u64::MAX);
+}
+
+#[test]
+fn test_event_filter_composition() {
+    let sender = SuiAddress::random_for_testing_only();
+    let event = SuiEvent {
+        id: EventID {
+            tx_digest: TransactionDigest::random(),
+            event_seq: 0,
+        },
+        package_id: ObjectID::from(SUI_FRAMEWORK_ADDRESS),
+        transaction_module: Identifier::from(ident_str!("coin")),
+        sender,
+        type_: GasCoin::type_(),
+        parsed_json: json!({"amount": "1500", "owner": "alice", "count": 7}),
+        bcs: vec![],
+        timestamp_ms: None,
+    };
+    let field = |path: &str, operator, value| EventFilter::MoveEventFieldComparison {
+        path: path.to_string(),
+        operator,
+        value,
+    };
+
+    // Integers encoded as strings are compared by value.
+    assert!(field("/amount", EventFieldOperator::Gt, json!(1000)).matches(&event));
+    assert!(field("/amount", EventFieldOperator::Lt, json!("10000")).matches(&event));
+    assert!(field("/amount", EventFieldOperator::Eq, json!("01500")).matches(&event));
+    assert!(!field("/amount", EventFieldOperator::Ge, json!(1501)).matches(&event));
+    assert!(field("/count", EventFieldOperator::Le, json!(7)).matches(&event));
+    assert!(field("/owner", EventFieldOperator::Ne, json!("bob")).matches(&event));
+    // Values that cannot be ordered or missing fields do not match.
+    assert!(!field("/owner", EventFieldOperator::Gt, json!(1)).matches(&event));
+    assert!(!field("/missing", EventFieldOperator::Ne, json!(1)).matches(&event));
+
+    let large_amount = field("/amount", EventFieldOperator::Gt, json!(1000));
+    let from_sender = EventFilter::Sender(sender);
+    let other_package = EventFilter::Package(ObjectID::from(MOVE_STDLIB_ADDRESS));
+    assert!(large_amount
+        .clone()
+        .and(from_sender.clone())
+        .and(other_package.clone().not())
+        .matches(&event));
+    assert!(EventFilter::Or(
+        Box::new(other_package.clone()),
+        Box::new(EventFilter::And(
+            Box::new(large_amount.clone()),
+            Box::new(from_sender.clone())
+        ))
+    )
+    .matches(&event));
+    assert!(!EventFilter::Any(vec![other_package.clone(), from_sender.not()]).matches(&event));
+
+    assert!(large_amount.validate().is_ok());
+    assert!(field("amount", EventFieldOperator::Eq, json!(1))
+        .not()
+        .validate()
+        .is_err());
+    let too_large = EventFilter::Any(vec![other_package; MAX_EVENT_FILTER_SIZE]);
+    assert!(too_large.validate().is_err());
+}
+
+#[test]
+fn test_object_lock_status_serde() -> Result<(), anyhow::Error> {
+    let digest = TransactionDigest::random();
+    let locked = SuiObjectLockStatus::Locked {
+        transaction_digest: digest,
+        epoch: 3,
+    };
+    assert_eq!(
+        serde_json::to_value(&locked)?,
+        json!({ "status": "Locked", "transactionDigest": digest.to_string(), "epoch": "3" })
+    );
+    assert_eq!(
+        serde_json::from_value::<SuiObjectLockStatus>(serde_json::to_value(&locked)?)?,
+        locked
+    );
+
+    let consumed = SuiObjectLockStatus::Consumed {
+        latest_version: SequenceNumber::from_u64(7),
+    };
+    assert_eq!(
+        serde_json::to_value(&consumed)?,
+        json!({ "status": "Consumed", "latestVersion": "7" })
+    );
+    assert_eq!(
+        serde_json::to_value(SuiObjectLockStatus::Unlocked)?,
+        json!({ "status": "Unlocked" })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_page_truncate_to_size() {
+    // Each item is 12 bytes of JSON, with its quotes.
+    let page = || Page {
+        data: (0..10).map(|i| format!("item-{i:05}")).collect::<Vec<_>>(),
+        next_cursor: Some(9),
+        has_next_page: false,
+        truncated_due_to_limit: false,
+    };
+
+    // Pages within the limit are left as they are.
+    let mut within = page();
+    within.truncate_to_size(120, |i, _| i);
+    assert_eq!(within.data.len(), 10);
+    assert_eq!(within.next_cursor, Some(9));
+    assert!(!within.has_next_page && !within.truncated_due_to_limit);
+    let json = serde_json::to_value(&within).unwrap();
+    assert!(json.get("truncatedDueToLimit").is_none());
+
+    let mut truncated = page();
+    truncated.truncate_to_size(50, |i, _| i);
+    assert_eq!(truncated.data.len(), 4);
+    assert_eq!(truncated.next_cursor, Some(3));
+    assert!(truncated.has_next_page && truncated.truncated_due_to_limit);
+    let json = serde_json::to_value(&truncated).unwrap();
+    assert_eq!(json["truncatedDueToLimit"], json!(true));
+    let read: Page<String, usize> = serde_json::from_value(json).unwrap();
+    assert!(read.truncated_due_to_limit);
+
+    // The first item is kept even if larger than the limit.
+    let mut first = page();
+    first.truncate_to_size(1, |i, _| i);
+    assert_eq!(first.data, vec!["item-00000".to_string()]);
+    assert_eq!(first.next_cursor, Some(0));
+    assert!(first.has_next_page && first.truncated_due_to_limit);
+
+    // Pages serialized without the flag are not truncated.
+    let read: Page<String, usize> =
+        serde_json::from_value(json!({"data": [], "nextCursor": null, "hasNextPage": false}))
+            .unwrap();
+    assert!(!read.truncated_due_to_limit);
+}
+
+#[test]
+fn test_page_size_budget() {
+    // Each item is 12 bytes of JSON, with its quotes.
+    let mut budget = PageSizeBudget::new(30);
+    assert!(budget.try_add(&"item-00000"));
+    assert!(budget.try_add(&"item-00001"));
+    assert!(!budget.try_add(&"item-00002"));
+    // The page is full, even for items small enough to fit in the rest of the budget.
+    assert!(!budget.try_add(&""));
+
+    // The first item is added even if larger than the budget.
+    let mut budget = PageSizeBudget::new(1);
+    assert!(budget.try_add(&"item-00000"));
+    assert!(!budget.try_add(&"item-00001"));
+}
+
+#[test]
+fn test_object_versions_diff() {
+    let object = |balance, name: &str, items: &[u64]| {
+        SuiMoveStruct::WithFields(BTreeMap::from([
+            ("balance".to_string(), SuiMoveValue::Number(balance)),
+            (
+                "inner".to_string(),
+                SuiMoveValue::Struct(SuiMoveStruct::WithFields(BTreeMap::from([
+                    ("flag".to_string(), SuiMoveValue::Bool(true)),
+                    ("name".to_string(), SuiMoveValue::String(name.to_string())),
+                ]))),
+            ),
+            (
+                "items".to_string(),
+                SuiMoveValue::Vector(items.iter().copied().map(SuiMoveValue::Number).collect()),
+            ),
+        ]))
+    };
+    let diff = |from, to| {
+        SuiObjectVersionsDiff::new(
+            ObjectID::random(),
+            GasCoin::type_(),
+            SequenceNumber::from_u64(1),
+            from,
+            SequenceNumber::from_u64(2),
+            to,
+        )
+        .changes
+    };
+
+    assert_eq!(
+        diff(object(10, "a", &[1, 2]), object(15, "b", &[1, 3, 4])),
+        vec![
+            SuiObjectFieldChange::Changed {
+                path: "balance".to_string(),
+                from: json!(10),
+                to: json!(15),
+            },
+            SuiObjectFieldChange::Changed {
+                path: "inner.name".to_string(),
+                from: json!("a"),
+                to: json!("b"),
+            },
+            SuiObjectFieldChange::Changed {
+                path: "items[1]".to_string(),
+                from: json!(2),
+                to: json!(3),
+            },
+            SuiObjectFieldChange::Added {
+                path: "items[2]".to_string(),
+                value: json!(4),
+            },
+        ]
+    );
+    assert_eq!(
+        diff(object(10, "a", &[1, 2]), object(10, "a", &[])),
+        vec![
+            SuiObjectFieldChange::Removed {
+                path: "items[0]".to_string(),
+                value: json!(1),
+            },
+            SuiObjectFieldChange::Removed {
+                path: "items[1]".to_string(),
+                value: json!(2),
+            },
+        ]
+    );
+    assert!(diff(object(10, "a", &[1]), object(10, "a", &[1])).is_empty());
+}
@@ -5,10 +5,13 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee_proc_macros::rpc;
 
 use sui_json_rpc_types::{
-    CheckpointedObjectID, EpochInfo, EpochPage, MoveCallMetrics, NetworkMetrics, QueryObjectsPage,
-    SuiObjectResponseQuery,
+    AddressGasUsagePage, AddressSummary, CheckpointBookmark, CheckpointedObjectID, EpochCommittee,
+    EpochInfo, EpochPage, GasPriceRecommendation, MoveCallMetrics, MoveCallStats,
+    MoveCallStatsOrder, NetworkMetrics, ObjectOwnershipHistoryPage, ObjectsSnapshotRange,
+    QueryObjectsPage, SuiObjectResponseQuery,
 };
 use sui_open_rpc_macros::open_rpc;
+use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::sui_serde::BigInt;
 
 #[open_rpc(namespace = "suix", tag = "Extended API")]
@@ -30,7 +33,17 @@ pub trait ExtendedApi {
     #[method(name = "getCurrentEpoch")]
     async fn get_current_epoch(&self) -> RpcResult<EpochInfo>;
 
-    /// Return the list of queried objects. Note that this is an enhanced full node only api.
+    /// Return the committees of a range of epochs, with the voting power, commission rate and stake of their validators, and the validators which joined or left the committee at each epoch. Note that this is an enhanced full node only api.
+    #[method(name = "getCommitteeHistory")]
+    async fn get_committee_history(
+        &self,
+        /// the first epoch of the range
+        from_epoch: BigInt<u64>,
+        /// the last epoch of the range, included
+        to_epoch: BigInt<u64>,
+    ) -> RpcResult<Vec<EpochCommittee>>;
+
+    /// Return the list of queried objects, as of the checkpoint of the cursor, or as of the consistent checkpoint returned by suix_getObjectsSnapshotRange if no cursor is given. Note that this is an enhanced full node only api.
     #[method(name = "queryObjects")]
     async fn query_objects(
         &self,
@@ -42,6 +55,70 @@ pub trait ExtendedApi {
         limit: Option<BigInt<u64>>,
     ) -> RpcResult<QueryObjectsPage>;
 
+    /// Return the checkpoints at which suix_queryObjects reads the objects consistently: queries without a cursor read them as of the consistent checkpoint, and cursors are valid back to the earliest checkpoint. Note that this is an enhanced full node only api.
+    #[method(name = "getObjectsSnapshotRange")]
+    async fn get_objects_snapshot_range(&self) -> RpcResult<ObjectsSnapshotRange>;
+
+    /// Return the owner transitions of an object, starting from its creation. Note that this is an enhanced full node only api.
+    #[method(name = "getObjectOwnershipHistory")]
+    async fn get_object_ownership_history(
+        &self,
+        /// the ID of the queried object
+        object_id: ObjectID,
+        /// optional paging cursor, the object version of the last transition of the previous page
+        cursor: Option<BigInt<u64>>,
+        /// maximum number of items per page
+        limit: Option<BigInt<u64>>,
+        /// flag to return results in descending order
+        descending_order: Option<bool>,
+    ) -> RpcResult<ObjectOwnershipHistoryPage>;
+
+    /// Return the activity of an address: its first and last transactions, the number of transactions it sent or received objects from, the gas spent by the transactions it sent and the number of distinct packages they called. Note that this is an enhanced full node only api.
+    #[method(name = "getAddressSummary")]
+    async fn get_address_summary(
+        &self,
+        /// the queried address
+        address: SuiAddress,
+    ) -> RpcResult<Option<AddressSummary>>;
+
+    /// Return the gas fees paid and the storage rebates received by an address in every epoch of a range, as the gas owner of the transactions, in ascending order of epochs. Epochs without such transactions are omitted. Note that this is an enhanced full node only api.
+    #[method(name = "getAddressGasUsage")]
+    async fn get_address_gas_usage(
+        &self,
+        /// the queried address
+        address: SuiAddress,
+        /// the first epoch of the range, default to the genesis epoch
+        from_epoch: Option<BigInt<u64>>,
+        /// the last epoch of the range, default to the current epoch
+        to_epoch: Option<BigInt<u64>>,
+        /// optional paging cursor, the epoch of the last item of the previous page
+        cursor: Option<BigInt<u64>>,
+        /// maximum number of items per page
+        limit: Option<BigInt<u64>>,
+    ) -> RpcResult<AddressGasUsagePage>;
+
+    /// Return the named checkpoint bookmark of an indexer client, if it exists. Note that this is an enhanced full node only api.
+    #[method(name = "getCheckpointBookmark")]
+    async fn get_checkpoint_bookmark(
+        &self,
+        /// the name of the bookmark, e.g. "my-app-cursor"
+        name: String,
+    ) -> RpcResult<Option<CheckpointBookmark>>;
+
+    /// Set the named checkpoint bookmark of an indexer client to the last checkpoint it processed, only if the bookmark is still at the expected checkpoint, so that concurrent consumers cannot overwrite each other's progress. Requires the checkpoint bookmark auth token configured on the indexer. Note that this is an enhanced full node only api.
+    #[method(name = "setCheckpointBookmark")]
+    async fn set_checkpoint_bookmark(
+        &self,
+        /// the name of the bookmark, e.g. "my-app-cursor"
+        name: String,
+        /// the last checkpoint processed by the consumer
+        checkpoint: BigInt<u64>,
+        /// the current checkpoint of the bookmark, none to create a new bookmark
+        expected_checkpoint: Option<BigInt<u64>>,
+        /// the checkpoint bookmark auth token configured on the indexer
+        auth_token: String,
+    ) -> RpcResult<CheckpointBookmark>;
+
     /// Return Network metrics
     #[method(name = "getNetworkMetrics")]
     async fn get_network_metrics(&self) -> RpcResult<NetworkMetrics>;
@@ -49,4 +126,26 @@ pub trait ExtendedApi {
     /// Return Network metrics
     #[method(name = "getMoveCallMetrics")]
     async fn get_move_call_metrics(&self) -> RpcResult<MoveCallMetrics>;
+
+    /// Return the Move functions called in an epoch, ranked by their number of calls, unique callers, gas used or failure rate. Note that this is an enhanced full node only api.
+    #[method(name = "getMoveCallStats")]
+    async fn get_move_call_stats(
+        &self,
+        /// the epoch of the calls
+        epoch: BigInt<u64>,
+        /// only return the functions of this package
+        package: Option<ObjectID>,
+        /// the statistic the functions are ranked by, default to the number of calls
+        order: Option<MoveCallStatsOrder>,
+        /// maximum number of functions returned
+        limit: Option<BigInt<u64>>,
+    ) -> RpcResult<Vec<MoveCallStats>>;
+
+    /// Return the reference gas price and the percentiles of the gas prices paid by the transactions using shared objects in the last checkpoints, e.g. to offer slow, normal and fast gas prices. Note that this is an enhanced full node only api.
+    #[method(name = "getGasPriceRecommendation")]
+    async fn get_gas_price_recommendation(
+        &self,
+        /// the number of last checkpoints the percentiles are computed from, default to 100
+        checkpoints: Option<BigInt<u64>>,
+    ) -> RpcResult<GasPriceRecommendation>;
 }
@@ -4,9 +4,12 @@
 use jsonrpsee::core::RpcResult;
 use jsonrpsee_proc_macros::rpc;
 
-use sui_json_rpc_types::{DelegatedStake, SuiCommittee};
+use sui_json_rpc_types::{
+    DelegatedStake, SuiCommittee, ValidatorEpochOutcomePage, ValidatorReport,
+};
 use sui_open_rpc_macros::open_rpc;
 use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::event::EventID;
 use sui_types::sui_serde::BigInt;
 use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
 
@@ -36,6 +39,24 @@ pub trait GovernanceReadApi {
     #[method(name = "getLatestSuiSystemState")]
     async fn get_latest_sui_system_state(&self) -> RpcResult<SuiSystemStateSummary>;
 
+    /// Return the tallying rule reports of the current epoch: the validators reporting each reported validator, and whether its staking rewards will be slashed at the end of the epoch.
+    #[method(name = "getValidatorReports")]
+    async fn get_validator_reports(&self) -> RpcResult<Vec<ValidatorReport>>;
+
+    /// Return the outcomes of the tallying rule at the end of past epochs: the reporters of each validator, whether its staking rewards were slashed, and the rewards it earned.
+    #[method(name = "getValidatorEpochOutcomes")]
+    async fn get_validator_epoch_outcomes(
+        &self,
+        /// optional validator to return the outcomes of, all the validators if not specified
+        validator: Option<SuiAddress>,
+        /// optional paging cursor
+        cursor: Option<EventID>,
+        /// maximum number of items per page, default to [QUERY_MAX_RESULT_LIMIT] if not specified.
+        limit: Option<usize>,
+        /// query result ordering, default to false (ascending order), oldest record first.
+        descending_order: Option<bool>,
+    ) -> RpcResult<ValidatorEpochOutcomePage>;
+
     /// Return the reference gas price for the network
     #[method(name = "getReferenceGasPrice")]
     async fn get_reference_gas_price(&self) -> RpcResult<BigInt<u64>>;
@@ -5,8 +5,9 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee_proc_macros::rpc;
 
 use sui_json_rpc_types::{
-    DynamicFieldPage, EventFilter, EventPage, ObjectsPage, SuiEvent, SuiObjectResponse,
-    SuiObjectResponseQuery, SuiTransactionBlockResponseQuery, TransactionBlocksPage,
+    DynamicFieldExportPage, DynamicFieldPage, EventFilter, EventPage, ObjectsPage,
+    OwnedObjectChange, SuiEvent, SuiObjectResponse, SuiObjectResponseQuery,
+    SuiTransactionBlockResponseQuery, SuiTransactionBlockStatus, TransactionBlocksPage,
 };
 use sui_open_rpc_macros::open_rpc;
 use sui_types::base_types::{ObjectID, SuiAddress};
@@ -71,6 +72,45 @@ pub trait IndexerApi {
         filter: EventFilter,
     );
 
+    /// Subscribe to a stream of the changes of the objects owned by an address, as the transactions
+    /// changing them are executed: objects created, mutated, transferred to or from the address,
+    /// wrapped or deleted.
+    #[subscription(name = "subscribeOwnedObjectChanges", item = OwnedObjectChange)]
+    fn subscribe_owned_object_changes(
+        &self,
+        /// the owner of the objects
+        address: SuiAddress,
+    );
+
+    /// Subscribe to the status of a transaction block on this node, pushed when the subscription
+    /// starts and every time it changes, until the transaction block is finalized. Statuses the
+    /// transaction block moves through faster than they are polled by the node are skipped.
+    #[subscription(name = "subscribeTransactionStatus", item = SuiTransactionBlockStatus)]
+    fn subscribe_transaction_status(
+        &self,
+        /// the digest of the transaction block
+        digest: TransactionDigest,
+    );
+
+    /// Subscribe to a stream of all the dynamic fields of an object with their values, in pages of
+    /// fields ordered by field object ID. The stream ends after the page without a next page, an
+    /// export interrupted before can be resumed from the `nextCursor` of the last page received.
+    /// The stream is closed with an error if a page cannot be read.
+    #[subscription(
+        name = "exportDynamicFields",
+        unsubscribe = "unsubscribeExportDynamicFields",
+        item = DynamicFieldExportPage
+    )]
+    fn export_dynamic_fields(
+        &self,
+        /// The ID of the parent object
+        parent_object_id: ObjectID,
+        /// An optional paging cursor. If provided, the export will start from the next field after the specified cursor. Default to start from the first field if not specified.
+        cursor: Option<ObjectID>,
+        /// Maximum fields per page, default to [QUERY_MAX_RESULT_LIMIT] if not specified.
+        page_size: Option<usize>,
+    );
+
     /// Return the list of dynamic field objects owned by an object.
     #[method(name = "getDynamicFields", blocking)]
     fn get_dynamic_fields(
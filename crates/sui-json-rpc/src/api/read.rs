@@ -5,9 +5,10 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee_proc_macros::rpc;
 
 use sui_json_rpc_types::{
-    Checkpoint, CheckpointId, CheckpointPage, SuiEvent, SuiGetPastObjectRequest,
-    SuiObjectDataOptions, SuiObjectResponse, SuiPastObjectResponse, SuiTransactionBlockResponse,
-    SuiTransactionBlockResponseOptions,
+    Checkpoint, CheckpointId, CheckpointPage, SuiCheckpointCertificate, SuiEvent,
+    SuiGetPastObjectRequest, SuiObjectDataOptions, SuiObjectLockStatus, SuiObjectProof,
+    SuiObjectResponse, SuiObjectVersionsDiff, SuiPastObjectResponse, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions, SuiTransactionProof,
 };
 use sui_open_rpc_macros::open_rpc;
 use sui_types::base_types::{ObjectID, SequenceNumber, TransactionDigest};
@@ -86,6 +87,54 @@ pub trait ReadApi {
         options: Option<SuiObjectDataOptions>,
     ) -> RpcResult<Vec<SuiPastObjectResponse>>;
 
+    /// Return the fields which differ between two versions of a Move object, with their paths in
+    /// the content of the object. Both versions are subject to the pruning policy of the node, as
+    /// for `sui_tryGetPastObject`.
+    #[method(name = "diffObjectVersions")]
+    async fn diff_object_versions(
+        &self,
+        /// the ID of the queried object
+        object_id: ObjectID,
+        /// the earlier version of the object
+        from_version: SequenceNumber,
+        /// the later version of the object
+        to_version: SequenceNumber,
+    ) -> RpcResult<SuiObjectVersionsDiff>;
+
+    /// Return whether an owned object version is locked to a transaction in the current epoch,
+    /// and by which transaction. Validators lock the owned objects of the transactions they sign,
+    /// fullnodes do not sign transactions and only report whether the version was consumed.
+    #[method(name = "getObjectLockStatus", blocking)]
+    fn get_object_lock_status(
+        &self,
+        /// the ID of the queried object
+        object_id: ObjectID,
+        /// the version of the queried object
+        version: SequenceNumber,
+    ) -> RpcResult<SuiObjectLockStatus>;
+
+    /// Return the proof that an object version is part of the state at the checkpoint including
+    /// the transaction which wrote it, verifiable against the committee of the epoch of the
+    /// checkpoint without trusting the node.
+    #[method(name = "getObjectProof", blocking)]
+    fn get_object_proof(
+        &self,
+        /// the ID of the queried object
+        object_id: ObjectID,
+        /// the version of the queried object
+        version: SequenceNumber,
+    ) -> RpcResult<SuiObjectProof>;
+
+    /// Return the proof that a transaction was executed with its effects and events, from the
+    /// checkpoint including it, verifiable offline against the committee of the epoch of the
+    /// checkpoint without trusting the node.
+    #[method(name = "getTransactionProof", blocking)]
+    fn get_transaction_proof(
+        &self,
+        /// the digest of the queried transaction
+        digest: TransactionDigest,
+    ) -> RpcResult<SuiTransactionProof>;
+
     /// Return a checkpoint
     #[method(name = "getCheckpoint")]
     async fn get_checkpoint(
@@ -94,6 +143,15 @@ pub trait ReadApi {
         id: CheckpointId,
     ) -> RpcResult<Checkpoint>;
 
+    /// Return the summary of a checkpoint with the signatures of the validators certifying it, for
+    /// clients to verify the checkpoint against the committee of its epoch themselves.
+    #[method(name = "getCheckpointCertificate", blocking)]
+    fn get_checkpoint_certificate(
+        &self,
+        /// Checkpoint identifier, can use either checkpoint digest, or checkpoint sequence number as input.
+        id: CheckpointId,
+    ) -> RpcResult<SuiCheckpointCertificate>;
+
     /// Return paginated list of checkpoints
     #[method(name = "getCheckpoints", blocking)]
     fn get_checkpoints(
@@ -6,8 +6,8 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee_proc_macros::rpc;
 
 use sui_json_rpc_types::{
-    DevInspectResults, DryRunTransactionBlockResponse, SuiTransactionBlockResponse,
-    SuiTransactionBlockResponseOptions,
+    DevInspectResults, DryRunTransactionBlockResponse, GasBudgetEstimate, GasBudgetSafetyMargin,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
 use sui_open_rpc_macros::open_rpc;
 use sui_types::base_types::SuiAddress;
@@ -53,6 +53,9 @@ pub trait WriteApi {
         gas_price: Option<BigInt<u64>>,
         /// The epoch to perform the call. Will be set from the system state object if not provided
         epoch: Option<BigInt<u64>>,
+        /// The timestamp of the `Clock` object during the call, to simulate time based logic at
+        /// another time. Defaults to the timestamp of the last consensus commit
+        clock_timestamp_ms: Option<BigInt<u64>>,
     ) -> RpcResult<DevInspectResults>;
 
     /// Return transaction execution effects including the gas cost summary,
@@ -62,4 +65,17 @@ pub trait WriteApi {
         &self,
         tx_bytes: Base64,
     ) -> RpcResult<DryRunTransactionBlockResponse>;
+
+    /// Estimate the gas budget of a transaction by dry running it, and adding safety margins
+    /// to the gas used. The budget and gas payment of the transaction are ignored, and the
+    /// estimate is for the higher of its gas price and the reference gas price.
+    #[method(name = "estimateGasBudget")]
+    async fn estimate_gas_budget(
+        &self,
+        /// BCS serialized transaction data bytes without its type tag, as base-64 encoded string.
+        tx_bytes: Base64,
+        /// Safety margins added to the dry run costs, defaults to 10% on computation and storage,
+        /// and an additional 20% on computation for transactions using shared objects.
+        safety_margin: Option<GasBudgetSafetyMargin>,
+    ) -> RpcResult<GasBudgetEstimate>;
 }
@@ -19,6 +19,7 @@ use sui_types::messages::TransactionEffectsAPI;
 use sui_types::messages::{ExecutionStatus, TransactionEffects};
 use sui_types::object::{Object, Owner};
 use sui_types::storage::WriteKind;
+use sui_types::temporary_store::InnerTemporaryStore;
 
 pub async fn get_balance_changes_from_effect<P: ObjectProvider<Error = E>, E>(
     object_provider: &P,
@@ -196,6 +197,23 @@ impl<P> ObjectProviderCache<P> {
             provider,
         }
     }
+
+    /// Caches both the input objects and the objects written by an execution which was not
+    /// committed, e.g. a dry run, so that the input coins missing from the provider, like a mock
+    /// gas coin, are accounted for in the balance changes.
+    pub fn new_with_temporary_store(provider: P, store: InnerTemporaryStore) -> Self {
+        let mut cache = Self::new_with_cache(provider, store.written);
+        {
+            let object_cache = cache.object_cache.get_mut();
+            let last_version_cache = cache.last_version_cache.get_mut();
+            for (object_id, object) in store.objects {
+                let key = (object_id, object.version());
+                last_version_cache.entry(key).or_insert(key.1);
+                object_cache.entry(key).or_insert(object);
+            }
+        }
+        cache
+    }
 }
 
 #[async_trait]
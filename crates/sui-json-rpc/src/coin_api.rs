@@ -117,6 +117,7 @@ impl CoinReadApi {
             data,
             next_cursor,
             has_next_page,
+            truncated_due_to_limit: false,
         })
     }
 
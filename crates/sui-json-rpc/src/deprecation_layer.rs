@@ -0,0 +1,250 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use hyper::{Body, HeaderMap, Method, Request, Response};
+use jsonrpsee::core::__reexports::serde_json;
+use jsonrpsee::core::error::GenericTransportError;
+use jsonrpsee::core::http_helpers::read_body;
+use jsonrpsee::types::Request as RpcRequest;
+use prometheus::{register_int_counter_vec_with_registry, IntCounterVec, Registry};
+use tower::{Layer, Service};
+
+use crate::routing_layer::{is_json, response};
+use crate::{APP_NAME_HEADER, CLIENT_SDK_TYPE_HEADER, CLIENT_SDK_VERSION_HEADER, MAX_REQUEST_SIZE};
+
+/// Former names of renamed methods, and the methods they are served by.
+pub const DEPRECATED_METHOD_ALIASES: [(&str, &str); 6] = [
+    ("sui_getTransaction", "sui_getTransactionBlock"),
+    ("sui_multiGetTransactions", "sui_multiGetTransactionBlocks"),
+    ("sui_executeTransaction", "sui_executeTransactionBlock"),
+    ("sui_dryRunTransaction", "sui_dryRunTransactionBlock"),
+    (
+        "sui_devInspectTransaction",
+        "sui_devInspectTransactionBlock",
+    ),
+    (
+        "sui_getTotalTransactionNumber",
+        "sui_getTotalTransactionBlocks",
+    ),
+];
+
+/// Clients beyond this number are counted together, so that the headers of the requests cannot
+/// grow the number of time series without bound.
+pub(crate) const MAX_TRACKED_CLIENTS: usize = 256;
+const MAX_LABEL_LENGTH: usize = 64;
+const UNKNOWN_LABEL: &str = "unknown";
+const OTHER_CLIENTS_LABEL: &str = "other";
+
+/// The deprecated methods of the server, and the counters of their usage by client, so that
+/// operators can see who still depends on them before turning them off.
+#[derive(Clone)]
+pub struct DeprecatedMethods {
+    aliases: Arc<HashMap<String, String>>,
+    deprecated: Arc<HashSet<String>>,
+    disable_aliases: bool,
+    usage: IntCounterVec,
+    tracked_clients: Arc<Mutex<HashSet<ClientInfo>>>,
+}
+
+/// The client of a request, from the headers set by the SDKs.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ClientInfo {
+    pub sdk_type: String,
+    pub sdk_version: String,
+    pub app_name: String,
+}
+
+impl ClientInfo {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.chars().take(MAX_LABEL_LENGTH).collect())
+                .unwrap_or_else(|| UNKNOWN_LABEL.to_string())
+        };
+        Self {
+            sdk_type: header(CLIENT_SDK_TYPE_HEADER),
+            sdk_version: header(CLIENT_SDK_VERSION_HEADER),
+            app_name: header(APP_NAME_HEADER),
+        }
+    }
+
+    fn other() -> Self {
+        Self {
+            sdk_type: OTHER_CLIENTS_LABEL.to_string(),
+            sdk_version: OTHER_CLIENTS_LABEL.to_string(),
+            app_name: OTHER_CLIENTS_LABEL.to_string(),
+        }
+    }
+}
+
+impl DeprecatedMethods {
+    /// `aliases` maps the former names of renamed methods to their current names, and
+    /// `deprecated` holds the methods still served under their name but due for removal. If
+    /// `disable_aliases` is set, the former names are no longer served, their usage is still
+    /// counted.
+    pub fn new(
+        registry: &Registry,
+        aliases: HashMap<String, String>,
+        deprecated: HashSet<String>,
+        disable_aliases: bool,
+    ) -> Self {
+        Self {
+            aliases: Arc::new(aliases),
+            deprecated: Arc::new(deprecated),
+            disable_aliases,
+            usage: register_int_counter_vec_with_registry!(
+                "rpc_deprecated_method_requests",
+                "Number of requests of deprecated methods by method and client",
+                &["method", "client_type", "client_version", "app_name"],
+                registry,
+            )
+            .unwrap(),
+            tracked_clients: Default::default(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty() && self.deprecated.is_empty()
+    }
+
+    /// Number of requests of the deprecated `method` by `client`.
+    pub fn usage(&self, method: &str, client: &ClientInfo) -> u64 {
+        self.usage
+            .with_label_values(&[
+                method,
+                &client.sdk_type,
+                &client.sdk_version,
+                &client.app_name,
+            ])
+            .get()
+    }
+
+    fn record(&self, method: &str, client: &ClientInfo) {
+        let client = {
+            let mut tracked = self.tracked_clients.lock().unwrap();
+            if tracked.contains(client) || tracked.len() < MAX_TRACKED_CLIENTS {
+                tracked.insert(client.clone());
+                client.clone()
+            } else {
+                ClientInfo::other()
+            }
+        };
+        self.usage
+            .with_label_values(&[
+                method,
+                &client.sdk_type,
+                &client.sdk_version,
+                &client.app_name,
+            ])
+            .inc();
+    }
+
+    /// Counts the request in `body` if it calls a deprecated method, and returns the request to
+    /// serve, calling the current name of a renamed method. The request is returned unchanged
+    /// if it cannot be parsed.
+    pub fn process_single_request(&self, body: &[u8], client: &ClientInfo) -> Vec<u8> {
+        let Ok(mut request) = serde_json::from_slice::<RpcRequest>(body) else {
+            return body.to_vec();
+        };
+        if self.deprecated.contains(request.method.as_ref()) {
+            self.record(&request.method, client);
+            return body.to_vec();
+        }
+        let Some(route_to) = self.aliases.get(request.method.as_ref()) else {
+            return body.to_vec();
+        };
+        self.record(&request.method, client);
+        if self.disable_aliases {
+            return body.to_vec();
+        }
+        request.method = route_to.clone().into();
+        serde_json::to_vec(&request).unwrap_or_else(|_| body.to_vec())
+    }
+}
+
+#[derive(Clone)]
+pub struct DeprecationLayer {
+    methods: DeprecatedMethods,
+}
+
+impl DeprecationLayer {
+    pub fn new(methods: DeprecatedMethods) -> Self {
+        Self { methods }
+    }
+}
+
+impl<S> Layer<S> for DeprecationLayer {
+    type Service = DeprecationService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        DeprecationService {
+            inner,
+            methods: self.methods.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DeprecationService<S> {
+    inner: S,
+    methods: DeprecatedMethods,
+}
+
+impl<S> Service<Request<Body>> for DeprecationService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Response: 'static,
+    S::Error: Into<Box<dyn Error + Send + Sync>> + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = Box<dyn Error + Send + Sync + 'static>;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let clone = self.inner.clone();
+        let methods = self.methods.clone();
+        // take the service that was ready
+        // https://docs.rs/tower/latest/tower/trait.Service.html#be-careful-when-cloning-inner-services
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let res_fut = async move {
+            let req = if !methods.is_empty() && req.method() == Method::POST && is_json(&req) {
+                let (parts, body) = req.into_parts();
+                let client = ClientInfo::from_headers(&parts.headers);
+                let body = match read_body(&parts.headers, body, MAX_REQUEST_SIZE).await {
+                    Ok((body, true)) => methods.process_single_request(&body, &client),
+                    // The server does not support batched requests.
+                    Ok((body, false)) => body,
+                    Err(GenericTransportError::TooLarge) => {
+                        return Ok(response::too_large(MAX_REQUEST_SIZE))
+                    }
+                    Err(GenericTransportError::Malformed) => return Ok(response::malformed()),
+                    Err(GenericTransportError::Inner(e)) => {
+                        tracing::error!("Internal error reading request body: {}", e);
+                        return Ok(response::internal_error());
+                    }
+                };
+                Request::from_parts(parts, Body::from(body))
+            } else {
+                req
+            };
+            inner.call(req).await.map_err(|err| err.into())
+        };
+        Box::pin(res_fut)
+    }
+}
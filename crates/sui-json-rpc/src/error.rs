@@ -4,7 +4,9 @@
 use fastcrypto::error::FastCryptoError;
 use hyper::header::InvalidHeaderValue;
 use jsonrpsee::core::Error as RpcError;
-use jsonrpsee::types::error::CallError;
+use jsonrpsee::types::error::{CallError, CALL_EXECUTION_FAILED_CODE};
+use jsonrpsee::types::ErrorObject;
+use sui_json_rpc_types::{SuiRpcErrorCode, SuiRpcErrorData};
 use sui_types::error::{SuiError, SuiObjectResponseError, UserInputError};
 use sui_types::quorum_driver_types::QuorumDriverError;
 use thiserror::Error;
@@ -49,8 +51,31 @@ pub enum Error {
     SuiObjectResponseError(#[from] SuiObjectResponseError),
 }
 
+impl Error {
+    /// The stable error code sent to clients in the `data` field of the JSON-RPC error.
+    pub fn error_code(&self) -> Option<SuiRpcErrorCode> {
+        match self {
+            Error::SuiError(e) => SuiRpcErrorCode::from_sui_error(e),
+            Error::UserInputError(e) => SuiRpcErrorCode::from_user_input_error(e),
+            Error::QuorumDriverError(e) => SuiRpcErrorCode::from_quorum_driver_error(e),
+            Error::SuiObjectResponseError(e) => SuiRpcErrorCode::from_object_response_error(e),
+            Error::InternalError(e) => e
+                .downcast_ref::<SuiError>()
+                .and_then(SuiRpcErrorCode::from_sui_error),
+            _ => None,
+        }
+    }
+}
+
 impl From<Error> for RpcError {
     fn from(e: Error) -> Self {
-        RpcError::Call(CallError::Failed(e.into()))
+        match e.error_code() {
+            Some(code) => RpcError::Call(CallError::Custom(ErrorObject::owned(
+                CALL_EXECUTION_FAILED_CODE,
+                e.to_string(),
+                Some(SuiRpcErrorData { code }),
+            ))),
+            None => RpcError::Call(CallError::Failed(e.into())),
+        }
     }
 }
@@ -11,12 +11,15 @@ use jsonrpsee::RpcModule;
 
 use sui_core::authority::AuthorityState;
 use sui_json_rpc_types::SuiCommittee;
-use sui_json_rpc_types::{DelegatedStake, Stake, StakeStatus};
+use sui_json_rpc_types::{
+    DelegatedStake, EventFilter, Page, Stake, StakeStatus, ValidatorEpochOutcomePage,
+    ValidatorReport,
+};
 use sui_open_rpc::Module;
 use sui_types::base_types::{MoveObjectType, ObjectID, SuiAddress};
-use sui_types::committee::EpochId;
-use sui_types::dynamic_field::get_dynamic_field_from_store;
-use sui_types::error::{SuiError, UserInputError};
+use sui_types::committee::{EpochId, QUORUM_THRESHOLD};
+use sui_types::error::UserInputError;
+use sui_types::event::EventID;
 use sui_types::governance::StakedSui;
 use sui_types::id::ID;
 use sui_types::object::ObjectRead;
@@ -24,14 +27,20 @@ use sui_types::sui_serde::BigInt;
 use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
 use sui_types::sui_system_state::PoolTokenExchangeRate;
 use sui_types::sui_system_state::SuiSystemStateTrait;
+use sui_types::sui_system_state::ValidatorEpochInfoEventV2;
 use sui_types::sui_system_state::{
-    get_validator_from_table, sui_system_state_summary::get_validator_by_pool_id, SuiSystemState,
+    get_exchange_rate_at_epoch, get_validator_from_table,
+    sui_system_state_summary::get_validator_by_pool_id, SuiSystemState,
 };
 
-use crate::api::GovernanceReadApiServer;
+use crate::api::{cap_page_limit, GovernanceReadApiServer, QUERY_MAX_RESULT_LIMIT};
 use crate::error::Error;
 use crate::SuiRpcModule;
 
+/// Maximum number of epoch change events scanned by a request for the outcomes of a validator.
+/// The page ends at the last scanned event when the scan reaches it.
+const MAX_SCANNED_OUTCOME_EVENTS: usize = 10 * QUERY_MAX_RESULT_LIMIT;
+
 pub struct GovernanceReadApi {
     state: Arc<AuthorityState>,
 }
@@ -176,6 +185,56 @@ impl GovernanceReadApi {
         Ok(delegated_stakes)
     }
 
+    fn get_validator_reports(&self) -> Result<Vec<ValidatorReport>, Error> {
+        let system_state = self.get_system_state()?.into_sui_system_state_summary();
+        let voting_power: BTreeMap<_, _> = system_state
+            .active_validators
+            .iter()
+            .map(|v| (v.sui_address, v.voting_power))
+            .collect();
+        Ok(system_state
+            .validator_report_records
+            .into_iter()
+            .map(|(reportee, reporters)| {
+                // Reporters which are not in the committee anymore have no voting power.
+                let reporters_voting_power: u64 = reporters
+                    .iter()
+                    .filter_map(|reporter| voting_power.get(reporter))
+                    .sum();
+                ValidatorReport {
+                    reportee,
+                    reporters,
+                    reporters_voting_power,
+                    slashing_pending: reporters_voting_power >= QUORUM_THRESHOLD,
+                }
+            })
+            .collect())
+    }
+
+    fn get_validator_epoch_outcomes(
+        &self,
+        validator: Option<SuiAddress>,
+        cursor: Option<EventID>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> Result<ValidatorEpochOutcomePage, Error> {
+        let descending = descending_order.unwrap_or_default();
+        let filter = EventFilter::MoveEventType(ValidatorEpochInfoEventV2::type_());
+        scan_validator_epoch_outcomes(
+            validator,
+            cursor,
+            cap_page_limit(limit),
+            MAX_SCANNED_OUTCOME_EVENTS,
+            |cursor, batch_size| {
+                self.state
+                    .query_events(filter.clone(), cursor, batch_size, descending)?
+                    .into_iter()
+                    .map(|event| Ok::<_, Error>((event.id, bcs::from_bytes(&event.bcs)?)))
+                    .collect()
+            },
+        )
+    }
+
     fn get_system_state(&self) -> Result<SuiSystemState, Error> {
         Ok(self.state.database.get_sui_system_state_object()?)
     }
@@ -185,16 +244,8 @@ impl GovernanceReadApi {
         system_state: &SuiSystemStateSummary,
         pool_id: &ObjectID,
     ) -> Result<ObjectID, Error> {
-        let active_rate = system_state.active_validators.iter().find_map(|v| {
-            if &v.staking_pool_id == pool_id {
-                Some(v.exchange_rates_id)
-            } else {
-                None
-            }
-        });
-
-        if let Some(active_rate) = active_rate {
-            Ok(active_rate)
+        if let Some(active) = system_state.active_validator_by_pool_id(pool_id) {
+            Ok(active.exchange_rates_id)
         } else {
             // try find from inactive pool
             let validator = get_validator_from_table(
@@ -212,15 +263,69 @@ impl GovernanceReadApi {
         table: ObjectID,
         epoch: EpochId,
     ) -> Result<PoolTokenExchangeRate, Error> {
-        let exchange_rate: PoolTokenExchangeRate = get_dynamic_field_from_store(
+        Ok(get_exchange_rate_at_epoch(
             self.state.db().as_ref(),
             table,
-            &epoch,
-        )
-        .map_err(|err| {
-            SuiError::SuiSystemStateReadError(format!("Failed to get exchange rate: {:?}", err))
-        })?;
-        Ok(exchange_rate)
+            epoch,
+        )?)
+    }
+}
+
+/// Reads the outcomes of `validator`, or of all the validators, from the epoch change events
+/// returned in batches by `query_events` from a cursor. The events of all the validators are
+/// scanned and filtered by validator, at most `max_scanned` of them: the page is truncated at the
+/// last scanned event when the scan reaches it.
+pub(crate) fn scan_validator_epoch_outcomes(
+    validator: Option<SuiAddress>,
+    cursor: Option<EventID>,
+    limit: usize,
+    max_scanned: usize,
+    mut query_events: impl FnMut(
+        Option<EventID>,
+        usize,
+    ) -> Result<Vec<(EventID, ValidatorEpochInfoEventV2)>, Error>,
+) -> Result<ValidatorEpochOutcomePage, Error> {
+    let mut data = vec![];
+    let mut next_cursor = cursor.clone();
+    let mut scan_cursor = cursor;
+    let mut scanned = 0;
+    loop {
+        if scanned == max_scanned {
+            return Ok(Page {
+                data,
+                next_cursor: scan_cursor,
+                has_next_page: true,
+                truncated_due_to_limit: true,
+            });
+        }
+        let batch_size = QUERY_MAX_RESULT_LIMIT.min(max_scanned - scanned);
+        let events = query_events(scan_cursor.clone(), batch_size)?;
+        scanned += events.len();
+        let last_batch = events.len() < batch_size;
+        for (id, outcome) in events {
+            scan_cursor = Some(id.clone());
+            if validator.map_or(false, |v| v != outcome.validator_address) {
+                continue;
+            }
+            if data.len() == limit {
+                return Ok(Page {
+                    data,
+                    next_cursor,
+                    has_next_page: true,
+                    truncated_due_to_limit: false,
+                });
+            }
+            data.push(outcome.into());
+            next_cursor = Some(id);
+        }
+        if last_batch {
+            return Ok(Page {
+                data,
+                next_cursor,
+                has_next_page: false,
+                truncated_due_to_limit: false,
+            });
+        }
     }
 }
 
@@ -255,6 +360,20 @@ impl GovernanceReadApiServer for GovernanceReadApi {
             .into_sui_system_state_summary())
     }
 
+    async fn get_validator_reports(&self) -> RpcResult<Vec<ValidatorReport>> {
+        Ok(self.get_validator_reports()?)
+    }
+
+    async fn get_validator_epoch_outcomes(
+        &self,
+        validator: Option<SuiAddress>,
+        cursor: Option<EventID>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<ValidatorEpochOutcomePage> {
+        Ok(self.get_validator_epoch_outcomes(validator, cursor, limit, descending_order)?)
+    }
+
     async fn get_reference_gas_price(&self) -> RpcResult<BigInt<u64>> {
         let epoch_store = self.state.load_epoch_store_one_call_per_task();
         Ok(epoch_store.reference_gas_price().into())
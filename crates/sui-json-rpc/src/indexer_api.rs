@@ -1,35 +1,54 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::fmt::Display;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::anyhow;
 use async_trait::async_trait;
-use futures::Stream;
+use futures::{stream, Stream, TryStream};
 use jsonrpsee::core::error::SubscriptionClosed;
 use jsonrpsee::core::RpcResult;
+use jsonrpsee::types::error::CallError;
 use jsonrpsee::types::SubscriptionResult;
 use jsonrpsee::{RpcModule, SubscriptionSink};
 use serde::Serialize;
+use serde_json::Value;
 use tracing::{debug, warn};
 
 use mysten_metrics::spawn_monitored_task;
 use sui_core::authority::AuthorityState;
 use sui_json_rpc_types::{
-    DynamicFieldPage, EventFilter, EventPage, ObjectsPage, Page, SuiObjectDataOptions,
-    SuiObjectResponse, SuiObjectResponseQuery, SuiTransactionBlockResponse,
-    SuiTransactionBlockResponseQuery, TransactionBlocksPage,
+    DynamicFieldEntry, DynamicFieldExportPage, DynamicFieldPage, EventFilter, EventPage,
+    ObjectsPage, Page, PageSizeBudget, SuiMoveStruct, SuiObjectDataOptions, SuiObjectResponse,
+    SuiObjectResponseQuery, SuiTransactionBlockResponse, SuiTransactionBlockResponseQuery,
+    SuiTransactionBlockStatus, TransactionBlocksPage,
 };
 use sui_open_rpc::Module;
 use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::digests::TransactionDigest;
-use sui_types::dynamic_field::DynamicFieldName;
+use sui_types::dynamic_field::{DynamicFieldInfo, DynamicFieldName, DynamicFieldType};
 use sui_types::event::EventID;
+use sui_types::object::ObjectRead;
 
 use crate::api::{
     cap_page_limit, validate_limit, IndexerApiServer, ReadApiServer, QUERY_MAX_RESULT_LIMIT_OBJECTS,
 };
-use crate::SuiRpcModule;
+use crate::{RequestLimits, SuiRpcModule};
+
+/// Interval at which the status of a transaction block is read for its subscribers.
+const TRANSACTION_STATUS_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Time after which a transaction status subscription fails if the transaction is not finalized.
+const TRANSACTION_STATUS_SUBSCRIPTION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Maximum number of transaction status subscriptions served concurrently by a node.
+const MAX_TRANSACTION_STATUS_SUBSCRIPTIONS: usize = 1000;
+
+/// Number of items of a page read at once while it fits in the max response size.
+const PAGE_FETCH_CHUNK_SIZE: usize = 10;
 
 pub fn spawn_subscription<S, T>(mut sink: SubscriptionSink, rx: S)
 where
@@ -50,14 +69,110 @@ where
     });
 }
 
+/// Like [spawn_subscription], but the subscription is closed with the first error of the stream.
+pub fn spawn_try_subscription<S, T, E>(mut sink: SubscriptionSink, rx: S)
+where
+    S: TryStream<Ok = T, Error = E> + Unpin + Send + 'static,
+    T: Serialize,
+    E: Display,
+{
+    spawn_monitored_task!(async move {
+        match sink.pipe_from_try_stream(rx).await {
+            SubscriptionClosed::Success => {
+                sink.close(SubscriptionClosed::Success);
+            }
+            SubscriptionClosed::RemotePeerAborted => (),
+            SubscriptionClosed::Failed(err) => {
+                debug!(error = ?err, "Subscription closed with an error.");
+                sink.close(err);
+            }
+        };
+    });
+}
+
+/// A slot of a subscription counted against a maximum, released when dropped with the stream of
+/// the subscription.
+struct SubscriptionPermit(Arc<AtomicUsize>);
+
+impl SubscriptionPermit {
+    fn acquire(count: &Arc<AtomicUsize>, max: usize) -> Option<Self> {
+        count
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| {
+                (n < max).then_some(n + 1)
+            })
+            .ok()
+            .map(|_| Self(count.clone()))
+    }
+}
+
+impl Drop for SubscriptionPermit {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
 pub struct IndexerApi<R> {
     state: Arc<AuthorityState>,
     read_api: R,
+    request_limits: RequestLimits,
+    transaction_status_subscriptions: Arc<AtomicUsize>,
 }
 
 impl<R: ReadApiServer> IndexerApi<R> {
-    pub fn new(state: Arc<AuthorityState>, read_api: R) -> Self {
-        Self { state, read_api }
+    /// The pages returned are truncated to the max response size of `request_limits`.
+    pub fn new(state: Arc<AuthorityState>, read_api: R, request_limits: RequestLimits) -> Self {
+        Self {
+            state,
+            read_api,
+            request_limits,
+            transaction_status_subscriptions: Default::default(),
+        }
+    }
+
+    fn truncate_to_max_size<T: Serialize, C>(
+        &self,
+        mut page: Page<T, C>,
+        cursor: impl FnOnce(usize, &T) -> C,
+    ) -> Page<T, C> {
+        if let Some(max_size) = self.request_limits.max_response_size() {
+            page.truncate_to_size(max_size, cursor);
+        }
+        page
+    }
+
+    /// Builds the page of the items of `keys`, read with `fetch` a chunk at a time, and stops
+    /// reading once their JSON serialization no longer fits in the max response size of
+    /// `request_limits`. The page keeps at least one item, and is truncated after the last one
+    /// that fits.
+    fn fetch_page_within_max_size<K: Clone, T: Serialize>(
+        &self,
+        keys: &[K],
+        next_cursor: Option<K>,
+        has_next_page: bool,
+        mut fetch: impl FnMut(Vec<K>) -> RpcResult<Vec<T>>,
+    ) -> RpcResult<Page<T, K>> {
+        let mut page = Page {
+            data: Vec::with_capacity(keys.len()),
+            next_cursor,
+            has_next_page,
+            truncated_due_to_limit: false,
+        };
+        let Some(max_size) = self.request_limits.max_response_size() else {
+            page.data = fetch(keys.to_vec())?;
+            return Ok(page);
+        };
+        let mut budget = PageSizeBudget::new(max_size);
+        for chunk in keys.chunks(PAGE_FETCH_CHUNK_SIZE) {
+            for item in fetch(chunk.to_vec())? {
+                if !budget.try_add(&item) {
+                    let last = page.data.len() - 1;
+                    page.mark_truncated(keys[last].clone());
+                    return Ok(page);
+                }
+                page.data.push(item);
+            }
+        }
+        Ok(page)
     }
 }
 
@@ -86,21 +201,23 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
             .cloned()
             .map_or(cursor, |o_info| Some(o_info.object_id));
 
-        let data = match options.is_not_in_object_info() {
-            true => {
-                let object_ids = objects.iter().map(|obj| obj.object_id).collect();
-                self.read_api.multi_get_objects(object_ids, Some(options))?
-            }
-            false => objects
+        let object_ids: Vec<_> = objects.iter().map(|obj| obj.object_id).collect();
+        if !options.is_not_in_object_info() {
+            let data = objects
                 .into_iter()
                 .map(|o_info| SuiObjectResponse::try_from((o_info, options.clone())))
-                .collect::<Result<Vec<SuiObjectResponse>, _>>()?,
-        };
+                .collect::<Result<Vec<SuiObjectResponse>, _>>()?;
+            let page = Page {
+                data,
+                next_cursor,
+                has_next_page,
+                truncated_due_to_limit: false,
+            };
+            return Ok(self.truncate_to_max_size(page, |index, _| object_ids[index]));
+        }
 
-        Ok(Page {
-            data,
-            next_cursor,
-            has_next_page,
+        self.fetch_page_within_max_size(&object_ids, next_cursor, has_next_page, |ids| {
+            self.read_api.multi_get_objects(ids, Some(options.clone()))
         })
     }
 
@@ -126,20 +243,22 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
         digests.truncate(limit);
         let next_cursor = digests.last().cloned().map_or(cursor, Some);
 
-        let data: Vec<SuiTransactionBlockResponse> = if opts.only_digest() {
-            digests
-                .into_iter()
-                .map(SuiTransactionBlockResponse::new)
-                .collect()
-        } else {
-            self.read_api
-                .multi_get_transaction_blocks(digests, Some(opts))?
-        };
+        if opts.only_digest() {
+            let page = Page {
+                data: digests
+                    .into_iter()
+                    .map(SuiTransactionBlockResponse::new)
+                    .collect(),
+                next_cursor,
+                has_next_page,
+                truncated_due_to_limit: false,
+            };
+            return Ok(self.truncate_to_max_size(page, |_, tx| tx.digest));
+        }
 
-        Ok(Page {
-            data,
-            next_cursor,
-            has_next_page,
+        self.fetch_page_within_max_size(&digests, next_cursor, has_next_page, |digests| {
+            self.read_api
+                .multi_get_transaction_blocks(digests, Some(opts.clone()))
         })
     }
     fn query_events(
@@ -166,18 +285,144 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
         let has_next_page = data.len() > limit;
         data.truncate(limit);
         let next_cursor = data.last().map_or(cursor, |e| Some(e.id.clone()));
-        Ok(EventPage {
+        let page = EventPage {
             data,
             next_cursor,
             has_next_page,
-        })
+            truncated_due_to_limit: false,
+        };
+        Ok(self.truncate_to_max_size(page, |_, event| event.id.clone()))
     }
 
-    fn subscribe_event(&self, sink: SubscriptionSink, filter: EventFilter) -> SubscriptionResult {
+    fn subscribe_event(
+        &self,
+        mut sink: SubscriptionSink,
+        filter: EventFilter,
+    ) -> SubscriptionResult {
+        if let Err(e) = filter.validate() {
+            sink.reject(CallError::InvalidParams(e))?;
+            return Ok(());
+        }
         spawn_subscription(sink, self.state.event_handler.subscribe(filter));
         Ok(())
     }
 
+    fn subscribe_owned_object_changes(
+        &self,
+        sink: SubscriptionSink,
+        address: SuiAddress,
+    ) -> SubscriptionResult {
+        spawn_subscription(
+            sink,
+            self.state
+                .event_handler
+                .subscribe_owned_object_changes(address),
+        );
+        Ok(())
+    }
+
+    fn subscribe_transaction_status(
+        &self,
+        mut sink: SubscriptionSink,
+        digest: TransactionDigest,
+    ) -> SubscriptionResult {
+        let Some(permit) = SubscriptionPermit::acquire(
+            &self.transaction_status_subscriptions,
+            MAX_TRANSACTION_STATUS_SUBSCRIPTIONS,
+        ) else {
+            sink.reject(CallError::Failed(anyhow!(
+                "Too many transaction status subscriptions, at most \
+                 {MAX_TRANSACTION_STATUS_SUBSCRIPTIONS} are served at once"
+            )))?;
+            return Ok(());
+        };
+        let state = self.state.clone();
+        let deadline = tokio::time::Instant::now() + TRANSACTION_STATUS_SUBSCRIPTION_TIMEOUT;
+        // The state holds the last status pushed, and is None once the subscription ends, with the
+        // finalized status or an error.
+        let statuses = stream::unfold(Some(None), move |last| {
+            // The permit is released when the subscription ends and drops the stream.
+            let _permit = &permit;
+            let state = state.clone();
+            async move {
+                let last: Option<SuiTransactionBlockStatus> = last?;
+                loop {
+                    match state.get_transaction_block_status(&digest) {
+                        Ok(Some(status)) if last.as_ref() != Some(&status) => {
+                            let next = match status {
+                                SuiTransactionBlockStatus::Finalized { .. } => None,
+                                _ => Some(Some(status.clone())),
+                            };
+                            return Some((Ok(status), next));
+                        }
+                        Ok(_) => {}
+                        Err(e) => {
+                            let error =
+                                anyhow!("Cannot read the status of transaction {digest}: {e}");
+                            return Some((Err(error), None));
+                        }
+                    }
+                    if tokio::time::Instant::now() >= deadline {
+                        let error = anyhow!(
+                            "Transaction {digest} was not finalized within \
+                             {TRANSACTION_STATUS_SUBSCRIPTION_TIMEOUT:?}"
+                        );
+                        return Some((Err(error), None));
+                    }
+                    tokio::time::sleep(TRANSACTION_STATUS_POLL_INTERVAL).await;
+                }
+            }
+        });
+        spawn_try_subscription(sink, Box::pin(statuses));
+        Ok(())
+    }
+
+    fn export_dynamic_fields(
+        &self,
+        sink: SubscriptionSink,
+        parent_object_id: ObjectID,
+        cursor: Option<ObjectID>,
+        page_size: Option<usize>,
+    ) -> SubscriptionResult {
+        let page_size = cap_page_limit(page_size);
+        let state = self.state.clone();
+        // The next page starts after the cursor in the state, and there is none after the last
+        // page, or after a page which could not be read, which closes the subscription with its
+        // error.
+        let pages = stream::unfold(Some(cursor), move |cursor| {
+            let state = state.clone();
+            async move {
+                let cursor = cursor?;
+                let page = tokio::task::spawn_blocking(move || {
+                    read_dynamic_field_export_page(&state, parent_object_id, cursor, page_size)
+                })
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|page| page);
+                match page {
+                    Ok(page) => {
+                        let next_cursor = page.has_next_page.then_some(page.next_cursor);
+                        Some((Ok(page), next_cursor))
+                    }
+                    Err(e) => {
+                        debug!(
+                            ?parent_object_id,
+                            ?cursor,
+                            "Dynamic field export failed: {e}"
+                        );
+                        let error = anyhow!(
+                            "Cannot export the dynamic fields of {parent_object_id} after \
+                             {cursor:?}: {e}"
+                        );
+                        Some((Err(error), None))
+                    }
+                }
+            }
+        });
+        spawn_try_subscription(sink, Box::pin(pages));
+        Ok(())
+    }
+
     fn get_dynamic_fields(
         &self,
         parent_object_id: ObjectID,
@@ -193,11 +438,13 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
         let has_next_page = data.len() > limit;
         data.truncate(limit);
         let next_cursor = data.last().cloned().map_or(cursor, |c| Some(c.object_id));
-        Ok(DynamicFieldPage {
+        let page = DynamicFieldPage {
             data,
             next_cursor,
             has_next_page,
-        })
+            truncated_due_to_limit: false,
+        };
+        Ok(self.truncate_to_max_size(page, |_, field| field.object_id))
     }
 
     async fn get_dynamic_field_object(
@@ -218,6 +465,53 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
     }
 }
 
+fn read_dynamic_field_export_page(
+    state: &AuthorityState,
+    parent_object_id: ObjectID,
+    cursor: Option<ObjectID>,
+    page_size: usize,
+) -> Result<DynamicFieldExportPage, anyhow::Error> {
+    let mut fields = state.get_dynamic_fields(parent_object_id, cursor, page_size + 1)?;
+    let has_next_page = fields.len() > page_size;
+    fields.truncate(page_size);
+    let next_cursor = fields.last().map_or(cursor, |field| Some(field.object_id));
+    let data = fields
+        .into_iter()
+        .map(|field| {
+            let value = read_dynamic_field_value(state, &field)?;
+            Ok(DynamicFieldEntry { field, value })
+        })
+        .collect::<Result<_, anyhow::Error>>()?;
+    Ok(Page {
+        data,
+        next_cursor,
+        has_next_page,
+        truncated_due_to_limit: false,
+    })
+}
+
+/// Returns the value of a dynamic field, or the content of the child object of a dynamic object
+/// field, as JSON.
+fn read_dynamic_field_value(
+    state: &AuthorityState,
+    field: &DynamicFieldInfo,
+) -> Result<Value, anyhow::Error> {
+    let ObjectRead::Exists(_, object, Some(layout)) = state.get_object_read(&field.object_id)? else {
+        return Err(anyhow!("Cannot find the Move object of dynamic field {}", field.object_id));
+    };
+    let move_object = object
+        .data
+        .try_as_move()
+        .ok_or_else(|| anyhow!("Dynamic field {} is not a Move object", field.object_id))?;
+    let content = SuiMoveStruct::from(move_object.to_move_struct(&layout)?).to_json_value();
+    Ok(match (&field.type_, content) {
+        (DynamicFieldType::DynamicField, Value::Object(mut fields)) => {
+            fields.remove("value").unwrap_or_default()
+        }
+        (_, content) => content,
+    })
+}
+
 impl<R: ReadApiServer> SuiRpcModule for IndexerApi<R> {
     fn rpc(self) -> RpcModule<Self> {
         self.into_rpc()
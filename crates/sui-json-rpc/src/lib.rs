@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::BTreeMap;
 use std::env;
 use std::net::SocketAddr;
 use std::str::FromStr;
@@ -13,28 +14,38 @@ use jsonrpsee::server::{AllowHosts, ServerBuilder};
 use jsonrpsee::RpcModule;
 use prometheus::Registry;
 use tap::TapFallible;
+use telemetry_subscribers::trace_context::{TRACEPARENT_HEADER, TRACESTATE_HEADER};
 use tower_http::cors::{AllowOrigin, CorsLayer};
 use tracing::{info, warn};
 
 pub use balance_changes::*;
 pub use object_changes::*;
+pub use request_limits_layer::RequestLimits;
 use sui_open_rpc::{Module, Project};
 
+use crate::deprecation_layer::{DeprecatedMethods, DeprecationLayer, DEPRECATED_METHOD_ALIASES};
 use crate::error::Error;
 use crate::metrics::MetricsLogger;
+use crate::openrpc_layer::OpenRpcLayer;
+use crate::request_limits_layer::{MethodLimitsLayer, RequestLimitsLayer};
 use crate::routing_layer::RoutingLayer;
+use crate::trace_context_layer::TraceContextLayer;
 
 pub mod api;
 mod balance_changes;
 pub mod coin_api;
+pub mod deprecation_layer;
 pub mod error;
 pub mod governance_api;
 pub mod indexer_api;
 mod metrics;
 pub mod move_utils;
 mod object_changes;
+pub mod openrpc_layer;
 pub mod read_api;
+mod request_limits_layer;
 mod routing_layer;
+mod trace_context_layer;
 pub mod transaction_builder_api;
 pub mod transaction_execution_api;
 
@@ -48,6 +59,18 @@ pub const APP_NAME_HEADER: &str = "app-name";
 
 pub const MAX_REQUEST_SIZE: u32 = 2 << 30;
 
+#[cfg(test)]
+#[path = "unit_tests/deprecation_tests.rs"]
+mod deprecation_tests;
+#[cfg(test)]
+#[path = "unit_tests/governance_api_tests.rs"]
+mod governance_api_tests;
+#[cfg(test)]
+#[path = "unit_tests/openrpc_tests.rs"]
+mod openrpc_tests;
+#[cfg(test)]
+#[path = "unit_tests/request_limits_tests.rs"]
+mod request_limits_tests;
 #[cfg(test)]
 #[path = "unit_tests/rpc_server_tests.rs"]
 mod rpc_server_test;
@@ -59,6 +82,48 @@ pub struct JsonRpcServerBuilder {
     module: RpcModule<()>,
     rpc_doc: Project,
     registry: Registry,
+    request_limits: RequestLimits,
+    features: BTreeMap<RpcFeature, bool>,
+}
+
+/// The optional features of a node, which the OpenRPC document of the node reports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RpcFeature {
+    /// The methods served from the indexes of the node.
+    Indexer,
+    /// The subscriptions, served over websocket.
+    Subscriptions,
+    /// The execution of transactions, served by nodes running a transaction orchestrator.
+    TransactionExecution,
+}
+
+impl RpcFeature {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RpcFeature::Indexer => "indexer",
+            RpcFeature::Subscriptions => "subscriptions",
+            RpcFeature::TransactionExecution => "transactionExecution",
+        }
+    }
+
+    /// The methods served only when the feature is enabled.
+    pub fn methods(&self) -> &'static [&'static str] {
+        match self {
+            RpcFeature::Indexer => &[
+                "suix_getDynamicFields",
+                "suix_getOwnedObjects",
+                "suix_queryEvents",
+                "suix_queryTransactionBlocks",
+            ],
+            RpcFeature::Subscriptions => &["suix_subscribeEvent"],
+            RpcFeature::TransactionExecution => &[
+                "sui_devInspectTransactionBlock",
+                "sui_dryRunTransactionBlock",
+                "sui_estimateGasBudget",
+                "sui_executeTransactionBlock",
+            ],
+        }
+    }
 }
 
 pub fn sui_rpc_doc(version: &str) -> Project {
@@ -80,9 +145,23 @@ impl JsonRpcServerBuilder {
             module: RpcModule::new(()),
             rpc_doc: sui_rpc_doc(version),
             registry: prometheus_registry.clone(),
+            request_limits: RequestLimits::new(None),
+            features: Default::default(),
         }
     }
 
+    /// Limit the requests served by the server. The limits can be updated while the server is
+    /// running.
+    pub fn set_request_limits(&mut self, request_limits: RequestLimits) {
+        self.request_limits = request_limits;
+    }
+
+    /// Records whether the optional `feature` is enabled on this node. The methods of the
+    /// features which are not enabled are left out of the OpenRPC document of the node.
+    pub fn set_feature(&mut self, feature: RpcFeature, enabled: bool) {
+        self.features.insert(feature, enabled);
+    }
+
     pub fn register_module<T: SuiRpcModule>(&mut self, module: T) -> Result<(), Error> {
         self.rpc_doc.add_module(T::rpc_doc_module());
         Ok(self.module.merge(module.rpc())?)
@@ -103,8 +182,8 @@ impl JsonRpcServerBuilder {
         info!(?acl);
 
         let cors = CorsLayer::new()
-            // Allow `POST` when accessing the resource
-            .allow_methods([Method::POST])
+            // Allow `POST` when accessing the resource, and `GET` for the OpenRPC document
+            .allow_methods([Method::POST, Method::GET])
             // Allow requests from any origin
             .allow_origin(acl)
             .allow_headers([
@@ -113,9 +192,24 @@ impl JsonRpcServerBuilder {
                 HeaderName::from_static(CLIENT_SDK_VERSION_HEADER),
                 HeaderName::from_static(CLIENT_TARGET_API_VERSION_HEADER),
                 HeaderName::from_static(APP_NAME_HEADER),
+                HeaderName::from_static(TRACEPARENT_HEADER),
+                HeaderName::from_static(TRACESTATE_HEADER),
             ]);
 
+        for (feature, enabled) in &self.features {
+            self.rpc_doc.set_feature(feature.name(), *enabled);
+            if !enabled {
+                self.rpc_doc.remove_methods(feature.methods());
+            }
+        }
+        let openrpc_layer = OpenRpcLayer::new(&self.rpc_doc).map_err(anyhow::Error::from)?;
+
         let routing = self.rpc_doc.method_routing.clone();
+        let deprecated = self
+            .rpc_doc
+            .deprecated_methods()
+            .map(str::to_string)
+            .collect();
 
         self.module
             .register_method("rpc.discover", move |_, _| Ok(self.rpc_doc.clone()))?;
@@ -147,9 +241,46 @@ impl JsonRpcServerBuilder {
         // We need to use the routing layer to block access to the old methods when routing is disabled.
         let routing_layer = RoutingLayer::new(routing, disable_routing);
 
+        // Former names of renamed methods are served by the renamed methods, unless a method is
+        // still registered under the former name.
+        let aliases = DEPRECATED_METHOD_ALIASES
+            .iter()
+            .filter(|(name, route_to)| {
+                !methods_names.contains(name) && methods_names.contains(route_to)
+            })
+            .map(|(name, route_to)| (name.to_string(), route_to.to_string()))
+            .collect();
+        let disable_aliases = env::var("DISABLE_DEPRECATED_METHOD_ALIASES")
+            .ok()
+            .and_then(|v| bool::from_str(&v).ok())
+            .unwrap_or_default();
+        info!(
+            "Deprecated method aliases {}.",
+            if disable_aliases {
+                "disabled"
+            } else {
+                "enabled"
+            }
+        );
+        let deprecation_layer = DeprecationLayer::new(DeprecatedMethods::new(
+            &self.registry,
+            aliases,
+            deprecated,
+            disable_aliases,
+        ));
+
         let middleware = tower::ServiceBuilder::new()
+            .layer(TraceContextLayer)
             .layer(cors)
-            .layer(routing_layer);
+            .layer(openrpc_layer)
+            .layer(RequestLimitsLayer::new(self.request_limits.clone()))
+            .layer(deprecation_layer)
+            .layer(routing_layer)
+            // After the layers renaming methods, to limit the methods actually served.
+            .layer(MethodLimitsLayer::new(
+                self.request_limits.clone(),
+                &self.registry,
+            ));
 
         let server = ServerBuilder::default()
             .batch_requests_supported(false)
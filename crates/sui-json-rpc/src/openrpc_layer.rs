@@ -0,0 +1,76 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use hyper::{Body, Method, Request, Response, StatusCode};
+use jsonrpsee::core::__reexports::serde_json;
+use tower::{Layer, Service};
+
+use sui_open_rpc::Project;
+
+/// The path the OpenRPC document of the server is served at.
+pub const OPENRPC_DOC_PATH: &str = "/api/openrpc.json";
+
+/// Serves the OpenRPC document of the server, as generated for the modules and features enabled
+/// on this node, to `GET` requests of [OPENRPC_DOC_PATH].
+#[derive(Clone)]
+pub struct OpenRpcLayer {
+    doc: Arc<Vec<u8>>,
+}
+
+impl OpenRpcLayer {
+    pub fn new(doc: &Project) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            doc: Arc::new(serde_json::to_vec(doc)?),
+        })
+    }
+}
+
+impl<S> Layer<S> for OpenRpcLayer {
+    type Service = OpenRpcService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        OpenRpcService {
+            inner,
+            doc: self.doc.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct OpenRpcService<S> {
+    inner: S,
+    doc: Arc<Vec<u8>>,
+}
+
+impl<S> Service<Request<Body>> for OpenRpcService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        if req.method() == Method::GET && req.uri().path() == OPENRPC_DOC_PATH {
+            let response = Response::builder()
+                .status(StatusCode::OK)
+                .header(hyper::header::CONTENT_TYPE, "application/json")
+                .body(Body::from(self.doc.as_ref().clone()))
+                .expect("Unable to build response");
+            Box::pin(async move { Ok(response) })
+        } else {
+            Box::pin(self.inner.call(req))
+        }
+    }
+}
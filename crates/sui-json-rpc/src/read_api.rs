@@ -21,12 +21,14 @@ use tap::TapFallible;
 use tracing::{debug, error, warn};
 
 use shared_crypto::intent::{AppId, Intent, IntentMessage, IntentScope, IntentVersion};
+use sui_archival::ArchiveFallback;
 use sui_core::authority::AuthorityState;
 use sui_json_rpc_types::{
     BalanceChange, Checkpoint, CheckpointId, CheckpointPage, DisplayFieldsResponse, EventFilter,
-    ObjectChange, SuiEvent, SuiGetPastObjectRequest, SuiMoveStruct, SuiMoveValue,
-    SuiObjectDataOptions, SuiObjectResponse, SuiPastObjectResponse, SuiTransactionBlock,
-    SuiTransactionBlockEvents, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    ObjectChange, SuiCheckpointCertificate, SuiEvent, SuiGetPastObjectRequest, SuiMoveStruct,
+    SuiMoveValue, SuiObjectDataOptions, SuiObjectLockStatus, SuiObjectProof, SuiObjectResponse,
+    SuiObjectVersionsDiff, SuiPastObjectResponse, SuiTransactionBlock, SuiTransactionBlockEvents,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions, SuiTransactionProof,
 };
 use sui_open_rpc::Module;
 use sui_types::base_types::{ObjectID, SequenceNumber, TransactionDigest};
@@ -59,6 +61,8 @@ const MAX_DISPLAY_NESTED_LEVEL: usize = 10;
 #[derive(Clone)]
 pub struct ReadApi {
     pub state: Arc<AuthorityState>,
+    /// Archive serving the transactions the node does not hold.
+    archive_fallback: Option<Arc<ArchiveFallback>>,
 }
 
 // Internal data structure to make it easy to work with data returned from
@@ -87,8 +91,58 @@ impl IntermediateTransactionResponse {
 }
 
 impl ReadApi {
-    pub fn new(state: Arc<AuthorityState>) -> Self {
-        Self { state }
+    pub fn new(state: Arc<AuthorityState>, archive_fallback: Option<Arc<ArchiveFallback>>) -> Self {
+        Self {
+            state,
+            archive_fallback,
+        }
+    }
+
+    /// Reads a transaction block the node does not hold from its archive fallback. Only the
+    /// transaction, its effects and its checkpoint are read from the archive, and they are only
+    /// served once checked against the certified summary of the checkpoint held by the node.
+    async fn get_archived_transaction_block(
+        &self,
+        digest: TransactionDigest,
+        opts: &SuiTransactionBlockResponseOptions,
+    ) -> Result<Option<SuiTransactionBlockResponse>, Error> {
+        let Some(archive_fallback) = &self.archive_fallback else {
+            return Ok(None);
+        };
+        let checkpoint = self
+            .state
+            .get_transaction_checkpoint_sequence(&digest)?
+            .map(|(_, seq)| seq);
+        let Some(archived) = archive_fallback.get_transaction(&digest, checkpoint).await? else {
+            return Ok(None);
+        };
+        let summary = self
+            .state
+            .get_verified_checkpoint_by_sequence_number(archived.checkpoint)?;
+        archived.verify(summary.data())?;
+
+        let mut temp_response = IntermediateTransactionResponse::new(digest);
+        temp_response.checkpoint_seq = Some(archived.checkpoint);
+        if opts.require_input() {
+            // The transaction is part of the certified contents of its checkpoint.
+            temp_response.transaction =
+                Some(VerifiedTransaction::new_unchecked(archived.transaction));
+        }
+        if opts.require_effects() {
+            temp_response.effects = Some(archived.effects);
+        }
+        if opts.show_events || opts.show_balance_changes || opts.show_object_changes {
+            temp_response.errors.push(
+                "Events, balance changes and object changes are not read from the archive"
+                    .to_string(),
+            );
+        }
+        let epoch_store = self.state.load_epoch_store_one_call_per_task();
+        Ok(Some(convert_to_response(
+            temp_response,
+            opts,
+            epoch_store.module_cache(),
+        )))
     }
 
     fn get_checkpoint_internal(&self, id: CheckpointId) -> Result<Checkpoint, Error> {
@@ -523,6 +577,62 @@ impl ReadApiServer for ReadApi {
         }
     }
 
+    async fn diff_object_versions(
+        &self,
+        object_id: ObjectID,
+        from_version: SequenceNumber,
+        to_version: SequenceNumber,
+    ) -> RpcResult<SuiObjectVersionsDiff> {
+        let (object_type, from) =
+            get_past_move_struct(&self.state, object_id, from_version).await?;
+        let (to_type, to) = get_past_move_struct(&self.state, object_id, to_version).await?;
+        if object_type != to_type {
+            return Err(anyhow!(
+                "Object {object_id} has type {object_type} at version {from_version} and {to_type} at version {to_version}"
+            )
+            .into());
+        }
+        Ok(SuiObjectVersionsDiff::new(
+            object_id,
+            object_type,
+            from_version,
+            from,
+            to_version,
+            to,
+        ))
+    }
+
+    fn get_object_lock_status(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> RpcResult<SuiObjectLockStatus> {
+        Ok(self
+            .state
+            .get_object_lock_status(object_id, version)
+            .map_err(Error::from)?)
+    }
+
+    fn get_object_proof(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> RpcResult<SuiObjectProof> {
+        let proof = self
+            .state
+            .get_object_proof(object_id, version)
+            .map_err(Error::from)?;
+        Ok(SuiObjectProof::new(&proof)?)
+    }
+
+    fn get_transaction_proof(&self, digest: TransactionDigest) -> RpcResult<SuiTransactionProof> {
+        let proof = self
+            .state
+            .get_transaction_proof(digest)
+            .map_err(Error::from)?;
+        Ok(SuiTransactionProof::new(&proof)?)
+    }
+
     async fn get_total_transaction_blocks(&self) -> RpcResult<BigInt<u64>> {
         Ok(self.state.get_total_transaction_blocks()?.into())
     }
@@ -536,10 +646,16 @@ impl ReadApiServer for ReadApi {
         let mut temp_response = IntermediateTransactionResponse::new(digest);
 
         // Fetch transaction to determine existence
-        let transaction =
-            Some(self.state.get_executed_transaction(digest).await.tap_err(
-                |err| debug!(tx_digest=?digest, "Failed to get transaction: {:?}", err),
-            )?);
+        let transaction = match self.state.get_executed_transaction(digest).await {
+            Ok(transaction) => Some(transaction),
+            Err(err) => {
+                debug!(tx_digest=?digest, "Failed to get transaction: {:?}", err);
+                if let Some(response) = self.get_archived_transaction_block(digest, &opts).await? {
+                    return Ok(response);
+                }
+                return Err(Error::from(err).into());
+            }
+        };
 
         // the input is needed for object_changes to retrieve the sender address.
         if opts.require_input() {
@@ -690,6 +806,18 @@ impl ReadApiServer for ReadApi {
         Ok(self.get_checkpoint_internal(id)?)
     }
 
+    fn get_checkpoint_certificate(&self, id: CheckpointId) -> RpcResult<SuiCheckpointCertificate> {
+        let checkpoint = match id {
+            CheckpointId::SequenceNumber(seq) => {
+                self.state.get_verified_checkpoint_by_sequence_number(seq)?
+            }
+            CheckpointId::Digest(digest) => self
+                .state
+                .get_verified_checkpoint_summary_by_digest(digest)?,
+        };
+        Ok(SuiCheckpointCertificate::new(checkpoint.inner())?)
+    }
+
     fn get_checkpoints(
         &self,
         // If `Some`, the query will start from the next item after the specified cursor
@@ -719,6 +847,7 @@ impl ReadApiServer for ReadApi {
             data,
             next_cursor,
             has_next_page,
+            truncated_due_to_limit: false,
         })
     }
 }
@@ -809,6 +938,26 @@ fn get_object_type_and_struct(
     }
 }
 
+async fn get_past_move_struct(
+    state: &AuthorityState,
+    object_id: ObjectID,
+    version: SequenceNumber,
+) -> RpcResult<(StructTag, SuiMoveStruct)> {
+    match state
+        .get_past_object_read(&object_id, version)
+        .await
+        .map_err(Error::from)?
+    {
+        PastObjectRead::VersionFound(_, o, layout) => {
+            let Some((object_type, move_struct)) = get_object_type_and_struct(&o, &layout)? else {
+                return Err(anyhow!("Object {object_id} is a package, not a Move object").into());
+            };
+            Ok((object_type, move_struct.into()))
+        }
+        read => Err(anyhow!("Object {object_id} not found at version {version}: {read:?}").into()),
+    }
+}
+
 fn get_move_struct(o: &Object, layout: &Option<MoveStructLayout>) -> RpcResult<MoveStruct> {
     let layout = layout
         .as_ref()
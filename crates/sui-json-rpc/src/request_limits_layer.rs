@@ -0,0 +1,542 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use hyper::{Body, Method, Request, Response, StatusCode};
+use jsonrpsee::core::__reexports::serde_json;
+use jsonrpsee::core::error::GenericTransportError;
+use jsonrpsee::core::http_helpers::read_body;
+use jsonrpsee::types::Request as RpcRequest;
+use prometheus::{
+    register_histogram_vec_with_registry, register_int_counter_vec_with_registry,
+    register_int_gauge_vec_with_registry, HistogramVec, IntCounterVec, IntGaugeVec, Registry,
+};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+use tower::{Layer, Service};
+
+use crate::deprecation_layer::DEPRECATED_METHOD_ALIASES;
+use crate::routing_layer::{is_json, response};
+use crate::MAX_REQUEST_SIZE;
+
+/// The methods served in the execution lane of [RequestPriority::Execution].
+pub const EXECUTION_LANE_METHODS: [&str; 2] =
+    ["sui_executeTransactionBlock", "sui_dryRunTransactionBlock"];
+
+const QUEUE_DELAY_SEC_BUCKETS: &[f64] = &[
+    0.001, 0.005, 0.01, 0.05, 0.1, 0.25, 0.5, 1., 2.5, 5., 10., 20., 30., 60.,
+];
+
+/// Limits of the requests served by the JSON-RPC server, which can be updated while the server
+/// is running.
+#[derive(Debug, Clone)]
+pub struct RequestLimits {
+    inner: Arc<RequestLimitsInner>,
+}
+
+#[derive(Debug)]
+struct RequestLimitsInner {
+    max_concurrent_requests: AtomicUsize,
+    concurrent_requests: AtomicUsize,
+    execution_lane_size: AtomicUsize,
+    execution_lane_requests: AtomicUsize,
+    max_response_size: AtomicUsize,
+    method_limits: RwLock<HashMap<String, MethodLimit>>,
+    method_queue_timeout_ms: AtomicU64,
+}
+
+#[derive(Debug)]
+struct MethodLimit {
+    max_concurrent_requests: usize,
+    semaphore: Arc<Semaphore>,
+}
+
+impl RequestLimits {
+    /// No limit if `max_concurrent_requests` is None.
+    pub fn new(max_concurrent_requests: Option<usize>) -> Self {
+        Self {
+            inner: Arc::new(RequestLimitsInner {
+                max_concurrent_requests: AtomicUsize::new(
+                    max_concurrent_requests.unwrap_or(usize::MAX),
+                ),
+                concurrent_requests: AtomicUsize::new(0),
+                execution_lane_size: AtomicUsize::new(0),
+                execution_lane_requests: AtomicUsize::new(0),
+                max_response_size: AtomicUsize::new(usize::MAX),
+                method_limits: Default::default(),
+                method_queue_timeout_ms: AtomicU64::new(u64::MAX),
+            }),
+        }
+    }
+
+    /// Update the maximum number of requests served concurrently. Requests being served are not
+    /// affected, so the number of concurrent requests may exceed a lower limit for a while.
+    pub fn set_max_concurrent_requests(&self, max_concurrent_requests: Option<usize>) {
+        self.inner.max_concurrent_requests.store(
+            max_concurrent_requests.unwrap_or(usize::MAX),
+            Ordering::Relaxed,
+        );
+    }
+
+    pub fn max_concurrent_requests(&self) -> Option<usize> {
+        match self.inner.max_concurrent_requests.load(Ordering::Relaxed) {
+            usize::MAX => None,
+            max => Some(max),
+        }
+    }
+
+    /// Update the number of concurrent requests reserved to the methods of the execution lane,
+    /// served in addition to the maximum number of concurrent requests, so that reads using up
+    /// that limit cannot delay the submission of transactions. The requests of the execution
+    /// lane beyond its size share the maximum number of concurrent requests with the other
+    /// methods.
+    pub fn set_execution_lane_size(&self, execution_lane_size: usize) {
+        self.inner
+            .execution_lane_size
+            .store(execution_lane_size, Ordering::Relaxed);
+    }
+
+    pub fn execution_lane_size(&self) -> usize {
+        self.inner.execution_lane_size.load(Ordering::Relaxed)
+    }
+
+    /// Update the size, in bytes of JSON, beyond which the pages of results returned by the
+    /// paginated methods are truncated. No limit if `max_response_size` is None.
+    pub fn set_max_response_size(&self, max_response_size: Option<usize>) {
+        self.inner
+            .max_response_size
+            .store(max_response_size.unwrap_or(usize::MAX), Ordering::Relaxed);
+    }
+
+    pub fn max_response_size(&self) -> Option<usize> {
+        match self.inner.max_response_size.load(Ordering::Relaxed) {
+            usize::MAX => None,
+            max => Some(max),
+        }
+    }
+
+    /// Update the maximum number of concurrent requests of each method in `limits`, the requests
+    /// of the other methods are only limited by the maximum number of concurrent requests. As
+    /// with that limit, the requests being served are not affected.
+    pub fn set_method_concurrency_limits(&self, limits: &BTreeMap<String, usize>) {
+        let mut method_limits = self.inner.method_limits.write().unwrap();
+        method_limits
+            .retain(|method, limit| limits.get(method) == Some(&limit.max_concurrent_requests));
+        for (method, max) in limits {
+            method_limits
+                .entry(method.clone())
+                .or_insert_with(|| MethodLimit {
+                    max_concurrent_requests: *max,
+                    semaphore: Arc::new(Semaphore::new(*max)),
+                });
+        }
+    }
+
+    pub fn method_concurrency_limits(&self) -> BTreeMap<String, usize> {
+        self.inner
+            .method_limits
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(method, limit)| (method.clone(), limit.max_concurrent_requests))
+            .collect()
+    }
+
+    /// Update how long requests wait for the concurrency limit of their method before being
+    /// rejected. Requests wait until served if `timeout` is None.
+    pub fn set_method_queue_timeout(&self, timeout: Option<Duration>) {
+        self.inner.method_queue_timeout_ms.store(
+            timeout.map_or(u64::MAX, |timeout| timeout.as_millis() as u64),
+            Ordering::Relaxed,
+        );
+    }
+
+    pub fn method_queue_timeout(&self) -> Option<Duration> {
+        match self.inner.method_queue_timeout_ms.load(Ordering::Relaxed) {
+            u64::MAX => None,
+            ms => Some(Duration::from_millis(ms)),
+        }
+    }
+
+    fn has_method_limits(&self) -> bool {
+        !self.inner.method_limits.read().unwrap().is_empty()
+    }
+
+    fn method_semaphore(&self, method: &str) -> Option<Arc<Semaphore>> {
+        self.inner
+            .method_limits
+            .read()
+            .unwrap()
+            .get(method)
+            .map(|limit| limit.semaphore.clone())
+    }
+
+    fn try_acquire(&self, priority: RequestPriority) -> Option<ConcurrentRequestGuard> {
+        let lane = match priority {
+            RequestPriority::Execution => {
+                let size = self.inner.execution_lane_size.load(Ordering::Relaxed);
+                try_increment(&self.inner.execution_lane_requests, size).then_some(Lane::Execution)
+            }
+            RequestPriority::Read => None,
+        };
+        let lane = lane.or_else(|| {
+            let max = self.inner.max_concurrent_requests.load(Ordering::Relaxed);
+            try_increment(&self.inner.concurrent_requests, max).then_some(Lane::Shared)
+        })?;
+        Some(ConcurrentRequestGuard {
+            limits: self.inner.clone(),
+            lane,
+        })
+    }
+}
+
+/// Increments `counter` if it is below `max`.
+fn try_increment(counter: &AtomicUsize, max: usize) -> bool {
+    counter
+        .fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| {
+            (current < max).then_some(current + 1)
+        })
+        .is_ok()
+}
+
+/// The priority of the requests of a method, of which the requests of the methods submitting
+/// transactions are served in their own lane.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RequestPriority {
+    /// The methods of [EXECUTION_LANE_METHODS], and their former names.
+    Execution,
+    Read,
+}
+
+impl RequestPriority {
+    pub fn of_method(method: &str) -> Self {
+        let method = DEPRECATED_METHOD_ALIASES
+            .iter()
+            .find(|(name, _)| *name == method)
+            .map_or(method, |(_, route_to)| *route_to);
+        if EXECUTION_LANE_METHODS.contains(&method) {
+            RequestPriority::Execution
+        } else {
+            RequestPriority::Read
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Lane {
+    Execution,
+    Shared,
+}
+
+struct ConcurrentRequestGuard {
+    limits: Arc<RequestLimitsInner>,
+    lane: Lane,
+}
+
+impl Drop for ConcurrentRequestGuard {
+    fn drop(&mut self) {
+        let requests = match self.lane {
+            Lane::Execution => &self.limits.execution_lane_requests,
+            Lane::Shared => &self.limits.concurrent_requests,
+        };
+        requests.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Rejects requests with `429 Too Many Requests` when the server is already serving the maximum
+/// number of concurrent requests of its [RequestLimits]. When an execution lane is configured,
+/// the method of the requests is read from their body to serve the requests of the execution
+/// methods in the lane.
+#[derive(Debug, Clone)]
+pub struct RequestLimitsLayer {
+    limits: RequestLimits,
+}
+
+impl RequestLimitsLayer {
+    pub fn new(limits: RequestLimits) -> Self {
+        Self { limits }
+    }
+}
+
+impl<S> Layer<S> for RequestLimitsLayer {
+    type Service = RequestLimitsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestLimitsService {
+            inner,
+            limits: self.limits.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RequestLimitsService<S> {
+    inner: S,
+    limits: RequestLimits,
+}
+
+impl<S> Service<Request<Body>> for RequestLimitsService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Response: 'static,
+    S::Error: Into<Box<dyn Error + Send + Sync>> + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = Box<dyn Error + Send + Sync + 'static>;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        if self.limits.execution_lane_size() == 0 {
+            let Some(guard) = self.limits.try_acquire(RequestPriority::Read) else {
+                return Box::pin(async { Ok(too_many_concurrent_requests()) });
+            };
+            let response = self.inner.call(req);
+            return Box::pin(async move {
+                let response = response.await.map_err(Into::into);
+                drop(guard);
+                response
+            });
+        }
+
+        let clone = self.inner.clone();
+        let limits = self.limits.clone();
+        // take the service that was ready
+        // https://docs.rs/tower/latest/tower/trait.Service.html#be-careful-when-cloning-inner-services
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        Box::pin(async move {
+            let (req, priority) = if req.method() == Method::POST && is_json(&req) {
+                let (parts, body) = req.into_parts();
+                let body = match read_body(&parts.headers, body, MAX_REQUEST_SIZE).await {
+                    Ok((body, _)) => body,
+                    Err(GenericTransportError::TooLarge) => {
+                        return Ok(response::too_large(MAX_REQUEST_SIZE))
+                    }
+                    Err(GenericTransportError::Malformed) => return Ok(response::malformed()),
+                    Err(GenericTransportError::Inner(e)) => {
+                        tracing::error!("Internal error reading request body: {}", e);
+                        return Ok(response::internal_error());
+                    }
+                };
+                let priority = serde_json::from_slice::<RpcRequest>(&body)
+                    .map_or(RequestPriority::Read, |request| {
+                        RequestPriority::of_method(&request.method)
+                    });
+                (Request::from_parts(parts, Body::from(body)), priority)
+            } else {
+                (req, RequestPriority::Read)
+            };
+            let Some(guard) = limits.try_acquire(priority) else {
+                return Ok(too_many_concurrent_requests());
+            };
+            let response = inner.call(req).await.map_err(Into::into);
+            drop(guard);
+            response
+        })
+    }
+}
+
+fn too_many_concurrent_requests() -> Response<Body> {
+    let mut response = Response::new(Body::from("Too many concurrent requests"));
+    *response.status_mut() = StatusCode::TOO_MANY_REQUESTS;
+    response
+}
+
+#[derive(Debug, Clone)]
+struct MethodLimitsMetrics {
+    queued_requests: IntGaugeVec,
+    queue_delay: HistogramVec,
+    rejected_requests: IntCounterVec,
+}
+
+impl MethodLimitsMetrics {
+    fn new(registry: &Registry) -> Self {
+        Self {
+            queued_requests: register_int_gauge_vec_with_registry!(
+                "rpc_method_queued_requests",
+                "Number of requests waiting for the concurrency limit of their method",
+                &["method"],
+                registry,
+            )
+            .unwrap(),
+            queue_delay: register_histogram_vec_with_registry!(
+                "rpc_method_queue_delay",
+                "Time requests waited for the concurrency limit of their method",
+                &["method"],
+                QUEUE_DELAY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            rejected_requests: register_int_counter_vec_with_registry!(
+                "rpc_method_rejected_requests",
+                "Number of requests rejected after waiting for the concurrency limit of their \
+                 method for the queue timeout",
+                &["method"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+/// Serves at most the configured number of concurrent requests of each method of
+/// [RequestLimits::set_method_concurrency_limits], so that the requests of expensive methods
+/// cannot starve the others. The requests beyond the limit of their method wait for the
+/// requests being served, and are rejected with `429 Too Many Requests` after the queue
+/// timeout. Only the methods with a limit are labelled in the metrics.
+#[derive(Clone)]
+pub struct MethodLimitsLayer {
+    limits: RequestLimits,
+    metrics: MethodLimitsMetrics,
+}
+
+impl MethodLimitsLayer {
+    pub fn new(limits: RequestLimits, registry: &Registry) -> Self {
+        Self {
+            limits,
+            metrics: MethodLimitsMetrics::new(registry),
+        }
+    }
+}
+
+impl<S> Layer<S> for MethodLimitsLayer {
+    type Service = MethodLimitsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MethodLimitsService {
+            inner,
+            limits: self.limits.clone(),
+            metrics: self.metrics.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct MethodLimitsService<S> {
+    inner: S,
+    limits: RequestLimits,
+    metrics: MethodLimitsMetrics,
+}
+
+impl<S> Service<Request<Body>> for MethodLimitsService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Response: 'static,
+    S::Error: Into<Box<dyn Error + Send + Sync>> + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = Box<dyn Error + Send + Sync + 'static>;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let clone = self.inner.clone();
+        let limits = self.limits.clone();
+        let metrics = self.metrics.clone();
+        // take the service that was ready
+        // https://docs.rs/tower/latest/tower/trait.Service.html#be-careful-when-cloning-inner-services
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        Box::pin(async move {
+            if !limits.has_method_limits() || req.method() != Method::POST || !is_json(&req) {
+                return inner.call(req).await.map_err(Into::into);
+            }
+            let (parts, body) = req.into_parts();
+            let body = match read_body(&parts.headers, body, MAX_REQUEST_SIZE).await {
+                Ok((body, _)) => body,
+                Err(GenericTransportError::TooLarge) => {
+                    return Ok(response::too_large(MAX_REQUEST_SIZE))
+                }
+                Err(GenericTransportError::Malformed) => return Ok(response::malformed()),
+                Err(GenericTransportError::Inner(e)) => {
+                    tracing::error!("Internal error reading request body: {}", e);
+                    return Ok(response::internal_error());
+                }
+            };
+            // The server does not support batched requests, which are left to it to reject.
+            let permit = match serde_json::from_slice::<RpcRequest>(&body) {
+                Ok(request) => match limits.method_semaphore(&request.method) {
+                    Some(semaphore) => {
+                        let method = request.method.as_ref();
+                        match acquire(semaphore, &limits, &metrics, method).await {
+                            Some(permit) => Some(permit),
+                            None => return Ok(too_many_requests(method)),
+                        }
+                    }
+                    None => None,
+                },
+                Err(_) => None,
+            };
+            let response = inner
+                .call(Request::from_parts(parts, Body::from(body)))
+                .await
+                .map_err(Into::into);
+            drop(permit);
+            response
+        })
+    }
+}
+
+/// Waits for a permit of `semaphore` for at most the queue timeout of `limits`.
+async fn acquire(
+    semaphore: Arc<Semaphore>,
+    limits: &RequestLimits,
+    metrics: &MethodLimitsMetrics,
+    method: &str,
+) -> Option<OwnedSemaphorePermit> {
+    if let Ok(permit) = semaphore.clone().try_acquire_owned() {
+        metrics
+            .queue_delay
+            .with_label_values(&[method])
+            .observe(0.0);
+        return Some(permit);
+    }
+
+    let queued = metrics.queued_requests.with_label_values(&[method]);
+    queued.inc();
+    let start = Instant::now();
+    let permit = match limits.method_queue_timeout() {
+        Some(timeout) => tokio::time::timeout(timeout, semaphore.acquire_owned())
+            .await
+            .ok(),
+        None => Some(semaphore.acquire_owned().await),
+    };
+    queued.dec();
+    metrics
+        .queue_delay
+        .with_label_values(&[method])
+        .observe(start.elapsed().as_secs_f64());
+    // The semaphores are never closed.
+    let permit = permit.and_then(Result::ok);
+    if permit.is_none() {
+        metrics.rejected_requests.with_label_values(&[method]).inc();
+    }
+    permit
+}
+
+fn too_many_requests(method: &str) -> Response<Body> {
+    let mut response = Response::new(Body::from(format!(
+        "Too many concurrent requests of {method}"
+    )));
+    *response.status_mut() = StatusCode::TOO_MANY_REQUESTS;
+    response
+}
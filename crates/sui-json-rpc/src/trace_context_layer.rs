@@ -0,0 +1,66 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::task::{Context, Poll};
+
+use hyper::{HeaderMap, Request};
+use telemetry_subscribers::trace_context::{
+    set_remote_parent, TraceParent, TRACEPARENT_HEADER, TRACESTATE_HEADER,
+};
+use tower::{Layer, Service};
+use tracing::instrument::Instrumented;
+use tracing::{field, Instrument, Span};
+
+/// Runs each request in a `json_rpc_request` span, which continues the trace of the caller if
+/// the request has a W3C `traceparent` header. The spans of the request handlers (e.g. the
+/// transaction orchestrator or the quorum driver) are its children.
+///
+/// Websocket connections are served from a separate task, so only http requests are traced.
+#[derive(Debug, Clone, Default)]
+pub struct TraceContextLayer;
+
+impl<S> Layer<S> for TraceContextLayer {
+    type Service = TraceContextService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TraceContextService { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TraceContextService<S> {
+    inner: S,
+}
+
+impl<S, B> Service<Request<B>> for TraceContextService<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Instrumented<S::Future>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let span = request_span(req.headers());
+        let _guard = span.enter();
+        self.inner.call(req).instrument(span.clone())
+    }
+}
+
+fn request_span(headers: &HeaderMap) -> Span {
+    let span = tracing::info_span!("json_rpc_request", trace_id = field::Empty);
+    let traceparent = headers
+        .get(TRACEPARENT_HEADER)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.parse::<TraceParent>().ok());
+    if let Some(traceparent) = traceparent {
+        let tracestate = headers.get(TRACESTATE_HEADER).and_then(|h| h.to_str().ok());
+        set_remote_parent(&span, &traceparent, tracestate);
+    }
+    span
+}
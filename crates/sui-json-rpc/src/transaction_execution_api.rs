@@ -16,20 +16,23 @@ use sui_core::authority::AuthorityState;
 use sui_core::authority_client::NetworkAuthorityClient;
 use sui_core::transaction_orchestrator::TransactiondOrchestrator;
 use sui_json_rpc_types::{
-    DevInspectResults, DryRunTransactionBlockResponse, SuiTransactionBlock,
-    SuiTransactionBlockData, SuiTransactionBlockEvents, SuiTransactionBlockResponse,
-    SuiTransactionBlockResponseOptions,
+    DevInspectResults, DryRunTransactionBlockResponse, GasBudgetEstimate, GasBudgetSafetyMargin,
+    SuiEffectsCertificate, SuiTransactionBlock, SuiTransactionBlockData, SuiTransactionBlockEvents,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
 use sui_open_rpc::Module;
-use sui_types::base_types::SuiAddress;
+use sui_types::base_types::{SuiAddress, TransactionDigest};
+use sui_types::crypto::default_hash;
+use sui_types::gas::SuiCostTable;
 use sui_types::messages::{
-    ExecuteTransactionRequest, ExecuteTransactionRequestType, TransactionEffectsAPI,
-    TransactionKind,
+    EffectsFinalityInfo, ExecuteTransactionRequest, ExecuteTransactionRequestType, GasData,
+    TransactionEffectsAPI, TransactionKind,
 };
 use sui_types::messages::{ExecuteTransactionResponse, Transaction};
 use sui_types::messages::{TransactionData, TransactionDataAPI};
 use sui_types::signature::GenericSignature;
 use sui_types::sui_serde::BigInt;
+use tracing::Instrument;
 
 use crate::api::WriteApiServer;
 use crate::error::Error;
@@ -90,12 +93,12 @@ impl TransactionExecutionApi {
         let digest = *txn.digest();
 
         let transaction_orchestrator = self.transaction_orchestrator.clone();
-        let response = spawn_monitored_task!(transaction_orchestrator.execute_transaction_block(
-            ExecuteTransactionRequest {
+        let response = spawn_monitored_task!(transaction_orchestrator
+            .execute_transaction_block(ExecuteTransactionRequest {
                 transaction: txn,
                 request_type,
-            }
-        ))
+            })
+            .in_current_span())
         .await??;
 
         match response {
@@ -137,6 +140,19 @@ impl TransactionExecutionApi {
                     None
                 };
 
+                // Effects finalized by a checkpoint carry the checkpoint rather than signatures.
+                let effects_certificate = match &effects.finality_info {
+                    EffectsFinalityInfo::Certified(auth_sign_info)
+                        if opts.show_effects_certificate =>
+                    {
+                        Some(SuiEffectsCertificate::new(
+                            &effects.effects,
+                            auth_sign_info,
+                        )?)
+                    }
+                    _ => None,
+                };
+
                 Ok(SuiTransactionBlockResponse {
                     digest,
                     transaction: opts.show_input.then_some(tx),
@@ -149,6 +165,7 @@ impl TransactionExecutionApi {
                     confirmed_local_execution: Some(is_executed_locally),
                     checkpoint: None,
                     errors: vec![],
+                    effects_certificate,
                 })
             }
         }
@@ -165,11 +182,12 @@ impl TransactionExecutionApi {
             .module_cache()
             .clone();
         let input = SuiTransactionBlockData::try_from(txn_data.clone(), &module_cache)?;
-        let (resp, written_objects, transaction_effects) = self
+        let (resp, temporary_store, transaction_effects) = self
             .state
             .dry_exec_transaction(txn_data.clone(), txn_digest)
             .await?;
-        let object_cache = ObjectProviderCache::new_with_cache(self.state.clone(), written_objects);
+        let object_cache =
+            ObjectProviderCache::new_with_temporary_store(self.state.clone(), temporary_store);
         let balance_changes =
             get_balance_changes_from_effect(&object_cache, &transaction_effects).await?;
         let object_changes = get_object_changes(
@@ -189,6 +207,56 @@ impl TransactionExecutionApi {
             input,
         })
     }
+
+    async fn estimate_gas_budget(
+        &self,
+        tx_bytes: Base64,
+        safety_margin: Option<GasBudgetSafetyMargin>,
+    ) -> Result<GasBudgetEstimate, Error> {
+        let (txn_data, _) = get_transaction_data_and_digest(tx_bytes)?;
+        let epoch_store = self.state.load_epoch_store_one_call_per_task();
+        let cost_table = SuiCostTable::new(epoch_store.protocol_config());
+        let gas_price = txn_data.gas_price().max(epoch_store.reference_gas_price());
+
+        // Dry run with the maximum budget, paid by a mock gas coin of the sender, so that
+        // the estimate does not depend on the budget or gas coins of the transaction.
+        let sender = txn_data.sender();
+        let uses_shared_objects = txn_data.contains_shared_object();
+        let dry_run_data = TransactionData::new_with_gas_data(
+            txn_data.into_kind(),
+            sender,
+            GasData {
+                payment: vec![],
+                owner: sender,
+                price: gas_price,
+                budget: cost_table.max_gas_budget(),
+            },
+        );
+        let dry_run_digest = TransactionDigest::new(default_hash(&dry_run_data));
+        let (_, _, effects) = self
+            .state
+            .dry_exec_transaction(dry_run_data, dry_run_digest)
+            .await?;
+        if !effects.status().is_ok() {
+            return Err(anyhow!(
+                "Cannot estimate gas budget, transaction failed in dry run: {:?}",
+                effects.status()
+            )
+            .into());
+        }
+
+        let breakdown = safety_margin
+            .unwrap_or_default()
+            .apply(effects.gas_cost_summary().clone(), uses_shared_objects);
+        let gas_budget = breakdown
+            .gas_budget()
+            .clamp(cost_table.min_gas_budget(), cost_table.max_gas_budget());
+        Ok(GasBudgetEstimate {
+            gas_budget,
+            gas_price,
+            breakdown,
+        })
+    }
 }
 
 #[async_trait]
@@ -211,13 +279,25 @@ impl WriteApiServer for TransactionExecutionApi {
         tx_bytes: Base64,
         gas_price: Option<BigInt<u64>>,
         _epoch: Option<BigInt<u64>>,
+        clock_timestamp_ms: Option<BigInt<u64>>,
     ) -> RpcResult<DevInspectResults> {
         let tx_kind: TransactionKind =
             bcs::from_bytes(&tx_bytes.to_vec().map_err(|e| anyhow!(e))?).map_err(|e| anyhow!(e))?;
-        Ok(self
+        let (mut results, temporary_store, effects) = self
             .state
-            .dev_inspect_transaction_block(sender_address, tx_kind, gas_price.map(|i| *i))
-            .await?)
+            .dev_inspect_transaction_block_with_store(
+                sender_address,
+                tx_kind,
+                gas_price.map(|i| *i),
+                clock_timestamp_ms.map(|i| *i),
+            )
+            .await?;
+        let object_cache =
+            ObjectProviderCache::new_with_temporary_store(self.state.clone(), temporary_store);
+        results.balance_changes = get_balance_changes_from_effect(&object_cache, &effects)
+            .await
+            .map_err(Error::from)?;
+        Ok(results)
     }
 
     async fn dry_run_transaction_block(
@@ -226,6 +306,14 @@ impl WriteApiServer for TransactionExecutionApi {
     ) -> RpcResult<DryRunTransactionBlockResponse> {
         Ok(self.dry_run_transaction_block(tx_bytes).await?)
     }
+
+    async fn estimate_gas_budget(
+        &self,
+        tx_bytes: Base64,
+        safety_margin: Option<GasBudgetSafetyMargin>,
+    ) -> RpcResult<GasBudgetEstimate> {
+        Ok(self.estimate_gas_budget(tx_bytes, safety_margin).await?)
+    }
 }
 
 impl SuiRpcModule for TransactionExecutionApi {
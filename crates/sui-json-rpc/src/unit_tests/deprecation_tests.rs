@@ -0,0 +1,141 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use hyper::{Body, Request, Response};
+use jsonrpsee::core::__reexports::serde_json;
+use prometheus::Registry;
+use tower::{Layer, Service};
+
+use crate::deprecation_layer::{
+    ClientInfo, DeprecatedMethods, DeprecationLayer, MAX_TRACKED_CLIENTS,
+};
+use crate::{APP_NAME_HEADER, CLIENT_SDK_TYPE_HEADER, CLIENT_SDK_VERSION_HEADER};
+
+/// Responds with the body of the request.
+#[derive(Clone)]
+struct EchoService;
+
+impl Service<Request<Body>> for EchoService {
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        Box::pin(async move { Ok(Response::new(req.into_body())) })
+    }
+}
+
+fn deprecated_methods(disable_aliases: bool) -> DeprecatedMethods {
+    DeprecatedMethods::new(
+        &Registry::new(),
+        HashMap::from([(
+            "sui_getTransaction".to_string(),
+            "sui_getTransactionBlock".to_string(),
+        )]),
+        HashSet::from(["sui_getEvents".to_string()]),
+        disable_aliases,
+    )
+}
+
+fn request(method: &str, app_name: &str) -> Request<Body> {
+    let body = serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": []});
+    Request::post("/")
+        .header(hyper::header::CONTENT_TYPE, "application/json")
+        .header(CLIENT_SDK_TYPE_HEADER, "typescript")
+        .header(CLIENT_SDK_VERSION_HEADER, "0.29.0")
+        .header(APP_NAME_HEADER, app_name)
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn client(app_name: &str) -> ClientInfo {
+    ClientInfo {
+        sdk_type: "typescript".to_string(),
+        sdk_version: "0.29.0".to_string(),
+        app_name: app_name.to_string(),
+    }
+}
+
+async fn served_method(
+    service: &mut impl Service<Request<Body>, Response = Response<Body>>,
+    request: Request<Body>,
+) -> String {
+    let Ok(response) = service.call(request).await else {
+        panic!("Request failed");
+    };
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    request["method"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_deprecated_method_aliases() {
+    let methods = deprecated_methods(false);
+    let mut service = DeprecationLayer::new(methods.clone()).layer(EchoService);
+
+    // The former name of a renamed method is served by the renamed method.
+    assert_eq!(
+        served_method(&mut service, request("sui_getTransaction", "wallet")).await,
+        "sui_getTransactionBlock"
+    );
+    assert_eq!(
+        served_method(&mut service, request("sui_getTransaction", "wallet")).await,
+        "sui_getTransactionBlock"
+    );
+    assert_eq!(
+        served_method(&mut service, request("sui_getEvents", "explorer")).await,
+        "sui_getEvents"
+    );
+    assert_eq!(
+        served_method(&mut service, request("sui_getTransactionBlock", "wallet")).await,
+        "sui_getTransactionBlock"
+    );
+
+    assert_eq!(methods.usage("sui_getTransaction", &client("wallet")), 2);
+    assert_eq!(methods.usage("sui_getTransaction", &client("explorer")), 0);
+    assert_eq!(methods.usage("sui_getEvents", &client("explorer")), 1);
+    assert_eq!(
+        methods.usage("sui_getTransactionBlock", &client("wallet")),
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_disabled_deprecated_method_aliases() {
+    let methods = deprecated_methods(true);
+    let mut service = DeprecationLayer::new(methods.clone()).layer(EchoService);
+
+    // The former names are no longer served, their usage is still counted.
+    assert_eq!(
+        served_method(&mut service, request("sui_getTransaction", "wallet")).await,
+        "sui_getTransaction"
+    );
+    assert_eq!(methods.usage("sui_getTransaction", &client("wallet")), 1);
+}
+
+#[test]
+fn test_tracked_clients_are_bounded() {
+    let methods = deprecated_methods(false);
+    let body = serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "sui_getEvents"});
+    let body = body.to_string();
+    for i in 0..MAX_TRACKED_CLIENTS + 10 {
+        methods.process_single_request(body.as_bytes(), &client(&i.to_string()));
+    }
+    assert_eq!(methods.usage("sui_getEvents", &client("0")), 1);
+    let other = ClientInfo {
+        sdk_type: "other".to_string(),
+        sdk_version: "other".to_string(),
+        app_name: "other".to_string(),
+    };
+    assert_eq!(methods.usage("sui_getEvents", &other), 10);
+}
@@ -0,0 +1,121 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_types::base_types::SuiAddress;
+use sui_types::digests::TransactionDigest;
+use sui_types::event::EventID;
+use sui_types::sui_system_state::ValidatorEpochInfoEventV2;
+
+use crate::error::Error;
+use crate::governance_api::scan_validator_epoch_outcomes;
+
+type OutcomeEvents = Vec<(EventID, ValidatorEpochInfoEventV2)>;
+
+/// One epoch change event per epoch and validator, ordered by epoch then validator.
+fn epoch_change_events(validators: &[SuiAddress], epochs: u64) -> OutcomeEvents {
+    let tx_digest = TransactionDigest::random();
+    (1..=epochs)
+        .flat_map(|epoch| validators.iter().map(move |v| (epoch, *v)))
+        .enumerate()
+        .map(|(seq, (epoch, validator_address))| {
+            let id = EventID {
+                tx_digest,
+                event_seq: seq as u64,
+            };
+            let event = ValidatorEpochInfoEventV2 {
+                epoch,
+                validator_address,
+                reference_gas_survey_quote: 1,
+                stake: 1,
+                voting_power: 1,
+                commission_rate: 0,
+                pool_staking_reward: 1,
+                storage_fund_staking_reward: 1,
+                pool_token_exchange_rate: Default::default(),
+                tallying_rule_reporters: vec![],
+                tallying_rule_global_score: 1,
+            };
+            (id, event)
+        })
+        .collect()
+}
+
+/// Serves `events` in batches after the cursor, like the event store.
+fn query_events(
+    events: &[(EventID, ValidatorEpochInfoEventV2)],
+) -> impl FnMut(Option<EventID>, usize) -> Result<OutcomeEvents, Error> + '_ {
+    move |cursor, batch_size| {
+        let start = cursor.map_or(0, |cursor| cursor.event_seq as usize + 1);
+        Ok(events
+            .iter()
+            .skip(start)
+            .take(batch_size)
+            .cloned()
+            .collect())
+    }
+}
+
+#[test]
+fn test_scan_validator_epoch_outcomes() {
+    let validators = [
+        SuiAddress::random_for_testing_only(),
+        SuiAddress::random_for_testing_only(),
+    ];
+    let events = epoch_change_events(&validators, 3);
+
+    // All the outcomes fit in a page.
+    let page = scan_validator_epoch_outcomes(None, None, 10, 100, query_events(&events)).unwrap();
+    assert_eq!(page.data.len(), 6);
+    assert!(!page.has_next_page);
+    assert!(!page.truncated_due_to_limit);
+
+    // The outcomes of one validator, paged by the limit.
+    let page =
+        scan_validator_epoch_outcomes(Some(validators[1]), None, 2, 100, query_events(&events))
+            .unwrap();
+    assert_eq!(
+        page.data.iter().map(|o| o.epoch).collect::<Vec<_>>(),
+        vec![0, 1]
+    );
+    assert!(page.has_next_page);
+    assert!(!page.truncated_due_to_limit);
+    assert_eq!(page.next_cursor, Some(events[3].0.clone()));
+}
+
+#[test]
+fn test_scan_validator_epoch_outcomes_truncated() {
+    let validators = [
+        SuiAddress::random_for_testing_only(),
+        SuiAddress::random_for_testing_only(),
+    ];
+    let events = epoch_change_events(&validators, 3);
+
+    // The scan stops at the cap, and ends the page at the last event it scanned, which is not an
+    // outcome of the validator.
+    let page =
+        scan_validator_epoch_outcomes(Some(validators[0]), None, 10, 4, query_events(&events))
+            .unwrap();
+    assert_eq!(
+        page.data.iter().map(|o| o.epoch).collect::<Vec<_>>(),
+        vec![0, 1]
+    );
+    assert!(page.has_next_page);
+    assert!(page.truncated_due_to_limit);
+    assert_eq!(page.next_cursor, Some(events[3].0.clone()));
+
+    // The next page resumes after the last scanned event.
+    let page = scan_validator_epoch_outcomes(
+        Some(validators[0]),
+        page.next_cursor,
+        10,
+        4,
+        query_events(&events),
+    )
+    .unwrap();
+    assert_eq!(
+        page.data.iter().map(|o| o.epoch).collect::<Vec<_>>(),
+        vec![2]
+    );
+    assert!(!page.has_next_page);
+    assert!(!page.truncated_due_to_limit);
+}
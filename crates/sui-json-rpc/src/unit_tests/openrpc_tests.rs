@@ -0,0 +1,90 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use hyper::{Body, Request, Response, StatusCode};
+use jsonrpsee::core::__reexports::serde_json;
+use tower::{Layer, Service};
+
+use crate::api::{IndexerApiOpenRpc, WriteApiOpenRpc};
+use crate::openrpc_layer::{OpenRpcLayer, OPENRPC_DOC_PATH};
+use crate::{sui_rpc_doc, RpcFeature};
+
+/// Responds with an empty body.
+#[derive(Clone)]
+struct EmptyService;
+
+impl Service<Request<Body>> for EmptyService {
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _req: Request<Body>) -> Self::Future {
+        Box::pin(async move { Ok(Response::new(Body::empty())) })
+    }
+}
+
+fn method_names(doc: &serde_json::Value) -> Vec<&str> {
+    doc["methods"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|method| method["name"].as_str().unwrap())
+        .collect()
+}
+
+#[test]
+fn test_feature_methods_are_documented() {
+    let mut doc = sui_rpc_doc("0.0.0");
+    doc.add_module(IndexerApiOpenRpc::module_doc());
+    doc.add_module(WriteApiOpenRpc::module_doc());
+    let doc = serde_json::to_value(&doc).unwrap();
+    let methods = method_names(&doc);
+    for feature in [
+        RpcFeature::Indexer,
+        RpcFeature::Subscriptions,
+        RpcFeature::TransactionExecution,
+    ] {
+        for method in feature.methods() {
+            assert!(methods.contains(method), "{method} is not documented");
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_openrpc_doc_reflects_features() {
+    let mut doc = sui_rpc_doc("0.0.0");
+    doc.add_module(IndexerApiOpenRpc::module_doc());
+    doc.set_feature(RpcFeature::Indexer.name(), false);
+    doc.remove_methods(RpcFeature::Indexer.methods());
+    doc.set_feature(RpcFeature::Subscriptions.name(), true);
+    let mut service = OpenRpcLayer::new(&doc).unwrap().layer(EmptyService);
+
+    let request = Request::get(OPENRPC_DOC_PATH).body(Body::empty()).unwrap();
+    let response = service.call(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    let served: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(served["x-features"]["indexer"], false);
+    assert_eq!(served["x-features"]["subscriptions"], true);
+    let methods = method_names(&served);
+    assert!(methods.contains(&"suix_subscribeEvent"));
+    for method in RpcFeature::Indexer.methods() {
+        assert!(!methods.contains(method), "{method} is documented");
+    }
+
+    // Other requests are passed to the server.
+    let request = Request::post(OPENRPC_DOC_PATH).body(Body::empty()).unwrap();
+    let response = service.call(request).await.unwrap();
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    assert!(body.is_empty());
+}
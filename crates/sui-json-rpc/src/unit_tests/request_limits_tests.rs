@@ -0,0 +1,190 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use hyper::{Body, Request, Response, StatusCode};
+use prometheus::Registry;
+use tokio::sync::Semaphore;
+use tower::{Layer, Service};
+
+use crate::request_limits_layer::{MethodLimitsLayer, RequestLimitsLayer, RequestPriority};
+use crate::RequestLimits;
+
+/// Responds once a permit of `release` is available.
+#[derive(Clone)]
+struct BlockingService {
+    release: Arc<Semaphore>,
+}
+
+impl Service<Request<Body>> for BlockingService {
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _req: Request<Body>) -> Self::Future {
+        let release = self.release.clone();
+        Box::pin(async move {
+            release.acquire().await.unwrap().forget();
+            Ok(Response::new(Body::empty()))
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_max_concurrent_requests() {
+    let release = Arc::new(Semaphore::new(0));
+    let limits = RequestLimits::new(Some(1));
+    let mut service = RequestLimitsLayer::new(limits.clone()).layer(BlockingService {
+        release: release.clone(),
+    });
+
+    let first = service.call(Request::new(Body::empty()));
+    let rejected = service.call(Request::new(Body::empty())).await.unwrap();
+    assert_eq!(rejected.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    // A higher limit applies to the next requests.
+    limits.set_max_concurrent_requests(Some(2));
+    assert_eq!(limits.max_concurrent_requests(), Some(2));
+    let second = service.call(Request::new(Body::empty()));
+
+    release.add_permits(2);
+    assert_eq!(first.await.unwrap().status(), StatusCode::OK);
+    assert_eq!(second.await.unwrap().status(), StatusCode::OK);
+
+    // Completed requests no longer count.
+    limits.set_max_concurrent_requests(Some(1));
+    release.add_permits(1);
+    let response = service.call(Request::new(Body::empty())).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    limits.set_max_concurrent_requests(None);
+    assert_eq!(limits.max_concurrent_requests(), None);
+}
+
+fn rpc_request(method: &str) -> Request<Body> {
+    Request::post("/")
+        .header(hyper::header::CONTENT_TYPE, "application/json")
+        .body(Body::from(format!(
+            r#"{{"jsonrpc":"2.0","id":1,"method":"{method}","params":[]}}"#
+        )))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_method_concurrency_limits() {
+    let release = Arc::new(Semaphore::new(0));
+    let limits = RequestLimits::new(None);
+    limits.set_method_concurrency_limits(&BTreeMap::from([(
+        "suix_queryTransactionBlocks".to_string(),
+        1,
+    )]));
+    limits.set_method_queue_timeout(Some(Duration::from_millis(100)));
+    let registry = Registry::new();
+    let mut service = MethodLimitsLayer::new(limits.clone(), &registry).layer(BlockingService {
+        release: release.clone(),
+    });
+
+    let mut first = service.call(rpc_request("suix_queryTransactionBlocks"));
+    assert!(futures::poll!(&mut first).is_pending());
+
+    // Requests of the limited method wait for the request being served, up to the timeout.
+    let rejected = service
+        .call(rpc_request("suix_queryTransactionBlocks"))
+        .await
+        .unwrap();
+    assert_eq!(rejected.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    // The other methods are not limited.
+    let mut other = service.call(rpc_request("sui_getObject"));
+    assert!(futures::poll!(&mut other).is_pending());
+    release.add_permits(2);
+    assert_eq!(first.await.unwrap().status(), StatusCode::OK);
+    assert_eq!(other.await.unwrap().status(), StatusCode::OK);
+
+    // Queued requests are served once the requests of their method complete.
+    limits.set_method_queue_timeout(None);
+    let mut first = service.call(rpc_request("suix_queryTransactionBlocks"));
+    assert!(futures::poll!(&mut first).is_pending());
+    let mut queued = service.call(rpc_request("suix_queryTransactionBlocks"));
+    assert!(futures::poll!(&mut queued).is_pending());
+    release.add_permits(2);
+    assert_eq!(first.await.unwrap().status(), StatusCode::OK);
+    assert_eq!(queued.await.unwrap().status(), StatusCode::OK);
+
+    let rejected = registry
+        .gather()
+        .into_iter()
+        .find(|family| family.get_name() == "rpc_method_rejected_requests")
+        .unwrap();
+    assert_eq!(rejected.get_metric()[0].get_counter().get_value(), 1.0);
+
+    limits.set_method_concurrency_limits(&BTreeMap::new());
+    assert!(limits.method_concurrency_limits().is_empty());
+}
+
+#[tokio::test]
+async fn test_execution_lane() {
+    let release = Arc::new(Semaphore::new(0));
+    let limits = RequestLimits::new(Some(1));
+    limits.set_execution_lane_size(1);
+    let mut service = RequestLimitsLayer::new(limits.clone()).layer(BlockingService {
+        release: release.clone(),
+    });
+
+    let mut read = service.call(rpc_request("suix_queryTransactionBlocks"));
+    assert!(futures::poll!(&mut read).is_pending());
+    let rejected = service.call(rpc_request("sui_getObject")).await.unwrap();
+    assert_eq!(rejected.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    // Transactions are submitted while reads use up the shared limit, up to the lane size.
+    let mut execute = service.call(rpc_request("sui_executeTransactionBlock"));
+    assert!(futures::poll!(&mut execute).is_pending());
+    let rejected = service
+        .call(rpc_request("sui_dryRunTransactionBlock"))
+        .await
+        .unwrap();
+    assert_eq!(rejected.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    release.add_permits(2);
+    assert_eq!(read.await.unwrap().status(), StatusCode::OK);
+    assert_eq!(execute.await.unwrap().status(), StatusCode::OK);
+
+    // Beyond the lane, executions share the limit of the other methods.
+    let mut first = service.call(rpc_request("sui_executeTransactionBlock"));
+    assert!(futures::poll!(&mut first).is_pending());
+    let mut second = service.call(rpc_request("sui_dryRunTransactionBlock"));
+    assert!(futures::poll!(&mut second).is_pending());
+    let rejected = service.call(rpc_request("sui_getObject")).await.unwrap();
+    assert_eq!(rejected.status(), StatusCode::TOO_MANY_REQUESTS);
+    release.add_permits(2);
+    assert_eq!(first.await.unwrap().status(), StatusCode::OK);
+    assert_eq!(second.await.unwrap().status(), StatusCode::OK);
+}
+
+#[test]
+fn test_request_priority() {
+    assert_eq!(
+        RequestPriority::of_method("sui_executeTransactionBlock"),
+        RequestPriority::Execution
+    );
+    // Former names of the execution methods.
+    assert_eq!(
+        RequestPriority::of_method("sui_dryRunTransaction"),
+        RequestPriority::Execution
+    );
+    assert_eq!(
+        RequestPriority::of_method("sui_getObject"),
+        RequestPriority::Read
+    );
+}
@@ -5,6 +5,7 @@ use crate::api::{
     CoinReadApiClient, GovernanceReadApiClient, IndexerApiClient, ReadApiClient,
     TransactionBuilderClient, WriteApiClient,
 };
+use fastcrypto::encoding::Base64;
 use std::collections::BTreeMap;
 use std::path::Path;
 #[cfg(not(msim))]
@@ -18,19 +19,23 @@ use sui_json::{call_args, type_args};
 use sui_json_rpc_types::ObjectChange;
 use sui_json_rpc_types::ObjectsPage;
 use sui_json_rpc_types::{
-    Balance, CoinPage, DelegatedStake, StakeStatus, SuiCoinMetadata, SuiExecutionStatus,
-    SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery, SuiTransactionBlockEffectsAPI,
-    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions, TransactionBlockBytes,
+    Balance, CheckpointId, CoinPage, DelegatedStake, StakeStatus, SuiCoinMetadata,
+    SuiExecutionStatus, SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery,
+    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    TransactionBlockBytes,
 };
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
 use sui_macros::sim_test;
 use sui_types::balance::Supply;
 use sui_types::base_types::ObjectID;
 use sui_types::base_types::SequenceNumber;
+use sui_types::base_types::TransactionDigest;
 use sui_types::coin::{TreasuryCap, COIN_MODULE_NAME};
 use sui_types::digests::ObjectDigest;
 use sui_types::gas_coin::GAS;
-use sui_types::messages::ExecuteTransactionRequestType;
+use sui_types::messages::{ExecuteTransactionRequestType, TransactionEffectsAPI, TransactionKind};
+use sui_types::object::Owner;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::utils::to_sender_signed_transaction;
 use sui_types::{parse_sui_struct_tag, SUI_FRAMEWORK_ADDRESS};
 use test_utils::network::TestClusterBuilder;
@@ -171,6 +176,44 @@ fn assert_same_object_changes_ignoring_version_and_digest(
     }
 }
 
+#[sim_test]
+async fn test_dev_inspect_balance_changes() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await?;
+    let http_client = cluster.rpc_client();
+    let sender = cluster.accounts[0];
+    let recipient = cluster.accounts[1];
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.transfer_sui(recipient, Some(1000));
+    let tx_kind = TransactionKind::programmable(builder.finish());
+    let results = http_client
+        .dev_inspect_transaction_block(
+            sender,
+            Base64::from_bytes(&bcs::to_bytes(&tx_kind)?),
+            None,
+            None,
+            None,
+        )
+        .await?;
+    assert!(results.effects.status().is_ok());
+
+    // The mock gas coin of the sender pays both the amount transferred and the gas
+    let gas_used = results.effects.gas_cost_summary().net_gas_usage() as i128;
+    let balance_changes = results
+        .balance_changes
+        .iter()
+        .map(|change| (change.owner, change.amount))
+        .collect::<BTreeMap<_, _>>();
+    assert_eq!(
+        balance_changes,
+        BTreeMap::from([
+            (Owner::AddressOwner(sender), -1000 - gas_used),
+            (Owner::AddressOwner(recipient), 1000),
+        ])
+    );
+    Ok(())
+}
+
 #[sim_test]
 async fn test_publish() -> Result<(), anyhow::Error> {
     let cluster = TestClusterBuilder::new().build().await?;
@@ -321,6 +364,127 @@ async fn test_get_object_info() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_get_object_proof() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await?;
+    let http_client = cluster.rpc_client();
+    let address = cluster.accounts.first().unwrap();
+    let committee = cluster.swarm.config().genesis.committee()?;
+    let objects = http_client
+        .get_owned_objects(
+            *address,
+            Some(SuiObjectResponseQuery::new_with_options(
+                SuiObjectDataOptions::new(),
+            )),
+            None,
+            None,
+        )
+        .await?
+        .data;
+
+    // The genesis objects are written by the genesis transaction, in the first checkpoint.
+    for obj in objects {
+        let object_ref = obj.object().unwrap().object_ref();
+        let proof = http_client
+            .get_object_proof(object_ref.0, object_ref.1)
+            .await?;
+        assert_eq!(proof.object_ref.to_object_ref(), object_ref);
+        assert_eq!(proof.checkpoint, 0);
+        let verified = proof.verify(&committee)?;
+        assert_eq!(verified.object.compute_object_reference(), object_ref);
+    }
+
+    let unknown = http_client
+        .get_object_proof(ObjectID::random(), SequenceNumber::from_u64(1))
+        .await;
+    assert!(unknown.is_err());
+    Ok(())
+}
+
+#[sim_test]
+async fn test_get_transaction_proof() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await?;
+    let http_client = cluster.rpc_client();
+    let address = cluster.accounts.first().unwrap();
+    let committee = cluster.swarm.config().genesis.committee()?;
+    let objects = http_client
+        .get_owned_objects(
+            *address,
+            Some(SuiObjectResponseQuery::new_with_options(
+                SuiObjectDataOptions::new().with_previous_transaction(),
+            )),
+            None,
+            None,
+        )
+        .await?
+        .data;
+
+    // The genesis objects are written by the genesis transaction, in the first checkpoint.
+    let digest = objects[0].object().unwrap().previous_transaction.unwrap();
+    let proof = http_client.get_transaction_proof(digest).await?;
+    assert_eq!(proof.digest, digest);
+    assert_eq!(proof.checkpoint, 0);
+    let verified = proof.verify(&committee)?;
+    assert_eq!(*verified.effects.transaction_digest(), digest);
+
+    let unknown = http_client
+        .get_transaction_proof(TransactionDigest::random())
+        .await;
+    assert!(unknown.is_err());
+    Ok(())
+}
+
+#[sim_test]
+async fn test_effects_and_checkpoint_certificates() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await?;
+    let http_client = cluster.rpc_client();
+    let address = cluster.accounts.first().unwrap();
+    let committee = cluster.swarm.config().genesis.committee()?;
+    let objects = http_client
+        .get_owned_objects(*address, None, None, None)
+        .await?
+        .data;
+    let obj = objects.first().unwrap().object().unwrap().object_id;
+    let gas = objects.last().unwrap().object().unwrap().object_id;
+
+    let transaction_bytes: TransactionBlockBytes = http_client
+        .transfer_object(*address, obj, Some(gas), 10_000.into(), *address)
+        .await?;
+    let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    let tx = to_sender_signed_transaction(transaction_bytes.to_data()?, keystore.get_key(address)?);
+    let (tx_bytes, signatures) = tx.to_tx_bytes_and_signatures();
+    let tx_response = http_client
+        .execute_transaction_block(
+            tx_bytes,
+            signatures,
+            Some(SuiTransactionBlockResponseOptions::new().with_effects_certificate()),
+            Some(ExecuteTransactionRequestType::WaitForEffectsCert),
+        )
+        .await?;
+    let effects = tx_response
+        .effects_certificate
+        .unwrap()
+        .verify(&committee)?;
+    assert_eq!(effects.transaction_digest(), tx.digest());
+
+    // Certificates are only returned on execution.
+    let tx_response = http_client
+        .get_transaction_block(
+            *tx.digest(),
+            Some(SuiTransactionBlockResponseOptions::new().with_effects_certificate()),
+        )
+        .await?;
+    assert!(tx_response.effects_certificate.is_none());
+
+    let certificate = http_client
+        .get_checkpoint_certificate(CheckpointId::SequenceNumber(0))
+        .await?;
+    let checkpoint = certificate.verify(&committee)?;
+    assert_eq!(checkpoint.sequence_number, 0);
+    Ok(())
+}
+
 #[sim_test]
 async fn test_get_object_data_with_content() -> Result<(), anyhow::Error> {
     let cluster = TestClusterBuilder::new().build().await?;
@@ -943,3 +1107,40 @@ async fn test_staking_multiple_coins() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[sim_test]
+async fn test_validator_reports_and_outcomes() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new()
+        .with_epoch_duration_ms(10000)
+        .build()
+        .await?;
+    let http_client = cluster.rpc_client();
+
+    // No validator reported another one.
+    assert!(http_client.get_validator_reports().await?.is_empty());
+
+    cluster.wait_for_epoch(Some(1)).await;
+    let validators = cluster.swarm.validators().count();
+    let outcomes = http_client
+        .get_validator_epoch_outcomes(None, None, None, None)
+        .await?;
+    assert_eq!(outcomes.data.len(), validators);
+    assert!(!outcomes.has_next_page);
+    for outcome in &outcomes.data {
+        assert_eq!(outcome.epoch, 0);
+        assert!(!outcome.slashed);
+        assert!(outcome.reporters.is_empty());
+    }
+
+    // Outcomes filtered by validator, one per page.
+    let validator = outcomes.data[0].validator_address;
+    let page = http_client
+        .get_validator_epoch_outcomes(Some(validator), None, Some(1), None)
+        .await?;
+    assert_eq!(page.data, vec![outcomes.data[0].clone()]);
+    let page = http_client
+        .get_validator_epoch_outcomes(Some(validator), page.next_cursor, Some(1), None)
+        .await?;
+    assert!(page.data.iter().all(|o| o.epoch > 0));
+    Ok(())
+}
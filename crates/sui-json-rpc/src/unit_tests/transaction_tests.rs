@@ -4,14 +4,20 @@
 use sui_config::SUI_KEYSTORE_FILENAME;
 use sui_json_rpc_types::SuiTransactionBlockResponseQuery;
 use sui_json_rpc_types::{
-    SuiObjectDataOptions, SuiObjectResponseQuery, SuiTransactionBlockResponse,
-    SuiTransactionBlockResponseOptions, TransactionBlockBytes,
+    SuiObjectDataOptions, SuiObjectResponseQuery, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions, TransactionBlockBytes,
 };
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
 use sui_macros::sim_test;
-use sui_types::messages::{ExecuteTransactionRequestType, SenderSignedData};
+use sui_transaction_builder::LazyProgrammableTransaction;
+use sui_types::governance::ADD_STAKE_FUN_NAME;
+use sui_types::messages::{
+    Argument, CallArg, ExecuteTransactionRequestType, ObjectArg, SenderSignedData,
+};
 use sui_types::query::TransactionFilter;
+use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
 use sui_types::utils::to_sender_signed_transaction;
+use sui_types::{SUI_SYSTEM_PACKAGE_ID, SUI_SYSTEM_STATE_OBJECT_ID};
 use test_utils::network::TestClusterBuilder;
 
 use crate::api::{IndexerApiClient, TransactionBuilderClient, WriteApiClient};
@@ -327,3 +333,103 @@ async fn test_get_fullnode_transaction() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[sim_test]
+async fn test_lazy_transaction() -> Result<(), anyhow::Error> {
+    let mut cluster = TestClusterBuilder::new().build().await?;
+    let address = *cluster.accounts.first().unwrap();
+    let client = cluster.wallet.get_client().await?;
+
+    let coin = client
+        .read_api()
+        .get_owned_objects(address, None, None, None)
+        .await?
+        .data[0]
+        .object()?
+        .object_ref();
+    let validator = client
+        .governance_api()
+        .get_latest_sui_system_state()
+        .await?
+        .active_validators[0]
+        .sui_address;
+
+    let mut lazy = LazyProgrammableTransaction::new();
+    let system_state = lazy.object(SUI_SYSTEM_STATE_OBJECT_ID);
+    let stake = lazy.object(coin.0);
+    // Adding an object twice reuses its input.
+    assert_eq!(stake, lazy.object(coin.0));
+    let validator_arg = lazy.pure(&validator)?;
+
+    // The arity of the calls is checked against the signature of the function.
+    let mut wrong_arity = lazy.clone();
+    wrong_arity.move_call(
+        SUI_SYSTEM_PACKAGE_ID,
+        SUI_SYSTEM_MODULE_NAME.to_owned(),
+        ADD_STAKE_FUN_NAME.to_owned(),
+        vec![],
+        vec![system_state, stake],
+    );
+    assert!(client
+        .transaction_builder()
+        .resolve_lazy_transaction(wrong_arity)
+        .await
+        .is_err());
+
+    lazy.move_call(
+        SUI_SYSTEM_PACKAGE_ID,
+        SUI_SYSTEM_MODULE_NAME.to_owned(),
+        ADD_STAKE_FUN_NAME.to_owned(),
+        vec![],
+        vec![system_state, stake, validator_arg],
+    );
+    let pt = client
+        .transaction_builder()
+        .resolve_lazy_transaction(lazy.clone())
+        .await?;
+    assert_eq!(3, pt.inputs.len());
+    // The system state is shared, and taken by mutable reference.
+    assert!(matches!(
+        pt.inputs[0],
+        CallArg::Object(ObjectArg::SharedObject {
+            id: SUI_SYSTEM_STATE_OBJECT_ID,
+            mutable: true,
+            ..
+        })
+    ));
+    assert_eq!(
+        CallArg::Object(ObjectArg::ImmOrOwnedObject(coin)),
+        pt.inputs[1]
+    );
+    assert_eq!(Argument::Input(1), stake);
+
+    // The signature of the function is cached by the client, TxContext included.
+    let module_cache = client.read_api().module_cache();
+    assert!(module_cache.cached_version(SUI_SYSTEM_PACKAGE_ID).is_some());
+    let signature = module_cache
+        .get_function_signature(
+            SUI_SYSTEM_PACKAGE_ID,
+            SUI_SYSTEM_MODULE_NAME.as_str(),
+            ADD_STAKE_FUN_NAME.as_str(),
+        )
+        .await?;
+    assert_eq!(4, signature.parameters.len());
+
+    let data = client
+        .transaction_builder()
+        .finish_lazy_transaction(address, lazy, None, 100_000_000)
+        .await?;
+    let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    let tx = to_sender_signed_transaction(data, keystore.get_key(&address)?);
+    let response = client
+        .quorum_driver()
+        .execute_transaction_block(
+            tx,
+            SuiTransactionBlockResponseOptions::new().with_effects(),
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await?;
+    assert!(response.effects.unwrap().status().is_ok());
+    Ok(())
+}
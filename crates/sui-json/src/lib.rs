@@ -8,8 +8,11 @@ use std::str::FromStr;
 use anyhow::{anyhow, bail};
 use fastcrypto::encoding::{Encoding, Hex};
 use move_binary_format::{
-    access::ModuleAccess, binary_views::BinaryIndexedView, file_format::SignatureToken,
+    access::ModuleAccess,
+    binary_views::BinaryIndexedView,
+    file_format::{SignatureToken, StructFieldInformation},
     file_format_common::VERSION_MAX,
+    CompiledModule,
 };
 use move_core_types::account_address::AccountAddress;
 use move_core_types::identifier::IdentStr;
@@ -26,6 +29,8 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Number, Value as JsonValue};
 
+use sui_framework::BuiltInFramework;
+use sui_protocol_config::ProtocolConfig;
 use sui_types::base_types::{
     ObjectID, SuiAddress, STD_ASCII_MODULE_NAME, STD_ASCII_STRUCT_NAME, STD_OPTION_MODULE_NAME,
     STD_OPTION_STRUCT_NAME, STD_UTF8_MODULE_NAME, STD_UTF8_STRUCT_NAME,
@@ -84,6 +89,61 @@ impl fmt::Display for SuiJsonValueError {
     }
 }
 
+/// Maximum nesting depth of the arrays of a JSON argument. It only bounds the work of the node
+/// resolving the argument, validators never see JSON, so it applies at every protocol version.
+pub const MAX_JSON_ARGUMENT_DEPTH: u32 = 32;
+
+/// Limits on the JSON arguments of Move calls, which bound the resources used to resolve them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SuiJsonLimits {
+    /// Maximum nesting depth of arrays.
+    pub max_depth: u32,
+    /// Pure arguments must be smaller than this size in bytes once serialized, or they are
+    /// rejected by the validators.
+    pub max_pure_argument_size: u32,
+}
+
+impl SuiJsonLimits {
+    pub fn new(protocol_config: &ProtocolConfig) -> Self {
+        Self {
+            max_depth: MAX_JSON_ARGUMENT_DEPTH,
+            max_pure_argument_size: protocol_config.max_pure_argument_size(),
+        }
+    }
+}
+
+impl Default for SuiJsonLimits {
+    /// The limits of the latest protocol version.
+    fn default() -> Self {
+        Self::new(&ProtocolConfig::get_for_max_version())
+    }
+}
+
+/// A JSON argument exceeding one of the [SuiJsonLimits].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SuiJsonLimitError {
+    /// Arrays are nested deeper than `limit`.
+    DepthLimitExceeded { limit: u32 },
+    /// The argument would serialize to at least `limit` bytes.
+    SizeLimitExceeded { limit: u32 },
+}
+
+impl std::error::Error for SuiJsonLimitError {}
+
+impl fmt::Display for SuiJsonLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SuiJsonLimitError::DepthLimitExceeded { limit } => {
+                write!(f, "JSON arrays nested deeper than the maximum of {limit}.")
+            }
+            SuiJsonLimitError::SizeLimitExceeded { limit } => write!(
+                f,
+                "Argument serialized size exceeds the maximum of {limit} bytes."
+            ),
+        }
+    }
+}
+
 // Intermediate type to hold resolved args
 #[derive(Eq, PartialEq, Debug)]
 pub enum ResolvedCallArg {
@@ -108,9 +168,9 @@ impl SuiJsonValue {
                 }
             }
             // Must be homogeneous
-            JsonValue::Array(a) => {
+            JsonValue::Array(_) | JsonValue::Object(_) => {
                 // Fail if not homogeneous
-                check_valid_homogeneous(&JsonValue::Array(a))?
+                check_valid_homogeneous(&json_value)?
             }
             _ => bail!("{json_value} not allowed."),
         };
@@ -121,10 +181,70 @@ impl SuiJsonValue {
         Self(JsonValue::String(id.to_hex_uncompressed()))
     }
 
+    /// Serializes the value as a `ty`, within the [SuiJsonLimits] of the latest protocol version.
     pub fn to_bcs_bytes(&self, ty: &MoveTypeLayout) -> Result<Vec<u8>, anyhow::Error> {
-        let move_value = Self::to_move_value(&self.0, ty)?;
-        MoveValue::simple_serialize(&move_value)
-            .ok_or_else(|| anyhow!("Unable to serialize {:?}. Expected {}", move_value, ty))
+        self.to_bcs_bytes_with_limits(ty, &SuiJsonLimits::default())
+    }
+
+    /// Serializes the value as a `ty`. Values exceeding `limits` are rejected with a
+    /// [SuiJsonLimitError], before they are converted.
+    pub fn to_bcs_bytes_with_limits(
+        &self,
+        ty: &MoveTypeLayout,
+        limits: &SuiJsonLimits,
+    ) -> Result<Vec<u8>, anyhow::Error> {
+        self.check_limits(limits)?;
+        let move_value = Self::to_move_value(&self.0, ty, limits)?;
+        let bytes = MoveValue::simple_serialize(&move_value)
+            .ok_or_else(|| anyhow!("Unable to serialize {:?}. Expected {}", move_value, ty))?;
+        if bytes.len() >= limits.max_pure_argument_size as usize {
+            return Err(SuiJsonLimitError::SizeLimitExceeded {
+                limit: limits.max_pure_argument_size,
+            }
+            .into());
+        }
+        Ok(bytes)
+    }
+
+    /// Checks the depth of the arrays and objects of the value, and that it doesn't have too many
+    /// elements to fit in a pure argument, each serializing to at least one byte. Strings are
+    /// checked while they are converted, as their size depends on the type they are converted to.
+    pub fn check_limits(&self, limits: &SuiJsonLimits) -> Result<(), SuiJsonLimitError> {
+        // Iterative, as values too deep to recurse on are the ones to reject.
+        let mut stack = vec![(&self.0, 0)];
+        let mut elements: usize = 0;
+        while let Some((value, depth)) = stack.pop() {
+            let children: Vec<&JsonValue> = match value {
+                JsonValue::Array(a) => a.iter().collect(),
+                JsonValue::Object(o) => o.values().collect(),
+                _ => continue,
+            };
+            if depth >= limits.max_depth {
+                return Err(SuiJsonLimitError::DepthLimitExceeded {
+                    limit: limits.max_depth,
+                });
+            }
+            elements += children.len();
+            if elements >= limits.max_pure_argument_size as usize {
+                return Err(SuiJsonLimitError::SizeLimitExceeded {
+                    limit: limits.max_pure_argument_size,
+                });
+            }
+            stack.extend(children.into_iter().map(|v| (v, depth + 1)));
+        }
+        Ok(())
+    }
+
+    /// Whether the value is a JSON object, or an array of them, for an argument of a struct type.
+    fn is_struct_like(&self) -> bool {
+        let mut value = &self.0;
+        while let JsonValue::Array(a) = value {
+            match a.first() {
+                Some(first) => value = first,
+                None => return false,
+            }
+        }
+        matches!(value, JsonValue::Object(_))
     }
 
     pub fn from_bcs_bytes(
@@ -174,6 +294,7 @@ impl SuiJsonValue {
         val: &JsonValue,
         ty: &MoveTypeLayout,
         s: &String,
+        limits: &SuiJsonLimits,
     ) -> Result<MoveValue, anyhow::Error> {
         // delegate MoveValue construction to the case when JsonValue::String and
         // MoveTypeLayout::Vector are handled to get an address (with 0x string
@@ -190,10 +311,10 @@ impl SuiJsonValue {
         match &inner_vec[0] {
             MoveTypeLayout::Vector(inner) => match **inner {
                 MoveTypeLayout::U8 => Ok(MoveValue::Struct(MoveStruct::Runtime(vec![
-                    Self::to_move_value(val, &inner_vec[0].clone())?,
+                    Self::to_move_value(val, &inner_vec[0].clone(), limits)?,
                 ]))),
                 MoveTypeLayout::Address => Ok(MoveValue::Struct(MoveStruct::Runtime(vec![
-                    Self::to_move_value(val, &MoveTypeLayout::Address)?,
+                    Self::to_move_value(val, &MoveTypeLayout::Address, limits)?,
                 ]))),
                 _ => bail!(
                     "Cannot convert string arg {s} to {ty} \
@@ -208,7 +329,11 @@ impl SuiJsonValue {
         }
     }
 
-    fn to_move_value(val: &JsonValue, ty: &MoveTypeLayout) -> Result<MoveValue, anyhow::Error> {
+    fn to_move_value(
+        val: &JsonValue,
+        ty: &MoveTypeLayout,
+        limits: &SuiJsonLimits,
+    ) -> Result<MoveValue, anyhow::Error> {
         Ok(match (val, ty) {
             // Bool to Bool is simple
             (JsonValue::Bool(b), MoveTypeLayout::Bool) => MoveValue::Bool(*b),
@@ -247,7 +372,7 @@ impl SuiJsonValue {
                 MoveValue::U256(convert_string_to_u256(s.as_str())?)
             }
             (JsonValue::String(s), MoveTypeLayout::Struct(MoveStructLayout::Runtime(inner))) => {
-                Self::handle_inner_struct_layout(inner, val, ty, s)?
+                Self::handle_inner_struct_layout(inner, val, ty, s, limits)?
             }
             // For ascii and utf8 strings
             (
@@ -258,6 +383,7 @@ impl SuiJsonValue {
                 val,
                 ty,
                 s,
+                limits,
             )?,
             // For ID
             (
@@ -291,21 +417,52 @@ impl SuiJsonValue {
                             // Else raw bytes
                             s.as_bytes().to_vec()
                         };
+                        if vec.len() >= limits.max_pure_argument_size as usize {
+                            return Err(SuiJsonLimitError::SizeLimitExceeded {
+                                limit: limits.max_pure_argument_size,
+                            }
+                            .into());
+                        }
                         MoveValue::Vector(vec.iter().copied().map(MoveValue::U8).collect())
                     }
                     MoveTypeLayout::Struct(MoveStructLayout::Runtime(inner)) => {
-                        Self::handle_inner_struct_layout(inner, val, ty, s)?
+                        Self::handle_inner_struct_layout(inner, val, ty, s, limits)?
                     }
                     _ => bail!("Cannot convert string arg {s} to {ty}"),
                 }
             }
 
+            // Structs are expressed as objects with their field names
+            (
+                JsonValue::Object(o),
+                MoveTypeLayout::Struct(
+                    MoveStructLayout::WithFields(fields)
+                    | MoveStructLayout::WithTypes { fields, .. },
+                ),
+            ) => {
+                if let Some(name) = o
+                    .keys()
+                    .find(|name| !fields.iter().any(|f| f.name.as_str() == name.as_str()))
+                {
+                    bail!("Unexpected field {name} for expected type {ty}");
+                }
+                MoveValue::Struct(MoveStruct::Runtime(
+                    fields
+                        .iter()
+                        .map(|f| match o.get(f.name.as_str()) {
+                            Some(v) => Self::to_move_value(v, &f.layout, limits),
+                            None => bail!("Missing field {} for expected type {ty}", f.name),
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                ))
+            }
+
             // We have already checked that the array is homogeneous in the constructor
             (JsonValue::Array(a), MoveTypeLayout::Vector(inner)) => {
                 // Recursively build an IntermediateValue array
                 MoveValue::Vector(
                     a.iter()
-                        .map(|i| Self::to_move_value(i, inner))
+                        .map(|i| Self::to_move_value(i, inner, limits))
                         .collect::<Result<Vec<_>, _>>()?,
                 )
             }
@@ -449,6 +606,7 @@ enum ValidJsonType {
     Number,
     String,
     Array,
+    Object,
     // Matches any type
     Any,
 }
@@ -484,6 +642,13 @@ fn check_valid_homogeneous_rec(curr_q: &mut VecDeque<&JsonValue>) -> Result<(),
                 w.iter().for_each(|t| next_q.push_back(t));
                 ValidJsonType::Array
             }
+            // The fields of an object have different types, each must be valid on its own
+            JsonValue::Object(o) => {
+                for field in o.values() {
+                    check_valid_homogeneous(field)?;
+                }
+                ValidJsonType::Object
+            }
             // Not valid
             _ => {
                 return Err(SuiJsonValueError::new(
@@ -700,28 +865,140 @@ fn resolve_object_vec_arg(idx: usize, arg: &SuiJsonValue) -> Result<Vec<ObjectID
     }
 }
 
-fn resolve_call_arg(
-    view: &BinaryIndexedView,
+/// The module `address::module_name`, looked up in `package` and in the system packages.
+fn find_struct_module(
+    package: &MovePackage,
+    address: &AccountAddress,
+    module_name: &IdentStr,
+) -> Result<CompiledModule, anyhow::Error> {
+    if let Ok(module) = package.deserialize_module(&module_name.to_owned(), VERSION_MAX) {
+        if module.self_id().address() == address {
+            return Ok(module);
+        }
+    }
+    BuiltInFramework::iter_system_packages()
+        .find(|p| AccountAddress::from(*p.id()) == *address)
+        .and_then(|p| {
+            p.modules()
+                .into_iter()
+                .find(|m| m.self_id().name() == module_name)
+        })
+        .ok_or_else(|| {
+            anyhow!(
+                "Cannot resolve module {address}::{module_name}, only the structs of the package \
+                 of the function and of the system packages are supported"
+            )
+        })
+}
+
+/// Resolves the layout of a struct type passed by value from the definitions of its fields, with
+/// their names. Supported structs have no type parameters, and fields of primitive types, of
+/// such structs, or vectors of those.
+fn pure_struct_layout(
+    package: &MovePackage,
+    module: &CompiledModule,
+    type_args: &[TypeTag],
+    param: &SignatureToken,
+    depth: u32,
+    limits: &SuiJsonLimits,
+) -> Result<MoveTypeLayout, anyhow::Error> {
+    if depth >= limits.max_depth {
+        return Err(SuiJsonLimitError::DepthLimitExceeded {
+            limit: limits.max_depth,
+        }
+        .into());
+    }
+    let view = BinaryIndexedView::Module(module);
+    if let (true, Some(layout)) = primitive_type(&view, type_args, param) {
+        return Ok(layout);
+    }
+    match param {
+        SignatureToken::Vector(inner) => Ok(MoveTypeLayout::Vector(Box::new(pure_struct_layout(
+            package,
+            module,
+            type_args,
+            inner,
+            depth + 1,
+            limits,
+        )?))),
+        SignatureToken::Struct(idx) => {
+            let handle = module.struct_handle_at(*idx);
+            let module_handle = module.module_handle_at(handle.module);
+            let address = module.address_identifier_at(module_handle.address);
+            let module_name = module.identifier_at(module_handle.name);
+            let name = module.identifier_at(handle.name);
+            if handle.abilities.has_key() {
+                bail!("{address}::{module_name}::{name} is an object, it must be passed by ID");
+            }
+
+            let defining_module = find_struct_module(package, address, module_name)?;
+            let fields = defining_module
+                .struct_defs()
+                .iter()
+                .find(|def| {
+                    defining_module
+                        .identifier_at(defining_module.struct_handle_at(def.struct_handle).name)
+                        == name
+                })
+                .and_then(|def| match &def.field_information {
+                    StructFieldInformation::Declared(fields) => Some(fields),
+                    StructFieldInformation::Native => None,
+                })
+                .ok_or_else(|| {
+                    anyhow!("Cannot resolve the fields of {address}::{module_name}::{name}")
+                })?;
+            let fields = fields
+                .iter()
+                .map(|f| {
+                    Ok(MoveFieldLayout::new(
+                        defining_module.identifier_at(f.name).to_owned(),
+                        pure_struct_layout(
+                            package,
+                            &defining_module,
+                            &[],
+                            &f.signature.0,
+                            depth + 1,
+                            limits,
+                        )?,
+                    ))
+                })
+                .collect::<Result<_, anyhow::Error>>()?;
+            Ok(MoveTypeLayout::Struct(MoveStructLayout::WithFields(fields)))
+        }
+        _ => bail!(
+            "Cannot resolve the layout of {param:?}, only structs without type parameters are \
+             supported"
+        ),
+    }
+}
+
+/// Resolve the JSON arg at `idx` of a function of `module`, with type `param`, into the expected
+/// format to make it usable by Move call.
+/// Pure args exceeding `limits` are rejected with a [SuiJsonLimitError].
+pub fn resolve_call_arg(
+    package: &MovePackage,
+    module: &CompiledModule,
     type_args: &[TypeTag],
     idx: usize,
     arg: &SuiJsonValue,
     param: &SignatureToken,
+    limits: &SuiJsonLimits,
 ) -> Result<ResolvedCallArg, anyhow::Error> {
-    let (is_primitive, layout_opt) = primitive_type(view, type_args, param);
+    let view = BinaryIndexedView::Module(module);
+    let (is_primitive, layout_opt) = primitive_type(&view, type_args, param);
     if is_primitive {
         match layout_opt {
             Some(layout) => {
-                return Ok(ResolvedCallArg::Pure(arg.to_bcs_bytes(&layout).map_err(
-                    |e| {
-                        anyhow!(
-                        "Could not serialize argument of type {:?} at {} into {}. Got error: {:?}",
-                        param,
-                        idx,
-                        layout,
-                        e
-                    )
-                    },
-                )?));
+                return Ok(ResolvedCallArg::Pure(
+                    arg.to_bcs_bytes_with_limits(&layout, limits).map_err(|e| {
+                        // Keep the error as the source, so that limit errors can be downcast.
+                        let msg = format!(
+                            "Could not serialize argument of type {:?} at {} into {}. Got error: {:?}",
+                            param, idx, layout, e
+                        );
+                        e.context(msg)
+                    })?,
+                ));
             }
             None => {
                 debug_assert!(
@@ -738,6 +1015,28 @@ fn resolve_call_arg(
         }
     }
 
+    // Structs passed by value (to non-entry functions) are expressed as JSON objects with the
+    // names of their fields, and objects are expressed with their IDs.
+    if arg.is_struct_like() {
+        let layout =
+            pure_struct_layout(package, module, type_args, param, 0, limits).map_err(|e| {
+                let msg = format!(
+                    "Could not resolve the type {:?} of the argument at {}. Got error: {:?}",
+                    param, idx, e
+                );
+                e.context(msg)
+            })?;
+        return Ok(ResolvedCallArg::Pure(
+            arg.to_bcs_bytes_with_limits(&layout, limits).map_err(|e| {
+                let msg = format!(
+                    "Could not serialize argument of type {:?} at {} into {}. Got error: {:?}",
+                    param, idx, layout, e
+                );
+                e.context(msg)
+            })?,
+        ));
+    }
+
     // in terms of non-primitives we only currently support objects and "flat" (depth == 1) vectors
     // of objects (but not, for example, vectors of references)
     match param {
@@ -772,31 +1071,33 @@ fn resolve_call_arg(
 }
 
 fn resolve_call_args(
-    view: &BinaryIndexedView,
+    package: &MovePackage,
+    module: &CompiledModule,
     type_args: &[TypeTag],
     json_args: &[SuiJsonValue],
     parameter_types: &[SignatureToken],
+    limits: &SuiJsonLimits,
 ) -> Result<Vec<ResolvedCallArg>, anyhow::Error> {
     json_args
         .iter()
         .zip(parameter_types)
         .enumerate()
-        .map(|(idx, (arg, param))| resolve_call_arg(view, type_args, idx, arg, param))
+        .map(|(idx, (arg, param))| {
+            resolve_call_arg(package, module, type_args, idx, arg, param, limits)
+        })
         .collect()
 }
 
-/// Resolve a the JSON args of a function into the expected formats to make them usable by Move call
-/// This is because we have special types which we need to specify in other formats
-pub fn resolve_move_function_args(
+/// Resolve the module of a function and the types of the parameters expected from the caller,
+/// which exclude the trailing TxContext.
+pub fn resolve_move_function_params(
     package: &MovePackage,
-    module_ident: Identifier,
-    function: Identifier,
-    type_args: &[TypeTag],
-    combined_args_json: Vec<SuiJsonValue>,
+    module_ident: &Identifier,
+    function: &Identifier,
     allow_arbitrary_function_call: bool,
-) -> Result<Vec<(ResolvedCallArg, SignatureToken)>, anyhow::Error> {
+) -> Result<(CompiledModule, Vec<SignatureToken>), anyhow::Error> {
     // Extract the expected function signature
-    let module = package.deserialize_module(&module_ident, VERSION_MAX)?;
+    let module = package.deserialize_module(module_ident, VERSION_MAX)?;
     let function_str = function.as_ident_str();
     let fdef = module
         .function_defs
@@ -824,24 +1125,54 @@ pub fn resolve_move_function_args(
 
     let view = BinaryIndexedView::Module(&module);
 
-    // Lengths have to match, less one, due to TxContext
+    // The TxContext is not passed by the caller
     let expected_len = match parameters.last() {
         Some(param) if is_tx_context(&view, param) != TxContextKind::None => parameters.len() - 1,
         _ => parameters.len(),
     };
-    if combined_args_json.len() != expected_len {
+    let parameters = parameters[..expected_len].to_vec();
+    Ok((module, parameters))
+}
+
+/// Resolve a the JSON args of a function into the expected formats to make them usable by Move call
+/// This is because we have special types which we need to specify in other formats
+/// Pure args exceeding `limits` are rejected with a [SuiJsonLimitError].
+pub fn resolve_move_function_args(
+    package: &MovePackage,
+    module_ident: Identifier,
+    function: Identifier,
+    type_args: &[TypeTag],
+    combined_args_json: Vec<SuiJsonValue>,
+    allow_arbitrary_function_call: bool,
+    limits: &SuiJsonLimits,
+) -> Result<Vec<(ResolvedCallArg, SignatureToken)>, anyhow::Error> {
+    let (module, parameters) = resolve_move_function_params(
+        package,
+        &module_ident,
+        &function,
+        allow_arbitrary_function_call,
+    )?;
+
+    // Lengths have to match
+    if combined_args_json.len() != parameters.len() {
         bail!(
             "Expected {} args, found {}",
-            expected_len,
+            parameters.len(),
             combined_args_json.len()
         );
     }
     // Check that the args are valid and convert to the correct format
-    let call_args = resolve_call_args(&view, type_args, &combined_args_json, parameters)?;
+    let call_args = resolve_call_args(
+        package,
+        &module,
+        type_args,
+        &combined_args_json,
+        &parameters,
+        limits,
+    )?;
     let tupled_call_args = call_args
         .into_iter()
-        .zip(parameters.iter())
-        .map(|(arg, expected_type)| (arg, expected_type.clone()))
+        .zip(parameters.into_iter())
         .collect::<Vec<_>>();
     Ok(tupled_call_args)
 }
@@ -14,6 +14,7 @@ use move_core_types::{
 use serde_json::{json, Value};
 use sui_framework::BuiltInFramework;
 use sui_framework_build::compiled_package::BuildConfig;
+use sui_protocol_config::ProtocolConfig;
 use test_fuzz::runtime::num_traits::ToPrimitive;
 
 use crate::ResolvedCallArg;
@@ -22,10 +23,13 @@ use sui_types::base_types::{
     STD_OPTION_MODULE_NAME, STD_OPTION_STRUCT_NAME,
 };
 use sui_types::object::Object;
-use sui_types::MOVE_STDLIB_ADDRESS;
+use sui_types::{MOVE_STDLIB_ADDRESS, MOVE_STDLIB_OBJECT_ID};
 
 use super::{check_valid_homogeneous, HEX_PREFIX};
-use super::{resolve_move_function_args, SuiJsonValue};
+use super::{
+    resolve_move_function_args, SuiJsonLimitError, SuiJsonLimits, SuiJsonValue,
+    MAX_JSON_ARGUMENT_DEPTH,
+};
 
 // Negative test cases
 #[test]
@@ -482,6 +486,7 @@ fn test_basic_args_linter_top_level() {
         &[],
         args,
         /* allow_arbitrary_function_call */ false,
+        &SuiJsonLimits::default(),
     )
     .unwrap();
 
@@ -529,6 +534,7 @@ fn test_basic_args_linter_top_level() {
         &[],
         args,
         /* allow_arbitrary_function_call */ false,
+        &SuiJsonLimits::default(),
     )
     .is_err());
 
@@ -574,6 +580,7 @@ fn test_basic_args_linter_top_level() {
         &[],
         args,
         /* allow_arbitrary_function_call */ false,
+        &SuiJsonLimits::default(),
     )
     .unwrap();
 
@@ -618,6 +625,7 @@ fn test_basic_args_linter_top_level() {
         &[],
         args,
         /* allow_arbitrary_function_call */ false,
+        &SuiJsonLimits::default(),
     )
     .unwrap();
 
@@ -671,6 +679,7 @@ fn test_basic_args_linter_top_level() {
         &[],
         args,
         /* allow_arbitrary_function_call */ false,
+        &SuiJsonLimits::default(),
     )
     .unwrap();
 
@@ -860,3 +869,108 @@ fn test_sui_call_arg_option_type() {
     let s = SuiJsonValue::from_str("[test, test2]").unwrap();
     println!("{s:?}");
 }
+
+#[test]
+fn test_json_depth_limit_at_every_protocol_version() {
+    let limits = SuiJsonLimits::new(&ProtocolConfig::get_for_min_version());
+    assert_eq!(limits.max_depth, MAX_JSON_ARGUMENT_DEPTH);
+    assert_eq!(SuiJsonLimits::default().max_depth, MAX_JSON_ARGUMENT_DEPTH);
+}
+
+#[test]
+fn test_json_limits() {
+    let limits = SuiJsonLimits {
+        max_depth: 2,
+        max_pure_argument_size: 8,
+    };
+    let u8_vec = MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8));
+    let u8_vec_vec = MoveTypeLayout::Vector(Box::new(u8_vec.clone()));
+    let u8_vec_vec_vec = MoveTypeLayout::Vector(Box::new(u8_vec_vec.clone()));
+
+    let ok = SuiJsonValue::new(json!([[1, 2], [3]])).unwrap();
+    assert_eq!(
+        ok.to_bcs_bytes_with_limits(&u8_vec_vec, &limits).unwrap(),
+        bcs::to_bytes(&vec![vec![1u8, 2], vec![3]]).unwrap()
+    );
+
+    let too_deep = SuiJsonValue::new(json!([[[1]]])).unwrap();
+    assert_eq!(
+        too_deep.check_limits(&limits).unwrap_err(),
+        SuiJsonLimitError::DepthLimitExceeded { limit: 2 }
+    );
+    let err = too_deep
+        .to_bcs_bytes_with_limits(&u8_vec_vec_vec, &limits)
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<SuiJsonLimitError>(),
+        Some(&SuiJsonLimitError::DepthLimitExceeded { limit: 2 })
+    );
+
+    // Too many elements to fit in a pure argument.
+    let too_many = SuiJsonValue::new(json!([1, 2, 3, 4, 5, 6, 7, 8])).unwrap();
+    assert_eq!(
+        too_many.check_limits(&limits).unwrap_err(),
+        SuiJsonLimitError::SizeLimitExceeded { limit: 8 }
+    );
+
+    // Strings are checked when converted, as hex strings are half the size of ascii ones.
+    let hex = SuiJsonValue::new(json!("0x010203040506")).unwrap();
+    assert!(hex.check_limits(&limits).is_ok());
+    assert!(hex.to_bcs_bytes_with_limits(&u8_vec, &limits).is_ok());
+    let ascii = SuiJsonValue::new(json!("01020304050607")).unwrap();
+    assert_eq!(
+        ascii
+            .to_bcs_bytes_with_limits(&u8_vec, &limits)
+            .unwrap_err()
+            .downcast_ref::<SuiJsonLimitError>(),
+        Some(&SuiJsonLimitError::SizeLimitExceeded { limit: 8 })
+    );
+
+    // The serialized size is checked, for values like u256 larger than their JSON.
+    let number = SuiJsonValue::new(json!("1")).unwrap();
+    assert_eq!(
+        number
+            .to_bcs_bytes_with_limits(&MoveTypeLayout::U256, &limits)
+            .unwrap_err()
+            .downcast_ref::<SuiJsonLimitError>(),
+        Some(&SuiJsonLimitError::SizeLimitExceeded { limit: 8 })
+    );
+}
+
+#[test]
+fn test_struct_args_with_named_fields() {
+    let stdlib = BuiltInFramework::get_package_by_id(&MOVE_STDLIB_OBJECT_ID).genesis_move_package();
+    let module = Identifier::new("fixed_point32").unwrap();
+    let function = Identifier::new("multiply_u64").unwrap();
+
+    /*
+    Function signature:
+            public fun multiply_u64(val: u64, multiplier: FixedPoint32): u64
+    where FixedPoint32 is `struct FixedPoint32 has copy, drop, store { value: u64 }`
+    */
+    let resolve = |multiplier: Value| {
+        resolve_move_function_args(
+            &stdlib,
+            module.clone(),
+            function.clone(),
+            &[],
+            vec![
+                SuiJsonValue::new(json!("10")).unwrap(),
+                SuiJsonValue::new(multiplier).unwrap(),
+            ],
+            /* allow_arbitrary_function_call */ true,
+            &SuiJsonLimits::default(),
+        )
+    };
+
+    let args = resolve(json!({ "value": "4294967296" })).unwrap();
+    assert_eq!(
+        args[1].0,
+        ResolvedCallArg::Pure(bcs::to_bytes(&4294967296u64).unwrap())
+    );
+
+    // Fields must match the struct exactly.
+    assert!(resolve(json!({})).is_err());
+    assert!(resolve(json!({ "value": "1", "scale": "1" })).is_err());
+    assert!(resolve(json!({ "value": true })).is_err());
+}
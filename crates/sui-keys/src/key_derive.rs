@@ -34,9 +34,61 @@ pub fn derive_key_pair_from_path(
     key_scheme: &SignatureScheme,
 ) -> Result<(SuiAddress, SuiKeyPair), SuiError> {
     let path = validate_path(key_scheme, derivation_path)?;
+    derive_key_pair_from_valid_path(seed, &path, key_scheme)
+}
+
+/// Derive `count` keypairs at consecutive indexes, starting from the given derivation path.
+/// The last level of the path is incremented for each subsequent key and keeps its hardened
+/// setting, e.g. m/44'/784'/0'/0'/0', m/44'/784'/0'/0'/1', ... for Ed25519.
+///
+/// If `custom_path` is set, the path is only checked against the requirements of the key
+/// scheme (every level hardened for Ed25519) rather than the Sui purpose and coin type.
+pub fn derive_key_pairs_from_path(
+    seed: &[u8],
+    derivation_path: Option<DerivationPath>,
+    key_scheme: &SignatureScheme,
+    count: u32,
+    custom_path: bool,
+) -> Result<Vec<(SuiAddress, SuiKeyPair, DerivationPath)>, SuiError> {
+    if count == 0 {
+        return Err(SuiError::SignatureKeyGenError(
+            "Key count must be greater than 0".to_string(),
+        ));
+    }
+    let path = if custom_path {
+        validate_custom_path(key_scheme, derivation_path)?
+    } else {
+        validate_path(key_scheme, derivation_path)?
+    };
+    let (parent, first) = match (path.parent(), path.as_ref().last()) {
+        (Some(parent), Some(first)) => (parent, *first),
+        _ => return Err(SuiError::SignatureKeyGenError("Invalid path".to_string())),
+    };
+    (0..count)
+        .map(|offset| -> Result<_, SuiError> {
+            let child = first
+                .index()
+                .checked_add(offset)
+                .and_then(|index| ChildNumber::new(index, first.is_hardened()).ok())
+                .ok_or_else(|| {
+                    SuiError::SignatureKeyGenError("Derivation path index overflow".to_string())
+                })?;
+            let mut child_path = parent.clone();
+            child_path.push(child);
+            let (address, kp) = derive_key_pair_from_valid_path(seed, &child_path, key_scheme)?;
+            Ok((address, kp, child_path))
+        })
+        .collect()
+}
+
+fn derive_key_pair_from_valid_path(
+    seed: &[u8],
+    path: &DerivationPath,
+    key_scheme: &SignatureScheme,
+) -> Result<(SuiAddress, SuiKeyPair), SuiError> {
     match key_scheme {
         SignatureScheme::ED25519 => {
-            let indexes = path.into_iter().map(|i| i.into()).collect::<Vec<_>>();
+            let indexes = path.iter().map(|i| i.into()).collect::<Vec<_>>();
             let derived = derive_ed25519_private_key(seed, &indexes);
             let sk = Ed25519PrivateKey::from_bytes(&derived)
                 .map_err(|e| SuiError::SignatureKeyGenError(e.to_string()))?;
@@ -44,7 +96,7 @@ pub fn derive_key_pair_from_path(
             Ok((kp.public().into(), SuiKeyPair::Ed25519(kp)))
         }
         SignatureScheme::Secp256k1 => {
-            let child_xprv = XPrv::derive_from_path(seed, &path)
+            let child_xprv = XPrv::derive_from_path(seed, path)
                 .map_err(|e| SuiError::SignatureKeyGenError(e.to_string()))?;
             let kp = Secp256k1KeyPair::from(
                 Secp256k1PrivateKey::from_bytes(child_xprv.private_key().to_bytes().as_slice())
@@ -53,7 +105,7 @@ pub fn derive_key_pair_from_path(
             Ok((kp.public().into(), SuiKeyPair::Secp256k1(kp)))
         }
         SignatureScheme::Secp256r1 => {
-            let child_xprv = XPrv::derive_from_path(seed, &path)
+            let child_xprv = XPrv::derive_from_path(seed, path)
                 .map_err(|e| SuiError::SignatureKeyGenError(e.to_string()))?;
             let kp = Secp256r1KeyPair::from(
                 Secp256r1PrivateKey::from_bytes(child_xprv.private_key().to_bytes().as_slice())
@@ -166,6 +218,42 @@ pub fn validate_path(
     }
 }
 
+/// Validate a user supplied derivation path that does not follow the Sui purpose and coin type
+/// convention. Ed25519 keys are derived with SLIP-0010, which only supports hardened levels.
+/// Falls back to the default path of the key scheme if no path is given.
+pub fn validate_custom_path(
+    key_scheme: &SignatureScheme,
+    path: Option<DerivationPath>,
+) -> Result<DerivationPath, SuiError> {
+    let p = match path {
+        Some(p) => p,
+        None => return validate_path(key_scheme, None),
+    };
+    match key_scheme {
+        SignatureScheme::ED25519 => {
+            if !p.is_empty() && p.iter().all(|c| c.is_hardened()) {
+                Ok(p)
+            } else {
+                Err(SuiError::SignatureKeyGenError(
+                    "Ed25519 derivation path must be hardened at all levels".to_string(),
+                ))
+            }
+        }
+        SignatureScheme::Secp256k1 | SignatureScheme::Secp256r1 => {
+            if !p.is_empty() {
+                Ok(p)
+            } else {
+                Err(SuiError::SignatureKeyGenError("Invalid path".to_string()))
+            }
+        }
+        SignatureScheme::BLS12381 | SignatureScheme::MultiSig => {
+            Err(SuiError::UnsupportedFeatureError {
+                error: format!("key derivation not supported {:?}", key_scheme),
+            })
+        }
+    }
+}
+
 pub fn generate_new_key(
     key_scheme: SignatureScheme,
     derivation_path: Option<DerivationPath>,
@@ -179,6 +267,36 @@ pub fn generate_new_key(
     }
 }
 
+/// Generate a new mnemonic and derive `count` keypairs from it at consecutive indexes, see
+/// [derive_key_pairs_from_path].
+pub fn generate_new_keys(
+    key_scheme: SignatureScheme,
+    derivation_path: Option<DerivationPath>,
+    word_length: Option<String>,
+    count: u32,
+    custom_path: bool,
+) -> Result<
+    (
+        Vec<(SuiAddress, SuiKeyPair, DerivationPath)>,
+        SignatureScheme,
+        String,
+    ),
+    anyhow::Error,
+> {
+    let mnemonic = Mnemonic::new(parse_word_length(word_length)?, Language::English);
+    let seed = Seed::new(&mnemonic, "");
+    match derive_key_pairs_from_path(
+        seed.as_bytes(),
+        derivation_path,
+        &key_scheme,
+        count,
+        custom_path,
+    ) {
+        Ok(keys) => Ok((keys, key_scheme, mnemonic.phrase().to_string())),
+        Err(e) => Err(anyhow!("Failed to generate keypairs: {:?}", e)),
+    }
+}
+
 fn parse_word_length(s: Option<String>) -> Result<MnemonicType, anyhow::Error> {
     match s {
         None => Ok(MnemonicType::Words12),
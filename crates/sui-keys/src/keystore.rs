@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::key_derive::{derive_key_pair_from_path, generate_new_key};
+use crate::key_derive::{derive_key_pair_from_path, derive_key_pairs_from_path, generate_new_key};
 use anyhow::anyhow;
 use bip32::DerivationPath;
 use bip39::{Language, Mnemonic, Seed};
@@ -76,6 +76,35 @@ pub trait AccountKeystore: Send + Sync {
             Err(e) => Err(anyhow!("error getting keypair {:?}", e)),
         }
     }
+
+    /// Import `count` keys derived from the mnemonic phrase at consecutive indexes of the
+    /// derivation path, returning the address and derivation path of each imported key.
+    fn import_batch_from_mnemonic(
+        &mut self,
+        phrase: &str,
+        key_scheme: SignatureScheme,
+        derivation_path: Option<DerivationPath>,
+        count: u32,
+        custom_path: bool,
+    ) -> Result<Vec<(SuiAddress, DerivationPath)>, anyhow::Error> {
+        let mnemonic = Mnemonic::from_phrase(phrase, Language::English)
+            .map_err(|e| anyhow::anyhow!("Invalid mnemonic phrase: {:?}", e))?;
+        let seed = Seed::new(&mnemonic, "");
+        let keys = derive_key_pairs_from_path(
+            seed.as_bytes(),
+            derivation_path,
+            &key_scheme,
+            count,
+            custom_path,
+        )
+        .map_err(|e| anyhow!("error getting keypairs {:?}", e))?;
+        let mut imported = Vec::with_capacity(keys.len());
+        for (address, kp, path) in keys {
+            self.add_key(kp)?;
+            imported.push((address, path));
+        }
+        Ok(imported)
+    }
 }
 
 impl Display for Keystore {
@@ -0,0 +1,214 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of the data served by fullnodes without trusting them, for light clients such as
+//! bridges and off-chain verifiers. Starting from a trusted committee, e.g. the genesis committee
+//! or the committee of an epoch commitment published out of band:
+//!
+//! - [CommitteeTracker] verifies the checkpoints certified by the committee, and follows the
+//!   committee handoffs recorded in the last checkpoint of each epoch.
+//! - [verify_transaction_inclusion] and [verify_effects_inclusion] verify that a transaction and
+//!   its effects are part of a verified checkpoint, from the contents of the checkpoint.
+
+use std::collections::BTreeMap;
+
+use sui_types::base_types::{ExecutionDigests, TransactionDigest};
+use sui_types::committee::{Committee, EpochId, TOTAL_VOTING_POWER};
+use sui_types::digests::{CheckpointContentsDigest, TransactionEffectsDigest};
+use sui_types::error::SuiError;
+use sui_types::message_envelope::Message;
+use sui_types::messages::{TransactionEffects, TransactionEffectsAPI};
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointContents, CheckpointSequenceNumber, CheckpointSummary,
+    VerifiedCheckpoint,
+};
+use thiserror::Error;
+
+#[cfg(test)]
+#[path = "unit_tests/light_client_tests.rs"]
+mod light_client_tests;
+
+pub type LightClientResult<T> = Result<T, LightClientError>;
+
+#[derive(Debug, Error)]
+pub enum LightClientError {
+    #[error(
+        "Checkpoint {sequence_number} is of epoch {actual}, the committee is of epoch {expected}"
+    )]
+    EpochMismatch {
+        sequence_number: CheckpointSequenceNumber,
+        expected: EpochId,
+        actual: EpochId,
+    },
+    #[error("Invalid signature of checkpoint {sequence_number}: {error}")]
+    InvalidSignature {
+        sequence_number: CheckpointSequenceNumber,
+        error: SuiError,
+    },
+    #[error("Checkpoint {0} is not the last checkpoint of its epoch")]
+    NotEndOfEpoch(CheckpointSequenceNumber),
+    #[error("Invalid committee of epoch {epoch}: {reason}")]
+    InvalidCommittee { epoch: EpochId, reason: String },
+    #[error("Contents digest {actual} does not match the digest {expected} of checkpoint {sequence_number}")]
+    ContentsMismatch {
+        sequence_number: CheckpointSequenceNumber,
+        expected: CheckpointContentsDigest,
+        actual: CheckpointContentsDigest,
+    },
+    #[error("Transaction {transaction} is not included in checkpoint {sequence_number}")]
+    TransactionNotIncluded {
+        sequence_number: CheckpointSequenceNumber,
+        transaction: TransactionDigest,
+    },
+    #[error("Effects {actual} of transaction {transaction} do not match the effects {expected} included in checkpoint {sequence_number}")]
+    EffectsMismatch {
+        sequence_number: CheckpointSequenceNumber,
+        transaction: TransactionDigest,
+        expected: TransactionEffectsDigest,
+        actual: TransactionEffectsDigest,
+    },
+}
+
+/// Verifies that `checkpoint` is certified by a quorum of `committee`.
+pub fn verify_checkpoint(
+    committee: &Committee,
+    checkpoint: CertifiedCheckpointSummary,
+) -> LightClientResult<VerifiedCheckpoint> {
+    let sequence_number = checkpoint.sequence_number;
+    if checkpoint.epoch != committee.epoch {
+        return Err(LightClientError::EpochMismatch {
+            sequence_number,
+            expected: committee.epoch,
+            actual: checkpoint.epoch,
+        });
+    }
+    checkpoint
+        .verify(committee)
+        .map_err(|error| LightClientError::InvalidSignature {
+            sequence_number,
+            error,
+        })
+}
+
+/// The committee of the epoch following the epoch ended by `checkpoint`.
+pub fn next_epoch_committee(checkpoint: &CheckpointSummary) -> LightClientResult<Committee> {
+    let next_epoch_committee = checkpoint
+        .next_epoch_committee()
+        .ok_or(LightClientError::NotEndOfEpoch(checkpoint.sequence_number))?;
+    let epoch = checkpoint.epoch + 1;
+    let voting_rights: BTreeMap<_, _> = next_epoch_committee.iter().cloned().collect();
+    // Committee::new panics on invalid voting rights.
+    if voting_rights.len() != next_epoch_committee.len() {
+        return Err(LightClientError::InvalidCommittee {
+            epoch,
+            reason: "duplicate validators".to_string(),
+        });
+    }
+    let total_votes = voting_rights
+        .values()
+        .try_fold(0u64, |total, votes| total.checked_add(*votes));
+    if total_votes != Some(TOTAL_VOTING_POWER) {
+        return Err(LightClientError::InvalidCommittee {
+            epoch,
+            reason: format!("total voting power is not {TOTAL_VOTING_POWER}"),
+        });
+    }
+    Ok(Committee::new(epoch, voting_rights))
+}
+
+/// Follows the committee of the network across epochs, from a trusted committee.
+#[derive(Clone, Debug)]
+pub struct CommitteeTracker {
+    committee: Committee,
+}
+
+impl CommitteeTracker {
+    /// Starts from `committee`, which must come from a trusted source, e.g. the genesis of the
+    /// network.
+    pub fn new(committee: Committee) -> Self {
+        Self { committee }
+    }
+
+    /// The committee of the current epoch of the tracker.
+    pub fn committee(&self) -> &Committee {
+        &self.committee
+    }
+
+    pub fn epoch(&self) -> EpochId {
+        self.committee.epoch
+    }
+
+    /// Verifies a checkpoint of the current epoch of the tracker.
+    pub fn verify_checkpoint(
+        &self,
+        checkpoint: CertifiedCheckpointSummary,
+    ) -> LightClientResult<VerifiedCheckpoint> {
+        verify_checkpoint(&self.committee, checkpoint)
+    }
+
+    /// Moves to the next epoch, from the last checkpoint of the current epoch. The checkpoint
+    /// is verified against the current committee, and records the committee of the next epoch.
+    pub fn advance_epoch(
+        &mut self,
+        end_of_epoch_checkpoint: CertifiedCheckpointSummary,
+    ) -> LightClientResult<&Committee> {
+        let checkpoint = self.verify_checkpoint(end_of_epoch_checkpoint)?;
+        self.committee = next_epoch_committee(checkpoint.data())?;
+        Ok(&self.committee)
+    }
+}
+
+/// Verifies that `contents` are the contents of `checkpoint`.
+pub fn verify_contents(
+    checkpoint: &VerifiedCheckpoint,
+    contents: &CheckpointContents,
+) -> LightClientResult<()> {
+    let actual = *contents.digest();
+    if actual != checkpoint.content_digest {
+        return Err(LightClientError::ContentsMismatch {
+            sequence_number: checkpoint.sequence_number,
+            expected: checkpoint.content_digest,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// Verifies that `transaction` is included in `checkpoint`, whose contents are `contents`.
+/// Returns the digests of the transaction and of its effects.
+pub fn verify_transaction_inclusion(
+    checkpoint: &VerifiedCheckpoint,
+    contents: &CheckpointContents,
+    transaction: &TransactionDigest,
+) -> LightClientResult<ExecutionDigests> {
+    verify_contents(checkpoint, contents)?;
+    contents
+        .iter()
+        .find(|digests| &digests.transaction == transaction)
+        .copied()
+        .ok_or(LightClientError::TransactionNotIncluded {
+            sequence_number: checkpoint.sequence_number,
+            transaction: *transaction,
+        })
+}
+
+/// Verifies that `effects` are the effects of a transaction included in `checkpoint`, whose
+/// contents are `contents`.
+pub fn verify_effects_inclusion(
+    checkpoint: &VerifiedCheckpoint,
+    contents: &CheckpointContents,
+    effects: &TransactionEffects,
+) -> LightClientResult<()> {
+    let transaction = effects.transaction_digest();
+    let digests = verify_transaction_inclusion(checkpoint, contents, transaction)?;
+    let actual = effects.digest();
+    if digests.effects != actual {
+        return Err(LightClientError::EffectsMismatch {
+            sequence_number: checkpoint.sequence_number,
+            transaction: *transaction,
+            expected: digests.effects,
+            actual,
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,188 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use fastcrypto::traits::KeyPair;
+use sui_types::committee::ProtocolVersion;
+use sui_types::crypto::AuthorityKeyPair;
+use sui_types::gas::GasCostSummary;
+use sui_types::messages_checkpoint::{EndOfEpochData, SignedCheckpointSummary};
+
+use super::*;
+
+fn certify(
+    committee: &Committee,
+    keys: &[AuthorityKeyPair],
+    sequence_number: CheckpointSequenceNumber,
+    contents: &CheckpointContents,
+    end_of_epoch_data: Option<EndOfEpochData>,
+) -> CertifiedCheckpointSummary {
+    let summary = CheckpointSummary::new(
+        committee.epoch,
+        sequence_number,
+        0,
+        contents,
+        None,
+        GasCostSummary::default(),
+        end_of_epoch_data,
+        0,
+    );
+    let sign_infos: Vec<_> = keys
+        .iter()
+        .map(|k| SignedCheckpointSummary::sign(committee.epoch, &summary, k, k.public().into()))
+        .collect();
+    CertifiedCheckpointSummary::new(summary, sign_infos, committee).unwrap()
+}
+
+fn end_of_epoch_data(next_epoch_committee: &Committee) -> EndOfEpochData {
+    EndOfEpochData {
+        next_epoch_committee: next_epoch_committee.voting_rights.clone(),
+        next_epoch_protocol_version: ProtocolVersion::MIN,
+        epoch_commitments: vec![],
+    }
+}
+
+fn random_contents() -> CheckpointContents {
+    CheckpointContents::new_with_causally_ordered_transactions(
+        [ExecutionDigests::random()].into_iter(),
+    )
+}
+
+#[test]
+fn test_verify_checkpoint() {
+    let (committee, keys) = Committee::new_simple_test_committee();
+    let (other_committee, other_keys) = Committee::new_simple_test_committee();
+    let contents = random_contents();
+
+    let checkpoint = certify(&committee, &keys, 1, &contents, None);
+    verify_checkpoint(&committee, checkpoint.clone()).unwrap();
+    assert!(matches!(
+        verify_checkpoint(&other_committee, checkpoint),
+        Err(LightClientError::InvalidSignature { .. })
+    ));
+
+    // Signed by another committee of the same epoch.
+    let forged = certify(&other_committee, &other_keys, 1, &contents, None);
+    assert!(verify_checkpoint(&committee, forged).is_err());
+}
+
+#[test]
+fn test_committee_handoff() {
+    let (genesis_committee, genesis_keys) = Committee::new_simple_test_committee();
+    let (next_committee, next_keys) = Committee::new_simple_test_committee_of_size(5);
+    let next_committee = Committee::new(1, next_committee.voting_rights.into_iter().collect());
+    let mut tracker = CommitteeTracker::new(genesis_committee.clone());
+
+    // Only the last checkpoint of an epoch hands off the committee.
+    let checkpoint = certify(
+        &genesis_committee,
+        &genesis_keys,
+        1,
+        &random_contents(),
+        None,
+    );
+    assert!(matches!(
+        tracker.advance_epoch(checkpoint),
+        Err(LightClientError::NotEndOfEpoch(1))
+    ));
+
+    let end_of_epoch = certify(
+        &genesis_committee,
+        &genesis_keys,
+        2,
+        &random_contents(),
+        Some(end_of_epoch_data(&next_committee)),
+    );
+    assert_eq!(
+        tracker.advance_epoch(end_of_epoch).unwrap(),
+        &next_committee
+    );
+    assert_eq!(tracker.epoch(), 1);
+
+    // Checkpoints of the new epoch are verified against the new committee only.
+    let checkpoint = certify(&next_committee, &next_keys, 3, &random_contents(), None);
+    tracker.verify_checkpoint(checkpoint).unwrap();
+    let stale = certify(
+        &genesis_committee,
+        &genesis_keys,
+        3,
+        &random_contents(),
+        None,
+    );
+    assert!(matches!(
+        tracker.verify_checkpoint(stale),
+        Err(LightClientError::EpochMismatch {
+            expected: 1,
+            actual: 0,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_invalid_next_epoch_committee() {
+    let (committee, keys) = Committee::new_simple_test_committee();
+    let mut tracker = CommitteeTracker::new(committee.clone());
+
+    let mut data = end_of_epoch_data(&committee);
+    data.next_epoch_committee[0].1 += 1;
+    let checkpoint = certify(&committee, &keys, 1, &random_contents(), Some(data));
+    assert!(matches!(
+        tracker.advance_epoch(checkpoint),
+        Err(LightClientError::InvalidCommittee { epoch: 1, .. })
+    ));
+
+    let mut data = end_of_epoch_data(&committee);
+    data.next_epoch_committee.clear();
+    let checkpoint = certify(&committee, &keys, 1, &random_contents(), Some(data));
+    assert!(tracker.advance_epoch(checkpoint).is_err());
+
+    // The tracker stays at the epoch of the invalid handoff.
+    assert_eq!(tracker.committee(), &committee);
+}
+
+#[test]
+fn test_inclusion() {
+    let (committee, keys) = Committee::new_simple_test_committee();
+    let effects = TransactionEffects::default();
+    let digests = ExecutionDigests::new(*effects.transaction_digest(), effects.digest());
+    let contents = CheckpointContents::new_with_causally_ordered_transactions(
+        [ExecutionDigests::random(), digests].into_iter(),
+    );
+    let checkpoint =
+        verify_checkpoint(&committee, certify(&committee, &keys, 1, &contents, None)).unwrap();
+
+    assert_eq!(
+        verify_transaction_inclusion(&checkpoint, &contents, &digests.transaction).unwrap(),
+        digests
+    );
+    verify_effects_inclusion(&checkpoint, &contents, &effects).unwrap();
+
+    let missing = TransactionDigest::random();
+    assert!(matches!(
+        verify_transaction_inclusion(&checkpoint, &contents, &missing),
+        Err(LightClientError::TransactionNotIncluded { .. })
+    ));
+
+    // Contents of another checkpoint.
+    let other_contents =
+        CheckpointContents::new_with_causally_ordered_transactions([digests].into_iter());
+    assert!(matches!(
+        verify_transaction_inclusion(&checkpoint, &other_contents, &digests.transaction),
+        Err(LightClientError::ContentsMismatch { .. })
+    ));
+
+    // Effects of the transaction which are not the certified ones.
+    let forged = CheckpointContents::new_with_causally_ordered_transactions(
+        [ExecutionDigests::new(
+            digests.transaction,
+            TransactionEffectsDigest::random(),
+        )]
+        .into_iter(),
+    );
+    let checkpoint =
+        verify_checkpoint(&committee, certify(&committee, &keys, 2, &forged, None)).unwrap();
+    assert!(matches!(
+        verify_effects_inclusion(&checkpoint, &forged, &effects),
+        Err(LightClientError::EffectsMismatch { .. })
+    ));
+}
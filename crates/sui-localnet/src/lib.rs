@@ -0,0 +1,223 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A local Sui network of in-process validators and a fullnode, optionally with an indexer, for
+//! testing applications against a real network. Validators can be stopped and restarted while
+//! the network is running, to test how applications behave when validators fail.
+
+use anyhow::{anyhow, Result};
+use std::path::Path;
+use sui_indexer::errors::IndexerError;
+use sui_indexer::test_utils::start_test_indexer;
+use sui_indexer::IndexerConfig;
+use sui_protocol_config::ProtocolVersion;
+use sui_types::base_types::AuthorityName;
+use sui_types::committee::EpochId;
+use sui_types::sui_system_state::SuiSystemState;
+use test_utils::network::{TestCluster, TestClusterBuilder};
+use tokio::task::JoinHandle;
+use tracing::info;
+
+const DEFAULT_NUM_VALIDATORS: usize = 4;
+
+/// Indexer to run against the fullnode of the network.
+#[derive(Debug, Clone)]
+pub struct IndexerOptions {
+    /// Url of the Postgres database of the indexer. The database is reset when the network
+    /// starts.
+    pub db_url: String,
+    /// Port of the JSON-RPC server of the indexer.
+    pub rpc_port: u16,
+}
+
+pub struct LocalNetworkBuilder {
+    num_validators: usize,
+    epoch_duration_ms: Option<u64>,
+    protocol_version: Option<ProtocolVersion>,
+    fullnode_rpc_port: Option<u16>,
+    indexer: Option<IndexerOptions>,
+}
+
+impl LocalNetworkBuilder {
+    pub fn new() -> Self {
+        Self {
+            num_validators: DEFAULT_NUM_VALIDATORS,
+            epoch_duration_ms: None,
+            protocol_version: None,
+            fullnode_rpc_port: None,
+            indexer: None,
+        }
+    }
+
+    pub fn with_num_validators(mut self, num_validators: usize) -> Self {
+        self.num_validators = num_validators;
+        self
+    }
+
+    pub fn with_epoch_duration_ms(mut self, epoch_duration_ms: u64) -> Self {
+        self.epoch_duration_ms = Some(epoch_duration_ms);
+        self
+    }
+
+    /// The protocol version of the genesis. Defaults to the latest version supported by this
+    /// binary.
+    pub fn with_protocol_version(mut self, protocol_version: ProtocolVersion) -> Self {
+        self.protocol_version = Some(protocol_version);
+        self
+    }
+
+    /// Defaults to a random free port.
+    pub fn with_fullnode_rpc_port(mut self, fullnode_rpc_port: u16) -> Self {
+        self.fullnode_rpc_port = Some(fullnode_rpc_port);
+        self
+    }
+
+    pub fn with_indexer(mut self, indexer: IndexerOptions) -> Self {
+        self.indexer = Some(indexer);
+        self
+    }
+
+    /// Starts the network, and returns once the fullnode serves JSON-RPC requests.
+    pub async fn start(self) -> Result<LocalNetwork> {
+        if self.num_validators == 0 {
+            return Err(anyhow!("A local network needs at least one validator"));
+        }
+
+        let mut builder = TestClusterBuilder::new()
+            .with_num_validators(self.num_validators)
+            .enable_fullnode_events();
+        if let Some(epoch_duration_ms) = self.epoch_duration_ms {
+            builder = builder.with_epoch_duration_ms(epoch_duration_ms);
+        }
+        if let Some(protocol_version) = self.protocol_version {
+            builder = builder.with_protocol_version(protocol_version);
+        }
+        if let Some(fullnode_rpc_port) = self.fullnode_rpc_port {
+            builder = builder.set_fullnode_rpc_port(fullnode_rpc_port);
+        }
+        let cluster = builder.build().await?;
+
+        let indexer = match self.indexer {
+            Some(IndexerOptions { db_url, rpc_port }) => {
+                let config = IndexerConfig {
+                    db_url,
+                    rpc_client_url: cluster.rpc_url().to_string(),
+                    rpc_server_url: "127.0.0.1".to_string(),
+                    rpc_server_port: rpc_port,
+                    reset_db: true,
+                    ..Default::default()
+                };
+                let (_store, handle) = start_test_indexer(config).await?;
+                Some((format!("http://127.0.0.1:{rpc_port}"), handle))
+            }
+            None => None,
+        };
+
+        info!(
+            num_validators = self.num_validators,
+            fullnode_url = cluster.rpc_url(),
+            "Started local network"
+        );
+        Ok(LocalNetwork { cluster, indexer })
+    }
+}
+
+impl Default for LocalNetworkBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A running local network. The network is torn down by [LocalNetwork::shutdown], or when it is
+/// dropped.
+pub struct LocalNetwork {
+    cluster: TestCluster,
+    indexer: Option<(String, JoinHandle<Result<(), IndexerError>>)>,
+}
+
+impl LocalNetwork {
+    pub fn builder() -> LocalNetworkBuilder {
+        LocalNetworkBuilder::new()
+    }
+
+    pub fn fullnode_url(&self) -> &str {
+        self.cluster.rpc_url()
+    }
+
+    pub fn indexer_url(&self) -> Option<&str> {
+        self.indexer.as_ref().map(|(url, _)| url.as_str())
+    }
+
+    /// Directory of the configs of the network, including the client config and keystore of
+    /// the accounts funded at genesis.
+    pub fn config_dir(&self) -> &Path {
+        self.cluster.swarm.dir()
+    }
+
+    /// The underlying cluster, for the tests which need more than this API.
+    pub fn test_cluster(&self) -> &TestCluster {
+        &self.cluster
+    }
+
+    pub fn validators(&self) -> Vec<AuthorityName> {
+        self.cluster.get_validator_addresses()
+    }
+
+    pub fn is_validator_running(&self, name: AuthorityName) -> Result<bool> {
+        Ok(self.validator(name)?.is_running())
+    }
+
+    /// Stops a validator, as if it crashed. Its database is kept, so that it recovers its state
+    /// when started again.
+    pub fn stop_validator(&self, name: AuthorityName) -> Result<()> {
+        self.validator(name)?.stop();
+        Ok(())
+    }
+
+    /// Starts a stopped validator. Does nothing if the validator is running.
+    pub async fn start_validator(&self, name: AuthorityName) -> Result<()> {
+        let validator = self.validator(name)?;
+        if !validator.is_running() {
+            validator.start().await?;
+        }
+        Ok(())
+    }
+
+    pub async fn restart_validator(&self, name: AuthorityName) -> Result<()> {
+        self.stop_validator(name)?;
+        self.start_validator(name).await
+    }
+
+    /// Waits until the network reaches `epoch`.
+    pub async fn wait_for_epoch(&self, epoch: EpochId) -> SuiSystemState {
+        self.cluster.wait_for_epoch(Some(epoch)).await
+    }
+
+    /// Stops the indexer, the fullnode and the validators, and removes the directory of the
+    /// network.
+    pub async fn shutdown(self) {
+        if let Some((_, indexer)) = &self.indexer {
+            indexer.abort();
+        }
+        self.cluster.fullnode_handle.sui_node.shutdown().await;
+        for validator in self.cluster.swarm.validators() {
+            validator.stop();
+        }
+        info!("Local network shut down");
+    }
+
+    fn validator(&self, name: AuthorityName) -> Result<&sui_swarm::memory::Node> {
+        self.cluster
+            .swarm
+            .validator(name)
+            .ok_or_else(|| anyhow!("No validator {} in the network", name.concise()))
+    }
+}
+
+impl Drop for LocalNetwork {
+    fn drop(&mut self) {
+        if let Some((_, indexer)) = &self.indexer {
+            indexer.abort();
+        }
+    }
+}
@@ -0,0 +1,30 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_localnet::LocalNetwork;
+use sui_macros::sim_test;
+
+#[sim_test]
+async fn test_stop_and_restart_validator() {
+    let network = LocalNetwork::builder()
+        .with_num_validators(4)
+        .with_epoch_duration_ms(5000)
+        .start()
+        .await
+        .unwrap();
+
+    let validators = network.validators();
+    assert_eq!(validators.len(), 4);
+    network.stop_validator(validators[0]).unwrap();
+    assert!(!network.is_validator_running(validators[0]).unwrap());
+
+    // The remaining validators are a quorum, so the network makes progress.
+    network.wait_for_epoch(1).await;
+
+    network.start_validator(validators[0]).await.unwrap();
+    assert!(network.is_validator_running(validators[0]).unwrap());
+    network.restart_validator(validators[1]).await.unwrap();
+    network.wait_for_epoch(2).await;
+
+    network.shutdown().await;
+}
@@ -0,0 +1,312 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! `sui move analyze` reports how close a package is to the limits enforced when it is published:
+//! the size of the package and of its modules, the dependency graph of the package including its
+//! transitive on-chain dependencies, and the modules and functions close to the limits of the
+//! bytecode verifier.
+//!
+//! The limits are those of the latest protocol version supported by this binary, which may be
+//! ahead of the protocol version of the network the package is published to.
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use move_binary_format::{
+    access::ModuleAccess,
+    binary_views::{BinaryIndexedView, FunctionView},
+    control_flow_graph::ControlFlowGraph,
+    file_format::FunctionDefinitionIndex,
+    file_format_common::BYTECODE_COUNT_MAX,
+    CompiledModule,
+};
+use move_bytecode_verifier::stack_usage;
+use move_cli::base;
+use move_compiler::compiled_unit::CompiledUnitEnum;
+use move_core_types::language_storage::ModuleId;
+use move_package::BuildConfig as MoveBuildConfig;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
+use sui_framework_build::compiled_package::{BuildConfig, CompiledPackage};
+use sui_protocol_config::ProtocolConfig;
+use sui_types::{move_package::MovePackage, object::OBJECT_START_VERSION};
+
+/// Report the size of the package, its dependency graph, and the functions close to the limits
+/// checked at publish time
+#[derive(Parser)]
+pub struct Analyze {
+    /// Percentage of a limit from which a module or a function is reported as close to it
+    #[clap(long = "threshold", default_value = "80")]
+    pub threshold: u64,
+}
+
+/// A measure of a module or a function, and the limit it is checked against.
+struct Usage {
+    what: &'static str,
+    value: u64,
+    limit: u64,
+}
+
+impl Usage {
+    fn new(what: &'static str, value: impl TryInto<u64>, limit: u64) -> Self {
+        Self {
+            what,
+            value: value.try_into().unwrap_or(u64::MAX),
+            limit,
+        }
+    }
+
+    fn is_close(&self, threshold: u64) -> bool {
+        self.value.saturating_mul(100) >= self.limit.saturating_mul(threshold)
+    }
+}
+
+impl Analyze {
+    pub fn execute(self, path: Option<PathBuf>, build_config: MoveBuildConfig) -> Result<()> {
+        let rerooted_path = base::reroot_path(path.clone())?;
+        let build_config = crate::build::resolve_lock_file_path(build_config, path)?;
+        let pkg = sui_framework::build_move_package(
+            &rerooted_path,
+            BuildConfig {
+                config: build_config,
+                run_bytecode_verifier: true,
+                print_diags_to_stderr: true,
+            },
+        )?;
+        let protocol_config = ProtocolConfig::get_for_max_version();
+
+        print_size_report(&pkg, &protocol_config)?;
+        println!();
+        print_dependency_graph(&pkg);
+        println!();
+        self.print_limits_report(&pkg, &protocol_config)?;
+        Ok(())
+    }
+
+    fn print_limits_report(
+        &self,
+        pkg: &CompiledPackage,
+        protocol_config: &ProtocolConfig,
+    ) -> Result<()> {
+        println!(
+            "Modules and functions at {}% or more of a limit:",
+            self.threshold
+        );
+        let mut found = false;
+        for module in pkg.get_modules() {
+            let (functions, back_edges) = function_usages(module, protocol_config)?;
+            let mut usages = vec![(
+                module.name().to_string(),
+                module_usages(module, back_edges, protocol_config),
+            )];
+            usages.extend(
+                functions
+                    .into_iter()
+                    .map(|(name, usages)| (format!("{}::{name}", module.name()), usages)),
+            );
+            for (name, usages) in usages {
+                for usage in usages.iter().filter(|u| u.is_close(self.threshold)) {
+                    found = true;
+                    println!(
+                        "  {name}: {} {} of {}",
+                        usage.what, usage.value, usage.limit
+                    );
+                }
+            }
+        }
+        if !found {
+            println!("  none");
+        }
+        Ok(())
+    }
+}
+
+/// Prints the size of the package as it is counted at publish time, and of every module.
+fn print_size_report(pkg: &CompiledPackage, protocol_config: &ProtocolConfig) -> Result<()> {
+    let modules: Vec<_> = pkg.get_modules().cloned().collect();
+    if modules.is_empty() {
+        return Err(anyhow!("The package has no modules"));
+    }
+    // The size of a package does not depend on its ID, and its linkage table holds all its
+    // transitive dependencies, as it does on-chain.
+    let package = MovePackage::new_system(
+        OBJECT_START_VERSION,
+        &modules,
+        pkg.get_dependency_original_package_ids(),
+    );
+    let size = package.size() as u64;
+    let max_size = protocol_config.max_move_package_size();
+    println!(
+        "Package {}: {size} bytes, {:.1}% of the {max_size} bytes limit ({})",
+        pkg.package.compiled_package_info.package_name,
+        size as f64 * 100.0 / max_size as f64,
+        match max_size.checked_sub(size) {
+            Some(headroom) => format!("{headroom} bytes of headroom"),
+            None => format!("{} bytes over the limit", size - max_size),
+        },
+    );
+    println!(
+        "Modules: {} of {} at most",
+        modules.len(),
+        protocol_config.max_modules_in_publish()
+    );
+
+    let mut module_sizes: Vec<_> = package
+        .serialized_module_map()
+        .iter()
+        .map(|(name, bytes)| (name, name.len() + bytes.len()))
+        .collect();
+    module_sizes.sort_by(|(_, a), (_, b)| b.cmp(a));
+    let name_width = module_sizes.iter().map(|(name, _)| name.len()).max();
+    for (name, module_size) in module_sizes {
+        println!(
+            "  {name:<width$}  {module_size:>8} bytes  {:>5.1}%",
+            module_size as f64 * 100.0 / size as f64,
+            width = name_width.unwrap_or_default(),
+        );
+    }
+    Ok(())
+}
+
+/// Prints the packages the package depends on, directly or transitively, with their on-chain IDs
+/// and the packages they use.
+fn print_dependency_graph(pkg: &CompiledPackage) {
+    let root = pkg.package.compiled_package_info.package_name.to_string();
+    let mut package_of_module: BTreeMap<ModuleId, String> = BTreeMap::new();
+    let mut modules_of_package: BTreeMap<String, Vec<&CompiledModule>> = BTreeMap::new();
+    let units = pkg
+        .package
+        .root_compiled_units
+        .iter()
+        .map(|unit| (root.clone(), unit))
+        .chain(
+            pkg.package
+                .deps_compiled_units
+                .iter()
+                .map(|(name, unit)| (name.to_string(), unit)),
+        );
+    for (package, unit) in units {
+        if let CompiledUnitEnum::Module(named) = &unit.unit {
+            package_of_module.insert(named.module.self_id(), package.clone());
+            modules_of_package
+                .entry(package)
+                .or_default()
+                .push(&named.module);
+        }
+    }
+    modules_of_package.entry(root.clone()).or_default();
+
+    println!("Dependency graph:");
+    // The root package first, then its dependencies by name.
+    let mut packages: Vec<_> = modules_of_package.iter().collect();
+    packages.sort_by_key(|(name, _)| **name != root);
+    for (name, modules) in packages {
+        let uses: BTreeSet<_> = modules
+            .iter()
+            .flat_map(|module| module.immediate_dependencies())
+            .filter_map(|dep| package_of_module.get(&dep))
+            .filter(|dep| *dep != name)
+            .map(String::as_str)
+            .collect();
+        let id = if *name == root {
+            pkg.published_at.as_ref().ok().copied()
+        } else {
+            pkg.dependency_ids
+                .published
+                .iter()
+                .find(|(dep, _)| dep.as_str() == name.as_str())
+                .map(|(_, id)| *id)
+        };
+        let id = match id {
+            Some(id) => id.to_string(),
+            None => "unpublished".to_owned(),
+        };
+        let uses: Vec<_> = uses.into_iter().collect();
+        if uses.is_empty() {
+            println!("  {name} ({id})");
+        } else {
+            println!("  {name} ({id}) -> {}", uses.join(", "));
+        }
+    }
+}
+
+fn module_usages(
+    module: &CompiledModule,
+    back_edges: usize,
+    protocol_config: &ProtocolConfig,
+) -> Vec<Usage> {
+    vec![
+        Usage::new(
+            "struct definitions",
+            module.struct_defs().len(),
+            protocol_config.max_struct_definitions(),
+        ),
+        Usage::new(
+            "function definitions",
+            module.function_defs().len(),
+            protocol_config.max_function_definitions(),
+        ),
+        Usage::new(
+            "back edges",
+            back_edges,
+            protocol_config.max_back_edges_per_module(),
+        ),
+    ]
+}
+
+/// The usages of the limits by every function of `module` with code, and the number of back
+/// edges of the module.
+fn function_usages(
+    module: &CompiledModule,
+    protocol_config: &ProtocolConfig,
+) -> Result<(Vec<(String, Vec<Usage>)>, usize)> {
+    let resolver = BinaryIndexedView::Module(module);
+    let mut functions = vec![];
+    let mut module_back_edges = 0;
+    for (index, function_def) in module.function_defs().iter().enumerate() {
+        let Some(code) = &function_def.code else {
+            continue;
+        };
+        let handle = module.function_handle_at(function_def.function);
+        let name = module.identifier_at(handle.name).to_string();
+        let view =
+            FunctionView::function(module, FunctionDefinitionIndex(index as u16), code, handle);
+        let stack = stack_usage(&resolver, &view)
+            .map_err(|err| anyhow!("Failed to compute the stack usage of {name}: {err}"))?;
+        let back_edges = view.cfg().num_back_edges();
+        module_back_edges += back_edges;
+        functions.push((
+            name,
+            vec![
+                Usage::new("instructions", code.code.len(), BYTECODE_COUNT_MAX),
+                Usage::new(
+                    "basic blocks",
+                    view.cfg().num_blocks(),
+                    protocol_config.max_basic_blocks(),
+                ),
+                Usage::new(
+                    "back edges",
+                    back_edges,
+                    protocol_config.max_back_edges_per_function(),
+                ),
+                Usage::new(
+                    "parameters",
+                    view.parameters().len(),
+                    protocol_config.max_function_parameters(),
+                ),
+                Usage::new(
+                    "stack height",
+                    stack.max_stack_height,
+                    protocol_config.max_value_stack_size(),
+                ),
+                Usage::new(
+                    "pushes in a basic block",
+                    stack.max_block_pushes,
+                    protocol_config.max_push_size(),
+                ),
+            ],
+        ));
+    }
+    Ok((functions, module_back_edges))
+}
@@ -0,0 +1,348 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Coverage reports of the sources of a package in the lcov and Cobertura formats, computed from
+//! the coverage map of a `sui move test --coverage` run, so that Move packages can use standard
+//! coverage dashboards and PR gates.
+
+use anyhow::{anyhow, Result};
+use clap::ArgEnum;
+use move_binary_format::{access::ModuleAccess, file_format::FunctionDefinitionIndex};
+use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
+use move_coverage::coverage_map::CoverageMap;
+use move_package::BuildConfig;
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The coverage map written by `move test --coverage` in the root of the package.
+const COVERAGE_MAP_FILENAME: &str = ".coverage_map.mvcov";
+
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoverageReportFormat {
+    Lcov,
+    Cobertura,
+}
+
+impl CoverageReportFormat {
+    pub fn file_name(self) -> &'static str {
+        match self {
+            CoverageReportFormat::Lcov => "lcov.info",
+            CoverageReportFormat::Cobertura => "cobertura.xml",
+        }
+    }
+}
+
+/// Coverage of the lines of a source file of the package.
+#[derive(Debug, Default)]
+pub struct SourceFileCoverage {
+    pub path: PathBuf,
+    /// Names of the modules defined in the file.
+    pub modules: Vec<String>,
+    /// Execution count of every 1-based line holding bytecode, the count of the most executed
+    /// instruction of the line.
+    pub lines: BTreeMap<usize, u64>,
+    pub functions: Vec<FunctionLineCoverage>,
+}
+
+#[derive(Debug)]
+pub struct FunctionLineCoverage {
+    pub name: String,
+    /// The 1-based line of the definition of the function.
+    pub line: usize,
+    /// Number of calls of the function.
+    pub hits: u64,
+}
+
+impl SourceFileCoverage {
+    fn lines_covered(&self) -> usize {
+        self.lines.values().filter(|hits| **hits > 0).count()
+    }
+}
+
+/// Writes the coverage report of the package at `path` in `format` to the root of the package,
+/// and returns the path of the report.
+pub fn write_coverage_report(
+    path: &Path,
+    build_config: BuildConfig,
+    format: CoverageReportFormat,
+) -> Result<PathBuf> {
+    let files = compute_source_coverage(path, build_config)?;
+    let report = match format {
+        CoverageReportFormat::Lcov => format_lcov(&files),
+        CoverageReportFormat::Cobertura => format_cobertura(path, &files),
+    };
+    let report_path = path.join(format.file_name());
+    fs::write(&report_path, report)?;
+    Ok(report_path)
+}
+
+/// Maps the instructions executed by the last `move test --coverage` run of the package at
+/// `path` to the lines of its sources, through the source maps of its modules.
+pub fn compute_source_coverage(
+    path: &Path,
+    build_config: BuildConfig,
+) -> Result<Vec<SourceFileCoverage>> {
+    let coverage_map_path = path.join(COVERAGE_MAP_FILENAME);
+    if !coverage_map_path.exists() {
+        return Err(anyhow!(
+            "No coverage map at {}, run the tests with `--coverage` first",
+            coverage_map_path.display()
+        ));
+    }
+    let coverage_map = CoverageMap::from_binary_file(coverage_map_path)?.to_unified_exec_map();
+    let package = build_config.compile_package(path, &mut Vec::new())?;
+
+    let mut files: BTreeMap<PathBuf, SourceFileCoverage> = BTreeMap::new();
+    for unit in package.root_modules() {
+        let CompiledUnit::Module(NamedCompiledModule {
+            module, source_map, ..
+        }) = &unit.unit else {
+            continue;
+        };
+        let source = fs::read_to_string(&unit.source_path)?;
+        // Byte offsets of the starts of the lines of the source.
+        let line_starts: Vec<_> = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        let line_of = |offset: u32| line_starts.partition_point(|start| *start <= offset as usize);
+
+        let module_id = module.self_id();
+        let module_map = coverage_map
+            .module_maps
+            .get(&(*module_id.address(), module_id.name().to_owned()));
+        let file = files
+            .entry(unit.source_path.clone())
+            .or_insert_with(|| SourceFileCoverage {
+                path: unit.source_path.clone(),
+                ..Default::default()
+            });
+        file.modules.push(module_id.name().to_string());
+
+        for (index, function_def) in module.function_defs().iter().enumerate() {
+            let Some(code) = &function_def.code else {
+                continue;
+            };
+            let index = FunctionDefinitionIndex(index as u16);
+            let name = module.identifier_at(module.function_handle_at(function_def.function).name);
+            let function_map = module_map.and_then(|map| map.function_maps.get(name));
+            let hits = |offset: usize| {
+                function_map
+                    .and_then(|map| map.get(&(offset as u64)))
+                    .copied()
+                    .unwrap_or(0)
+            };
+
+            let definition = source_map.get_function_source_map(index)?;
+            file.functions.push(FunctionLineCoverage {
+                name: name.to_string(),
+                line: line_of(definition.definition_location.start()),
+                hits: hits(0),
+            });
+            for offset in 0..code.code.len() {
+                let loc = source_map.get_code_location(index, offset as u16)?;
+                let line_hits = file.lines.entry(line_of(loc.start())).or_default();
+                *line_hits = (*line_hits).max(hits(offset));
+            }
+        }
+    }
+    Ok(files.into_values().collect())
+}
+
+/// Formats the coverage in the lcov tracefile format, see `man geninfo`.
+pub fn format_lcov(files: &[SourceFileCoverage]) -> String {
+    let mut out = String::new();
+    for file in files {
+        writeln!(out, "TN:").unwrap();
+        writeln!(out, "SF:{}", file.path.display()).unwrap();
+        for function in &file.functions {
+            writeln!(out, "FN:{},{}", function.line, function.name).unwrap();
+        }
+        for function in &file.functions {
+            writeln!(out, "FNDA:{},{}", function.hits, function.name).unwrap();
+        }
+        writeln!(out, "FNF:{}", file.functions.len()).unwrap();
+        let functions_hit = file.functions.iter().filter(|f| f.hits > 0).count();
+        writeln!(out, "FNH:{functions_hit}").unwrap();
+        for (line, hits) in &file.lines {
+            writeln!(out, "DA:{line},{hits}").unwrap();
+        }
+        writeln!(out, "LF:{}", file.lines.len()).unwrap();
+        writeln!(out, "LH:{}", file.lines_covered()).unwrap();
+        writeln!(out, "end_of_record").unwrap();
+    }
+    out
+}
+
+/// Formats the coverage in the Cobertura XML format, with a class for every source file. Branch
+/// coverage is not reported.
+pub fn format_cobertura(package_path: &Path, files: &[SourceFileCoverage]) -> String {
+    let lines_valid: usize = files.iter().map(|file| file.lines.len()).sum();
+    let lines_covered: usize = files.iter().map(SourceFileCoverage::lines_covered).sum();
+    let rate = |covered: usize, valid: usize| {
+        if valid == 0 {
+            1.0
+        } else {
+            covered as f64 / valid as f64
+        }
+    };
+
+    let mut out = String::new();
+    writeln!(out, r#"<?xml version="1.0" ?>"#).unwrap();
+    writeln!(
+        out,
+        r#"<!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">"#
+    )
+    .unwrap();
+    writeln!(
+        out,
+        r#"<coverage line-rate="{:.4}" branch-rate="0" lines-covered="{lines_covered}" lines-valid="{lines_valid}" branches-covered="0" branches-valid="0" complexity="0" version="0" timestamp="0">"#,
+        rate(lines_covered, lines_valid),
+    )
+    .unwrap();
+    writeln!(out, "  <sources>").unwrap();
+    writeln!(
+        out,
+        "    <source>{}</source>",
+        xml_escape(&package_path.display().to_string())
+    )
+    .unwrap();
+    writeln!(out, "  </sources>").unwrap();
+    writeln!(out, "  <packages>").unwrap();
+    writeln!(
+        out,
+        r#"    <package name="sources" line-rate="{:.4}" branch-rate="0" complexity="0">"#,
+        rate(lines_covered, lines_valid),
+    )
+    .unwrap();
+    writeln!(out, "      <classes>").unwrap();
+    for file in files {
+        let filename = file.path.strip_prefix(package_path).unwrap_or(&file.path);
+        writeln!(
+            out,
+            r#"        <class name="{}" filename="{}" line-rate="{:.4}" branch-rate="0" complexity="0">"#,
+            xml_escape(&file.modules.join(",")),
+            xml_escape(&filename.display().to_string()),
+            rate(file.lines_covered(), file.lines.len()),
+        )
+        .unwrap();
+        writeln!(out, "          <methods/>").unwrap();
+        writeln!(out, "          <lines>").unwrap();
+        for (line, hits) in &file.lines {
+            writeln!(out, r#"            <line number="{line}" hits="{hits}"/>"#).unwrap();
+        }
+        writeln!(out, "          </lines>").unwrap();
+        writeln!(out, "        </class>").unwrap();
+    }
+    writeln!(out, "      </classes>").unwrap();
+    writeln!(out, "    </package>").unwrap();
+    writeln!(out, "  </packages>").unwrap();
+    writeln!(out, "</coverage>").unwrap();
+    out
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files() -> Vec<SourceFileCoverage> {
+        let covered = SourceFileCoverage {
+            path: PathBuf::from("/pkg/sources/a.move"),
+            modules: vec!["a".to_string(), "b".to_string()],
+            lines: BTreeMap::from([(3, 2), (4, 2), (7, 0)]),
+            functions: vec![
+                FunctionLineCoverage {
+                    name: "f".to_string(),
+                    line: 3,
+                    hits: 2,
+                },
+                FunctionLineCoverage {
+                    name: "g".to_string(),
+                    line: 7,
+                    hits: 0,
+                },
+            ],
+        };
+        let empty = SourceFileCoverage {
+            path: PathBuf::from("/pkg/sources/c&d.move"),
+            modules: vec!["c".to_string()],
+            ..Default::default()
+        };
+        vec![covered, empty]
+    }
+
+    #[test]
+    fn test_format_lcov() {
+        let expected = "TN:\n\
+                        SF:/pkg/sources/a.move\n\
+                        FN:3,f\n\
+                        FN:7,g\n\
+                        FNDA:2,f\n\
+                        FNDA:0,g\n\
+                        FNF:2\n\
+                        FNH:1\n\
+                        DA:3,2\n\
+                        DA:4,2\n\
+                        DA:7,0\n\
+                        LF:3\n\
+                        LH:2\n\
+                        end_of_record\n\
+                        TN:\n\
+                        SF:/pkg/sources/c&d.move\n\
+                        FNF:0\n\
+                        FNH:0\n\
+                        LF:0\n\
+                        LH:0\n\
+                        end_of_record\n";
+        assert_eq!(format_lcov(&files()), expected);
+    }
+
+    #[test]
+    fn test_format_cobertura() {
+        let expected = r#"<?xml version="1.0" ?>
+<!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">
+<coverage line-rate="0.6667" branch-rate="0" lines-covered="2" lines-valid="3" branches-covered="0" branches-valid="0" complexity="0" version="0" timestamp="0">
+  <sources>
+    <source>/pkg</source>
+  </sources>
+  <packages>
+    <package name="sources" line-rate="0.6667" branch-rate="0" complexity="0">
+      <classes>
+        <class name="a,b" filename="sources/a.move" line-rate="0.6667" branch-rate="0" complexity="0">
+          <methods/>
+          <lines>
+            <line number="3" hits="2"/>
+            <line number="4" hits="2"/>
+            <line number="7" hits="0"/>
+          </lines>
+        </class>
+        <class name="c" filename="sources/c&amp;d.move" line-rate="1.0000" branch-rate="0" complexity="0">
+          <methods/>
+          <lines>
+          </lines>
+        </class>
+      </classes>
+    </package>
+  </packages>
+</coverage>
+"#;
+        assert_eq!(format_cobertura(Path::new("/pkg"), &files()), expected);
+    }
+
+    #[test]
+    fn test_format_cobertura_without_lines() {
+        // A package without any line holding bytecode is fully covered.
+        let report = format_cobertura(Path::new("/pkg"), &[]);
+        assert!(report.contains(r#"<coverage line-rate="1.0000" branch-rate="0""#));
+    }
+}
@@ -1,22 +1,35 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::anyhow;
 use clap::Parser;
 use move_binary_format::{binary_views::BinaryIndexedView, CompiledModule};
+use move_bytecode_source_map::{mapping::SourceMapping, source_map::SourceMap};
 use move_cli::base;
-use move_disassembler::disassembler::Disassembler;
+use move_disassembler::disassembler::{Disassembler, DisassemblerOptions};
 use move_ir_types::location::Spanned;
 use move_package::BuildConfig;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{BufReader, Read};
 use std::path::Path;
 use std::path::PathBuf;
+use sui_types::base_types::ObjectID;
 
 #[derive(Parser)]
 pub struct Disassemble {
     /// Path to a .mv file to disassemble
     #[clap(name = "module_path")]
-    module_path: PathBuf,
+    module_path: Option<PathBuf>,
+
+    /// Disassemble a module of this on-chain package instead, fetched from the active environment
+    /// of the client. The source lines are interleaved with the bytecode if the package at
+    /// `--path` is verified to be the source of the on-chain package
+    #[clap(long = "package-id")]
+    pub package_id: Option<ObjectID>,
+
+    /// The module of the on-chain package to disassemble
+    #[clap(long = "module")]
+    pub module: Option<String>,
 
     /// Whether to display the disassembly in raw Debug format
     #[clap(long = "Xdebug")]
@@ -29,10 +42,18 @@ impl Disassemble {
         package_path: Option<PathBuf>,
         build_config: BuildConfig,
     ) -> anyhow::Result<()> {
-        if base::reroot_path(Some(self.module_path.clone())).is_ok() {
+        if self.package_id.is_some() {
+            return Err(anyhow!(
+                "Disassembling on-chain packages is only supported by `sui move disassemble`"
+            ));
+        }
+        let module_path = self
+            .module_path
+            .clone()
+            .ok_or_else(|| anyhow!("Either a module path or --package-id is required"))?;
+        if base::reroot_path(Some(module_path.clone())).is_ok() {
             // disassembling bytecode inside the source package that produced it--use the source info
-            let module_name = self
-                .module_path
+            let module_name = module_path
                 .file_stem()
                 .expect("Bad module path")
                 .to_str()
@@ -49,26 +70,45 @@ impl Disassemble {
         }
 
         // disassembling a bytecode file with no source info
-        assert!(
-            Path::new(&self.module_path).exists(),
-            "Bad path to .mv file"
-        );
+        assert!(Path::new(&module_path).exists(), "Bad path to .mv file");
 
         let mut bytes = Vec::new();
-        let mut file = BufReader::new(File::open(self.module_path)?);
+        let mut file = BufReader::new(File::open(module_path)?);
         file.read_to_end(&mut bytes)?;
         // this deserialized a module to the max version of the bytecode but it's OK here because
         // it's not run as part of the deterministic replicated state machine.
         let module = CompiledModule::deserialize(&bytes)?;
+        self.print_disassembly(&module, None)
+    }
 
+    /// Prints the disassembly of `module`. With the source map of the module and the path of its
+    /// source file, the source lines are interleaved with the instructions.
+    pub fn print_disassembly(
+        &self,
+        module: &CompiledModule,
+        source: Option<(SourceMap, PathBuf)>,
+    ) -> anyhow::Result<()> {
         if self.debug {
             println!("{module:#?}");
-        } else {
-            let view = BinaryIndexedView::Module(&module);
-            let d = Disassembler::from_view(view, Spanned::unsafe_no_loc(()).loc)?;
-            println!("{}", d.disassemble()?);
+            return Ok(());
         }
-
+        let view = BinaryIndexedView::Module(module);
+        let d = match source {
+            None => Disassembler::from_view(view, Spanned::unsafe_no_loc(()).loc)?,
+            Some((source_map, source_path)) => {
+                let mut source_mapping = SourceMapping::new(source_map, view);
+                source_mapping.with_source_code((
+                    source_path.display().to_string(),
+                    fs::read_to_string(&source_path)?,
+                ));
+                let options = DisassemblerOptions {
+                    print_source: true,
+                    ..DisassemblerOptions::new()
+                };
+                Disassembler::new(source_mapping, options)
+            }
+        };
+        println!("{}", d.disassemble()?);
         Ok(())
     }
 }
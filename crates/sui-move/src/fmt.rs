@@ -0,0 +1,307 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! `sui move fmt` formats the Move sources of a package:
+//! - lines end with `\n`, without trailing whitespace, and the file ends with a single newline.
+//! - blank lines are collapsed to one, and dropped at the start and the end of the file.
+//! - every level of nesting of `{`, `(` and `[` is indented by 4 spaces, and a line closing a
+//!   block is aligned with the line opening it. A continuation line keeps its extra indentation
+//!   relative to the first line of its block.
+//!
+//! The contents of strings and of block comments are left unchanged, so formatting never changes
+//! the tokens of a source.
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use move_cli::base::reroot_path;
+use move_package::source_package::layout::SourcePackageLayout;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const INDENT_WIDTH: usize = 4;
+
+/// The directories of a package holding Move sources.
+const SOURCE_DIRS: [SourcePackageLayout; 4] = [
+    SourcePackageLayout::Sources,
+    SourcePackageLayout::Tests,
+    SourcePackageLayout::Examples,
+    SourcePackageLayout::Scripts,
+];
+
+/// Format the Move sources of the package
+#[derive(Parser)]
+pub struct Fmt {
+    /// Only check that the sources are formatted, and fail listing the unformatted ones, e.g. in
+    /// CI, instead of formatting them
+    #[clap(long = "check")]
+    pub check: bool,
+}
+
+impl Fmt {
+    pub fn execute(self, path: Option<PathBuf>) -> Result<()> {
+        let rerooted_path = reroot_path(path)?;
+        let mut unformatted = vec![];
+        for file in package_sources(&rerooted_path)? {
+            let source = fs::read_to_string(&file)?;
+            let formatted = format_move_source(&source);
+            if formatted == source {
+                continue;
+            }
+            if !self.check {
+                fs::write(&file, formatted)?;
+            }
+            println!("{}", file.display());
+            unformatted.push(file);
+        }
+        if self.check && !unformatted.is_empty() {
+            return Err(anyhow!(
+                "{} Move files are not formatted, run `sui move fmt` to format them",
+                unformatted.len()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The Move files of the package at `package_path`, in a stable order.
+fn package_sources(package_path: &Path) -> Result<Vec<PathBuf>> {
+    fn collect(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+        if !dir.is_dir() {
+            return Ok(());
+        }
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                collect(&path, files)?;
+            } else if path.extension().map_or(false, |ext| ext == "move") {
+                files.push(path);
+            }
+        }
+        Ok(())
+    }
+    let mut files = vec![];
+    for dir in SOURCE_DIRS {
+        collect(&package_path.join(dir.path()), &mut files)?;
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// A block opened by `{`, `(` or `[`, or the top level of the source.
+struct Block {
+    /// Indentation of the line opening the block, and of the line closing it.
+    opening_indent: usize,
+    contents_indent: usize,
+    /// Original indentation of the first line of the block, which its other lines are indented
+    /// relative to.
+    original_indent: Option<usize>,
+}
+
+/// Where a line starts in the lexical structure of the source.
+#[derive(Default)]
+struct Scanner {
+    /// Nesting depth of the block comments, which can be nested.
+    comment_depth: usize,
+    in_string: bool,
+}
+
+impl Scanner {
+    /// Scans `line`, calling `on_bracket` with every bracket outside strings and comments.
+    fn scan(&mut self, line: &str, mut on_bracket: impl FnMut(char)) {
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            if self.in_string {
+                match c {
+                    '\\' => {
+                        chars.next();
+                    }
+                    '"' => self.in_string = false,
+                    _ => (),
+                }
+            } else if self.comment_depth > 0 {
+                match (c, chars.peek()) {
+                    ('/', Some('*')) => {
+                        chars.next();
+                        self.comment_depth += 1;
+                    }
+                    ('*', Some('/')) => {
+                        chars.next();
+                        self.comment_depth -= 1;
+                    }
+                    _ => (),
+                }
+            } else {
+                match (c, chars.peek()) {
+                    ('/', Some('/')) => return,
+                    ('/', Some('*')) => {
+                        chars.next();
+                        self.comment_depth = 1;
+                    }
+                    ('"', _) => self.in_string = true,
+                    ('{' | '(' | '[' | '}' | ')' | ']', _) => on_bracket(c),
+                    _ => (),
+                }
+            }
+        }
+    }
+}
+
+/// Width of the indentation of `line`, counting a tab as a level of indentation.
+fn indentation_width(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { INDENT_WIDTH } else { 1 })
+        .sum()
+}
+
+/// Number of closing brackets at the start of `contents`.
+fn leading_closing_brackets(contents: &str) -> usize {
+    contents
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .take_while(|c| matches!(c, '}' | ')' | ']'))
+        .count()
+}
+
+/// Formats a Move source, see the [module documentation](self).
+pub fn format_move_source(source: &str) -> String {
+    let mut scanner = Scanner::default();
+    let mut blocks = vec![Block {
+        opening_indent: 0,
+        contents_indent: 0,
+        original_indent: None,
+    }];
+    let mut lines: Vec<String> = vec![];
+    let mut pending_blank_line = false;
+
+    for line in source.lines() {
+        if scanner.in_string || scanner.comment_depth > 0 {
+            // Lines inside strings are contents of the string, and only the trailing whitespace
+            // of lines inside comments is dropped.
+            let line = if scanner.in_string {
+                line.to_owned()
+            } else {
+                line.trim_end().to_owned()
+            };
+            scanner.scan(&line, |_| ());
+            lines.push(line);
+            continue;
+        }
+
+        let contents = line.trim_start();
+        if contents.trim_end().is_empty() {
+            pending_blank_line = !lines.is_empty();
+            continue;
+        }
+        if pending_blank_line {
+            lines.push(String::new());
+            pending_blank_line = false;
+        }
+
+        // A line starting with closing brackets is aligned with the line opening the outermost of
+        // the blocks they close.
+        let closed = leading_closing_brackets(contents).min(blocks.len() - 1);
+        let indent = if closed > 0 {
+            blocks[blocks.len() - closed].opening_indent
+        } else {
+            let block = blocks.last_mut().unwrap();
+            let original = indentation_width(line);
+            let first_line_indent = *block.original_indent.get_or_insert(original);
+            block.contents_indent + original.saturating_sub(first_line_indent)
+        };
+
+        // A block opened after closing a block of an earlier line, e.g. the body of a function
+        // after its parameters, is aligned with the line opening the closed block.
+        let mut opening_indent = indent;
+        scanner.scan(contents, |bracket| match bracket {
+            '{' | '(' | '[' => blocks.push(Block {
+                opening_indent,
+                contents_indent: opening_indent + INDENT_WIDTH,
+                original_indent: None,
+            }),
+            _ => {
+                if blocks.len() > 1 {
+                    let closed = blocks.pop().unwrap();
+                    opening_indent = opening_indent.min(closed.opening_indent);
+                }
+            }
+        });
+        // The trailing whitespace of a line ending inside a string is part of the string.
+        let contents = if scanner.in_string {
+            contents
+        } else {
+            contents.trim_end()
+        };
+        lines.push(format!("{}{}", " ".repeat(indent), contents));
+    }
+
+    let mut formatted = lines.join("\n");
+    formatted.push('\n');
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_move_source;
+
+    #[test]
+    fn test_format_move_source() {
+        let source = "module a::m {\r\n\
+                      \tuse sui::object;   \n\
+                      \n\
+                      \n\
+                      \x20 fun f(x: u64,\n\
+                      \x20        y: u64): u64 {\n\
+                      \x20   if (x > y) {\n\
+                      \x20           x\n\
+                      \x20   } else { y }\n\
+                      \x20 }\n\
+                      /* keep\n\
+                      \x20     as is */\n\
+                      \x20 const S: vector<u8> = b\"{\n\
+                      \x20  (\";\n\
+                      }\n\n";
+        let expected = "module a::m {\n\
+                        \x20   use sui::object;\n\
+                        \n\
+                        \x20   fun f(x: u64,\n\
+                        \x20       y: u64): u64 {\n\
+                        \x20       if (x > y) {\n\
+                        \x20           x\n\
+                        \x20       } else { y }\n\
+                        \x20   }\n\
+                        \x20   /* keep\n\
+                        \x20     as is */\n\
+                        \x20   const S: vector<u8> = b\"{\n\
+                        \x20  (\";\n\
+                        }\n";
+        let formatted = format_move_source(source);
+        assert_eq!(formatted, expected);
+        assert_eq!(format_move_source(&formatted), formatted);
+    }
+
+    #[test]
+    fn test_format_multi_line_byte_string() {
+        // The whitespace at the end of the lines of the string, and at the start of its lines
+        // after the first one, is part of the string.
+        let source = "module a::m {\n\
+                      \x20       const S: vector<u8> = b\"first  \n\
+                      \x20   second\t\n\
+                      \n\
+                      third\";   \n\
+                      \x20       const T: u64 = 0;\n\
+                      }\n";
+        let expected = "module a::m {\n\
+                        \x20   const S: vector<u8> = b\"first  \n\
+                        \x20   second\t\n\
+                        \n\
+                        third\";   \n\
+                        \x20   const T: u64 = 0;\n\
+                        }\n";
+        let formatted = format_move_source(source);
+        assert_eq!(formatted, expected);
+        assert_eq!(format_move_source(&formatted), formatted);
+    }
+}
@@ -9,28 +9,40 @@ use move_package::BuildConfig;
 use move_unit_test::UnitTestingConfig;
 use std::path::PathBuf;
 
+#[cfg(feature = "analyze")]
+pub mod analyze;
 #[cfg(feature = "build")]
 pub mod build;
 #[cfg(feature = "calibrate")]
 mod cost_calib;
 #[cfg(feature = "coverage")]
 pub mod coverage;
+#[cfg(feature = "unit_test")]
+pub mod coverage_report;
 #[cfg(feature = "disassemble")]
 pub mod disassemble;
+#[cfg(feature = "fmt")]
+pub mod fmt;
 pub mod new;
 #[cfg(feature = "prove")]
 pub mod prove;
 #[cfg(feature = "unit_test")]
 pub mod unit_test;
+#[cfg(feature = "unit_test")]
+pub mod watch;
 
 #[derive(Parser)]
 pub enum Command {
+    #[cfg(feature = "analyze")]
+    Analyze(analyze::Analyze),
     #[cfg(feature = "build")]
     Build(build::Build),
     #[cfg(feature = "coverage")]
     Coverage(coverage::Coverage),
     #[cfg(feature = "disassemble")]
     Disassemble(disassemble::Disassemble),
+    #[cfg(feature = "fmt")]
+    Fmt(fmt::Fmt),
     New(new::New),
     #[cfg(feature = "prove")]
     Prove(prove::Prove),
@@ -53,12 +65,16 @@ pub fn execute_move_command(
     command: Command,
 ) -> anyhow::Result<()> {
     match command {
+        #[cfg(feature = "analyze")]
+        Command::Analyze(c) => c.execute(package_path, build_config),
         #[cfg(feature = "build")]
         Command::Build(c) => c.execute(package_path, build_config),
         #[cfg(feature = "coverage")]
         Command::Coverage(c) => c.execute(package_path, build_config),
         #[cfg(feature = "disassemble")]
         Command::Disassemble(c) => c.execute(package_path, build_config),
+        #[cfg(feature = "fmt")]
+        Command::Fmt(c) => c.execute(package_path),
         Command::New(c) => c.execute(package_path),
         #[cfg(feature = "prove")]
         Command::Prove(c) => c.execute(package_path, build_config),
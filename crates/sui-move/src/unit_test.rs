@@ -2,11 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::build;
+use crate::coverage_report::{write_coverage_report, CoverageReportFormat};
+use crate::watch;
+use anyhow::anyhow;
 use clap::Parser;
 use move_cli::base::{
     self,
     test::{self, UnitTestResult},
 };
+use move_compiler::{compiled_unit::AnnotatedCompiledUnit, diagnostics::FilesSourceText};
 use move_package::BuildConfig;
 use move_unit_test::{extensions::set_extension_hook, UnitTestingConfig};
 use move_vm_runtime::native_extensions::NativeContextExtensions;
@@ -30,6 +34,13 @@ const MAX_UNIT_TEST_INSTRUCTIONS: u64 = 1_000_000;
 pub struct Test {
     #[clap(flatten)]
     pub test: test::Test,
+    /// Also write a report of the coverage of the sources of the package in this format, to
+    /// `lcov.info` or `cobertura.xml` in the root of the package. Requires `--coverage`
+    #[clap(long = "coverage-report", arg_enum)]
+    pub coverage_report: Option<CoverageReportFormat>,
+    /// Watch the sources of the package, and rerun the tests affected by every change
+    #[clap(long = "watch")]
+    pub watch: bool,
 }
 
 impl Test {
@@ -39,8 +50,22 @@ impl Test {
         build_config: BuildConfig,
         unit_test_config: UnitTestingConfig,
     ) -> anyhow::Result<UnitTestResult> {
+        if self.coverage_report.is_some() && !self.test.compute_coverage {
+            return Err(anyhow!("--coverage-report requires --coverage"));
+        }
         // find manifest file directory from a given path or (if missing) from current dir
         let rerooted_path = base::reroot_path(path)?;
+        if self.watch {
+            if self.coverage_report.is_some() {
+                return Err(anyhow!("--coverage-report is not supported with --watch"));
+            }
+            return watch::watch_and_rerun_tests(
+                &rerooted_path,
+                build_config,
+                unit_test_config,
+                self.test.compute_coverage,
+            );
+        }
         // pre build for Sui-specific verifications
         let with_unpublished_deps = false;
         let dump_bytecode_as_base64 = false;
@@ -57,12 +82,17 @@ impl Test {
             generate_struct_layouts,
             dump_package_digest,
         )?;
-        run_move_unit_tests(
+        let result = run_move_unit_tests(
             &rerooted_path,
-            build_config,
+            build_config.clone(),
             Some(unit_test_config),
             self.test.compute_coverage,
-        )
+        )?;
+        if let Some(format) = self.coverage_report {
+            let report_path = write_coverage_report(&rerooted_path, build_config, format)?;
+            println!("Wrote the coverage report to {}", report_path.display());
+        }
+        Ok(result)
     }
 }
 
@@ -76,6 +106,24 @@ pub fn run_move_unit_tests(
     build_config: BuildConfig,
     config: Option<UnitTestingConfig>,
     compute_coverage: bool,
+) -> anyhow::Result<UnitTestResult> {
+    run_move_unit_tests_with_hook(path, build_config, config, compute_coverage, |_, _, _| {
+        Ok(())
+    })
+}
+
+/// Like [run_move_unit_tests], but calls `compiled_units_hook` with the units compiled from the
+/// package before running its tests, see [test::run_move_unit_tests_with_hook].
+pub fn run_move_unit_tests_with_hook(
+    path: &Path,
+    build_config: BuildConfig,
+    config: Option<UnitTestingConfig>,
+    compute_coverage: bool,
+    compiled_units_hook: impl FnMut(
+        &FilesSourceText,
+        &[AnnotatedCompiledUnit],
+        &mut UnitTestingConfig,
+    ) -> anyhow::Result<()>,
 ) -> anyhow::Result<UnitTestResult> {
     // bind the extension hook if it has not yet been done
     Lazy::force(&SET_EXTENSION_HOOK);
@@ -83,7 +131,7 @@ pub fn run_move_unit_tests(
     let config = config
         .unwrap_or_else(|| UnitTestingConfig::default_with_bound(Some(MAX_UNIT_TEST_INSTRUCTIONS)));
 
-    move_cli::base::test::run_move_unit_tests(
+    test::run_move_unit_tests_with_hook(
         path,
         build_config,
         UnitTestingConfig {
@@ -94,6 +142,7 @@ pub fn run_move_unit_tests(
         Some(initial_cost_schedule_for_unit_tests()),
         compute_coverage,
         &mut std::io::stdout(),
+        compiled_units_hook,
     )
 }
 
@@ -0,0 +1,362 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Watch mode of `sui move test`, which reruns the tests affected by every change to the sources
+//! of the package.
+//!
+//! The sources are polled for changes of their contents, so that saving a file without changes
+//! does not trigger a run. After a change, the package is rebuilt once, and only the tests of the
+//! modules changed since the last successful run, and of the modules depending on them, are run
+//! again. A failed build is followed by a run of every test, as the tests of the files changed
+//! while it failed could not be selected.
+//!
+//! The dependencies of the package are resolved from the cache of the package system after the
+//! first run, instead of fetching the latest version of every git dependency on each change, until
+//! the manifest changes. The Move compiler has no incremental compilation of the root package, so
+//! a run still pays for its compilation, but no longer for the resolution of its dependencies or
+//! for the tests of the unchanged modules.
+
+use crate::unit_test::run_move_unit_tests_with_hook;
+use anyhow::Result;
+use move_binary_format::access::ModuleAccess;
+use move_cli::base::test::UnitTestResult;
+use move_compiler::{
+    compiled_unit::{AnnotatedCompiledUnit, CompiledUnitEnum},
+    diagnostics::FilesSourceText,
+};
+use move_package::{source_package::layout::SourcePackageLayout, BuildConfig};
+use move_unit_test::UnitTestingConfig;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+use sui_framework_build::compiled_package::{verify_modules, BuildConfig as SuiBuildConfig};
+
+/// Interval between two scans of the sources of the package.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The directories of a package holding the sources built in test mode.
+const SOURCE_DIRS: [SourcePackageLayout; 3] = [
+    SourcePackageLayout::Sources,
+    SourcePackageLayout::Tests,
+    SourcePackageLayout::Examples,
+];
+
+/// Contents of the manifest and of the Move sources of a package, by canonical path.
+#[derive(PartialEq, Eq)]
+struct SourceSnapshot(BTreeMap<PathBuf, Vec<u8>>);
+
+impl SourceSnapshot {
+    fn take(package_path: &Path) -> Result<Self> {
+        let mut files = BTreeMap::new();
+        let manifest = package_path.join(SourcePackageLayout::Manifest.path());
+        files.insert(fs::canonicalize(&manifest)?, fs::read(&manifest)?);
+        for dir in SOURCE_DIRS {
+            collect_move_files(&package_path.join(dir.path()), &mut files)?;
+        }
+        Ok(Self(files))
+    }
+
+    /// The files added, removed or modified since `previous`.
+    fn changed_files(&self, previous: &SourceSnapshot) -> BTreeSet<PathBuf> {
+        let modified = self
+            .0
+            .iter()
+            .filter(|(path, contents)| previous.0.get(*path) != Some(contents))
+            .map(|(path, _)| path.clone());
+        let removed = previous
+            .0
+            .keys()
+            .filter(|path| !self.0.contains_key(*path))
+            .cloned();
+        modified.chain(removed).collect()
+    }
+}
+
+fn collect_move_files(dir: &Path, files: &mut BTreeMap<PathBuf, Vec<u8>>) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_move_files(&path, files)?;
+        } else if path.extension().map_or(false, |ext| ext == "move") {
+            files.insert(fs::canonicalize(&path)?, fs::read(&path)?);
+        }
+    }
+    Ok(())
+}
+
+/// The modules of the root package, with their source file and the modules of the root package
+/// they use.
+struct ModuleGraph {
+    source_files: BTreeMap<String, PathBuf>,
+    dependencies: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl ModuleGraph {
+    /// The graph of the modules of the root package among `units`, which are the modules compiled
+    /// from the files of `sources`. The modules are also verified like `sui move build` does, so
+    /// that the tests only run against code passing the Sui verifiers.
+    fn new(
+        files: &FilesSourceText,
+        units: &[AnnotatedCompiledUnit],
+        sources: &SourceSnapshot,
+    ) -> Result<Self> {
+        let mut source_files = BTreeMap::new();
+        let mut modules = vec![];
+        for unit in units {
+            let CompiledUnitEnum::Module(annotated) = unit else {
+                continue;
+            };
+            let Some((file_name, _)) = files.get(&annotated.loc.file_hash()) else {
+                continue;
+            };
+            let source_path = fs::canonicalize(file_name.as_str())?;
+            if sources.0.contains_key(&source_path) {
+                let module = &annotated.named_module.module;
+                source_files.insert(module.self_id().name().to_string(), source_path);
+                modules.push(module);
+            }
+        }
+        verify_modules(modules.iter().copied(), &SuiBuildConfig::fn_info(units))?;
+        let dependencies = modules
+            .iter()
+            .map(|module| {
+                let name = module.self_id().name().to_string();
+                let dependencies = module
+                    .immediate_dependencies()
+                    .iter()
+                    .map(|dep| dep.name().to_string())
+                    .filter(|dep| source_files.contains_key(dep))
+                    .collect();
+                (name, dependencies)
+            })
+            .collect();
+        Ok(Self {
+            source_files,
+            dependencies,
+        })
+    }
+
+    /// The modules defined in `changed_files`, and the modules depending on them, or `None` if
+    /// every module is affected, e.g. by a change of the manifest.
+    fn affected_modules(&self, changed_files: &BTreeSet<PathBuf>) -> Option<BTreeSet<String>> {
+        let mut affected: BTreeSet<_> = self
+            .source_files
+            .iter()
+            .filter(|(_, path)| changed_files.contains(*path))
+            .map(|(name, _)| name.clone())
+            .collect();
+        let defining_modules: BTreeSet<_> = self.source_files.values().collect();
+        if affected.is_empty() || changed_files.iter().any(|f| !defining_modules.contains(f)) {
+            return None;
+        }
+        loop {
+            let dependents: Vec<_> = self
+                .dependencies
+                .iter()
+                .filter(|(name, deps)| !affected.contains(*name) && !deps.is_disjoint(&affected))
+                .map(|(name, _)| name.clone())
+                .collect();
+            if dependents.is_empty() {
+                return Some(affected);
+            }
+            affected.extend(dependents);
+        }
+    }
+}
+
+/// The files changed since the last successful run, whose tests are run by the next run.
+#[derive(Debug, PartialEq, Eq)]
+enum PendingChanges {
+    /// Every test is run, e.g. by the first run, or after a failed build.
+    All,
+    Files(BTreeSet<PathBuf>),
+}
+
+impl PendingChanges {
+    fn add(&mut self, changed_files: BTreeSet<PathBuf>) {
+        if let Self::Files(files) = self {
+            files.extend(changed_files);
+        }
+    }
+
+    /// Updates the changes with the `result` of a run: a successful run clears them, failing tests
+    /// keep them so that the tests are run again, and a failed build runs every test next.
+    fn finish_run(&mut self, result: &Result<UnitTestResult>) {
+        match result {
+            Ok(UnitTestResult::Success) => *self = Self::Files(BTreeSet::new()),
+            Ok(UnitTestResult::Failure) => (),
+            Err(_) => *self = Self::All,
+        }
+    }
+
+    fn affected_modules(&self, graph: &ModuleGraph) -> Option<BTreeSet<String>> {
+        match self {
+            Self::All => None,
+            Self::Files(files) => graph.affected_modules(files),
+        }
+    }
+}
+
+/// Runs the tests of the package at `path`, and then the tests affected by every change to its
+/// sources, until the process is interrupted.
+pub fn watch_and_rerun_tests(
+    path: &Path,
+    build_config: BuildConfig,
+    unit_test_config: UnitTestingConfig,
+    compute_coverage: bool,
+) -> Result<UnitTestResult> {
+    let manifest = fs::canonicalize(path.join(SourcePackageLayout::Manifest.path()))?;
+    let mut snapshot = SourceSnapshot::take(path)?;
+    let mut pending = PendingChanges::All;
+    let mut skip_fetch_latest_git_deps = build_config.skip_fetch_latest_git_deps;
+    loop {
+        let result = run_move_unit_tests_with_hook(
+            path,
+            BuildConfig {
+                skip_fetch_latest_git_deps,
+                ..build_config.clone()
+            },
+            Some(unit_test_config.clone()),
+            compute_coverage,
+            |files, units, config| {
+                let graph = ModuleGraph::new(files, units, &snapshot)?;
+                config.module_filter = pending.affected_modules(&graph);
+                if let Some(modules) = &config.module_filter {
+                    let modules: Vec<_> = modules.iter().map(String::as_str).collect();
+                    println!("Running the tests affected by {}", modules.join(", "));
+                }
+                Ok(())
+            },
+        );
+        // Errors, e.g. of compilation, are fixed by the next change.
+        if let Err(err) = &result {
+            eprintln!("{err:?}");
+        }
+        pending.finish_run(&result);
+        // The dependencies were fetched by this run, and are only fetched again if the manifest
+        // changes.
+        skip_fetch_latest_git_deps = true;
+
+        println!("Watching {} for changes...", path.display());
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let next = SourceSnapshot::take(path)?;
+            if next != snapshot {
+                let changed_files = next.changed_files(&snapshot);
+                if changed_files.contains(&manifest) {
+                    skip_fetch_latest_git_deps = build_config.skip_fetch_latest_git_deps;
+                }
+                pending.add(changed_files);
+                snapshot = next;
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ModuleGraph, PendingChanges};
+    use anyhow::anyhow;
+    use move_cli::base::test::UnitTestResult;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::path::PathBuf;
+
+    /// `b` uses `a`, `c` uses `b`, and `d` is independent.
+    fn module_graph() -> ModuleGraph {
+        let mut source_files = BTreeMap::new();
+        let mut dependencies = BTreeMap::new();
+        for (name, dep) in [("a", ""), ("b", "a"), ("c", "b"), ("d", "")] {
+            source_files.insert(name.to_string(), source_path(name));
+            let deps = (!dep.is_empty()).then(|| dep.to_string());
+            dependencies.insert(name.to_string(), deps.into_iter().collect());
+        }
+        ModuleGraph {
+            source_files,
+            dependencies,
+        }
+    }
+
+    fn source_path(module: &str) -> PathBuf {
+        PathBuf::from(format!("/package/sources/{module}.move"))
+    }
+
+    fn affected_modules(graph: &ModuleGraph, changed: &[PathBuf]) -> Option<Vec<String>> {
+        let changed: BTreeSet<_> = changed.iter().cloned().collect();
+        graph
+            .affected_modules(&changed)
+            .map(|modules| modules.into_iter().collect())
+    }
+
+    #[test]
+    fn test_affected_modules() {
+        let graph = module_graph();
+        assert_eq!(
+            affected_modules(&graph, &[source_path("a")]),
+            Some(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+        );
+        assert_eq!(
+            affected_modules(&graph, &[source_path("b"), source_path("d")]),
+            Some(vec!["b".to_string(), "c".to_string(), "d".to_string()])
+        );
+        assert_eq!(
+            affected_modules(&graph, &[source_path("c")]),
+            Some(vec!["c".to_string()])
+        );
+        // A change of the manifest, or of a file without modules, affects every module.
+        let manifest = PathBuf::from("/package/Move.toml");
+        assert_eq!(affected_modules(&graph, &[manifest]), None);
+        assert_eq!(
+            affected_modules(&graph, &[source_path("a"), source_path("new")]),
+            None
+        );
+        assert_eq!(affected_modules(&graph, &[]), None);
+    }
+
+    #[test]
+    fn test_pending_changes() {
+        let graph = module_graph();
+        let changed = |modules: &[&str]| modules.iter().map(|m| source_path(m)).collect();
+        let affected = |pending: &PendingChanges| {
+            pending
+                .affected_modules(&graph)
+                .map(|modules| modules.into_iter().collect::<Vec<_>>())
+        };
+
+        // The first run runs every test.
+        let mut pending = PendingChanges::All;
+        pending.add(changed(&["c"]));
+        assert_eq!(affected(&pending), None);
+        pending.finish_run(&Ok(UnitTestResult::Success));
+        assert_eq!(pending, PendingChanges::Files(BTreeSet::new()));
+
+        // The changes are kept until a run succeeds, so that failing tests are run again.
+        pending.add(changed(&["c"]));
+        pending.finish_run(&Ok(UnitTestResult::Failure));
+        pending.add(changed(&["d"]));
+        assert_eq!(
+            affected(&pending),
+            Some(vec!["c".to_string(), "d".to_string()])
+        );
+        pending.finish_run(&Ok(UnitTestResult::Success));
+
+        // A failed build runs every test next, including the tests of the files changed meanwhile.
+        pending.add(changed(&["c"]));
+        pending.finish_run(&Err(anyhow!("Compilation error")));
+        pending.add(changed(&["d"]));
+        assert_eq!(affected(&pending), None);
+        pending.finish_run(&Ok(UnitTestResult::Success));
+        pending.add(changed(&["b"]));
+        assert_eq!(
+            affected(&pending),
+            Some(vec!["b".to_string(), "c".to_string()])
+        );
+    }
+}
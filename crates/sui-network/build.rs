@@ -87,9 +87,33 @@ fn main() -> Result<()> {
         )
         .build();
 
+    let remote_signer_service = Service::builder()
+        .name("RemoteSigner")
+        .package("sui.validator")
+        .comment("Signing service holding validator keys outside of the validator host")
+        .method(
+            Method::builder()
+                .name("sign")
+                .route_name("Sign")
+                .input_type("sui_types::remote_signer::SignRequest")
+                .output_type("sui_types::remote_signer::SignResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
+        .method(
+            Method::builder()
+                .name("public_key")
+                .route_name("PublicKey")
+                .input_type("sui_types::remote_signer::PublicKeyRequest")
+                .output_type("sui_types::remote_signer::PublicKeyResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
+        .build();
+
     Builder::new()
         .out_dir(&out_dir)
-        .compile(&[validator_service]);
+        .compile(&[validator_service, remote_signer_service]);
 
     build_anemo_services(&out_dir);
 
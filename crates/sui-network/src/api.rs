@@ -5,7 +5,16 @@ mod validator {
     include!(concat!(env!("OUT_DIR"), "/sui.validator.Validator.rs"));
 }
 
+mod remote_signer {
+    include!(concat!(env!("OUT_DIR"), "/sui.validator.RemoteSigner.rs"));
+}
+
 pub use validator::{
     validator_client::ValidatorClient,
     validator_server::{Validator, ValidatorServer},
 };
+
+pub use remote_signer::{
+    remote_signer_client::RemoteSignerClient,
+    remote_signer_server::{RemoteSigner, RemoteSignerServer},
+};
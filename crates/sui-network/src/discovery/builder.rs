@@ -9,7 +9,7 @@ use std::{
     collections::HashMap,
     sync::{Arc, RwLock},
 };
-use sui_config::p2p::P2pConfig;
+use sui_config::p2p::{P2pConfig, SeedPeer};
 use tap::Pipe;
 use tokio::{
     sync::{oneshot, watch},
@@ -64,9 +64,11 @@ impl Builder {
         } = self;
         let config = config.unwrap();
         let (sender, receiver) = oneshot::channel();
+        let (seed_peers_tx, seed_peers_rx) = watch::channel(config.seed_peers.clone());
 
         let handle = Handle {
             _shutdown_handle: Arc::new(sender),
+            seed_peers_tx: Arc::new(seed_peers_tx),
         };
 
         let state = State {
@@ -88,6 +90,7 @@ impl Builder {
                 shutdown_handle: receiver,
                 state,
                 trusted_peer_change_rx,
+                seed_peers_rx,
             },
             server,
         )
@@ -101,6 +104,7 @@ pub struct UnstartedDiscovery {
     pub(super) shutdown_handle: oneshot::Receiver<()>,
     pub(super) state: Arc<RwLock<State>>,
     pub(super) trusted_peer_change_rx: watch::Receiver<TrustedPeerChangeEvent>,
+    pub(super) seed_peers_rx: watch::Receiver<Vec<SeedPeer>>,
 }
 
 impl UnstartedDiscovery {
@@ -111,6 +115,7 @@ impl UnstartedDiscovery {
             shutdown_handle,
             state,
             trusted_peer_change_rx,
+            seed_peers_rx,
         } = self;
 
         let discovery_config = config.discovery.clone().unwrap_or_default();
@@ -126,6 +131,7 @@ impl UnstartedDiscovery {
                 shutdown_handle,
                 state,
                 trusted_peer_change_rx,
+                seed_peers_rx,
             },
             handle,
         )
@@ -143,4 +149,14 @@ impl UnstartedDiscovery {
 /// been dropped.
 pub struct Handle {
     _shutdown_handle: Arc<oneshot::Sender<()>>,
+    seed_peers_tx: Arc<watch::Sender<Vec<SeedPeer>>>,
+}
+
+impl Handle {
+    /// Replace the seed peers of the config, e.g. when the config is reloaded. Seed peers with a
+    /// peer id are preferred peers. Removed preferred peers stay connected until the connection is
+    /// lost, but are no longer reconnected to.
+    pub fn update_seed_peers(&self, seed_peers: Vec<SeedPeer>) {
+        self.seed_peers_tx.send_replace(seed_peers);
+    }
 }
@@ -6,7 +6,7 @@ use anemo::{types::PeerEvent, Network, Peer, PeerId, Request, Response};
 use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::{Arc, RwLock},
     time::Duration,
 };
@@ -76,6 +76,7 @@ struct DiscoveryEventLoop {
     shutdown_handle: oneshot::Receiver<()>,
     state: Arc<RwLock<State>>,
     trusted_peer_change_rx: watch::Receiver<TrustedPeerChangeEvent>,
+    seed_peers_rx: watch::Receiver<Vec<SeedPeer>>,
 }
 
 impl DiscoveryEventLoop {
@@ -104,6 +105,10 @@ impl DiscoveryEventLoop {
                     let event: TrustedPeerChangeEvent = self.trusted_peer_change_rx.borrow_and_update().clone();
                     self.handle_trusted_peer_change_event(event);
                 }
+                Ok(()) = self.seed_peers_rx.changed() => {
+                    let seed_peers = self.seed_peers_rx.borrow_and_update().clone();
+                    self.handle_seed_peers_change(seed_peers);
+                }
                 Some(task_result) = self.tasks.join_next() => {
                     match task_result {
                         Ok(()) => {},
@@ -189,6 +194,19 @@ impl DiscoveryEventLoop {
         }
     }
 
+    fn handle_seed_peers_change(&mut self, seed_peers: Vec<SeedPeer>) {
+        let new_peer_ids: HashSet<_> = seed_peers.iter().filter_map(|p| p.peer_id).collect();
+        for peer_id in self.config.seed_peers.iter().filter_map(|p| p.peer_id) {
+            if !new_peer_ids.contains(&peer_id) {
+                debug!(?peer_id, "Remove seed peer from preferred peers.");
+                self.network.known_peers().remove(&peer_id);
+            }
+        }
+        info!("Updated seed peers: {:?}", seed_peers);
+        self.config.seed_peers = seed_peers;
+        self.configure_preferred_peers();
+    }
+
     fn handle_peer_event(&mut self, peer_event: Result<PeerEvent, RecvError>) {
         match peer_event {
             Ok(PeerEvent::NewPeer(peer_id)) => {
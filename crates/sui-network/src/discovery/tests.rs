@@ -123,6 +123,29 @@ async fn make_connection_to_seed_peer_with_peer_id() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn seed_peers_change_updates_preferred_peers() -> Result<()> {
+    let seed_peer = |peer_id| SeedPeer {
+        peer_id: Some(PeerId(peer_id)),
+        address: "/dns/localhost/udp/8080".parse().unwrap(),
+    };
+
+    let mut config = P2pConfig::default();
+    config.seed_peers.push(seed_peer([1; 32]));
+    let (builder, server) = Builder::new(create_test_channel().1).config(config).build();
+    let network = build_network(|router| router.add_rpc_service(server));
+    let (mut event_loop, _handle) = builder.build(network.clone());
+    event_loop.configure_preferred_peers();
+    assert!(network.known_peers().get(&PeerId([1; 32])).is_some());
+
+    event_loop.handle_seed_peers_change(vec![seed_peer([2; 32])]);
+    assert!(network.known_peers().get(&PeerId([1; 32])).is_none());
+    let peer_info = network.known_peers().get(&PeerId([2; 32])).unwrap();
+    assert_eq!(peer_info.affinity, PeerAffinity::High);
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "current_thread", start_paused = true)]
 async fn three_nodes_can_connect_via_discovery() -> Result<()> {
     // Setup the peer that will be the seed for the other two
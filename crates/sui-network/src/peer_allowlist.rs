@@ -0,0 +1,102 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anemo::rpc::Status;
+use anemo::{PeerId, Request, Response};
+use anemo_tower::auth::AuthorizeRequest;
+use bytes::Bytes;
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+
+/// The peers allowed to send requests to this node, which can be replaced while the network is
+/// running, e.g. when the config of the node is reloaded. An empty allowlist allows every peer.
+#[derive(Clone, Debug, Default)]
+pub struct PeerAllowlist {
+    peers: Arc<RwLock<HashSet<PeerId>>>,
+}
+
+impl PeerAllowlist {
+    pub fn new(peers: impl IntoIterator<Item = PeerId>) -> Self {
+        Self {
+            peers: Arc::new(RwLock::new(peers.into_iter().collect())),
+        }
+    }
+
+    /// Replace the allowed peers. Requests in flight are not affected.
+    pub fn update(&self, peers: impl IntoIterator<Item = PeerId>) {
+        *self.peers.write().unwrap() = peers.into_iter().collect();
+    }
+
+    pub fn is_allowed(&self, peer_id: &PeerId) -> bool {
+        let peers = self.peers.read().unwrap();
+        peers.is_empty() || peers.contains(peer_id)
+    }
+}
+
+impl AuthorizeRequest for PeerAllowlist {
+    fn authorize(&self, request: &mut Request<Bytes>) -> Result<(), Response<Bytes>> {
+        use anemo::types::response::{IntoResponse, StatusCode};
+
+        if self.peers.read().unwrap().is_empty() {
+            return Ok(());
+        }
+        let peer_id = request.peer_id().ok_or_else(|| {
+            Status::new_with_message(StatusCode::BadRequest, "unable to query sender's PeerId")
+                .into_response()
+        })?;
+
+        if self.is_allowed(peer_id) {
+            Ok(())
+        } else {
+            Err(
+                Status::new_with_message(StatusCode::BadRequest, "peer is not in the allowlist")
+                    .into_response(),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anemo::types::response::StatusCode;
+    use anemo_tower::auth::RequireAuthorizationLayer;
+    use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+    #[tokio::test]
+    async fn authorize_request_by_peer_id() {
+        let allowlist = PeerAllowlist::default();
+        let mut svc = ServiceBuilder::new()
+            .layer(RequireAuthorizationLayer::new(allowlist.clone()))
+            .service_fn(echo);
+
+        // Every peer is allowed by an empty allowlist.
+        let response = svc.ready().await.unwrap().call(request_from([1; 32])).await;
+        assert_eq!(response.unwrap().status(), StatusCode::Success);
+
+        allowlist.update([PeerId([2; 32])]);
+        let response = svc.ready().await.unwrap().call(request_from([1; 32])).await;
+        assert_eq!(response.unwrap().status(), StatusCode::BadRequest);
+        let response = svc.ready().await.unwrap().call(request_from([2; 32])).await;
+        assert_eq!(response.unwrap().status(), StatusCode::Success);
+
+        // Unable to query requesters PeerId
+        let response = svc
+            .ready()
+            .await
+            .unwrap()
+            .call(Request::new(Bytes::from("foobar")))
+            .await;
+        assert_eq!(response.unwrap().status(), StatusCode::BadRequest);
+    }
+
+    fn request_from(peer_id: [u8; 32]) -> Request<Bytes> {
+        let mut request = Request::new(Bytes::from("foobar"));
+        request.extensions_mut().insert(PeerId(peer_id));
+        request
+    }
+
+    async fn echo(req: Request<Bytes>) -> Result<Response<Bytes>, BoxError> {
+        Ok(Response::new(req.into_body()))
+    }
+}
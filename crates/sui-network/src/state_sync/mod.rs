@@ -99,6 +99,7 @@ use self::metrics::Metrics;
 pub struct Handle {
     sender: mpsc::Sender<StateSyncMessage>,
     checkpoint_event_sender: broadcast::Sender<VerifiedCheckpoint>,
+    peer_heights: Arc<RwLock<PeerHeights>>,
 }
 
 impl Handle {
@@ -121,8 +122,18 @@ impl Handle {
     pub fn subscribe_to_synced_checkpoints(&self) -> broadcast::Receiver<VerifiedCheckpoint> {
         self.checkpoint_event_sender.subscribe()
     }
+
+    /// The highest checkpoint sequence number known from the peers on the same chain as us, which
+    /// may not be synchronized yet.
+    pub fn highest_known_checkpoint_sequence_number(&self) -> Option<CheckpointSequenceNumber> {
+        self.peer_heights
+            .read()
+            .unwrap()
+            .highest_known_checkpoint_sequence_number()
+    }
 }
 
+#[derive(Debug)]
 struct PeerHeights {
     /// Table used to track the highest checkpoint for each of our peers.
     peers: HashMap<PeerId, PeerStateSyncInfo>,
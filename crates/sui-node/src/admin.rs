@@ -1,23 +1,39 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::SuiNode;
+use crate::health::HealthReport;
+use crate::{LoadConfig, SuiNode};
 use axum::{
     extract::{Query, State},
     http::StatusCode,
     routing::{get, post},
-    Router,
+    Json, Router,
 };
 use mysten_metrics::spawn_monitored_task;
 use serde::Deserialize;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use sui_core::authority::AuthorityStore;
 use sui_types::error::SuiError;
-use telemetry_subscribers::FilterHandle;
-use tracing::info;
+use telemetry_subscribers::{BoxError, FilterHandle};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
 
 // Example commands:
 //
+// Get the current log filter:
+//
+//   $ curl 'http://127.0.0.1:1337/logging'
+//
+// Set the log filter:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/logging' -d 'info'
+//
+// Enable debug logs of one module for 10 minutes, after which the previous filter is restored:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/logging?ttl_secs=600' -d 'info,sui_core::consensus_adapter=debug'
+//
 // Set buffer stake for current epoch 2 to 1500 basis points:
 //
 //   $ curl -X POST 'http://127.0.0.1:1337/set-override-buffer-stake?buffer_bps=1500&epoch=2'
@@ -34,29 +50,102 @@ use tracing::info;
 // View current all capabilities from all authorities that have been received by this node:
 //
 //   $ curl 'http://127.0.0.1:1337/capabilities'
+//
+// Check whether the node is alive (for liveness probes), or ready to serve traffic (for readiness
+// probes and load-balancer draining). Both return 200 if healthy and 503 otherwise, with the
+// outcome of each check:
+//
+//   $ curl 'http://127.0.0.1:1337/health/live'
+//   $ curl 'http://127.0.0.1:1337/health/ready'
+//
+// Reload the fields of the config file which can be changed without a restart (the same as
+// sending SIGHUP to the node):
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/reload-config'
+//
+// Compact a table of the perpetual store, e.g. after pruning a large number of objects:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/db/compact?cf=objects'
+//
+// Pause the automatic compactions of a table during an execution-heavy period, then resume them:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/db/auto-compactions?cf=objects&enabled=false'
+//   $ curl -X POST 'http://127.0.0.1:1337/db/auto-compactions?cf=objects&enabled=true'
+//
+// Throttle the background compactions and flushes of the perpetual store to 2 concurrent jobs:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/db/compaction-throttle?max_background_jobs=2'
 
 const LOGGING_ROUTE: &str = "/logging";
 const SET_BUFFER_STAKE_ROUTE: &str = "/set-override-buffer-stake";
 const CLEAR_BUFFER_STAKE_ROUTE: &str = "/clear-override-buffer-stake";
 const FORCE_CLOSE_EPOCH: &str = "/force-close-epoch";
 const CAPABILITIES: &str = "/capabilities";
+const HEALTH_LIVE_ROUTE: &str = "/health/live";
+const HEALTH_READY_ROUTE: &str = "/health/ready";
+const RELOAD_CONFIG_ROUTE: &str = "/reload-config";
+const DB_COMPACT_ROUTE: &str = "/db/compact";
+const DB_AUTO_COMPACTIONS_ROUTE: &str = "/db/auto-compactions";
+const DB_COMPACTION_THROTTLE_ROUTE: &str = "/db/compaction-throttle";
 
 struct AppState {
     node: Arc<SuiNode>,
-    filter_handle: FilterHandle,
+    log_filter: Arc<LogFilter<FilterHandle>>,
+    load_config: LoadConfig,
+}
+
+/// The log filter of the node, and the pending revert of a temporary filter.
+struct LogFilter<H> {
+    handle: H,
+    revert: Mutex<FilterRevert>,
+}
+
+/// Reads and updates the log filter, see [FilterHandle].
+trait UpdateFilter: Send + Sync + 'static {
+    fn get(&self) -> Result<String, BoxError>;
+    fn update(&self, directives: &str) -> Result<(), BoxError>;
+}
+
+impl UpdateFilter for FilterHandle {
+    fn get(&self) -> Result<String, BoxError> {
+        FilterHandle::get(self)
+    }
+
+    fn update(&self, directives: &str) -> Result<(), BoxError> {
+        FilterHandle::update(self, directives)
+    }
+}
+
+/// Pending revert of a temporary log filter.
+#[derive(Default)]
+struct FilterRevert {
+    /// Incremented by each update of the filter, so that a revert task only restores the filter
+    /// if it was not updated since.
+    version: u64,
+    /// The filter to restore, and the task restoring it.
+    pending: Option<(String, JoinHandle<()>)>,
 }
 
-pub fn start_admin_server(node: Arc<SuiNode>, port: u16, filter_handle: FilterHandle) {
+pub fn start_admin_server(
+    node: Arc<SuiNode>,
+    port: u16,
+    filter_handle: FilterHandle,
+    load_config: LoadConfig,
+) {
     let filter = filter_handle.get().unwrap();
 
+    let store = node.state().database.clone();
     let app_state = AppState {
         node,
-        filter_handle,
+        log_filter: Arc::new(LogFilter::new(filter_handle)),
+        load_config,
     };
 
     let app = Router::new()
         .route(LOGGING_ROUTE, get(get_filter))
         .route(CAPABILITIES, get(capabilities))
+        .route(HEALTH_LIVE_ROUTE, get(health_live))
+        .route(HEALTH_READY_ROUTE, get(health_ready))
         .route(LOGGING_ROUTE, post(set_filter))
         .route(
             SET_BUFFER_STAKE_ROUTE,
@@ -67,6 +156,8 @@ pub fn start_admin_server(node: Arc<SuiNode>, port: u16, filter_handle: FilterHa
             post(clear_override_protocol_upgrade_buffer_stake),
         )
         .route(FORCE_CLOSE_EPOCH, post(force_close_epoch))
+        .route(RELOAD_CONFIG_ROUTE, post(reload_config))
+        .merge(db_router(store))
         .with_state(Arc::new(app_state));
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
@@ -84,23 +175,93 @@ pub fn start_admin_server(node: Arc<SuiNode>, port: u16, filter_handle: FilterHa
     });
 }
 
+/// Routes managing the perpetual store, which only need the store.
+fn db_router<S: Clone + Send + Sync + 'static>(store: Arc<AuthorityStore>) -> Router<S> {
+    Router::new()
+        .route(DB_COMPACT_ROUTE, post(compact_table))
+        .route(DB_AUTO_COMPACTIONS_ROUTE, post(set_auto_compactions))
+        .route(DB_COMPACTION_THROTTLE_ROUTE, post(set_compaction_throttle))
+        .with_state(store)
+}
+
 async fn get_filter(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
-    match state.filter_handle.get() {
+    match state.log_filter.handle.get() {
         Ok(filter) => (StatusCode::OK, filter),
         Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
     }
 }
 
+#[derive(Deserialize)]
+struct FilterTtl {
+    /// Restore the previous filter after this many seconds.
+    ttl_secs: Option<u64>,
+}
+
 async fn set_filter(
     State(state): State<Arc<AppState>>,
+    ttl: Query<FilterTtl>,
     new_filter: String,
 ) -> (StatusCode, String) {
-    match state.filter_handle.update(&new_filter) {
-        Ok(()) => {
-            info!(filter =% new_filter, "Log filter updated");
-            (StatusCode::OK, "".into())
+    let Query(FilterTtl { ttl_secs }) = ttl;
+    state.log_filter.set(new_filter, ttl_secs)
+}
+
+impl<H: UpdateFilter> LogFilter<H> {
+    fn new(handle: H) -> Self {
+        Self {
+            handle,
+            revert: Mutex::new(FilterRevert::default()),
         }
-        Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
+    }
+
+    /// Sets the filter to `new_filter`, and restores the previous filter after `ttl_secs` if set.
+    fn set(self: &Arc<Self>, new_filter: String, ttl_secs: Option<u64>) -> (StatusCode, String) {
+        let mut filter_revert = self.revert.lock().unwrap();
+        let current_filter = match self.handle.get() {
+            Ok(filter) => filter,
+            Err(err) => return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+        };
+        if let Err(err) = self.handle.update(&new_filter) {
+            return (StatusCode::BAD_REQUEST, err.to_string());
+        }
+        filter_revert.version += 1;
+        // A new filter replaces any temporary one. When it is temporary too, it restores the
+        // filter that was set before the first temporary filter.
+        let pending_filter = filter_revert.pending.take().map(|(filter, revert)| {
+            revert.abort();
+            filter
+        });
+        let Some(ttl_secs) = ttl_secs else {
+            info!(filter =% new_filter, "Log filter updated");
+            return (StatusCode::OK, "".into());
+        };
+
+        let previous_filter = pending_filter.unwrap_or(current_filter);
+        let version = filter_revert.version;
+        let log_filter = self.clone();
+        let revert_filter = previous_filter.clone();
+        let revert = spawn_monitored_task!(async move {
+            tokio::time::sleep(Duration::from_secs(ttl_secs)).await;
+            let mut filter_revert = log_filter.revert.lock().unwrap();
+            if filter_revert.version != version {
+                return;
+            }
+            match log_filter.handle.update(&revert_filter) {
+                Ok(()) => {
+                    info!(filter =% revert_filter, "Temporary log filter expired, filter restored")
+                }
+                Err(err) => warn!(filter =% revert_filter, "Failed to restore log filter: {err}"),
+            }
+            filter_revert.pending = None;
+        });
+        filter_revert.pending = Some((previous_filter.clone(), revert));
+        info!(
+            filter =% new_filter,
+            ttl_secs,
+            previous_filter =% previous_filter,
+            "Log filter updated temporarily"
+        );
+        (StatusCode::OK, "".into())
     }
 }
 
@@ -116,6 +277,114 @@ async fn capabilities(State(state): State<Arc<AppState>>) -> (StatusCode, String
     (StatusCode::OK, output)
 }
 
+async fn health_live(State(state): State<Arc<AppState>>) -> (StatusCode, Json<HealthReport>) {
+    health_response(state.node.liveness())
+}
+
+async fn health_ready(State(state): State<Arc<AppState>>) -> (StatusCode, Json<HealthReport>) {
+    health_response(state.node.readiness())
+}
+
+fn health_response(report: HealthReport) -> (StatusCode, Json<HealthReport>) {
+    let status = if report.healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report))
+}
+
+async fn reload_config(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    let new_config = match (state.load_config)() {
+        Ok(config) => config,
+        Err(err) => return (StatusCode::BAD_REQUEST, err.to_string()),
+    };
+    let reload = match state.node.reload_config(new_config).await {
+        Ok(reload) => reload,
+        Err(err) => return (StatusCode::BAD_REQUEST, err.to_string()),
+    };
+    let mut response = if reload.applied.is_empty() {
+        "no config changes\n".to_string()
+    } else {
+        format!("reloaded config fields: {}\n", reload.applied.join(", "))
+    };
+    if reload.requires_restart {
+        response.push_str("other config changes require a restart\n");
+    }
+    (StatusCode::OK, response)
+}
+
+#[derive(Deserialize)]
+struct Table {
+    cf: String,
+}
+
+async fn compact_table(
+    State(store): State<Arc<AuthorityStore>>,
+    table: Query<Table>,
+) -> (StatusCode, String) {
+    let Query(Table { cf }) = table;
+    let start = Instant::now();
+    let table = cf.clone();
+    // The compaction blocks until it is done.
+    match tokio::task::spawn_blocking(move || store.compact_perpetual_table(&table)).await {
+        Ok(Ok(())) => (
+            StatusCode::OK,
+            format!("compacted {cf} in {:?}\n", start.elapsed()),
+        ),
+        Ok(Err(err)) => (StatusCode::BAD_REQUEST, err.to_string()),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+struct AutoCompactions {
+    cf: String,
+    enabled: bool,
+}
+
+async fn set_auto_compactions(
+    State(store): State<Arc<AuthorityStore>>,
+    auto_compactions: Query<AutoCompactions>,
+) -> (StatusCode, String) {
+    let Query(AutoCompactions { cf, enabled }) = auto_compactions;
+    match store.set_perpetual_table_auto_compactions(&cf, enabled) {
+        Ok(()) => {
+            info!(table =% cf, enabled, "Automatic compactions updated");
+            let status = if enabled { "enabled" } else { "disabled" };
+            (
+                StatusCode::OK,
+                format!("automatic compactions of {cf} {status}\n"),
+            )
+        }
+        Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+struct CompactionThrottle {
+    max_background_jobs: u32,
+}
+
+async fn set_compaction_throttle(
+    State(store): State<Arc<AuthorityStore>>,
+    throttle: Query<CompactionThrottle>,
+) -> (StatusCode, String) {
+    let Query(CompactionThrottle {
+        max_background_jobs,
+    }) = throttle;
+    match store.set_perpetual_tables_compaction_throttle(max_background_jobs) {
+        Ok(()) => {
+            info!(max_background_jobs, "Compaction throttle updated");
+            (
+                StatusCode::OK,
+                format!("background jobs limited to {max_background_jobs}\n"),
+            )
+        }
+        Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
+    }
+}
+
 #[derive(Deserialize)]
 struct Epoch {
     epoch: u64,
@@ -188,3 +457,68 @@ async fn force_close_epoch(
         Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
     }
 }
+
+#[cfg(test)]
+#[path = "unit_tests/admin_tests.rs"]
+mod admin_tests;
+
+#[cfg(test)]
+mod tests {
+    use super::{LogFilter, UpdateFilter};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use telemetry_subscribers::BoxError;
+    use tokio::time::sleep;
+
+    struct TestFilter(Mutex<String>);
+
+    impl UpdateFilter for TestFilter {
+        fn get(&self) -> Result<String, BoxError> {
+            Ok(self.0.lock().unwrap().clone())
+        }
+
+        fn update(&self, directives: &str) -> Result<(), BoxError> {
+            *self.0.lock().unwrap() = directives.to_string();
+            Ok(())
+        }
+    }
+
+    fn test_log_filter() -> Arc<LogFilter<TestFilter>> {
+        Arc::new(LogFilter::new(TestFilter(Mutex::new("info".to_string()))))
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_temporary_filter_reverts_after_ttl() {
+        let log_filter = test_log_filter();
+        log_filter.set("debug".to_string(), Some(10));
+        assert_eq!(log_filter.handle.get().unwrap(), "debug");
+
+        sleep(Duration::from_secs(9)).await;
+        assert_eq!(log_filter.handle.get().unwrap(), "debug");
+        sleep(Duration::from_secs(2)).await;
+        assert_eq!(log_filter.handle.get().unwrap(), "info");
+        assert!(log_filter.revert.lock().unwrap().pending.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_new_filter_supersedes_pending_revert() {
+        // A permanent filter cancels the revert.
+        let log_filter = test_log_filter();
+        log_filter.set("debug".to_string(), Some(10));
+        sleep(Duration::from_secs(5)).await;
+        log_filter.set("warn".to_string(), None);
+        sleep(Duration::from_secs(10)).await;
+        assert_eq!(log_filter.handle.get().unwrap(), "warn");
+
+        // A temporary filter replaces the revert, and restores the filter set before the first
+        // temporary filter when it expires.
+        let log_filter = test_log_filter();
+        log_filter.set("debug".to_string(), Some(10));
+        sleep(Duration::from_secs(5)).await;
+        log_filter.set("trace".to_string(), Some(20));
+        sleep(Duration::from_secs(10)).await;
+        assert_eq!(log_filter.handle.get().unwrap(), "trace");
+        sleep(Duration::from_secs(11)).await;
+        assert_eq!(log_filter.handle.get().unwrap(), "info");
+    }
+}
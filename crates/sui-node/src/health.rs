@@ -0,0 +1,175 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Health checks of a node, served by the admin server for liveness and readiness probes.
+
+use narwhal_network::connectivity::ConnectionStatus;
+use serde::Serialize;
+use sui_core::consensus_adapter::CheckConnection;
+use sui_types::committee::Committee;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+use crate::SuiNode;
+
+/// Outcome of one health check.
+#[derive(Debug, Serialize)]
+pub struct HealthCheck {
+    pub name: &'static str,
+    pub healthy: bool,
+    pub detail: String,
+}
+
+/// Outcome of all the health checks of a probe. The node is healthy if all of them are.
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+    pub healthy: bool,
+    pub checks: Vec<HealthCheck>,
+}
+
+impl HealthReport {
+    fn new(checks: Vec<HealthCheck>) -> Self {
+        Self {
+            healthy: checks.iter().all(|c| c.healthy),
+            checks,
+        }
+    }
+}
+
+impl SuiNode {
+    /// Checks whether the node is alive, i.e. whether it should be restarted if not.
+    /// Falling behind or losing connections is not a reason to restart the node, so these are
+    /// only part of [SuiNode::readiness].
+    pub fn liveness(&self) -> HealthReport {
+        HealthReport::new(vec![self.check_db()])
+    }
+
+    /// Checks whether the node is ready to serve traffic: its database can be read, it is in
+    /// sync with the network, validators are connected to a quorum of the committee, and the
+    /// JSON-RPC server of fullnodes is running.
+    pub fn readiness(&self) -> HealthReport {
+        let mut checks = vec![self.check_db(), self.check_checkpoint_lag()];
+        let epoch_store = self.state.load_epoch_store_one_call_per_task();
+        if self.state.is_validator(&epoch_store) {
+            checks.push(self.check_consensus_connected(epoch_store.committee()));
+        }
+        // Only fullnodes serve JSON-RPC.
+        if self.config.consensus_config().is_none() {
+            checks.push(self.check_json_rpc());
+        }
+        HealthReport::new(checks)
+    }
+
+    fn check_db(&self) -> HealthCheck {
+        let (healthy, detail) = match self
+            .checkpoint_store
+            .get_highest_executed_checkpoint_seq_number()
+        {
+            Ok(_) => (true, "ok".to_string()),
+            Err(err) => (false, format!("failed to read checkpoint store: {err}")),
+        };
+        HealthCheck {
+            name: "db",
+            healthy,
+            detail,
+        }
+    }
+
+    fn check_checkpoint_lag(&self) -> HealthCheck {
+        // The checkpoints known from peers are not synchronized yet, so a node which stopped
+        // synchronizing them falls behind the checkpoints of the peers, even if it executed all
+        // the checkpoints it verified.
+        let highest_known_from_peers = self.state_sync.highest_known_checkpoint_sequence_number();
+        let watermarks = self
+            .checkpoint_store
+            .get_highest_verified_checkpoint()
+            .map(|verified| verified.map(|c| *c.sequence_number()))
+            .and_then(|verified| {
+                self.checkpoint_store
+                    .get_highest_executed_checkpoint_seq_number()
+                    .map(|executed| (verified.max(highest_known_from_peers), executed))
+            });
+        match watermarks {
+            Ok((highest_known, executed)) => check_checkpoint_lag(
+                highest_known,
+                executed,
+                self.config.health_check_config.max_checkpoint_lag,
+            ),
+            Err(err) => HealthCheck {
+                name: "checkpoint_lag",
+                healthy: false,
+                detail: format!("failed to read checkpoint watermarks: {err}"),
+            },
+        }
+    }
+
+    fn check_consensus_connected(&self, committee: &Committee) -> HealthCheck {
+        let connected_stake: u64 = committee
+            .members()
+            .filter(|(name, _)| {
+                self.connection_monitor_status
+                    .check_connection(&self.state.name, name)
+                    == Some(ConnectionStatus::Connected)
+            })
+            .map(|(_, stake)| *stake)
+            .sum();
+        let quorum_threshold = committee.quorum_threshold();
+        HealthCheck {
+            name: "consensus_connected",
+            healthy: connected_stake >= quorum_threshold,
+            detail: format!(
+                "connected to validators with stake {connected_stake} (quorum {quorum_threshold})"
+            ),
+        }
+    }
+
+    fn check_json_rpc(&self) -> HealthCheck {
+        let (healthy, detail) = match &self.json_rpc_service {
+            Some(handle) if !handle.is_stopped() => (true, "serving".to_string()),
+            Some(_) => (false, "server stopped".to_string()),
+            None => (false, "server not started".to_string()),
+        };
+        HealthCheck {
+            name: "json_rpc",
+            healthy,
+            detail,
+        }
+    }
+}
+
+/// Checks that the `executed` checkpoint is at most `max_lag` checkpoints behind the
+/// `highest_known` checkpoint.
+fn check_checkpoint_lag(
+    highest_known: Option<CheckpointSequenceNumber>,
+    executed: Option<CheckpointSequenceNumber>,
+    max_lag: u64,
+) -> HealthCheck {
+    let lag = match (highest_known, executed) {
+        (Some(highest_known), Some(executed)) => highest_known.saturating_sub(executed),
+        (Some(highest_known), None) => highest_known + 1,
+        (None, _) => 0,
+    };
+    HealthCheck {
+        name: "checkpoint_lag",
+        healthy: lag <= max_lag,
+        detail: format!(
+            "highest known checkpoint {highest_known:?}, highest executed checkpoint \
+             {executed:?}, lag {lag} (max {max_lag})"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_checkpoint_lag;
+
+    #[test]
+    fn test_check_checkpoint_lag() {
+        assert!(check_checkpoint_lag(None, None, 0).healthy);
+        assert!(check_checkpoint_lag(Some(0), Some(0), 0).healthy);
+        assert!(check_checkpoint_lag(Some(110), Some(10), 100).healthy);
+        assert!(!check_checkpoint_lag(Some(111), Some(10), 100).healthy);
+        // Nothing is executed yet.
+        assert!(check_checkpoint_lag(Some(99), None, 100).healthy);
+        assert!(!check_checkpoint_lag(Some(100), None, 100).healthy);
+    }
+}
@@ -10,6 +10,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use anemo::Network;
+use anemo_tower::auth::RequireAuthorizationLayer;
 use anemo_tower::callback::CallbackLayer;
 use anemo_tower::trace::DefaultMakeSpan;
 use anemo_tower::trace::DefaultOnFailure;
@@ -19,6 +20,7 @@ use anyhow::Result;
 use arc_swap::ArcSwap;
 use futures::TryFutureExt;
 use prometheus::Registry;
+use sui_archival::{ArchiveFallback, ArchiveWriter};
 use sui_core::consensus_adapter::LazyNarwhalClient;
 use sui_types::sui_system_state::SuiSystemState;
 use tap::tap::TapFallible;
@@ -27,18 +29,21 @@ use tokio::sync::oneshot::Sender;
 use tokio::sync::{watch, Mutex};
 use tokio::task::JoinHandle;
 use tower::ServiceBuilder;
-use tracing::{debug, warn};
+use tracing::{debug, error, warn};
 use tracing::{error_span, info, Instrument};
 
-use checkpoint_executor::CheckpointExecutor;
+use checkpoint_executor::{CheckpointExecutor, StopReason};
 pub use handle::SuiNodeHandle;
 use mysten_metrics::{spawn_monitored_task, RegistryService};
 use mysten_network::server::ServerBuilder;
+use narwhal_crypto::ProtocolSigner;
 use narwhal_network::metrics::MetricsMakeCallbackHandler;
 use narwhal_network::metrics::{NetworkConnectionMetrics, NetworkMetrics};
-use sui_config::node::DBCheckpointConfig;
+pub use reload::{ConfigReload, LoadConfig};
+use sui_config::node::{DBCheckpointConfig, MetricsConfig};
 use sui_config::{ConsensusConfig, NodeConfig};
 use sui_core::authority::authority_per_epoch_store::AuthorityPerEpochStore;
+use sui_core::authority::authority_store_compaction::AuthorityStoreCompactionScheduler;
 use sui_core::authority::epoch_start_configuration::EpochStartConfiguration;
 use sui_core::authority_aggregator::AuthorityAggregator;
 use sui_core::authority_server::ValidatorService;
@@ -63,8 +68,11 @@ use sui_core::signature_verifier::SignatureVerifierMetrics;
 use sui_core::state_accumulator::StateAccumulator;
 use sui_core::storage::RocksDbStore;
 use sui_core::transaction_orchestrator::TransactiondOrchestrator;
+use sui_core::validator_signer::{
+    authority_signer, consensus_signer, RemoteValidatorSigner, ValidatorSigner,
+};
 use sui_core::{
-    authority::{AuthorityState, AuthorityStore},
+    authority::{AuthorityState, AuthorityStore, StableSyncAuthoritySigner},
     authority_client::NetworkAuthorityClient,
 };
 use sui_json_rpc::coin_api::CoinReadApi;
@@ -74,11 +82,12 @@ use sui_json_rpc::move_utils::MoveUtils;
 use sui_json_rpc::read_api::ReadApi;
 use sui_json_rpc::transaction_builder_api::TransactionBuilderApi;
 use sui_json_rpc::transaction_execution_api::TransactionExecutionApi;
-use sui_json_rpc::{JsonRpcServerBuilder, ServerHandle};
+use sui_json_rpc::{JsonRpcServerBuilder, RequestLimits, RpcFeature, ServerHandle};
 use sui_macros::fail_point_async;
 use sui_network::api::ValidatorServer;
 use sui_network::discovery;
 use sui_network::discovery::TrustedPeerChangeEvent;
+use sui_network::peer_allowlist::PeerAllowlist;
 use sui_network::state_sync;
 use sui_protocol_config::{ProtocolConfig, SupportedProtocolVersions};
 use sui_storage::IndexStore;
@@ -97,7 +106,9 @@ use crate::metrics::GrpcMetrics;
 
 pub mod admin;
 mod handle;
+pub mod health;
 pub mod metrics;
+mod reload;
 
 pub struct ValidatorComponents {
     validator_server_handle: JoinHandle<Result<()>>,
@@ -115,12 +126,19 @@ pub struct ValidatorComponents {
 pub struct SuiNode {
     config: NodeConfig,
     validator_components: Mutex<Option<ValidatorComponents>>,
-    _json_rpc_service: Option<ServerHandle>,
+    /// Signs consensus messages with the protocol key, when the node is a validator.
+    consensus_signer: ProtocolSigner,
+    json_rpc_service: Option<ServerHandle>,
+    /// Limits of the JSON-RPC server, which can be updated by [SuiNode::reload_config].
+    request_limits: RequestLimits,
     state: Arc<AuthorityState>,
     transaction_orchestrator: Option<Arc<TransactiondOrchestrator<NetworkAuthorityClient>>>,
     registry_service: RegistryService,
 
-    _discovery: discovery::Handle,
+    discovery: discovery::Handle,
+    /// Peers allowed to send requests to the p2p network, which can be updated by
+    /// [SuiNode::reload_config].
+    peer_allowlist: PeerAllowlist,
     state_sync: state_sync::Handle,
     checkpoint_store: Arc<CheckpointStore>,
     accumulator: Arc<StateAccumulator>,
@@ -133,6 +151,18 @@ pub struct SuiNode {
     trusted_peer_change_tx: watch::Sender<TrustedPeerChangeEvent>,
 
     _db_checkpoint_handle: Option<Sender<()>>,
+    _archive_writer_handle: Option<Sender<()>>,
+    _db_compaction_scheduler: Option<AuthorityStoreCompactionScheduler>,
+
+    /// Set to true when the node starts shutting down, see [SuiNode::shutdown].
+    shutdown_tx: watch::Sender<bool>,
+    /// The task running [SuiNode::monitor_reconfiguration].
+    reconfiguration_task: Mutex<Option<JoinHandle<Result<()>>>>,
+
+    /// Updates the config of the metrics push task.
+    metrics_push_tx: watch::Sender<Option<MetricsConfig>>,
+    /// The config last applied by [SuiNode::reload_config].
+    reloaded_config: Mutex<NodeConfig>,
 
     #[cfg(msim)]
     sim_node: sui_simulator::runtime::NodeHandle,
@@ -167,7 +197,43 @@ impl SuiNode {
         let is_full_node = !is_validator;
         let prometheus_registry = registry_service.default_registry();
 
-        info!(node =? config.protocol_public_key(),
+        // The protocol key signs transaction votes, checkpoint signatures and consensus messages.
+        // It is held either by the node or by a remote signer.
+        let (name, secret, consensus_signer): (_, StableSyncAuthoritySigner, ProtocolSigner) =
+            match &config.remote_signer_config {
+                Some(remote_signer_config) => {
+                    if config.holds_validator_keys() {
+                        return Err(anyhow!(
+                            "The config sets a remote signer, so it must not set the protocol \
+                             and account keys, which are held by the remote signer"
+                        ));
+                    }
+                    let signer = Arc::new(
+                        RemoteValidatorSigner::connect(
+                            &remote_signer_config.address,
+                            config.network_key_pair().copy(),
+                            remote_signer_config.protocol_public_key,
+                        )
+                        .await?,
+                    );
+                    info!(
+                        "Signing with remote signer at {}",
+                        remote_signer_config.address
+                    );
+                    (
+                        signer.protocol_public_key(),
+                        authority_signer(signer.clone()),
+                        consensus_signer(signer)?,
+                    )
+                }
+                None => (
+                    config.protocol_public_key(),
+                    Arc::pin(config.protocol_key_pair().copy()),
+                    config.protocol_key_pair().copy().into(),
+                ),
+            };
+
+        info!(node =? name,
             "Initializing sui-node listening on {}", config.network_address
         );
 
@@ -177,7 +243,6 @@ impl SuiNode {
 
         let genesis = config.genesis()?;
 
-        let secret = Arc::pin(config.protocol_key_pair().copy());
         let genesis_committee = genesis.committee()?;
         let committee_store = Arc::new(CommitteeStore::new(
             config.db_path().join("epochs"),
@@ -208,7 +273,7 @@ impl SuiNode {
         let signature_verifier_metrics = SignatureVerifierMetrics::new(&prometheus_registry);
 
         let epoch_store = AuthorityPerEpochStore::new(
-            config.protocol_public_key(),
+            name,
             committee.clone(),
             &config.db_path().join("store"),
             None,
@@ -254,16 +319,32 @@ impl SuiNode {
         // TODO only configure validators as seed/preferred peers for validators and not for
         // fullnodes once we've had a chance to re-work fullnode configuration generation.
         let (trusted_peer_change_tx, trusted_peer_change_rx) = watch::channel(Default::default());
+        let archive_writer_handle = config
+            .archive_config
+            .as_ref()
+            .map(|archive_config| -> Result<_> {
+                let writer = ArchiveWriter::new(
+                    &archive_config.writer_config,
+                    state_sync_store.clone(),
+                    archive_config.operator_key_pair.keypair().copy(),
+                )?;
+                info!(signer = ?writer.signer(), "Archiving the synced checkpoints");
+                Ok(writer.start(Duration::from_secs(archive_config.archive_interval_secs)))
+            })
+            .transpose()?;
+
+        let peer_allowlist = PeerAllowlist::new(config.p2p_config.allowlisted_peers.clone());
         let (p2p_network, discovery_handle, state_sync_handle) = Self::create_p2p_network(
             &config,
             state_sync_store,
             trusted_peer_change_rx,
+            peer_allowlist.clone(),
             &prometheus_registry,
         )?;
         // We must explicitly send this instead of relying on the initial value to trigger
         // watch value change, so that state-sync is able to process it.
         send_trusted_peer_change(
-            &config,
+            name,
             &trusted_peer_change_tx,
             epoch_store.epoch_start_state(),
         )
@@ -291,7 +372,7 @@ impl SuiNode {
         };
 
         let state = AuthorityState::new(
-            config.protocol_public_key(),
+            name,
             secret,
             config.supported_protocol_versions.unwrap(),
             store.clone(),
@@ -300,7 +381,7 @@ impl SuiNode {
             index_store.clone(),
             checkpoint_store.clone(),
             &prometheus_registry,
-            config.authority_store_pruning_config,
+            config.authority_store_pruning_config.clone(),
             genesis.objects(),
             &db_checkpoint_config,
             config.expensive_safety_check_config.clone(),
@@ -333,6 +414,8 @@ impl SuiNode {
                     end_of_epoch_receiver,
                     &config.db_path(),
                     &prometheus_registry,
+                    &config.validator_selection,
+                    &config.transaction_policy,
                 )
                 .await?,
             ))
@@ -340,11 +423,22 @@ impl SuiNode {
             None
         };
 
+        let request_limits = RequestLimits::new(config.rpc_limits.max_concurrent_requests);
+        request_limits.set_max_response_size(config.rpc_limits.max_response_size);
+        request_limits.set_execution_lane_size(config.rpc_limits.execution_lane_size.unwrap_or(0));
+        request_limits.set_method_concurrency_limits(&config.rpc_limits.method_concurrency_limits);
+        request_limits.set_method_queue_timeout(
+            config
+                .rpc_limits
+                .method_queue_timeout_ms
+                .map(Duration::from_millis),
+        );
         let json_rpc_service = build_server(
             state.clone(),
             &transaction_orchestrator.clone(),
             &config,
             &prometheus_registry,
+            &request_limits,
         )
         .await?;
 
@@ -381,6 +475,7 @@ impl SuiNode {
         let validator_components = if state.is_validator(&epoch_store) {
             let components = Self::construct_validator_components(
                 &config,
+                consensus_signer.clone(),
                 state.clone(),
                 committee,
                 epoch_store.clone(),
@@ -400,15 +495,27 @@ impl SuiNode {
             None
         };
 
+        let metrics_push_tx = metrics::start_metrics_push_task(&config, registry_service.clone());
+
+        let db_compaction_scheduler = config
+            .db_compaction_config
+            .clone()
+            .map(|config| AuthorityStoreCompactionScheduler::new(state.database.clone(), config))
+            .transpose()?;
+
         let node = Self {
+            reloaded_config: Mutex::new(config.clone()),
             config,
             validator_components: Mutex::new(validator_components),
-            _json_rpc_service: json_rpc_service,
+            consensus_signer,
+            json_rpc_service,
+            request_limits,
             state,
             transaction_orchestrator,
             registry_service,
 
-            _discovery: discovery_handle,
+            discovery: discovery_handle,
+            peer_allowlist,
             state_sync: state_sync_handle,
             checkpoint_store,
             accumulator,
@@ -417,6 +524,11 @@ impl SuiNode {
             trusted_peer_change_tx,
 
             _db_checkpoint_handle: db_checkpoint_handle,
+            _archive_writer_handle: archive_writer_handle,
+            _db_compaction_scheduler: db_compaction_scheduler,
+            shutdown_tx: watch::channel(false).0,
+            reconfiguration_task: Mutex::new(None),
+            metrics_push_tx,
             #[cfg(msim)]
             sim_node: sui_simulator::runtime::NodeHandle::current(),
             #[cfg(msim)]
@@ -426,7 +538,9 @@ impl SuiNode {
         info!("SuiNode started!");
         let node = Arc::new(node);
         let node_copy = node.clone();
-        spawn_monitored_task!(async move { Self::monitor_reconfiguration(node_copy).await });
+        let reconfiguration_task =
+            spawn_monitored_task!(async move { Self::monitor_reconfiguration(node_copy).await });
+        *node.reconfiguration_task.lock().await = Some(reconfiguration_task);
 
         Ok(node)
     }
@@ -496,6 +610,7 @@ impl SuiNode {
         config: &NodeConfig,
         state_sync_store: RocksDbStore,
         trusted_peer_change_rx: watch::Receiver<TrustedPeerChangeEvent>,
+        peer_allowlist: PeerAllowlist,
         prometheus_registry: &Registry,
     ) -> Result<(Network, discovery::Handle, state_sync::Handle)> {
         let (state_sync, state_sync_server) = state_sync::Builder::new()
@@ -528,6 +643,7 @@ impl SuiNode {
                     Arc::new(inbound_network_metrics),
                     config.p2p_config.excessive_message_size(),
                 )))
+                .layer(RequireAuthorizationLayer::new(peer_allowlist))
                 .service(routes);
 
             let outbound_layer = ServiceBuilder::new()
@@ -566,6 +682,7 @@ impl SuiNode {
 
     async fn construct_validator_components(
         config: &NodeConfig,
+        consensus_signer: ProtocolSigner,
         state: Arc<AuthorityState>,
         committee: Arc<Committee>,
         epoch_store: Arc<AuthorityPerEpochStore>,
@@ -587,8 +704,12 @@ impl SuiNode {
             connection_monitor_status.clone(),
             &registry_service.default_registry(),
         ));
-        let narwhal_manager =
-            Self::construct_narwhal_manager(config, consensus_config, registry_service)?;
+        let narwhal_manager = Self::construct_narwhal_manager(
+            config,
+            consensus_signer,
+            consensus_config,
+            registry_service,
+        )?;
 
         let mut narwhal_epoch_data_remover =
             EpochDataRemover::new(narwhal_manager.get_storage_base_path());
@@ -726,7 +847,7 @@ impl SuiNode {
         let checkpoint_output = Box::new(SubmitCheckpointToConsensus {
             sender: consensus_adapter,
             signer: state.secret.clone(),
-            authority: config.protocol_public_key(),
+            authority: state.name,
             next_reconfiguration_timestamp_ms: epoch_start_timestamp_ms
                 .checked_add(epoch_duration_ms)
                 .expect("Overflow calculating next_reconfiguration_timestamp_ms"),
@@ -754,11 +875,12 @@ impl SuiNode {
 
     fn construct_narwhal_manager(
         config: &NodeConfig,
+        consensus_signer: ProtocolSigner,
         consensus_config: &ConsensusConfig,
         registry_service: &RegistryService,
     ) -> Result<NarwhalManager> {
         let narwhal_config = NarwhalConfiguration {
-            primary_keypair: config.protocol_key_pair().copy(),
+            primary_signer: consensus_signer,
             network_keypair: config.network_key_pair().copy(),
             worker_ids_and_keypairs: vec![(0, config.worker_key_pair().copy())],
             storage_base_path: consensus_config.db_path().to_path_buf(),
@@ -821,6 +943,74 @@ impl SuiNode {
         Ok(grpc_server)
     }
 
+    /// Shut down the node gracefully: stop serving requests and consensus, wait for the
+    /// transactions and checkpoints being executed, and flush the databases so that they do not
+    /// need to be recovered from their write-ahead logs on restart. Waiting is bounded by
+    /// `shutdown_timeout_secs` of the node config, the databases are flushed in any case.
+    pub async fn shutdown(&self) {
+        let timeout = Duration::from_secs(self.config.shutdown_timeout_secs);
+        let deadline = tokio::time::Instant::now() + timeout;
+        info!(?timeout, "Shutting down SuiNode");
+        self.shutdown_tx.send_replace(true);
+
+        if let Some(json_rpc_service) = &self.json_rpc_service {
+            if json_rpc_service.stop().is_ok()
+                && tokio::time::timeout_at(deadline, json_rpc_service.clone().stopped())
+                    .await
+                    .is_err()
+            {
+                warn!("Timed out waiting for the JSON-RPC server to stop");
+            }
+        }
+
+        match tokio::time::timeout_at(deadline, self.validator_components.lock()).await {
+            Ok(mut components) => {
+                if let Some(components) = components.take() {
+                    components.validator_server_handle.abort();
+                    components.narwhal_manager.shutdown().await;
+                    // Stops the checkpoint service tasks.
+                    drop(components.checkpoint_service_exit);
+                }
+            }
+            Err(_) => warn!("Timed out waiting to stop the validator components"),
+        }
+
+        // The checkpoint executor returns once the checkpoints being executed are done.
+        let reconfiguration_task = self.reconfiguration_task.lock().await.take();
+        if let Some(reconfiguration_task) = reconfiguration_task {
+            if tokio::time::timeout_at(deadline, reconfiguration_task)
+                .await
+                .is_err()
+            {
+                warn!("Timed out waiting for checkpoint execution to stop");
+            }
+        }
+
+        let transaction_manager = self.state.transaction_manager();
+        while transaction_manager.executing_certificates_len() > 0 {
+            if tokio::time::Instant::now() >= deadline {
+                warn!(
+                    "Timed out waiting for {} executing transactions",
+                    transaction_manager.executing_certificates_len()
+                );
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+
+        let epoch_store = self.state.load_epoch_store_one_call_per_task();
+        let flushed = self
+            .checkpoint_store
+            .flush()
+            .map_err(SuiError::from)
+            .and_then(|()| self.state.db().flush())
+            .and_then(|()| epoch_store.flush());
+        match flushed {
+            Ok(()) => info!("SuiNode shut down"),
+            Err(err) => error!("Failed to flush databases on shutdown: {err}"),
+        }
+    }
+
     pub fn state(&self) -> Arc<AuthorityState> {
         self.state.clone()
     }
@@ -866,6 +1056,7 @@ impl SuiNode {
     pub async fn monitor_reconfiguration(self: Arc<Self>) -> Result<()> {
         let mut checkpoint_executor = CheckpointExecutor::new(
             self.state_sync.subscribe_to_synced_checkpoints(),
+            self.shutdown_tx.subscribe(),
             self.checkpoint_store.clone(),
             self.state.database.clone(),
             self.state.transaction_manager().clone(),
@@ -900,7 +1091,11 @@ impl SuiNode {
                     .submit(transaction, None, &cur_epoch_store)?;
             }
 
-            checkpoint_executor.run_epoch(cur_epoch_store.clone()).await;
+            if checkpoint_executor.run_epoch(cur_epoch_store.clone()).await == StopReason::Shutdown
+            {
+                info!("Node is shutting down, stopped executing checkpoints");
+                return Ok(());
+            }
             let latest_system_state = self
                 .state
                 .get_sui_system_state_object_during_reconfig()
@@ -950,7 +1145,7 @@ impl SuiNode {
             cur_epoch_store.record_epoch_reconfig_start_time_metric();
 
             let _ = send_trusted_peer_change(
-                &self.config,
+                self.state.name,
                 &self.trusted_peer_change_tx,
                 &new_epoch_start_state,
             );
@@ -1027,6 +1222,7 @@ impl SuiNode {
                     Some(
                         Self::construct_validator_components(
                             &self.config,
+                            self.consensus_signer.clone(),
                             self.state.clone(),
                             Arc::new(next_epoch_committee.clone()),
                             new_epoch_store.clone(),
@@ -1088,13 +1284,13 @@ impl SuiNode {
 
 /// Notify state-sync that a new list of trusted peers are now available.
 fn send_trusted_peer_change(
-    config: &NodeConfig,
+    name: AuthorityName,
     sender: &watch::Sender<TrustedPeerChangeEvent>,
     epoch_state_state: &EpochStartSystemState,
 ) -> Result<(), watch::error::SendError<TrustedPeerChangeEvent>> {
     sender
         .send(TrustedPeerChangeEvent {
-            new_peers: epoch_state_state.get_validator_as_p2p_peers(config.protocol_public_key()),
+            new_peers: epoch_state_state.get_validator_as_p2p_peers(name),
         })
         .tap_err(|err| {
             warn!(
@@ -1109,6 +1305,7 @@ pub async fn build_server(
     transaction_orchestrator: &Option<Arc<TransactiondOrchestrator<NetworkAuthorityClient>>>,
     config: &NodeConfig,
     prometheus_registry: &Registry,
+    request_limits: &RequestLimits,
 ) -> Result<Option<ServerHandle>> {
     // Validators do not expose these APIs
     if config.consensus_config().is_some() {
@@ -1116,8 +1313,15 @@ pub async fn build_server(
     }
 
     let mut server = JsonRpcServerBuilder::new(env!("CARGO_PKG_VERSION"), prometheus_registry);
+    server.set_request_limits(request_limits.clone());
 
-    server.register_module(ReadApi::new(state.clone()))?;
+    let archive_fallback = config
+        .archive_reader_config
+        .as_ref()
+        .map(|config| ArchiveFallback::new(config).map(Arc::new))
+        .transpose()?;
+
+    server.register_module(ReadApi::new(state.clone(), archive_fallback.clone()))?;
     server.register_module(CoinReadApi::new(state.clone()))?;
     server.register_module(TransactionBuilderApi::new(state.clone()))?;
     server.register_module(GovernanceReadApi::new(state.clone()))?;
@@ -1129,9 +1333,21 @@ pub async fn build_server(
         ))?;
     }
 
-    server.register_module(IndexerApi::new(state.clone(), ReadApi::new(state.clone())))?;
+    server.register_module(IndexerApi::new(
+        state.clone(),
+        ReadApi::new(state.clone(), archive_fallback),
+        request_limits.clone(),
+    ))?;
     server.register_module(MoveUtils::new(state.clone()))?;
 
+    server.set_feature(RpcFeature::Indexer, state.has_indexes());
+    // Events are only emitted to subscribers once their transaction is indexed.
+    server.set_feature(RpcFeature::Subscriptions, state.has_indexes());
+    server.set_feature(
+        RpcFeature::TransactionExecution,
+        transaction_orchestrator.is_some(),
+    );
+
     let rpc_server_handle = server.start(config.json_rpc_address).await?;
 
     Ok(Some(rpc_server_handle))
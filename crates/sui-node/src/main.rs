@@ -4,9 +4,10 @@
 use anyhow::Result;
 use clap::Parser;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 use sui_config::{Config, NodeConfig};
-use sui_node::metrics;
+use sui_node::{metrics, LoadConfig};
 use sui_protocol_config::SupportedProtocolVersions;
 use sui_telemetry::send_telemetry_event;
 use sui_types::multiaddr::Multiaddr;
@@ -43,6 +44,18 @@ struct Args {
     listen_address: Option<Multiaddr>,
 }
 
+impl Args {
+    /// Loads the config file, with the overrides of the command line, both when starting the node
+    /// and when reloading its config.
+    fn load_config(&self) -> Result<NodeConfig> {
+        let mut config = NodeConfig::load(&self.config_path)?;
+        if let Some(listen_address) = &self.listen_address {
+            config.network_address = listen_address.clone();
+        }
+        Ok(config)
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Ensure that a validator never calls get_for_min_version/get_for_max_version.
@@ -50,7 +63,7 @@ async fn main() -> Result<()> {
     // ProtocolConfig::poison_get_for_min_version();
 
     let args = Args::parse();
-    let mut config = NodeConfig::load(&args.config_path)?;
+    let mut config = args.load_config()?;
     assert!(
         config.supported_protocol_versions.is_none(),
         "supported_protocol_versions cannot be read from the config file"
@@ -80,12 +93,6 @@ async fn main() -> Result<()> {
         config.metrics_address
     );
 
-    metrics::start_metrics_push_task(&config, registry_service.clone());
-
-    if let Some(listen_address) = args.listen_address {
-        config.network_address = listen_address;
-    }
-
     let is_validator = config.consensus_config().is_some();
     task::spawn(async move {
         loop {
@@ -95,10 +102,55 @@ async fn main() -> Result<()> {
     });
 
     let node = sui_node::SuiNode::start(&config, registry_service).await?;
-    sui_node::admin::start_admin_server(node.clone(), config.admin_interface_port, filter_handle);
+    let load_config: LoadConfig = Arc::new(move || args.load_config());
+    sui_node::admin::start_admin_server(
+        node.clone(),
+        config.admin_interface_port,
+        filter_handle,
+        load_config.clone(),
+    );
+
+    #[cfg(unix)]
+    task::spawn(reload_config_on_sighup(node.clone(), load_config));
 
-    // TODO: Do we want to provide a way for the node to gracefully shutdown?
-    loop {
-        tokio::time::sleep(Duration::from_secs(1000)).await;
+    wait_for_shutdown_signal().await;
+    node.shutdown().await;
+    Ok(())
+}
+
+/// Reloads the config fields which can be changed without a restart, see
+/// [sui_node::SuiNode::reload_config], when receiving SIGHUP.
+#[cfg(unix)]
+async fn reload_config_on_sighup(node: Arc<sui_node::SuiNode>, load_config: LoadConfig) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut sighup = signal(SignalKind::hangup()).expect("Failed to listen for SIGHUP");
+    while sighup.recv().await.is_some() {
+        info!("Received SIGHUP, reloading config");
+        let result = match load_config() {
+            Ok(new_config) => node.reload_config(new_config).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            tracing::warn!("Failed to reload config: {err}");
+        }
+    }
+}
+
+async fn wait_for_shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut sigterm = signal(SignalKind::terminate()).expect("Failed to listen for SIGTERM");
+        tokio::select! {
+            _ = sigterm.recv() => info!("Received SIGTERM"),
+            _ = tokio::signal::ctrl_c() => info!("Received SIGINT"),
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+        info!("Received Ctrl-C");
     }
 }
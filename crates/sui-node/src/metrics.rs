@@ -19,6 +19,8 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use sui_network::tonic::Code;
 
 use mysten_metrics::RegistryService;
+use sui_config::node::MetricsConfig;
+use tokio::sync::watch;
 use tracing::warn;
 
 const METRICS_ROUTE: &str = "/metrics";
@@ -96,15 +98,13 @@ impl MetricsPushClient {
     }
 }
 
-/// Starts a task to periodically push metrics to a configured endpoint if a metrics push endpoint
-/// is configured.
-pub fn start_metrics_push_task(config: &sui_config::NodeConfig, registry: RegistryService) {
-    use fastcrypto::traits::KeyPair;
-    use sui_config::node::MetricsConfig;
-
-    const DEFAULT_METRICS_PUSH_INTERVAL: Duration = Duration::from_secs(60);
+const DEFAULT_METRICS_PUSH_INTERVAL: Duration = Duration::from_secs(60);
 
-    let (interval, url) = match &config.metrics {
+/// Returns the push interval and url of a metrics config, or None if no push url is configured.
+pub fn metrics_push_target(
+    metrics: &Option<MetricsConfig>,
+) -> Result<Option<(Duration, reqwest::Url)>, anyhow::Error> {
+    match metrics {
         Some(MetricsConfig {
             push_interval_seconds,
             push_url: Some(url),
@@ -112,11 +112,25 @@ pub fn start_metrics_push_task(config: &sui_config::NodeConfig, registry: Regist
             let interval = push_interval_seconds
                 .map(Duration::from_secs)
                 .unwrap_or(DEFAULT_METRICS_PUSH_INTERVAL);
-            let url = reqwest::Url::parse(url).expect("unable to parse metrics push url");
-            (interval, url)
+            let url = reqwest::Url::parse(url)
+                .map_err(|e| anyhow::anyhow!("unable to parse metrics push url {url}: {e}"))?;
+            Ok(Some((interval, url)))
         }
-        _ => return,
-    };
+        _ => Ok(None),
+    }
+}
+
+/// Starts a task to periodically push metrics to the metrics push endpoint of the config, if one
+/// is configured. The returned sender updates the metrics config of the task, which stops when
+/// the sender is dropped.
+pub fn start_metrics_push_task(
+    config: &sui_config::NodeConfig,
+    registry: RegistryService,
+) -> watch::Sender<Option<MetricsConfig>> {
+    use fastcrypto::traits::KeyPair;
+
+    metrics_push_target(&config.metrics).expect("invalid metrics push config");
+    let (metrics_config_tx, mut metrics_config_rx) = watch::channel(config.metrics.clone());
 
     let client = MetricsPushClient::new(config.network_key_pair().copy());
 
@@ -169,19 +183,47 @@ pub fn start_metrics_push_task(config: &sui_config::NodeConfig, registry: Regist
     }
 
     tokio::spawn(async move {
-        tracing::info!(push_url =% url, interval =? interval, "Started Metrics Push Service");
-
-        let mut interval = tokio::time::interval(interval);
-        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
-
         loop {
-            interval.tick().await;
+            let target = match metrics_push_target(&metrics_config_rx.borrow_and_update()) {
+                Ok(target) => target,
+                Err(error) => {
+                    warn!("not pushing metrics: {error}");
+                    None
+                }
+            };
 
-            if let Err(error) = push_metrics(&client, &url, &registry).await {
-                tracing::warn!("unable to push metrics: {error}");
+            let Some((interval, url)) = target else {
+                // Wait for a push url to be configured.
+                if metrics_config_rx.changed().await.is_err() {
+                    return;
+                }
+                continue;
+            };
+
+            tracing::info!(push_url =% url, interval =? interval, "Started Metrics Push Service");
+
+            let mut interval = tokio::time::interval(interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        if let Err(error) = push_metrics(&client, &url, &registry).await {
+                            tracing::warn!("unable to push metrics: {error}");
+                        }
+                    }
+                    changed = metrics_config_rx.changed() => {
+                        if changed.is_err() {
+                            return;
+                        }
+                        break;
+                    }
+                }
             }
         }
     });
+
+    metrics_config_tx
 }
 
 #[derive(Clone)]
@@ -0,0 +1,110 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reloading of the config fields which can be changed without restarting the node.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use sui_config::NodeConfig;
+use tracing::{info, warn};
+
+use crate::metrics::metrics_push_target;
+use crate::SuiNode;
+
+/// Loads the config file of a running node, with the same overrides, e.g. from the command line, as
+/// the config the node was started with.
+pub type LoadConfig = Arc<dyn Fn() -> Result<NodeConfig> + Send + Sync>;
+
+/// The outcome of [SuiNode::reload_config].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ConfigReload {
+    /// The names of the changed fields, which were applied.
+    pub applied: Vec<&'static str>,
+    /// Whether other fields changed, which only take effect after a restart.
+    pub requires_restart: bool,
+}
+
+impl SuiNode {
+    /// Applies the fields of `new_config` which can be changed while the node is running:
+    /// `rpc-limits`, `authority-store-pruning-config`, `metrics`, `transaction-policy`, and the
+    /// seed peers and allowlisted peers of `p2p-config`, and returns the names of those that
+    /// changed. Changes to any other field are ignored with a warning, and only take effect after
+    /// a restart.
+    pub async fn reload_config(&self, mut new_config: NodeConfig) -> Result<ConfigReload> {
+        metrics_push_target(&new_config.metrics)?;
+
+        let mut current = self.reloaded_config.lock().await;
+        // Not read from the config file, see main.rs.
+        new_config.supported_protocol_versions = current.supported_protocol_versions;
+
+        let mut changed = vec![];
+        if new_config.rpc_limits != current.rpc_limits {
+            self.request_limits
+                .set_max_concurrent_requests(new_config.rpc_limits.max_concurrent_requests);
+            self.request_limits
+                .set_max_response_size(new_config.rpc_limits.max_response_size);
+            self.request_limits
+                .set_execution_lane_size(new_config.rpc_limits.execution_lane_size.unwrap_or(0));
+            self.request_limits
+                .set_method_concurrency_limits(&new_config.rpc_limits.method_concurrency_limits);
+            self.request_limits.set_method_queue_timeout(
+                new_config
+                    .rpc_limits
+                    .method_queue_timeout_ms
+                    .map(Duration::from_millis),
+            );
+            changed.push("rpc-limits");
+        }
+        if new_config.authority_store_pruning_config != current.authority_store_pruning_config {
+            self.state
+                .update_pruning_config(new_config.authority_store_pruning_config.clone());
+            changed.push("authority-store-pruning-config");
+        }
+        if new_config.metrics != current.metrics {
+            self.metrics_push_tx
+                .send_replace(new_config.metrics.clone());
+            changed.push("metrics");
+        }
+        if new_config.transaction_policy != current.transaction_policy {
+            if let Some(transaction_orchestrator) = &self.transaction_orchestrator {
+                transaction_orchestrator
+                    .transaction_policy()
+                    .update_config(new_config.transaction_policy.clone());
+            }
+            changed.push("transaction-policy");
+        }
+        if new_config.p2p_config.seed_peers != current.p2p_config.seed_peers {
+            self.discovery
+                .update_seed_peers(new_config.p2p_config.seed_peers.clone());
+            changed.push("p2p-config.seed-peers");
+        }
+        if new_config.p2p_config.allowlisted_peers != current.p2p_config.allowlisted_peers {
+            self.peer_allowlist
+                .update(new_config.p2p_config.allowlisted_peers.iter().copied());
+            changed.push("p2p-config.allowlisted-peers");
+        }
+
+        // Compare the remaining fields, by giving the reloaded fields their new values.
+        let mut reloaded = current.clone();
+        reloaded.rpc_limits = new_config.rpc_limits.clone();
+        reloaded.authority_store_pruning_config = new_config.authority_store_pruning_config.clone();
+        reloaded.metrics = new_config.metrics.clone();
+        reloaded.transaction_policy = new_config.transaction_policy.clone();
+        reloaded.p2p_config.seed_peers = new_config.p2p_config.seed_peers.clone();
+        reloaded.p2p_config.allowlisted_peers = new_config.p2p_config.allowlisted_peers.clone();
+        let requires_restart =
+            serde_yaml::to_value(&reloaded)? != serde_yaml::to_value(&new_config)?;
+        if requires_restart {
+            warn!("Config has changes which can't be reloaded, they require a restart");
+        }
+        *current = reloaded;
+
+        info!(?changed, "Reloaded config");
+        Ok(ConfigReload {
+            applied: changed,
+            requires_restart,
+        })
+    }
+}
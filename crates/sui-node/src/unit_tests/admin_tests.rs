@@ -0,0 +1,63 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use axum::body::Body;
+use axum::http::Request;
+use sui_config::builder::ConfigBuilder;
+use tower::ServiceExt;
+
+async fn open_store(dir: &tempfile::TempDir) -> Arc<AuthorityStore> {
+    let genesis = ConfigBuilder::new(dir).build().genesis;
+    let committee = genesis.committee().unwrap();
+    let store = AuthorityStore::open_with_committee_for_testing(
+        &dir.path().join("store"),
+        None,
+        &committee,
+        &genesis,
+        0,
+    )
+    .await
+    .unwrap();
+    Arc::new(store)
+}
+
+async fn post(app: &Router, uri: &str) -> StatusCode {
+    let request = Request::post(uri).body(Body::empty()).unwrap();
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_compact_table() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let app = db_router(open_store(&dir).await);
+
+    let uri = format!("{DB_COMPACT_ROUTE}?cf=objects");
+    assert_eq!(post(&app, &uri).await, StatusCode::OK);
+    let uri = format!("{DB_COMPACT_ROUTE}?cf=unknown");
+    assert_eq!(post(&app, &uri).await, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_set_auto_compactions() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let app = db_router(open_store(&dir).await);
+
+    for enabled in [false, true] {
+        let uri = format!("{DB_AUTO_COMPACTIONS_ROUTE}?cf=objects&enabled={enabled}");
+        assert_eq!(post(&app, &uri).await, StatusCode::OK);
+    }
+    let uri = format!("{DB_AUTO_COMPACTIONS_ROUTE}?cf=unknown&enabled=false");
+    assert_eq!(post(&app, &uri).await, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_set_compaction_throttle() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let app = db_router(open_store(&dir).await);
+
+    let uri = format!("{DB_COMPACTION_THROTTLE_ROUTE}?max_background_jobs=2");
+    assert_eq!(post(&app, &uri).await, StatusCode::OK);
+    let uri = format!("{DB_COMPACTION_THROTTLE_ROUTE}?max_background_jobs=0");
+    assert_eq!(post(&app, &uri).await, StatusCode::BAD_REQUEST);
+}
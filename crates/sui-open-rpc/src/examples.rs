@@ -401,6 +401,7 @@ impl RpcExampleProvider {
             data,
             next_cursor,
             has_next_page,
+            truncated_due_to_limit: false,
         };
         Examples::new(
             "suix_queryTransactionBlocks",
@@ -536,6 +537,7 @@ impl RpcExampleProvider {
             confirmed_local_execution: None,
             checkpoint: None,
             errors: vec![],
+            effects_certificate: None,
         };
 
         (data2, signatures, recipient, obj_id, result)
@@ -563,6 +565,7 @@ impl RpcExampleProvider {
             data: vec![event],
             next_cursor: Some((tx_dig, 5).into()),
             has_next_page: false,
+            truncated_due_to_limit: false,
         };
         Examples::new(
             "sui_getEvents",
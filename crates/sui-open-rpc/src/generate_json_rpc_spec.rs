@@ -18,6 +18,8 @@ use sui_json_rpc::sui_rpc_doc;
 use sui_json_rpc::transaction_builder_api::TransactionBuilderApi;
 use sui_json_rpc::transaction_execution_api::TransactionExecutionApi;
 use sui_json_rpc::SuiRpcModule;
+use sui_json_rpc_types::RPC_TYPES_SCHEMA_VERSION;
+use sui_open_rpc::JsonSchemaSet;
 
 mod examples;
 
@@ -26,6 +28,8 @@ enum Action {
     Print,
     Test,
     Record,
+    /// Prints the JSON Schema of the RPC types, to publish it for the SDKs in other languages.
+    PrintJsonSchema,
 }
 
 #[derive(Debug, Parser)]
@@ -39,6 +43,7 @@ struct Options {
 }
 
 const FILE_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/spec/openrpc.json",);
+const JSON_SCHEMA_FILE_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/spec/json-schema.json");
 
 #[tokio::main]
 async fn main() {
@@ -55,6 +60,7 @@ async fn main() {
     open_rpc.add_module(MoveUtilsOpenRpc::module_doc());
 
     open_rpc.add_examples(RpcExampleProvider::new().examples());
+    let json_schema = open_rpc.json_schema_set(RPC_TYPES_SCHEMA_VERSION);
 
     match options.action {
         Action::Print => {
@@ -65,11 +71,37 @@ async fn main() {
             let content = serde_json::to_string_pretty(&open_rpc).unwrap();
             let mut f = File::create(FILE_PATH).unwrap();
             writeln!(f, "{content}").unwrap();
+            record_json_schema(&json_schema);
         }
         Action::Test => {
             let reference = std::fs::read_to_string(FILE_PATH).unwrap();
             let content = serde_json::to_string_pretty(&open_rpc).unwrap() + "\n";
             assert_str_eq!(&reference, &content);
+            let reference = std::fs::read_to_string(JSON_SCHEMA_FILE_PATH).unwrap();
+            let content = serde_json::to_string_pretty(&json_schema).unwrap() + "\n";
+            assert_str_eq!(&reference, &content);
+        }
+        Action::PrintJsonSchema => {
+            let content = serde_json::to_string_pretty(&json_schema).unwrap();
+            println!("{content}");
         }
     }
 }
+
+/// Records the JSON Schema of the RPC types, refusing to record changed definitions under the
+/// version of the recorded ones.
+fn record_json_schema(json_schema: &JsonSchemaSet) {
+    if let Ok(reference) = std::fs::read_to_string(JSON_SCHEMA_FILE_PATH) {
+        let reference: JsonSchemaSet = serde_json::from_str(&reference).unwrap();
+        assert!(
+            reference.version != json_schema.version
+                || reference.definitions == json_schema.definitions,
+            "The RPC types changed since version {} of their JSON Schema, bump \
+             RPC_TYPES_SCHEMA_VERSION in sui-json-rpc-types and record again",
+            reference.version
+        );
+    }
+    let content = serde_json::to_string_pretty(json_schema).unwrap();
+    let mut f = File::create(JSON_SCHEMA_FILE_PATH).unwrap();
+    writeln!(f, "{content}").unwrap();
+}
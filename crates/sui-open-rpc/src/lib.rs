@@ -13,6 +13,12 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use versions::Versioning;
 
+/// Where the open rpc document keeps the schemas of the types of the methods.
+const SCHEMAS_PATH: &str = "#/components/schemas/";
+const DEFINITIONS_PATH: &str = "#/definitions/";
+/// The schemas are generated by schemars following draft 7 of JSON Schema.
+const JSON_SCHEMA_DIALECT: &str = "http://json-schema.org/draft-07/schema#";
+
 /// OPEN-RPC documentation following the OpenRPC specification <https://spec.open-rpc.org>
 /// The implementation is partial, only required fields and subset of optional fields
 /// in the specification are implemented catered to Sui's need.
@@ -22,6 +28,14 @@ pub struct Project {
     info: Info,
     methods: Vec<Method>,
     components: Components,
+    /// The optional features of the server, and whether they are enabled. A specification
+    /// extension, not part of the open rpc spec.
+    #[serde(
+        rename = "x-features",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    features: BTreeMap<String, bool>,
     // Method routing for backward compatibility, not part of the open rpc spec.
     #[serde(skip)]
     pub method_routing: HashMap<String, MethodRouting>,
@@ -61,6 +75,7 @@ impl Project {
                 content_descriptors: Default::default(),
                 schemas: Default::default(),
             },
+            features: Default::default(),
             method_routing: Default::default(),
         }
     }
@@ -77,6 +92,26 @@ impl Project {
         self.method_routing.extend(module.method_routing);
     }
 
+    /// The names of the methods marked as deprecated.
+    pub fn deprecated_methods(&self) -> impl Iterator<Item = &str> {
+        self.methods
+            .iter()
+            .filter(|method| method.deprecated)
+            .map(|method| method.name.as_str())
+    }
+
+    /// Records whether the optional `feature` of the server is enabled.
+    pub fn set_feature(&mut self, feature: &str, enabled: bool) {
+        self.features.insert(feature.to_string(), enabled);
+    }
+
+    /// Leaves the methods `names` out of the documentation, for the methods of a feature which
+    /// is not enabled.
+    pub fn remove_methods(&mut self, names: &[&str]) {
+        self.methods
+            .retain(|method| !names.contains(&method.name.as_str()));
+    }
+
     pub fn add_examples(&mut self, mut example_provider: BTreeMap<String, Vec<ExamplePairing>>) {
         for method in &mut self.methods {
             if let Occupied(entry) = example_provider.entry(method.name.clone()) {
@@ -104,6 +139,92 @@ impl Project {
             }
         }
     }
+
+    /// The JSON Schema of the types of the requests and responses of the methods, recorded as
+    /// `version` of the schema.
+    pub fn json_schema_set(&self, version: &str) -> JsonSchemaSet {
+        let definitions = self
+            .components
+            .schemas
+            .iter()
+            .map(|(name, schema)| {
+                let mut schema = serde_json::to_value(schema).expect("schemas serialize to JSON");
+                point_refs_to_definitions(&mut schema);
+                (name.clone(), schema)
+            })
+            .collect();
+        JsonSchemaSet {
+            schema: JSON_SCHEMA_DIALECT.to_string(),
+            title: format!("{} types", self.info.title),
+            version: version.to_string(),
+            definitions,
+        }
+    }
+}
+
+/// The JSON Schema of the types of the requests and responses of the API, for SDKs in other
+/// languages to generate their models from. The definitions are the schemas of the open rpc
+/// document, referring to each other within the set.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct JsonSchemaSet {
+    #[serde(rename = "$schema")]
+    schema: String,
+    title: String,
+    /// Semantic version of the definitions.
+    pub version: String,
+    pub definitions: BTreeMap<String, Value>,
+}
+
+/// Rewrites the references to the schemas of the open rpc document within `schema` into
+/// references to the definitions of a [JsonSchemaSet].
+fn point_refs_to_definitions(schema: &mut Value) {
+    match schema {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                match value {
+                    Value::String(reference) if key == "$ref" => {
+                        let rewritten = reference
+                            .strip_prefix(SCHEMAS_PATH)
+                            .map(|name| format!("{DEFINITIONS_PATH}{name}"));
+                        if let Some(rewritten) = rewritten {
+                            *reference = rewritten;
+                        }
+                    }
+                    _ => point_refs_to_definitions(value),
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(point_refs_to_definitions),
+        _ => {}
+    }
+}
+
+#[test]
+fn test_point_refs_to_definitions() {
+    let mut schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "digest": { "$ref": "#/components/schemas/TransactionDigest" },
+            "events": {
+                "type": "array",
+                "items": { "anyOf": [{ "$ref": "#/components/schemas/Event" }, { "type": "null" }] }
+            }
+        }
+    });
+    point_refs_to_definitions(&mut schema);
+    assert_eq!(
+        schema,
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "digest": { "$ref": "#/definitions/TransactionDigest" },
+                "events": {
+                    "type": "array",
+                    "items": { "anyOf": [{ "$ref": "#/definitions/Event" }, { "type": "null" }] }
+                }
+            }
+        })
+    );
 }
 
 pub struct Module {
@@ -306,7 +427,7 @@ impl Default for RpcModuleDocBuilder {
     fn default() -> Self {
         let schema_generator = SchemaSettings::default()
             .with(|s| {
-                s.definitions_path = "#/components/schemas/".to_string();
+                s.definitions_path = SCHEMAS_PATH.to_string();
             })
             .into_generator();
 
@@ -6,6 +6,7 @@
 fn test_json_rpc_spec() {
     // If this test breaks and you intended a json rpc schema change, you need to run to get the fresh schema:
     // # cargo -q run --example generate-json-rpc-spec -- record
+    // Changes of the RPC types also need a new RPC_TYPES_SCHEMA_VERSION for their JSON Schema.
     let status = std::process::Command::new("cargo")
         .current_dir("..")
         .args(["run", "--example", "generate-json-rpc-spec", "--"])
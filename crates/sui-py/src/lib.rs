@@ -0,0 +1,200 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Python bindings of the serialization of the core Sui types, the parsing of addresses and type
+//! tags, and the verification of user signatures, built as the `sui_py` extension module with
+//! maturin.
+//!
+//! Values cross the binding as JSON strings, in the format of the serde implementations of the
+//! Rust types, and as `bytes` for their BCS serialization. Malformed inputs raise `ValueError`.
+
+use std::str::FromStr;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use shared_crypto::intent::{Intent, IntentMessage, IntentScope, PersonalMessage};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::crypto::{default_hash, ToFromBytes};
+use sui_types::digests::TransactionDigest;
+use sui_types::event::Event;
+use sui_types::messages::{
+    SenderSignedData, TransactionData, TransactionDataAPI, TransactionEffects, TransactionEvents,
+};
+use sui_types::messages_checkpoint::{CheckpointContents, CheckpointSummary};
+use sui_types::object::Object;
+use sui_types::parse_sui_type_tag;
+use sui_types::signature::{AuthenticatorTrait, GenericSignature};
+use sui_types::TypeTag;
+
+#[cfg(test)]
+#[path = "unit_tests/sui_py_tests.rs"]
+mod sui_py_tests;
+
+fn value_error(e: impl ToString) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+macro_rules! bcs_types {
+    ($($type_:ident),* $(,)?) => {
+        /// Names of the types converted by [bcs_to_json] and [json_to_bcs].
+        const TYPE_NAMES: &[&str] = &[$(stringify!($type_)),*];
+
+        fn bcs_to_json_as(type_name: &str, bytes: &[u8]) -> PyResult<String> {
+            match type_name {
+                $(stringify!($type_) => to_json(&from_bcs::<$type_>(bytes)?),)*
+                _ => Err(unknown_type(type_name)),
+            }
+        }
+
+        fn json_to_bcs_as(type_name: &str, json: &str) -> PyResult<Vec<u8>> {
+            match type_name {
+                $(stringify!($type_) => to_bcs(&from_json::<$type_>(json)?),)*
+                _ => Err(unknown_type(type_name)),
+            }
+        }
+    };
+}
+
+bcs_types!(
+    CheckpointContents,
+    CheckpointSummary,
+    Event,
+    Object,
+    ObjectID,
+    SenderSignedData,
+    SuiAddress,
+    TransactionData,
+    TransactionEffects,
+    TransactionEvents,
+    TypeTag,
+);
+
+fn unknown_type(type_name: &str) -> PyErr {
+    PyValueError::new_err(format!(
+        "Unknown type {type_name}, expected one of {TYPE_NAMES:?}"
+    ))
+}
+
+fn from_bcs<T: DeserializeOwned>(bytes: &[u8]) -> PyResult<T> {
+    bcs::from_bytes(bytes).map_err(value_error)
+}
+
+fn to_bcs<T: Serialize>(value: &T) -> PyResult<Vec<u8>> {
+    bcs::to_bytes(value).map_err(value_error)
+}
+
+fn from_json<T: DeserializeOwned>(json: &str) -> PyResult<T> {
+    serde_json::from_str(json).map_err(value_error)
+}
+
+fn to_json<T: Serialize>(value: &T) -> PyResult<String> {
+    serde_json::to_string(value).map_err(value_error)
+}
+
+/// Decodes `data`, the BCS serialization of a value of the type named `type_name`, to JSON.
+#[pyfunction]
+fn bcs_to_json(type_name: &str, data: &[u8]) -> PyResult<String> {
+    bcs_to_json_as(type_name, data)
+}
+
+/// Encodes `json`, a value of the type named `type_name`, to BCS.
+#[pyfunction]
+fn json_to_bcs<'p>(py: Python<'p>, type_name: &str, json: &str) -> PyResult<&'p PyBytes> {
+    Ok(PyBytes::new(py, &json_to_bcs_as(type_name, json)?))
+}
+
+/// Parses a hex address, with or without `0x` prefix, and returns it in its canonical form.
+#[pyfunction]
+fn parse_address(address: &str) -> PyResult<String> {
+    Ok(SuiAddress::from_str(address)
+        .map_err(value_error)?
+        .to_string())
+}
+
+#[pyfunction]
+fn parse_object_id(object_id: &str) -> PyResult<String> {
+    Ok(ObjectID::from_str(object_id)
+        .map_err(value_error)?
+        .to_string())
+}
+
+/// Parses a type tag such as `0x2::coin::Coin<0x2::sui::SUI>`, and returns it with its addresses
+/// in full.
+#[pyfunction]
+fn parse_type_tag(type_tag: &str) -> PyResult<String> {
+    Ok(parse_sui_type_tag(type_tag)
+        .map_err(value_error)?
+        .to_string())
+}
+
+#[pyfunction]
+fn type_tag_to_bcs<'p>(py: Python<'p>, type_tag: &str) -> PyResult<&'p PyBytes> {
+    let type_tag = parse_sui_type_tag(type_tag).map_err(value_error)?;
+    Ok(PyBytes::new(py, &to_bcs(&type_tag)?))
+}
+
+/// Digest of the transaction of BCS serialized data `tx_data`, in base58.
+#[pyfunction]
+fn transaction_digest(tx_data: &[u8]) -> PyResult<String> {
+    let tx_data: TransactionData = from_bcs(tx_data)?;
+    Ok(TransactionDigest::new(default_hash(&tx_data)).to_string())
+}
+
+/// Whether `signature`, a single or multi signature serialized as in transactions, is the one
+/// of the sender of the transaction of BCS serialized data `tx_data`.
+#[pyfunction]
+fn verify_transaction_signature(tx_data: &[u8], signature: &[u8]) -> PyResult<bool> {
+    let tx_data: TransactionData = from_bcs(tx_data)?;
+    let sender = tx_data.sender();
+    verify(
+        &IntentMessage::new(Intent::sui_transaction(), tx_data),
+        signature,
+        sender,
+    )
+}
+
+/// Whether `signature` is a signature of the personal message `message` by `address`.
+#[pyfunction]
+fn verify_personal_message_signature(
+    message: &[u8],
+    signature: &[u8],
+    address: &str,
+) -> PyResult<bool> {
+    let address = SuiAddress::from_str(address).map_err(value_error)?;
+    let message = PersonalMessage {
+        message: message.to_vec(),
+    };
+    verify(
+        &IntentMessage::new(Intent::sui_app(IntentScope::PersonalMessage), message),
+        signature,
+        address,
+    )
+}
+
+fn verify<T: Serialize>(
+    message: &IntentMessage<T>,
+    signature: &[u8],
+    author: SuiAddress,
+) -> PyResult<bool> {
+    let signature = GenericSignature::from_bytes(signature).map_err(value_error)?;
+    Ok(signature.verify_secure_generic(message, author).is_ok())
+}
+
+#[pymodule]
+fn sui_py(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add("TYPE_NAMES", TYPE_NAMES.to_vec())?;
+    m.add_function(wrap_pyfunction!(bcs_to_json, m)?)?;
+    m.add_function(wrap_pyfunction!(json_to_bcs, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_address, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_object_id, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_type_tag, m)?)?;
+    m.add_function(wrap_pyfunction!(type_tag_to_bcs, m)?)?;
+    m.add_function(wrap_pyfunction!(transaction_digest, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_transaction_signature, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_personal_message_signature, m)?)?;
+    Ok(())
+}
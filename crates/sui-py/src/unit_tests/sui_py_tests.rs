@@ -0,0 +1,115 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use sui_types::base_types::random_object_ref;
+use sui_types::crypto::{get_key_pair, AccountKeyPair, Signature, SuiKeyPair};
+use sui_types::messages::Transaction;
+
+fn new_key_pair() -> (SuiAddress, SuiKeyPair) {
+    let (address, key_pair): (_, AccountKeyPair) = get_key_pair();
+    (address, SuiKeyPair::Ed25519(key_pair))
+}
+
+fn transfer(sender: SuiAddress) -> TransactionData {
+    TransactionData::new_transfer_sui_with_dummy_gas_price(
+        SuiAddress::random_for_testing_only(),
+        sender,
+        Some(1000),
+        random_object_ref(),
+        10000,
+    )
+}
+
+#[test]
+fn test_bcs_json_round_trip() {
+    let tx_data = transfer(SuiAddress::random_for_testing_only());
+    let type_tag = parse_sui_type_tag("0x2::coin::Coin<0x2::sui::SUI>").unwrap();
+    let values = [
+        ("TransactionData", bcs::to_bytes(&tx_data).unwrap()),
+        (
+            "Object",
+            bcs::to_bytes(&Object::immutable_with_id_for_testing(ObjectID::random())).unwrap(),
+        ),
+        (
+            "SuiAddress",
+            bcs::to_bytes(&SuiAddress::random_for_testing_only()).unwrap(),
+        ),
+        ("TypeTag", bcs::to_bytes(&type_tag).unwrap()),
+    ];
+    for (type_name, bytes) in values {
+        let json = bcs_to_json_as(type_name, &bytes).unwrap();
+        assert_eq!(
+            json_to_bcs_as(type_name, &json).unwrap(),
+            bytes,
+            "{type_name}"
+        );
+    }
+
+    let bytes = bcs::to_bytes(&tx_data).unwrap();
+    assert!(bcs_to_json_as("TransactionData", &bytes[..bytes.len() - 1]).is_err());
+    assert!(bcs_to_json_as("Transaction", &bytes).is_err());
+    assert!(json_to_bcs_as("TransactionData", "{}").is_err());
+}
+
+#[test]
+fn test_transaction_digest() {
+    let (sender, key_pair) = new_key_pair();
+    let tx_data = transfer(sender);
+    let transaction = Transaction::from_data_and_signer(
+        tx_data.clone(),
+        Intent::sui_transaction(),
+        vec![&key_pair],
+    );
+    assert_eq!(
+        transaction_digest(&bcs::to_bytes(&tx_data).unwrap()).unwrap(),
+        transaction.digest().to_string()
+    );
+    assert!(transaction_digest(&[0]).is_err());
+}
+
+#[test]
+fn test_verify_transaction_signature() {
+    let (sender, key_pair) = new_key_pair();
+    let tx_data = transfer(sender);
+    let tx_bytes = bcs::to_bytes(&tx_data).unwrap();
+    let signature = Signature::new_secure(
+        &IntentMessage::new(Intent::sui_transaction(), tx_data),
+        &key_pair,
+    );
+    assert!(verify_transaction_signature(&tx_bytes, signature.as_ref()).unwrap());
+
+    // Signed by another key than the one of the sender.
+    let (_, other_key_pair) = new_key_pair();
+    let other = transfer(SuiAddress::from(&other_key_pair.public()));
+    let other_signature = Signature::new_secure(
+        &IntentMessage::new(Intent::sui_transaction(), other),
+        &other_key_pair,
+    );
+    assert!(!verify_transaction_signature(&tx_bytes, other_signature.as_ref()).unwrap());
+
+    assert!(verify_transaction_signature(&tx_bytes, &[0; 3]).is_err());
+}
+
+#[test]
+fn test_verify_personal_message_signature() {
+    let (address, key_pair) = new_key_pair();
+    let message = b"Hello, Sui".to_vec();
+    let signature = Signature::new_secure(
+        &IntentMessage::new(
+            Intent::sui_app(IntentScope::PersonalMessage),
+            PersonalMessage {
+                message: message.clone(),
+            },
+        ),
+        &key_pair,
+    );
+    let address = address.to_string();
+    assert!(verify_personal_message_signature(&message, signature.as_ref(), &address).unwrap());
+    assert!(
+        !verify_personal_message_signature(b"Hello, Move", signature.as_ref(), &address).unwrap()
+    );
+    let other = SuiAddress::random_for_testing_only().to_string();
+    assert!(!verify_personal_message_signature(&message, signature.as_ref(), &other).unwrap());
+    assert!(verify_personal_message_signature(&message, signature.as_ref(), "0xinvalid").is_err());
+}
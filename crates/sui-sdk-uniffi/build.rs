@@ -0,0 +1,27 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use camino::Utf8Path;
+
+const UDL_FILE: &str = "src/sui_sdk.udl";
+
+/// Generates the Rust scaffolding of the UDL interface, and its Swift and Kotlin bindings in
+/// `$OUT_DIR/bindings`, for the mobile projects linking the library.
+fn main() {
+    uniffi::generate_scaffolding(UDL_FILE).unwrap();
+
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    let bindings_dir = Utf8Path::new(&out_dir).join("bindings");
+    uniffi_bindgen::generate_bindings(
+        Utf8Path::new(UDL_FILE),
+        None,
+        vec!["swift", "kotlin"],
+        Some(&bindings_dir),
+        None,
+        false,
+    )
+    .unwrap();
+
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed={UDL_FILE}");
+}
@@ -0,0 +1,218 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! UniFFI bindings of a subset of the SDK for mobile wallets, declared in `src/sui_sdk.udl`. The
+//! Swift and Kotlin sources are generated in `bindings/` when the crate is built.
+//!
+//! The foreign languages call the SDK synchronously: each [SuiClient] runs its requests, and the
+//! event subscriptions it opens, on a runtime of its own.
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use futures::{future, StreamExt};
+use shared_crypto::intent::{Intent, IntentMessage};
+use sui_sdk::rpc_types::{EventFilter, SuiEvent, SuiTransactionBlockResponseOptions};
+use sui_sdk::types::base_types::SuiAddress;
+use sui_sdk::types::crypto::{Signature, SuiKeyPair};
+use sui_sdk::types::messages::{ExecuteTransactionRequestType, Transaction};
+use sui_sdk::SuiClientBuilder;
+use tokio::runtime::Runtime;
+use tokio::task::JoinHandle;
+
+uniffi::include_scaffolding!("sui_sdk");
+
+#[derive(Debug, thiserror::Error)]
+pub enum SuiSdkError {
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+    #[error(transparent)]
+    Rpc(#[from] sui_sdk::error::Error),
+    #[error("No coin of {owner} can pay for a gas budget of {gas_budget} and transfer {amount}")]
+    InsufficientGas {
+        owner: SuiAddress,
+        gas_budget: u64,
+        amount: u64,
+    },
+    #[error("Failed to start the runtime of the client: {0}")]
+    Runtime(std::io::Error),
+}
+
+fn invalid_argument(e: impl ToString) -> SuiSdkError {
+    SuiSdkError::InvalidArgument(e.to_string())
+}
+
+pub struct Balance {
+    pub coin_type: String,
+    pub coin_object_count: u64,
+    pub total_balance: String,
+}
+
+impl From<sui_sdk::rpc_types::Balance> for Balance {
+    fn from(balance: sui_sdk::rpc_types::Balance) -> Self {
+        Self {
+            coin_type: balance.coin_type,
+            coin_object_count: balance.coin_object_count as u64,
+            total_balance: balance.total_balance.to_string(),
+        }
+    }
+}
+
+pub struct Event {
+    pub tx_digest: String,
+    pub event_seq: u64,
+    pub package_id: String,
+    pub transaction_module: String,
+    pub sender: String,
+    pub event_type: String,
+    pub parsed_json: String,
+    pub timestamp_ms: Option<u64>,
+}
+
+impl From<SuiEvent> for Event {
+    fn from(event: SuiEvent) -> Self {
+        Self {
+            tx_digest: event.id.tx_digest.to_string(),
+            event_seq: event.id.event_seq,
+            package_id: event.package_id.to_string(),
+            transaction_module: event.transaction_module.to_string(),
+            sender: event.sender.to_string(),
+            event_type: event.type_.to_string(),
+            parsed_json: event.parsed_json.to_string(),
+            timestamp_ms: event.timestamp_ms,
+        }
+    }
+}
+
+pub trait EventListener: Send + Sync {
+    fn on_event(&self, event: Event);
+    fn on_error(&self, message: String);
+}
+
+/// Events of a subscription being delivered to its listener, until cancelled.
+pub struct EventSubscription {
+    task: JoinHandle<()>,
+}
+
+impl EventSubscription {
+    pub fn cancel(&self) {
+        self.task.abort();
+    }
+}
+
+pub struct SuiClient {
+    runtime: Runtime,
+    client: sui_sdk::SuiClient,
+}
+
+impl SuiClient {
+    pub fn new(http_url: String, ws_url: Option<String>) -> Result<Self, SuiSdkError> {
+        let runtime = Runtime::new().map_err(SuiSdkError::Runtime)?;
+        let mut builder = SuiClientBuilder::default();
+        if let Some(ws_url) = ws_url {
+            builder = builder.ws_url(ws_url);
+        }
+        let client = runtime.block_on(builder.build(http_url))?;
+        Ok(Self { runtime, client })
+    }
+
+    pub fn get_balance(
+        &self,
+        owner: String,
+        coin_type: Option<String>,
+    ) -> Result<Balance, SuiSdkError> {
+        let owner = SuiAddress::from_str(&owner).map_err(invalid_argument)?;
+        let balance = self
+            .runtime
+            .block_on(self.client.coin_read_api().get_balance(owner, coin_type))?;
+        Ok(balance.into())
+    }
+
+    pub fn get_all_balances(&self, owner: String) -> Result<Vec<Balance>, SuiSdkError> {
+        let owner = SuiAddress::from_str(&owner).map_err(invalid_argument)?;
+        let balances = self
+            .runtime
+            .block_on(self.client.coin_read_api().get_all_balances(owner))?;
+        Ok(balances.into_iter().map(Balance::from).collect())
+    }
+
+    /// Transfers `amount` MIST from the address of `key` to `recipient`, paying for gas with the
+    /// same coin, and returns the digest of the transaction once executed by the fullnode.
+    pub fn transfer_sui(
+        &self,
+        key: Vec<u8>,
+        recipient: String,
+        amount: Option<u64>,
+        gas_budget: u64,
+    ) -> Result<String, SuiSdkError> {
+        let key = SuiKeyPair::from_bytes(&key).map_err(invalid_argument)?;
+        let owner = SuiAddress::from(&key.public());
+        let recipient = SuiAddress::from_str(&recipient).map_err(invalid_argument)?;
+        self.runtime.block_on(async {
+            let required = amount.unwrap_or(0) as u128 + gas_budget as u128;
+            let coin = self
+                .client
+                .coin_read_api()
+                .get_coins_stream(owner, None)
+                .filter(|coin| future::ready(coin.balance as u128 >= required))
+                .boxed()
+                .next()
+                .await
+                .ok_or(SuiSdkError::InsufficientGas {
+                    owner,
+                    gas_budget,
+                    amount: amount.unwrap_or(0),
+                })?;
+            let data = self
+                .client
+                .transaction_builder()
+                .transfer_sui(owner, coin.coin_object_id, gas_budget, recipient, amount)
+                .await
+                .map_err(|e| sui_sdk::error::Error::DataError(e.to_string()))?;
+            let signature = Signature::new_secure(
+                &IntentMessage::new(Intent::sui_transaction(), data.clone()),
+                &key,
+            );
+            let transaction =
+                Transaction::from_data(data, Intent::sui_transaction(), vec![signature])
+                    .verify()
+                    .map_err(invalid_argument)?;
+            let response = self
+                .client
+                .quorum_driver()
+                .execute_transaction_block(
+                    transaction,
+                    SuiTransactionBlockResponseOptions::new(),
+                    Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+                )
+                .await?;
+            Ok(response.digest.to_string())
+        })
+    }
+
+    /// Delivers the events matching `filter` to `listener`, from a task of the runtime of the
+    /// client. The client must be built with a WebSocket URL.
+    pub fn subscribe_events(
+        &self,
+        filter: String,
+        listener: Box<dyn EventListener>,
+    ) -> Result<Arc<EventSubscription>, SuiSdkError> {
+        let filter: EventFilter = serde_json::from_str(&filter).map_err(invalid_argument)?;
+        let mut events = self
+            .runtime
+            .block_on(self.client.event_api().subscribe_event(filter))?
+            .boxed();
+        let task = self.runtime.spawn(async move {
+            while let Some(event) = events.next().await {
+                match event {
+                    Ok(event) => listener.on_event(event.into()),
+                    Err(e) => {
+                        listener.on_error(e.to_string());
+                        return;
+                    }
+                }
+            }
+        });
+        Ok(Arc::new(EventSubscription { task }))
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::Path;
+use std::str::FromStr;
+
+use sui_keys::keystore::AccountKeystore;
+use sui_sdk::types::base_types::TransactionDigest;
+use sui_sdk_uniffi::{SuiClient, SuiSdkError};
+use test_utils::network::TestClusterBuilder;
+use tokio::runtime::Runtime;
+
+const GAS_BUDGET: u64 = 50_000_000;
+
+/// The bindings generated from the UDL file declare the interface implemented by the crate.
+#[test]
+fn test_generated_bindings() {
+    let bindings_dir = Path::new(env!("OUT_DIR")).join("bindings");
+    let swift = std::fs::read_to_string(bindings_dir.join("sui_sdk.swift")).unwrap();
+    let kotlin = std::fs::read_to_string(bindings_dir.join("uniffi/sui_sdk/sui_sdk.kt")).unwrap();
+    for (bindings, names) in [
+        (
+            swift,
+            ["SuiClient", "transferSui", "subscribeEvents", "SuiSdkError"],
+        ),
+        (
+            kotlin,
+            [
+                "SuiClient",
+                "transferSui",
+                "subscribeEvents",
+                "SuiSdkException",
+            ],
+        ),
+    ] {
+        for name in names {
+            assert!(bindings.contains(name), "{name} is not in the bindings");
+        }
+    }
+}
+
+#[test]
+fn test_invalid_arguments() {
+    // The client fails instead of panicking across the FFI boundary.
+    assert!(SuiClient::new("not a url".to_string(), None).is_err());
+}
+
+#[test]
+fn test_transfer_sui() -> Result<(), anyhow::Error> {
+    // The client blocks on a runtime of its own, and is called from outside of the runtime of
+    // the cluster, as by the foreign languages.
+    let runtime = Runtime::new()?;
+    // The cluster is dropped within the context of its runtime.
+    let _guard = runtime.enter();
+    let cluster = runtime.block_on(TestClusterBuilder::new().build())?;
+    let sender = cluster.get_address_0();
+    let recipient = cluster.get_address_1().to_string();
+    let key = cluster.wallet.config.keystore.get_key(&sender)?.to_bytes();
+
+    let client = SuiClient::new(cluster.rpc_url().to_string(), None)?;
+    let balance = |address: &str| -> Result<u128, anyhow::Error> {
+        Ok(client
+            .get_balance(address.to_string(), None)?
+            .total_balance
+            .parse()?)
+    };
+    let before = balance(&recipient)?;
+    let digest = client.transfer_sui(key.clone(), recipient.clone(), Some(1000), GAS_BUDGET)?;
+    assert!(TransactionDigest::from_str(&digest).is_ok());
+    assert_eq!(balance(&recipient)?, before + 1000);
+
+    assert!(matches!(
+        client.transfer_sui(key.clone(), recipient.clone(), Some(u64::MAX), GAS_BUDGET),
+        Err(SuiSdkError::InsufficientGas { .. })
+    ));
+    assert!(matches!(
+        client.transfer_sui(key[1..].to_vec(), recipient, Some(1000), GAS_BUDGET),
+        Err(SuiSdkError::InvalidArgument(_))
+    ));
+    Ok(())
+}
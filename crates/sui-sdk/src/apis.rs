@@ -11,6 +11,7 @@ use futures::stream;
 use futures::StreamExt;
 use futures_core::Stream;
 use jsonrpsee::core::client::Subscription;
+use serde::de::DeserializeOwned;
 
 use sui_json_rpc::api::GovernanceReadApiClient;
 use sui_json_rpc::api::{
@@ -18,11 +19,14 @@ use sui_json_rpc::api::{
 };
 use sui_json_rpc_types::{
     Balance, Checkpoint, CheckpointId, Coin, CoinPage, DelegatedStake,
-    DryRunTransactionBlockResponse, DynamicFieldPage, EventFilter, EventPage, ObjectsPage,
-    SuiCoinMetadata, SuiCommittee, SuiEvent, SuiGetPastObjectRequest, SuiMoveNormalizedModule,
-    SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery, SuiPastObjectResponse,
+    DryRunTransactionBlockResponse, DynamicFieldExportPage, DynamicFieldPage, EventFilter,
+    EventPage, GasBudgetEstimate, GasBudgetSafetyMargin, ObjectsPage, OwnedObjectChange,
+    SuiCheckpointCertificate, SuiCoinMetadata, SuiCommittee, SuiEvent, SuiGetPastObjectRequest,
+    SuiMoveNormalizedModule, SuiObjectDataOptions, SuiObjectLockStatus, SuiObjectProof,
+    SuiObjectResponse, SuiObjectResponseQuery, SuiObjectVersionsDiff, SuiPastObjectResponse,
     SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
-    SuiTransactionBlockResponseQuery, TransactionBlocksPage,
+    SuiTransactionBlockResponseQuery, SuiTransactionBlockStatus, SuiTransactionProof,
+    TransactionBlocksPage, ValidatorEpochOutcomePage, ValidatorReport,
 };
 use sui_types::balance::Supply;
 use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
@@ -34,17 +38,31 @@ use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::sui_serde::BigInt;
 use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
 
+use crate::equivocation_guard::EquivocationGuard;
 use crate::error::{Error, SuiRpcResult};
+use crate::module_cache::NormalizedModuleCache;
+use crate::retry::RetryPolicy;
+use crate::typed_events::{EventLayoutCache, TypedEventStream};
 use crate::{RpcClient, WAIT_FOR_TX_TIMEOUT_SEC};
 
+const EXECUTE_TRANSACTION_BLOCK_METHOD: &str = "sui_executeTransactionBlock";
+
 #[derive(Debug)]
 pub struct ReadApi {
     api: Arc<RpcClient>,
+    modules: Arc<NormalizedModuleCache>,
 }
 
 impl ReadApi {
     pub(crate) fn new(api: Arc<RpcClient>) -> Self {
-        Self { api }
+        let modules = Arc::new(NormalizedModuleCache::new(api.clone()));
+        Self { api, modules }
+    }
+
+    /// Cache of the normalized Move modules of the packages, used to resolve the signatures of
+    /// the functions called by the transactions built with this client.
+    pub fn module_cache(&self) -> &Arc<NormalizedModuleCache> {
+        &self.modules
     }
 
     pub async fn get_owned_objects(
@@ -74,6 +92,27 @@ impl ReadApi {
             .await?)
     }
 
+    /// Streams all the dynamic fields of the object with their values, from the field after
+    /// `cursor`, in pages of up to `page_size` fields.
+    pub async fn export_dynamic_fields(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<ObjectID>,
+        page_size: Option<usize>,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<DynamicFieldExportPage>>> {
+        match &self.api.ws {
+            Some(c) => {
+                let subscription: Subscription<DynamicFieldExportPage> = c
+                    .export_dynamic_fields(object_id, cursor, page_size)
+                    .await?;
+                Ok(subscription.map(|item| Ok(item?)))
+            }
+            _ => Err(Error::Subscription(
+                "Subscription only supported by WebSocket client.".to_string(),
+            )),
+        }
+    }
+
     pub async fn try_get_parsed_past_object(
         &self,
         object_id: ObjectID,
@@ -87,6 +126,54 @@ impl ReadApi {
             .await?)
     }
 
+    /// The fields which differ between two versions of the object, see
+    /// [SuiObjectVersionsDiff::changes].
+    pub async fn diff_object_versions(
+        &self,
+        object_id: ObjectID,
+        from_version: SequenceNumber,
+        to_version: SequenceNumber,
+    ) -> SuiRpcResult<SuiObjectVersionsDiff> {
+        Ok(self
+            .api
+            .http
+            .diff_object_versions(object_id, from_version, to_version)
+            .await?)
+    }
+
+    /// Whether the object version is locked to a transaction in the current epoch, as seen by the
+    /// node, see `sui-tool object-lock-status` to query all the validators.
+    pub async fn get_object_lock_status(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> SuiRpcResult<SuiObjectLockStatus> {
+        Ok(self
+            .api
+            .http
+            .get_object_lock_status(object_id, version)
+            .await?)
+    }
+
+    /// The proof that the object version is part of the state at the checkpoint including the
+    /// transaction which wrote it, see [SuiObjectProof::verify].
+    pub async fn get_object_proof(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> SuiRpcResult<SuiObjectProof> {
+        Ok(self.api.http.get_object_proof(object_id, version).await?)
+    }
+
+    /// The proof that the transaction was executed with its effects and events, from the
+    /// checkpoint including it, see [SuiTransactionProof::verify].
+    pub async fn get_transaction_proof(
+        &self,
+        digest: TransactionDigest,
+    ) -> SuiRpcResult<SuiTransactionProof> {
+        Ok(self.api.http.get_transaction_proof(digest).await?)
+    }
+
     pub async fn try_multi_get_parsed_past_object(
         &self,
         past_objects: Vec<SuiGetPastObjectRequest>,
@@ -173,6 +260,15 @@ impl ReadApi {
         Ok(self.api.http.get_checkpoint(id).await?)
     }
 
+    /// Return the summary of a checkpoint with the signatures of the validators certifying it, to
+    /// verify against the committee of its epoch with [SuiCheckpointCertificate::verify]
+    pub async fn get_checkpoint_certificate(
+        &self,
+        id: CheckpointId,
+    ) -> SuiRpcResult<SuiCheckpointCertificate> {
+        Ok(self.api.http.get_checkpoint_certificate(id).await?)
+    }
+
     /// Return the sequence number of the latest checkpoint that has been executed
     pub async fn get_latest_checkpoint_sequence_number(
         &self,
@@ -242,6 +338,18 @@ impl ReadApi {
             .dry_run_transaction_block(Base64::from_bytes(&bcs::to_bytes(&tx)?))
             .await?)
     }
+
+    pub async fn estimate_gas_budget(
+        &self,
+        tx: TransactionData,
+        safety_margin: Option<GasBudgetSafetyMargin>,
+    ) -> SuiRpcResult<GasBudgetEstimate> {
+        Ok(self
+            .api
+            .http
+            .estimate_gas_budget(Base64::from_bytes(&bcs::to_bytes(&tx)?), safety_margin)
+            .await?)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -358,11 +466,13 @@ impl CoinReadApi {
 #[derive(Clone)]
 pub struct EventApi {
     api: Arc<RpcClient>,
+    layouts: Arc<EventLayoutCache>,
 }
 
 impl EventApi {
     pub(crate) fn new(api: Arc<RpcClient>) -> Self {
-        Self { api }
+        let layouts = Arc::new(EventLayoutCache::new(api.clone()));
+        Self { api, layouts }
     }
 
     pub async fn subscribe_event(
@@ -380,6 +490,39 @@ impl EventApi {
         }
     }
 
+    /// Subscribe to the changes of the objects owned by `address`, instead of polling its owned
+    /// objects.
+    pub async fn subscribe_owned_object_changes(
+        &self,
+        address: SuiAddress,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<OwnedObjectChange>>> {
+        match &self.api.ws {
+            Some(c) => {
+                let subscription: Subscription<OwnedObjectChange> =
+                    c.subscribe_owned_object_changes(address).await?;
+                Ok(subscription.map(|item| Ok(item?)))
+            }
+            _ => Err(Error::Subscription(
+                "Subscription only supported by WebSocket client.".to_string(),
+            )),
+        }
+    }
+
+    /// Subscribe to the events emitted by a package, with their contents decoded client side from
+    /// the BCS bytes. Use `T = serde_json::Value` to get the json representation of the events.
+    pub async fn subscribe_typed_events<T: DeserializeOwned + Send + 'static>(
+        &self,
+        package: ObjectID,
+    ) -> SuiRpcResult<TypedEventStream<T>> {
+        let events = self.subscribe_event(EventFilter::Package(package)).await?;
+        Ok(TypedEventStream::new(events, package, self.layouts.clone()))
+    }
+
+    /// Cache of the Move layouts used to decode the events of [EventApi::subscribe_typed_events].
+    pub fn layout_cache(&self) -> &Arc<EventLayoutCache> {
+        &self.layouts
+    }
+
     pub async fn get_events(&self, digest: TransactionDigest) -> SuiRpcResult<Vec<SuiEvent>> {
         Ok(self.api.http.get_events(digest).await?)
     }
@@ -429,11 +572,27 @@ impl EventApi {
 #[derive(Clone)]
 pub struct QuorumDriver {
     api: Arc<RpcClient>,
+    guard: Option<Arc<EquivocationGuard>>,
+    modules: Arc<NormalizedModuleCache>,
 }
 
 impl QuorumDriver {
-    pub(crate) fn new(api: Arc<RpcClient>) -> Self {
-        Self { api }
+    pub(crate) fn new(
+        api: Arc<RpcClient>,
+        guard: Option<Arc<EquivocationGuard>>,
+        modules: Arc<NormalizedModuleCache>,
+    ) -> Self {
+        Self {
+            api,
+            guard,
+            modules,
+        }
+    }
+
+    /// The guard tracking the owned objects of the transactions being executed, if enabled with
+    /// [crate::SuiClientBuilder::equivocation_guard].
+    pub fn equivocation_guard(&self) -> Option<&EquivocationGuard> {
+        self.guard.as_deref()
     }
 
     /// Execute a transaction with a FullNode client. `request_type`
@@ -443,24 +602,106 @@ impl QuorumDriver {
     /// the fullnode until the fullnode recognizes this transaction, or
     /// until times out (see WAIT_FOR_TX_TIMEOUT_SEC). If it times out, an
     /// error is returned from this call.
+    /// Transient failures are retried according to the client [RetryPolicy]:
+    /// before re-submitting, the client checks whether the transaction was
+    /// executed by the failed attempt and returns it if so.
+    /// With an [EquivocationGuard], transactions using the owned objects of
+    /// a transaction which is not finalized yet are rejected or queued.
     pub async fn execute_transaction_block(
         &self,
         tx: VerifiedTransaction,
         options: SuiTransactionBlockResponseOptions,
         request_type: Option<ExecuteTransactionRequestType>,
+    ) -> SuiRpcResult<SuiTransactionBlockResponse> {
+        let reservation = match &self.guard {
+            Some(guard) => Some(
+                guard
+                    .reserve(*tx.digest(), tx.data().transaction_data())
+                    .await?,
+            ),
+            None => None,
+        };
+        let result = self
+            .execute_transaction_block_unguarded(tx, options, request_type)
+            .await;
+        match (&result, reservation) {
+            // The transaction may still be executed, its objects must not be reused.
+            (Err(Error::RpcError(e)), Some(reservation)) if RetryPolicy::is_retryable(e) => {
+                reservation.keep()
+            }
+            // Otherwise the transaction is finalized, or was rejected.
+            _ => {}
+        }
+        if let Ok(SuiTransactionBlockResponse {
+            object_changes: Some(changes),
+            ..
+        }) = &result
+        {
+            self.modules.apply_object_changes(changes);
+        }
+        result
+    }
+
+    /// Subscribe to the status of a transaction block on the fullnode, until it is finalized.
+    pub async fn subscribe_transaction_status(
+        &self,
+        digest: TransactionDigest,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiTransactionBlockStatus>>> {
+        match &self.api.ws {
+            Some(c) => {
+                let subscription: Subscription<SuiTransactionBlockStatus> =
+                    c.subscribe_transaction_status(digest).await?;
+                Ok(subscription.map(|item| Ok(item?)))
+            }
+            _ => Err(Error::Subscription(
+                "Subscription only supported by WebSocket client.".to_string(),
+            )),
+        }
+    }
+
+    async fn execute_transaction_block_unguarded(
+        &self,
+        tx: VerifiedTransaction,
+        options: SuiTransactionBlockResponseOptions,
+        request_type: Option<ExecuteTransactionRequestType>,
     ) -> SuiRpcResult<SuiTransactionBlockResponse> {
         let (tx_bytes, signatures) = tx.to_tx_bytes_and_signatures();
         let request_type = request_type.unwrap_or_else(|| options.default_execution_request_type());
-        let mut response: SuiTransactionBlockResponse = self
+        let backoff = self
             .api
             .http
-            .execute_transaction_block(
-                tx_bytes,
-                signatures,
-                Some(options),
-                Some(request_type.clone()),
-            )
-            .await?;
+            .policy()
+            .backoff(EXECUTE_TRANSACTION_BLOCK_METHOD);
+        let mut attempt = 1;
+        let mut response: SuiTransactionBlockResponse = loop {
+            let result = self
+                .api
+                .http
+                .execute_transaction_block(
+                    tx_bytes.clone(),
+                    signatures.clone(),
+                    Some(options.clone()),
+                    Some(request_type.clone()),
+                )
+                .await;
+            match result {
+                Err(e) if attempt < backoff.max_attempts && RetryPolicy::is_retryable(&e) => {
+                    tokio::time::sleep(backoff.delay(attempt)).await;
+                    attempt += 1;
+                    // The failed attempt may still have been executed, in which case the
+                    // transaction must not be submitted again.
+                    if let Ok(response) = self
+                        .api
+                        .http
+                        .get_transaction_block(*tx.digest(), Some(options.clone()))
+                        .await
+                    {
+                        break response;
+                    }
+                }
+                result => break result?,
+            }
+        };
 
         Ok(match request_type {
             ExecuteTransactionRequestType::WaitForEffectsCert => response,
@@ -551,4 +792,26 @@ impl GovernanceApi {
     pub async fn get_reference_gas_price(&self) -> SuiRpcResult<u64> {
         Ok(*self.api.http.get_reference_gas_price().await?)
     }
+
+    /// Return the tallying rule reports of the current epoch, and whether the staking rewards of
+    /// the reported validators will be slashed at the end of the epoch.
+    pub async fn get_validator_reports(&self) -> SuiRpcResult<Vec<ValidatorReport>> {
+        Ok(self.api.http.get_validator_reports().await?)
+    }
+
+    /// Return the outcomes of the tallying rule at the end of past epochs, of all the validators
+    /// or only of `validator`.
+    pub async fn get_validator_epoch_outcomes(
+        &self,
+        validator: Option<SuiAddress>,
+        cursor: Option<EventID>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> SuiRpcResult<ValidatorEpochOutcomePage> {
+        Ok(self
+            .api
+            .http
+            .get_validator_epoch_outcomes(validator, cursor, limit, Some(descending_order))
+            .await?)
+    }
 }
@@ -0,0 +1,389 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Consolidation of the coins owned by an address. Every payment received creates a new coin
+//! object, and addresses receiving many payments end up owning many small coins ("dust"), which
+//! must all be passed as inputs to spend the balance. The [CoinManager] monitors the number of
+//! coins of each coin type owned by an address and, when it exceeds a threshold, merges them with
+//! programmable transactions, checking each transaction with a dry run before submitting it.
+
+use std::time::Duration;
+
+use shared_crypto::intent::Intent;
+use sui_json_rpc_types::{
+    Coin, DryRunTransactionBlockResponse, SuiExecutionStatus, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockResponseOptions,
+};
+use sui_keys::keystore::AccountKeystore;
+use sui_types::base_types::{ObjectRef, SuiAddress, TransactionDigest};
+use sui_types::gas_coin::GAS;
+use sui_types::messages::{
+    Argument, Command, ExecuteTransactionRequestType, ObjectArg, Transaction, TransactionData,
+};
+use sui_types::object::Owner;
+use sui_types::parse_sui_type_tag;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+use crate::error::{Error, SuiRpcResult};
+use crate::SuiClient;
+
+/// Number of consolidation reports buffered by the background task, later reports are dropped
+/// until the buffered ones are received.
+const REPORT_CHANNEL_SIZE: usize = 100;
+
+#[derive(Clone, Debug)]
+pub struct CoinManagerConfig {
+    /// Coin types owned in more coins than this are consolidated.
+    pub coin_count_threshold: usize,
+    /// Maximum number of coins merged by one transaction, including the coin they are merged
+    /// into. For SUI, the coins are merged as gas payment, so this is also bounded by the
+    /// `max_gas_payment_objects` limit of the protocol.
+    pub max_inputs_per_transaction: usize,
+    /// Maximum number of transactions submitted by one consolidation run. Coins that are left
+    /// are consolidated by the next runs.
+    pub max_transactions_per_run: usize,
+    pub gas_budget: u64,
+    /// Coin types to consolidate, e.g. `0x2::sui::SUI`, or all the coin types owned by the
+    /// address if None.
+    pub coin_types: Option<Vec<String>>,
+    /// Interval between the consolidation runs of the background task.
+    pub interval: Duration,
+}
+
+impl Default for CoinManagerConfig {
+    fn default() -> Self {
+        Self {
+            coin_count_threshold: 256,
+            max_inputs_per_transaction: 256,
+            max_transactions_per_run: 10,
+            gas_budget: 50_000_000,
+            coin_types: None,
+            interval: Duration::from_secs(60),
+        }
+    }
+}
+
+/// The consolidation transactions of one coin type.
+#[derive(Clone, Debug)]
+pub struct ConsolidationReport {
+    pub coin_type: String,
+    pub coin_count_before: usize,
+    pub coin_count_after: usize,
+    pub transactions: Vec<TransactionDigest>,
+}
+
+/// Consolidates the coins owned by an address, whose keys are in the keystore.
+pub struct CoinManager<K> {
+    client: SuiClient,
+    address: SuiAddress,
+    keystore: K,
+    config: CoinManagerConfig,
+}
+
+impl<K: AccountKeystore + 'static> CoinManager<K> {
+    pub fn new(
+        client: SuiClient,
+        address: SuiAddress,
+        keystore: K,
+        config: CoinManagerConfig,
+    ) -> SuiRpcResult<Self> {
+        if config.max_inputs_per_transaction < 2 {
+            return Err(Error::DataError(
+                "Coin consolidation needs at least 2 inputs per transaction".into(),
+            ));
+        }
+        if !keystore.addresses().contains(&address) {
+            return Err(Error::DataError(format!(
+                "Address {address} is not in the keystore"
+            )));
+        }
+        Ok(Self {
+            client,
+            address,
+            keystore,
+            config,
+        })
+    }
+
+    pub fn address(&self) -> SuiAddress {
+        self.address
+    }
+
+    /// The coin types owned in more coins than the threshold, with their number of coins.
+    pub async fn fragmented_coin_types(&self) -> SuiRpcResult<Vec<(String, usize)>> {
+        let balances = self
+            .client
+            .coin_read_api()
+            .get_all_balances(self.address)
+            .await?;
+        Ok(balances
+            .into_iter()
+            .filter(|balance| {
+                self.config
+                    .coin_types
+                    .as_ref()
+                    .map_or(true, |coin_types| coin_types.contains(&balance.coin_type))
+            })
+            .filter(|balance| balance.coin_object_count > self.config.coin_count_threshold)
+            .map(|balance| (balance.coin_type, balance.coin_object_count))
+            .collect())
+    }
+
+    /// Consolidates the coins of all the fragmented coin types, submitting at most
+    /// `max_transactions_per_run` transactions.
+    pub async fn consolidate(&self) -> SuiRpcResult<Vec<ConsolidationReport>> {
+        let mut reports = vec![];
+        let mut remaining = self.config.max_transactions_per_run;
+        for (coin_type, _) in self.fragmented_coin_types().await? {
+            if remaining == 0 {
+                break;
+            }
+            let report = self.consolidate_coin_type(&coin_type, remaining).await?;
+            remaining -= report.transactions.len();
+            reports.push(report);
+        }
+        Ok(reports)
+    }
+
+    /// Merges the coins of `coin_type`, smallest first, into the largest coin, until a single
+    /// coin is left or `max_transactions` transactions were submitted.
+    pub async fn consolidate_coin_type(
+        &self,
+        coin_type: &str,
+        max_transactions: usize,
+    ) -> SuiRpcResult<ConsolidationReport> {
+        let is_sui = parse_sui_type_tag(coin_type)
+            .map_err(|e| Error::DataError(format!("Invalid coin type {coin_type}: {e}")))?
+            == GAS::type_tag();
+        let mut coins = self.get_mergeable_coins(coin_type).await?;
+        let coin_count_before = coins.len();
+        let mut transactions = vec![];
+
+        while coins.len() > 1 && transactions.len() < max_transactions {
+            // The largest coin is kept, and the smallest ones are merged into it.
+            let batch_size = coins.len().min(self.config.max_inputs_per_transaction);
+            let mut batch = vec![coins[0].object_ref()];
+            batch.extend(
+                coins[coins.len() - batch_size + 1..]
+                    .iter()
+                    .map(|coin| coin.object_ref()),
+            );
+            let tx = if is_sui {
+                self.merge_sui_transaction(batch).await?
+            } else {
+                self.merge_coins_transaction(batch).await?
+            };
+            let digest = self.dry_run_and_execute(tx).await?;
+            transactions.push(digest);
+            coins = self.get_mergeable_coins(coin_type).await?;
+        }
+
+        Ok(ConsolidationReport {
+            coin_type: coin_type.to_string(),
+            coin_count_before,
+            coin_count_after: coins.len(),
+            transactions,
+        })
+    }
+
+    /// Runs [CoinManager::consolidate] every `interval` in a background task, until the handle is
+    /// shut down or dropped.
+    pub fn spawn(self) -> CoinManagerHandle {
+        let (shutdown_sender, mut shutdown) = oneshot::channel();
+        let (report_sender, reports) = mpsc::channel(REPORT_CHANNEL_SIZE);
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.interval);
+            loop {
+                tokio::select! {
+                    _ = &mut shutdown => break,
+                    _ = interval.tick() => {
+                        let result = self.consolidate().await;
+                        // Reports are dropped when nobody receives them.
+                        let _ = report_sender.try_send(result);
+                    }
+                }
+            }
+        });
+        CoinManagerHandle {
+            shutdown: Some(shutdown_sender),
+            reports,
+            task,
+        }
+    }
+
+    /// The coins of `coin_type` which can be merged, i.e. which are not locked, largest first.
+    async fn get_mergeable_coins(&self, coin_type: &str) -> SuiRpcResult<Vec<Coin>> {
+        let mut coins = vec![];
+        let mut cursor = None;
+        loop {
+            let page = self
+                .client
+                .coin_read_api()
+                .get_coins(self.address, Some(coin_type.to_string()), cursor, None)
+                .await?;
+            coins.extend(
+                page.data
+                    .into_iter()
+                    .filter(|coin| coin.locked_until_epoch.is_none()),
+            );
+            if !page.has_next_page {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+        coins.sort_by(|a, b| b.balance.cmp(&a.balance));
+        Ok(coins)
+    }
+
+    /// SUI coins are merged by paying the gas with all of them, which merges them into the first
+    /// one, and transferring the merged gas coin back to the address.
+    async fn merge_sui_transaction(&self, coins: Vec<ObjectRef>) -> SuiRpcResult<TransactionData> {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.pay_all_sui(self.address);
+        let gas_price = self.client.read_api().get_reference_gas_price().await?;
+        Ok(TransactionData::new_programmable(
+            self.address,
+            coins,
+            builder.finish(),
+            self.config.gas_budget,
+            gas_price,
+        ))
+    }
+
+    /// Other coins are merged with a `MergeCoins` command, and the gas is paid with the largest
+    /// SUI coin of the address.
+    async fn merge_coins_transaction(
+        &self,
+        coins: Vec<ObjectRef>,
+    ) -> SuiRpcResult<TransactionData> {
+        let gas = self
+            .get_mergeable_coins(&GAS::type_tag().to_string())
+            .await?
+            .into_iter()
+            .next()
+            .filter(|coin| coin.balance >= self.config.gas_budget)
+            .ok_or_else(|| Error::InsufficientFund {
+                address: self.address,
+                amount: self.config.gas_budget as u128,
+            })?;
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let mut arguments = coins
+            .into_iter()
+            .map(|coin| builder.obj(ObjectArg::ImmOrOwnedObject(coin)))
+            .collect::<Result<Vec<Argument>, _>>()
+            .map_err(|e| Error::DataError(e.to_string()))?;
+        let target = arguments.remove(0);
+        builder.command(Command::MergeCoins(target, arguments));
+        let gas_price = self.client.read_api().get_reference_gas_price().await?;
+        Ok(TransactionData::new_programmable(
+            self.address,
+            vec![gas.object_ref()],
+            builder.finish(),
+            self.config.gas_budget,
+            gas_price,
+        ))
+    }
+
+    async fn dry_run_and_execute(&self, tx: TransactionData) -> SuiRpcResult<TransactionDigest> {
+        let dry_run = self
+            .client
+            .read_api()
+            .dry_run_transaction_block(tx.clone())
+            .await?;
+        check_consolidation_dry_run(self.address, self.config.gas_budget, &dry_run)?;
+
+        let signature = self
+            .keystore
+            .sign_secure(&self.address, &tx, Intent::sui_transaction())
+            .map_err(|e| Error::DataError(format!("Cannot sign the transaction: {e}")))?;
+        let response = self
+            .client
+            .quorum_driver()
+            .execute_transaction_block(
+                Transaction::from_data(tx, Intent::sui_transaction(), vec![signature])
+                    .verify()
+                    .map_err(|e| Error::DataError(format!("Invalid signature: {e}")))?,
+                SuiTransactionBlockResponseOptions::new().with_effects(),
+                Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+            )
+            .await?;
+        if let Some(effects) = &response.effects {
+            if let Some(error) = execution_error(effects.status()) {
+                return Err(Error::ConsolidationFailed(format!(
+                    "Transaction {} failed: {error}",
+                    response.digest
+                )));
+            }
+        }
+        Ok(response.digest)
+    }
+}
+
+/// A consolidation transaction must succeed, and must not change any balance but the SUI balance
+/// of the address, which pays the gas and may receive storage rebates of the merged coins.
+fn check_consolidation_dry_run(
+    address: SuiAddress,
+    gas_budget: u64,
+    dry_run: &DryRunTransactionBlockResponse,
+) -> SuiRpcResult<()> {
+    if let Some(error) = execution_error(dry_run.effects.status()) {
+        return Err(Error::ConsolidationFailed(format!(
+            "Dry run failed: {error}"
+        )));
+    }
+    for change in &dry_run.balance_changes {
+        let allowed = change.owner == Owner::AddressOwner(address)
+            && if change.coin_type == GAS::type_tag() {
+                change.amount >= -(gas_budget as i128)
+            } else {
+                change.amount == 0
+            };
+        if !allowed {
+            return Err(Error::ConsolidationFailed(format!(
+                "Dry run changes the {} balance of {} by {}",
+                change.coin_type, change.owner, change.amount
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn execution_error(status: &SuiExecutionStatus) -> Option<&str> {
+    match status {
+        SuiExecutionStatus::Success => None,
+        SuiExecutionStatus::Failure { error } => Some(error),
+    }
+}
+
+/// Handle of the background task of a [CoinManager], which stops it when dropped.
+pub struct CoinManagerHandle {
+    shutdown: Option<oneshot::Sender<()>>,
+    reports: mpsc::Receiver<SuiRpcResult<Vec<ConsolidationReport>>>,
+    task: JoinHandle<()>,
+}
+
+impl CoinManagerHandle {
+    /// The result of the next consolidation run, or None if the task stopped.
+    pub async fn next_report(&mut self) -> Option<SuiRpcResult<Vec<ConsolidationReport>>> {
+        self.reports.recv().await
+    }
+
+    /// Stops the background task, after the current consolidation run if any.
+    pub async fn shutdown(mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        let _ = (&mut self.task).await;
+    }
+}
+
+impl Drop for CoinManagerHandle {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
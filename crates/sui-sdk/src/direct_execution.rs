@@ -0,0 +1,331 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Execution of transactions by the validators directly, without the fullnode hop. The
+//! [DirectExecutor] fetches the committee from the fullnode, submits the transaction to the
+//! validators, forms the certificate from the signatures of a quorum of them, submits the
+//! certificate to the validators, and forms the effects certificate from their signed effects.
+//!
+//! If the validators cannot be reached or fail to execute the transaction, e.g. across an epoch
+//! change, the transaction is executed by the fullnode instead. Submitting the same transaction
+//! again is not an equivocation, the validators which already signed it sign it again.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use futures::stream::FuturesUnordered;
+use futures::StreamExt;
+
+use sui_core::authority_client::{
+    make_authority_clients_with_timeout_config, AuthorityAPI, NetworkAuthorityClient,
+};
+use sui_json_rpc_types::{SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions};
+use sui_types::base_types::AuthorityName;
+use sui_types::committee::{Committee, StakeUnit};
+use sui_types::crypto::AuthoritySignInfo;
+use sui_types::digests::TransactionEffectsDigest;
+use sui_types::messages::{
+    CertifiedTransaction, CertifiedTransactionEffects, SignedTransaction, TransactionEvents,
+    TransactionStatus, VerifiedCertificate, VerifiedCertifiedTransactionEffects,
+    VerifiedTransaction,
+};
+
+use crate::error::{Error, SuiRpcResult};
+use crate::SuiClient;
+
+#[derive(Clone, Debug)]
+pub struct DirectExecutionConfig {
+    /// Timeout of the connections to the validators.
+    pub connect_timeout: Duration,
+    /// Timeout of each request to a validator.
+    pub request_timeout: Duration,
+    /// Whether transactions are executed by the fullnode when the direct execution fails.
+    pub fallback_to_fullnode: bool,
+}
+
+impl Default for DirectExecutionConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(10),
+            request_timeout: Duration::from_secs(10),
+            fallback_to_fullnode: true,
+        }
+    }
+}
+
+/// How a transaction was executed by [DirectExecutor::execute_transaction_block].
+#[derive(Debug)]
+pub enum DirectExecutionResponse {
+    /// Executed by the validators, with the effects certified by a quorum of the committee.
+    Validators {
+        certificate: VerifiedCertificate,
+        effects: VerifiedCertifiedTransactionEffects,
+        events: TransactionEvents,
+    },
+    /// Executed by the fullnode, after the direct execution failed with `error`.
+    Fullnode {
+        response: SuiTransactionBlockResponse,
+        error: String,
+    },
+}
+
+struct ValidatorCommittee {
+    committee: Committee,
+    clients: BTreeMap<AuthorityName, NetworkAuthorityClient>,
+}
+
+pub struct DirectExecutor {
+    client: SuiClient,
+    config: DirectExecutionConfig,
+    committee: RwLock<Arc<ValidatorCommittee>>,
+}
+
+impl DirectExecutor {
+    /// Creates an executor for the current committee, fetched from the fullnode of `client`.
+    pub async fn new(client: SuiClient, config: DirectExecutionConfig) -> SuiRpcResult<Self> {
+        let committee = Self::fetch_committee(&client, &config).await?;
+        Ok(Self {
+            client,
+            config,
+            committee: RwLock::new(Arc::new(committee)),
+        })
+    }
+
+    /// The epoch of the committee the transactions are submitted to.
+    pub fn epoch(&self) -> u64 {
+        self.committee.read().unwrap().committee.epoch
+    }
+
+    /// Fetches the committee again, e.g. after an epoch change.
+    pub async fn refresh_committee(&self) -> SuiRpcResult<()> {
+        let committee = Self::fetch_committee(&self.client, &self.config).await?;
+        *self.committee.write().unwrap() = Arc::new(committee);
+        Ok(())
+    }
+
+    async fn fetch_committee(
+        client: &SuiClient,
+        config: &DirectExecutionConfig,
+    ) -> SuiRpcResult<ValidatorCommittee> {
+        let committee = client
+            .governance_api()
+            .get_latest_sui_system_state()
+            .await?
+            .get_sui_committee_for_benchmarking();
+        let clients = make_authority_clients_with_timeout_config(
+            &committee,
+            config.connect_timeout,
+            config.request_timeout,
+        )
+        .map_err(|e| Error::DataError(format!("Cannot connect to the validators: {e}")))?;
+        Ok(ValidatorCommittee {
+            committee: committee.committee,
+            clients,
+        })
+    }
+
+    /// Executes the transaction by the validators, or by the fullnode if the direct execution
+    /// fails and `fallback_to_fullnode` is set. The committee is fetched again after a failure.
+    pub async fn execute_transaction_block(
+        &self,
+        tx: VerifiedTransaction,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> SuiRpcResult<DirectExecutionResponse> {
+        let error = match self.execute_by_validators(&tx).await {
+            Ok((certificate, effects, events)) => {
+                return Ok(DirectExecutionResponse::Validators {
+                    certificate,
+                    effects,
+                    events,
+                })
+            }
+            Err(e) => e,
+        };
+        let result = if self.config.fallback_to_fullnode {
+            self.client
+                .quorum_driver()
+                .execute_transaction_block(tx, options, None)
+                .await
+                .map(|response| DirectExecutionResponse::Fullnode {
+                    response,
+                    error: error.to_string(),
+                })
+        } else {
+            Err(error)
+        };
+        // The failure may be caused by an epoch change. If the committee cannot be fetched, the
+        // next execution falls back to the fullnode again.
+        self.refresh_committee().await.ok();
+        result
+    }
+
+    /// Executes the transaction by the validators: forms the certificate of the transaction,
+    /// then the certificate of its effects.
+    pub async fn execute_by_validators(
+        &self,
+        tx: &VerifiedTransaction,
+    ) -> SuiRpcResult<(
+        VerifiedCertificate,
+        VerifiedCertifiedTransactionEffects,
+        TransactionEvents,
+    )> {
+        let committee = self.committee.read().unwrap().clone();
+        let certificate = Self::certify_transaction(&committee, tx).await?;
+        let (effects, events) = Self::certify_effects(&committee, &certificate).await?;
+        Ok((certificate, effects, events))
+    }
+
+    async fn certify_transaction(
+        committee: &ValidatorCommittee,
+        tx: &VerifiedTransaction,
+    ) -> SuiRpcResult<VerifiedCertificate> {
+        let mut responses: FuturesUnordered<_> = committee
+            .clients
+            .iter()
+            .map(|(name, client)| {
+                let tx = tx.clone().into_inner();
+                async move { (*name, client.handle_transaction(tx).await) }
+            })
+            .collect();
+
+        let mut signatures = vec![];
+        let mut signed_stake: StakeUnit = 0;
+        let mut errors = Errors::default();
+        while let Some((name, response)) = responses.next().await {
+            let weight = committee.committee.weight(&name);
+            let signature = match response.map(|response| response.status) {
+                Ok(TransactionStatus::Signed(signature)) if signature.authority == name => {
+                    signature
+                }
+                Ok(TransactionStatus::Signed(_)) => {
+                    errors.add(name, weight, "signed by another validator".to_string());
+                    if errors.stake >= committee.committee.validity_threshold() {
+                        break;
+                    }
+                    continue;
+                }
+                // The transaction was already certified in this epoch.
+                Ok(TransactionStatus::Executed(Some(signature), _, _))
+                    if signature.epoch == committee.committee.epoch =>
+                {
+                    let certificate =
+                        CertifiedTransaction::new_from_data_and_sig(tx.data().clone(), signature);
+                    match certificate.verify(&committee.committee) {
+                        Ok(certificate) => return Ok(certificate),
+                        Err(e) => {
+                            errors.add(name, weight, e.to_string());
+                            continue;
+                        }
+                    }
+                }
+                Ok(TransactionStatus::Executed(..)) => {
+                    errors.add(name, weight, "executed in a previous epoch".to_string());
+                    continue;
+                }
+                Err(e) => {
+                    errors.add(name, weight, e.to_string());
+                    if errors.stake >= committee.committee.validity_threshold() {
+                        break;
+                    }
+                    continue;
+                }
+            };
+            let signed = SignedTransaction::new_from_data_and_sig(tx.data().clone(), signature);
+            if let Err(e) = signed.verify_signature(&committee.committee) {
+                errors.add(name, weight, e.to_string());
+                continue;
+            }
+            signatures.push(signed.into_data_and_sig().1);
+            signed_stake += weight;
+            if signed_stake >= committee.committee.quorum_threshold() {
+                let certificate =
+                    CertifiedTransaction::new(tx.data().clone(), signatures, &committee.committee)
+                        .and_then(|certificate| certificate.verify(&committee.committee))
+                        .map_err(|e| Error::DataError(format!("Invalid certificate: {e}")))?;
+                return Ok(certificate);
+            }
+        }
+        Err(errors.into_error("Cannot certify the transaction"))
+    }
+
+    async fn certify_effects(
+        committee: &ValidatorCommittee,
+        certificate: &VerifiedCertificate,
+    ) -> SuiRpcResult<(VerifiedCertifiedTransactionEffects, TransactionEvents)> {
+        let mut responses: FuturesUnordered<_> = committee
+            .clients
+            .iter()
+            .map(|(name, client)| {
+                let certificate = certificate.clone().into_inner();
+                async move { (*name, client.handle_certificate(certificate).await) }
+            })
+            .collect();
+
+        // Signatures of the effects, by digest of the effects.
+        let mut effects: BTreeMap<TransactionEffectsDigest, (Vec<AuthoritySignInfo>, StakeUnit)> =
+            BTreeMap::new();
+        let mut errors = Errors::default();
+        while let Some((name, response)) = responses.next().await {
+            let weight = committee.committee.weight(&name);
+            let response = match response {
+                Ok(response) => response,
+                Err(e) => {
+                    errors.add(name, weight, e.to_string());
+                    if errors.stake >= committee.committee.validity_threshold() {
+                        break;
+                    }
+                    continue;
+                }
+            };
+            let signed_effects = response.signed_effects;
+            if signed_effects.auth_sig().authority != name {
+                errors.add(name, weight, "signed by another validator".to_string());
+                continue;
+            }
+            if let Err(e) = signed_effects.verify_signature(&committee.committee) {
+                errors.add(name, weight, e.to_string());
+                continue;
+            }
+            let digest = *signed_effects.digest();
+            let (data, signature) = signed_effects.into_data_and_sig();
+            let (signatures, stake) = effects.entry(digest).or_default();
+            signatures.push(signature);
+            *stake += weight;
+            if *stake >= committee.committee.quorum_threshold() {
+                let signatures = std::mem::take(signatures);
+                let effects =
+                    CertifiedTransactionEffects::new(data, signatures, &committee.committee)
+                        .and_then(|effects| effects.verify(&committee.committee))
+                        .map_err(|e| {
+                            Error::DataError(format!("Invalid effects certificate: {e}"))
+                        })?;
+                return Ok((effects, response.events));
+            }
+        }
+        Err(errors.into_error("Cannot certify the effects"))
+    }
+}
+
+/// Errors of the validators, with their total stake.
+#[derive(Default)]
+struct Errors {
+    errors: Vec<(AuthorityName, String)>,
+    stake: StakeUnit,
+}
+
+impl Errors {
+    fn add(&mut self, name: AuthorityName, weight: StakeUnit, error: String) {
+        self.errors.push((name, error));
+        self.stake += weight;
+    }
+
+    fn into_error(self, context: &str) -> Error {
+        let errors: Vec<_> = self
+            .errors
+            .into_iter()
+            .map(|(name, error)| format!("{}: {error}", name.concise()))
+            .collect();
+        Error::DataError(format!("{context}: [{}]", errors.join(", ")))
+    }
+}
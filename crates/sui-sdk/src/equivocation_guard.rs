@@ -0,0 +1,153 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Client-side protection against equivocation. Validators lock the owned objects of a
+//! transaction when signing it, and refuse to sign another transaction using the same object
+//! versions until the end of the epoch. Submitting a second transaction reusing an owned object
+//! before the first one is finalized may split the validators between the two transactions, in
+//! which case neither can reach a quorum and the objects are unusable until the end of the epoch.
+//!
+//! The [EquivocationGuard] of a [crate::SuiClient] tracks the owned objects of the transactions
+//! being executed by [crate::apis::QuorumDriver], and rejects or queues the transactions reusing
+//! them. Immutable objects cannot be told apart from owned objects in a transaction, so
+//! transactions sharing an immutable object are also serialized.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::Notify;
+
+use sui_types::base_types::{ObjectID, TransactionDigest};
+use sui_types::messages::{InputObjectKind, TransactionData, TransactionDataAPI};
+
+use crate::error::{Error, SuiRpcResult};
+
+/// What to do with a transaction using an object of a transaction which is not finalized yet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GuardMode {
+    /// Fail with [Error::ObjectInFlight].
+    Reject,
+    /// Wait until the objects are released.
+    Queue,
+}
+
+#[derive(Debug)]
+pub struct EquivocationGuard {
+    mode: GuardMode,
+    in_flight: Mutex<HashMap<ObjectID, TransactionDigest>>,
+    released: Notify,
+}
+
+impl EquivocationGuard {
+    pub fn new(mode: GuardMode) -> Self {
+        Self {
+            mode,
+            in_flight: Mutex::new(HashMap::new()),
+            released: Notify::new(),
+        }
+    }
+
+    pub fn mode(&self) -> GuardMode {
+        self.mode
+    }
+
+    /// The owned objects of the transactions which are not finalized, with the transaction using
+    /// them.
+    pub fn in_flight(&self) -> BTreeMap<ObjectID, TransactionDigest> {
+        self.in_flight
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(id, digest)| (*id, *digest))
+            .collect()
+    }
+
+    /// Releases the objects of a transaction. The objects of transactions whose outcome is
+    /// unknown, e.g. after a network error, stay reserved until the transaction is re-submitted
+    /// successfully, or until they are released once the transaction is known to have failed or
+    /// to be expired at the end of the epoch.
+    pub fn release(&self, digest: &TransactionDigest) {
+        self.in_flight
+            .lock()
+            .unwrap()
+            .retain(|_, in_flight| in_flight != digest);
+        self.released.notify_waiters();
+    }
+
+    /// Reserves the owned objects of a transaction, for transactions submitted without the
+    /// [crate::apis::QuorumDriver]. Re-submitting the same transaction is not an equivocation,
+    /// its objects can be reserved again.
+    pub async fn reserve(
+        self: &Arc<Self>,
+        digest: TransactionDigest,
+        data: &TransactionData,
+    ) -> SuiRpcResult<Reservation> {
+        let objects = owned_input_objects(data)?;
+        loop {
+            // Registered before checking the objects, so that releases are not missed.
+            let released = self.released.notified();
+            {
+                let mut in_flight = self.in_flight.lock().unwrap();
+                let conflict = objects.iter().find_map(|id| {
+                    in_flight
+                        .get(id)
+                        .filter(|in_flight| **in_flight != digest)
+                        .map(|in_flight| (*id, *in_flight))
+                });
+                match conflict {
+                    None => {
+                        for id in &objects {
+                            in_flight.insert(*id, digest);
+                        }
+                        return Ok(Reservation {
+                            guard: self.clone(),
+                            digest,
+                            keep: false,
+                        });
+                    }
+                    Some((object_id, in_flight)) if self.mode == GuardMode::Reject => {
+                        return Err(Error::ObjectInFlight {
+                            object_id,
+                            digest: in_flight,
+                        })
+                    }
+                    Some(_) => {}
+                }
+            }
+            released.await;
+        }
+    }
+}
+
+/// Objects reserved for a transaction, released when dropped unless kept.
+pub struct Reservation {
+    guard: Arc<EquivocationGuard>,
+    digest: TransactionDigest,
+    keep: bool,
+}
+
+impl Reservation {
+    /// Keeps the objects reserved after the execution, when its outcome is unknown.
+    pub fn keep(mut self) {
+        self.keep = true;
+    }
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        if !self.keep {
+            self.guard.release(&self.digest);
+        }
+    }
+}
+
+fn owned_input_objects(data: &TransactionData) -> SuiRpcResult<Vec<ObjectID>> {
+    Ok(data
+        .input_objects()?
+        .into_iter()
+        .filter_map(|input| match input {
+            InputObjectKind::ImmOrOwnedMoveObject((id, _, _)) => Some(id),
+            InputObjectKind::MovePackage(_) | InputObjectKind::SharedMoveObject { .. } => None,
+        })
+        .collect())
+}
@@ -1,7 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use sui_types::base_types::{SuiAddress, TransactionDigest};
+use jsonrpsee::types::error::CallError;
+pub use sui_json_rpc_types::SuiRpcErrorCode;
+use sui_json_rpc_types::SuiRpcErrorData;
+use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
 use sui_types::error::UserInputError;
 use thiserror::Error;
 
@@ -30,4 +33,32 @@ pub enum Error {
     },
     #[error("Insufficient fund for address [{address}], requested amount: {amount}")]
     InsufficientFund { address: SuiAddress, amount: u128 },
+    #[error("Object {object_id} is used by transaction {digest}, which is not finalized yet")]
+    ObjectInFlight {
+        object_id: ObjectID,
+        digest: TransactionDigest,
+    },
+    #[error("Coin consolidation failed: {0}")]
+    ConsolidationFailed(String),
+}
+
+impl Error {
+    /// The stable error code reported by the node in the `data` field of the JSON-RPC error.
+    /// Prefer this over matching error messages when deciding how to handle a failure.
+    pub fn error_code(&self) -> Option<SuiRpcErrorCode> {
+        match self {
+            Error::RpcError(e) | Error::TransactionConfirmationError(_, e) => rpc_error_code(e),
+            _ => None,
+        }
+    }
+}
+
+pub(crate) fn rpc_error_code(error: &jsonrpsee::core::Error) -> Option<SuiRpcErrorCode> {
+    match error {
+        jsonrpsee::core::Error::Call(CallError::Custom(error)) => error
+            .data()
+            .and_then(|data| serde_json::from_str::<SuiRpcErrorData>(data.get()).ok())
+            .map(|data| data.code),
+        _ => None,
+    }
 }
@@ -0,0 +1,369 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Gas coins for concurrent transactions of one sender. Transactions paying gas with the same
+//! coin cannot be executed concurrently: the coin is locked by the first transaction signed by
+//! validators, and its version changes when the transaction is executed. The [GasPool] splits a
+//! funding coin into gas coins, leases each coin to one transaction at a time, tracks the new
+//! versions of the coins from the effects of the transactions, and merges the coins back into
+//! the funding coin when the pool is idle.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use shared_crypto::intent::Intent;
+use tokio::sync::Notify;
+
+use sui_json_rpc_types::{
+    SuiExecutionStatus, SuiObjectDataOptions, SuiTransactionBlockEffects,
+    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+};
+use sui_keys::keystore::AccountKeystore;
+use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress, TransactionDigest};
+use sui_types::messages::{ExecuteTransactionRequestType, Transaction, TransactionData};
+use sui_types::object::Owner;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+
+use crate::error::{Error, SuiRpcResult};
+use crate::SuiClient;
+
+/// Maximum number of coins merged by one transaction, below the `max_gas_payment_objects` limit
+/// of the protocol.
+const MAX_MERGED_COINS: usize = 255;
+
+#[derive(Clone, Debug)]
+pub struct GasPoolConfig {
+    /// Number of gas coins split from the funding coin by [GasPool::fund].
+    pub pool_size: usize,
+    /// Balance of each gas coin, which must cover the gas of all the transactions leasing it.
+    pub coin_balance: u64,
+    /// Gas budget of the transactions splitting and merging the coins.
+    pub gas_budget: u64,
+    /// Time without leases after which [GasPool::merge_if_idle] merges the coins back.
+    pub idle_timeout: Duration,
+}
+
+impl Default for GasPoolConfig {
+    fn default() -> Self {
+        Self {
+            pool_size: 10,
+            coin_balance: 1_000_000_000,
+            gas_budget: 50_000_000,
+            idle_timeout: Duration::from_secs(300),
+        }
+    }
+}
+
+/// A coin of the pool, with its latest known version, or which must be fetched again because
+/// the transaction using it did not report its effects.
+enum PooledCoin {
+    Ready(ObjectRef),
+    Stale(ObjectID),
+}
+
+struct PoolState {
+    available: VecDeque<PooledCoin>,
+    leased: usize,
+    idle_since: Instant,
+}
+
+struct PoolCoins {
+    state: Mutex<PoolState>,
+    returned: Notify,
+}
+
+impl PoolCoins {
+    fn put_back(&self, coin: PooledCoin) {
+        let mut state = self.state.lock().unwrap();
+        state.available.push_back(coin);
+        state.leased -= 1;
+        if state.leased == 0 {
+            state.idle_since = Instant::now();
+        }
+        drop(state);
+        self.returned.notify_one();
+    }
+}
+
+/// A gas coin leased to one transaction. The lease must be returned with the effects of the
+/// transaction, so that the pool knows the new version of the coin. Leases dropped without
+/// effects are fetched again before being leased again.
+pub struct GasLease {
+    coin: ObjectRef,
+    coins: Arc<PoolCoins>,
+    returned: bool,
+}
+
+impl GasLease {
+    /// The gas coin to pay the transaction with.
+    pub fn gas(&self) -> ObjectRef {
+        self.coin
+    }
+
+    /// Returns the coin after the transaction was executed, successfully or not.
+    pub fn return_with_effects(mut self, effects: &SuiTransactionBlockEffects) {
+        let gas = effects.gas_object().reference.to_object_ref();
+        let coin = if gas.0 == self.coin.0 {
+            PooledCoin::Ready(gas)
+        } else {
+            PooledCoin::Stale(self.coin.0)
+        };
+        self.returned = true;
+        self.coins.put_back(coin);
+    }
+
+    /// Returns the coin when it was not used by any transaction.
+    pub fn return_unused(mut self) {
+        self.returned = true;
+        self.coins.put_back(PooledCoin::Ready(self.coin));
+    }
+}
+
+impl Drop for GasLease {
+    fn drop(&mut self) {
+        if !self.returned {
+            self.coins.put_back(PooledCoin::Stale(self.coin.0));
+        }
+    }
+}
+
+/// Gas coins of an address, whose keys are in the keystore, leased to concurrent transactions.
+pub struct GasPool<K> {
+    client: SuiClient,
+    address: SuiAddress,
+    keystore: K,
+    config: GasPoolConfig,
+    coins: Arc<PoolCoins>,
+    /// The coin the gas coins were split from, which they are merged back into.
+    funding: Mutex<Option<ObjectRef>>,
+}
+
+impl<K: AccountKeystore> GasPool<K> {
+    pub fn new(client: SuiClient, address: SuiAddress, keystore: K, config: GasPoolConfig) -> Self {
+        Self {
+            client,
+            address,
+            keystore,
+            config,
+            coins: Arc::new(PoolCoins {
+                state: Mutex::new(PoolState {
+                    available: VecDeque::new(),
+                    leased: 0,
+                    idle_since: Instant::now(),
+                }),
+                returned: Notify::new(),
+            }),
+            funding: Mutex::new(None),
+        }
+    }
+
+    /// Number of coins available for leases.
+    pub fn available(&self) -> usize {
+        self.coins.state.lock().unwrap().available.len()
+    }
+
+    /// Number of coins leased to transactions.
+    pub fn leased(&self) -> usize {
+        self.coins.state.lock().unwrap().leased
+    }
+
+    /// Splits `pool_size` gas coins of `coin_balance` from the funding coin, which pays the gas
+    /// of the split, and adds them to the pool.
+    pub async fn fund(&self, funding_coin: ObjectRef) -> SuiRpcResult<Vec<ObjectRef>> {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder
+            .pay_sui(
+                vec![self.address; self.config.pool_size],
+                vec![self.config.coin_balance; self.config.pool_size],
+            )
+            .map_err(|e| Error::DataError(e.to_string()))?;
+        let gas_price = self.client.read_api().get_reference_gas_price().await?;
+        let tx = TransactionData::new_programmable(
+            self.address,
+            vec![funding_coin],
+            builder.finish(),
+            self.config.gas_budget,
+            gas_price,
+        );
+        let effects = self.execute(tx).await?;
+
+        let coins: Vec<_> = effects
+            .created()
+            .iter()
+            .filter(|created| created.owner == Owner::AddressOwner(self.address))
+            .map(|created| created.reference.to_object_ref())
+            .collect();
+        *self.funding.lock().unwrap() = Some(effects.gas_object().reference.to_object_ref());
+        let mut state = self.coins.state.lock().unwrap();
+        state
+            .available
+            .extend(coins.iter().map(|coin| PooledCoin::Ready(*coin)));
+        drop(state);
+        self.coins.returned.notify_waiters();
+        Ok(coins)
+    }
+
+    /// Leases a gas coin, waiting for one to be returned if all the coins are leased.
+    pub async fn lease(&self) -> SuiRpcResult<GasLease> {
+        loop {
+            let returned = self.coins.returned.notified();
+            let coin = {
+                let mut state = self.coins.state.lock().unwrap();
+                match state.available.pop_front() {
+                    Some(coin) => {
+                        state.leased += 1;
+                        Some(coin)
+                    }
+                    None if state.leased == 0 => {
+                        return Err(Error::DataError(
+                            "The gas pool has no coins, it must be funded first".into(),
+                        ))
+                    }
+                    None => None,
+                }
+            };
+            let Some(coin) = coin else {
+                returned.await;
+                continue;
+            };
+            let coin = match coin {
+                PooledCoin::Ready(coin) => coin,
+                PooledCoin::Stale(id) => match self.latest_object_ref(id).await {
+                    Ok(coin) => coin,
+                    Err(e) => {
+                        // The coin cannot be fetched, e.g. it was merged by another transaction,
+                        // and is removed from the pool.
+                        let mut state = self.coins.state.lock().unwrap();
+                        state.leased -= 1;
+                        let empty = state.leased == 0 && state.available.is_empty();
+                        drop(state);
+                        self.coins.returned.notify_one();
+                        if empty {
+                            return Err(e);
+                        }
+                        continue;
+                    }
+                },
+            };
+            return Ok(GasLease {
+                coin,
+                coins: self.coins.clone(),
+                returned: false,
+            });
+        }
+    }
+
+    /// Merges all the coins of the pool back into the funding coin, if no coin was leased for
+    /// `idle_timeout`. Returns the digests of the merge transactions.
+    pub async fn merge_if_idle(&self) -> SuiRpcResult<Vec<TransactionDigest>> {
+        let coins: Vec<_> = {
+            let mut state = self.coins.state.lock().unwrap();
+            if state.leased > 0
+                || state.available.is_empty()
+                || state.idle_since.elapsed() < self.config.idle_timeout
+            {
+                return Ok(vec![]);
+            }
+            state.available.drain(..).collect()
+        };
+
+        let ids: Vec<_> = coins
+            .iter()
+            .map(|coin| match coin {
+                PooledCoin::Ready(coin) => coin.0,
+                PooledCoin::Stale(id) => *id,
+            })
+            .collect();
+        let result = self.merge(coins).await;
+        if result.is_err() {
+            // The coins which were not merged are fetched again when leased, the merged ones are
+            // removed from the pool then.
+            let mut state = self.coins.state.lock().unwrap();
+            state
+                .available
+                .extend(ids.into_iter().map(PooledCoin::Stale));
+        }
+        result
+    }
+
+    async fn merge(&self, coins: Vec<PooledCoin>) -> SuiRpcResult<Vec<TransactionDigest>> {
+        let mut refs = Vec::with_capacity(coins.len());
+        for coin in coins {
+            match coin {
+                PooledCoin::Ready(coin) => refs.push(coin),
+                PooledCoin::Stale(id) => refs.push(self.latest_object_ref(id).await?),
+            }
+        }
+        let funding = *self.funding.lock().unwrap();
+        if let Some(funding) = funding {
+            refs.insert(0, funding);
+        }
+
+        let gas_price = self.client.read_api().get_reference_gas_price().await?;
+        let mut digests = vec![];
+        let mut refs = refs.into_iter().peekable();
+        let mut target = None;
+        while refs.peek().is_some() {
+            // The coins are merged by paying the gas with all of them, into the first one.
+            let mut batch: Vec<_> = target.into_iter().collect();
+            batch.extend(refs.by_ref().take(MAX_MERGED_COINS - batch.len()));
+            let mut builder = ProgrammableTransactionBuilder::new();
+            builder.pay_all_sui(self.address);
+            let tx = TransactionData::new_programmable(
+                self.address,
+                batch,
+                builder.finish(),
+                self.config.gas_budget,
+                gas_price,
+            );
+            let effects = self.execute(tx).await?;
+            target = Some(effects.gas_object().reference.to_object_ref());
+            // Updated as soon as the batch is executed, so that the funding coin keeps its latest
+            // version if a later batch fails.
+            *self.funding.lock().unwrap() = target;
+            digests.push(*effects.transaction_digest());
+        }
+        Ok(digests)
+    }
+
+    async fn latest_object_ref(&self, id: ObjectID) -> SuiRpcResult<ObjectRef> {
+        let response = self
+            .client
+            .read_api()
+            .get_object_with_options(id, SuiObjectDataOptions::new())
+            .await?;
+        Ok(response
+            .object()
+            .map_err(|e| Error::DataError(format!("Cannot fetch gas coin {id}: {e}")))?
+            .object_ref())
+    }
+
+    async fn execute(&self, tx: TransactionData) -> SuiRpcResult<SuiTransactionBlockEffects> {
+        let signature = self
+            .keystore
+            .sign_secure(&self.address, &tx, Intent::sui_transaction())
+            .map_err(|e| Error::DataError(format!("Cannot sign the transaction: {e}")))?;
+        let response: SuiTransactionBlockResponse = self
+            .client
+            .quorum_driver()
+            .execute_transaction_block(
+                Transaction::from_data(tx, Intent::sui_transaction(), vec![signature])
+                    .verify()
+                    .map_err(|e| Error::DataError(format!("Invalid signature: {e}")))?,
+                SuiTransactionBlockResponseOptions::new().with_effects(),
+                Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+            )
+            .await?;
+        let effects = response
+            .effects
+            .ok_or_else(|| Error::DataError("Expect effects to be non-empty".to_string()))?;
+        if let SuiExecutionStatus::Failure { error } = effects.status() {
+            return Err(Error::DataError(format!(
+                "Transaction {} failed: {error}",
+                response.digest
+            )));
+        }
+        Ok(effects)
+    }
+}
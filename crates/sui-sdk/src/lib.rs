@@ -21,18 +21,37 @@ use sui_json_rpc::{
 };
 pub use sui_json_rpc_types as rpc_types;
 use sui_json_rpc_types::{
-    ObjectsPage, SuiObjectDataFilter, SuiObjectDataOptions, SuiObjectResponse,
-    SuiObjectResponseQuery,
+    ObjectsPage, SuiMoveNormalizedFunction, SuiObjectDataFilter, SuiObjectDataOptions,
+    SuiObjectResponse, SuiObjectResponseQuery,
 };
 use sui_transaction_builder::{DataReader, TransactionBuilder};
 pub use sui_types as types;
 use sui_types::base_types::{ObjectID, ObjectInfo, SuiAddress};
 
 use crate::apis::{CoinReadApi, EventApi, GovernanceApi, QuorumDriver, ReadApi};
+use crate::equivocation_guard::{EquivocationGuard, GuardMode};
 use crate::error::{Error, SuiRpcResult};
+use crate::metrics::RequestMetrics;
+use crate::retry::{RetryClient, RetryPolicy};
+use crate::ws::ReconnectingWsClient;
 
 pub mod apis;
+pub mod coin_manager;
+#[cfg(feature = "direct-execution")]
+pub mod direct_execution;
+pub mod equivocation_guard;
 pub mod error;
+pub mod gas_pool;
+mod metrics;
+pub mod module_cache;
+pub mod move_call;
+pub mod retry;
+pub mod typed_events;
+mod ws;
+
+#[cfg(feature = "metrics")]
+pub use metrics::SdkMetrics;
+
 pub const SUI_COIN_TYPE: &str = "0x2::sui::SUI";
 const WAIT_FOR_TX_TIMEOUT_SEC: u64 = 60;
 
@@ -40,6 +59,10 @@ pub struct SuiClientBuilder {
     request_timeout: Duration,
     max_concurrent_requests: usize,
     ws_url: Option<String>,
+    retry_policy: RetryPolicy,
+    equivocation_guard: Option<GuardMode>,
+    #[cfg(feature = "metrics")]
+    metrics: Option<Arc<SdkMetrics>>,
 }
 
 impl Default for SuiClientBuilder {
@@ -48,6 +71,10 @@ impl Default for SuiClientBuilder {
             request_timeout: Duration::from_secs(60),
             max_concurrent_requests: 256,
             ws_url: None,
+            retry_policy: RetryPolicy::default(),
+            equivocation_guard: None,
+            #[cfg(feature = "metrics")]
+            metrics: None,
         }
     }
 }
@@ -68,6 +95,29 @@ impl SuiClientBuilder {
         self
     }
 
+    /// Retry policy of requests sent over http. Transactions are only re-submitted after
+    /// checking that a previous attempt did not land.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Track the owned objects of the transactions executed by the client, and reject or queue
+    /// the transactions reusing them before the previous ones are finalized, see
+    /// [EquivocationGuard].
+    pub fn equivocation_guard(mut self, mode: GuardMode) -> Self {
+        self.equivocation_guard = Some(mode);
+        self
+    }
+
+    /// Record request counts, latencies, errors and websocket reconnects of the client. The same
+    /// [SdkMetrics] can be shared by several clients.
+    #[cfg(feature = "metrics")]
+    pub fn metrics(mut self, metrics: Arc<SdkMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
     pub async fn build(self, http: impl AsRef<str>) -> SuiRpcResult<SuiClient> {
         let client_version = env!("CARGO_PKG_VERSION");
         let mut headers = HeaderMap::new();
@@ -82,16 +132,19 @@ impl SuiClientBuilder {
         );
         headers.insert(CLIENT_SDK_TYPE_HEADER, HeaderValue::from_static("rust"));
 
+        #[cfg(feature = "metrics")]
+        let metrics = RequestMetrics::new(self.metrics);
+        #[cfg(not(feature = "metrics"))]
+        let metrics = RequestMetrics::default();
+
         let ws = if let Some(url) = self.ws_url {
-            Some(
-                WsClientBuilder::default()
-                    .max_request_body_size(2 << 30)
-                    .max_concurrent_requests(self.max_concurrent_requests)
-                    .set_headers(headers.clone())
-                    .request_timeout(self.request_timeout)
-                    .build(url)
-                    .await?,
-            )
+            let builder = WsClientBuilder::default()
+                .max_request_body_size(2 << 30)
+                .max_concurrent_requests(self.max_concurrent_requests)
+                .set_headers(headers.clone())
+                .request_timeout(self.request_timeout);
+            let client = builder.clone().build(&url).await?;
+            Some((url, builder, client))
         } else {
             None
         };
@@ -103,12 +156,18 @@ impl SuiClientBuilder {
             .request_timeout(self.request_timeout)
             .build(http)?;
 
-        let info = Self::get_server_info(&http, &ws).await?;
+        let info = Self::get_server_info(&http, ws.as_ref().map(|(_, _, client)| client)).await?;
 
+        let http = RetryClient::new(http, self.retry_policy, metrics.clone());
+        let ws = ws
+            .map(|(url, builder, client)| ReconnectingWsClient::new(url, builder, client, metrics));
         let rpc = RpcClient { http, ws, info };
         let api = Arc::new(rpc);
         let read_api = Arc::new(ReadApi::new(api.clone()));
-        let quorum_driver = QuorumDriver::new(api.clone());
+        let guard = self
+            .equivocation_guard
+            .map(|mode| Arc::new(EquivocationGuard::new(mode)));
+        let quorum_driver = QuorumDriver::new(api.clone(), guard, read_api.module_cache().clone());
         let event_api = EventApi::new(api.clone());
         let transaction_builder = TransactionBuilder::new(read_api.clone());
         let coin_read_api = CoinReadApi::new(api.clone());
@@ -127,7 +186,7 @@ impl SuiClientBuilder {
 
     async fn get_server_info(
         http: &HttpClient,
-        ws: &Option<WsClient>,
+        ws: Option<&WsClient>,
     ) -> Result<ServerInfo, Error> {
         let rpc_spec: Value = http.request("rpc.discover", rpc_params![]).await?;
         let version = rpc_spec
@@ -182,8 +241,8 @@ pub struct SuiClient {
 }
 
 pub(crate) struct RpcClient {
-    http: HttpClient,
-    ws: Option<WsClient>,
+    http: RetryClient<HttpClient>,
+    ws: Option<ReconnectingWsClient>,
     info: ServerInfo,
 }
 
@@ -276,6 +335,7 @@ impl DataReader for ReadApi {
                 data,
                 next_cursor,
                 has_next_page,
+                ..
             } = self
                 .get_owned_objects(address, query.clone(), cursor, None)
                 .await?;
@@ -298,6 +358,28 @@ impl DataReader for ReadApi {
         Ok(self.get_object_with_options(object_id, options).await?)
     }
 
+    async fn multi_get_objects_with_options(
+        &self,
+        object_ids: Vec<ObjectID>,
+        options: SuiObjectDataOptions,
+    ) -> Result<Vec<SuiObjectResponse>, anyhow::Error> {
+        Ok(self
+            .multi_get_object_with_options(object_ids, options)
+            .await?)
+    }
+
+    async fn get_function_signature(
+        &self,
+        package: ObjectID,
+        module: &str,
+        function: &str,
+    ) -> Result<SuiMoveNormalizedFunction, anyhow::Error> {
+        Ok(self
+            .module_cache()
+            .get_function_signature(package, module, function)
+            .await?)
+    }
+
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error> {
         Ok(self.get_reference_gas_price().await?)
     }
@@ -0,0 +1,140 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::future::Future;
+#[cfg(feature = "metrics")]
+use std::sync::Arc;
+
+#[cfg(feature = "metrics")]
+use prometheus::{
+    register_histogram_vec_with_registry, register_int_counter_vec_with_registry,
+    register_int_counter_with_registry, HistogramVec, IntCounter, IntCounterVec, Registry,
+};
+
+#[cfg(feature = "metrics")]
+const LATENCY_SEC_BUCKETS: &[f64] = &[
+    0.001, 0.005, 0.01, 0.05, 0.1, 0.25, 0.5, 1., 2.5, 5., 10., 20., 30., 60., 90.,
+];
+
+/// Prometheus metrics of a [crate::SuiClient], see [crate::SuiClientBuilder::metrics].
+#[cfg(feature = "metrics")]
+#[derive(Debug, Clone)]
+pub struct SdkMetrics {
+    /// Requests sent, by method. Retries of a request are not counted separately.
+    requests: IntCounterVec,
+    /// Latency of requests by method, including retries.
+    request_latency: HistogramVec,
+    /// Failed requests, by method and error code.
+    request_errors: IntCounterVec,
+    /// Number of times the websocket client reconnected to the server.
+    ws_reconnects: IntCounter,
+}
+
+#[cfg(feature = "metrics")]
+impl SdkMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            requests: register_int_counter_vec_with_registry!(
+                "sui_sdk_requests",
+                "Number of requests sent by the SDK, by method",
+                &["method"],
+                registry,
+            )
+            .unwrap(),
+            request_latency: register_histogram_vec_with_registry!(
+                "sui_sdk_request_latency",
+                "Latency of requests sent by the SDK, by method",
+                &["method"],
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            request_errors: register_int_counter_vec_with_registry!(
+                "sui_sdk_request_errors",
+                "Number of failed requests sent by the SDK, by method and error code",
+                &["method", "code"],
+                registry,
+            )
+            .unwrap(),
+            ws_reconnects: register_int_counter_with_registry!(
+                "sui_sdk_ws_reconnects",
+                "Number of times the SDK websocket client reconnected",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+/// Label of the error of a failed request: the [sui_json_rpc_types::SuiRpcErrorCode] if the
+/// server sent one, the JSON-RPC error code otherwise, or the kind of client side failure.
+#[cfg(feature = "metrics")]
+fn error_code_label(error: &jsonrpsee::core::Error) -> String {
+    use jsonrpsee::types::error::CallError;
+
+    if let Some(code) = crate::error::rpc_error_code(error) {
+        return serde_json::to_value(code)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_else(|| format!("{code:?}"));
+    }
+    match error {
+        jsonrpsee::core::Error::Call(CallError::Custom(e)) => e.code().to_string(),
+        jsonrpsee::core::Error::Call(CallError::InvalidParams(_)) => "invalidParams".to_string(),
+        jsonrpsee::core::Error::Call(CallError::Failed(_)) => "callFailed".to_string(),
+        jsonrpsee::core::Error::Transport(_) => "transport".to_string(),
+        jsonrpsee::core::Error::RequestTimeout => "timeout".to_string(),
+        jsonrpsee::core::Error::RestartNeeded(_) => "restartNeeded".to_string(),
+        jsonrpsee::core::Error::ParseError(_) => "parseError".to_string(),
+        _ => "client".to_string(),
+    }
+}
+
+/// Records [SdkMetrics] if the client was built with metrics, does nothing otherwise.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RequestMetrics {
+    #[cfg(feature = "metrics")]
+    metrics: Option<Arc<SdkMetrics>>,
+}
+
+impl RequestMetrics {
+    #[cfg(feature = "metrics")]
+    pub(crate) fn new(metrics: Option<Arc<SdkMetrics>>) -> Self {
+        Self { metrics }
+    }
+
+    /// Run the request to `method`, recording its latency and outcome.
+    pub(crate) async fn observe<T>(
+        &self,
+        method: &str,
+        request: impl Future<Output = Result<T, jsonrpsee::core::Error>>,
+    ) -> Result<T, jsonrpsee::core::Error> {
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.metrics {
+            metrics.requests.with_label_values(&[method]).inc();
+            let timer = metrics
+                .request_latency
+                .with_label_values(&[method])
+                .start_timer();
+            let result = request.await;
+            timer.observe_duration();
+            if let Err(e) = &result {
+                metrics
+                    .request_errors
+                    .with_label_values(&[method, &error_code_label(e)])
+                    .inc();
+            }
+            return result;
+        }
+        #[cfg(not(feature = "metrics"))]
+        let _ = method;
+        request.await
+    }
+
+    pub(crate) fn ws_reconnected(&self) {
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.metrics {
+            metrics.ws_reconnects.inc();
+        }
+    }
+}
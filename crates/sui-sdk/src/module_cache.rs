@@ -0,0 +1,147 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+
+use futures::future::try_join;
+
+use sui_json_rpc::api::{MoveUtilsClient, ReadApiClient};
+use sui_json_rpc_types::{
+    ObjectChange, SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiObjectDataOptions,
+};
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::is_system_package;
+
+use crate::error::{Error, SuiRpcResult};
+use crate::RpcClient;
+
+type NormalizedModules = Arc<BTreeMap<String, SuiMoveNormalizedModule>>;
+
+/// Number of times the modules of a system package are fetched while it keeps being upgraded.
+const MAX_FETCH_ATTEMPTS: usize = 3;
+
+/// Client side cache of the normalized Move modules of packages, keyed by package ID and version.
+///
+/// Packages are fetched from the fullnode the first time one of their modules is needed. Upgrades
+/// publish other packages at new IDs, so the modules of a package ID never change, except for the
+/// system packages, which are upgraded in place at epoch boundaries: their version is checked
+/// again before their cached modules are reused. An entry is also dropped when the package is seen
+/// at another version in the object changes of a transaction, or with [Self::invalidate].
+#[derive(Debug)]
+pub struct NormalizedModuleCache {
+    api: Arc<RpcClient>,
+    packages: RwLock<HashMap<ObjectID, (SequenceNumber, NormalizedModules)>>,
+}
+
+impl NormalizedModuleCache {
+    pub(crate) fn new(api: Arc<RpcClient>) -> Self {
+        Self {
+            api,
+            packages: Default::default(),
+        }
+    }
+
+    /// Normalized modules of `package`, by module name, fetching them if needed.
+    pub async fn get_modules(&self, package: ObjectID) -> SuiRpcResult<NormalizedModules> {
+        let cached = self.packages.read().unwrap().get(&package).cloned();
+        if let Some((version, modules)) = cached {
+            if !is_system_package(package) || self.fetch_version(package).await? == version {
+                return Ok(modules);
+            }
+        }
+        let (version, modules) = self.fetch_modules(package).await?;
+        self.packages
+            .write()
+            .unwrap()
+            .insert(package, (version, modules.clone()));
+        Ok(modules)
+    }
+
+    /// Fetches the modules of `package` with the version they belong to. The version of a system
+    /// package is read before and after its modules, which are fetched again if it was upgraded
+    /// in between.
+    async fn fetch_modules(
+        &self,
+        package: ObjectID,
+    ) -> SuiRpcResult<(SequenceNumber, NormalizedModules)> {
+        if !is_system_package(package) {
+            let (version, modules) = try_join(
+                self.fetch_version(package),
+                self.api
+                    .http
+                    .get_normalized_move_modules_by_package(package),
+            )
+            .await?;
+            return Ok((version, Arc::new(modules)));
+        }
+        let mut version = self.fetch_version(package).await?;
+        for _ in 0..MAX_FETCH_ATTEMPTS {
+            let modules = self
+                .api
+                .http
+                .get_normalized_move_modules_by_package(package)
+                .await?;
+            let current = self.fetch_version(package).await?;
+            if current == version {
+                return Ok((version, Arc::new(modules)));
+            }
+            version = current;
+        }
+        Err(Error::DataError(format!(
+            "Package {package} was upgraded while fetching its modules"
+        )))
+    }
+
+    async fn fetch_version(&self, package: ObjectID) -> SuiRpcResult<SequenceNumber> {
+        let object = self
+            .api
+            .http
+            .get_object(package, Some(SuiObjectDataOptions::new()))
+            .await?;
+        Ok(object
+            .into_object()
+            .map_err(|e| Error::DataError(e.to_string()))?
+            .version)
+    }
+
+    /// Version of `package` its cached modules were fetched at, if any.
+    pub fn cached_version(&self, package: ObjectID) -> Option<SequenceNumber> {
+        let packages = self.packages.read().unwrap();
+        packages.get(&package).map(|(version, _)| *version)
+    }
+
+    /// Signature of the function `package::module::function`, which must be public or entry.
+    pub async fn get_function_signature(
+        &self,
+        package: ObjectID,
+        module: &str,
+        function: &str,
+    ) -> SuiRpcResult<SuiMoveNormalizedFunction> {
+        let modules = self.get_modules(package).await?;
+        modules
+            .get(module)
+            .and_then(|module| module.exposed_functions.get(function))
+            .cloned()
+            .ok_or_else(|| {
+                Error::DataError(format!(
+                    "No function {function} in module {module} of package {package}"
+                ))
+            })
+    }
+
+    /// Drops the modules of the packages changed at another version than the cached one.
+    pub fn apply_object_changes(&self, changes: &[ObjectChange]) {
+        let mut packages = self.packages.write().unwrap();
+        for change in changes {
+            let (id, version, _) = change.object_ref();
+            if matches!(packages.get(&id), Some((cached, _)) if *cached != version) {
+                packages.remove(&id);
+            }
+        }
+    }
+
+    pub fn invalidate(&self, package: ObjectID) {
+        self.packages.write().unwrap().remove(&package);
+    }
+}
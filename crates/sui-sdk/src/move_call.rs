@@ -0,0 +1,136 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Typed descriptors of Move functions, declared with [crate::move_call], so that calls with the
+//! wrong number or kinds of arguments fail to compile instead of failing when the transaction is
+//! built or executed.
+
+use move_core_types::identifier::Identifier;
+use serde::Serialize;
+use sui_types::base_types::ObjectID;
+
+pub use move_core_types::language_storage::TypeTag;
+pub use sui_transaction_builder::LazyProgrammableTransaction;
+pub use sui_types::messages::Argument;
+
+use crate::error::{Error, SuiRpcResult};
+
+/// An argument of a Move call taking an object: the object itself, added as an input of the
+/// transaction by ID, or the result of a previous command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectArgument {
+    Id(ObjectID),
+    Argument(Argument),
+}
+
+impl From<ObjectID> for ObjectArgument {
+    fn from(id: ObjectID) -> Self {
+        Self::Id(id)
+    }
+}
+
+impl From<Argument> for ObjectArgument {
+    fn from(argument: Argument) -> Self {
+        Self::Argument(argument)
+    }
+}
+
+impl ObjectArgument {
+    pub fn into_argument(self, tx: &mut LazyProgrammableTransaction) -> Argument {
+        match self {
+            Self::Id(id) => tx.object(id),
+            Self::Argument(argument) => argument,
+        }
+    }
+}
+
+/// Declares Rust functions adding calls of Move functions to a [LazyProgrammableTransaction].
+/// Each parameter is either an `object`, given as an [ObjectID] or a previous result, or a
+/// `pure<T>` value of the Rust type `T`, serialized with BCS. Type parameters of the Move function
+/// become [TypeTag] parameters, before the others. The `TxContext` parameter is left out.
+///
+/// The declared signature is checked against the one of the package on chain when the
+/// transaction is resolved, see [sui_transaction_builder::TransactionBuilder].
+///
+/// ```ignore
+/// sui_sdk::move_call! {
+///     /// Stakes `stake` with the validator `validator_address`.
+///     pub fn request_add_stake(
+///         wrapper: object,
+///         stake: object,
+///         validator_address: pure<SuiAddress>,
+///     ) in SUI_SYSTEM_PACKAGE_ID, sui_system;
+///
+///     pub fn join<T>(self_: object, c: object) in SUI_FRAMEWORK_OBJECT_ID, coin;
+/// }
+///
+/// let mut tx = LazyProgrammableTransaction::new();
+/// request_add_stake(&mut tx, SUI_SYSTEM_STATE_OBJECT_ID, coin, validator)?;
+/// ```
+#[macro_export]
+macro_rules! move_call {
+    (@param object) => {
+        impl Into<$crate::move_call::ObjectArgument>
+    };
+    (@param pure $type_:ty) => {
+        $type_
+    };
+    (@argument $tx:ident, $param:ident, object) => {
+        $crate::move_call::ObjectArgument::into_argument($param.into(), $tx)
+    };
+    (@argument $tx:ident, $param:ident, pure) => {
+        $crate::move_call::pure($tx, &$param)?
+    };
+    ($(
+        $(#[$meta:meta])*
+        $vis:vis fn $function:ident $(<$($type_param:ident),* $(,)?>)? (
+            $($param:ident : $kind:ident $(<$type_:ty>)?),* $(,)?
+        ) in $package:expr, $module:ident;
+    )*) => {$(
+        $(#[$meta])*
+        #[allow(non_snake_case)]
+        $vis fn $function(
+            tx: &mut $crate::move_call::LazyProgrammableTransaction,
+            $($($type_param: $crate::move_call::TypeTag,)*)?
+            $($param: $crate::move_call!(@param $kind $($type_)?),)*
+        ) -> $crate::error::SuiRpcResult<$crate::move_call::Argument> {
+            let arguments = vec![$($crate::move_call!(@argument tx, $param, $kind)),*];
+            $crate::move_call::add_move_call(
+                tx,
+                $package,
+                stringify!($module),
+                stringify!($function),
+                vec![$($($type_param),*)?],
+                arguments,
+            )
+        }
+    )*};
+}
+
+#[doc(hidden)]
+pub fn pure<T: Serialize>(
+    tx: &mut LazyProgrammableTransaction,
+    value: &T,
+) -> SuiRpcResult<Argument> {
+    Ok(tx.pure_bytes(bcs::to_bytes(value)?))
+}
+
+#[doc(hidden)]
+pub fn add_move_call(
+    tx: &mut LazyProgrammableTransaction,
+    package: ObjectID,
+    module: &str,
+    function: &str,
+    type_arguments: Vec<TypeTag>,
+    arguments: Vec<Argument>,
+) -> SuiRpcResult<Argument> {
+    let identifier =
+        |name: &str| Identifier::new(name).map_err(|e| Error::DataError(e.to_string()));
+    Ok(tx.move_call(
+        package,
+        identifier(module)?,
+        identifier(function)?,
+        type_arguments,
+        arguments,
+    ))
+}
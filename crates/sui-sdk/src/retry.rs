@@ -0,0 +1,252 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use jsonrpsee::core::client::{BatchResponse, ClientT, Subscription, SubscriptionClientT};
+use jsonrpsee::core::params::BatchRequestBuilder;
+use jsonrpsee::core::traits::ToRpcParams;
+use rand::Rng;
+use serde::de::DeserializeOwned;
+use serde_json::value::RawValue;
+
+use crate::error::rpc_error_code;
+use crate::metrics::RequestMetrics;
+
+/// Methods that must not be blindly re-sent, because the first attempt may have been applied
+/// by the server. They are sent once by the client, and [crate::apis::QuorumDriver] retries
+/// them by checking whether the transaction digest landed before re-submitting.
+const DIGEST_IDEMPOTENT_METHODS: &[&str] = &["sui_executeTransactionBlock"];
+
+/// How a failed request to a JSON-RPC method may be retried.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RetrySafety {
+    /// The method is read-only or has no side effects, it can be re-sent as is.
+    Safe,
+    /// The method submits a transaction, it can only be re-sent after checking that the
+    /// transaction digest has not been executed.
+    DigestIdempotent,
+}
+
+/// Exponential backoff with jitter between attempts of a request.
+#[derive(Clone, Debug)]
+pub struct Backoff {
+    /// Total number of attempts, including the first one.
+    pub max_attempts: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub multiplier: f64,
+    /// Fraction of the delay that is randomized, between 0 and 1.
+    pub jitter: f64,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+            multiplier: 2.0,
+            jitter: 0.2,
+        }
+    }
+}
+
+impl Backoff {
+    pub fn no_retry() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Delay before the given retry, starting from 1 for the first retry.
+    pub fn delay(&self, retry: u32) -> Duration {
+        let exponent = retry.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let delay = (self.initial_delay.as_secs_f64() * self.multiplier.powi(exponent))
+            .min(self.max_delay.as_secs_f64());
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        let factor = if jitter > 0.0 {
+            rand::thread_rng().gen_range(1.0 - jitter..=1.0 + jitter)
+        } else {
+            1.0
+        };
+        Duration::from_secs_f64(delay * factor)
+    }
+}
+
+/// Retry configuration of a [crate::SuiClient], with a default [Backoff] and per-method overrides.
+#[derive(Clone, Debug, Default)]
+pub struct RetryPolicy {
+    default: Backoff,
+    methods: HashMap<String, Backoff>,
+}
+
+impl RetryPolicy {
+    pub fn new(default: Backoff) -> Self {
+        Self {
+            default,
+            methods: HashMap::new(),
+        }
+    }
+
+    pub fn no_retry() -> Self {
+        Self::new(Backoff::no_retry())
+    }
+
+    /// Use a different backoff for the given method, e.g. `sui_executeTransactionBlock`.
+    pub fn with_method(mut self, method: impl Into<String>, backoff: Backoff) -> Self {
+        self.methods.insert(method.into(), backoff);
+        self
+    }
+
+    pub fn backoff(&self, method: &str) -> &Backoff {
+        self.methods.get(method).unwrap_or(&self.default)
+    }
+
+    pub fn safety(method: &str) -> RetrySafety {
+        if DIGEST_IDEMPOTENT_METHODS.contains(&method) {
+            RetrySafety::DigestIdempotent
+        } else {
+            RetrySafety::Safe
+        }
+    }
+
+    /// Whether the error is transient, i.e. the same request may succeed later.
+    pub fn is_retryable(error: &jsonrpsee::core::Error) -> bool {
+        match error {
+            jsonrpsee::core::Error::Transport(_)
+            | jsonrpsee::core::Error::RequestTimeout
+            | jsonrpsee::core::Error::RestartNeeded(_) => true,
+            e => rpc_error_code(e).map_or(false, |code| code.is_retryable()),
+        }
+    }
+}
+
+/// Params already serialized by the first attempt, so that they can be re-sent.
+struct RawParams(Option<Box<RawValue>>);
+
+impl ToRpcParams for RawParams {
+    fn to_rpc_params(self) -> Result<Option<Box<RawValue>>, jsonrpsee::core::Error> {
+        Ok(self.0)
+    }
+}
+
+/// JSON-RPC client retrying requests to [RetrySafety::Safe] methods according to a [RetryPolicy].
+/// Requests to other methods, notifications, batches and subscriptions are sent once.
+#[derive(Debug)]
+pub(crate) struct RetryClient<C> {
+    inner: C,
+    policy: RetryPolicy,
+    metrics: RequestMetrics,
+}
+
+impl<C> RetryClient<C> {
+    pub(crate) fn new(inner: C, policy: RetryPolicy, metrics: RequestMetrics) -> Self {
+        Self {
+            inner,
+            policy,
+            metrics,
+        }
+    }
+
+    pub(crate) fn policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+}
+
+impl<C: ClientT + Send + Sync> RetryClient<C> {
+    async fn request_with_retries<R, Params>(
+        &self,
+        method: &str,
+        params: Params,
+    ) -> Result<R, jsonrpsee::core::Error>
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send,
+    {
+        if RetryPolicy::safety(method) == RetrySafety::DigestIdempotent {
+            return self.inner.request(method, params).await;
+        }
+        let backoff = self.policy.backoff(method);
+        let params = params.to_rpc_params()?;
+        let mut attempt = 1;
+        loop {
+            match self.inner.request(method, RawParams(params.clone())).await {
+                Err(e) if attempt < backoff.max_attempts && RetryPolicy::is_retryable(&e) => {
+                    tokio::time::sleep(backoff.delay(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<C: ClientT + Send + Sync> ClientT for RetryClient<C> {
+    async fn notification<Params>(
+        &self,
+        method: &str,
+        params: Params,
+    ) -> Result<(), jsonrpsee::core::Error>
+    where
+        Params: ToRpcParams + Send,
+    {
+        self.inner.notification(method, params).await
+    }
+
+    async fn request<R, Params>(
+        &self,
+        method: &str,
+        params: Params,
+    ) -> Result<R, jsonrpsee::core::Error>
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send,
+    {
+        self.metrics
+            .observe(method, self.request_with_retries(method, params))
+            .await
+    }
+
+    async fn batch_request<'a, R>(
+        &self,
+        batch: BatchRequestBuilder<'a>,
+    ) -> Result<BatchResponse<'a, R>, jsonrpsee::core::Error>
+    where
+        R: DeserializeOwned + std::fmt::Debug + 'a,
+    {
+        self.inner.batch_request(batch).await
+    }
+}
+
+#[async_trait]
+impl<C: SubscriptionClientT + Send + Sync> SubscriptionClientT for RetryClient<C> {
+    async fn subscribe<'a, Notif, Params>(
+        &self,
+        subscribe_method: &'a str,
+        params: Params,
+        unsubscribe_method: &'a str,
+    ) -> Result<Subscription<Notif>, jsonrpsee::core::Error>
+    where
+        Params: ToRpcParams + Send,
+        Notif: DeserializeOwned,
+    {
+        self.inner
+            .subscribe(subscribe_method, params, unsubscribe_method)
+            .await
+    }
+
+    async fn subscribe_to_method<'a, Notif>(
+        &self,
+        method: &'a str,
+    ) -> Result<Subscription<Notif>, jsonrpsee::core::Error>
+    where
+        Notif: DeserializeOwned,
+    {
+        self.inner.subscribe_to_method(method).await
+    }
+}
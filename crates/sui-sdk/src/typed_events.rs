@@ -0,0 +1,264 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+use std::task::{Context, Poll};
+
+use futures::StreamExt;
+use futures_core::Stream;
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::CompiledModule;
+use move_bytecode_utils::module_cache::GetModule;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::{ModuleId, StructTag, TypeTag};
+use move_core_types::value::{MoveStruct, MoveStructLayout};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use sui_json_rpc::api::ReadApiClient;
+use sui_json_rpc_types::{
+    type_and_fields_from_move_struct, SuiEvent, SuiObjectDataOptions, SuiRawData,
+};
+use sui_types::base_types::ObjectID;
+use sui_types::object::{MoveObject, ObjectFormatOptions};
+
+use crate::error::{Error, SuiRpcResult};
+use crate::RpcClient;
+
+/// Client side cache of the Move modules and struct layouts needed to decode event contents.
+///
+/// Packages are fetched from the fullnode the first time one of their types is decoded, together
+/// with their transitive dependencies, resolved through the package linkage table. Entries are
+/// dropped when decoding fails or a package is upgraded, so that they are fetched again.
+pub struct EventLayoutCache {
+    api: Arc<RpcClient>,
+    modules: RwLock<BTreeMap<ModuleId, Arc<CompiledModule>>>,
+    /// Address the modules are registered under, to the ID of the package they were fetched from.
+    packages: RwLock<BTreeMap<AccountAddress, ObjectID>>,
+    layouts: RwLock<HashMap<StructTag, MoveStructLayout>>,
+}
+
+impl EventLayoutCache {
+    pub(crate) fn new(api: Arc<RpcClient>) -> Self {
+        Self {
+            api,
+            modules: Default::default(),
+            packages: Default::default(),
+            layouts: Default::default(),
+        }
+    }
+
+    /// Layout of the given struct type, fetching the packages it depends on if needed.
+    pub async fn layout(&self, type_: &StructTag) -> SuiRpcResult<MoveStructLayout> {
+        if let Some(layout) = self.layouts.read().unwrap().get(type_) {
+            return Ok(layout.clone());
+        }
+        self.load_packages(type_).await?;
+        let layout = MoveObject::get_layout_from_struct_tag(
+            type_.clone(),
+            ObjectFormatOptions::default(),
+            self,
+        )
+        .map_err(|e| Error::DataError(e.to_string()))?;
+        self.layouts
+            .write()
+            .unwrap()
+            .insert(type_.clone(), layout.clone());
+        Ok(layout)
+    }
+
+    /// Decode the BCS contents of an event into the same json representation as the
+    /// `parsedJson` field returned by the fullnode.
+    pub async fn decode_json(&self, type_: &StructTag, contents: &[u8]) -> SuiRpcResult<Value> {
+        match self.try_decode_json(type_, contents).await {
+            Ok(value) => Ok(value),
+            Err(_) => {
+                // The cached modules may predate an upgrade of one of the packages, refetch them.
+                for address in struct_tag_addresses(type_) {
+                    self.invalidate(address.into());
+                }
+                self.try_decode_json(type_, contents).await
+            }
+        }
+    }
+
+    /// Decode the BCS contents of an event into a user provided type, deserialized from the
+    /// json representation of the event.
+    pub async fn decode<T: DeserializeOwned>(
+        &self,
+        type_: &StructTag,
+        contents: &[u8],
+    ) -> SuiRpcResult<T> {
+        let value = self.decode_json(type_, contents).await?;
+        serde_json::from_value(value).map_err(|e| Error::DataError(e.to_string()))
+    }
+
+    /// Drop the modules fetched from, or registered under the address of, the given package,
+    /// together with all cached layouts.
+    pub fn invalidate(&self, package: ObjectID) {
+        let mut packages = self.packages.write().unwrap();
+        let addresses = packages
+            .iter()
+            .filter(|(address, id)| **id == package || ObjectID::from(**address) == package)
+            .map(|(address, _)| *address)
+            .collect::<Vec<_>>();
+        if addresses.is_empty() {
+            return;
+        }
+        for address in &addresses {
+            packages.remove(address);
+        }
+        self.modules
+            .write()
+            .unwrap()
+            .retain(|id, _| !addresses.contains(id.address()));
+        self.layouts.write().unwrap().clear();
+    }
+
+    async fn try_decode_json(&self, type_: &StructTag, contents: &[u8]) -> SuiRpcResult<Value> {
+        let layout = self.layout(type_).await?;
+        let move_struct = MoveStruct::simple_deserialize(contents, &layout)
+            .map_err(|e| Error::DataError(e.to_string()))?;
+        let (_, fields) = type_and_fields_from_move_struct(type_, move_struct);
+        Ok(fields.to_json_value())
+    }
+
+    async fn load_packages(&self, type_: &StructTag) -> SuiRpcResult<()> {
+        let mut pending = struct_tag_addresses(type_)
+            .into_iter()
+            .map(|address| (address, ObjectID::from(address)))
+            .collect::<Vec<_>>();
+
+        while let Some((address, package_id)) = pending.pop() {
+            if self.packages.read().unwrap().contains_key(&address) {
+                continue;
+            }
+            let response = self
+                .api
+                .http
+                .get_object(package_id, Some(SuiObjectDataOptions::new().with_bcs()))
+                .await?;
+            let package = match response.into_object().ok().and_then(|data| data.bcs) {
+                Some(SuiRawData::Package(package)) => package,
+                _ => {
+                    return Err(Error::DataError(format!(
+                        "Cannot find Move package {package_id}"
+                    )))
+                }
+            };
+
+            let mut packages = self.packages.write().unwrap();
+            let mut modules = self.modules.write().unwrap();
+            let mut self_addresses = vec![];
+            for bytes in package.module_map.values() {
+                let module = Arc::new(
+                    CompiledModule::deserialize(bytes)
+                        .map_err(|e| Error::DataError(e.to_string()))?,
+                );
+                // Modules refer to their dependencies by the ID of their original version, the
+                // linkage table tells which version of the dependency they were published with.
+                for dependency in module.immediate_dependencies() {
+                    let original = ObjectID::from(*dependency.address());
+                    let upgraded = package
+                        .linkage_table
+                        .get(&original)
+                        .map_or(original, |info| info.upgraded_id);
+                    pending.push((*dependency.address(), upgraded));
+                }
+                // Types are referred to by the ID of the package defining them, which differs
+                // from the module address for types introduced by an upgrade.
+                let self_id = module.self_id();
+                modules.insert(
+                    ModuleId::new(address, self_id.name().to_owned()),
+                    module.clone(),
+                );
+                if !packages.contains_key(self_id.address()) {
+                    self_addresses.push(*self_id.address());
+                    modules.insert(self_id, module);
+                }
+            }
+            packages.insert(address, package_id);
+            for self_address in self_addresses {
+                packages.entry(self_address).or_insert(package_id);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl GetModule for EventLayoutCache {
+    type Error = Error;
+    type Item = Arc<CompiledModule>;
+
+    fn get_module_by_id(&self, id: &ModuleId) -> Result<Option<Self::Item>, Self::Error> {
+        Ok(self.modules.read().unwrap().get(id).cloned())
+    }
+}
+
+fn struct_tag_addresses(type_: &StructTag) -> Vec<AccountAddress> {
+    let mut addresses = vec![type_.address];
+    for type_param in &type_.type_params {
+        type_tag_addresses(type_param, &mut addresses);
+    }
+    addresses.sort();
+    addresses.dedup();
+    addresses
+}
+
+fn type_tag_addresses(type_: &TypeTag, addresses: &mut Vec<AccountAddress>) {
+    match type_ {
+        TypeTag::Struct(tag) => addresses.extend(struct_tag_addresses(tag)),
+        TypeTag::Vector(inner) => type_tag_addresses(inner, addresses),
+        _ => {}
+    }
+}
+
+/// An event together with its contents decoded into `T`.
+#[derive(Clone, Debug)]
+pub struct TypedEvent<T> {
+    pub event: SuiEvent,
+    pub data: T,
+}
+
+/// Stream of the events emitted by a package, decoded with the layouts of an
+/// [EventLayoutCache]. Use `T = serde_json::Value` to get the json representation of the events.
+pub struct TypedEventStream<T> {
+    inner: Pin<Box<dyn Stream<Item = SuiRpcResult<TypedEvent<T>>> + Send>>,
+}
+
+impl<T: DeserializeOwned + Send + 'static> TypedEventStream<T> {
+    pub(crate) fn new(
+        events: impl Stream<Item = SuiRpcResult<SuiEvent>> + Send + 'static,
+        package: ObjectID,
+        cache: Arc<EventLayoutCache>,
+    ) -> Self {
+        let mut seen_packages = HashSet::from([package]);
+        let inner = events.then(move |event| {
+            let cache = cache.clone();
+            // Events of an upgraded package are emitted with the ID of the new version.
+            let upgraded = matches!(&event, Ok(event) if seen_packages.insert(event.package_id));
+            async move {
+                let event = event?;
+                if upgraded {
+                    cache.invalidate(event.package_id);
+                    cache.invalidate(package);
+                }
+                let data = cache.decode(&event.type_, &event.bcs).await?;
+                Ok(TypedEvent { event, data })
+            }
+        });
+        Self {
+            inner: Box::pin(inner),
+        }
+    }
+}
+
+impl<T> Stream for TypedEventStream<T> {
+    type Item = SuiRpcResult<TypedEvent<T>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
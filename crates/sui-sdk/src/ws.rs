@@ -0,0 +1,127 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use jsonrpsee::core::client::{BatchResponse, ClientT, Subscription, SubscriptionClientT};
+use jsonrpsee::core::params::BatchRequestBuilder;
+use jsonrpsee::core::traits::ToRpcParams;
+use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
+use serde::de::DeserializeOwned;
+use tokio::sync::RwLock;
+
+use crate::metrics::RequestMetrics;
+
+/// Websocket client reconnecting to the server when the connection was lost. Subscriptions of
+/// the lost connection end, and must be subscribed again by the caller.
+#[derive(Debug)]
+pub(crate) struct ReconnectingWsClient {
+    url: String,
+    builder: WsClientBuilder,
+    client: RwLock<Arc<WsClient>>,
+    metrics: RequestMetrics,
+}
+
+impl ReconnectingWsClient {
+    pub(crate) fn new(
+        url: String,
+        builder: WsClientBuilder,
+        client: WsClient,
+        metrics: RequestMetrics,
+    ) -> Self {
+        Self {
+            url,
+            builder,
+            client: RwLock::new(Arc::new(client)),
+            metrics,
+        }
+    }
+
+    async fn client(&self) -> Result<Arc<WsClient>, jsonrpsee::core::Error> {
+        let client = self.client.read().await.clone();
+        if client.is_connected() {
+            return Ok(client);
+        }
+        let mut client = self.client.write().await;
+        // Another request may have reconnected while waiting for the lock.
+        if !client.is_connected() {
+            *client = Arc::new(self.builder.clone().build(&self.url).await?);
+            self.metrics.ws_reconnected();
+        }
+        Ok(client.clone())
+    }
+}
+
+#[async_trait]
+impl ClientT for ReconnectingWsClient {
+    async fn notification<Params>(
+        &self,
+        method: &str,
+        params: Params,
+    ) -> Result<(), jsonrpsee::core::Error>
+    where
+        Params: ToRpcParams + Send,
+    {
+        self.client().await?.notification(method, params).await
+    }
+
+    async fn request<R, Params>(
+        &self,
+        method: &str,
+        params: Params,
+    ) -> Result<R, jsonrpsee::core::Error>
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send,
+    {
+        self.metrics
+            .observe(method, async {
+                self.client().await?.request(method, params).await
+            })
+            .await
+    }
+
+    async fn batch_request<'a, R>(
+        &self,
+        batch: BatchRequestBuilder<'a>,
+    ) -> Result<BatchResponse<'a, R>, jsonrpsee::core::Error>
+    where
+        R: DeserializeOwned + std::fmt::Debug + 'a,
+    {
+        self.client().await?.batch_request(batch).await
+    }
+}
+
+#[async_trait]
+impl SubscriptionClientT for ReconnectingWsClient {
+    async fn subscribe<'a, Notif, Params>(
+        &self,
+        subscribe_method: &'a str,
+        params: Params,
+        unsubscribe_method: &'a str,
+    ) -> Result<Subscription<Notif>, jsonrpsee::core::Error>
+    where
+        Params: ToRpcParams + Send,
+        Notif: DeserializeOwned,
+    {
+        self.metrics
+            .observe(subscribe_method, async {
+                self.client()
+                    .await?
+                    .subscribe(subscribe_method, params, unsubscribe_method)
+                    .await
+            })
+            .await
+    }
+
+    async fn subscribe_to_method<'a, Notif>(
+        &self,
+        method: &'a str,
+    ) -> Result<Subscription<Notif>, jsonrpsee::core::Error>
+    where
+        Notif: DeserializeOwned,
+    {
+        self.client().await?.subscribe_to_method(method).await
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use sui_config::genesis_config::{DEFAULT_GAS_AMOUNT, DEFAULT_NUMBER_OF_OBJECT_PER_ACCOUNT};
+use sui_config::SUI_KEYSTORE_FILENAME;
+use sui_keys::keystore::{FileBasedKeystore, Keystore};
+use sui_sdk::coin_manager::{CoinManager, CoinManagerConfig};
+use sui_sdk::{SuiClientBuilder, SUI_COIN_TYPE};
+use test_utils::network::TestClusterBuilder;
+
+#[tokio::test]
+async fn test_consolidate_coins() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let client = SuiClientBuilder::default()
+        .build(test_cluster.rpc_url())
+        .await?;
+    let keystore = Keystore::from(FileBasedKeystore::new(
+        &test_cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME),
+    )?);
+
+    let config = CoinManagerConfig {
+        coin_count_threshold: DEFAULT_NUMBER_OF_OBJECT_PER_ACCOUNT - 1,
+        max_inputs_per_transaction: 3,
+        ..Default::default()
+    };
+    let manager = CoinManager::new(client.clone(), address, keystore, config)?;
+    assert_eq!(
+        manager.fragmented_coin_types().await?,
+        vec![(
+            SUI_COIN_TYPE.to_string(),
+            DEFAULT_NUMBER_OF_OBJECT_PER_ACCOUNT
+        )]
+    );
+
+    // 5 coins are merged into 3, then into 1, by two transactions.
+    let reports = manager.consolidate().await?;
+    assert_eq!(reports.len(), 1);
+    assert_eq!(
+        reports[0].coin_count_before,
+        DEFAULT_NUMBER_OF_OBJECT_PER_ACCOUNT
+    );
+    assert_eq!(reports[0].coin_count_after, 1);
+    assert_eq!(reports[0].transactions.len(), 2);
+
+    // At most the gas budgets were spent.
+    let balance = client
+        .coin_read_api()
+        .get_balance(address, Some(SUI_COIN_TYPE.to_string()))
+        .await?;
+    assert_eq!(balance.coin_object_count, 1);
+    let initial_balance = DEFAULT_NUMBER_OF_OBJECT_PER_ACCOUNT as u128 * DEFAULT_GAS_AMOUNT as u128;
+    assert!(balance.total_balance > initial_balance - 2 * 50_000_000);
+
+    // Nothing is left to consolidate by the background task.
+    assert!(manager.fragmented_coin_types().await?.is_empty());
+    let manager = CoinManager::new(
+        client,
+        address,
+        Keystore::from(FileBasedKeystore::new(
+            &test_cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME),
+        )?),
+        CoinManagerConfig {
+            interval: Duration::from_millis(100),
+            ..Default::default()
+        },
+    )?;
+    let mut handle = manager.spawn();
+    assert!(handle.next_report().await.unwrap()?.is_empty());
+    handle.shutdown().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_coin_manager_config() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await?;
+    let client = SuiClientBuilder::default()
+        .build(test_cluster.rpc_url())
+        .await?;
+    let keystore_path = test_cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
+
+    // Coins can only be consolidated for addresses of the keystore.
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    let unknown = sui_types::base_types::SuiAddress::random_for_testing_only();
+    assert!(CoinManager::new(client.clone(), unknown, keystore, Default::default()).is_err());
+
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    let config = CoinManagerConfig {
+        max_inputs_per_transaction: 1,
+        ..Default::default()
+    };
+    assert!(CoinManager::new(client, test_cluster.get_address_0(), keystore, config).is_err());
+    Ok(())
+}
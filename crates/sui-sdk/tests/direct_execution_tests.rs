@@ -0,0 +1,94 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use shared_crypto::intent::Intent;
+use sui_config::SUI_KEYSTORE_FILENAME;
+use sui_json_rpc_types::{SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponseOptions};
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
+use sui_sdk::direct_execution::{DirectExecutionConfig, DirectExecutionResponse, DirectExecutor};
+use sui_sdk::{SuiClientBuilder, SUI_COIN_TYPE};
+use sui_types::base_types::SuiAddress;
+use sui_types::messages::{ExecutionStatus, Transaction, TransactionData, TransactionEffectsAPI};
+use test_utils::network::TestClusterBuilder;
+
+#[tokio::test]
+async fn test_direct_execution() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let client = SuiClientBuilder::default()
+        .build(test_cluster.rpc_url())
+        .await?;
+    let keystore_path = test_cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    let coins = client
+        .coin_read_api()
+        .get_coins(address, Some(SUI_COIN_TYPE.to_string()), None, None)
+        .await?
+        .data;
+    let gas_price = client.read_api().get_reference_gas_price().await?;
+
+    let executor = DirectExecutor::new(client.clone(), DirectExecutionConfig::default()).await?;
+    assert_eq!(executor.epoch(), 0);
+
+    let tx = TransactionData::new_transfer_sui(
+        SuiAddress::random_for_testing_only(),
+        address,
+        Some(1000),
+        coins[0].object_ref(),
+        50_000_000,
+        gas_price,
+    );
+    let signature = keystore.sign_secure(&address, &tx, Intent::sui_transaction())?;
+    let tx = Transaction::from_data(tx, Intent::sui_transaction(), vec![signature]).verify()?;
+    let response = executor
+        .execute_transaction_block(tx.clone(), SuiTransactionBlockResponseOptions::new())
+        .await?;
+    let DirectExecutionResponse::Validators {
+        certificate,
+        effects,
+        ..
+    } = response else {
+        panic!("Expected the transaction to be executed by the validators: {response:?}");
+    };
+    assert_eq!(certificate.digest(), tx.digest());
+    assert_eq!(effects.data().transaction_digest(), tx.digest());
+    assert_eq!(effects.data().status(), &ExecutionStatus::Success);
+
+    // Executing the transaction again returns the same effects.
+    let (_, effects_again, _) = executor.execute_by_validators(&tx).await?;
+    assert_eq!(effects_again.digest(), effects.digest());
+
+    // The fullnode executes the transaction when the requests to the validators time out.
+    let executor = DirectExecutor::new(
+        client,
+        DirectExecutionConfig {
+            request_timeout: Duration::from_nanos(1),
+            ..Default::default()
+        },
+    )
+    .await?;
+    let tx = TransactionData::new_transfer_sui(
+        SuiAddress::random_for_testing_only(),
+        address,
+        Some(1000),
+        coins[1].object_ref(),
+        50_000_000,
+        gas_price,
+    );
+    let signature = keystore.sign_secure(&address, &tx, Intent::sui_transaction())?;
+    let tx = Transaction::from_data(tx, Intent::sui_transaction(), vec![signature]).verify()?;
+    let response = executor
+        .execute_transaction_block(
+            tx.clone(),
+            SuiTransactionBlockResponseOptions::new().with_effects(),
+        )
+        .await?;
+    let DirectExecutionResponse::Fullnode { response, .. } = response else {
+        panic!("Expected the transaction to be executed by the fullnode: {response:?}");
+    };
+    assert_eq!(&response.digest, tx.digest());
+    assert!(response.effects.unwrap().status().is_ok());
+    Ok(())
+}
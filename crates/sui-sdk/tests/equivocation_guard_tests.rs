@@ -0,0 +1,96 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use sui_sdk::equivocation_guard::{EquivocationGuard, GuardMode};
+use sui_sdk::error::Error;
+use sui_types::base_types::{random_object_ref, SuiAddress, TransactionDigest};
+use sui_types::messages::TransactionData;
+
+fn transfer(
+    object: sui_types::base_types::ObjectRef,
+    gas: sui_types::base_types::ObjectRef,
+) -> TransactionData {
+    TransactionData::new_transfer(
+        SuiAddress::random_for_testing_only(),
+        object,
+        SuiAddress::random_for_testing_only(),
+        gas,
+        1_000_000,
+        1000,
+    )
+}
+
+#[tokio::test]
+async fn test_reject_in_flight_objects() -> Result<(), anyhow::Error> {
+    let guard = Arc::new(EquivocationGuard::new(GuardMode::Reject));
+    let gas = random_object_ref();
+    let (first, second) = (TransactionDigest::random(), TransactionDigest::random());
+
+    let reservation = guard
+        .reserve(first, &transfer(random_object_ref(), gas))
+        .await?;
+    assert_eq!(guard.in_flight().get(&gas.0), Some(&first));
+
+    // A second transaction using the same gas coin is rejected.
+    let err = guard
+        .reserve(second, &transfer(random_object_ref(), gas))
+        .await
+        .err()
+        .unwrap();
+    assert!(
+        matches!(err, Error::ObjectInFlight { object_id, digest } if object_id == gas.0 && digest == first)
+    );
+    // Transactions using other objects are not.
+    drop(
+        guard
+            .reserve(second, &transfer(random_object_ref(), random_object_ref()))
+            .await?,
+    );
+
+    // Once the first transaction is finalized, its objects can be reused.
+    drop(reservation);
+    assert!(guard.in_flight().is_empty());
+    guard
+        .reserve(second, &transfer(random_object_ref(), gas))
+        .await?
+        .keep();
+
+    // The objects of transactions whose outcome is unknown stay reserved until released.
+    assert_eq!(guard.in_flight().len(), 2);
+    guard.release(&second);
+    assert!(guard.in_flight().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_queue_in_flight_objects() -> Result<(), anyhow::Error> {
+    let guard = Arc::new(EquivocationGuard::new(GuardMode::Queue));
+    let object = random_object_ref();
+    let (first, second) = (TransactionDigest::random(), TransactionDigest::random());
+
+    let reservation = guard
+        .reserve(first, &transfer(object, random_object_ref()))
+        .await?;
+    let queued = {
+        let guard = guard.clone();
+        tokio::spawn(async move {
+            guard
+                .reserve(second, &transfer(object, random_object_ref()))
+                .await
+                .map(|reservation| reservation.keep())
+        })
+    };
+
+    // The second transaction waits for the first one to be finalized.
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(!queued.is_finished());
+    assert_eq!(guard.in_flight().get(&object.0), Some(&first));
+
+    drop(reservation);
+    queued.await??;
+    assert_eq!(guard.in_flight().get(&object.0), Some(&second));
+    Ok(())
+}
@@ -0,0 +1,102 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use shared_crypto::intent::Intent;
+use sui_config::SUI_KEYSTORE_FILENAME;
+use sui_json_rpc_types::SuiTransactionBlockResponseOptions;
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
+use sui_sdk::gas_pool::{GasPool, GasPoolConfig};
+use sui_sdk::{SuiClientBuilder, SUI_COIN_TYPE};
+use sui_types::base_types::SuiAddress;
+use sui_types::messages::{ExecuteTransactionRequestType, Transaction, TransactionData};
+use test_utils::network::TestClusterBuilder;
+
+#[tokio::test]
+async fn test_gas_pool() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let client = SuiClientBuilder::default()
+        .build(test_cluster.rpc_url())
+        .await?;
+    let keystore_path = test_cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    let coins = client
+        .coin_read_api()
+        .get_coins(address, Some(SUI_COIN_TYPE.to_string()), None, None)
+        .await?
+        .data;
+
+    let pool = GasPool::new(
+        client.clone(),
+        address,
+        Keystore::from(FileBasedKeystore::new(&keystore_path)?),
+        GasPoolConfig {
+            pool_size: 3,
+            idle_timeout: Duration::ZERO,
+            ..Default::default()
+        },
+    );
+    // Coins cannot be leased before the pool is funded.
+    assert!(pool.lease().await.is_err());
+    assert_eq!(pool.fund(coins[0].object_ref()).await?.len(), 3);
+
+    let leases = vec![
+        pool.lease().await?,
+        pool.lease().await?,
+        pool.lease().await?,
+    ];
+    assert_eq!(pool.leased(), 3);
+    assert_eq!(pool.available(), 0);
+    // All the coins are leased, the next lease waits for one to be returned.
+    assert!(
+        tokio::time::timeout(Duration::from_millis(100), pool.lease())
+            .await
+            .is_err()
+    );
+
+    let mut leases = leases.into_iter();
+    let lease = leases.next().unwrap();
+    let gas = lease.gas();
+    let gas_price = client.read_api().get_reference_gas_price().await?;
+    let tx = TransactionData::new_transfer_sui(
+        SuiAddress::random_for_testing_only(),
+        address,
+        Some(1000),
+        gas,
+        50_000_000,
+        gas_price,
+    );
+    let signature = keystore.sign_secure(&address, &tx, Intent::sui_transaction())?;
+    let response = client
+        .quorum_driver()
+        .execute_transaction_block(
+            Transaction::from_data(tx, Intent::sui_transaction(), vec![signature]).verify()?,
+            SuiTransactionBlockResponseOptions::new().with_effects(),
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await?;
+    lease.return_with_effects(response.effects.as_ref().unwrap());
+
+    // The coin is leased again at its new version.
+    let lease = pool.lease().await?;
+    assert_eq!(lease.gas().0, gas.0);
+    assert!(lease.gas().1 > gas.1);
+    lease.return_unused();
+
+    // Leases dropped without effects are fetched again.
+    drop(leases);
+    assert_eq!(pool.available(), 3);
+    assert_eq!(pool.leased(), 0);
+
+    // The idle pool is merged back into the funding coin.
+    assert_eq!(pool.merge_if_idle().await?.len(), 1);
+    assert_eq!(pool.available(), 0);
+    let balance = client
+        .coin_read_api()
+        .get_balance(address, Some(SUI_COIN_TYPE.to_string()))
+        .await?;
+    assert_eq!(balance.coin_object_count, coins.len());
+    Ok(())
+}
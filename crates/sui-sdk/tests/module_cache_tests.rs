@@ -0,0 +1,48 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_json_rpc_types::ObjectChange;
+use sui_sdk::SuiClientBuilder;
+use sui_types::base_types::{ObjectDigest, SequenceNumber};
+use sui_types::SUI_FRAMEWORK_OBJECT_ID;
+use test_utils::network::TestClusterBuilder;
+
+#[tokio::test]
+async fn test_module_cache_invalidation() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await?;
+    let client = SuiClientBuilder::default()
+        .build(test_cluster.rpc_url())
+        .await?;
+    let cache = client.read_api().module_cache();
+    assert_eq!(cache.cached_version(SUI_FRAMEWORK_OBJECT_ID), None);
+
+    let signature = cache
+        .get_function_signature(SUI_FRAMEWORK_OBJECT_ID, "coin", "join")
+        .await?;
+    assert_eq!(signature.parameters.len(), 2);
+    let version = cache.cached_version(SUI_FRAMEWORK_OBJECT_ID).unwrap();
+
+    // The version of a system package is checked again before its modules are reused.
+    cache.get_modules(SUI_FRAMEWORK_OBJECT_ID).await?;
+    assert_eq!(cache.cached_version(SUI_FRAMEWORK_OBJECT_ID), Some(version));
+
+    // Changes at the cached version keep the modules.
+    let published = |version| ObjectChange::Published {
+        package_id: SUI_FRAMEWORK_OBJECT_ID,
+        version,
+        digest: ObjectDigest::random(),
+        modules: vec![],
+    };
+    cache.apply_object_changes(&[published(version)]);
+    assert_eq!(cache.cached_version(SUI_FRAMEWORK_OBJECT_ID), Some(version));
+
+    // The package upgraded to another version is dropped, and fetched again when needed.
+    cache.apply_object_changes(&[published(SequenceNumber::from_u64(version.value() + 1))]);
+    assert_eq!(cache.cached_version(SUI_FRAMEWORK_OBJECT_ID), None);
+    cache.get_modules(SUI_FRAMEWORK_OBJECT_ID).await?;
+    assert_eq!(cache.cached_version(SUI_FRAMEWORK_OBJECT_ID), Some(version));
+
+    cache.invalidate(SUI_FRAMEWORK_OBJECT_ID);
+    assert_eq!(cache.cached_version(SUI_FRAMEWORK_OBJECT_ID), None);
+    Ok(())
+}
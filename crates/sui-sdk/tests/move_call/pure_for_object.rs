@@ -0,0 +1,20 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_sdk::move_call::LazyProgrammableTransaction;
+use sui_types::base_types::SuiAddress;
+use sui_types::{SUI_SYSTEM_PACKAGE_ID, SUI_SYSTEM_STATE_OBJECT_ID};
+
+sui_sdk::move_call! {
+    fn request_add_stake(
+        wrapper: object,
+        stake: object,
+        validator_address: pure<SuiAddress>,
+    ) in SUI_SYSTEM_PACKAGE_ID, sui_system;
+}
+
+fn main() {
+    let mut tx = LazyProgrammableTransaction::new();
+    let address = SuiAddress::ZERO;
+    let _ = request_add_stake(&mut tx, SUI_SYSTEM_STATE_OBJECT_ID, address, address);
+}
@@ -0,0 +1,67 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_sdk::move_call::{Argument, LazyProgrammableTransaction};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::gas_coin::GAS;
+use sui_types::messages::Command;
+use sui_types::{SUI_FRAMEWORK_OBJECT_ID, SUI_SYSTEM_PACKAGE_ID, SUI_SYSTEM_STATE_OBJECT_ID};
+
+sui_sdk::move_call! {
+    fn request_add_stake(
+        wrapper: object,
+        stake: object,
+        validator_address: pure<SuiAddress>,
+    ) in SUI_SYSTEM_PACKAGE_ID, sui_system;
+
+    fn join<T>(self_: object, c: object) in SUI_FRAMEWORK_OBJECT_ID, coin;
+}
+
+#[test]
+fn test_move_call() -> Result<(), anyhow::Error> {
+    let mut tx = LazyProgrammableTransaction::new();
+    let coin = ObjectID::random();
+    let other_coin = ObjectID::random();
+
+    // Objects are given by ID or as the result of a previous command.
+    let joined = join(&mut tx, GAS::type_tag(), coin, other_coin)?;
+    let stake = request_add_stake(
+        &mut tx,
+        SUI_SYSTEM_STATE_OBJECT_ID,
+        Argument::Input(0),
+        SuiAddress::random_for_testing_only(),
+    )?;
+    assert_eq!(Argument::Result(0), joined);
+    assert_eq!(Argument::Result(1), stake);
+
+    let [Command::MoveCall(join_call), Command::MoveCall(stake_call)] = tx.commands() else {
+        panic!("Unexpected commands {:?}", tx.commands());
+    };
+    assert_eq!(SUI_FRAMEWORK_OBJECT_ID, join_call.package);
+    assert_eq!("coin", join_call.module.as_str());
+    assert_eq!("join", join_call.function.as_str());
+    assert_eq!(vec![GAS::type_tag()], join_call.type_arguments);
+    assert_eq!(
+        vec![Argument::Input(0), Argument::Input(1)],
+        join_call.arguments
+    );
+
+    assert_eq!(SUI_SYSTEM_PACKAGE_ID, stake_call.package);
+    assert_eq!("sui_system", stake_call.module.as_str());
+    assert_eq!("request_add_stake", stake_call.function.as_str());
+    assert!(stake_call.type_arguments.is_empty());
+    // The validator address is a new pure input.
+    assert_eq!(
+        vec![Argument::Input(2), Argument::Input(0), Argument::Input(3)],
+        stake_call.arguments
+    );
+    Ok(())
+}
+
+#[test]
+fn test_move_call_compile_errors() {
+    // Calls with the wrong number of arguments, or a pure value given for an object, are
+    // rejected by the compiler.
+    let tests = trybuild::TestCases::new();
+    tests.compile_fail("tests/move_call/*.rs");
+}
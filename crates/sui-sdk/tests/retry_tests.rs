@@ -0,0 +1,123 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use jsonrpsee::core::Error as RpcError;
+use jsonrpsee::types::error::{CallError, CALL_EXECUTION_FAILED_CODE};
+use jsonrpsee::types::ErrorObject;
+use std::time::Duration;
+use sui_json_rpc_types::{SuiRpcErrorCode, SuiRpcErrorData};
+use sui_sdk::retry::{Backoff, RetryPolicy, RetrySafety};
+
+fn backoff_without_jitter() -> Backoff {
+    Backoff {
+        max_attempts: 10,
+        initial_delay: Duration::from_millis(100),
+        max_delay: Duration::from_secs(1),
+        multiplier: 2.0,
+        jitter: 0.0,
+    }
+}
+
+fn error_with_code(code: SuiRpcErrorCode) -> RpcError {
+    RpcError::Call(CallError::Custom(ErrorObject::owned(
+        CALL_EXECUTION_FAILED_CODE,
+        "error",
+        Some(SuiRpcErrorData { code }),
+    )))
+}
+
+#[test]
+fn test_backoff_delay() {
+    let backoff = backoff_without_jitter();
+    let delays: Vec<_> = (1..=6).map(|retry| backoff.delay(retry)).collect();
+    let expected: Vec<_> = [100, 200, 400, 800, 1000, 1000]
+        .into_iter()
+        .map(Duration::from_millis)
+        .collect();
+    assert_eq!(delays, expected);
+    // The exponent saturates instead of overflowing, the delay stays capped.
+    assert_eq!(backoff.delay(u32::MAX), Duration::from_secs(1));
+    assert_eq!(backoff.delay(0), Duration::from_millis(100));
+}
+
+#[test]
+fn test_backoff_jitter() {
+    let backoff = Backoff {
+        jitter: 0.5,
+        ..backoff_without_jitter()
+    };
+    for _ in 0..100 {
+        let delay = backoff.delay(2);
+        assert!(delay >= Duration::from_millis(100), "{delay:?}");
+        assert!(delay <= Duration::from_millis(300), "{delay:?}");
+    }
+
+    // The jitter is clamped to the delay itself.
+    let backoff = Backoff {
+        jitter: 3.0,
+        ..backoff_without_jitter()
+    };
+    for _ in 0..100 {
+        assert!(backoff.delay(1) <= Duration::from_millis(200));
+    }
+}
+
+#[test]
+fn test_retry_policy_backoff() {
+    let execute = "sui_executeTransactionBlock";
+    let policy = RetryPolicy::default().with_method(execute, Backoff::no_retry());
+    assert_eq!(policy.backoff(execute).max_attempts, 1);
+    assert_eq!(
+        policy.backoff("sui_getObject").max_attempts,
+        Backoff::default().max_attempts
+    );
+    assert_eq!(
+        RetryPolicy::no_retry()
+            .backoff("sui_getObject")
+            .max_attempts,
+        1
+    );
+}
+
+#[test]
+fn test_retry_safety() {
+    assert_eq!(
+        RetryPolicy::safety("sui_executeTransactionBlock"),
+        RetrySafety::DigestIdempotent
+    );
+    assert_eq!(RetryPolicy::safety("sui_getObject"), RetrySafety::Safe);
+    assert_eq!(
+        RetryPolicy::safety("sui_dryRunTransactionBlock"),
+        RetrySafety::Safe
+    );
+}
+
+#[test]
+fn test_is_retryable() {
+    // Transient failures of the transport.
+    assert!(RetryPolicy::is_retryable(&RpcError::RequestTimeout));
+    let transport = RpcError::Transport(anyhow::anyhow!("connection reset"));
+    assert!(RetryPolicy::is_retryable(&transport));
+
+    // Errors reported by the node, according to their code.
+    for (code, retryable) in [
+        (SuiRpcErrorCode::ObjectNotFound, true),
+        (SuiRpcErrorCode::ObjectVersionTooHigh, true),
+        (SuiRpcErrorCode::Congestion, true),
+        (SuiRpcErrorCode::ObjectVersionTooOld, false),
+        (SuiRpcErrorCode::InsufficientGas, false),
+        (SuiRpcErrorCode::DeniedByPolicy, false),
+    ] {
+        assert_eq!(
+            RetryPolicy::is_retryable(&error_with_code(code)),
+            retryable,
+            "{code:?}"
+        );
+    }
+
+    // Errors without a code, or of the request itself, are final.
+    let without_code = RpcError::Call(CallError::Failed(anyhow::anyhow!("failed")));
+    assert!(!RetryPolicy::is_retryable(&without_code));
+    let invalid_params = RpcError::Call(CallError::InvalidParams(anyhow::anyhow!("invalid")));
+    assert!(!RetryPolicy::is_retryable(&invalid_params));
+}
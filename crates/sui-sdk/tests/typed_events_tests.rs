@@ -0,0 +1,113 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use futures::StreamExt;
+use serde::Deserialize;
+use sui_json_rpc_types::SuiEvent;
+use sui_sdk::SuiClientBuilder;
+use sui_types::base_types::{SuiAddress, TransactionDigest};
+use sui_types::SUI_SYSTEM_PACKAGE_ID;
+use test_utils::messages::make_staking_transaction_with_wallet_context;
+use test_utils::network::{TestCluster, TestClusterBuilder};
+
+/// The fields of `0x3::validator::StakingRequestEvent` with a fixed json representation.
+#[derive(Deserialize)]
+struct StakingRequestEvent {
+    validator_address: SuiAddress,
+    staker_address: SuiAddress,
+}
+
+/// Stakes with the first validator, and returns the address of the validator and the digest of
+/// the transaction.
+async fn stake(
+    test_cluster: &mut TestCluster,
+) -> Result<(SuiAddress, TransactionDigest), anyhow::Error> {
+    let validator_address = test_cluster
+        .sui_client()
+        .governance_api()
+        .get_latest_sui_system_state()
+        .await?
+        .active_validators[0]
+        .sui_address;
+    let tx =
+        make_staking_transaction_with_wallet_context(test_cluster.wallet_mut(), validator_address)
+            .await;
+    let response = test_cluster.execute_transaction(tx).await?;
+    assert!(response.status_ok().unwrap());
+    Ok((validator_address, response.digest))
+}
+
+fn is_staking_request(event: &SuiEvent) -> bool {
+    event.type_.name.as_str() == "StakingRequestEvent"
+}
+
+#[tokio::test]
+async fn test_event_layout_cache() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new()
+        .enable_fullnode_events()
+        .build()
+        .await?;
+    let (validator_address, digest) = stake(&mut test_cluster).await?;
+    let client = SuiClientBuilder::default()
+        .build(test_cluster.rpc_url())
+        .await?;
+    let events = client.event_api().get_events(digest).await?;
+    let event = events.into_iter().find(is_staking_request).unwrap();
+    let cache = client.event_api().layout_cache();
+
+    // The contents are decoded into the json representation of the fullnode.
+    let layout = cache.layout(&event.type_).await?;
+    assert_eq!(layout.into_fields().len(), 5);
+    let json = cache.decode_json(&event.type_, &event.bcs).await?;
+    assert_eq!(json, event.parsed_json);
+
+    // And into user types.
+    let decoded: StakingRequestEvent = cache.decode(&event.type_, &event.bcs).await?;
+    assert_eq!(decoded.validator_address, validator_address);
+    assert_eq!(decoded.staker_address, event.sender);
+
+    // The packages are fetched again after an invalidation.
+    cache.invalidate(SUI_SYSTEM_PACKAGE_ID);
+    let json = cache.decode_json(&event.type_, &event.bcs).await?;
+    assert_eq!(json, event.parsed_json);
+
+    // Contents not matching the layout fail to decode.
+    assert!(cache
+        .decode_json(&event.type_, &event.bcs[..event.bcs.len() - 1])
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_typed_event_stream() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new()
+        .enable_fullnode_events()
+        .build()
+        .await?;
+    let client = SuiClientBuilder::default()
+        .ws_url(&test_cluster.fullnode_handle.ws_url)
+        .build(test_cluster.rpc_url())
+        .await?;
+    let mut events = client
+        .event_api()
+        .subscribe_typed_events::<serde_json::Value>(SUI_SYSTEM_PACKAGE_ID)
+        .await?;
+
+    let (validator_address, _) = stake(&mut test_cluster).await?;
+    let event = tokio::time::timeout(Duration::from_secs(30), async {
+        loop {
+            let event = events.next().await.unwrap().unwrap();
+            if is_staking_request(&event.event) {
+                return event;
+            }
+        }
+    })
+    .await?;
+    assert_eq!(event.data, event.event.parsed_json);
+    let decoded: StakingRequestEvent = serde_json::from_value(event.data)?;
+    assert_eq!(decoded.validator_address, validator_address);
+    Ok(())
+}
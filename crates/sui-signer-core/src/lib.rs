@@ -0,0 +1,132 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The signing surface of Sui user transactions, without std or allocation, for signers such as
+//! hardware wallets and TEEs. Signers either receive the BCS bytes of a `TransactionData` from a
+//! host, or build programmable transactions themselves with the encoder of [transaction].
+//!
+//! Signatures commit to the Blake2b-256 digest of the BCS serialization of an intent message,
+//! that is the 3 bytes of the intent followed by the serialized value, and are serialized as
+//! `flag || signature || public key`, as `sui_types::crypto::Signature`.
+
+#![no_std]
+
+pub mod transaction;
+
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+use ed25519_consensus::SigningKey;
+use transaction::{write_uleb128, BcsWriter, Encode, TransactionData};
+
+pub const INTENT_PREFIX_LENGTH: usize = 3;
+
+/// Intent of the signature of a user on a transaction data: scope `TransactionData`, version
+/// `V0` and app ID `Sui`, see `shared_crypto::intent::Intent`.
+pub const TRANSACTION_DATA_INTENT: [u8; INTENT_PREFIX_LENGTH] = [0, 0, 0];
+
+/// Intent of the signature of a user on a personal message.
+pub const PERSONAL_MESSAGE_INTENT: [u8; INTENT_PREFIX_LENGTH] = [3, 0, 0];
+
+pub const ED25519_FLAG: u8 = 0x00;
+
+pub const ED25519_PUBLIC_KEY_LENGTH: usize = 32;
+
+pub const ED25519_SIGNATURE_LENGTH: usize = 64;
+
+/// Length of a serialized Ed25519 signature, `flag || signature || public key`.
+pub const ED25519_SUI_SIGNATURE_LENGTH: usize =
+    1 + ED25519_SIGNATURE_LENGTH + ED25519_PUBLIC_KEY_LENGTH;
+
+type Blake2b256 = Blake2b<U32>;
+
+impl BcsWriter for Blake2b256 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+}
+
+/// Digest signed for a value of BCS serialization `value`, under `intent`.
+pub fn intent_message_digest(intent: [u8; INTENT_PREFIX_LENGTH], value: &[u8]) -> [u8; 32] {
+    let mut hasher = Blake2b256::new();
+    hasher.update(intent);
+    hasher.update(value);
+    hasher.finalize().into()
+}
+
+/// Digest signed for the transaction data of BCS serialization `tx_data`.
+pub fn transaction_data_digest(tx_data: &[u8]) -> [u8; 32] {
+    intent_message_digest(TRANSACTION_DATA_INTENT, tx_data)
+}
+
+/// Digest signed for the transaction data `tx_data`, without serializing it in memory.
+pub fn encoded_transaction_data_digest(tx_data: &TransactionData) -> [u8; 32] {
+    let mut hasher = Blake2b256::new();
+    hasher.update(TRANSACTION_DATA_INTENT);
+    tx_data.encode(&mut hasher);
+    hasher.finalize().into()
+}
+
+/// Digest signed for the personal message `message`, which is serialized as a vector of bytes.
+pub fn personal_message_digest(message: &[u8]) -> [u8; 32] {
+    let mut hasher = Blake2b256::new();
+    hasher.update(PERSONAL_MESSAGE_INTENT);
+    write_uleb128(&mut hasher, message.len());
+    hasher.update(message);
+    hasher.finalize().into()
+}
+
+/// Sui address of the Ed25519 key `public_key`: the Blake2b-256 digest of `flag || public key`.
+pub fn ed25519_address(public_key: &[u8; ED25519_PUBLIC_KEY_LENGTH]) -> [u8; 32] {
+    let mut hasher = Blake2b256::new();
+    hasher.update([ED25519_FLAG]);
+    hasher.update(public_key);
+    hasher.finalize().into()
+}
+
+/// An Ed25519 private key signing on behalf of a Sui address.
+pub struct Ed25519Signer {
+    key: SigningKey,
+}
+
+impl Ed25519Signer {
+    pub fn from_bytes(private_key: [u8; 32]) -> Self {
+        Self {
+            key: SigningKey::from(private_key),
+        }
+    }
+
+    pub fn public_key(&self) -> [u8; ED25519_PUBLIC_KEY_LENGTH] {
+        self.key.verification_key().to_bytes()
+    }
+
+    pub fn address(&self) -> [u8; 32] {
+        ed25519_address(&self.public_key())
+    }
+
+    /// Signs the transaction data of BCS serialization `tx_data`.
+    pub fn sign_transaction_data(&self, tx_data: &[u8]) -> [u8; ED25519_SUI_SIGNATURE_LENGTH] {
+        self.sign_digest(&transaction_data_digest(tx_data))
+    }
+
+    /// Signs the transaction data `tx_data` built by the signer.
+    pub fn sign_transaction(
+        &self,
+        tx_data: &TransactionData,
+    ) -> [u8; ED25519_SUI_SIGNATURE_LENGTH] {
+        self.sign_digest(&encoded_transaction_data_digest(tx_data))
+    }
+
+    pub fn sign_personal_message(&self, message: &[u8]) -> [u8; ED25519_SUI_SIGNATURE_LENGTH] {
+        self.sign_digest(&personal_message_digest(message))
+    }
+
+    /// Signs a digest computed by [intent_message_digest].
+    pub fn sign_digest(&self, digest: &[u8; 32]) -> [u8; ED25519_SUI_SIGNATURE_LENGTH] {
+        let signature = self.key.sign(digest).to_bytes();
+        let mut bytes = [0; ED25519_SUI_SIGNATURE_LENGTH];
+        bytes[0] = ED25519_FLAG;
+        bytes[1..=ED25519_SIGNATURE_LENGTH].copy_from_slice(&signature);
+        bytes[1 + ED25519_SIGNATURE_LENGTH..].copy_from_slice(&self.public_key());
+        bytes
+    }
+}
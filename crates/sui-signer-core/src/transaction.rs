@@ -0,0 +1,418 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! BCS encoding of the transaction data of programmable transactions, with the layout of
+//! `sui_types::messages::TransactionData`, for signers building their transactions themselves.
+//!
+//! Vectors are borrowed slices, so that transactions are encoded without allocation, straight
+//! into the hasher of the signed digest or into a buffer. Only the commands not publishing
+//! packages are supported.
+
+pub const ADDRESS_LENGTH: usize = 32;
+
+pub const DIGEST_LENGTH: usize = 32;
+
+pub type Address = [u8; ADDRESS_LENGTH];
+
+/// Receives the bytes of a BCS serialization.
+pub trait BcsWriter {
+    fn write(&mut self, bytes: &[u8]);
+}
+
+/// Types with a BCS serialization.
+pub trait Encode {
+    fn encode<W: BcsWriter>(&self, writer: &mut W);
+}
+
+/// Writes the BCS serialization of `value` at the start of `buf`, and returns its length, or
+/// `None` if `buf` is too small to hold it.
+pub fn encode_into<T: Encode>(value: &T, buf: &mut [u8]) -> Option<usize> {
+    let mut writer = SliceWriter {
+        buf,
+        len: 0,
+        overflow: false,
+    };
+    value.encode(&mut writer);
+    (!writer.overflow).then_some(writer.len)
+}
+
+struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+    overflow: bool,
+}
+
+impl BcsWriter for SliceWriter<'_> {
+    fn write(&mut self, bytes: &[u8]) {
+        let end = self.len + bytes.len();
+        match self.buf.get_mut(self.len..end) {
+            Some(dest) if !self.overflow => {
+                dest.copy_from_slice(bytes);
+                self.len = end;
+            }
+            _ => self.overflow = true,
+        }
+    }
+}
+
+/// Writes the length of a sequence, or an enum variant index, in ULEB128.
+pub(crate) fn write_uleb128<W: BcsWriter>(writer: &mut W, mut value: usize) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            writer.write(&[byte]);
+            return;
+        }
+        writer.write(&[byte | 0x80]);
+    }
+}
+
+fn write_bytes<W: BcsWriter>(writer: &mut W, bytes: &[u8]) {
+    write_uleb128(writer, bytes.len());
+    writer.write(bytes);
+}
+
+fn write_seq<W: BcsWriter, T: Encode>(writer: &mut W, values: &[T]) {
+    write_uleb128(writer, values.len());
+    for value in values {
+        value.encode(writer);
+    }
+}
+
+impl Encode for u16 {
+    fn encode<W: BcsWriter>(&self, writer: &mut W) {
+        writer.write(&self.to_le_bytes());
+    }
+}
+
+impl Encode for u64 {
+    fn encode<W: BcsWriter>(&self, writer: &mut W) {
+        writer.write(&self.to_le_bytes());
+    }
+}
+
+impl Encode for bool {
+    fn encode<W: BcsWriter>(&self, writer: &mut W) {
+        writer.write(&[*self as u8]);
+    }
+}
+
+impl Encode for Address {
+    fn encode<W: BcsWriter>(&self, writer: &mut W) {
+        writer.write(self);
+    }
+}
+
+impl Encode for str {
+    fn encode<W: BcsWriter>(&self, writer: &mut W) {
+        write_bytes(writer, self.as_bytes());
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn encode<W: BcsWriter>(&self, writer: &mut W) {
+        match self {
+            None => writer.write(&[0]),
+            Some(value) => {
+                writer.write(&[1]);
+                value.encode(writer);
+            }
+        }
+    }
+}
+
+/// `sui_types::base_types::ObjectRef`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ObjectRef {
+    pub object_id: Address,
+    pub version: u64,
+    pub digest: [u8; DIGEST_LENGTH],
+}
+
+impl Encode for ObjectRef {
+    fn encode<W: BcsWriter>(&self, writer: &mut W) {
+        self.object_id.encode(writer);
+        self.version.encode(writer);
+        // Digests are serialized as byte vectors.
+        write_bytes(writer, &self.digest);
+    }
+}
+
+/// `sui_types::messages::ObjectArg`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObjectArg {
+    ImmOrOwnedObject(ObjectRef),
+    SharedObject {
+        id: Address,
+        initial_shared_version: u64,
+        mutable: bool,
+    },
+}
+
+impl Encode for ObjectArg {
+    fn encode<W: BcsWriter>(&self, writer: &mut W) {
+        match self {
+            Self::ImmOrOwnedObject(object_ref) => {
+                write_uleb128(writer, 0);
+                object_ref.encode(writer);
+            }
+            Self::SharedObject {
+                id,
+                initial_shared_version,
+                mutable,
+            } => {
+                write_uleb128(writer, 1);
+                id.encode(writer);
+                initial_shared_version.encode(writer);
+                mutable.encode(writer);
+            }
+        }
+    }
+}
+
+/// `sui_types::messages::CallArg`, where pure arguments are BCS serialized values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallArg<'a> {
+    Pure(&'a [u8]),
+    Object(ObjectArg),
+}
+
+impl Encode for CallArg<'_> {
+    fn encode<W: BcsWriter>(&self, writer: &mut W) {
+        match self {
+            Self::Pure(bytes) => {
+                write_uleb128(writer, 0);
+                write_bytes(writer, bytes);
+            }
+            Self::Object(object_arg) => {
+                write_uleb128(writer, 1);
+                object_arg.encode(writer);
+            }
+        }
+    }
+}
+
+/// `sui_types::messages::Argument`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Argument {
+    GasCoin,
+    Input(u16),
+    Result(u16),
+    NestedResult(u16, u16),
+}
+
+impl Encode for Argument {
+    fn encode<W: BcsWriter>(&self, writer: &mut W) {
+        match self {
+            Self::GasCoin => write_uleb128(writer, 0),
+            Self::Input(input) => {
+                write_uleb128(writer, 1);
+                input.encode(writer);
+            }
+            Self::Result(result) => {
+                write_uleb128(writer, 2);
+                result.encode(writer);
+            }
+            Self::NestedResult(result, nested) => {
+                write_uleb128(writer, 3);
+                result.encode(writer);
+                nested.encode(writer);
+            }
+        }
+    }
+}
+
+/// `move_core_types::language_storage::TypeTag`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TypeTag<'a> {
+    Bool,
+    U8,
+    U64,
+    U128,
+    Address,
+    Signer,
+    Vector(&'a TypeTag<'a>),
+    Struct(&'a StructTag<'a>),
+    U16,
+    U32,
+    U256,
+}
+
+impl Encode for TypeTag<'_> {
+    fn encode<W: BcsWriter>(&self, writer: &mut W) {
+        match self {
+            Self::Bool => write_uleb128(writer, 0),
+            Self::U8 => write_uleb128(writer, 1),
+            Self::U64 => write_uleb128(writer, 2),
+            Self::U128 => write_uleb128(writer, 3),
+            Self::Address => write_uleb128(writer, 4),
+            Self::Signer => write_uleb128(writer, 5),
+            Self::Vector(element) => {
+                write_uleb128(writer, 6);
+                element.encode(writer);
+            }
+            Self::Struct(struct_tag) => {
+                write_uleb128(writer, 7);
+                struct_tag.encode(writer);
+            }
+            Self::U16 => write_uleb128(writer, 8),
+            Self::U32 => write_uleb128(writer, 9),
+            Self::U256 => write_uleb128(writer, 10),
+        }
+    }
+}
+
+/// `move_core_types::language_storage::StructTag`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StructTag<'a> {
+    pub address: Address,
+    pub module: &'a str,
+    pub name: &'a str,
+    pub type_params: &'a [TypeTag<'a>],
+}
+
+impl Encode for StructTag<'_> {
+    fn encode<W: BcsWriter>(&self, writer: &mut W) {
+        self.address.encode(writer);
+        self.module.encode(writer);
+        self.name.encode(writer);
+        write_seq(writer, self.type_params);
+    }
+}
+
+/// `sui_types::messages::ProgrammableMoveCall`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProgrammableMoveCall<'a> {
+    pub package: Address,
+    pub module: &'a str,
+    pub function: &'a str,
+    pub type_arguments: &'a [TypeTag<'a>],
+    pub arguments: &'a [Argument],
+}
+
+impl Encode for ProgrammableMoveCall<'_> {
+    fn encode<W: BcsWriter>(&self, writer: &mut W) {
+        self.package.encode(writer);
+        self.module.encode(writer);
+        self.function.encode(writer);
+        write_seq(writer, self.type_arguments);
+        write_seq(writer, self.arguments);
+    }
+}
+
+/// The commands of `sui_types::messages::Command` not publishing or upgrading packages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command<'a> {
+    MoveCall(ProgrammableMoveCall<'a>),
+    TransferObjects(&'a [Argument], Argument),
+    SplitCoins(Argument, &'a [Argument]),
+    MergeCoins(Argument, &'a [Argument]),
+    MakeMoveVec(Option<TypeTag<'a>>, &'a [Argument]),
+}
+
+impl Encode for Command<'_> {
+    fn encode<W: BcsWriter>(&self, writer: &mut W) {
+        // The variant indices of `sui_types::messages::Command`, where `Publish` is 4 and
+        // `Upgrade` is 6.
+        match self {
+            Self::MoveCall(move_call) => {
+                write_uleb128(writer, 0);
+                move_call.encode(writer);
+            }
+            Self::TransferObjects(objects, recipient) => {
+                write_uleb128(writer, 1);
+                write_seq(writer, *objects);
+                recipient.encode(writer);
+            }
+            Self::SplitCoins(coin, amounts) => {
+                write_uleb128(writer, 2);
+                coin.encode(writer);
+                write_seq(writer, *amounts);
+            }
+            Self::MergeCoins(coin, coins) => {
+                write_uleb128(writer, 3);
+                coin.encode(writer);
+                write_seq(writer, *coins);
+            }
+            Self::MakeMoveVec(type_tag, elements) => {
+                write_uleb128(writer, 5);
+                type_tag.encode(writer);
+                write_seq(writer, *elements);
+            }
+        }
+    }
+}
+
+/// `sui_types::messages::ProgrammableTransaction`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProgrammableTransaction<'a> {
+    pub inputs: &'a [CallArg<'a>],
+    pub commands: &'a [Command<'a>],
+}
+
+impl Encode for ProgrammableTransaction<'_> {
+    fn encode<W: BcsWriter>(&self, writer: &mut W) {
+        write_seq(writer, self.inputs);
+        write_seq(writer, self.commands);
+    }
+}
+
+/// `sui_types::messages::GasData`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GasData<'a> {
+    pub payment: &'a [ObjectRef],
+    pub owner: Address,
+    pub price: u64,
+    pub budget: u64,
+}
+
+impl Encode for GasData<'_> {
+    fn encode<W: BcsWriter>(&self, writer: &mut W) {
+        write_seq(writer, self.payment);
+        self.owner.encode(writer);
+        self.price.encode(writer);
+        self.budget.encode(writer);
+    }
+}
+
+/// `sui_types::messages::TransactionExpiration`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransactionExpiration {
+    None,
+    Epoch(u64),
+}
+
+impl Encode for TransactionExpiration {
+    fn encode<W: BcsWriter>(&self, writer: &mut W) {
+        match self {
+            Self::None => write_uleb128(writer, 0),
+            Self::Epoch(epoch) => {
+                write_uleb128(writer, 1);
+                epoch.encode(writer);
+            }
+        }
+    }
+}
+
+/// `sui_types::messages::TransactionData` of a programmable transaction, the only kind of
+/// transactions signed by users.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransactionData<'a> {
+    pub kind: ProgrammableTransaction<'a>,
+    pub sender: Address,
+    pub gas_data: GasData<'a>,
+    pub expiration: TransactionExpiration,
+}
+
+impl Encode for TransactionData<'_> {
+    fn encode<W: BcsWriter>(&self, writer: &mut W) {
+        // `TransactionData::V1`, of kind `TransactionKind::ProgrammableTransaction`.
+        write_uleb128(writer, 0);
+        write_uleb128(writer, 0);
+        self.kind.encode(writer);
+        self.sender.encode(writer);
+        self.gas_data.encode(writer);
+        self.expiration.encode(writer);
+    }
+}
@@ -0,0 +1,202 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use fastcrypto::ed25519::{Ed25519KeyPair, Ed25519PrivateKey};
+use fastcrypto::traits::ToFromBytes;
+use move_core_types::ident_str;
+use shared_crypto::intent::{Intent, IntentMessage, IntentScope, PersonalMessage};
+use sui_signer_core::{transaction, Ed25519Signer};
+use sui_types::base_types::{random_object_ref, ObjectID, ObjectRef, SequenceNumber, SuiAddress};
+use sui_types::crypto::{Signature, SuiKeyPair};
+use sui_types::gas_coin::GasCoin;
+use sui_types::messages::{
+    Argument, CallArg, Command, ObjectArg, ProgrammableMoveCall, ProgrammableTransaction,
+    TransactionData, TransactionDataAPI, TransactionExpiration,
+};
+use sui_types::{TypeTag, SUI_FRAMEWORK_OBJECT_ID};
+
+const PRIVATE_KEY: [u8; 32] = [7; 32];
+
+fn key_pair() -> SuiKeyPair {
+    let key: Ed25519KeyPair = Ed25519PrivateKey::from_bytes(&PRIVATE_KEY).unwrap().into();
+    SuiKeyPair::Ed25519(key)
+}
+
+#[test]
+fn test_address_matches_sui_types() {
+    let signer = Ed25519Signer::from_bytes(PRIVATE_KEY);
+    let key_pair = key_pair();
+    assert_eq!(signer.public_key(), key_pair.public().as_ref());
+    assert_eq!(
+        signer.address(),
+        SuiAddress::from(&key_pair.public()).to_inner()
+    );
+}
+
+#[test]
+fn test_transaction_signature_matches_sui_types() {
+    let signer = Ed25519Signer::from_bytes(PRIVATE_KEY);
+    let sender = SuiAddress::from(&key_pair().public());
+    let tx_data = TransactionData::new_transfer_sui_with_dummy_gas_price(
+        SuiAddress::random_for_testing_only(),
+        sender,
+        Some(1000),
+        random_object_ref(),
+        10000,
+    );
+
+    let expected = Signature::new_secure(
+        &IntentMessage::new(Intent::sui_transaction(), tx_data.clone()),
+        &key_pair(),
+    );
+    let signature = signer.sign_transaction_data(&bcs::to_bytes(&tx_data).unwrap());
+    assert_eq!(signature.as_slice(), expected.as_ref());
+}
+
+#[test]
+fn test_personal_message_signature_matches_sui_types() {
+    let signer = Ed25519Signer::from_bytes(PRIVATE_KEY);
+    // Long enough for its length to take two bytes in ULEB128.
+    let message = vec![42; 300];
+
+    let expected = Signature::new_secure(
+        &IntentMessage::new(
+            Intent::sui_app(IntentScope::PersonalMessage),
+            PersonalMessage {
+                message: message.clone(),
+            },
+        ),
+        &key_pair(),
+    );
+    assert_eq!(
+        signer.sign_personal_message(&message).as_slice(),
+        expected.as_ref()
+    );
+}
+
+fn core_object_ref((id, version, digest): ObjectRef) -> transaction::ObjectRef {
+    transaction::ObjectRef {
+        object_id: id.into_bytes(),
+        version: version.value(),
+        digest: digest.into_inner(),
+    }
+}
+
+#[test]
+fn test_encoded_transaction_data_matches_sui_types() {
+    let signer = Ed25519Signer::from_bytes(PRIVATE_KEY);
+    let sender = SuiAddress::from(&key_pair().public());
+    let recipient = SuiAddress::random_for_testing_only();
+    let amount = bcs::to_bytes(&1000u64).unwrap();
+    let coin = random_object_ref();
+    let gas = random_object_ref();
+    let shared_id = ObjectID::random();
+
+    let pt = ProgrammableTransaction {
+        inputs: vec![
+            CallArg::Pure(amount.clone()),
+            CallArg::Pure(recipient.to_vec()),
+            CallArg::Object(ObjectArg::ImmOrOwnedObject(coin)),
+            CallArg::Object(ObjectArg::SharedObject {
+                id: shared_id,
+                initial_shared_version: SequenceNumber::from_u64(5),
+                mutable: true,
+            }),
+        ],
+        commands: vec![
+            Command::SplitCoins(Argument::GasCoin, vec![Argument::Input(0)]),
+            Command::MergeCoins(Argument::Input(2), vec![Argument::NestedResult(0, 0)]),
+            Command::MoveCall(Box::new(ProgrammableMoveCall {
+                package: SUI_FRAMEWORK_OBJECT_ID,
+                module: ident_str!("pay").to_owned(),
+                function: ident_str!("join").to_owned(),
+                type_arguments: vec![TypeTag::Struct(Box::new(GasCoin::type_()))],
+                arguments: vec![Argument::Input(3), Argument::Result(0)],
+            })),
+            Command::MakeMoveVec(Some(TypeTag::Vector(Box::new(TypeTag::U8))), vec![]),
+            Command::TransferObjects(vec![Argument::Input(2)], Argument::Input(1)),
+        ],
+    };
+    let mut tx_data = TransactionData::new_programmable(sender, vec![gas], pt, 10000, 1000);
+    *tx_data.expiration_mut_for_testing() = TransactionExpiration::Epoch(7);
+
+    let sui = transaction::StructTag {
+        address: SUI_FRAMEWORK_OBJECT_ID.into_bytes(),
+        module: "sui",
+        name: "SUI",
+        type_params: &[],
+    };
+    let sui_type = [transaction::TypeTag::Struct(&sui)];
+    let coin_type = transaction::StructTag {
+        address: SUI_FRAMEWORK_OBJECT_ID.into_bytes(),
+        module: "coin",
+        name: "Coin",
+        type_params: &sui_type,
+    };
+    let core_coin = core_object_ref(coin);
+    let inputs = [
+        transaction::CallArg::Pure(&amount),
+        transaction::CallArg::Pure(recipient.as_ref()),
+        transaction::CallArg::Object(transaction::ObjectArg::ImmOrOwnedObject(core_coin)),
+        transaction::CallArg::Object(transaction::ObjectArg::SharedObject {
+            id: shared_id.into_bytes(),
+            initial_shared_version: 5,
+            mutable: true,
+        }),
+    ];
+    let commands = [
+        transaction::Command::SplitCoins(
+            transaction::Argument::GasCoin,
+            &[transaction::Argument::Input(0)],
+        ),
+        transaction::Command::MergeCoins(
+            transaction::Argument::Input(2),
+            &[transaction::Argument::NestedResult(0, 0)],
+        ),
+        transaction::Command::MoveCall(transaction::ProgrammableMoveCall {
+            package: SUI_FRAMEWORK_OBJECT_ID.into_bytes(),
+            module: "pay",
+            function: "join",
+            type_arguments: &[transaction::TypeTag::Struct(&coin_type)],
+            arguments: &[
+                transaction::Argument::Input(3),
+                transaction::Argument::Result(0),
+            ],
+        }),
+        transaction::Command::MakeMoveVec(
+            Some(transaction::TypeTag::Vector(&transaction::TypeTag::U8)),
+            &[],
+        ),
+        transaction::Command::TransferObjects(
+            &[transaction::Argument::Input(2)],
+            transaction::Argument::Input(1),
+        ),
+    ];
+    let core_tx_data = transaction::TransactionData {
+        kind: transaction::ProgrammableTransaction {
+            inputs: &inputs,
+            commands: &commands,
+        },
+        sender: sender.to_inner(),
+        gas_data: transaction::GasData {
+            payment: &[core_object_ref(gas)],
+            owner: sender.to_inner(),
+            price: 1000,
+            budget: 10000,
+        },
+        expiration: transaction::TransactionExpiration::Epoch(7),
+    };
+
+    let expected = bcs::to_bytes(&tx_data).unwrap();
+    let mut buf = [0; 1024];
+    let len = transaction::encode_into(&core_tx_data, &mut buf).unwrap();
+    assert_eq!(&buf[..len], expected.as_slice());
+    assert_eq!(
+        transaction::encode_into(&core_tx_data, &mut buf[..len - 1]),
+        None
+    );
+    assert_eq!(
+        signer.sign_transaction(&core_tx_data),
+        signer.sign_transaction_data(&expected)
+    );
+}
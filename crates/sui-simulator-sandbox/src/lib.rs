@@ -0,0 +1,452 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Local execution of transactions against an in-memory object store, without a network or a
+//! validator, for fast tests of transaction building logic. The [Sandbox] starts with the system
+//! packages of this build and the clock, and is loaded with the objects and packages the
+//! transactions use, either fetched from a fullnode or read from fixtures saved beforehand.
+//!
+//! Transactions are checked and executed as validators would, except that they are not signed:
+//! the effects of successful or failed transactions are applied to the store, so that later
+//! transactions see the objects they wrote.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use sui_adapter::adapter::{new_move_vm, MoveVM};
+use sui_adapter::execution_engine::execute_transaction_to_effects;
+use sui_adapter::execution_mode;
+use sui_framework::BuiltInFramework;
+use sui_json_rpc_types::SuiObjectDataOptions;
+use sui_protocol_config::ProtocolConfig;
+use sui_sdk::SuiClient;
+use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress, TransactionDigest};
+use sui_types::clock::Clock;
+use sui_types::crypto::default_hash;
+use sui_types::epoch_data::EpochData;
+use sui_types::error::{ExecutionError, SuiError, UserInputError};
+use sui_types::gas::{SuiCostTable, SuiGasStatus};
+use sui_types::id::UID;
+use sui_types::in_memory_storage::InMemoryStorage;
+use sui_types::messages::{
+    InputObjectKind, InputObjects, ProgrammableTransaction, TransactionData, TransactionDataAPI,
+    TransactionEffects, TransactionEvents, VersionedProtocolMessage,
+};
+use sui_types::object::{MoveObject, Object, Owner, OBJECT_START_VERSION};
+use sui_types::temporary_store::TemporaryStore;
+use sui_types::{SUI_CLOCK_OBJECT_ID, SUI_CLOCK_OBJECT_SHARED_VERSION};
+use thiserror::Error;
+
+#[cfg(test)]
+#[path = "unit_tests/sandbox_tests.rs"]
+mod sandbox_tests;
+
+pub type SandboxResult<T> = Result<T, SandboxError>;
+
+#[derive(Debug, Error)]
+pub enum SandboxError {
+    #[error(transparent)]
+    UserInput(#[from] UserInputError),
+    #[error(transparent)]
+    Sui(#[from] SuiError),
+    #[error("Failed fetching objects: {0}")]
+    Rpc(String),
+    #[error("Invalid fixture {path}: {error}")]
+    InvalidFixture { path: PathBuf, error: String },
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// The outcome of a transaction executed in the [Sandbox].
+#[derive(Debug)]
+pub struct ExecutionOutcome {
+    pub effects: TransactionEffects,
+    pub events: TransactionEvents,
+    /// The error the transaction failed with, also recorded in the status of the effects.
+    pub execution_error: Option<ExecutionError>,
+}
+
+pub struct Sandbox {
+    store: InMemoryStorage,
+    protocol_config: ProtocolConfig,
+    move_vm: Arc<MoveVM>,
+    epoch_data: EpochData,
+    reference_gas_price: u64,
+}
+
+impl Sandbox {
+    /// Creates a sandbox at the latest protocol version, in epoch 0 with a reference gas price of 1.
+    pub fn new() -> Self {
+        Self::with_protocol_config(ProtocolConfig::get_for_max_version())
+    }
+
+    pub fn with_protocol_config(protocol_config: ProtocolConfig) -> Self {
+        let native_functions = sui_framework::natives::all_natives(/* silent */ true);
+        let move_vm = Arc::new(
+            new_move_vm(native_functions, &protocol_config, false)
+                .expect("We defined natives to not fail here"),
+        );
+        let mut sandbox = Self {
+            store: InMemoryStorage::new(BuiltInFramework::genesis_objects().collect()),
+            protocol_config,
+            move_vm,
+            epoch_data: EpochData::new_genesis(0),
+            reference_gas_price: 1,
+        };
+        sandbox.set_clock(0);
+        sandbox
+    }
+
+    pub fn with_epoch_data(mut self, epoch_data: EpochData) -> Self {
+        self.epoch_data = epoch_data;
+        self
+    }
+
+    pub fn with_reference_gas_price(mut self, reference_gas_price: u64) -> Self {
+        self.reference_gas_price = reference_gas_price;
+        self
+    }
+
+    pub fn protocol_config(&self) -> &ProtocolConfig {
+        &self.protocol_config
+    }
+
+    pub fn reference_gas_price(&self) -> u64 {
+        self.reference_gas_price
+    }
+
+    pub fn get_object(&self, object_id: &ObjectID) -> Option<&Object> {
+        self.store.get_object(object_id)
+    }
+
+    /// Adds an object or a package to the store, replacing the version it holds, if any.
+    pub fn add_object(&mut self, object: Object) {
+        self.store.insert_object(object);
+    }
+
+    pub fn add_objects(&mut self, objects: impl IntoIterator<Item = Object>) {
+        for object in objects {
+            self.add_object(object);
+        }
+    }
+
+    /// Adds a gas coin of `balance` MIST owned by `owner`, and returns its reference.
+    pub fn add_gas_coin(&mut self, owner: SuiAddress, balance: u64) -> ObjectRef {
+        let object = Object::new_move(
+            MoveObject::new_gas_coin(OBJECT_START_VERSION, ObjectID::random(), balance),
+            Owner::AddressOwner(owner),
+            TransactionDigest::genesis(),
+        );
+        let object_ref = object.compute_object_reference();
+        self.add_object(object);
+        object_ref
+    }
+
+    /// Sets the time of the clock object, as the consensus commit prologue would.
+    pub fn set_clock(&mut self, timestamp_ms: u64) {
+        let contents = bcs::to_bytes(&Clock {
+            id: UID::new(SUI_CLOCK_OBJECT_ID),
+            timestamp_ms,
+        })
+        .unwrap();
+        let version = self
+            .store
+            .get_object(&SUI_CLOCK_OBJECT_ID)
+            .map_or(SUI_CLOCK_OBJECT_SHARED_VERSION, |clock| clock.version());
+        // Safe because the contents are those of a clock, which has no public transfer.
+        let clock = unsafe {
+            MoveObject::new_from_execution(
+                Clock::type_().into(),
+                false,
+                version,
+                contents,
+                &self.protocol_config,
+            )
+        }
+        .expect("The clock is within the object size limit");
+        self.add_object(Object::new_move(
+            clock,
+            Owner::Shared {
+                initial_shared_version: SUI_CLOCK_OBJECT_SHARED_VERSION,
+            },
+            TransactionDigest::genesis(),
+        ));
+    }
+
+    /// Fetches the latest versions of `object_ids` from a fullnode, along with the packages the
+    /// fetched packages depend on, and adds them to the store.
+    pub async fn fetch_objects(
+        &mut self,
+        client: &SuiClient,
+        object_ids: Vec<ObjectID>,
+    ) -> SandboxResult<()> {
+        let mut to_fetch = object_ids;
+        while !to_fetch.is_empty() {
+            let responses = client
+                .read_api()
+                .multi_get_object_with_options(to_fetch, SuiObjectDataOptions::bcs_lossless())
+                .await
+                .map_err(|e| SandboxError::Rpc(e.to_string()))?;
+            let mut dependencies = HashSet::new();
+            for response in responses {
+                let data = response
+                    .into_object()
+                    .map_err(|e| SandboxError::Rpc(e.to_string()))?;
+                let object = TryInto::<Object>::try_into(data)
+                    .map_err(|e| SandboxError::Rpc(e.to_string()))?;
+                if let Some(package) = object.data.try_as_package() {
+                    dependencies.extend(
+                        package
+                            .linkage_table()
+                            .values()
+                            .map(|upgrade| upgrade.upgraded_id)
+                            .filter(|id| self.store.get_object(id).is_none()),
+                    );
+                }
+                self.add_object(object);
+            }
+            to_fetch = dependencies.into_iter().collect();
+        }
+        Ok(())
+    }
+
+    /// Adds the objects of the fixtures in `dir`, as saved by [Self::save_fixtures].
+    pub fn load_fixtures(&mut self, dir: impl AsRef<Path>) -> SandboxResult<()> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let object =
+                bcs::from_bytes(&fs::read(&path)?).map_err(|e| SandboxError::InvalidFixture {
+                    path: path.clone(),
+                    error: e.to_string(),
+                })?;
+            self.add_object(object);
+        }
+        Ok(())
+    }
+
+    /// Saves the current versions of `object_ids` as fixtures in `dir`, one BCS file per object,
+    /// e.g. to run tests against objects fetched once from a fullnode.
+    pub fn save_fixtures(
+        &self,
+        dir: impl AsRef<Path>,
+        object_ids: &[ObjectID],
+    ) -> SandboxResult<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        for object_id in object_ids {
+            let object =
+                self.store
+                    .get_object(object_id)
+                    .ok_or(UserInputError::ObjectNotFound {
+                        object_id: *object_id,
+                        version: None,
+                    })?;
+            fs::write(
+                dir.join(format!("{object_id}.bcs")),
+                bcs::to_bytes(object).unwrap(),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Executes `pt` from `sender`, paid with a new gas coin of the sender holding twice the
+    /// maximum budget, at the reference gas price.
+    pub fn execute_programmable(
+        &mut self,
+        sender: SuiAddress,
+        pt: ProgrammableTransaction,
+    ) -> SandboxResult<ExecutionOutcome> {
+        let gas_budget = self.protocol_config.max_tx_gas();
+        let gas = self.add_gas_coin(sender, gas_budget * 2);
+        self.execute(TransactionData::new_programmable(
+            sender,
+            vec![gas],
+            pt,
+            gas_budget,
+            self.reference_gas_price,
+        ))
+    }
+
+    /// Executes `transaction` and applies its effects to the store. Errors are returned for the
+    /// transactions which validators would not sign, those failing during execution are returned
+    /// with their effects.
+    pub fn execute(&mut self, transaction: TransactionData) -> SandboxResult<ExecutionOutcome> {
+        transaction.check_version_supported(&self.protocol_config)?;
+        transaction.validity_check(&self.protocol_config)?;
+        let input_objects = self.load_input_objects(&transaction)?;
+        let gas_status = self.gas_status(&transaction)?;
+
+        let digest = TransactionDigest::new(default_hash(&transaction));
+        let shared_object_refs = input_objects.filter_shared_objects();
+        let transaction_dependencies = input_objects.transaction_dependencies();
+        let temporary_store = TemporaryStore::new(
+            &mut self.store,
+            input_objects,
+            digest,
+            &self.protocol_config,
+        );
+        let (kind, signer, gas) = transaction.execution_parts();
+        let (inner_temp_store, effects, result) =
+            execute_transaction_to_effects::<execution_mode::Normal, _>(
+                shared_object_refs,
+                temporary_store,
+                kind,
+                signer,
+                &gas,
+                digest,
+                transaction_dependencies,
+                &self.move_vm,
+                gas_status,
+                &self.epoch_data,
+                &self.protocol_config,
+            );
+        self.store
+            .finish(inner_temp_store.written, inner_temp_store.deleted);
+
+        Ok(ExecutionOutcome {
+            effects,
+            events: inner_temp_store.events,
+            execution_error: result.err(),
+        })
+    }
+
+    fn load_input_objects(&self, transaction: &TransactionData) -> SandboxResult<InputObjects> {
+        let mut objects = vec![];
+        let mut mutable_objects = HashSet::new();
+        for kind in transaction.input_objects()? {
+            let object = self
+                .store
+                .get_object(&kind.object_id())
+                .ok_or_else(|| kind.object_not_found_error())?;
+            if !object.is_immutable() && !mutable_objects.insert(object.id()) {
+                return Err(UserInputError::MutableObjectUsedMoreThanOnce {
+                    object_id: object.id(),
+                }
+                .into());
+            }
+            let owner = if transaction.gas().iter().any(|gas| gas.0 == object.id()) {
+                transaction.gas_owner()
+            } else {
+                transaction.sender()
+            };
+            check_input_object(&owner, kind, object, transaction.is_system_tx())?;
+            objects.push((kind, object.clone()));
+        }
+        Ok(InputObjects::new(objects))
+    }
+
+    fn gas_status(&self, transaction: &TransactionData) -> SandboxResult<SuiGasStatus<'static>> {
+        if transaction.is_system_tx() {
+            return Ok(SuiGasStatus::new_unmetered(&self.protocol_config));
+        }
+        if transaction.gas_price() < self.reference_gas_price {
+            return Err(UserInputError::GasPriceUnderRGP {
+                gas_price: transaction.gas_price(),
+                reference_gas_price: self.reference_gas_price,
+            }
+            .into());
+        }
+        // The gas coins are input objects, which are loaded by now.
+        let gas_objects: Vec<_> = transaction
+            .gas()
+            .iter()
+            .filter_map(|gas| self.store.get_object(&gas.0))
+            .collect();
+        let Some((gas_object, more_gas_objects)) = gas_objects.split_first() else {
+            return Err(UserInputError::MissingGasPayment.into());
+        };
+        SuiCostTable::new(&self.protocol_config).check_gas_balance(
+            gas_object,
+            more_gas_objects.to_vec(),
+            transaction.gas_budget(),
+            transaction.gas_price(),
+        )?;
+        Ok(SuiGasStatus::new_with_budget(
+            transaction.gas_budget(),
+            transaction.gas_price(),
+            &self.protocol_config,
+        ))
+    }
+}
+
+impl Default for Sandbox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Checks an input object of a transaction of `owner` against the object in the store.
+fn check_input_object(
+    owner: &SuiAddress,
+    kind: InputObjectKind,
+    object: &Object,
+    system_transaction: bool,
+) -> Result<(), UserInputError> {
+    match kind {
+        InputObjectKind::MovePackage(package_id) => {
+            if object.data.try_as_package().is_none() {
+                return Err(UserInputError::MoveObjectAsPackage {
+                    object_id: package_id,
+                });
+            }
+        }
+        InputObjectKind::ImmOrOwnedMoveObject(object_ref) => {
+            let (object_id, version, digest) = object_ref;
+            if object.is_package() {
+                return Err(UserInputError::MovePackageAsObject { object_id });
+            }
+            if object.version() != version {
+                return Err(UserInputError::ObjectVersionUnavailableForConsumption {
+                    provided_obj_ref: object_ref,
+                    current_version: object.version(),
+                });
+            }
+            if object.digest() != digest {
+                return Err(UserInputError::InvalidObjectDigest {
+                    object_id,
+                    expected_digest: object.digest(),
+                });
+            }
+            match object.owner {
+                Owner::Immutable => (),
+                Owner::AddressOwner(actual_owner) if &actual_owner == owner => (),
+                Owner::AddressOwner(actual_owner) => {
+                    return Err(UserInputError::IncorrectUserSignature {
+                        error: format!("Object {object_id} is owned by account address {actual_owner}, but given owner/signer address is {owner}"),
+                    });
+                }
+                Owner::ObjectOwner(parent) => {
+                    return Err(UserInputError::InvalidChildObjectArgument {
+                        child_id: object_id,
+                        parent_id: parent.into(),
+                    });
+                }
+                Owner::Shared { .. } => return Err(UserInputError::NotSharedObjectError),
+            }
+        }
+        InputObjectKind::SharedMoveObject {
+            id: SUI_CLOCK_OBJECT_ID,
+            mutable: true,
+            ..
+        } if !system_transaction => {
+            return Err(UserInputError::ImmutableParameterExpectedError {
+                object_id: SUI_CLOCK_OBJECT_ID,
+            });
+        }
+        InputObjectKind::SharedMoveObject {
+            initial_shared_version,
+            ..
+        } => match object.owner {
+            Owner::Shared {
+                initial_shared_version: actual_initial_shared_version,
+            } if actual_initial_shared_version == initial_shared_version => (),
+            Owner::Shared { .. } => {
+                return Err(UserInputError::SharedObjectStartingVersionMismatch)
+            }
+            _ => return Err(UserInputError::NotSharedObjectError),
+        },
+    }
+    Ok(())
+}
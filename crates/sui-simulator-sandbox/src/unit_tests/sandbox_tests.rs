@@ -0,0 +1,132 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_types::gas_coin::GasCoin;
+use sui_types::messages::TransactionEffectsAPI;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+
+use super::*;
+
+fn transfer_sui(recipient: SuiAddress, amount: u64) -> ProgrammableTransaction {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.transfer_sui(recipient, Some(amount));
+    builder.finish()
+}
+
+fn balance(sandbox: &Sandbox, object_id: &ObjectID) -> u64 {
+    GasCoin::try_from(sandbox.get_object(object_id).unwrap())
+        .unwrap()
+        .value()
+}
+
+#[test]
+fn test_execute_programmable() {
+    let mut sandbox = Sandbox::new();
+    let sender = SuiAddress::random_for_testing_only();
+    let recipient = SuiAddress::random_for_testing_only();
+
+    let outcome = sandbox
+        .execute_programmable(sender, transfer_sui(recipient, 1000))
+        .unwrap();
+    assert!(outcome.effects.status().is_ok());
+    assert!(outcome.execution_error.is_none());
+
+    // The effects are applied to the store.
+    let created = outcome.effects.created();
+    assert_eq!(created.len(), 1);
+    let ((coin_id, _, _), owner) = created[0];
+    assert_eq!(owner, Owner::AddressOwner(recipient));
+    assert_eq!(balance(&sandbox, &coin_id), 1000);
+    let ((gas_id, gas_version, _), _) = *outcome.effects.gas_object();
+    assert_eq!(sandbox.get_object(&gas_id).unwrap().version(), gas_version);
+}
+
+#[test]
+fn test_failed_transaction_is_charged() {
+    let mut sandbox = Sandbox::new();
+    let sender = SuiAddress::random_for_testing_only();
+    let gas_budget = sandbox.protocol_config().max_tx_gas();
+    let gas = sandbox.add_gas_coin(sender, gas_budget);
+
+    // The coin cannot be split for more than its balance.
+    let transaction = TransactionData::new_programmable(
+        sender,
+        vec![gas],
+        transfer_sui(sender, gas_budget * 2),
+        gas_budget,
+        sandbox.reference_gas_price(),
+    );
+    let outcome = sandbox.execute(transaction).unwrap();
+    assert!(!outcome.effects.status().is_ok());
+    assert!(outcome.execution_error.is_some());
+    assert!(outcome.effects.created().is_empty());
+    assert!(balance(&sandbox, &gas.0) < gas_budget);
+}
+
+#[test]
+fn test_invalid_inputs_are_rejected() {
+    let mut sandbox = Sandbox::new();
+    let sender = SuiAddress::random_for_testing_only();
+    let gas_budget = sandbox.protocol_config().max_tx_gas();
+    let gas_price = sandbox.reference_gas_price();
+    let gas = sandbox.add_gas_coin(sender, gas_budget * 2);
+    let transaction = |gas| {
+        TransactionData::new_programmable(
+            sender,
+            vec![gas],
+            transfer_sui(sender, 1000),
+            gas_budget,
+            gas_price,
+        )
+    };
+    let first = transaction(gas);
+    sandbox.execute(first.clone()).unwrap();
+
+    // The gas coin was mutated by the first transaction.
+    assert!(matches!(
+        sandbox.execute(first),
+        Err(SandboxError::UserInput(
+            UserInputError::ObjectVersionUnavailableForConsumption { .. }
+        ))
+    ));
+
+    let other_owner = SuiAddress::random_for_testing_only();
+    let others_gas = sandbox.add_gas_coin(other_owner, gas_budget * 2);
+    assert!(matches!(
+        sandbox.execute(transaction(others_gas)),
+        Err(SandboxError::UserInput(
+            UserInputError::IncorrectUserSignature { .. }
+        ))
+    ));
+
+    let missing_gas = (ObjectID::random(), OBJECT_START_VERSION, gas.2);
+    assert!(matches!(
+        sandbox.execute(transaction(missing_gas)),
+        Err(SandboxError::UserInput(
+            UserInputError::ObjectNotFound { .. }
+        ))
+    ));
+}
+
+#[test]
+fn test_fixtures() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut sandbox = Sandbox::new();
+    let owner = SuiAddress::random_for_testing_only();
+    let gas = sandbox.add_gas_coin(owner, 1000);
+    sandbox.set_clock(1000);
+    let object_ids = [gas.0, SUI_CLOCK_OBJECT_ID];
+    sandbox.save_fixtures(dir.path(), &object_ids).unwrap();
+
+    let mut loaded = Sandbox::new();
+    loaded.load_fixtures(dir.path()).unwrap();
+    for object_id in &object_ids {
+        assert_eq!(loaded.get_object(object_id), sandbox.get_object(object_id));
+    }
+    assert!(matches!(
+        sandbox.save_fixtures(dir.path(), &[ObjectID::random()]),
+        Err(SandboxError::UserInput(
+            UserInputError::ObjectNotFound { .. }
+        ))
+    ));
+}
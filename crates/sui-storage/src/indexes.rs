@@ -828,6 +828,25 @@ impl IndexStore {
             .map(|(_, object_info)| object_info))
     }
 
+    /// Iterates over all the entries of the owner index.
+    pub fn owner_index_iter(&self) -> impl Iterator<Item = (OwnerIndexKey, ObjectInfo)> + '_ {
+        self.tables.owner_index.iter()
+    }
+
+    /// Replaces entries of the owner index which no longer match the object store, e.g. after a
+    /// crash between the execution of a transaction and its indexing.
+    pub fn repair_owner_index(
+        &self,
+        deleted_owners: Vec<OwnerIndexKey>,
+        new_owners: Vec<(OwnerIndexKey, ObjectInfo)>,
+    ) -> SuiResult {
+        let mut batch = self.tables.owner_index.batch();
+        batch.delete_batch(&self.tables.owner_index, deleted_owners.into_iter())?;
+        batch.insert_batch(&self.tables.owner_index, new_owners.into_iter())?;
+        batch.write()?;
+        Ok(())
+    }
+
     pub fn insert_genesis_objects(&self, object_index_changes: ObjectIndexChanges) -> SuiResult {
         let mut batch = self.tables.owner_index.batch();
         batch.insert_batch(
@@ -0,0 +1,303 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Resumable download of a directory of an object store, e.g. the db checkpoint of an epoch,
+//! which can take hours for a mainnet db.
+//!
+//! The files already written to the local directory are recorded with their checksum in a progress
+//! manifest in the directory, so that a download interrupted by a network failure or a restart
+//! resumes with the remaining files instead of starting from scratch.
+
+use crate::object_store::{ObjectStoreConfig, ObjectStoreType};
+use anyhow::{Context, Result};
+use backoff::future::retry;
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::HashFunction;
+use futures::{StreamExt, TryStreamExt};
+use object_store::path::Path;
+use object_store::{DynObjectStore, ObjectMeta};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use sui_types::crypto::DefaultHash;
+use tracing::info;
+
+pub const DOWNLOAD_PROGRESS_FILENAME: &str = "_DOWNLOAD_PROGRESS";
+const PROGRESS_REPORT_INTERVAL: Duration = Duration::from_secs(10);
+/// Number of downloaded files between two writes of the progress file.
+const PROGRESS_SAVE_BATCH_SIZE: usize = 128;
+
+/// The files of a download written to the local directory.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DownloadProgress {
+    pub completed: BTreeMap<String, DownloadedFile>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DownloadedFile {
+    pub size: usize,
+    /// Hex encoded Blake2b256 checksum of the contents written to the local file.
+    pub checksum: String,
+}
+
+impl DownloadedFile {
+    fn new(contents: &[u8]) -> Self {
+        Self {
+            size: contents.len(),
+            checksum: Hex::encode(DefaultHash::digest(contents).digest),
+        }
+    }
+}
+
+impl DownloadProgress {
+    fn load(local_dir: &std::path::Path) -> Result<Self> {
+        let path = local_dir.join(DOWNLOAD_PROGRESS_FILENAME);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        serde_json::from_slice(&fs::read(&path)?)
+            .with_context(|| format!("Invalid download progress file {}", path.display()))
+    }
+
+    /// Writes the progress to a temporary file first, so that an interruption never leaves a
+    /// truncated progress file.
+    fn save(&self, local_dir: &std::path::Path) -> Result<()> {
+        let path = local_dir.join(DOWNLOAD_PROGRESS_FILENAME);
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_vec(self)?)?;
+        fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Whether `file` was downloaded by a previous run: the remote file has the recorded size,
+    /// and the local file still has the recorded checksum.
+    async fn is_downloaded(&self, file: &ObjectMeta, local_dir: &std::path::Path) -> Result<bool> {
+        let Some(downloaded) = self.completed.get(file.location.as_ref()) else {
+            return Ok(false);
+        };
+        if downloaded.size != file.size {
+            return Ok(false);
+        }
+        let local_path = local_dir.join(file.location.as_ref());
+        match tokio::fs::read(&local_path).await {
+            Ok(contents) => Ok(&DownloadedFile::new(&contents) == downloaded),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(err) => {
+                Err(err).with_context(|| format!("Failed to read {}", local_path.display()))
+            }
+        }
+    }
+}
+
+/// Downloads `file` from `from` to the same path in `to`, and returns the size and checksum of
+/// the downloaded contents.
+async fn download_file(
+    file: &ObjectMeta,
+    from: Arc<DynObjectStore>,
+    to: Arc<DynObjectStore>,
+) -> Result<DownloadedFile> {
+    let contents = from.get(&file.location).await?.bytes().await?;
+    let downloaded = DownloadedFile::new(&contents);
+    to.put(&file.location, contents).await?;
+    Ok(downloaded)
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DownloadStats {
+    pub files: usize,
+    /// Files already downloaded by a previous run.
+    pub skipped_files: usize,
+    pub total_bytes: usize,
+    pub downloaded_bytes: usize,
+}
+
+/// Downloads the files under `dir` in `from` to the same paths in `local_dir`, skipping the
+/// files downloaded by a previous run, and reports the throughput and the estimated time left.
+/// The progress file is written every [PROGRESS_SAVE_BATCH_SIZE] downloaded files, and when the
+/// download stops.
+pub async fn download_recursively(
+    dir: &Path,
+    from: Arc<DynObjectStore>,
+    local_dir: &std::path::Path,
+    concurrency: NonZeroUsize,
+) -> Result<DownloadStats> {
+    fs::create_dir_all(local_dir)?;
+    let to = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(local_dir.to_path_buf()),
+        ..Default::default()
+    }
+    .make()?;
+    let mut progress = DownloadProgress::load(local_dir)?;
+    let files: Vec<_> = from.list(Some(dir)).await?.try_collect().await?;
+
+    let mut stats = DownloadStats {
+        files: files.len(),
+        total_bytes: files.iter().map(|file| file.size).sum(),
+        ..Default::default()
+    };
+    let is_downloaded: Vec<bool> = futures::stream::iter(&files)
+        .map(|file| progress.is_downloaded(file, local_dir))
+        .buffered(concurrency.get())
+        .try_collect()
+        .await?;
+    let remaining: Vec<_> = files
+        .into_iter()
+        .zip(is_downloaded)
+        .filter_map(|(file, is_downloaded)| (!is_downloaded).then_some(file))
+        .collect();
+    stats.skipped_files = stats.files - remaining.len();
+    let already_downloaded = stats.total_bytes - remaining.iter().map(|f| f.size).sum::<usize>();
+    info!(
+        "Downloading {} files of {dir}, {} already downloaded",
+        remaining.len(),
+        stats.skipped_files
+    );
+
+    let start = Instant::now();
+    let mut last_report = start;
+    let mut downloads = futures::stream::iter(remaining)
+        .map(|file| {
+            let (from, to) = (from.clone(), to.clone());
+            async move {
+                let result = retry(backoff::ExponentialBackoff::default(), || async {
+                    download_file(&file, from.clone(), to.clone())
+                        .await
+                        .map_err(backoff::Error::transient)
+                })
+                .await;
+                result.map(|downloaded| (file, downloaded))
+            }
+        })
+        .buffer_unordered(concurrency.get());
+    let mut unsaved = 0;
+    while let Some(result) = downloads.next().await {
+        let (file, downloaded) = match result {
+            Ok(result) => result,
+            Err(err) => {
+                // Keep the files downloaded so far for the next run.
+                progress.save(local_dir)?;
+                return Err(err);
+            }
+        };
+        stats.downloaded_bytes += downloaded.size;
+        progress
+            .completed
+            .insert(file.location.to_string(), downloaded);
+        unsaved += 1;
+        if unsaved == PROGRESS_SAVE_BATCH_SIZE {
+            progress.save(local_dir)?;
+            unsaved = 0;
+        }
+
+        if last_report.elapsed() >= PROGRESS_REPORT_INTERVAL {
+            last_report = Instant::now();
+            let throughput = stats.downloaded_bytes as f64 / start.elapsed().as_secs_f64();
+            let left = stats.total_bytes - already_downloaded - stats.downloaded_bytes;
+            info!(
+                "Downloaded {}/{} MiB of {dir}, {:.1} MiB/s, {:.0}s left",
+                (already_downloaded + stats.downloaded_bytes) >> 20,
+                stats.total_bytes >> 20,
+                throughput / (1 << 20) as f64,
+                left as f64 / throughput
+            );
+        }
+    }
+    progress.save(local_dir)?;
+    info!(
+        "Downloaded {} MiB of {dir} in {:.0}s",
+        stats.downloaded_bytes >> 20,
+        start.elapsed().as_secs_f64()
+    );
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_download_resumes() -> anyhow::Result<()> {
+        let remote = TempDir::new()?;
+        let epoch_dir = remote.path().join("epoch_0");
+        fs::create_dir_all(epoch_dir.join("store"))?;
+        fs::write(epoch_dir.join("CURRENT"), "current")?;
+        fs::write(epoch_dir.join("store").join("000001.sst"), "sst")?;
+        let from = ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::File),
+            directory: Some(remote.path().to_path_buf()),
+            ..Default::default()
+        }
+        .make()?;
+        let local = TempDir::new()?;
+        let concurrency = NonZeroUsize::new(2).unwrap();
+
+        let stats = download_recursively(
+            &Path::from("epoch_0"),
+            from.clone(),
+            local.path(),
+            concurrency,
+        )
+        .await?;
+        assert_eq!(
+            stats,
+            DownloadStats {
+                files: 2,
+                skipped_files: 0,
+                total_bytes: 10,
+                downloaded_bytes: 10,
+            }
+        );
+        assert_eq!(
+            fs::read_to_string(local.path().join("epoch_0/store/000001.sst"))?,
+            "sst"
+        );
+
+        let progress = DownloadProgress::load(local.path())?;
+        assert_eq!(
+            progress.completed["epoch_0/store/000001.sst"],
+            DownloadedFile::new(b"sst")
+        );
+
+        // Only the files missing from the progress manifest are downloaded again.
+        fs::write(epoch_dir.join("store").join("000002.sst"), "sst")?;
+        let stats = download_recursively(
+            &Path::from("epoch_0"),
+            from.clone(),
+            local.path(),
+            concurrency,
+        )
+        .await?;
+        assert_eq!((stats.skipped_files, stats.downloaded_bytes), (2, 3));
+
+        // A local file whose checksum changed since the last run is downloaded again.
+        fs::write(local.path().join("epoch_0/CURRENT"), "partial")?;
+        let stats = download_recursively(
+            &Path::from("epoch_0"),
+            from.clone(),
+            local.path(),
+            concurrency,
+        )
+        .await?;
+        assert_eq!((stats.skipped_files, stats.downloaded_bytes), (2, 7));
+        assert_eq!(
+            fs::read_to_string(local.path().join("epoch_0/CURRENT"))?,
+            "current"
+        );
+
+        // A file whose size changed since the last run is downloaded again.
+        fs::write(epoch_dir.join("CURRENT"), "current2")?;
+        let stats =
+            download_recursively(&Path::from("epoch_0"), from, local.path(), concurrency).await?;
+        assert_eq!((stats.skipped_files, stats.downloaded_bytes), (2, 8));
+        assert_eq!(
+            fs::read_to_string(local.path().join("epoch_0/CURRENT"))?,
+            "current2"
+        );
+        Ok(())
+    }
+}
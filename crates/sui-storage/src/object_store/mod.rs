@@ -10,6 +10,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use tracing::info;
 
+pub mod download;
 pub mod util;
 
 /// Object-store type.
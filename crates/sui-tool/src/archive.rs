@@ -0,0 +1,108 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Commands over the checkpoint archives written by [sui_archival].
+
+use crate::load_object_store_config;
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use std::path::PathBuf;
+use sui_archival::ArchiveReader;
+use sui_config::genesis::Genesis;
+use sui_types::base_types::{SuiAddress, TransactionDigest};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum ArchiveCommand {
+    /// Verify a range of checkpoints of an archive end to end: the signature of the manifest,
+    /// the checksums of the files, the certificates of the checkpoints from genesis and their
+    /// contents.
+    Verify {
+        /// Path of the yaml config of the object store of the archive
+        #[clap(long = "object-store-config")]
+        object_store_config: PathBuf,
+        #[clap(long = "genesis")]
+        genesis: PathBuf,
+        #[clap(
+            long,
+            help = "Address of the operator key signing the manifest of the archive"
+        )]
+        signer: SuiAddress,
+        #[clap(long, default_value = "0", help = "First checkpoint of the range")]
+        from: CheckpointSequenceNumber,
+        #[clap(
+            long,
+            help = "Last checkpoint of the range, the last archived checkpoint if not set"
+        )]
+        to: Option<CheckpointSequenceNumber>,
+    },
+    /// Read a transaction and its effects from an archive, e.g. after the network pruned it.
+    GetTransaction {
+        /// Path of the yaml config of the object store of the archive
+        #[clap(long = "object-store-config")]
+        object_store_config: PathBuf,
+        #[clap(
+            long,
+            help = "Address of the operator key signing the manifest of the archive"
+        )]
+        signer: SuiAddress,
+        #[clap(long, help = "The transaction ID to fetch")]
+        digest: TransactionDigest,
+        #[clap(
+            long,
+            help = "Checkpoint of the transaction, to only load the index of its file"
+        )]
+        checkpoint: Option<CheckpointSequenceNumber>,
+    },
+}
+
+pub async fn execute_archive_command(cmd: ArchiveCommand) -> Result<()> {
+    match cmd {
+        ArchiveCommand::Verify {
+            object_store_config,
+            genesis,
+            signer,
+            from,
+            to,
+        } => {
+            let config = load_object_store_config(&object_store_config)?;
+            let genesis_committee = Genesis::load(genesis)?.committee()?;
+            let reader = ArchiveReader::new(&config)?;
+            let manifest = reader.read_manifest(signer).await?;
+            let end = match to {
+                Some(to) => to + 1,
+                None => manifest.next_checkpoint_seq_num,
+            };
+            if from >= end {
+                return Err(anyhow!("The range of checkpoints {from}..{end} is empty"));
+            }
+            let verification = reader
+                .verify(&manifest, genesis_committee, from..end)
+                .await?;
+            print!("{verification}");
+        }
+        ArchiveCommand::GetTransaction {
+            object_store_config,
+            signer,
+            digest,
+            checkpoint,
+        } => {
+            let reader = ArchiveReader::new(&load_object_store_config(&object_store_config)?)?;
+            let manifest = reader.read_manifest(signer).await?;
+            let range = match checkpoint {
+                Some(checkpoint) => checkpoint..checkpoint.saturating_add(1),
+                None => 0..manifest.next_checkpoint_seq_num,
+            };
+            let index = reader.load_transaction_index(&manifest, range).await?;
+            let archived = reader
+                .get_transaction(&index, &digest)
+                .await?
+                .ok_or_else(|| anyhow!("Transaction {digest} is not in the archive"))?;
+            println!("Checkpoint: {}", archived.checkpoint);
+            println!("Transaction: {:#?}", archived.transaction.data());
+            println!("Effects: {:#?}", archived.effects);
+        }
+    }
+    Ok(())
+}
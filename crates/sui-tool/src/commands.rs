@@ -2,11 +2,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    archive::{execute_archive_command, ArchiveCommand},
+    contention::analyze_contention,
     db_tool::{execute_db_tool_command, print_db_all_tables, DbToolCommand},
-    get_object, get_transaction_block, make_clients, restore_from_db_checkpoint,
-    ConciseObjectOutput, GroupedObjectOutput, VerboseObjectOutput,
+    db_verify::db_verify,
+    download_db_checkpoint,
+    gas_report::export_gas_usage,
+    get_object, get_transaction_block, load_object_store_config, make_clients,
+    restore_from_db_checkpoint,
+    verify_accumulator::{verify_accumulator, AccumulatorOutput},
+    ConciseObjectOutput, GroupedObjectOutput, ObjectDisagreementOutput, ObjectLockOutput,
+    VerboseObjectOutput,
 };
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use sui_config::genesis::Genesis;
 use sui_core::authority_client::AuthorityAPI;
@@ -50,6 +59,14 @@ pub enum ToolCommand {
         )]
         validator: Option<AuthorityName>,
 
+        /// Fetch the latest version of the object from all validators, and report the validators
+        /// which disagree on its version, content or lock. Exits with an error if they disagree.
+        #[clap(
+            long = "all-validators",
+            conflicts_with_all = &["validator", "version", "history"]
+        )]
+        all_validators: bool,
+
         #[clap(long = "genesis")]
         genesis: PathBuf,
 
@@ -79,6 +96,20 @@ pub enum ToolCommand {
         concise_no_header: bool,
     },
 
+    /// Fetch the lock of an owned object version from all validators, and report the
+    /// transactions locking it
+    #[clap(name = "object-lock-status")]
+    ObjectLockStatus {
+        #[clap(long, help = "The object ID to inspect")]
+        id: ObjectID,
+
+        #[clap(long, help = "The version of the object to inspect")]
+        version: u64,
+
+        #[clap(long = "genesis")]
+        genesis: PathBuf,
+    },
+
     #[clap(name = "fetch-transaction")]
     FetchTransaction {
         #[clap(long = "genesis")]
@@ -126,12 +157,99 @@ pub enum ToolCommand {
         sequence_number: Option<CheckpointSequenceNumber>,
     },
 
+    /// Report the shared objects touched by the most transactions over a range of checkpoints,
+    /// with the gas price distribution of their transactions.
+    #[clap(name = "analyze-contention")]
+    AnalyzeContention {
+        #[clap(long = "genesis")]
+        genesis: PathBuf,
+        #[clap(long, help = "First checkpoint of the range")]
+        from: CheckpointSequenceNumber,
+        #[clap(long, help = "Last checkpoint of the range")]
+        to: CheckpointSequenceNumber,
+        #[clap(long, default_value = "20", help = "Number of hotspots to report")]
+        top: usize,
+    },
+
+    /// Export the gas fees paid and the storage rebates received by addresses in every epoch, as
+    /// the gas owner of transactions, from the JSON-RPC server of an indexer, as CSV.
+    #[clap(name = "gas-usage-report")]
+    GasUsageReport {
+        #[clap(long = "rpc-url", help = "URL of the JSON-RPC server of an indexer")]
+        rpc_url: String,
+        #[clap(
+            long = "address",
+            required = true,
+            help = "Address to report, can be repeated"
+        )]
+        addresses: Vec<SuiAddress>,
+        #[clap(long = "from-epoch", help = "First epoch of the report")]
+        from_epoch: Option<EpochId>,
+        #[clap(long = "to-epoch", help = "Last epoch of the report")]
+        to_epoch: Option<EpochId>,
+        #[clap(long, help = "File the CSV is written to, default to stdout")]
+        output: Option<PathBuf>,
+    },
+
+    /// Recompute the state accumulator at the end of an epoch from the effects of the checkpoints
+    /// in the db of a stopped node, compare it with the commitment of the last checkpoint of the
+    /// epoch, and report the live objects whose stored digest differs from their effects.
+    #[clap(name = "verify-accumulator")]
+    VerifyAccumulator {
+        /// Path of the DB of the node
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+        #[clap(long, help = "Epoch whose end of epoch accumulator is verified")]
+        epoch: EpochId,
+    },
+
+    /// Check the db of a stopped node for entries referencing data missing from other tables:
+    /// executed transactions without effects, effects without transactions, locks of missing
+    /// objects and owner index entries of objects which are not live.
+    #[clap(name = "db-verify")]
+    DbVerify {
+        /// Path of the DB of the node
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+        #[clap(
+            long,
+            help = "Remove the locks of missing objects and fix the dangling owner index entries"
+        )]
+        repair: bool,
+    },
+
+    /// Commands over the checkpoint archives written to an object store.
+    #[clap(name = "archive")]
+    Archive {
+        #[clap(subcommand)]
+        cmd: ArchiveCommand,
+    },
+
     #[clap(name = "anemo")]
     Anemo {
         #[clap(next_help_heading = "foo", flatten)]
         args: anemo_cli::Args,
     },
 
+    /// Download the db checkpoint of an epoch uploaded to an object store, to restore it with
+    /// restore-db. Run again to resume an interrupted download.
+    #[clap(name = "download-db-checkpoint")]
+    DownloadDBCheckpoint {
+        /// Path of the yaml config of the object store of the db checkpoints
+        #[clap(long = "object-store-config")]
+        object_store_config: PathBuf,
+        #[clap(long, help = "Epoch of the db checkpoint")]
+        epoch: u32,
+        #[clap(long, help = "Local directory to download the db checkpoint to")]
+        path: PathBuf,
+        #[clap(
+            long,
+            default_value = "20",
+            help = "Number of files downloaded concurrently"
+        )]
+        concurrency: NonZeroUsize,
+    },
+
     #[clap(name = "restore-db")]
     RestoreFromDBCheckpoint {
         #[clap(long = "config-path")]
@@ -201,6 +319,7 @@ impl ToolCommand {
             ToolCommand::FetchObject {
                 id,
                 validator,
+                all_validators,
                 genesis,
                 version,
                 history,
@@ -209,6 +328,15 @@ impl ToolCommand {
             } => {
                 let output = get_object(id, version, validator, genesis, history).await?;
 
+                if all_validators {
+                    let output = ObjectDisagreementOutput(output);
+                    println!("{}", output);
+                    if output.has_disagreements() {
+                        return Err(anyhow!("Validators disagree on object {id}"));
+                    }
+                    return Ok(());
+                }
+
                 match verbosity {
                     Verbosity::Grouped => {
                         println!("{}", GroupedObjectOutput(output));
@@ -224,6 +352,14 @@ impl ToolCommand {
                     }
                 }
             }
+            ToolCommand::ObjectLockStatus {
+                id,
+                version,
+                genesis,
+            } => {
+                let output = get_object(id, Some(version), None, genesis, false).await?;
+                println!("{}", ObjectLockOutput(output));
+            }
             ToolCommand::FetchTransaction { genesis, digest } => {
                 print!("{}", get_transaction_block(digest, genesis).await?);
             }
@@ -279,10 +415,78 @@ impl ToolCommand {
                     println!("Content: {:?}\n", contents);
                 }
             }
+            ToolCommand::AnalyzeContention {
+                genesis,
+                from,
+                to,
+                top,
+            } => {
+                print!("{}", analyze_contention(genesis, from, to, top).await?);
+            }
+            ToolCommand::GasUsageReport {
+                rpc_url,
+                addresses,
+                from_epoch,
+                to_epoch,
+                output,
+            } => match output {
+                Some(path) => {
+                    let file = std::fs::File::create(&path)?;
+                    export_gas_usage(&rpc_url, &addresses, from_epoch, to_epoch, file).await?;
+                }
+                None => {
+                    export_gas_usage(
+                        &rpc_url,
+                        &addresses,
+                        from_epoch,
+                        to_epoch,
+                        std::io::stdout(),
+                    )
+                    .await?;
+                }
+            },
+            ToolCommand::VerifyAccumulator { db_path, epoch } => {
+                let audit = verify_accumulator(&db_path, epoch)?;
+                let consistent = audit.is_consistent();
+                print!("{}", AccumulatorOutput(audit));
+                if !consistent {
+                    return Err(anyhow!(
+                        "The state accumulator of epoch {epoch} does not match the effects"
+                    ));
+                }
+            }
+            ToolCommand::DbVerify { db_path, repair } => {
+                let output = db_verify(&db_path, repair)?;
+                let consistent = output.is_consistent();
+                print!("{output}");
+                if !consistent {
+                    return Err(anyhow!("The db at {} is inconsistent", db_path.display()));
+                }
+            }
+            ToolCommand::Archive { cmd } => {
+                execute_archive_command(cmd).await?;
+            }
             ToolCommand::Anemo { args } => {
                 let config = crate::make_anemo_config();
                 anemo_cli::run(config, args).await
             }
+            ToolCommand::DownloadDBCheckpoint {
+                object_store_config,
+                epoch,
+                path,
+                concurrency,
+            } => {
+                let config = load_object_store_config(&object_store_config)?;
+                let (db_checkpoint_path, stats) =
+                    download_db_checkpoint(&config, epoch, &path, concurrency).await?;
+                println!(
+                    "Downloaded {} of {} files ({} bytes), the db checkpoint is at {}",
+                    stats.files - stats.skipped_files,
+                    stats.files,
+                    stats.downloaded_bytes,
+                    db_checkpoint_path.display()
+                );
+            }
             ToolCommand::RestoreFromDBCheckpoint {
                 config_path,
                 db_checkpoint_path,
@@ -0,0 +1,240 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Analysis of the contention on shared objects over a range of checkpoints, to find the objects
+//! which congest the network.
+
+use anyhow::{anyhow, Result};
+use futures::future::join_all;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use sui_core::authority_client::{AuthorityAPI, NetworkAuthorityClient};
+use sui_types::base_types::{AuthorityName, ObjectID, TransactionDigest};
+use sui_types::messages::{TransactionDataAPI, TransactionInfoRequest, TransactionInfoResponse};
+use sui_types::messages_checkpoint::{
+    CheckpointContents, CheckpointRequest, CheckpointResponse, CheckpointSequenceNumber,
+};
+
+use crate::make_clients;
+
+/// Transactions touching one shared object.
+#[derive(Debug)]
+pub struct ObjectContention {
+    pub id: ObjectID,
+    pub transactions: u64,
+    /// Transactions taking the object by mutable reference, which are sequenced one after another.
+    pub mutable_transactions: u64,
+    /// Number of transactions touching the object in each checkpoint where it was touched.
+    pub transactions_per_checkpoint: BTreeMap<CheckpointSequenceNumber, u64>,
+    /// Gas price of the transactions, sorted.
+    pub gas_prices: Vec<u64>,
+}
+
+impl ObjectContention {
+    fn new(id: ObjectID) -> Self {
+        Self {
+            id,
+            transactions: 0,
+            mutable_transactions: 0,
+            transactions_per_checkpoint: BTreeMap::new(),
+            gas_prices: vec![],
+        }
+    }
+
+    /// The gas price under which `percentile` percent of the transactions are.
+    pub fn gas_price_percentile(&self, percentile: usize) -> u64 {
+        let index = (self.gas_prices.len() * percentile / 100).min(self.gas_prices.len() - 1);
+        self.gas_prices[index]
+    }
+}
+
+/// Contention on the shared objects of the transactions of a range of checkpoints.
+///
+/// Validators don't defer the transactions of congested objects to later consensus rounds, they
+/// sequence them one after another in the same commit. Instead of deferrals, the report shows how
+/// many transactions touched each hotspot in the same checkpoint.
+#[derive(Debug)]
+pub struct ContentionReport {
+    pub from: CheckpointSequenceNumber,
+    pub to: CheckpointSequenceNumber,
+    pub transactions: u64,
+    pub shared_object_transactions: u64,
+    /// The most contended objects, most touched first.
+    pub hotspots: Vec<ObjectContention>,
+}
+
+impl std::fmt::Display for ContentionReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Checkpoints {} to {}: {} transactions, {} with shared objects",
+            self.from, self.to, self.transactions, self.shared_object_transactions
+        )?;
+        writeln!(
+            f,
+            "{:<66} {:>8} {:>8} {:>11} {:>10} {:>10} {:>9} {:>9} {:>9} {:>9}",
+            "object",
+            "txs",
+            "mut_txs",
+            "checkpoints",
+            "avg_txs/cp",
+            "max_txs/cp",
+            "gas_min",
+            "gas_p50",
+            "gas_p90",
+            "gas_max",
+        )?;
+        for hotspot in &self.hotspots {
+            let checkpoints = hotspot.transactions_per_checkpoint.len() as u64;
+            let max_per_checkpoint = hotspot
+                .transactions_per_checkpoint
+                .values()
+                .max()
+                .copied()
+                .unwrap_or_default();
+            writeln!(
+                f,
+                "{:<66} {:>8} {:>8} {:>11} {:>10.2} {:>10} {:>9} {:>9} {:>9} {:>9}",
+                hotspot.id,
+                hotspot.transactions,
+                hotspot.mutable_transactions,
+                checkpoints,
+                hotspot.transactions as f64 / checkpoints as f64,
+                max_per_checkpoint,
+                hotspot.gas_prices[0],
+                hotspot.gas_price_percentile(50),
+                hotspot.gas_price_percentile(90),
+                hotspot.gas_prices[hotspot.gas_prices.len() - 1],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+type Clients = BTreeMap<AuthorityName, (sui_types::multiaddr::Multiaddr, NetworkAuthorityClient)>;
+
+/// Fetches the contents of a checkpoint from the first validator which has it.
+async fn fetch_checkpoint_contents(
+    clients: &Clients,
+    sequence_number: CheckpointSequenceNumber,
+) -> Result<CheckpointContents> {
+    for (name, (_, client)) in clients {
+        let resp = client
+            .handle_checkpoint(CheckpointRequest {
+                sequence_number: Some(sequence_number),
+                request_content: true,
+            })
+            .await;
+        match resp {
+            Ok(CheckpointResponse {
+                contents: Some(contents),
+                ..
+            }) => return Ok(contents),
+            Ok(_) => eprintln!(
+                "Checkpoint {sequence_number} not found on {:?}",
+                name.concise()
+            ),
+            Err(err) => eprintln!(
+                "Failed to fetch checkpoint {sequence_number} from {:?}: {err}",
+                name.concise()
+            ),
+        }
+    }
+    Err(anyhow!(
+        "Checkpoint {sequence_number} not found on any validator"
+    ))
+}
+
+/// Fetches a transaction from the first validator which has it.
+async fn fetch_transaction(
+    clients: &Clients,
+    digest: TransactionDigest,
+) -> Result<TransactionInfoResponse> {
+    for (name, (_, client)) in clients {
+        match client
+            .handle_transaction_info_request(TransactionInfoRequest {
+                transaction_digest: digest,
+            })
+            .await
+        {
+            Ok(resp) => return Ok(resp),
+            Err(err) => eprintln!(
+                "Failed to fetch transaction {digest} from {:?}: {err}",
+                name.concise()
+            ),
+        }
+    }
+    Err(anyhow!("Transaction {digest} not found on any validator"))
+}
+
+/// Downloads the checkpoints `from..=to` and their transactions from the validators of the
+/// genesis, and reports the `top` shared objects touched by the most transactions.
+pub async fn analyze_contention(
+    genesis: PathBuf,
+    from: CheckpointSequenceNumber,
+    to: CheckpointSequenceNumber,
+    top: usize,
+) -> Result<ContentionReport> {
+    if from > to {
+        return Err(anyhow!("--from {from} is after --to {to}"));
+    }
+    let clients = make_clients(genesis)?;
+
+    let mut transactions = 0;
+    let mut shared_object_transactions = 0;
+    let mut objects: BTreeMap<ObjectID, ObjectContention> = BTreeMap::new();
+    for sequence_number in from..=to {
+        let contents = fetch_checkpoint_contents(&clients, sequence_number).await?;
+        let responses = join_all(
+            contents
+                .iter()
+                .map(|digests| fetch_transaction(&clients, digests.transaction)),
+        )
+        .await;
+
+        for resp in responses {
+            let tx = resp?.transaction;
+            let data = tx.transaction_data();
+            transactions += 1;
+            let shared_objects = data.shared_input_objects();
+            if shared_objects.is_empty() {
+                continue;
+            }
+            shared_object_transactions += 1;
+            // A transaction may take the same object more than once.
+            let mut seen = BTreeSet::new();
+            for object in shared_objects {
+                if !seen.insert(object.id) {
+                    continue;
+                }
+                let contention = objects
+                    .entry(object.id)
+                    .or_insert_with(|| ObjectContention::new(object.id));
+                contention.transactions += 1;
+                if object.mutable {
+                    contention.mutable_transactions += 1;
+                }
+                *contention
+                    .transactions_per_checkpoint
+                    .entry(sequence_number)
+                    .or_default() += 1;
+                contention.gas_prices.push(data.gas_price());
+            }
+        }
+    }
+
+    let mut hotspots: Vec<_> = objects.into_values().collect();
+    hotspots.sort_by(|a, b| b.transactions.cmp(&a.transactions));
+    hotspots.truncate(top);
+    for hotspot in &mut hotspots {
+        hotspot.gas_prices.sort_unstable();
+    }
+
+    Ok(ContentionReport {
+        from,
+        to,
+        transactions,
+        shared_object_transactions,
+        hotspots,
+    })
+}
@@ -0,0 +1,75 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of the invariants between the tables of the db of a node, e.g. at an epoch
+//! boundary or after restoring a db checkpoint.
+
+use anyhow::Result;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use sui_core::authority::authority_store_integrity::{
+    repair_db_integrity, verify_db_integrity, DbIntegrityReport,
+};
+use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_storage::IndexStore;
+
+pub struct DbVerifyOutput {
+    pub report: DbIntegrityReport,
+    pub repaired: Option<usize>,
+}
+
+impl DbVerifyOutput {
+    /// Whether no violation remains after the repairs, if any.
+    pub fn is_consistent(&self) -> bool {
+        self.report
+            .violations
+            .iter()
+            .all(|v| self.repaired.is_some() && v.is_repairable())
+    }
+}
+
+impl Display for DbVerifyOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let report = &self.report;
+        writeln!(
+            f,
+            "Checked {} executed transactions, {} effects, {} owned object locks, {} owner index entries",
+            report.executed_transactions, report.effects, report.locks, report.owner_index_entries
+        )?;
+        for violation in &report.violations {
+            let repairable = if violation.is_repairable() {
+                ""
+            } else {
+                " (not repairable)"
+            };
+            writeln!(f, "{violation}{repairable}")?;
+        }
+        writeln!(f, "{} violations", report.violations.len())?;
+        if let Some(repaired) = self.repaired {
+            writeln!(f, "Repaired {repaired} violations")?;
+        }
+        Ok(())
+    }
+}
+
+/// Verifies the integrity of the db of a stopped node at `db_path`, and repairs the violations
+/// which can be repaired from the other tables if `repair` is set.
+pub fn db_verify(db_path: &Path, repair: bool) -> Result<DbVerifyOutput> {
+    let perpetual_tables = AuthorityPerpetualTables::open(&db_path.join("store"), None);
+    // Only fullnodes maintain the indexes.
+    let index_path = db_path.join("indexes");
+    let index_store = index_path
+        .exists()
+        .then(|| IndexStore::new(index_path.clone()));
+    let report = verify_db_integrity(&perpetual_tables, index_store.as_ref())?;
+    let repaired = if repair {
+        Some(repair_db_integrity(
+            &perpetual_tables,
+            index_store.as_ref(),
+            &report.violations,
+        )?)
+    } else {
+        None
+    };
+    Ok(DbVerifyOutput { report, repaired })
+}
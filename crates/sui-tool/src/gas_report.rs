@@ -0,0 +1,75 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Export of the gas paid by addresses in every epoch, served by the `suix_getAddressGasUsage`
+//! method of an indexer, as CSV for accounting.
+
+use anyhow::Result;
+use jsonrpsee::http_client::HttpClientBuilder;
+use std::io;
+use sui_json_rpc::api::ExtendedApiClient;
+use sui_json_rpc_types::AddressGasUsage;
+use sui_types::base_types::{EpochId, SuiAddress};
+
+const CSV_HEADER: [&str; 8] = [
+    "address",
+    "epoch",
+    "transaction_count",
+    "computation_cost",
+    "storage_cost",
+    "storage_rebate",
+    "non_refundable_storage_fee",
+    "net_gas_cost",
+];
+
+/// Fetches the gas usage of every address from the indexer at `rpc_url`, and writes it to
+/// `writer`, one row per address and epoch, in the order of `addresses` then of epochs.
+pub async fn export_gas_usage(
+    rpc_url: &str,
+    addresses: &[SuiAddress],
+    from_epoch: Option<EpochId>,
+    to_epoch: Option<EpochId>,
+    writer: impl io::Write,
+) -> Result<()> {
+    let client = HttpClientBuilder::default().build(rpc_url)?;
+    let mut usage = vec![];
+    for address in addresses {
+        let mut cursor = None;
+        loop {
+            let page = client
+                .get_address_gas_usage(
+                    *address,
+                    from_epoch.map(Into::into),
+                    to_epoch.map(Into::into),
+                    cursor,
+                    None,
+                )
+                .await?;
+            usage.extend(page.data);
+            if !page.has_next_page {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+    }
+    write_csv(&usage, writer)
+}
+
+fn write_csv(usage: &[AddressGasUsage], writer: impl io::Write) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(writer);
+    writer.write_record(CSV_HEADER)?;
+    for u in usage {
+        writer.write_record([
+            u.address.to_string(),
+            u.epoch.to_string(),
+            u.transaction_count.to_string(),
+            u.computation_cost.to_string(),
+            u.storage_cost.to_string(),
+            u.storage_rebate.to_string(),
+            u.non_refundable_storage_fee.to_string(),
+            u.net_gas_cost.to_string(),
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -7,11 +7,15 @@ use futures::future::join_all;
 use itertools::Itertools;
 use std::collections::BTreeMap;
 use std::fmt::Write;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::{fs, io};
 use sui_config::{genesis::Genesis, NodeConfig};
 use sui_core::authority_client::{AuthorityAPI, NetworkAuthorityClient};
+use sui_core::db_checkpoint_handler::SUCCESS_MARKER;
 use sui_network::default_mysten_network_config;
+use sui_storage::object_store::download::{download_recursively, DownloadStats};
+use sui_storage::object_store::ObjectStoreConfig;
 use sui_types::multiaddr::Multiaddr;
 use sui_types::object::ObjectFormatOptions;
 use sui_types::{base_types::*, messages::*, object::Owner};
@@ -19,8 +23,13 @@ use tokio::time::Instant;
 
 use anyhow::anyhow;
 
+pub mod archive;
 pub mod commands;
+pub mod contention;
 pub mod db_tool;
+pub mod db_verify;
+pub mod gas_report;
+pub mod verify_accumulator;
 
 fn make_clients(
     genesis: PathBuf,
@@ -268,6 +277,215 @@ impl std::fmt::Display for VerboseObjectOutput {
     }
 }
 
+/// Disagreements between validators on the latest version of an object. Validators which are
+/// behind report older versions, but validators at the same version should agree on the object
+/// and on its lock. An owned object locked on different transactions by different validators is
+/// the sign of a client equivocating.
+pub struct ObjectDisagreementOutput(pub ObjectData);
+
+impl ObjectDisagreementOutput {
+    /// Latest version reported by each validator, with its object digest and lock.
+    #[allow(clippy::type_complexity)]
+    fn latest_versions(
+        &self,
+    ) -> BTreeMap<SequenceNumber, Vec<(AuthorityName, ObjectDigest, Option<TransactionDigest>)>>
+    {
+        let mut versions: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for (name, _, resp) in &self.0.responses {
+            if let Some((_, Ok(resp), _)) = resp.first() {
+                let lock = resp.lock_for_debugging.as_ref().map(|lock| *lock.digest());
+                versions.entry(resp.object.version()).or_default().push((
+                    *name,
+                    resp.object.compute_object_reference().2,
+                    lock,
+                ));
+            }
+        }
+        versions
+    }
+
+    /// Whether some validators disagree, or could not be queried.
+    pub fn has_disagreements(&self) -> bool {
+        let versions = self.latest_versions();
+        let failed = self
+            .0
+            .responses
+            .iter()
+            .any(|(_, _, resp)| !matches!(resp.first(), Some((_, Ok(_), _))));
+        failed
+            || versions.len() > 1
+            || versions.values().any(|validators| {
+                validators
+                    .iter()
+                    .map(|(_, digest, lock)| (digest, lock))
+                    .unique()
+                    .count()
+                    > 1
+            })
+    }
+}
+
+fn concise_names<'a>(names: impl Iterator<Item = &'a AuthorityName>) -> String {
+    names.map(|name| format!("{}", name.concise())).join(", ")
+}
+
+impl std::fmt::Display for ObjectDisagreementOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Object: {}", self.0.requested_id)?;
+
+        let failed = self
+            .0
+            .responses
+            .iter()
+            .filter(|(_, _, resp)| !matches!(resp.first(), Some((_, Ok(_), _))))
+            .map(|(name, _, _)| name);
+        let failed = concise_names(failed);
+        if !failed.is_empty() {
+            writeln!(f, "FAILED to fetch the object from: {failed}")?;
+        }
+
+        let versions = self.latest_versions();
+        if versions.len() > 1 {
+            writeln!(f, "DISAGREEMENT on the latest version:")?;
+            for (version, validators) in &versions {
+                writeln!(
+                    f,
+                    "        {:<8} {}",
+                    version.value(),
+                    concise_names(validators.iter().map(|(name, _, _)| name))
+                )?;
+            }
+        }
+
+        for (version, validators) in &versions {
+            let digests = validators.iter().into_group_map_by(|(_, digest, _)| digest);
+            if digests.len() > 1 {
+                writeln!(
+                    f,
+                    "DISAGREEMENT on the object at version {}:",
+                    version.value()
+                )?;
+                for (digest, validators) in &digests {
+                    writeln!(
+                        f,
+                        "        {:<44} {}",
+                        digest,
+                        concise_names(validators.iter().map(|(name, _, _)| name))
+                    )?;
+                }
+            }
+
+            let locks = validators.iter().into_group_map_by(|(_, _, lock)| lock);
+            let locked_txs = locks.keys().filter(|lock| lock.is_some()).count();
+            if locks.len() > 1 {
+                if locked_txs > 1 {
+                    writeln!(
+                        f,
+                        "EQUIVOCATION: version {} is locked on {} different transactions:",
+                        version.value(),
+                        locked_txs
+                    )?;
+                } else {
+                    writeln!(
+                        f,
+                        "DISAGREEMENT on the lock of version {}:",
+                        version.value()
+                    )?;
+                }
+                for (lock, validators) in &locks {
+                    writeln!(
+                        f,
+                        "        {:<44} {}",
+                        lock.opt_display("not-locked"),
+                        concise_names(validators.iter().map(|(name, _, _)| name))
+                    )?;
+                }
+            }
+        }
+
+        if !self.has_disagreements() {
+            let version = versions.keys().next().opt_debug("-");
+            writeln!(
+                f,
+                "All validators agree on version {version}, object and lock"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The lock of an owned object version on each validator, grouped by the transaction locking it.
+/// Validators lock the owned objects of a transaction when signing it, a transaction failing
+/// because its objects are "locked by another transaction" was signed by validators which had
+/// already signed another transaction using the same object versions.
+pub struct ObjectLockOutput(pub ObjectData);
+
+impl ObjectLockOutput {
+    fn locks(&self) -> BTreeMap<String, Vec<AuthorityName>> {
+        let mut locks: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for (name, _, resp) in &self.0.responses {
+            let lock = match resp.first() {
+                Some((_, Ok(resp), _)) if !resp.object.is_address_owned() => {
+                    "not-owned".to_string()
+                }
+                Some((_, Ok(resp), _)) => resp
+                    .lock_for_debugging
+                    .as_ref()
+                    .map(|lock| format!("locked by {}", lock.digest()))
+                    .unwrap_or_else(|| "not-locked".to_string()),
+                Some((_, Err(e), _)) => format!("error: {e}"),
+                None => "no-response".to_string(),
+            };
+            locks.entry(lock).or_default().push(*name);
+        }
+        locks
+    }
+
+    /// Number of different transactions locking the object version.
+    pub fn locked_transactions(&self) -> usize {
+        self.0
+            .responses
+            .iter()
+            .filter_map(|(_, _, resp)| match resp.first() {
+                Some((_, Ok(resp), _)) => resp.lock_for_debugging.as_ref().map(|l| *l.digest()),
+                _ => None,
+            })
+            .unique()
+            .count()
+    }
+}
+
+impl std::fmt::Display for ObjectLockOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let version = self
+            .0
+            .responses
+            .iter()
+            .find_map(|(_, _, resp)| resp.first().and_then(|(version, _, _)| *version));
+        writeln!(
+            f,
+            "Object: {}, version: {}",
+            self.0.requested_id,
+            version.map(|v| v.value()).opt_debug("-")
+        )?;
+        let total = self.0.responses.len();
+        for (lock, validators) in self.locks() {
+            writeln!(f, "{lock} ({}/{total} validators)", validators.len())?;
+            writeln!(f, "        {}", concise_names(validators.iter()))?;
+        }
+        let locked_transactions = self.locked_transactions();
+        if locked_transactions > 1 {
+            writeln!(
+                f,
+                "EQUIVOCATION: the version is locked by {locked_transactions} different transactions. \
+                 Unless one of them is certified by a quorum of validators, the object cannot be \
+                 used until the end of the epoch."
+            )?;
+        }
+        Ok(())
+    }
+}
+
 pub async fn get_object(
     obj_id: ObjectID,
     version: Option<u64>,
@@ -557,6 +775,30 @@ fn copy_dir_all(
     Ok(())
 }
 
+/// Loads the yaml config of an object store.
+pub fn load_object_store_config(path: &Path) -> Result<ObjectStoreConfig> {
+    Ok(serde_yaml::from_reader(fs::File::open(path)?)?)
+}
+
+/// Downloads the db checkpoint of `epoch` uploaded to an object store by the db checkpoint
+/// handler of a node to `path`, from which it can be restored with [restore_from_db_checkpoint].
+/// An interrupted download resumes with the files missing from `path`.
+pub async fn download_db_checkpoint(
+    object_store_config: &ObjectStoreConfig,
+    epoch: u32,
+    path: &Path,
+    concurrency: NonZeroUsize,
+) -> Result<(PathBuf, DownloadStats)> {
+    let store = object_store_config.make()?;
+    let dir = object_store::path::Path::from(format!("epoch_{epoch}"));
+    store
+        .get(&dir.child(SUCCESS_MARKER))
+        .await
+        .map_err(|e| anyhow!("The db checkpoint of epoch {epoch} is not fully uploaded: {e}"))?;
+    let stats = download_recursively(&dir, store, path, concurrency).await?;
+    Ok((path.join(dir.to_string()), stats))
+}
+
 pub async fn restore_from_db_checkpoint(
     config: &NodeConfig,
     db_checkpoint_path: &Path,
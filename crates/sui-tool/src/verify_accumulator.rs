@@ -0,0 +1,70 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Independent verification of the state accumulator of an epoch from the db of a node, e.g.
+//! after the node restarted from a crash or was restored from a db checkpoint.
+
+use anyhow::Result;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_core::checkpoints::CheckpointStore;
+use sui_core::state_accumulator::{audit_epoch_accumulator, EpochAccumulatorAudit};
+use sui_types::base_types::EpochId;
+use sui_types::messages_checkpoint::ECMHLiveObjectSetDigest;
+
+pub struct AccumulatorOutput(pub EpochAccumulatorAudit);
+
+fn digest_output(digest: &Option<ECMHLiveObjectSetDigest>) -> String {
+    match digest {
+        Some(digest) => format!("{:?}", digest.digest),
+        None => "none".to_string(),
+    }
+}
+
+impl Display for AccumulatorOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let audit = &self.0;
+        writeln!(
+            f,
+            "Epoch {}, last checkpoint {}: {} live objects",
+            audit.epoch, audit.last_checkpoint, audit.live_objects
+        )?;
+        writeln!(f, "replayed:  {:?}", audit.replayed.digest)?;
+        writeln!(f, "committed: {}", digest_output(&audit.committed))?;
+        writeln!(f, "local:     {}", digest_output(&audit.local))?;
+        if audit.mismatches.is_empty() {
+            return Ok(());
+        }
+        writeln!(
+            f,
+            "{:<66} {:>8} {:<44} {:<44}",
+            "object", "version", "expected_digest", "stored_digest"
+        )?;
+        for mismatch in &audit.mismatches {
+            writeln!(
+                f,
+                "{:<66} {:>8} {:<44} {:<44}",
+                mismatch.object_id,
+                mismatch.version.value(),
+                mismatch.expected.to_string(),
+                mismatch
+                    .actual
+                    .map_or_else(|| "missing".to_string(), |d| d.to_string()),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Recomputes the state accumulator at the end of `epoch` from the db of a stopped node at
+/// `db_path`, see [audit_epoch_accumulator].
+pub fn verify_accumulator(db_path: &Path, epoch: EpochId) -> Result<EpochAccumulatorAudit> {
+    let perpetual_tables = AuthorityPerpetualTables::open(&db_path.join("store"), None);
+    let checkpoint_store = CheckpointStore::new(&db_path.join("checkpoints"));
+    Ok(audit_epoch_accumulator(
+        &perpetual_tables,
+        &checkpoint_store,
+        epoch,
+    )?)
+}
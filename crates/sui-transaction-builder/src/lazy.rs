@@ -0,0 +1,284 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Programmable transactions whose object inputs are given by ID only, and resolved by
+//! [TransactionBuilder::resolve_lazy_transaction] when the transaction is built: the versions,
+//! digests and owners of the objects are fetched in a single batch, and shared objects are taken
+//! mutably unless every use of them is an immutable reference parameter of a Move call.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use anyhow::{anyhow, bail, Ok};
+use futures::future::{try_join, try_join_all};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::TypeTag;
+use serde::Serialize;
+
+use sui_adapter::execution_mode::ExecutionMode;
+use sui_json_rpc_types::{SuiMoveNormalizedType, SuiObjectDataOptions};
+use sui_types::base_types::{ObjectID, SuiAddress, TX_CONTEXT_MODULE_NAME, TX_CONTEXT_STRUCT_NAME};
+use sui_types::messages::{
+    Argument, CallArg, Command, ObjectArg, ProgrammableMoveCall, ProgrammableTransaction,
+    TransactionData,
+};
+use sui_types::object::Owner;
+use sui_types::SUI_FRAMEWORK_ADDRESS;
+
+use crate::TransactionBuilder;
+
+#[derive(Debug, Clone)]
+enum LazyInput {
+    Pure(Vec<u8>),
+    Object(ObjectID),
+}
+
+/// A programmable transaction under construction, whose object inputs are only known by ID.
+/// Each object is a single input of the transaction, however many times it is added.
+#[derive(Debug, Clone, Default)]
+pub struct LazyProgrammableTransaction {
+    inputs: Vec<LazyInput>,
+    objects: HashMap<ObjectID, u16>,
+    commands: Vec<Command>,
+}
+
+impl LazyProgrammableTransaction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the object `id` as an input of the transaction, or returns the existing input if it
+    /// already is one.
+    pub fn object(&mut self, id: ObjectID) -> Argument {
+        let next = self.inputs.len() as u16;
+        let index = *self.objects.entry(id).or_insert(next);
+        if index == next {
+            self.inputs.push(LazyInput::Object(id));
+        }
+        Argument::Input(index)
+    }
+
+    /// Adds the BCS serialization of `value` as an input of the transaction.
+    pub fn pure<T: Serialize>(&mut self, value: &T) -> anyhow::Result<Argument> {
+        Ok(self.pure_bytes(bcs::to_bytes(value)?))
+    }
+
+    pub fn pure_bytes(&mut self, bytes: Vec<u8>) -> Argument {
+        self.inputs.push(LazyInput::Pure(bytes));
+        Argument::Input(self.inputs.len() as u16 - 1)
+    }
+
+    /// Adds a call of `package::module::function`, whose arguments are checked against the
+    /// signature of the function when the transaction is resolved. Returns the result of the call.
+    pub fn move_call(
+        &mut self,
+        package: ObjectID,
+        module: Identifier,
+        function: Identifier,
+        type_arguments: Vec<TypeTag>,
+        arguments: Vec<Argument>,
+    ) -> Argument {
+        self.command(Command::move_call(
+            package,
+            module,
+            function,
+            type_arguments,
+            arguments,
+        ))
+    }
+
+    pub fn command(&mut self, command: Command) -> Argument {
+        self.commands.push(command);
+        Argument::Result(self.commands.len() as u16 - 1)
+    }
+
+    /// Commands of the transaction, as added so far.
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    fn object_ids(&self) -> Vec<ObjectID> {
+        self.inputs
+            .iter()
+            .filter_map(|input| match input {
+                LazyInput::Object(id) => Some(*id),
+                LazyInput::Pure(_) => None,
+            })
+            .collect()
+    }
+
+    fn move_calls(&self) -> impl Iterator<Item = &ProgrammableMoveCall> {
+        self.commands.iter().filter_map(|command| match command {
+            Command::MoveCall(call) => Some(call.as_ref()),
+            _ => None,
+        })
+    }
+}
+
+impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
+    /// Resolves the object inputs of `lazy` into the [ProgrammableTransaction] they stand for.
+    /// The objects are fetched in a single batch, together with the signatures of the functions
+    /// called, which the data reader may cache.
+    pub async fn resolve_lazy_transaction(
+        &self,
+        lazy: LazyProgrammableTransaction,
+    ) -> anyhow::Result<ProgrammableTransaction> {
+        let functions: Vec<_> = lazy
+            .move_calls()
+            .map(|call| (call.package, call.module.as_str(), call.function.as_str()))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let (objects, signatures) = try_join(
+            self.0.multi_get_objects_with_options(
+                lazy.object_ids(),
+                SuiObjectDataOptions::new().with_owner(),
+            ),
+            try_join_all(functions.iter().map(|(package, module, function)| {
+                self.0.get_function_signature(*package, module, function)
+            })),
+        )
+        .await?;
+        let mut parameters = BTreeMap::new();
+        for ((package, module, function), signature) in functions.into_iter().zip(signatures) {
+            if !Mode::allow_arbitrary_function_calls() && !signature.is_entry {
+                bail!("{package}::{module}::{function} is not an entry function");
+            }
+            let mut params = signature.parameters;
+            if params.last().map_or(false, is_tx_context) {
+                params.pop();
+            }
+            parameters.insert((package, module, function), params);
+        }
+
+        let immutable_uses = immutable_uses(&lazy, &parameters)?;
+        let mut objects = objects
+            .into_iter()
+            .map(|response| {
+                let object = response.into_object()?;
+                let Some(owner) = object.owner else {
+                    bail!("Owner field in object [{}] is missing.", object.object_id);
+                };
+                Ok((object.object_id, (object.object_ref(), owner)))
+            })
+            .collect::<anyhow::Result<BTreeMap<_, _>>>()?;
+
+        let mut inputs = vec![];
+        for (index, input) in lazy.inputs.into_iter().enumerate() {
+            inputs.push(match input {
+                LazyInput::Pure(bytes) => CallArg::Pure(bytes),
+                LazyInput::Object(id) => {
+                    let (object_ref, owner) = objects
+                        .remove(&id)
+                        .ok_or_else(|| anyhow!("Object [{id}] was not returned"))?;
+                    CallArg::Object(match owner {
+                        Owner::Shared {
+                            initial_shared_version,
+                        } => ObjectArg::SharedObject {
+                            id,
+                            initial_shared_version,
+                            mutable: !immutable_uses.contains(&(index as u16)),
+                        },
+                        Owner::AddressOwner(_) | Owner::ObjectOwner(_) | Owner::Immutable => {
+                            ObjectArg::ImmOrOwnedObject(object_ref)
+                        }
+                    })
+                }
+            });
+        }
+        Ok(ProgrammableTransaction {
+            inputs,
+            commands: lazy.commands,
+        })
+    }
+
+    /// Resolves `lazy` and wraps it into [TransactionData], as
+    /// [Self::finish_programmable_transaction].
+    pub async fn finish_lazy_transaction(
+        &self,
+        signer: SuiAddress,
+        lazy: LazyProgrammableTransaction,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> anyhow::Result<TransactionData> {
+        let pt = self.resolve_lazy_transaction(lazy).await?;
+        self.finish_programmable_transaction(signer, pt, gas, gas_budget)
+            .await
+    }
+}
+
+/// Returns the inputs of `lazy` only used as immutable reference arguments of Move calls, after
+/// checking that every call has as many arguments as its function has `parameters`.
+fn immutable_uses(
+    lazy: &LazyProgrammableTransaction,
+    parameters: &BTreeMap<(ObjectID, &str, &str), Vec<SuiMoveNormalizedType>>,
+) -> anyhow::Result<BTreeSet<u16>> {
+    let mut immutable = BTreeSet::new();
+    let mut mutable = BTreeSet::new();
+    for command in &lazy.commands {
+        let Command::MoveCall(call) = command else {
+            mutable.extend(command_inputs(command));
+            continue;
+        };
+        let params = &parameters[&(call.package, call.module.as_str(), call.function.as_str())];
+        if call.arguments.len() != params.len() {
+            bail!(
+                "Expected {} args for {}::{}::{}, found {}",
+                params.len(),
+                call.package,
+                call.module,
+                call.function,
+                call.arguments.len()
+            );
+        }
+        for (arg, param) in call.arguments.iter().zip(params) {
+            if let Argument::Input(index) = arg {
+                if matches!(param, SuiMoveNormalizedType::Reference(_)) {
+                    immutable.insert(*index);
+                } else {
+                    mutable.insert(*index);
+                }
+            }
+        }
+    }
+    Ok(immutable.difference(&mutable).copied().collect())
+}
+
+/// Whether `param` is the `TxContext` passed by the runtime to the functions taking one last.
+fn is_tx_context(param: &SuiMoveNormalizedType) -> bool {
+    match param {
+        SuiMoveNormalizedType::Reference(inner)
+        | SuiMoveNormalizedType::MutableReference(inner) => {
+            matches!(
+                inner.as_ref(),
+                SuiMoveNormalizedType::Struct { address, module, name, .. }
+                    if AccountAddress::from_hex_literal(address).ok() == Some(SUI_FRAMEWORK_ADDRESS)
+                        && module == TX_CONTEXT_MODULE_NAME.as_str()
+                        && name == TX_CONTEXT_STRUCT_NAME.as_str()
+            )
+        }
+        _ => false,
+    }
+}
+
+/// Returns the inputs used directly by `command`.
+fn command_inputs(command: &Command) -> Vec<u16> {
+    let arguments: Vec<&Argument> = match command {
+        Command::MoveCall(call) => call.arguments.iter().collect(),
+        Command::TransferObjects(objects, recipient) => {
+            objects.iter().chain(std::iter::once(recipient)).collect()
+        }
+        Command::SplitCoins(coin, amounts) => std::iter::once(coin).chain(amounts.iter()).collect(),
+        Command::MergeCoins(coin, coins) => std::iter::once(coin).chain(coins.iter()).collect(),
+        Command::MakeMoveVec(_, elements) => elements.iter().collect(),
+        Command::Upgrade(_, _, _, ticket) => vec![ticket],
+        Command::Publish(_, _) => vec![],
+    };
+    arguments
+        .into_iter()
+        .filter_map(|arg| match arg {
+            Argument::Input(index) => Some(*index),
+            _ => None,
+        })
+        .collect()
+}
@@ -12,15 +12,19 @@ use async_trait::async_trait;
 use futures::future::join_all;
 use move_binary_format::file_format::SignatureToken;
 use move_binary_format::file_format_common::VERSION_MAX;
+use move_core_types::ident_str;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{StructTag, TypeTag};
 
 use sui_adapter::adapter::{resolve_and_type_check, CheckCallArg};
 use sui_adapter::execution_mode::ExecutionMode;
-use sui_json::{resolve_move_function_args, ResolvedCallArg, SuiJsonValue};
+use sui_json::{
+    resolve_call_arg, resolve_move_function_args, resolve_move_function_params, ResolvedCallArg,
+    SuiJsonLimits, SuiJsonValue,
+};
 use sui_json_rpc_types::{
-    RPCTransactionRequestParams, SuiData, SuiObjectDataOptions, SuiObjectResponse, SuiRawData,
-    SuiTypeTag,
+    RPCTransactionRequestParams, SuiData, SuiMoveNormalizedFunction, SuiMoveNormalizedModule,
+    SuiObjectDataOptions, SuiObjectResponse, SuiRawData, SuiTypeTag,
 };
 use sui_protocol_config::ProtocolConfig;
 use sui_types::base_types::{ObjectID, ObjectInfo, ObjectRef, ObjectType, SuiAddress};
@@ -28,9 +32,10 @@ use sui_types::error::UserInputError;
 use sui_types::gas_coin::GasCoin;
 use sui_types::governance::{ADD_STAKE_MUL_COIN_FUN_NAME, WITHDRAW_STAKE_FUN_NAME};
 use sui_types::messages::{
-    Argument, CallArg, Command, InputObjectKind, ObjectArg, TransactionData, TransactionKind,
+    Argument, CallArg, Command, InputObjectKind, ObjectArg, ProgrammableTransaction,
+    TransactionData, TransactionKind,
 };
-use sui_types::move_package::MovePackage;
+use sui_types::move_package::{normalize_modules, MovePackage};
 use sui_types::object::{Object, Owner};
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
@@ -39,6 +44,22 @@ use sui_types::{
     SUI_SYSTEM_STATE_OBJECT_SHARED_VERSION,
 };
 
+pub use lazy::LazyProgrammableTransaction;
+
+mod lazy;
+
+/// A restriction of the upgrades an `UpgradeCap` allows, applied by the entry functions of
+/// `sui::package`. Restrictions can only be tightened, never loosened.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UpgradeCapRestriction {
+    /// Only allow upgrades adding new code to the package, `package::only_additive_upgrades`.
+    Additive,
+    /// Only allow upgrades changing the dependencies of the package, `package::only_dep_upgrades`.
+    DepOnly,
+    /// Disallow any further upgrade by destroying the capability, `package::make_immutable`.
+    Immutable,
+}
+
 #[async_trait]
 pub trait DataReader {
     async fn get_owned_objects(
@@ -53,9 +74,69 @@ pub trait DataReader {
         options: SuiObjectDataOptions,
     ) -> Result<SuiObjectResponse, anyhow::Error>;
 
+    /// Fetches the objects `object_ids`, in the same order. Readers able to fetch several
+    /// objects at once should override the default, which fetches them one by one.
+    async fn multi_get_objects_with_options(
+        &self,
+        object_ids: Vec<ObjectID>,
+        options: SuiObjectDataOptions,
+    ) -> Result<Vec<SuiObjectResponse>, anyhow::Error> {
+        join_all(
+            object_ids
+                .into_iter()
+                .map(|id| self.get_object_with_options(id, options.clone())),
+        )
+        .await
+        .into_iter()
+        .collect()
+    }
+
+    /// Signature of the public or entry function `package::module::function`. Readers caching
+    /// packages should override the default, which fetches the whole package on every call.
+    async fn get_function_signature(
+        &self,
+        package: ObjectID,
+        module: &str,
+        function: &str,
+    ) -> Result<SuiMoveNormalizedFunction, anyhow::Error> {
+        let object = self
+            .get_object_with_options(package, SuiObjectDataOptions::bcs_lossless())
+            .await?
+            .into_object()?;
+        let Some(SuiRawData::Package(raw_package)) = object.bcs else {
+            bail!("Bcs field in object [{}] is missing or not a package.", package);
+        };
+        let bytes = raw_package
+            .module_map
+            .get(module)
+            .ok_or_else(|| anyhow!("No module {module} in package [{package}]"))?;
+        let normalized: SuiMoveNormalizedModule =
+            normalize_modules(std::iter::once(bytes), VERSION_MAX)?
+                .remove(module)
+                .ok_or_else(|| anyhow!("No module {module} in package [{package}]"))?
+                .into();
+        normalized
+            .exposed_functions
+            .get(function)
+            .cloned()
+            .ok_or_else(|| {
+                anyhow!("No function {function} in module {module} of package [{package}]")
+            })
+    }
+
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error>;
 }
 
+/// An argument of [TransactionBuilder::programmable_move_call].
+#[derive(Debug, Clone)]
+pub enum PTBArg {
+    /// A value resolved against the type of the parameter, as the arguments of
+    /// [TransactionBuilder::move_call].
+    Json(SuiJsonValue),
+    /// An argument of the transaction, like the gas coin or the result of a previous command.
+    Argument(Argument),
+}
+
 #[derive(Clone)]
 pub struct TransactionBuilder<Mode: ExecutionMode>(
     Arc<dyn DataReader + Sync + Send>,
@@ -278,7 +359,19 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             call_args,
         )
         .await?;
-        let pt = builder.finish();
+        self.finish_programmable_transaction(signer, builder.finish(), gas, gas_budget)
+            .await
+    }
+
+    /// Wraps a programmable transaction into [TransactionData], paid with `gas`, or with a gas
+    /// coin of `signer` which isn't an input of the transaction if `gas` is None.
+    pub async fn finish_programmable_transaction(
+        &self,
+        signer: SuiAddress,
+        pt: ProgrammableTransaction,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> anyhow::Result<TransactionData> {
         let input_objects = pt
             .input_objects()?
             .iter()
@@ -330,11 +423,105 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         Ok(())
     }
 
+    /// Adds a move call to `builder`, whose arguments are either JSON values, resolved against the
+    /// signature of the function, or arguments of the transaction, like the results of previous
+    /// commands. Returns the result of the call.
+    ///
+    /// Unlike [Self::single_move_call], the arguments are not type checked before execution,
+    /// as the types of the results of previous commands are not known.
+    pub async fn programmable_move_call(
+        &self,
+        builder: &mut ProgrammableTransactionBuilder,
+        package_id: ObjectID,
+        module: &str,
+        function: &str,
+        type_args: Vec<SuiTypeTag>,
+        call_args: Vec<PTBArg>,
+    ) -> anyhow::Result<Argument> {
+        let module = Identifier::from_str(module)?;
+        let function = Identifier::from_str(function)?;
+        let type_args = type_args
+            .into_iter()
+            .map(|ty| ty.try_into())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let package = self.get_move_package(package_id).await?;
+        let (compiled_module, parameters) = resolve_move_function_params(
+            &package,
+            &module,
+            &function,
+            Mode::allow_arbitrary_function_calls(),
+        )?;
+        if call_args.len() != parameters.len() {
+            bail!(
+                "Expected {} args, found {}",
+                parameters.len(),
+                call_args.len()
+            );
+        }
+
+        let limits = SuiJsonLimits::default();
+        let mut objects = BTreeMap::new();
+        let mut args = vec![];
+        for (idx, (arg, param)) in call_args.into_iter().zip(parameters).enumerate() {
+            let json = match arg {
+                PTBArg::Argument(arg) => {
+                    args.push(arg);
+                    continue;
+                }
+                PTBArg::Json(json) => json,
+            };
+            let is_mutable_ref = matches!(param, SignatureToken::MutableReference(_));
+            args.push(
+                match resolve_call_arg(
+                    &package,
+                    &compiled_module,
+                    &type_args,
+                    idx,
+                    &json,
+                    &param,
+                    &limits,
+                )? {
+                    ResolvedCallArg::Pure(bytes) => builder.input(CallArg::Pure(bytes))?,
+                    ResolvedCallArg::Object(id) => builder.obj(
+                        self.get_object_arg(id, &mut objects, is_mutable_ref)
+                            .await?,
+                    )?,
+                    ResolvedCallArg::ObjVec(ids) => {
+                        let mut obj_args = vec![];
+                        for id in ids {
+                            obj_args.push(
+                                self.get_object_arg(id, &mut objects, is_mutable_ref)
+                                    .await?,
+                            );
+                        }
+                        builder.make_obj_vec(obj_args)?
+                    }
+                },
+            );
+        }
+
+        Ok(builder.programmable_move_call(package_id, module, function, type_args, args))
+    }
+
+    /// Adds the object `id` as an input of `builder`. Shared objects are taken by mutable
+    /// reference.
+    pub async fn input_object(
+        &self,
+        builder: &mut ProgrammableTransactionBuilder,
+        id: ObjectID,
+    ) -> anyhow::Result<Argument> {
+        let obj_arg = self
+            .get_object_arg(id, &mut BTreeMap::new(), /* is_mutable_ref */ true)
+            .await?;
+        builder.obj(obj_arg)
+    }
+
     async fn get_object_arg(
         &self,
         id: ObjectID,
         objects: &mut BTreeMap<ObjectID, Object>,
-        expected_type: SignatureToken,
+        is_mutable_ref: bool,
     ) -> Result<ObjectArg, anyhow::Error> {
         let response = self
             .0
@@ -351,7 +538,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             } => ObjectArg::SharedObject {
                 id,
                 initial_shared_version,
-                mutable: matches!(expected_type, SignatureToken::MutableReference(_)),
+                mutable: is_mutable_ref,
             },
             Owner::AddressOwner(_) | Owner::ObjectOwner(_) | Owner::Immutable => {
                 ObjectArg::ImmOrOwnedObject(obj_ref)
@@ -368,22 +555,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         type_args: &[TypeTag],
         json_args: Vec<SuiJsonValue>,
     ) -> Result<Vec<Argument>, anyhow::Error> {
-        let object = self
-            .0
-            .get_object_with_options(package_id, SuiObjectDataOptions::bcs_lossless())
-            .await?
-            .into_object()?;
-        let Some(SuiRawData::Package(package)) = object.bcs else {
-            bail!("Bcs field in object [{}] is missing or not a package.", package_id);
-        };
-        let package: MovePackage = MovePackage::new(
-            package.id,
-            object.version,
-            package.module_map,
-            ProtocolConfig::get_for_min_version().max_move_package_size(),
-            package.type_origin_table,
-            package.linkage_table,
-        )?;
+        let package = self.get_move_package(package_id).await?;
 
         let json_args_and_tokens = resolve_move_function_args(
             &package,
@@ -392,20 +564,23 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             type_args,
             json_args,
             Mode::allow_arbitrary_function_calls(),
+            &SuiJsonLimits::default(),
         )?;
         let mut check_args = Vec::new();
         let mut objects = BTreeMap::new();
         for (arg, expected_type) in json_args_and_tokens {
+            let is_mutable_ref = matches!(expected_type, SignatureToken::MutableReference(_));
             check_args.push(match arg {
                 ResolvedCallArg::Object(id) => CheckCallArg::Object(
-                    self.get_object_arg(id, &mut objects, expected_type).await?,
+                    self.get_object_arg(id, &mut objects, is_mutable_ref)
+                        .await?,
                 ),
                 ResolvedCallArg::Pure(p) => CheckCallArg::Pure(p),
                 ResolvedCallArg::ObjVec(v) => {
                     let mut object_ids = vec![];
                     for id in v {
                         object_ids.push(
-                            self.get_object_arg(id, &mut objects, expected_type.clone())
+                            self.get_object_arg(id, &mut objects, is_mutable_ref)
                                 .await?,
                         );
                     }
@@ -435,6 +610,25 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         Ok(args)
     }
 
+    async fn get_move_package(&self, package_id: ObjectID) -> anyhow::Result<MovePackage> {
+        let object = self
+            .0
+            .get_object_with_options(package_id, SuiObjectDataOptions::bcs_lossless())
+            .await?
+            .into_object()?;
+        let Some(SuiRawData::Package(package)) = object.bcs else {
+            bail!("Bcs field in object [{}] is missing or not a package.", package_id);
+        };
+        Ok(MovePackage::new(
+            package.id,
+            object.version,
+            package.module_map,
+            ProtocolConfig::get_for_min_version().max_move_package_size(),
+            package.type_origin_table,
+            package.linkage_table,
+        )?)
+    }
+
     pub async fn publish(
         &self,
         sender: SuiAddress,
@@ -495,6 +689,59 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         )
     }
 
+    /// Restricts the upgrades allowed by `upgrade_capability`, which must be owned by `sender`,
+    /// or shared unless it is made immutable.
+    pub async fn restrict_upgrade_cap(
+        &self,
+        sender: SuiAddress,
+        upgrade_capability: ObjectID,
+        restriction: UpgradeCapRestriction,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> anyhow::Result<TransactionData> {
+        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas = self
+            .select_gas(sender, gas, gas_budget, vec![upgrade_capability], gas_price)
+            .await?;
+        let upgrade_cap = self
+            .0
+            .get_object_with_options(upgrade_capability, SuiObjectDataOptions::new().with_owner())
+            .await?
+            .into_object()?;
+        let cap_owner = upgrade_cap
+            .owner
+            .ok_or_else(|| anyhow!("Unable to determine ownership of upgrade capability"))?;
+        let capability_arg = match cap_owner {
+            Owner::AddressOwner(_) => ObjectArg::ImmOrOwnedObject(upgrade_cap.object_ref()),
+            Owner::Shared {
+                initial_shared_version,
+            } if restriction != UpgradeCapRestriction::Immutable => ObjectArg::SharedObject {
+                id: upgrade_capability,
+                initial_shared_version,
+                mutable: true,
+            },
+            Owner::Shared { .. } => bail!("A shared upgrade capability cannot be made immutable"),
+            Owner::Immutable => bail!("Upgrade capability is already immutable"),
+            Owner::ObjectOwner(_) => bail!("Upgrade capability controlled by object"),
+        };
+        let function = match restriction {
+            UpgradeCapRestriction::Additive => ident_str!("only_additive_upgrades"),
+            UpgradeCapRestriction::DepOnly => ident_str!("only_dep_upgrades"),
+            UpgradeCapRestriction::Immutable => ident_str!("make_immutable"),
+        };
+        TransactionData::new_move_call(
+            sender,
+            SUI_FRAMEWORK_OBJECT_ID,
+            ident_str!("package").to_owned(),
+            function.to_owned(),
+            vec![],
+            gas,
+            vec![CallArg::Object(capability_arg)],
+            gas_budget,
+            gas_price,
+        )
+    }
+
     // TODO: consolidate this with Pay transactions
     pub async fn split_coin(
         &self,
@@ -650,27 +897,8 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
                 }
             };
         }
-        let pt = builder.finish();
-        let all_inputs = pt.input_objects()?;
-        let inputs = all_inputs
-            .iter()
-            .flat_map(|obj| match obj {
-                InputObjectKind::ImmOrOwnedMoveObject((id, _, _)) => Some(*id),
-                _ => None,
-            })
-            .collect();
-        let gas_price = self.0.get_reference_gas_price().await?;
-        let gas = self
-            .select_gas(signer, gas, gas_budget, inputs, gas_price)
-            .await?;
-
-        Ok(TransactionData::new(
-            TransactionKind::programmable(pt),
-            signer,
-            gas,
-            gas_budget,
-            gas_price,
-        ))
+        self.finish_programmable_transaction(signer, builder.finish(), gas, gas_budget)
+            .await
     }
 
     pub async fn request_add_stake(
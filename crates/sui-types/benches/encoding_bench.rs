@@ -0,0 +1,79 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[macro_use]
+extern crate criterion;
+
+use criterion::{BenchmarkId, Criterion, Throughput};
+use fastcrypto::encoding::{Base64, Encoding, Hex};
+use sui_types::base_types::SuiAddress;
+use sui_types::sui_serde::{SimdBase64, SimdHex};
+
+fn encoding_benchmark(c: &mut Criterion) {
+    // Addresses and digests, public keys, signatures and larger blobs.
+    for len in [32, 48, 96, 1024] {
+        let bytes: Vec<u8> = (0..len).map(|i| i as u8).collect();
+        let hex = Hex::encode(&bytes);
+        let base64 = Base64::encode(&bytes);
+
+        let mut group = c.benchmark_group("hex");
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_with_input(
+            BenchmarkId::new("encode/fastcrypto", len),
+            &bytes,
+            |b, bytes| b.iter(|| Hex::encode(bytes)),
+        );
+        group.bench_with_input(BenchmarkId::new("encode/simd", len), &bytes, |b, bytes| {
+            b.iter(|| SimdHex::encode(bytes))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("decode/fastcrypto", len),
+            &hex,
+            |b, hex| b.iter(|| Hex::decode(hex).unwrap()),
+        );
+        group.bench_with_input(BenchmarkId::new("decode/simd", len), &hex, |b, hex| {
+            b.iter(|| SimdHex::decode(hex).unwrap())
+        });
+        group.finish();
+
+        let mut group = c.benchmark_group("base64");
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_with_input(
+            BenchmarkId::new("encode/fastcrypto", len),
+            &bytes,
+            |b, bytes| b.iter(|| Base64::encode(bytes)),
+        );
+        group.bench_with_input(BenchmarkId::new("encode/simd", len), &bytes, |b, bytes| {
+            b.iter(|| SimdBase64::encode(bytes))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("decode/fastcrypto", len),
+            &base64,
+            |b, base64| b.iter(|| Base64::decode(base64).unwrap()),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("decode/simd", len),
+            &base64,
+            |b, base64| b.iter(|| SimdBase64::decode(base64).unwrap()),
+        );
+        group.finish();
+    }
+
+    // The end to end human-readable serialization of addresses.
+    let addresses: Vec<_> = (0..1000)
+        .map(|_| SuiAddress::random_for_testing_only())
+        .collect();
+    let json = serde_json::to_string(&addresses).unwrap();
+    let mut group = c.benchmark_group("address_json");
+    group.throughput(Throughput::Elements(addresses.len() as u64));
+    group.bench_function("serialize", |b| {
+        b.iter(|| serde_json::to_string(&addresses).unwrap())
+    });
+    group.bench_function("deserialize", |b| {
+        b.iter(|| serde_json::from_str::<Vec<SuiAddress>>(&json).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, encoding_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,95 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[macro_use]
+extern crate criterion;
+
+use criterion::{BenchmarkId, Criterion, Throughput};
+use fastcrypto::traits::KeyPair;
+use shared_crypto::intent::Intent;
+use sui_types::base_types::{random_object_ref, AuthorityName, ExecutionDigests, ObjectID};
+use sui_types::committee::Committee;
+use sui_types::crypto::{get_key_pair, AccountKeyPair};
+use sui_types::messages::{
+    CertifiedTransaction, Command, SignedTransaction, Transaction, TransactionData,
+};
+use sui_types::messages_checkpoint::CheckpointContents;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::zero_copy::{CertifiedTransactionRef, CheckpointContentsRef};
+
+/// A certificate publishing a package of `package_size` bytes, split in modules of 4KB.
+fn certificate(package_size: usize) -> CertifiedTransaction {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.pure_bytes(vec![0; 256], true);
+    builder.command(Command::Publish(
+        vec![vec![1; 4096]; package_size / 4096],
+        vec![ObjectID::ZERO],
+    ));
+    let data = TransactionData::new_programmable(
+        sender,
+        vec![random_object_ref()],
+        builder.finish(),
+        10_000,
+        1,
+    );
+    let transaction =
+        Transaction::from_data_and_signer(data, Intent::sui_transaction(), vec![&sender_key]);
+    let (committee, keys) = Committee::new_simple_test_committee_of_size(4);
+    let signatures = keys
+        .iter()
+        .map(|key| {
+            SignedTransaction::sign(
+                0,
+                transaction.data(),
+                key,
+                AuthorityName::from(key.public()),
+            )
+        })
+        .collect();
+    CertifiedTransaction::new(transaction.into_data(), signatures, &committee).unwrap()
+}
+
+/// Contents of a checkpoint of `size` transactions with a signature each.
+fn checkpoint_contents(size: usize) -> CheckpointContents {
+    let signature = certificate(4096).data().tx_signatures()[0].clone();
+    CheckpointContents::new_with_causally_ordered_transactions_and_signatures(
+        (0..size).map(|_| ExecutionDigests::random()),
+        vec![vec![signature]; size],
+    )
+}
+
+fn zero_copy_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("deserialize_certificate");
+    for package_size in [4096, 64 * 1024, 512 * 1024] {
+        let bytes = bcs::to_bytes(&certificate(package_size)).unwrap();
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::new("owned", package_size),
+            &bytes,
+            |b, bytes| b.iter(|| bcs::from_bytes::<CertifiedTransaction>(bytes).unwrap()),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("borrowed", package_size),
+            &bytes,
+            |b, bytes| b.iter(|| bcs::from_bytes::<CertifiedTransactionRef>(bytes).unwrap()),
+        );
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("deserialize_checkpoint_contents");
+    for size in [100, 1000, 10_000] {
+        let bytes = bcs::to_bytes(&checkpoint_contents(size)).unwrap();
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::new("owned", size), &bytes, |b, bytes| {
+            b.iter(|| bcs::from_bytes::<CheckpointContents>(bytes).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("borrowed", size), &bytes, |b, bytes| {
+            b.iter(|| bcs::from_bytes::<CheckpointContentsRef>(bytes).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, zero_copy_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,19 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use sui_types::message_envelope::Message;
+use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSummary};
+use sui_types_fuzz::check_round_trip;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(summary) = bcs::from_bytes::<CheckpointSummary>(data) {
+        check_round_trip(&summary);
+        let _ = summary.digest();
+    }
+    if let Ok(certified) = bcs::from_bytes::<CertifiedCheckpointSummary>(data) {
+        check_round_trip(&certified);
+        let _ = certified.digest();
+    }
+});
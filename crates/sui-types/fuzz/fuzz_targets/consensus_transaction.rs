@@ -0,0 +1,24 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use once_cell::sync::Lazy;
+use sui_types::committee::Committee;
+use sui_types::messages::{ConsensusTransaction, ConsensusTransactionKind};
+use sui_types_fuzz::{check_round_trip, check_transaction_data};
+
+static COMMITTEE: Lazy<Committee> = Lazy::new(|| Committee::new_simple_test_committee().0);
+
+// Consensus transactions are decoded by the validators from the batches of the other validators,
+// see `sui_core::consensus_validator`.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(tx) = bcs::from_bytes::<ConsensusTransaction>(data) {
+        check_round_trip(&tx);
+        let _ = tx.key();
+        let _ = tx.verify(&COMMITTEE);
+        if let ConsensusTransactionKind::UserTransaction(certificate) = &tx.kind {
+            check_transaction_data(certificate.data().transaction_data());
+        }
+    }
+});
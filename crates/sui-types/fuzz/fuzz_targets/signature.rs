@@ -0,0 +1,19 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::ToFromBytes;
+use sui_types::signature::GenericSignature;
+
+// Signatures are encoded as `flag || signature || public key`, or `flag || bcs(MultiSig)`, within
+// the BCS of transactions.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(signature) = GenericSignature::from_bytes(data) {
+        let decoded = GenericSignature::from_bytes(signature.as_ref())
+            .expect("Encoded signature should decode");
+        assert_eq!(decoded, signature);
+        let _ = SuiAddress::try_from(&signature);
+    }
+});
@@ -0,0 +1,15 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use sui_types::messages::Transaction;
+use sui_types_fuzz::{check_round_trip, check_transaction_data};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(tx) = bcs::from_bytes::<Transaction>(data) {
+        check_round_trip(&tx);
+        let _ = tx.digest();
+        check_transaction_data(tx.data().transaction_data());
+    }
+});
@@ -0,0 +1,28 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{de::DeserializeOwned, Serialize};
+use sui_types::messages::{TransactionData, TransactionDataAPI};
+
+/// Checks that a value decoded from the fuzzer input encodes back to bytes which decode to the
+/// same value. The input itself may not round-trip, as some fields (e.g. the bitmaps of the
+/// signatures) have more than one valid encoding.
+pub fn check_round_trip<T: Serialize + DeserializeOwned>(value: &T) {
+    let bytes = bcs::to_bytes(value).expect("Serialization of a decoded value should not fail");
+    let decoded: T = bcs::from_bytes(&bytes).expect("Encoded value should decode");
+    assert_eq!(
+        bcs::to_bytes(&decoded).unwrap(),
+        bytes,
+        "Decoding changed the value"
+    );
+}
+
+/// Runs the checks which validators run on the data of transactions received from the network,
+/// before verifying their signatures.
+pub fn check_transaction_data(data: &TransactionData) {
+    let config = sui_protocol_config::ProtocolConfig::get_for_max_version();
+    let _ = data.validity_check(&config);
+    let _ = data.input_objects();
+    let _ = data.shared_input_objects();
+    let _ = data.signers();
+}
@@ -38,7 +38,7 @@ use strum::EnumString;
 use crate::base_types::{AuthorityName, SuiAddress};
 use crate::committee::{Committee, EpochId, StakeUnit};
 use crate::error::{SuiError, SuiResult};
-use crate::sui_serde::{Readable, SuiBitmap};
+use crate::sui_serde::{Readable, SimdBase64, SuiBitmap};
 pub use enum_dispatch::enum_dispatch;
 use fastcrypto::encoding::{Base64, Encoding, Hex};
 use fastcrypto::error::FastCryptoError;
@@ -146,51 +146,29 @@ impl SuiKeyPair {
             SuiKeyPair::Secp256r1(kp) => PublicKey::Secp256r1(kp.public().clone()),
         }
     }
-}
 
-impl Signer<Signature> for SuiKeyPair {
-    fn sign(&self, msg: &[u8]) -> Signature {
+    pub fn copy(&self) -> Self {
         match self {
-            SuiKeyPair::Ed25519(kp) => kp.sign(msg),
-            SuiKeyPair::Secp256k1(kp) => kp.sign(msg),
-            SuiKeyPair::Secp256r1(kp) => kp.sign(msg),
+            SuiKeyPair::Ed25519(kp) => SuiKeyPair::Ed25519(kp.copy()),
+            SuiKeyPair::Secp256k1(kp) => SuiKeyPair::Secp256k1(kp.copy()),
+            SuiKeyPair::Secp256r1(kp) => SuiKeyPair::Secp256r1(kp.copy()),
         }
     }
-}
-
-impl FromStr for SuiKeyPair {
-    type Err = eyre::Report;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let kp = Self::decode_base64(s).map_err(|e| eyre!("{}", e.to_string()))?;
-        Ok(kp)
-    }
-}
 
-impl EncodeDecodeBase64 for SuiKeyPair {
-    /// Encode a SuiKeyPair as `flag || privkey` in Base64. Note that the pubkey is not encoded.
-    fn encode_base64(&self) -> String {
+    /// Encode a SuiKeyPair as `flag || privkey`. Note that the pubkey is not encoded.
+    pub fn to_bytes(&self) -> Vec<u8> {
         let mut bytes: Vec<u8> = Vec::new();
+        bytes.push(self.public().flag());
         match self {
-            SuiKeyPair::Ed25519(kp) => {
-                bytes.push(self.public().flag());
-                bytes.extend_from_slice(kp.as_bytes());
-            }
-            SuiKeyPair::Secp256k1(kp) => {
-                bytes.push(self.public().flag());
-                bytes.extend_from_slice(kp.as_bytes());
-            }
-            SuiKeyPair::Secp256r1(kp) => {
-                bytes.push(self.public().flag());
-                bytes.extend_from_slice(kp.as_bytes());
-            }
+            SuiKeyPair::Ed25519(kp) => bytes.extend_from_slice(kp.as_bytes()),
+            SuiKeyPair::Secp256k1(kp) => bytes.extend_from_slice(kp.as_bytes()),
+            SuiKeyPair::Secp256r1(kp) => bytes.extend_from_slice(kp.as_bytes()),
         }
-        Base64::encode(&bytes[..])
+        bytes
     }
 
-    /// Decode a SuiKeyPair from `flag || privkey` in Base64. The public key is computed directly from the private key bytes.
-    fn decode_base64(value: &str) -> Result<Self, eyre::Report> {
-        let bytes = Base64::decode(value).map_err(|e| eyre!("{}", e.to_string()))?;
+    /// Decode a SuiKeyPair from `flag || privkey`. The public key is computed directly from the private key bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, eyre::Report> {
         match SignatureScheme::from_flag_byte(bytes.first().ok_or_else(|| eyre!("Invalid length"))?)
         {
             Ok(x) => match x {
@@ -214,6 +192,38 @@ impl EncodeDecodeBase64 for SuiKeyPair {
     }
 }
 
+impl Signer<Signature> for SuiKeyPair {
+    fn sign(&self, msg: &[u8]) -> Signature {
+        match self {
+            SuiKeyPair::Ed25519(kp) => kp.sign(msg),
+            SuiKeyPair::Secp256k1(kp) => kp.sign(msg),
+            SuiKeyPair::Secp256r1(kp) => kp.sign(msg),
+        }
+    }
+}
+
+impl FromStr for SuiKeyPair {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let kp = Self::decode_base64(s).map_err(|e| eyre!("{}", e.to_string()))?;
+        Ok(kp)
+    }
+}
+
+impl EncodeDecodeBase64 for SuiKeyPair {
+    /// Encode a SuiKeyPair as `flag || privkey` in Base64. Note that the pubkey is not encoded.
+    fn encode_base64(&self) -> String {
+        Base64::encode(self.to_bytes())
+    }
+
+    /// Decode a SuiKeyPair from `flag || privkey` in Base64. The public key is computed directly from the private key bytes.
+    fn decode_base64(value: &str) -> Result<Self, eyre::Report> {
+        let bytes = Base64::decode(value).map_err(|e| eyre!("{}", e.to_string()))?;
+        Self::from_bytes(&bytes)
+    }
+}
+
 impl Serialize for SuiKeyPair {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -365,7 +375,7 @@ impl PublicKey {
 #[as_ref(forward)]
 pub struct AuthorityPublicKeyBytes(
     #[schemars(with = "Base64")]
-    #[serde_as(as = "Readable<Base64, Bytes>")]
+    #[serde_as(as = "Readable<SimdBase64, Bytes>")]
     [u8; AuthorityPublicKey::LENGTH],
 );
 
@@ -499,15 +509,32 @@ pub trait SuiAuthoritySignature {
         T: Serialize;
 }
 
+/// Signs with the protocol key of an authority. Unlike [Signer], signing can fail, e.g. when the
+/// key is held by a remote signer that is unavailable.
+pub trait AuthoritySigner: Send + Sync {
+    fn try_sign(&self, msg: &[u8]) -> SuiResult<AuthoritySignature>;
+}
+
+impl<S: Signer<AuthoritySignature> + Send + Sync> AuthoritySigner for S {
+    fn try_sign(&self, msg: &[u8]) -> SuiResult<AuthoritySignature> {
+        Ok(self.sign(msg))
+    }
+}
+
+/// The bytes signed by an authority for an intent message in an epoch.
+fn authority_signing_bytes<T: Serialize>(value: &IntentMessage<T>, epoch: &EpochId) -> Vec<u8> {
+    let mut intent_msg_bytes =
+        bcs::to_bytes(&value).expect("Message serialization should not fail");
+    epoch.write(&mut intent_msg_bytes);
+    intent_msg_bytes
+}
+
 impl SuiAuthoritySignature for AuthoritySignature {
     fn new_secure<T>(value: &IntentMessage<T>, epoch: &EpochId, secret: &dyn Signer<Self>) -> Self
     where
         T: Serialize,
     {
-        let mut intent_msg_bytes =
-            bcs::to_bytes(&value).expect("Message serialization should not fail");
-        epoch.write(&mut intent_msg_bytes);
-        secret.sign(&intent_msg_bytes)
+        secret.sign(&authority_signing_bytes(value, epoch))
     }
 
     fn verify_secure<T>(
@@ -786,7 +813,7 @@ impl SuiPublicKey for BLS12381PublicKey {
 #[as_mut(forward)]
 pub struct Ed25519SuiSignature(
     #[schemars(with = "Base64")]
-    #[serde_as(as = "Readable<Base64, Bytes>")]
+    #[serde_as(as = "Readable<SimdBase64, Bytes>")]
     [u8; Ed25519PublicKey::LENGTH + Ed25519Signature::LENGTH + 1],
 );
 
@@ -834,7 +861,7 @@ impl Signer<Signature> for Ed25519KeyPair {
 #[as_mut(forward)]
 pub struct Secp256k1SuiSignature(
     #[schemars(with = "Base64")]
-    #[serde_as(as = "Readable<Base64, Bytes>")]
+    #[serde_as(as = "Readable<SimdBase64, Bytes>")]
     [u8; Secp256k1PublicKey::LENGTH + Secp256k1Signature::LENGTH + 1],
 );
 
@@ -875,7 +902,7 @@ impl Signer<Signature> for Secp256k1KeyPair {
 #[as_mut(forward)]
 pub struct Secp256r1SuiSignature(
     #[schemars(with = "Base64")]
-    #[serde_as(as = "Readable<Base64, Bytes>")]
+    #[serde_as(as = "Readable<SimdBase64, Bytes>")]
     [u8; Secp256r1PublicKey::LENGTH + Secp256r1Signature::LENGTH + 1],
 );
 
@@ -1127,6 +1154,25 @@ impl AuthoritySignInfo {
             ),
         }
     }
+
+    /// Like [AuthoritySignInfo::new], but returns an error if the signer fails to sign.
+    pub fn try_new<T>(
+        epoch: EpochId,
+        value: &T,
+        intent: Intent,
+        name: AuthorityName,
+        secret: &dyn AuthoritySigner,
+    ) -> SuiResult<Self>
+    where
+        T: Serialize,
+    {
+        let message = authority_signing_bytes(&IntentMessage::new(intent, value), &epoch);
+        Ok(Self {
+            epoch,
+            authority: name,
+            signature: secret.try_sign(&message)?,
+        })
+    }
 }
 
 impl Hash for AuthoritySignInfo {
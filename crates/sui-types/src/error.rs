@@ -21,6 +21,7 @@ use serde::{Deserialize, Serialize};
 use std::{collections::BTreeMap, fmt::Debug};
 use strum_macros::{AsRefStr, IntoStaticStr};
 use thiserror::Error;
+#[cfg(not(target_arch = "wasm32"))]
 use tonic::Status;
 use typed_store::rocks::TypedStoreError;
 
@@ -496,6 +497,14 @@ pub enum SuiError {
 
     #[error("Failed to perform file operation: {0}")]
     FileIOError(String),
+
+    #[error("Invalid object proof: {error}")]
+    InvalidObjectProof { error: String },
+    #[error("Invalid transaction proof: {error}")]
+    InvalidTransactionProof { error: String },
+
+    #[error("Failed to sign with the validator protocol key: {error:?}")]
+    ValidatorSigningFailed { error: String },
 }
 
 #[repr(u64)]
@@ -537,6 +546,7 @@ impl From<ExecutionError> for SuiError {
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl From<Status> for SuiError {
     fn from(status: Status) -> Self {
         let result = bcs::from_bytes::<SuiError>(status.details());
@@ -551,6 +561,7 @@ impl From<Status> for SuiError {
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl From<SuiError> for Status {
     fn from(error: SuiError) -> Self {
         let bytes = bcs::to_bytes(&error).unwrap();
@@ -642,6 +653,9 @@ impl SuiError {
             SuiError::TooManyTransactionsPendingOnObject { .. } => (true, true),
             SuiError::TooManyTransactionsPendingConsensus => (true, true),
 
+            // The remote signer of the validator may be temporarily unavailable
+            SuiError::ValidatorSigningFailed { .. } => (true, true),
+
             // Non retryable error
             SuiError::ExecutionError(..) => (false, true),
             SuiError::ByzantineAuthoritySuspicion { .. } => (false, true),
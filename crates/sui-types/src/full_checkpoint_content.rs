@@ -0,0 +1,30 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::messages::{Transaction, TransactionEffects, TransactionEvents};
+use crate::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointContents};
+use crate::object::Object;
+
+/// A checkpoint with everything needed to index it without querying a node: its transactions,
+/// their effects and events, and the objects they read and wrote.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CheckpointData {
+    pub checkpoint_summary: CertifiedCheckpointSummary,
+    pub checkpoint_contents: CheckpointContents,
+    pub transactions: Vec<CheckpointTransaction>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CheckpointTransaction {
+    /// The input transaction, as signed by its sender.
+    pub transaction: Transaction,
+    pub effects: TransactionEffects,
+    /// The events emitted by the transaction, if any.
+    pub events: Option<TransactionEvents>,
+    /// The objects at the versions read by the transaction.
+    pub input_objects: Vec<Object>,
+    /// The objects at the versions written by the transaction.
+    pub output_objects: Vec<Object>,
+}
@@ -33,6 +33,7 @@ pub mod digests;
 pub mod display;
 pub mod dynamic_field;
 pub mod event;
+pub mod full_checkpoint_content;
 pub mod gas;
 pub mod gas_coin;
 pub mod gas_model;
@@ -45,15 +46,23 @@ pub mod messages_checkpoint;
 pub mod move_package;
 pub mod multisig;
 pub mod object;
+pub mod object_proof;
 pub mod programmable_transaction_builder;
+#[cfg(any(test, feature = "fuzzing"))]
+pub mod proptest_types;
 pub mod query;
 pub mod quorum_driver_types;
+pub mod remote_signer;
 pub mod signature;
 pub mod storage;
 pub mod sui_serde;
 pub mod sui_system_state;
 pub mod temporary_store;
+#[cfg(any(test, feature = "test-fixtures"))]
+pub mod test_fixtures;
+pub mod transaction_proof;
 pub mod versioned;
+pub mod zero_copy;
 
 pub mod epoch_data;
 
@@ -6,7 +6,7 @@ use crate::certificate_proof::CertificateProof;
 use crate::committee::{Committee, EpochId};
 use crate::crypto::{
     AuthorityQuorumSignInfo, AuthoritySignInfo, AuthoritySignInfoTrait, AuthoritySignature,
-    AuthorityStrongQuorumSignInfo, EmptySignInfo, Signer,
+    AuthoritySigner, AuthorityStrongQuorumSignInfo, EmptySignInfo, Signer,
 };
 use crate::error::SuiResult;
 use crate::messages::VersionedProtocolMessage;
@@ -155,6 +155,22 @@ where
         AuthoritySignInfo::new(epoch, &data, Intent::sui_app(T::SCOPE), authority, secret)
     }
 
+    /// Like [Envelope::new], but returns an error if the signer fails to sign.
+    pub fn try_new(
+        epoch: EpochId,
+        data: T,
+        secret: &dyn AuthoritySigner,
+        authority: AuthorityName,
+    ) -> SuiResult<Self> {
+        let auth_signature =
+            AuthoritySignInfo::try_new(epoch, &data, Intent::sui_app(T::SCOPE), authority, secret)?;
+        Ok(Self {
+            digest: OnceCell::new(),
+            data,
+            auth_signature,
+        })
+    }
+
     pub fn epoch(&self) -> EpochId {
         self.auth_signature.epoch
     }
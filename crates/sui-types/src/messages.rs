@@ -6,12 +6,12 @@ use super::{base_types::*, committee::Committee, error::*, event::Event};
 use crate::certificate_proof::CertificateProof;
 use crate::committee::{EpochId, ProtocolVersion};
 use crate::crypto::{
-    default_hash, AuthoritySignInfo, AuthoritySignature, AuthorityStrongQuorumSignInfo,
-    DefaultHash, Ed25519SuiSignature, EmptySignInfo, Signature, Signer, SuiSignatureInner,
-    ToFromBytes,
+    default_hash, AuthoritySignInfo, AuthoritySignature, AuthoritySigner,
+    AuthorityStrongQuorumSignInfo, DefaultHash, Ed25519SuiSignature, EmptySignInfo, Signature,
+    Signer, SuiSignatureInner, ToFromBytes,
 };
 use crate::digests::{CertificateDigest, SenderSignedDataDigest, TransactionEventsDigest};
-use crate::gas::GasCostSummary;
+use crate::gas::{GasCostSummary, SuiCostTable};
 use crate::message_envelope::{Envelope, Message, TrustedEnvelope, VerifiedEnvelope};
 use crate::messages_checkpoint::{
     CheckpointSequenceNumber, CheckpointSignatureMessage, CheckpointTimestamp,
@@ -1472,6 +1472,75 @@ impl TransactionData {
             self.gas_data().payment.clone(),
         )
     }
+
+    /// Runs the checks validators apply to a transaction that do not need the input objects
+    /// or the epoch's reference gas price, so that clients can reject a transaction before
+    /// submitting it. Passing these checks does not guarantee that validators accept it: the
+    /// size check does not account for signatures, and gas coin balances and object ownership
+    /// are only checked against the validators' state.
+    pub fn validity_check_client_side(&self, config: &ProtocolConfig) -> SuiResult {
+        self.check_version_supported(config)?;
+        self.validity_check(config)?;
+
+        let tx_size =
+            bcs::serialized_size(self).map_err(|e| SuiError::TransactionSerializationError {
+                error: e.to_string(),
+            })?;
+        let max_tx_size_bytes = config.max_tx_size_bytes();
+        fp_ensure!(
+            tx_size as u64 <= max_tx_size_bytes,
+            UserInputError::SizeLimitExceeded {
+                limit: format!(
+                    "serialized transaction size exceeded maximum of {max_tx_size_bytes}"
+                ),
+                value: tx_size.to_string(),
+            }
+            .into()
+        );
+
+        let input_objects = self.input_objects()?;
+        fp_ensure!(
+            input_objects.len() <= config.max_input_objects() as usize,
+            UserInputError::SizeLimitExceeded {
+                limit: "maximum input objects in a transaction".to_string(),
+                value: config.max_input_objects().to_string()
+            }
+            .into()
+        );
+        // Gas coins are always mutable, so they cannot also be used as inputs.
+        let mut used = HashSet::new();
+        for object in &input_objects {
+            fp_ensure!(
+                used.insert(object.object_id()),
+                UserInputError::MutableObjectUsedMoreThanOnce {
+                    object_id: object.object_id()
+                }
+                .into()
+            );
+        }
+
+        if !self.is_system_tx() {
+            let cost_table = SuiCostTable::new(config);
+            let gas_budget = self.gas_budget();
+            fp_ensure!(
+                gas_budget <= cost_table.max_gas_budget(),
+                UserInputError::GasBudgetTooHigh {
+                    gas_budget,
+                    max_budget: cost_table.max_gas_budget(),
+                }
+                .into()
+            );
+            fp_ensure!(
+                gas_budget >= cost_table.min_gas_budget(),
+                UserInputError::GasBudgetTooLow {
+                    gas_budget,
+                    min_budget: cost_table.min_gas_budget(),
+                }
+                .into()
+            );
+        }
+        Ok(())
+    }
 }
 
 #[enum_dispatch]
@@ -1681,9 +1750,29 @@ impl TransactionDataAPI for TransactionDataV1 {
 
 impl TransactionDataV1 {}
 
+/// Only one transaction per [SenderSignedData] is supported, transactions with any other number
+/// are rejected on deserialization, so that the accessors of the transaction can't panic on
+/// transactions received from the network.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(try_from = "Vec<SenderSignedTransaction>")]
 pub struct SenderSignedData(Vec<SenderSignedTransaction>);
 
+impl TryFrom<Vec<SenderSignedTransaction>> for SenderSignedData {
+    type Error = SuiError;
+
+    fn try_from(txs: Vec<SenderSignedTransaction>) -> Result<Self, Self::Error> {
+        fp_ensure!(
+            txs.len() == 1,
+            SuiError::UserInputError {
+                error: UserInputError::Unsupported(
+                    "SenderSignedData must contain exactly one transaction".to_string()
+                )
+            }
+        );
+        Ok(Self(txs))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct SenderSignedTransaction {
     pub intent_message: IntentMessage<TransactionData>,
@@ -1717,7 +1806,7 @@ impl SenderSignedData {
     }
 
     pub fn inner(&self) -> &SenderSignedTransaction {
-        // assert is safe - SenderSignedTransaction::verify ensures length is 1.
+        // assert is safe - the constructors and deserialization ensure length is 1.
         assert_eq!(self.0.len(), 1);
         self.0
             .get(0)
@@ -1725,7 +1814,7 @@ impl SenderSignedData {
     }
 
     pub fn inner_mut(&mut self) -> &mut SenderSignedTransaction {
-        // assert is safe - SenderSignedTransaction::verify ensures length is 1.
+        // assert is safe - the constructors and deserialization ensure length is 1.
         assert_eq!(self.0.len(), 1);
         self.0
             .get_mut(0)
@@ -2013,6 +2102,21 @@ impl VerifiedSignedTransaction {
             authority,
         ))
     }
+
+    /// Like [VerifiedSignedTransaction::new], but returns an error if the signer fails to sign.
+    pub fn try_new(
+        epoch: EpochId,
+        transaction: VerifiedTransaction,
+        authority: AuthorityName,
+        secret: &dyn AuthoritySigner,
+    ) -> SuiResult<Self> {
+        Ok(Self::new_from_verified(SignedTransaction::try_new(
+            epoch,
+            transaction.into_inner().into_data(),
+            secret,
+            authority,
+        )?))
+    }
 }
 
 /// A transaction that is signed by a sender but not yet by an authority.
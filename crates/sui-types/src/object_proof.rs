@@ -0,0 +1,80 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::base_types::ObjectRef;
+use crate::committee::Committee;
+use crate::error::{SuiError, SuiResult};
+use crate::message_envelope::Message;
+use crate::messages::{TransactionEffects, TransactionEffectsAPI};
+use crate::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointContents};
+use crate::object::Object;
+
+#[cfg(test)]
+#[path = "unit_tests/object_proof_tests.rs"]
+mod object_proof_tests;
+
+/// Proof that an object version is part of the state at a checkpoint: the object version was
+/// written by a transaction of the checkpoint, and inserted in the state accumulator of the
+/// checkpoint by the effects of the transaction.
+///
+/// The state accumulator is a multiset hash, which does not support succinct membership proofs.
+/// The proof is instead rooted in the certified checkpoint summary: its contents digest commits
+/// to the effects of its transactions, which commit to the digests of the objects they write.
+/// The object may have been modified by later checkpoints.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ObjectProof {
+    /// The checkpoint including the transaction which wrote the object version.
+    pub checkpoint: CertifiedCheckpointSummary,
+    pub contents: CheckpointContents,
+    /// The effects of the transaction which wrote the object version.
+    pub effects: TransactionEffects,
+    pub object: Object,
+}
+
+impl ObjectProof {
+    /// Verifies the proof against the committee of the epoch of the checkpoint, and returns the
+    /// reference of the object version.
+    pub fn verify(&self, committee: &Committee) -> SuiResult<ObjectRef> {
+        self.checkpoint
+            .verify_with_contents(committee, Some(&self.contents))?;
+
+        let transaction = self.effects.transaction_digest();
+        let effects_digest = self.effects.digest();
+        if !self
+            .contents
+            .iter()
+            .any(|digests| &digests.transaction == transaction && digests.effects == effects_digest)
+        {
+            return Err(invalid_proof(format!(
+                "effects {effects_digest} of transaction {transaction} are not included in checkpoint {}",
+                self.checkpoint.sequence_number
+            )));
+        }
+
+        let object_ref = self.object.compute_object_reference();
+        let owner = self
+            .effects
+            .all_changed_objects()
+            .into_iter()
+            .find(|(changed, _, _)| **changed == object_ref)
+            .map(|(_, owner, _)| *owner)
+            .ok_or_else(|| {
+                invalid_proof(format!(
+                    "object {object_ref:?} is not written by transaction {transaction}"
+                ))
+            })?;
+        if owner != self.object.owner {
+            return Err(invalid_proof(format!(
+                "object {:?} is owned by {}, the effects record the owner {owner}",
+                object_ref, self.object.owner
+            )));
+        }
+        Ok(object_ref)
+    }
+}
+
+fn invalid_proof(error: String) -> SuiError {
+    SuiError::InvalidObjectProof { error }
+}
@@ -0,0 +1,293 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! [proptest::arbitrary::Arbitrary] and [arbitrary::Arbitrary] implementations of the core types,
+//! enabled with the `fuzzing` feature, for downstream crates to fuzz their own serialization
+//! paths, e.g. with [check_round_trip]. The values generated are well formed, e.g. signatures are
+//! valid signatures of some message, but not necessarily consistent with each other, e.g. the
+//! arguments of a programmable transaction may refer to inputs it does not have.
+//!
+//! Genesis transactions are not generated.
+
+use std::fmt::Debug;
+
+use fastcrypto::ed25519::Ed25519KeyPair;
+use fastcrypto::secp256k1::Secp256k1KeyPair;
+use fastcrypto::secp256r1::Secp256r1KeyPair;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::TypeTag;
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::strategy::ValueTree;
+use proptest::test_runner::{Config, RngAlgorithm, TestRng, TestRunner};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
+use crate::committee::ProtocolVersion;
+use crate::crypto::{get_key_pair_from_rng, Signature, SuiKeyPair};
+use crate::messages::{
+    Argument, CallArg, ChangeEpoch, Command, ConsensusCommitPrologue, GasData, ObjectArg,
+    ProgrammableMoveCall, ProgrammableTransaction, TransactionData, TransactionDataV1,
+    TransactionExpiration, TransactionKind,
+};
+use crate::multisig::{MultiSig, MultiSigPublicKey};
+use crate::object::Owner;
+use crate::signature::GenericSignature;
+
+#[cfg(test)]
+#[path = "unit_tests/proptest_types_tests.rs"]
+mod proptest_types_tests;
+
+/// Checks that `value` survives a round trip through BCS and through JSON, and that the value
+/// read back from JSON serializes to the same BCS bytes.
+pub fn check_round_trip<T>(value: &T) -> Result<(), TestCaseError>
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let bytes = bcs::to_bytes(value)
+        .map_err(|e| TestCaseError::fail(format!("BCS serialization failed: {e}")))?;
+    let from_bcs: T = bcs::from_bytes(&bytes)
+        .map_err(|e| TestCaseError::fail(format!("BCS deserialization failed: {e}")))?;
+    prop_assert_eq!(&from_bcs, value);
+
+    let json = serde_json::to_string(value)
+        .map_err(|e| TestCaseError::fail(format!("JSON serialization failed: {e}")))?;
+    let from_json: T = serde_json::from_str(&json)
+        .map_err(|e| TestCaseError::fail(format!("JSON deserialization of {json} failed: {e}")))?;
+    prop_assert_eq!(&from_json, value);
+    prop_assert_eq!(bcs::to_bytes(&from_json).unwrap(), bytes);
+    Ok(())
+}
+
+/// Generates a value of `strategy` from the seed read from `u`, to implement
+/// [arbitrary::Arbitrary] with the proptest strategies.
+pub fn from_unstructured<S: Strategy>(
+    u: &mut arbitrary::Unstructured<'_>,
+    strategy: S,
+) -> arbitrary::Result<S::Value> {
+    let seed: [u8; 32] = u.arbitrary()?;
+    let mut runner = TestRunner::new_with_rng(
+        Config::default(),
+        TestRng::from_seed(RngAlgorithm::ChaCha, &seed),
+    );
+    let tree = strategy
+        .new_tree(&mut runner)
+        .map_err(|_| arbitrary::Error::IncorrectFormat)?;
+    Ok(tree.current())
+}
+
+macro_rules! impl_arbitrary {
+    ($($ty:ty => $strategy:expr),* $(,)?) => {
+        $(
+            impl Arbitrary for $ty {
+                type Parameters = ();
+                type Strategy = BoxedStrategy<Self>;
+
+                fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+                    $strategy.boxed()
+                }
+            }
+
+            impl<'a> arbitrary::Arbitrary<'a> for $ty {
+                fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                    from_unstructured(u, any::<$ty>())
+                }
+            }
+        )*
+    };
+}
+
+impl_arbitrary! {
+    ObjectID => any::<[u8; ObjectID::LENGTH]>().prop_map(ObjectID::new),
+    SuiAddress => any::<ObjectID>().prop_map(SuiAddress::from),
+    SequenceNumber => (0..SequenceNumber::MAX.value()).prop_map(SequenceNumber::from_u64),
+    ObjectDigest => any::<[u8; 32]>().prop_map(ObjectDigest::new),
+    TransactionDigest => any::<[u8; 32]>().prop_map(TransactionDigest::new),
+    Owner => prop_oneof![
+        any::<SuiAddress>().prop_map(Owner::AddressOwner),
+        any::<SuiAddress>().prop_map(Owner::ObjectOwner),
+        any::<SequenceNumber>().prop_map(|initial_shared_version| Owner::Shared {
+            initial_shared_version,
+        }),
+        Just(Owner::Immutable),
+    ],
+    ObjectArg => prop_oneof![
+        any::<(ObjectID, SequenceNumber, ObjectDigest)>().prop_map(ObjectArg::ImmOrOwnedObject),
+        any::<(ObjectID, SequenceNumber, bool)>().prop_map(
+            |(id, initial_shared_version, mutable)| ObjectArg::SharedObject {
+                id,
+                initial_shared_version,
+                mutable,
+            }
+        ),
+    ],
+    CallArg => prop_oneof![
+        vec(any::<u8>(), 0..64).prop_map(CallArg::Pure),
+        any::<ObjectArg>().prop_map(CallArg::Object),
+    ],
+    Argument => prop_oneof![
+        Just(Argument::GasCoin),
+        any::<u16>().prop_map(Argument::Input),
+        any::<u16>().prop_map(Argument::Result),
+        any::<(u16, u16)>().prop_map(|(result, nested)| Argument::NestedResult(result, nested)),
+    ],
+    Command => command(),
+    ProgrammableTransaction => (vec(any::<CallArg>(), 0..8), vec(any::<Command>(), 0..8))
+        .prop_map(|(inputs, commands)| ProgrammableTransaction { inputs, commands }),
+    TransactionKind => prop_oneof![
+        4 => any::<ProgrammableTransaction>().prop_map(TransactionKind::ProgrammableTransaction),
+        1 => change_epoch().prop_map(TransactionKind::ChangeEpoch),
+        1 => any::<(u64, u64, u64)>().prop_map(|(epoch, round, commit_timestamp_ms)| {
+            TransactionKind::ConsensusCommitPrologue(ConsensusCommitPrologue {
+                epoch,
+                round,
+                commit_timestamp_ms,
+            })
+        }),
+    ],
+    TransactionData => transaction_data(),
+    GenericSignature => generic_signature(),
+}
+
+fn package_modules() -> impl Strategy<Value = Vec<Vec<u8>>> {
+    vec(vec(any::<u8>(), 0..64), 1..3)
+}
+
+fn command() -> impl Strategy<Value = Command> {
+    let arguments = || vec(any::<Argument>(), 0..4);
+    prop_oneof![
+        (
+            any::<ObjectID>(),
+            any::<Identifier>(),
+            any::<Identifier>(),
+            vec(any::<TypeTag>(), 0..3),
+            arguments(),
+        )
+            .prop_map(|(package, module, function, type_arguments, arguments)| {
+                Command::MoveCall(Box::new(ProgrammableMoveCall {
+                    package,
+                    module,
+                    function,
+                    type_arguments,
+                    arguments,
+                }))
+            }),
+        (arguments(), any::<Argument>())
+            .prop_map(|(objects, recipient)| Command::TransferObjects(objects, recipient)),
+        (any::<Argument>(), arguments())
+            .prop_map(|(coin, amounts)| Command::SplitCoins(coin, amounts)),
+        (any::<Argument>(), arguments()).prop_map(|(coin, coins)| Command::MergeCoins(coin, coins)),
+        (package_modules(), vec(any::<ObjectID>(), 0..3))
+            .prop_map(|(modules, dependencies)| Command::Publish(modules, dependencies)),
+        (proptest::option::of(any::<TypeTag>()), arguments())
+            .prop_map(|(type_, elements)| Command::MakeMoveVec(type_, elements)),
+        (
+            package_modules(),
+            vec(any::<ObjectID>(), 0..3),
+            any::<ObjectID>(),
+            any::<Argument>(),
+        )
+            .prop_map(|(modules, dependencies, package, ticket)| {
+                Command::Upgrade(modules, dependencies, package, ticket)
+            }),
+    ]
+}
+
+fn change_epoch() -> impl Strategy<Value = ChangeEpoch> {
+    (
+        any::<u64>(),
+        any::<u64>(),
+        any::<[u64; 5]>(),
+        vec(
+            (
+                any::<SequenceNumber>(),
+                package_modules(),
+                vec(any::<ObjectID>(), 0..3),
+            ),
+            0..3,
+        ),
+    )
+        .prop_map(
+            |(epoch, protocol_version, charges, system_packages)| ChangeEpoch {
+                epoch,
+                protocol_version: ProtocolVersion::new(protocol_version),
+                storage_charge: charges[0],
+                computation_charge: charges[1],
+                storage_rebate: charges[2],
+                non_refundable_storage_fee: charges[3],
+                epoch_start_timestamp_ms: charges[4],
+                system_packages,
+            },
+        )
+}
+
+fn transaction_data() -> impl Strategy<Value = TransactionData> {
+    (
+        any::<TransactionKind>(),
+        any::<SuiAddress>(),
+        vec(any::<(ObjectID, SequenceNumber, ObjectDigest)>(), 0..4),
+        any::<(SuiAddress, u64, u64)>(),
+        proptest::option::of(any::<u64>()),
+    )
+        .prop_map(
+            |(kind, sender, payment, (owner, price, budget), expiration)| {
+                TransactionData::V1(TransactionDataV1 {
+                    kind,
+                    sender,
+                    gas_data: GasData {
+                        payment,
+                        owner,
+                        price,
+                        budget,
+                    },
+                    expiration: expiration
+                        .map_or(TransactionExpiration::None, TransactionExpiration::Epoch),
+                })
+            },
+        )
+}
+
+/// Generates signatures of every scheme over a random message, and multisigs of up to three of
+/// them.
+fn generic_signature() -> impl Strategy<Value = GenericSignature> {
+    (
+        vec((0..3u8, any::<[u8; 32]>()), 1..4),
+        vec(any::<u8>(), 0..64),
+    )
+        .prop_map(|(keys, message)| {
+            let keys: Vec<_> = keys
+                .into_iter()
+                .map(|(scheme, seed)| {
+                    let mut rng = StdRng::from_seed(seed);
+                    match scheme {
+                        0 => SuiKeyPair::Ed25519(
+                            get_key_pair_from_rng::<Ed25519KeyPair, _>(&mut rng).1,
+                        ),
+                        1 => SuiKeyPair::Secp256k1(
+                            get_key_pair_from_rng::<Secp256k1KeyPair, _>(&mut rng).1,
+                        ),
+                        _ => SuiKeyPair::Secp256r1(
+                            get_key_pair_from_rng::<Secp256r1KeyPair, _>(&mut rng).1,
+                        ),
+                    }
+                })
+                .collect();
+            let signatures: Vec<_> = keys
+                .iter()
+                .map(|key| Signature::new_hashed(&message, key))
+                .collect();
+            if signatures.len() == 1 {
+                return signatures[0].clone().into();
+            }
+            let multisig_pk = MultiSigPublicKey::new(
+                keys.iter().map(|key| key.public()).collect(),
+                vec![1; keys.len()],
+                keys.len() as u16,
+            )
+            .unwrap();
+            MultiSig::combine(signatures, multisig_pk).unwrap().into()
+        })
+}
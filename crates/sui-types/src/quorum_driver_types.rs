@@ -48,6 +48,8 @@ pub enum QuorumDriverError {
         overloaded_stake: StakeUnit,
         errors: GroupedErrors,
     },
+    #[error("Transaction is refused by the policy of this node: {reason}.")]
+    TransactionDeniedByPolicy { reason: String },
 }
 
 pub type GroupedErrors = Vec<(SuiError, StakeUnit, Vec<ConciseAuthorityPublicKeyBytes>)>;
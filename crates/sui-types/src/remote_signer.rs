@@ -0,0 +1,63 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Wire types of the remote signing service, which lets a validator keep its keys in an
+//! external signer (HSM/KMS) rather than in key files on the validator host.
+
+use serde::{Deserialize, Serialize};
+
+/// The validator keys a remote signer can hold.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ValidatorKeyKind {
+    /// BLS12381 key used to sign transaction votes and checkpoints.
+    Protocol,
+    /// Key controlling the validator Sui address.
+    Account,
+    /// Ed25519 key identifying the validator on the p2p network.
+    Network,
+}
+
+/// Prefix of the bytes signed by the client key of a [SignRequest], so that they cannot be taken
+/// for another signature of the key.
+const SIGN_REQUEST_DOMAIN: &[u8] = b"SuiRemoteSignerRequest";
+
+/// A request to sign `message` with `key`. The request is authenticated with the Ed25519 key of
+/// the client, which the signer must allow. As signing is deterministic, a replayed request only
+/// returns the signature already returned to the client.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignRequest {
+    pub key: ValidatorKeyKind,
+    pub message: Vec<u8>,
+    /// The Ed25519 public key of the client.
+    pub client_public_key: Vec<u8>,
+    /// The signature by the client key of [SignRequest::client_signing_bytes].
+    pub client_signature: Vec<u8>,
+}
+
+impl SignRequest {
+    /// The bytes of a request for `message` signed by the client key.
+    pub fn client_signing_bytes(key: ValidatorKeyKind, message: &[u8]) -> Vec<u8> {
+        let mut bytes = SIGN_REQUEST_DOMAIN.to_vec();
+        bytes.extend(bcs::to_bytes(&(key, message)).expect("Serialization should not fail"));
+        bytes
+    }
+}
+
+/// The signature bytes. Protocol and network signatures are the raw signature of the scheme,
+/// account signatures are encoded as `flag || sig || pk`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignResponse {
+    pub signature: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PublicKeyRequest {
+    pub key: ValidatorKeyKind,
+}
+
+/// The public key bytes. Protocol and network keys are the raw public key of the scheme,
+/// account keys are encoded as `flag || pk`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PublicKeyResponse {
+    pub public_key: Vec<u8>,
+}
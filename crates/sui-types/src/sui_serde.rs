@@ -1,19 +1,19 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::{Debug, Display, Formatter};
 use std::marker::PhantomData;
 use std::ops::Deref;
 use std::str::FromStr;
 
-use fastcrypto::encoding::Hex;
 use move_core_types::account_address::AccountAddress;
 use move_core_types::language_storage::{StructTag, TypeTag};
 use schemars::JsonSchema;
 use serde;
-use serde::de::{Deserializer, Error};
-use serde::ser::{Error as SerError, Serializer};
+use serde::de::{DeserializeOwned, Deserializer, Error, SeqAccess, Visitor};
+use serde::ser::{Error as SerError, SerializeTuple, Serializer};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use serde_with::DisplayFromStr;
@@ -23,6 +23,10 @@ use sui_protocol_config::ProtocolVersion;
 
 use crate::{parse_sui_struct_tag, parse_sui_type_tag};
 
+#[cfg(test)]
+#[path = "unit_tests/sui_serde_tests.rs"]
+mod sui_serde_tests;
+
 #[inline]
 fn to_custom_error<'de, D, E>(e: E) -> D::Error
 where
@@ -102,7 +106,7 @@ impl SerializeAs<AccountAddress> for HexAccountAddress {
     where
         S: Serializer,
     {
-        Hex::serialize_as(value, serializer)
+        SimdHex::serialize_as(value, serializer)
     }
 }
 
@@ -121,6 +125,83 @@ impl<'de> DeserializeAs<'de, AccountAddress> for HexAccountAddress {
     }
 }
 
+/// Hex with a `0x` prefix, encoded and decoded with the SIMD instructions of the CPU detected at
+/// runtime. Serializes the same strings as [fastcrypto::encoding::Hex], and reads strings with or
+/// without the prefix.
+pub struct SimdHex;
+
+impl SimdHex {
+    pub fn encode(bytes: &[u8]) -> String {
+        let mut s = String::with_capacity(2 + 2 * bytes.len());
+        s.push_str("0x");
+        s.push_str(&hex_simd::encode_to_string(
+            bytes,
+            hex_simd::AsciiCase::Lower,
+        ));
+        s
+    }
+
+    pub fn decode(s: &str) -> Result<Vec<u8>, hex_simd::Error> {
+        hex_simd::decode_to_vec(s.strip_prefix("0x").unwrap_or(s))
+    }
+}
+
+impl<T: AsRef<[u8]>> SerializeAs<T> for SimdHex {
+    fn serialize_as<S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&Self::encode(value.as_ref()))
+    }
+}
+
+impl<'de, T: TryFrom<Vec<u8>>> DeserializeAs<'de, T> for SimdHex {
+    fn deserialize_as<D>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        let bytes = Self::decode(&s).map_err(to_custom_error::<'de, D, _>)?;
+        let length = bytes.len();
+        T::try_from(bytes).map_err(|_| Error::custom(format!("Invalid length {length}")))
+    }
+}
+
+/// Standard padded Base64, encoded and decoded with the SIMD instructions of the CPU detected at
+/// runtime. Serializes the same strings as [fastcrypto::encoding::Base64].
+pub struct SimdBase64;
+
+impl SimdBase64 {
+    pub fn encode(bytes: &[u8]) -> String {
+        base64_simd::STANDARD.encode_to_string(bytes)
+    }
+
+    pub fn decode(s: &str) -> Result<Vec<u8>, base64_simd::Error> {
+        base64_simd::STANDARD.decode_to_vec(s)
+    }
+}
+
+impl<T: AsRef<[u8]>> SerializeAs<T> for SimdBase64 {
+    fn serialize_as<S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&Self::encode(value.as_ref()))
+    }
+}
+
+impl<'de, T: TryFrom<Vec<u8>>> DeserializeAs<'de, T> for SimdBase64 {
+    fn deserialize_as<D>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        let bytes = Self::decode(&s).map_err(to_custom_error::<'de, D, _>)?;
+        let length = bytes.len();
+        T::try_from(bytes).map_err(|_| Error::custom(format!("Invalid length {length}")))
+    }
+}
+
 /// Serializes a bitmap according to the roaring bitmap on-disk standard.
 /// <https://github.com/RoaringBitmap/RoaringFormatSpec>
 pub struct SuiBitmap;
@@ -348,3 +429,132 @@ impl<'de> DeserializeAs<'de, ProtocolVersion> for AsProtocolVersion {
         Ok(ProtocolVersion::from(*b))
     }
 }
+
+/// A format of the values stored by a [VersionedEnvelope].
+///
+/// To change the format of a type, rename its current definition (e.g. `FooV1`), and bump
+/// `FORMAT_VERSION` of the new one, decoding the values stored with the previous versions in
+/// `decode_version`, which migrates them to the latest format at read time. Binaries reading a
+/// value stored with a newer version than theirs fail to decode it instead of misreading it, so
+/// a binary can only be downgraded to a release which knows the format version of the values
+/// stored since.
+pub trait VersionedFormat: Serialize + DeserializeOwned {
+    /// The version of the format the values are stored with.
+    const FORMAT_VERSION: u8;
+
+    /// Decodes the payload of a value stored with an older `version` of the format, usually with
+    /// [decode_payload_as]. Returns None for the versions which can not be decoded anymore.
+    fn decode_version<'de, A: SeqAccess<'de>>(
+        version: u8,
+        payload: &mut A,
+    ) -> Result<Option<Self>, A::Error> {
+        let _ = (version, payload);
+        Ok(None)
+    }
+}
+
+/// Decodes a payload stored as `Old`, migrating it to `T`.
+pub fn decode_payload_as<'de, Old, T, A>(payload: &mut A) -> Result<T, A::Error>
+where
+    Old: Deserialize<'de> + Into<T>,
+    A: SeqAccess<'de>,
+{
+    payload
+        .next_element::<Old>()?
+        .map(Into::into)
+        .ok_or_else(|| A::Error::invalid_length(1, &"a format version and a payload"))
+}
+
+/// Serializes a value as the version of its format followed by its payload, decoding the values
+/// stored with older versions of the format with [VersionedFormat::decode_version].
+///
+/// In BCS, a value of format version `N` is serialized exactly as the variant `N` of an enum
+/// holding it, so that single-variant version enums such as
+///
+/// ```text
+/// enum FooWrapper {
+///     V1(Foo),
+/// }
+/// ```
+///
+/// can be replaced by `VersionedEnvelope<Foo>` with a format version 0 without migrating the
+/// stored values.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionedEnvelope<T>(T);
+
+impl<T> VersionedEnvelope<T> {
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for VersionedEnvelope<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: VersionedFormat> Serialize for VersionedEnvelope<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&T::FORMAT_VERSION)?;
+        tuple.serialize_element(&self.0)?;
+        tuple.end()
+    }
+}
+
+impl<'de, T: VersionedFormat> Deserialize<'de> for VersionedEnvelope<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct EnvelopeVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: VersionedFormat> Visitor<'de> for EnvelopeVisitor<T> {
+            type Value = VersionedEnvelope<T>;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                f.write_str("a format version and a payload")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let version: u8 = seq
+                    .next_element()?
+                    .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+                let type_name = std::any::type_name::<T>();
+                let value = match version.cmp(&T::FORMAT_VERSION) {
+                    Ordering::Equal => decode_payload_as::<T, T, A>(&mut seq)?,
+                    Ordering::Less => T::decode_version(version, &mut seq)?.ok_or_else(|| {
+                        A::Error::custom(format!(
+                            "format version {version} of {type_name} can not be decoded anymore"
+                        ))
+                    })?,
+                    Ordering::Greater => {
+                        return Err(A::Error::custom(format!(
+                            "format version {version} of {type_name} is newer than the latest \
+                             known version {}",
+                            T::FORMAT_VERSION
+                        )))
+                    }
+                };
+                Ok(VersionedEnvelope(value))
+            }
+        }
+
+        deserializer.deserialize_tuple(2, EnvelopeVisitor(PhantomData))
+    }
+}
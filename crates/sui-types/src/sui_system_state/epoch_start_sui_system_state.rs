@@ -1,20 +1,25 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use enum_dispatch::enum_dispatch;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::HashMap;
 
 use crate::base_types::{AuthorityName, EpochId, SuiAddress};
 use crate::committee::{Committee, StakeUnit};
 use crate::multiaddr::Multiaddr;
-use anemo::types::{PeerAffinity, PeerInfo};
-use anemo::PeerId;
-use narwhal_config::{Committee as NarwhalCommittee, CommitteeBuilder, WorkerCache, WorkerIndex};
 use serde::{Deserialize, Serialize};
 use sui_protocol_config::ProtocolVersion;
-use tracing::warn;
 
-#[enum_dispatch]
+// The conversions to the committees and peers of the network are only needed by validators and
+// fullnodes, and are left out of wasm32 builds, which anemo and narwhal do not support.
+#[cfg(not(target_arch = "wasm32"))]
+use {
+    anemo::types::{PeerAffinity, PeerInfo},
+    anemo::PeerId,
+    narwhal_config::{Committee as NarwhalCommittee, CommitteeBuilder, WorkerCache, WorkerIndex},
+    std::collections::BTreeMap,
+    tracing::warn,
+};
+
 pub trait EpochStartSystemStateTrait {
     fn epoch(&self) -> EpochId;
     fn protocol_version(&self) -> ProtocolVersion;
@@ -23,10 +28,14 @@ pub trait EpochStartSystemStateTrait {
     fn epoch_start_timestamp_ms(&self) -> u64;
     fn epoch_duration_ms(&self) -> u64;
     fn get_sui_committee(&self) -> Committee;
+    #[cfg(not(target_arch = "wasm32"))]
     fn get_narwhal_committee(&self) -> NarwhalCommittee;
+    #[cfg(not(target_arch = "wasm32"))]
     fn get_validator_as_p2p_peers(&self, excluding_self: AuthorityName) -> Vec<PeerInfo>;
+    #[cfg(not(target_arch = "wasm32"))]
     fn get_authority_names_to_peer_ids(&self) -> HashMap<AuthorityName, PeerId>;
     fn get_authority_names_to_hostnames(&self) -> HashMap<AuthorityName, String>;
+    #[cfg(not(target_arch = "wasm32"))]
     fn get_narwhal_worker_cache(&self, transactions_address: &Multiaddr) -> WorkerCache;
 }
 
@@ -38,7 +47,6 @@ pub trait EpochStartSystemStateTrait {
 /// also add new db tables to store the new version. This is OK because we only store one copy of
 /// this as part of EpochStartConfiguration for the most recent epoch in the db.
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
-#[enum_dispatch(EpochStartSystemStateTrait)]
 pub enum EpochStartSystemState {
     V1(EpochStartSystemStateV1),
 }
@@ -73,6 +81,86 @@ impl EpochStartSystemState {
     }
 }
 
+// Dispatched by hand rather than with enum_dispatch, which does not carry the cfg attributes of
+// the methods over to the impl it generates.
+impl EpochStartSystemStateTrait for EpochStartSystemState {
+    fn epoch(&self) -> EpochId {
+        match self {
+            Self::V1(state) => state.epoch(),
+        }
+    }
+
+    fn protocol_version(&self) -> ProtocolVersion {
+        match self {
+            Self::V1(state) => state.protocol_version(),
+        }
+    }
+
+    fn reference_gas_price(&self) -> u64 {
+        match self {
+            Self::V1(state) => state.reference_gas_price(),
+        }
+    }
+
+    fn safe_mode(&self) -> bool {
+        match self {
+            Self::V1(state) => state.safe_mode(),
+        }
+    }
+
+    fn epoch_start_timestamp_ms(&self) -> u64 {
+        match self {
+            Self::V1(state) => state.epoch_start_timestamp_ms(),
+        }
+    }
+
+    fn epoch_duration_ms(&self) -> u64 {
+        match self {
+            Self::V1(state) => state.epoch_duration_ms(),
+        }
+    }
+
+    fn get_sui_committee(&self) -> Committee {
+        match self {
+            Self::V1(state) => state.get_sui_committee(),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn get_narwhal_committee(&self) -> NarwhalCommittee {
+        match self {
+            Self::V1(state) => state.get_narwhal_committee(),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn get_validator_as_p2p_peers(&self, excluding_self: AuthorityName) -> Vec<PeerInfo> {
+        match self {
+            Self::V1(state) => state.get_validator_as_p2p_peers(excluding_self),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn get_authority_names_to_peer_ids(&self) -> HashMap<AuthorityName, PeerId> {
+        match self {
+            Self::V1(state) => state.get_authority_names_to_peer_ids(),
+        }
+    }
+
+    fn get_authority_names_to_hostnames(&self) -> HashMap<AuthorityName, String> {
+        match self {
+            Self::V1(state) => state.get_authority_names_to_hostnames(),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn get_narwhal_worker_cache(&self, transactions_address: &Multiaddr) -> WorkerCache {
+        match self {
+            Self::V1(state) => state.get_narwhal_worker_cache(transactions_address),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
 pub struct EpochStartSystemStateV1 {
     epoch: EpochId,
@@ -136,6 +224,7 @@ impl EpochStartSystemStateTrait for EpochStartSystemStateV1 {
         Committee::new(self.epoch, voting_rights)
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
     #[allow(clippy::mutable_key_type)]
     fn get_narwhal_committee(&self) -> NarwhalCommittee {
         let mut committee_builder = CommitteeBuilder::new(self.epoch as narwhal_config::Epoch);
@@ -152,6 +241,7 @@ impl EpochStartSystemStateTrait for EpochStartSystemStateV1 {
         committee_builder.build()
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
     fn get_validator_as_p2p_peers(&self, excluding_self: AuthorityName) -> Vec<PeerInfo> {
         self.active_validators
             .iter()
@@ -178,6 +268,7 @@ impl EpochStartSystemStateTrait for EpochStartSystemStateV1 {
             .collect()
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
     fn get_authority_names_to_peer_ids(&self) -> HashMap<AuthorityName, PeerId> {
         self.active_validators
             .iter()
@@ -202,6 +293,7 @@ impl EpochStartSystemStateTrait for EpochStartSystemStateV1 {
             .collect()
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
     #[allow(clippy::mutable_key_type)]
     fn get_narwhal_worker_cache(&self, transactions_address: &Multiaddr) -> WorkerCache {
         let workers: BTreeMap<narwhal_crypto::PublicKey, WorkerIndex> = self
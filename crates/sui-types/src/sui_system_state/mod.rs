@@ -1,8 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::base_types::ObjectID;
-use crate::committee::CommitteeWithNetworkMetadata;
+use crate::base_types::{ObjectID, SuiAddress};
+use crate::committee::{
+    CommitteeWithNetworkMetadata, EpochId, StakeUnit, QUORUM_THRESHOLD, TOTAL_VOTING_POWER,
+};
 use crate::dynamic_field::{
     get_dynamic_field_from_store, get_dynamic_field_object_from_store, Field,
 };
@@ -29,6 +31,10 @@ pub mod sui_system_state_inner_v1;
 pub mod sui_system_state_inner_v2;
 pub mod sui_system_state_summary;
 
+#[cfg(test)]
+#[path = "../unit_tests/sui_system_state_tests.rs"]
+mod sui_system_state_tests;
+
 #[cfg(msim)]
 mod simtest_sui_system_state_inner;
 #[cfg(msim)]
@@ -40,6 +46,9 @@ use self::simtest_sui_system_state_inner::{
 const SUI_SYSTEM_STATE_WRAPPER_STRUCT_NAME: &IdentStr = ident_str!("SuiSystemState");
 
 pub const SUI_SYSTEM_MODULE_NAME: &IdentStr = ident_str!("sui_system");
+pub const VALIDATOR_SET_MODULE_NAME: &IdentStr = ident_str!("validator_set");
+const VALIDATOR_EPOCH_INFO_EVENT_V2_STRUCT_NAME: &IdentStr =
+    ident_str!("ValidatorEpochInfoEventV2");
 pub const ADVANCE_EPOCH_FUNCTION_NAME: &IdentStr = ident_str!("advance_epoch");
 pub const ADVANCE_EPOCH_SAFE_MODE_FUNCTION_NAME: &IdentStr = ident_str!("advance_epoch_safe_mode");
 
@@ -172,6 +181,8 @@ pub trait SuiSystemStateTrait {
     fn epoch_start_timestamp_ms(&self) -> u64;
     fn epoch_duration_ms(&self) -> u64;
     fn safe_mode(&self) -> bool;
+    /// Total stake of the active validators.
+    fn total_stake(&self) -> u64;
     fn advance_epoch_safe_mode(&mut self, params: &AdvanceEpochParams);
     fn get_current_epoch_committee(&self) -> CommitteeWithNetworkMetadata;
     fn into_epoch_start_state(self) -> EpochStartSystemState;
@@ -378,6 +389,44 @@ where
     }
 }
 
+/// Load the exchange rate of a staking pool at `epoch` from its `exchange_rates` table.
+pub fn get_exchange_rate_at_epoch<S>(
+    object_store: &S,
+    exchange_rates_id: ObjectID,
+    epoch: EpochId,
+) -> Result<PoolTokenExchangeRate, SuiError>
+where
+    S: ObjectStore,
+{
+    get_dynamic_field_from_store(object_store, exchange_rates_id, &epoch).map_err(|err| {
+        SuiError::SuiSystemStateReadError(format!("Failed to get exchange rate: {:?}", err))
+    })
+}
+
+/// Rust version of the Move sui_system::validator_set::derive_reference_gas_price function.
+/// Given the gas price quotes of the validators with their voting power, return the price quoted
+/// by the validator at the 2/3 percentile of the voting power, with the validators ordered by
+/// ascending gas price.
+pub fn derive_reference_gas_price<I>(quotes: I) -> u64
+where
+    I: IntoIterator<Item = (u64, StakeUnit)>,
+{
+    let mut quotes: Vec<_> = quotes.into_iter().collect();
+    // Highest gas price first.
+    quotes.sort_by(|a, b| b.0.cmp(&a.0));
+    let threshold = TOTAL_VOTING_POWER - QUORUM_THRESHOLD;
+    let mut sum = 0;
+    let mut result = 0;
+    for (gas_price, voting_power) in quotes {
+        if sum >= threshold {
+            break;
+        }
+        result = gas_price;
+        sum += voting_power;
+    }
+    result
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Default)]
 pub struct PoolTokenExchangeRate {
     sui_amount: u64,
@@ -385,6 +434,21 @@ pub struct PoolTokenExchangeRate {
 }
 
 impl PoolTokenExchangeRate {
+    pub fn new(sui_amount: u64, pool_token_amount: u64) -> Self {
+        Self {
+            sui_amount,
+            pool_token_amount,
+        }
+    }
+
+    pub fn sui_amount(&self) -> u64 {
+        self.sui_amount
+    }
+
+    pub fn pool_token_amount(&self) -> u64 {
+        self.pool_token_amount
+    }
+
     /// Rate of the staking pool, pool token amount : Sui amount
     pub fn rate(&self) -> f64 {
         if self.sui_amount == 0 {
@@ -395,6 +459,35 @@ impl PoolTokenExchangeRate {
     }
 }
 
+/// Rust version of the Move sui_system::validator_set::ValidatorEpochInfoEventV2 type, emitted
+/// for each validator of the committee at every epoch change.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct ValidatorEpochInfoEventV2 {
+    pub epoch: u64,
+    pub validator_address: SuiAddress,
+    pub reference_gas_survey_quote: u64,
+    pub stake: u64,
+    pub voting_power: u64,
+    pub commission_rate: u64,
+    pub pool_staking_reward: u64,
+    pub storage_fund_staking_reward: u64,
+    pub pool_token_exchange_rate: PoolTokenExchangeRate,
+    pub tallying_rule_reporters: Vec<SuiAddress>,
+    /// 0 if the rewards of the validator were slashed, 1 otherwise.
+    pub tallying_rule_global_score: u64,
+}
+
+impl ValidatorEpochInfoEventV2 {
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_SYSTEM_ADDRESS,
+            module: VALIDATOR_SET_MODULE_NAME.to_owned(),
+            name: VALIDATOR_EPOCH_INFO_EVENT_V2_STRUCT_NAME.to_owned(),
+            type_params: vec![],
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
 pub struct ValidatorWrapper {
     pub inner: Versioned,
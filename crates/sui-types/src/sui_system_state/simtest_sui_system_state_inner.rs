@@ -154,6 +154,14 @@ impl SuiSystemStateTrait for SimTestSuiSystemStateInnerV1 {
         self.safe_mode
     }
 
+    fn total_stake(&self) -> u64 {
+        self.validators
+            .active_validators
+            .iter()
+            .map(|v| v.stake.value())
+            .sum()
+    }
+
     fn advance_epoch_safe_mode(&mut self, params: &AdvanceEpochParams) {
         self.epoch = params.epoch;
         self.safe_mode = true;
@@ -261,6 +269,14 @@ impl SuiSystemStateTrait for SimTestSuiSystemStateInnerShallowV2 {
         self.safe_mode
     }
 
+    fn total_stake(&self) -> u64 {
+        self.validators
+            .active_validators
+            .iter()
+            .map(|v| v.stake.value())
+            .sum()
+    }
+
     fn advance_epoch_safe_mode(&mut self, params: &AdvanceEpochParams) {
         self.epoch = params.epoch;
         self.safe_mode = true;
@@ -397,6 +413,14 @@ impl SuiSystemStateTrait for SimTestSuiSystemStateInnerDeepV2 {
         self.safe_mode
     }
 
+    fn total_stake(&self) -> u64 {
+        self.validators
+            .active_validators
+            .iter()
+            .map(|v| v.stake.value())
+            .sum()
+    }
+
     fn advance_epoch_safe_mode(&mut self, params: &AdvanceEpochParams) {
         self.epoch = params.epoch;
         self.safe_mode = true;
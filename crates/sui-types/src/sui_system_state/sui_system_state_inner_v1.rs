@@ -150,19 +150,19 @@ impl ValidatorMetadataV1 {
         let p2p_address = Multiaddr::try_from(self.p2p_address.clone())
             .map_err(|_| E_METADATA_INVALID_P2P_ADDR)?;
         p2p_address
-            .to_anemo_address()
+            .validate_anemo_address()
             .map_err(|_| E_METADATA_INVALID_P2P_ADDR)?;
 
         let primary_address = Multiaddr::try_from(self.primary_address.clone())
             .map_err(|_| E_METADATA_INVALID_PRIMARY_ADDR)?;
         primary_address
-            .to_anemo_address()
+            .validate_anemo_address()
             .map_err(|_| E_METADATA_INVALID_PRIMARY_ADDR)?;
 
         let worker_address = Multiaddr::try_from(self.worker_address.clone())
             .map_err(|_| E_METADATA_INVALID_WORKER_ADDR)?;
         worker_address
-            .to_anemo_address()
+            .validate_anemo_address()
             .map_err(|_| E_METADATA_INVALID_WORKER_ADDR)?;
 
         let next_epoch_protocol_pubkey = match self.next_epoch_protocol_pubkey_bytes.clone() {
@@ -232,7 +232,7 @@ impl ValidatorMetadataV1 {
                 let address =
                     Multiaddr::try_from(address).map_err(|_| E_METADATA_INVALID_P2P_ADDR)?;
                 address
-                    .to_anemo_address()
+                    .validate_anemo_address()
                     .map_err(|_| E_METADATA_INVALID_P2P_ADDR)?;
 
                 Ok(Some(address))
@@ -245,7 +245,7 @@ impl ValidatorMetadataV1 {
                 let address =
                     Multiaddr::try_from(address).map_err(|_| E_METADATA_INVALID_PRIMARY_ADDR)?;
                 address
-                    .to_anemo_address()
+                    .validate_anemo_address()
                     .map_err(|_| E_METADATA_INVALID_PRIMARY_ADDR)?;
 
                 Ok(Some(address))
@@ -258,7 +258,7 @@ impl ValidatorMetadataV1 {
                 let address =
                     Multiaddr::try_from(address).map_err(|_| E_METADATA_INVALID_WORKER_ADDR)?;
                 address
-                    .to_anemo_address()
+                    .validate_anemo_address()
                     .map_err(|_| E_METADATA_INVALID_WORKER_ADDR)?;
 
                 Ok(Some(address))
@@ -527,6 +527,10 @@ impl SuiSystemStateTrait for SuiSystemStateInnerV1 {
         self.safe_mode
     }
 
+    fn total_stake(&self) -> u64 {
+        self.validators.total_stake
+    }
+
     fn advance_epoch_safe_mode(&mut self, params: &AdvanceEpochParams) {
         self.epoch = params.epoch;
         self.safe_mode = true;
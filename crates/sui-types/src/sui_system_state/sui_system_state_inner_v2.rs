@@ -102,6 +102,10 @@ impl SuiSystemStateTrait for SuiSystemStateInnerV2 {
         self.safe_mode
     }
 
+    fn total_stake(&self) -> u64 {
+        self.validators.total_stake
+    }
+
     fn advance_epoch_safe_mode(&mut self, params: &AdvanceEpochParams) {
         self.epoch = params.epoch;
         self.safe_mode = true;
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::base_types::{AuthorityName, ObjectID, SuiAddress};
+use crate::committee::EpochId;
 use crate::committee::{Committee, CommitteeWithNetworkMetadata, NetworkMetadata};
 use crate::dynamic_field::get_dynamic_field_from_store;
 use crate::error::SuiError;
@@ -9,7 +10,11 @@ use crate::id::ID;
 use crate::multiaddr::Multiaddr;
 use crate::storage::ObjectStore;
 use crate::sui_serde::BigInt;
-use crate::sui_system_state::get_validator_from_table;
+use crate::sui_serde::SimdBase64;
+use crate::sui_system_state::{
+    derive_reference_gas_price, get_exchange_rate_at_epoch, get_validator_from_table,
+    PoolTokenExchangeRate,
+};
 use fastcrypto::encoding::Base64;
 use fastcrypto::traits::ToFromBytes;
 use schemars::JsonSchema;
@@ -184,6 +189,41 @@ pub struct SuiSystemStateSummary {
 }
 
 impl SuiSystemStateSummary {
+    pub fn active_validator_by_address(
+        &self,
+        address: &SuiAddress,
+    ) -> Option<&SuiValidatorSummary> {
+        self.active_validators
+            .iter()
+            .find(|v| &v.sui_address == address)
+    }
+
+    pub fn active_validator_by_pool_id(&self, pool_id: &ObjectID) -> Option<&SuiValidatorSummary> {
+        self.active_validators
+            .iter()
+            .find(|v| &v.staking_pool_id == pool_id)
+    }
+
+    /// Reference gas price derived from the gas price quotes of the active validators for the
+    /// current epoch, which should match `reference_gas_price`.
+    pub fn derive_reference_gas_price(&self) -> u64 {
+        derive_reference_gas_price(
+            self.active_validators
+                .iter()
+                .map(|v| (v.gas_price, v.voting_power)),
+        )
+    }
+
+    /// Reference gas price of the next epoch, if the active validators stay in the committee
+    /// with their current voting power.
+    pub fn estimate_next_epoch_reference_gas_price(&self) -> u64 {
+        derive_reference_gas_price(
+            self.active_validators
+                .iter()
+                .map(|v| (v.next_epoch_gas_price, v.voting_power)),
+        )
+    }
+
     pub fn get_sui_committee_for_benchmarking(&self) -> CommitteeWithNetworkMetadata {
         let mut voting_rights = BTreeMap::new();
         let mut network_metadata = BTreeMap::new();
@@ -215,16 +255,16 @@ pub struct SuiValidatorSummary {
     // Metadata
     pub sui_address: SuiAddress,
     #[schemars(with = "Base64")]
-    #[serde_as(as = "Base64")]
+    #[serde_as(as = "SimdBase64")]
     pub protocol_pubkey_bytes: Vec<u8>,
     #[schemars(with = "Base64")]
-    #[serde_as(as = "Base64")]
+    #[serde_as(as = "SimdBase64")]
     pub network_pubkey_bytes: Vec<u8>,
     #[schemars(with = "Base64")]
-    #[serde_as(as = "Base64")]
+    #[serde_as(as = "SimdBase64")]
     pub worker_pubkey_bytes: Vec<u8>,
     #[schemars(with = "Base64")]
-    #[serde_as(as = "Base64")]
+    #[serde_as(as = "SimdBase64")]
     pub proof_of_possession_bytes: Vec<u8>,
     pub name: String,
     pub description: String,
@@ -314,6 +354,20 @@ pub struct SuiValidatorSummary {
     pub exchange_rates_size: u64,
 }
 
+impl SuiValidatorSummary {
+    /// Exchange rate of the staking pool of the validator at `epoch`.
+    pub fn exchange_rate_at_epoch<S>(
+        &self,
+        object_store: &S,
+        epoch: EpochId,
+    ) -> Result<PoolTokenExchangeRate, SuiError>
+    where
+        S: ObjectStore,
+    {
+        get_exchange_rate_at_epoch(object_store, self.exchange_rates_id, epoch)
+    }
+}
+
 impl Default for SuiSystemStateSummary {
     fn default() -> Self {
         Self {
@@ -0,0 +1,379 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Builders of valid and internally consistent instances of the core types for tests, enabled
+//! with the `test-fixtures` feature. The values which are not set explicitly are drawn from the
+//! random number generator passed to the builders, so that tests seeding it with [rng], e.g. from
+//! a proptest strategy, build the same instances on every run and can be replayed from their seed.
+
+use std::collections::BTreeMap;
+
+use fastcrypto::traits::KeyPair;
+use rand::rngs::StdRng;
+use rand::{CryptoRng, Rng, RngCore, SeedableRng};
+use shared_crypto::intent::Intent;
+
+use crate::base_types::{
+    ExecutionDigests, ObjectID, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest,
+    TransactionEffectsDigest,
+};
+use crate::committee::{Committee, ProtocolVersion};
+use crate::crypto::{get_key_pair_from_rng, AccountKeyPair, AuthorityKeyPair};
+use crate::gas::GasCostSummary;
+use crate::message_envelope::Message;
+use crate::messages::{ProgrammableTransaction, Transaction, TransactionData, DUMMY_GAS_PRICE};
+use crate::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointContents, CheckpointSummary, EndOfEpochData,
+    SignedCheckpointSummary,
+};
+use crate::object::{MoveObject, Object, Owner, OBJECT_START_VERSION};
+use crate::programmable_transaction_builder::ProgrammableTransactionBuilder;
+
+#[cfg(test)]
+#[path = "unit_tests/test_fixtures_tests.rs"]
+mod test_fixtures_tests;
+
+/// Returns a random number generator for the builders, seeded with `seed`.
+pub fn rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+pub fn random_address(rng: &mut impl Rng) -> SuiAddress {
+    ObjectID::new(rng.gen()).into()
+}
+
+pub fn random_object_id(rng: &mut impl Rng) -> ObjectID {
+    ObjectID::new(rng.gen())
+}
+
+pub fn random_transaction_digest(rng: &mut impl Rng) -> TransactionDigest {
+    TransactionDigest::new(rng.gen())
+}
+
+pub fn random_execution_digests(rng: &mut impl Rng) -> ExecutionDigests {
+    ExecutionDigests::new(
+        random_transaction_digest(rng),
+        TransactionEffectsDigest::new(rng.gen()),
+    )
+}
+
+/// Builds gas coins, owned by a random address unless set otherwise.
+#[derive(Clone, Debug, Default)]
+pub struct ObjectBuilder {
+    id: Option<ObjectID>,
+    version: Option<SequenceNumber>,
+    owner: Option<Owner>,
+    balance: Option<u64>,
+    previous_transaction: Option<TransactionDigest>,
+}
+
+impl ObjectBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn id(mut self, id: ObjectID) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    pub fn version(mut self, version: SequenceNumber) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    pub fn owner(mut self, owner: Owner) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    pub fn address_owner(self, owner: SuiAddress) -> Self {
+        self.owner(Owner::AddressOwner(owner))
+    }
+
+    /// Makes the object shared since `initial_shared_version`, at which it is built unless its
+    /// version is set.
+    pub fn shared(self, initial_shared_version: SequenceNumber) -> Self {
+        self.owner(Owner::Shared {
+            initial_shared_version,
+        })
+    }
+
+    pub fn balance(mut self, balance: u64) -> Self {
+        self.balance = Some(balance);
+        self
+    }
+
+    pub fn previous_transaction(mut self, digest: TransactionDigest) -> Self {
+        self.previous_transaction = Some(digest);
+        self
+    }
+
+    pub fn build(self, rng: &mut impl Rng) -> Object {
+        let id = self.id.unwrap_or_else(|| random_object_id(rng));
+        let owner = self
+            .owner
+            .unwrap_or_else(|| Owner::AddressOwner(random_address(rng)));
+        let version = self.version.unwrap_or(match owner {
+            Owner::Shared {
+                initial_shared_version,
+            } => initial_shared_version,
+            _ => OBJECT_START_VERSION,
+        });
+        let balance = self
+            .balance
+            .unwrap_or_else(|| rng.gen_range(1..1_000_000_000));
+        let previous_transaction = self
+            .previous_transaction
+            .unwrap_or_else(|| random_transaction_digest(rng));
+        Object::new_move(
+            MoveObject::new_gas_coin(version, id, balance),
+            owner,
+            previous_transaction,
+        )
+    }
+}
+
+/// A transaction built by a [TransactionDataBuilder], along with the key of its sender and the
+/// gas coins it pays with.
+pub struct TransactionFixture {
+    pub data: TransactionData,
+    pub sender_key: AccountKeyPair,
+    pub gas: Vec<Object>,
+}
+
+impl TransactionFixture {
+    pub fn sender(&self) -> SuiAddress {
+        self.sender_key.public().into()
+    }
+
+    pub fn gas_refs(&self) -> Vec<ObjectRef> {
+        self.gas
+            .iter()
+            .map(|gas| gas.compute_object_reference())
+            .collect()
+    }
+
+    /// Returns the transaction signed by its sender.
+    pub fn sign(&self) -> Transaction {
+        Transaction::from_data_and_signer(
+            self.data.clone(),
+            Intent::sui_transaction(),
+            vec![&self.sender_key],
+        )
+    }
+}
+
+/// Builds programmable transactions, by default transferring SUI from the gas coin of a random
+/// sender to a random address.
+pub struct TransactionDataBuilder {
+    sender_key: Option<AccountKeyPair>,
+    programmable: Option<ProgrammableTransaction>,
+    gas: Vec<Object>,
+    gas_budget: u64,
+    gas_price: u64,
+}
+
+impl Default for TransactionDataBuilder {
+    fn default() -> Self {
+        Self {
+            sender_key: None,
+            programmable: None,
+            gas: vec![],
+            gas_budget: 10_000,
+            gas_price: DUMMY_GAS_PRICE,
+        }
+    }
+}
+
+impl TransactionDataBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn sender_key(mut self, sender_key: AccountKeyPair) -> Self {
+        self.sender_key = Some(sender_key);
+        self
+    }
+
+    pub fn programmable(mut self, programmable: ProgrammableTransaction) -> Self {
+        self.programmable = Some(programmable);
+        self
+    }
+
+    /// Pays for the transaction with `gas`, expected to be owned by the sender. A coin of the
+    /// sender holding ten times the budget is built otherwise.
+    pub fn gas(mut self, gas: Vec<Object>) -> Self {
+        self.gas = gas;
+        self
+    }
+
+    pub fn gas_budget(mut self, gas_budget: u64) -> Self {
+        self.gas_budget = gas_budget;
+        self
+    }
+
+    pub fn gas_price(mut self, gas_price: u64) -> Self {
+        self.gas_price = gas_price;
+        self
+    }
+
+    pub fn build<R: CryptoRng + RngCore>(self, rng: &mut R) -> TransactionFixture {
+        let sender_key = self
+            .sender_key
+            .unwrap_or_else(|| get_key_pair_from_rng(rng).1);
+        let sender = sender_key.public().into();
+        let programmable = self.programmable.unwrap_or_else(|| {
+            let mut builder = ProgrammableTransactionBuilder::new();
+            builder.transfer_sui(random_address(rng), Some(rng.gen_range(1..1000)));
+            builder.finish()
+        });
+        let gas = if self.gas.is_empty() {
+            vec![ObjectBuilder::new()
+                .address_owner(sender)
+                .balance(self.gas_budget * 10)
+                .build(rng)]
+        } else {
+            self.gas
+        };
+        let data = TransactionData::new_programmable(
+            sender,
+            gas.iter()
+                .map(|gas| gas.compute_object_reference())
+                .collect(),
+            programmable,
+            self.gas_budget,
+            self.gas_price,
+        );
+        TransactionFixture {
+            data,
+            sender_key,
+            gas,
+        }
+    }
+}
+
+/// Builds a chain of checkpoints certified by a committee, each one following the previous one
+/// built, holding the transactions added since.
+pub struct CheckpointBuilder {
+    committee: Committee,
+    keys: Vec<AuthorityKeyPair>,
+    previous: Option<CheckpointSummary>,
+    transactions: Vec<ExecutionDigests>,
+    next_committee: Option<(Committee, Vec<AuthorityKeyPair>)>,
+}
+
+impl CheckpointBuilder {
+    /// Starts the chain at epoch 0, with a random committee of four validators.
+    pub fn new<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
+        let (committee, keys) = random_committee(0, rng);
+        Self::with_committee(committee, keys)
+    }
+
+    /// Starts the chain at the epoch of `committee`, of which `keys` are the validator keys.
+    pub fn with_committee(committee: Committee, keys: Vec<AuthorityKeyPair>) -> Self {
+        Self {
+            committee,
+            keys,
+            previous: None,
+            transactions: vec![],
+            next_committee: None,
+        }
+    }
+
+    /// The committee certifying the next checkpoint.
+    pub fn committee(&self) -> &Committee {
+        &self.committee
+    }
+
+    /// The last checkpoint built, if any.
+    pub fn previous(&self) -> Option<&CheckpointSummary> {
+        self.previous.as_ref()
+    }
+
+    pub fn transaction(&mut self, digests: ExecutionDigests) -> &mut Self {
+        self.transactions.push(digests);
+        self
+    }
+
+    pub fn random_transactions(&mut self, count: usize, rng: &mut impl Rng) -> &mut Self {
+        for _ in 0..count {
+            self.transactions.push(random_execution_digests(rng));
+        }
+        self
+    }
+
+    /// Makes the next checkpoint the last one of its epoch, handing off to a random committee
+    /// of four validators which certifies the checkpoints of the next epoch.
+    pub fn end_epoch<R: CryptoRng + RngCore>(&mut self, rng: &mut R) -> &mut Self {
+        self.next_committee = Some(random_committee(self.committee.epoch + 1, rng));
+        self
+    }
+
+    /// Builds the next checkpoint of the chain.
+    pub fn build(&mut self) -> (CertifiedCheckpointSummary, CheckpointContents) {
+        let contents = CheckpointContents::new_with_causally_ordered_transactions(
+            std::mem::take(&mut self.transactions).into_iter(),
+        );
+        let (sequence_number, network_total_transactions, previous_digest, timestamp_ms) =
+            match &self.previous {
+                Some(previous) => (
+                    previous.sequence_number + 1,
+                    previous.network_total_transactions,
+                    Some(previous.digest()),
+                    previous.timestamp_ms + 1000,
+                ),
+                None => (0, 0, None, 0),
+            };
+        let end_of_epoch_data = self
+            .next_committee
+            .as_ref()
+            .map(|(committee, _)| EndOfEpochData {
+                next_epoch_committee: committee.voting_rights.clone(),
+                next_epoch_protocol_version: ProtocolVersion::MAX,
+                epoch_commitments: vec![],
+            });
+        let summary = CheckpointSummary::new(
+            self.committee.epoch,
+            sequence_number,
+            network_total_transactions + contents.size() as u64,
+            &contents,
+            previous_digest,
+            GasCostSummary::default(),
+            end_of_epoch_data,
+            timestamp_ms,
+        );
+        let sign_infos: Vec<_> = self
+            .keys
+            .iter()
+            .map(|key| {
+                SignedCheckpointSummary::sign(
+                    self.committee.epoch,
+                    &summary,
+                    key,
+                    key.public().into(),
+                )
+            })
+            .collect();
+        let certified =
+            CertifiedCheckpointSummary::new(summary.clone(), sign_infos, &self.committee)
+                .expect("The checkpoint is signed by the whole committee");
+
+        self.previous = Some(summary);
+        if let Some((committee, keys)) = self.next_committee.take() {
+            self.committee = committee;
+            self.keys = keys;
+        }
+        (certified, contents)
+    }
+}
+
+fn random_committee<R: CryptoRng + RngCore>(
+    epoch: u64,
+    rng: &mut R,
+) -> (Committee, Vec<AuthorityKeyPair>) {
+    let keys: Vec<AuthorityKeyPair> = (0..4).map(|_| get_key_pair_from_rng(rng).1).collect();
+    let voting_weights: BTreeMap<_, _> = keys.iter().map(|key| (key.public().into(), 1)).collect();
+    let committee = Committee::new_for_testing_with_normalized_voting_power(epoch, voting_weights);
+    (committee, keys)
+}
@@ -0,0 +1,88 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::base_types::TransactionDigest;
+use crate::committee::Committee;
+use crate::error::{SuiError, SuiResult};
+use crate::message_envelope::Message;
+use crate::messages::{Transaction, TransactionEffects, TransactionEffectsAPI, TransactionEvents};
+use crate::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointContents};
+
+#[cfg(test)]
+#[path = "unit_tests/transaction_proof_tests.rs"]
+mod transaction_proof_tests;
+
+/// Proof that a transaction was executed with the given effects and events: the certified
+/// checkpoint summary commits to the checkpoint contents, which hold the digests of the
+/// transaction and of its effects, and the effects commit to the digest of the events.
+///
+/// The proof is self-contained, it can be verified offline against the committee of the epoch of
+/// the checkpoint.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TransactionProof {
+    /// The checkpoint including the transaction.
+    pub checkpoint: CertifiedCheckpointSummary,
+    pub contents: CheckpointContents,
+    /// The transaction with the signatures of its signers.
+    pub transaction: Transaction,
+    pub effects: TransactionEffects,
+    /// The events emitted by the transaction, if any.
+    pub events: Option<TransactionEvents>,
+}
+
+impl TransactionProof {
+    /// Verifies the proof against the committee of the epoch of the checkpoint, and returns the
+    /// digest of the transaction.
+    pub fn verify(&self, committee: &Committee) -> SuiResult<TransactionDigest> {
+        self.checkpoint
+            .verify_with_contents(committee, Some(&self.contents))?;
+        self.transaction.verify_signature()?;
+
+        let transaction = *self.transaction.digest();
+        if self.effects.transaction_digest() != &transaction {
+            return Err(invalid_proof(format!(
+                "the effects are the effects of transaction {}, not of transaction {transaction}",
+                self.effects.transaction_digest()
+            )));
+        }
+        let effects_digest = self.effects.digest();
+        if !self
+            .contents
+            .iter()
+            .any(|digests| digests.transaction == transaction && digests.effects == effects_digest)
+        {
+            return Err(invalid_proof(format!(
+                "effects {effects_digest} of transaction {transaction} are not included in checkpoint {}",
+                self.checkpoint.sequence_number
+            )));
+        }
+
+        match (self.effects.events_digest(), &self.events) {
+            (None, None) => (),
+            (Some(expected), Some(events)) if events.digest() == *expected => (),
+            (Some(expected), Some(events)) => {
+                return Err(invalid_proof(format!(
+                    "events {} do not match the events {expected} of the effects",
+                    events.digest()
+                )))
+            }
+            (Some(expected), None) => {
+                return Err(invalid_proof(format!(
+                    "the events {expected} of the effects are missing"
+                )))
+            }
+            (None, Some(_)) => {
+                return Err(invalid_proof(
+                    "the effects record no events, but the proof holds events".to_string(),
+                ))
+            }
+        }
+        Ok(transaction)
+    }
+}
+
+fn invalid_proof(error: String) -> SuiError {
+    SuiError::InvalidTransactionProof { error }
+}
@@ -1352,3 +1352,115 @@ fn check_approx_effects_components_size() {
         "Update APPROX_SIZE_OF_EXECUTION_STATUS constant"
     );
 }
+
+#[test]
+fn test_validity_check_client_side() {
+    let config = ProtocolConfig::get_for_max_version();
+    let sender = dbg_addr(1);
+    let gas = random_object_ref();
+    let transfer = |gas_payment, gas_budget| {
+        TransactionData::new_transfer(
+            dbg_addr(2),
+            random_object_ref(),
+            sender,
+            gas_payment,
+            gas_budget,
+            DUMMY_GAS_PRICE,
+        )
+    };
+
+    transfer(gas, MAX_GAS_BUDGET_FOR_TESTING)
+        .validity_check_client_side(&config)
+        .unwrap();
+
+    let err = transfer(gas, u64::MAX)
+        .validity_check_client_side(&config)
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        SuiError::UserInputError {
+            error: UserInputError::GasBudgetTooHigh { .. }
+        }
+    ));
+
+    let err = transfer(gas, 0)
+        .validity_check_client_side(&config)
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        SuiError::UserInputError {
+            error: UserInputError::GasBudgetTooLow { .. }
+        }
+    ));
+
+    // The gas coin cannot also be transferred as an input object.
+    let tx = TransactionData::new_transfer(
+        dbg_addr(2),
+        gas,
+        sender,
+        gas,
+        MAX_GAS_BUDGET_FOR_TESTING,
+        DUMMY_GAS_PRICE,
+    );
+    let err = tx.validity_check_client_side(&config).unwrap_err();
+    assert!(matches!(
+        err,
+        SuiError::UserInputError {
+            error: UserInputError::MutableObjectUsedMoreThanOnce { .. }
+        }
+    ));
+
+    // Each pure argument is within limits, but the transaction is too large.
+    let pt = {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let arg_size = config.max_pure_argument_size() as usize / 2;
+        for i in 0..=config.max_tx_size_bytes() as usize / arg_size {
+            builder.pure(vec![i as u8; arg_size]).unwrap();
+        }
+        builder.finish()
+    };
+    let tx = TransactionData::new_programmable(
+        sender,
+        vec![gas],
+        pt,
+        MAX_GAS_BUDGET_FOR_TESTING,
+        DUMMY_GAS_PRICE,
+    );
+    let err = tx.validity_check_client_side(&config).unwrap_err();
+    assert!(matches!(
+        err,
+        SuiError::UserInputError {
+            error: UserInputError::SizeLimitExceeded { .. }
+        }
+    ));
+}
+
+#[test]
+fn test_sender_signed_data_must_contain_one_transaction() {
+    let (receiver, _): (_, AccountKeyPair) = get_key_pair();
+    let (sender, sender_sec): (_, AccountKeyPair) = get_key_pair();
+    let tx = Transaction::from_data_and_signer(
+        TransactionData::new_transfer_with_dummy_gas_price(
+            receiver,
+            random_object_ref(),
+            sender,
+            random_object_ref(),
+            MAX_GAS_BUDGET_FOR_TESTING,
+        ),
+        Intent::sui_transaction(),
+        vec![&sender_sec],
+    );
+
+    let bytes = bcs::to_bytes(tx.data()).unwrap();
+    assert_eq!(
+        &bcs::from_bytes::<SenderSignedData>(&bytes).unwrap(),
+        tx.data()
+    );
+
+    // SenderSignedData is serialized as the list of its transactions.
+    let signed_tx = tx.data().inner().clone();
+    for txs in [vec![], vec![signed_tx.clone(), signed_tx]] {
+        let bytes = bcs::to_bytes(&txs).unwrap();
+        assert!(bcs::from_bytes::<SenderSignedData>(&bytes).is_err());
+    }
+}
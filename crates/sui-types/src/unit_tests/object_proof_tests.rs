@@ -0,0 +1,89 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use rand::rngs::StdRng;
+
+use super::*;
+use crate::base_types::ExecutionDigests;
+use crate::messages::TransactionEffectsV1;
+use crate::object::Owner;
+use crate::test_fixtures::{random_execution_digests, rng, CheckpointBuilder, ObjectBuilder};
+
+fn make_proof(
+    checkpoints: &mut CheckpointBuilder,
+    rng: &mut StdRng,
+    object: Object,
+    recorded: Object,
+) -> ObjectProof {
+    let effects = TransactionEffects::V1(TransactionEffectsV1 {
+        transaction_digest: object.previous_transaction,
+        created: vec![(recorded.compute_object_reference(), recorded.owner)],
+        ..Default::default()
+    });
+    let (checkpoint, contents) = checkpoints
+        .random_transactions(1, rng)
+        .transaction(ExecutionDigests::new(
+            *effects.transaction_digest(),
+            effects.digest(),
+        ))
+        .build();
+    ObjectProof {
+        checkpoint,
+        contents,
+        effects,
+        object,
+    }
+}
+
+#[test]
+fn test_valid_object_proof() {
+    let mut rng = rng(0);
+    let mut checkpoints = CheckpointBuilder::new(&mut rng);
+    let committee = checkpoints.committee().clone();
+    let object = ObjectBuilder::new().build(&mut rng);
+    let proof = make_proof(&mut checkpoints, &mut rng, object.clone(), object.clone());
+    assert_eq!(
+        proof.verify(&committee).unwrap(),
+        object.compute_object_reference()
+    );
+
+    // The proof survives a round trip through its serialized form.
+    let proof: ObjectProof = bcs::from_bytes(&bcs::to_bytes(&proof).unwrap()).unwrap();
+    proof.verify(&committee).unwrap();
+
+    let other_committee = CheckpointBuilder::new(&mut rng).committee().clone();
+    assert!(proof.verify(&other_committee).is_err());
+}
+
+#[test]
+fn test_invalid_object_proof() {
+    let mut rng = rng(0);
+    let mut checkpoints = CheckpointBuilder::new(&mut rng);
+    let committee = checkpoints.committee().clone();
+    let object = ObjectBuilder::new().build(&mut rng);
+
+    // Object contents which are not the ones written by the transaction.
+    let mut forged = object.clone();
+    forged.owner = Owner::Immutable;
+    let proof = make_proof(&mut checkpoints, &mut rng, forged, object.clone());
+    assert!(matches!(
+        proof.verify(&committee),
+        Err(SuiError::InvalidObjectProof { .. })
+    ));
+
+    // Effects which are not included in the checkpoint.
+    let mut proof = make_proof(&mut checkpoints, &mut rng, object.clone(), object.clone());
+    let TransactionEffects::V1(effects) = &mut proof.effects;
+    effects.mutated.push(effects.created[0]);
+    assert!(matches!(
+        proof.verify(&committee),
+        Err(SuiError::InvalidObjectProof { .. })
+    ));
+
+    // Contents which are not the ones of the checkpoint.
+    let mut proof = make_proof(&mut checkpoints, &mut rng, object.clone(), object);
+    proof.contents = CheckpointContents::new_with_causally_ordered_transactions(
+        [random_execution_digests(&mut rng)].into_iter(),
+    );
+    assert!(proof.verify(&committee).is_err());
+}
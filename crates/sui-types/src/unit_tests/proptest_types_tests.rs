@@ -0,0 +1,56 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use proptest::test_runner::Config as ProptestConfig;
+
+use super::*;
+
+proptest! {
+    #[test]
+    fn test_owner_round_trip(owner in any::<Owner>()) {
+        check_round_trip(&owner)?;
+    }
+
+    #[test]
+    fn test_type_tag_round_trip(type_tag in any::<TypeTag>()) {
+        check_round_trip(&type_tag)?;
+    }
+
+    #[test]
+    fn test_call_arg_round_trip(call_arg in any::<CallArg>()) {
+        check_round_trip(&call_arg)?;
+    }
+
+    #[test]
+    fn test_transaction_kind_round_trip(kind in any::<TransactionKind>()) {
+        check_round_trip(&kind)?;
+    }
+
+    #[test]
+    fn test_transaction_data_round_trip(data in any::<TransactionData>()) {
+        check_round_trip(&data)?;
+    }
+}
+
+proptest! {
+    // Generating keys and signing is slow.
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn test_generic_signature_round_trip(signature in any::<GenericSignature>()) {
+        check_round_trip(&signature)?;
+        if let GenericSignature::MultiSig(multisig) = &signature {
+            prop_assert!(multisig.validate().is_ok());
+        }
+    }
+}
+
+#[test]
+fn test_arbitrary_is_deterministic() {
+    let generate = |bytes: &[u8]| {
+        let mut u = arbitrary::Unstructured::new(bytes);
+        <TransactionData as arbitrary::Arbitrary>::arbitrary(&mut u).unwrap()
+    };
+    assert_eq!(generate(&[1; 32]), generate(&[1; 32]));
+    assert_ne!(generate(&[1; 32]), generate(&[2; 32]));
+}
@@ -0,0 +1,168 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct RecordV1 {
+    value: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct RecordV2 {
+    value: u64,
+    label: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct RecordV3 {
+    values: Vec<u64>,
+    label: String,
+}
+
+impl VersionedFormat for RecordV1 {
+    const FORMAT_VERSION: u8 = 0;
+}
+
+impl VersionedFormat for RecordV3 {
+    const FORMAT_VERSION: u8 = 2;
+
+    fn decode_version<'de, A: SeqAccess<'de>>(
+        version: u8,
+        payload: &mut A,
+    ) -> Result<Option<Self>, A::Error> {
+        Ok(match version {
+            1 => Some(decode_payload_as::<RecordV2, _, _>(payload)?),
+            _ => None,
+        })
+    }
+}
+
+impl From<RecordV2> for RecordV3 {
+    fn from(record: RecordV2) -> Self {
+        Self {
+            values: vec![record.value],
+            label: record.label,
+        }
+    }
+}
+
+/// Stands for the stored values of older binaries, which are enums of the format versions.
+#[derive(Serialize, Deserialize)]
+enum RecordWrapper {
+    V1(RecordV1),
+    V2(RecordV2),
+}
+
+#[test]
+fn test_versioned_envelope_round_trip() {
+    let envelope = VersionedEnvelope::new(RecordV3 {
+        values: vec![1, 2],
+        label: "record".to_string(),
+    });
+    let bytes = bcs::to_bytes(&envelope).unwrap();
+    assert_eq!(bytes[0], RecordV3::FORMAT_VERSION);
+    assert_eq!(&bytes[1..], bcs::to_bytes(envelope.inner()).unwrap());
+    assert_eq!(
+        bcs::from_bytes::<VersionedEnvelope<RecordV3>>(&bytes).unwrap(),
+        envelope
+    );
+
+    let json = serde_json::to_string(&envelope).unwrap();
+    assert_eq!(json, r#"[2,{"values":[1,2],"label":"record"}]"#);
+    assert_eq!(
+        serde_json::from_str::<VersionedEnvelope<RecordV3>>(&json).unwrap(),
+        envelope
+    );
+}
+
+#[test]
+fn test_versioned_envelope_reads_version_enums() {
+    let v1 = RecordV1 { value: 7 };
+    let bytes = bcs::to_bytes(&RecordWrapper::V1(v1.clone())).unwrap();
+    assert_eq!(
+        bytes,
+        bcs::to_bytes(&VersionedEnvelope::new(v1.clone())).unwrap()
+    );
+    assert_eq!(
+        bcs::from_bytes::<VersionedEnvelope<RecordV1>>(&bytes)
+            .unwrap()
+            .into_inner(),
+        v1
+    );
+
+    let bytes = bcs::to_bytes(&RecordWrapper::V2(RecordV2 {
+        value: 7,
+        label: "record".to_string(),
+    }))
+    .unwrap();
+    assert_eq!(
+        bcs::from_bytes::<VersionedEnvelope<RecordV3>>(&bytes)
+            .unwrap()
+            .into_inner(),
+        RecordV3 {
+            values: vec![7],
+            label: "record".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_versioned_envelope_rejects_unknown_versions() {
+    // Version 0 of RecordV3 has no decoder anymore.
+    let bytes = bcs::to_bytes(&VersionedEnvelope::new(RecordV1 { value: 7 })).unwrap();
+    let err = bcs::from_bytes::<VersionedEnvelope<RecordV3>>(&bytes).unwrap_err();
+    assert!(err.to_string().contains("can not be decoded anymore"));
+
+    // A binary only knowing version 0 can not read the values of newer binaries.
+    let bytes = bcs::to_bytes(&VersionedEnvelope::new(RecordV3 {
+        values: vec![7],
+        label: "record".to_string(),
+    }))
+    .unwrap();
+    let err = bcs::from_bytes::<VersionedEnvelope<RecordV1>>(&bytes).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("is newer than the latest known version 0"));
+}
+
+#[test]
+fn test_simd_encodings_match_fastcrypto() {
+    use fastcrypto::encoding::{Base64, Encoding, Hex};
+
+    #[serde_as]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Simd(
+        #[serde_as(as = "SimdHex")] [u8; 32],
+        #[serde_as(as = "SimdBase64")] Vec<u8>,
+    );
+
+    for len in [0, 1, 2, 3, 31, 32, 33, 96, 1000] {
+        let bytes: Vec<u8> = (0..len).map(|i| (i * 7) as u8).collect();
+        assert_eq!(
+            SimdHex::encode(&bytes),
+            format!("0x{}", Hex::encode(&bytes))
+        );
+        assert_eq!(SimdBase64::encode(&bytes), Base64::encode(&bytes));
+        assert_eq!(SimdBase64::decode(&Base64::encode(&bytes)).unwrap(), bytes);
+        assert_eq!(SimdHex::decode(&Hex::encode(&bytes)).unwrap(), bytes);
+
+        let address = [len as u8; 32];
+        let json = serde_json::to_string(&Simd(address, bytes.clone())).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"["0x{}","{}"]"#,
+                Hex::encode(address),
+                Base64::encode(&bytes)
+            )
+        );
+        assert_eq!(
+            serde_json::from_str::<Simd>(&json).unwrap(),
+            Simd(address, bytes)
+        );
+    }
+
+    assert!(serde_json::from_str::<Simd>(r#"["0x00", ""]"#).is_err());
+    assert!(SimdHex::decode("0xzz").is_err());
+}
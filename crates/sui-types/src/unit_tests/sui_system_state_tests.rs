@@ -0,0 +1,74 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::base_types::{ObjectID, SuiAddress};
+use crate::sui_system_state::derive_reference_gas_price;
+use crate::sui_system_state::sui_system_state_summary::{
+    SuiSystemStateSummary, SuiValidatorSummary,
+};
+
+fn validator(gas_price: u64, next_epoch_gas_price: u64, voting_power: u64) -> SuiValidatorSummary {
+    SuiValidatorSummary {
+        sui_address: SuiAddress::random_for_testing_only(),
+        staking_pool_id: ObjectID::random(),
+        gas_price,
+        next_epoch_gas_price,
+        voting_power,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_derive_reference_gas_price() {
+    // A single validator holds all the voting power.
+    assert_eq!(derive_reference_gas_price([(42, 10_000)]), 42);
+    // Equal voting power, the price at the 2/3 percentile is the second highest one.
+    assert_eq!(
+        derive_reference_gas_price([(1000, 2500), (10, 2500), (100, 2500), (1, 2500)]),
+        100
+    );
+    // The validators quoting the highest prices hold 1/3 of the voting power.
+    assert_eq!(
+        derive_reference_gas_price([(1000, 3333), (10, 3333), (100, 3334)]),
+        1000
+    );
+    assert_eq!(derive_reference_gas_price([]), 0);
+}
+
+#[test]
+fn test_summary_accessors() {
+    let validators = vec![
+        validator(1000, 10, 2500),
+        validator(10, 1000, 2500),
+        validator(100, 1, 2500),
+        validator(1, 1, 2500),
+    ];
+    let summary = SuiSystemStateSummary {
+        active_validators: validators.clone(),
+        ..Default::default()
+    };
+
+    for v in &validators {
+        assert_eq!(
+            summary
+                .active_validator_by_address(&v.sui_address)
+                .map(|v| v.staking_pool_id),
+            Some(v.staking_pool_id)
+        );
+        assert_eq!(
+            summary
+                .active_validator_by_pool_id(&v.staking_pool_id)
+                .map(|v| v.sui_address),
+            Some(v.sui_address)
+        );
+    }
+    assert!(summary
+        .active_validator_by_address(&SuiAddress::random_for_testing_only())
+        .is_none());
+    assert!(summary
+        .active_validator_by_pool_id(&ObjectID::random())
+        .is_none());
+
+    assert_eq!(summary.derive_reference_gas_price(), 100);
+    assert_eq!(summary.estimate_next_epoch_reference_gas_price(), 10);
+}
@@ -0,0 +1,92 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use proptest::prelude::*;
+use sui_protocol_config::ProtocolConfig;
+
+use super::*;
+use crate::gas_coin::GasCoin;
+use crate::messages::TransactionDataAPI;
+
+#[test]
+fn test_fixtures_are_deterministic() {
+    let build = |seed| {
+        let mut rng = rng(seed);
+        let object = ObjectBuilder::new().build(&mut rng);
+        let transaction = TransactionDataBuilder::new().build(&mut rng);
+        let (checkpoint, _) = CheckpointBuilder::new(&mut rng)
+            .random_transactions(3, &mut rng)
+            .build();
+        (object, transaction.data, checkpoint.into_data())
+    };
+    assert_eq!(build(1), build(1));
+    assert_ne!(build(1), build(2));
+}
+
+#[test]
+fn test_object_builder() {
+    let mut rng = rng(0);
+    let owner = random_address(&mut rng);
+    let object = ObjectBuilder::new()
+        .address_owner(owner)
+        .balance(100)
+        .build(&mut rng);
+    assert_eq!(object.owner, Owner::AddressOwner(owner));
+    assert_eq!(object.version(), OBJECT_START_VERSION);
+    assert_eq!(GasCoin::try_from(&object).unwrap().value(), 100);
+
+    let initial_shared_version = SequenceNumber::from_u64(5);
+    let shared = ObjectBuilder::new()
+        .shared(initial_shared_version)
+        .build(&mut rng);
+    assert_eq!(shared.version(), initial_shared_version);
+}
+
+#[test]
+fn test_checkpoint_chain() {
+    let mut rng = rng(0);
+    let mut builder = CheckpointBuilder::new(&mut rng);
+    let genesis_committee = builder.committee().clone();
+    let (first, first_contents) = builder.random_transactions(2, &mut rng).build();
+    let (last_of_epoch, _) = builder.end_epoch(&mut rng).build();
+    let next_committee = builder.committee().clone();
+    let (next, _) = builder.random_transactions(1, &mut rng).build();
+
+    first
+        .verify_with_contents(&genesis_committee, Some(&first_contents))
+        .unwrap();
+    last_of_epoch.verify_signature(&genesis_committee).unwrap();
+    next.verify_signature(&next_committee).unwrap();
+    assert!(next.verify_signature(&genesis_committee).is_err());
+
+    assert_eq!(last_of_epoch.previous_digest, Some(*first.digest()));
+    assert_eq!(
+        last_of_epoch
+            .end_of_epoch_data
+            .as_ref()
+            .unwrap()
+            .next_epoch_committee,
+        next_committee.voting_rights
+    );
+    assert_eq!(next.epoch, 1);
+    assert_eq!(next.sequence_number, 2);
+    assert_eq!(next.network_total_transactions, 3);
+}
+
+proptest! {
+    #[test]
+    fn test_transactions_are_valid(seed in any::<u64>()) {
+        let mut rng = rng(seed);
+        let fixture = TransactionDataBuilder::new().build(&mut rng);
+        fixture
+            .data
+            .validity_check(&ProtocolConfig::get_for_max_version())
+            .unwrap();
+        prop_assert_eq!(fixture.data.sender(), fixture.sender());
+        prop_assert_eq!(fixture.data.gas(), &fixture.gas_refs()[..]);
+        for gas in &fixture.gas {
+            prop_assert_eq!(gas.owner, Owner::AddressOwner(fixture.sender()));
+        }
+        fixture.sign().verify_signature().unwrap();
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use rand::rngs::StdRng;
+
+use super::*;
+use crate::base_types::ExecutionDigests;
+use crate::messages::TransactionEffectsV1;
+use crate::test_fixtures::{
+    random_execution_digests, rng, CheckpointBuilder, TransactionDataBuilder,
+};
+
+fn transaction(rng: &mut StdRng) -> Transaction {
+    TransactionDataBuilder::new().build(rng).sign()
+}
+
+fn make_proof(checkpoints: &mut CheckpointBuilder, rng: &mut StdRng) -> TransactionProof {
+    let transaction = transaction(rng);
+    let events = TransactionEvents::default();
+    let effects = TransactionEffects::V1(TransactionEffectsV1 {
+        transaction_digest: *transaction.digest(),
+        events_digest: Some(events.digest()),
+        ..Default::default()
+    });
+    let (checkpoint, contents) = checkpoints
+        .random_transactions(1, rng)
+        .transaction(ExecutionDigests::new(
+            *effects.transaction_digest(),
+            effects.digest(),
+        ))
+        .build();
+    TransactionProof {
+        checkpoint,
+        contents,
+        transaction,
+        effects,
+        events: Some(events),
+    }
+}
+
+#[test]
+fn test_valid_transaction_proof() {
+    let mut rng = rng(0);
+    let mut checkpoints = CheckpointBuilder::new(&mut rng);
+    let committee = checkpoints.committee().clone();
+    let proof = make_proof(&mut checkpoints, &mut rng);
+    let digest = *proof.transaction.digest();
+    assert_eq!(proof.verify(&committee).unwrap(), digest);
+
+    // The proof survives a round trip through its serialized form.
+    let proof: TransactionProof = bcs::from_bytes(&bcs::to_bytes(&proof).unwrap()).unwrap();
+    proof.verify(&committee).unwrap();
+
+    let other_committee = CheckpointBuilder::new(&mut rng).committee().clone();
+    assert!(proof.verify(&other_committee).is_err());
+}
+
+#[test]
+fn test_invalid_transaction_proof() {
+    let mut rng = rng(0);
+    let mut checkpoints = CheckpointBuilder::new(&mut rng);
+    let committee = checkpoints.committee().clone();
+
+    // A transaction which is not the one the effects are for.
+    let mut proof = make_proof(&mut checkpoints, &mut rng);
+    proof.transaction = transaction(&mut rng);
+    assert!(matches!(
+        proof.verify(&committee),
+        Err(SuiError::InvalidTransactionProof { .. })
+    ));
+
+    // Events which are not the ones of the effects.
+    let mut proof = make_proof(&mut checkpoints, &mut rng);
+    proof.events = None;
+    assert!(matches!(
+        proof.verify(&committee),
+        Err(SuiError::InvalidTransactionProof { .. })
+    ));
+
+    // Effects which are not included in the checkpoint.
+    let mut proof = make_proof(&mut checkpoints, &mut rng);
+    let TransactionEffects::V1(effects) = &mut proof.effects;
+    effects.events_digest = None;
+    proof.events = None;
+    assert!(matches!(
+        proof.verify(&committee),
+        Err(SuiError::InvalidTransactionProof { .. })
+    ));
+
+    // Contents which are not the ones of the checkpoint.
+    let mut proof = make_proof(&mut checkpoints, &mut rng);
+    proof.contents = CheckpointContents::new_with_causally_ordered_transactions(
+        [random_execution_digests(&mut rng)].into_iter(),
+    );
+    assert!(proof.verify(&committee).is_err());
+}
@@ -0,0 +1,96 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use fastcrypto::traits::KeyPair;
+
+use super::*;
+use crate::base_types::AuthorityName;
+use crate::committee::Committee;
+use crate::messages::SignedTransaction;
+use crate::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use crate::test_fixtures::{random_execution_digests, rng, TransactionDataBuilder};
+
+fn certificate() -> CertifiedTransaction {
+    let mut rng = rng(0);
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.pure_bytes(vec![7; 256], true);
+    builder.command(Command::Publish(
+        vec![vec![1; 1024], vec![2; 2048]],
+        vec![ObjectID::ZERO],
+    ));
+    let transaction = TransactionDataBuilder::new()
+        .programmable(builder.finish())
+        .build(&mut rng)
+        .sign();
+    let (committee, keys) = Committee::new_simple_test_committee_of_size(4);
+    let signatures = keys
+        .iter()
+        .map(|key| {
+            SignedTransaction::sign(
+                0,
+                transaction.data(),
+                key,
+                AuthorityName::from(key.public()),
+            )
+        })
+        .collect();
+    CertifiedTransaction::new(transaction.into_data(), signatures, &committee).unwrap()
+}
+
+#[test]
+fn test_certificate_view() {
+    let certificate = certificate();
+    let bytes = bcs::to_bytes(&certificate).unwrap();
+    let view: CertifiedTransactionRef = bcs::from_bytes(&bytes).unwrap();
+
+    // The payloads are read in place.
+    let TransactionKindRef::ProgrammableTransaction(pt) = view.data().intent_message.value.kind() else {
+        panic!("Expected a programmable transaction");
+    };
+    let CallArgRef::Pure(pure) = &pt.inputs[0] else {
+        panic!("Expected a pure argument");
+    };
+    assert_eq!(*pure, &[7; 256][..]);
+    assert!(bytes.as_ptr_range().contains(&pure.as_ptr()));
+    let CommandRef::Publish(modules, _) = &pt.commands[0] else {
+        panic!("Expected a publish command");
+    };
+    assert!(bytes.as_ptr_range().contains(&modules[1].as_ptr()));
+    assert_eq!(
+        view.data().intent_message.value.sender(),
+        certificate.data().transaction_data().sender()
+    );
+
+    assert_eq!(bcs::to_bytes(&view).unwrap(), bytes);
+    assert_eq!(view.into_owned().unwrap(), certificate);
+}
+
+#[test]
+fn test_certificate_view_rejects_several_transactions() {
+    let certificate = certificate();
+    let tx = certificate.data().inner().clone();
+    let bytes = bcs::to_bytes(&vec![tx.clone(), tx]).unwrap();
+    assert!(bcs::from_bytes::<SenderSignedDataRef>(&bytes).is_err());
+}
+
+#[test]
+fn test_checkpoint_contents_view() {
+    let mut rng = rng(0);
+    let signature = certificate().data().tx_signatures()[0].clone();
+    let contents = CheckpointContents::new_with_causally_ordered_transactions_and_signatures(
+        [
+            random_execution_digests(&mut rng),
+            random_execution_digests(&mut rng),
+        ],
+        vec![vec![signature], vec![]],
+    );
+    let bytes = bcs::to_bytes(&contents).unwrap();
+    let view: CheckpointContentsRef = bcs::from_bytes(&bytes).unwrap();
+
+    assert_eq!(view.transactions().len(), 2);
+    assert!(bytes
+        .as_ptr_range()
+        .contains(&view.user_signatures()[0][0].as_ptr()));
+    assert_eq!(bcs::to_bytes(&view).unwrap(), bytes);
+    assert_eq!(view.into_owned().unwrap(), contents);
+}
@@ -0,0 +1,360 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Borrowed views of the largest messages exchanged by validators, certificates and checkpoint
+//! contents, which are deserialized from BCS without copying their byte payloads (pure arguments,
+//! package modules and user signatures) out of the buffer they are read from. They serialize to
+//! the same BCS bytes as the messages they are views of, and can be turned into them with
+//! `into_owned` once the payloads need to outlive the buffer.
+//!
+//! The views are only meant for BCS: their human-readable serialization differs from the one of
+//! the messages.
+//!
+//! They are meant for readers holding the serialized messages, e.g. in files or archives, which
+//! inspect them without materializing their payloads. The network paths of validators, the gRPC
+//! certificate handlers and the checkpoint contents of state sync, still decode owned messages:
+//! their codecs deserialize from buffers that do not outlive the decoding, and the messages are
+//! stored and sent to consensus as owned values afterwards.
+
+use fastcrypto::traits::ToFromBytes;
+use move_core_types::language_storage::TypeTag;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize};
+use shared_crypto::intent::IntentMessage;
+
+use crate::base_types::{ExecutionDigests, ObjectID, SequenceNumber, SuiAddress};
+use crate::committee::{EpochId, ProtocolVersion};
+use crate::crypto::AuthorityStrongQuorumSignInfo;
+use crate::error::{SuiError, SuiResult};
+use crate::message_envelope::Envelope;
+use crate::messages::{
+    Argument, CallArg, CertifiedTransaction, ChangeEpoch, Command, ConsensusCommitPrologue,
+    GasData, GenesisTransaction, ObjectArg, ProgrammableMoveCall, ProgrammableTransaction,
+    SenderSignedData, TransactionData, TransactionDataV1, TransactionExpiration, TransactionKind,
+};
+use crate::messages_checkpoint::CheckpointContents;
+use crate::signature::GenericSignature;
+
+#[cfg(test)]
+#[path = "unit_tests/zero_copy_tests.rs"]
+mod zero_copy_tests;
+
+/// A view of a [CertifiedTransaction].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CertifiedTransactionRef<'a> {
+    #[serde(borrow)]
+    data: SenderSignedDataRef<'a>,
+    auth_signature: AuthorityStrongQuorumSignInfo,
+}
+
+impl<'a> CertifiedTransactionRef<'a> {
+    /// The signed transaction, of which there is exactly one.
+    pub fn data(&self) -> &SenderSignedTransactionRef<'a> {
+        &self.data.0[0]
+    }
+
+    pub fn auth_sig(&self) -> &AuthorityStrongQuorumSignInfo {
+        &self.auth_signature
+    }
+
+    pub fn into_owned(self) -> SuiResult<CertifiedTransaction> {
+        Ok(Envelope::new_from_data_and_sig(
+            self.data.into_owned()?,
+            self.auth_signature,
+        ))
+    }
+}
+
+/// A view of a [SenderSignedData], rejecting any number of transactions other than one on
+/// deserialization as well.
+#[derive(Debug, Serialize)]
+pub struct SenderSignedDataRef<'a>(Vec<SenderSignedTransactionRef<'a>>);
+
+impl<'de: 'a, 'a> Deserialize<'de> for SenderSignedDataRef<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let txs = Vec::<SenderSignedTransactionRef<'a>>::deserialize(deserializer)?;
+        if txs.len() != 1 {
+            return Err(D::Error::custom(
+                "SenderSignedData must contain exactly one transaction",
+            ));
+        }
+        Ok(Self(txs))
+    }
+}
+
+impl<'a> SenderSignedDataRef<'a> {
+    pub fn transaction(&self) -> &SenderSignedTransactionRef<'a> {
+        &self.0[0]
+    }
+
+    pub fn into_owned(self) -> SuiResult<SenderSignedData> {
+        let Some(tx) = self.0.into_iter().next() else {
+            unreachable!("SenderSignedDataRef holds exactly one transaction");
+        };
+        let IntentMessage { intent, value } = tx.intent_message;
+        Ok(SenderSignedData::new(
+            value.into_owned(),
+            intent,
+            signatures_into_owned(tx.tx_signatures)?,
+        ))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SenderSignedTransactionRef<'a> {
+    #[serde(borrow)]
+    pub intent_message: IntentMessage<TransactionDataRef<'a>>,
+    /// The serialized [GenericSignature]s of the transaction.
+    #[serde(borrow)]
+    pub tx_signatures: Vec<&'a [u8]>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum TransactionDataRef<'a> {
+    V1(#[serde(borrow)] TransactionDataV1Ref<'a>),
+}
+
+impl<'a> TransactionDataRef<'a> {
+    pub fn kind(&self) -> &TransactionKindRef<'a> {
+        match self {
+            Self::V1(v1) => &v1.kind,
+        }
+    }
+
+    pub fn sender(&self) -> SuiAddress {
+        match self {
+            Self::V1(v1) => v1.sender,
+        }
+    }
+
+    pub fn gas_data(&self) -> &GasData {
+        match self {
+            Self::V1(v1) => &v1.gas_data,
+        }
+    }
+
+    pub fn into_owned(self) -> TransactionData {
+        match self {
+            Self::V1(v1) => TransactionData::V1(TransactionDataV1 {
+                kind: v1.kind.into_owned(),
+                sender: v1.sender,
+                gas_data: v1.gas_data,
+                expiration: v1.expiration,
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransactionDataV1Ref<'a> {
+    #[serde(borrow)]
+    pub kind: TransactionKindRef<'a>,
+    pub sender: SuiAddress,
+    pub gas_data: GasData,
+    pub expiration: TransactionExpiration,
+}
+
+/// A view of a [TransactionKind]. The variants must be kept in the same order.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum TransactionKindRef<'a> {
+    ProgrammableTransaction(#[serde(borrow)] ProgrammableTransactionRef<'a>),
+    ChangeEpoch(#[serde(borrow)] ChangeEpochRef<'a>),
+    Genesis(GenesisTransaction),
+    ConsensusCommitPrologue(ConsensusCommitPrologue),
+}
+
+impl TransactionKindRef<'_> {
+    pub fn into_owned(self) -> TransactionKind {
+        match self {
+            Self::ProgrammableTransaction(pt) => {
+                TransactionKind::ProgrammableTransaction(pt.into_owned())
+            }
+            Self::ChangeEpoch(change_epoch) => {
+                TransactionKind::ChangeEpoch(change_epoch.into_owned())
+            }
+            Self::Genesis(genesis) => TransactionKind::Genesis(genesis),
+            Self::ConsensusCommitPrologue(prologue) => {
+                TransactionKind::ConsensusCommitPrologue(prologue)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChangeEpochRef<'a> {
+    pub epoch: EpochId,
+    pub protocol_version: ProtocolVersion,
+    pub storage_charge: u64,
+    pub computation_charge: u64,
+    pub storage_rebate: u64,
+    pub non_refundable_storage_fee: u64,
+    pub epoch_start_timestamp_ms: u64,
+    #[serde(borrow)]
+    pub system_packages: Vec<(SequenceNumber, Vec<&'a [u8]>, Vec<ObjectID>)>,
+}
+
+impl ChangeEpochRef<'_> {
+    pub fn into_owned(self) -> ChangeEpoch {
+        ChangeEpoch {
+            epoch: self.epoch,
+            protocol_version: self.protocol_version,
+            storage_charge: self.storage_charge,
+            computation_charge: self.computation_charge,
+            storage_rebate: self.storage_rebate,
+            non_refundable_storage_fee: self.non_refundable_storage_fee,
+            epoch_start_timestamp_ms: self.epoch_start_timestamp_ms,
+            system_packages: self
+                .system_packages
+                .into_iter()
+                .map(|(version, modules, dependencies)| {
+                    (version, modules_into_owned(modules), dependencies)
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProgrammableTransactionRef<'a> {
+    #[serde(borrow)]
+    pub inputs: Vec<CallArgRef<'a>>,
+    #[serde(borrow)]
+    pub commands: Vec<CommandRef<'a>>,
+}
+
+impl ProgrammableTransactionRef<'_> {
+    pub fn into_owned(self) -> ProgrammableTransaction {
+        ProgrammableTransaction {
+            inputs: self
+                .inputs
+                .into_iter()
+                .map(CallArgRef::into_owned)
+                .collect(),
+            commands: self
+                .commands
+                .into_iter()
+                .map(CommandRef::into_owned)
+                .collect(),
+        }
+    }
+}
+
+/// A view of a [CallArg]. The variants must be kept in the same order.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum CallArgRef<'a> {
+    Pure(&'a [u8]),
+    Object(ObjectArg),
+}
+
+impl CallArgRef<'_> {
+    pub fn into_owned(self) -> CallArg {
+        match self {
+            Self::Pure(bytes) => CallArg::Pure(bytes.to_vec()),
+            Self::Object(object) => CallArg::Object(object),
+        }
+    }
+}
+
+/// A view of a [Command]. The variants must be kept in the same order.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum CommandRef<'a> {
+    MoveCall(Box<ProgrammableMoveCall>),
+    TransferObjects(Vec<Argument>, Argument),
+    SplitCoins(Argument, Vec<Argument>),
+    MergeCoins(Argument, Vec<Argument>),
+    Publish(#[serde(borrow)] Vec<&'a [u8]>, Vec<ObjectID>),
+    MakeMoveVec(Option<TypeTag>, Vec<Argument>),
+    Upgrade(
+        #[serde(borrow)] Vec<&'a [u8]>,
+        Vec<ObjectID>,
+        ObjectID,
+        Argument,
+    ),
+}
+
+impl CommandRef<'_> {
+    pub fn into_owned(self) -> Command {
+        match self {
+            Self::MoveCall(call) => Command::MoveCall(call),
+            Self::TransferObjects(objects, recipient) => {
+                Command::TransferObjects(objects, recipient)
+            }
+            Self::SplitCoins(coin, amounts) => Command::SplitCoins(coin, amounts),
+            Self::MergeCoins(coin, coins) => Command::MergeCoins(coin, coins),
+            Self::Publish(modules, dependencies) => {
+                Command::Publish(modules_into_owned(modules), dependencies)
+            }
+            Self::MakeMoveVec(type_, elements) => Command::MakeMoveVec(type_, elements),
+            Self::Upgrade(modules, dependencies, package, ticket) => {
+                Command::Upgrade(modules_into_owned(modules), dependencies, package, ticket)
+            }
+        }
+    }
+}
+
+/// A view of a [CheckpointContents].
+#[derive(Debug, Serialize, Deserialize)]
+pub enum CheckpointContentsRef<'a> {
+    V1(#[serde(borrow)] CheckpointContentsV1Ref<'a>),
+}
+
+impl<'a> CheckpointContentsRef<'a> {
+    pub fn transactions(&self) -> &[ExecutionDigests] {
+        match self {
+            Self::V1(v1) => &v1.transactions,
+        }
+    }
+
+    /// The serialized [GenericSignature]s of the transactions, in the same order.
+    pub fn user_signatures(&self) -> &[Vec<&'a [u8]>] {
+        match self {
+            Self::V1(v1) => &v1.user_signatures,
+        }
+    }
+
+    pub fn into_owned(self) -> SuiResult<CheckpointContents> {
+        match self {
+            Self::V1(v1) => {
+                if v1.transactions.len() != v1.user_signatures.len() {
+                    return Err(SuiError::GenericAuthorityError {
+                        error: "Checkpoint contents must have as many signatures as transactions"
+                            .to_string(),
+                    });
+                }
+                let user_signatures = v1
+                    .user_signatures
+                    .into_iter()
+                    .map(signatures_into_owned)
+                    .collect::<SuiResult<_>>()?;
+                Ok(
+                    CheckpointContents::new_with_causally_ordered_transactions_and_signatures(
+                        v1.transactions,
+                        user_signatures,
+                    ),
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CheckpointContentsV1Ref<'a> {
+    pub transactions: Vec<ExecutionDigests>,
+    #[serde(borrow)]
+    pub user_signatures: Vec<Vec<&'a [u8]>>,
+}
+
+fn modules_into_owned(modules: Vec<&[u8]>) -> Vec<Vec<u8>> {
+    modules.into_iter().map(<[u8]>::to_vec).collect()
+}
+
+fn signatures_into_owned(signatures: Vec<&[u8]>) -> SuiResult<Vec<GenericSignature>> {
+    signatures
+        .into_iter()
+        .map(|bytes| {
+            GenericSignature::from_bytes(bytes).map_err(|e| SuiError::InvalidSignature {
+                error: e.to_string(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,207 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Balances of the coins owned by an address, shown by `sui client balance`. Balances are
+//! aggregated by coin type, formatted with the decimals of the `CoinMetadata` of the coin type,
+//! and optionally annotated with their value in USD by a [PriceProvider].
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use move_core_types::language_storage::TypeTag;
+use serde::Serialize;
+use sui_json_rpc_types::{Balance, SuiCoinMetadata};
+use sui_sdk::SuiClient;
+use sui_types::base_types::SuiAddress;
+use sui_types::parse_sui_type_tag;
+use tracing::warn;
+
+#[cfg(test)]
+#[path = "unit_tests/balance_tests.rs"]
+mod balance_tests;
+
+/// Prices of coins, used to annotate balances with their value in USD.
+#[async_trait]
+pub trait PriceProvider: Send + Sync {
+    /// The price in USD of one coin of `coin_type`, i.e. of `10^decimals` units of the coin, or
+    /// None if the price of the coin type is not known.
+    async fn usd_price(&self, coin_type: &TypeTag) -> Result<Option<f64>, anyhow::Error>;
+}
+
+/// Prices listed in a file, mapping coin types to their price in USD, in YAML or JSON:
+///
+/// ```yaml
+/// 0x2::sui::SUI: 1.25
+/// ```
+pub struct FilePriceProvider {
+    prices: BTreeMap<TypeTag, f64>,
+}
+
+impl FilePriceProvider {
+    pub fn new(prices: BTreeMap<TypeTag, f64>) -> Self {
+        Self { prices }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, anyhow::Error> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Cannot read the prices file {}: {e}", path.display()))?;
+        let prices: BTreeMap<String, f64> = serde_yaml::from_str(&content)
+            .map_err(|e| anyhow!("Invalid prices file {}: {e}", path.display()))?;
+        Ok(Self::new(
+            prices
+                .into_iter()
+                .map(|(coin_type, price)| Ok((parse_sui_type_tag(&coin_type)?, price)))
+                .collect::<Result<_, anyhow::Error>>()?,
+        ))
+    }
+}
+
+#[async_trait]
+impl PriceProvider for FilePriceProvider {
+    async fn usd_price(&self, coin_type: &TypeTag) -> Result<Option<f64>, anyhow::Error> {
+        Ok(self.prices.get(coin_type).copied())
+    }
+}
+
+/// The balance of the coins of one coin type owned by an address.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinBalance {
+    #[serde(flatten)]
+    pub balance: Balance,
+    /// Metadata of the coin type, if it was created with `coin::create_currency`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<SuiCoinMetadata>,
+    /// Price in USD of one coin, from the price provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usd_price: Option<f64>,
+}
+
+impl CoinBalance {
+    /// The total balance in coins, with the decimals of the coin type, e.g. `1.5 SUI` for
+    /// 1500000000 MIST. Balances of coin types without metadata are in units of the coin.
+    pub fn formatted_balance(&self) -> String {
+        match &self.metadata {
+            Some(metadata) => format!(
+                "{} {}",
+                format_amount(self.balance.total_balance, metadata.decimals),
+                metadata.symbol
+            ),
+            None => self.balance.total_balance.to_string(),
+        }
+    }
+
+    /// The value in USD of the total balance, if the price of the coin type is known.
+    pub fn usd_value(&self) -> Option<f64> {
+        let decimals = self.metadata.as_ref()?.decimals;
+        let price = self.usd_price?;
+        Some(self.balance.total_balance as f64 / 10f64.powi(decimals.into()) * price)
+    }
+}
+
+/// The balances of the coins owned by `address`, aggregated by coin type and sorted by coin type,
+/// or only the balance of `coin_type` if given. Balances are annotated with the prices of
+/// `prices`.
+pub async fn get_coin_balances(
+    client: &SuiClient,
+    address: SuiAddress,
+    coin_type: Option<String>,
+    prices: Option<&dyn PriceProvider>,
+) -> Result<Vec<CoinBalance>, anyhow::Error> {
+    let mut balances = match coin_type {
+        Some(coin_type) => vec![
+            client
+                .coin_read_api()
+                .get_balance(address, Some(coin_type))
+                .await?,
+        ],
+        None => client.coin_read_api().get_all_balances(address).await?,
+    };
+    balances.sort_by(|a, b| a.coin_type.cmp(&b.coin_type));
+
+    let mut coin_balances = Vec::with_capacity(balances.len());
+    for balance in balances {
+        // Coin types created without `coin::create_currency` have no metadata, their balance is
+        // shown in units of the coin.
+        let metadata = match client
+            .coin_read_api()
+            .get_coin_metadata(balance.coin_type.clone())
+            .await
+        {
+            Ok(metadata) => Some(metadata),
+            Err(e) => {
+                warn!("Cannot read the metadata of {}: {e}", balance.coin_type);
+                None
+            }
+        };
+        let usd_price = match prices {
+            Some(prices) => {
+                prices
+                    .usd_price(&parse_sui_type_tag(&balance.coin_type)?)
+                    .await?
+            }
+            None => None,
+        };
+        coin_balances.push(CoinBalance {
+            balance,
+            metadata,
+            usd_price,
+        });
+    }
+    Ok(coin_balances)
+}
+
+/// Formats `amount` units of a coin with `decimals` decimals, without trailing zeros, e.g.
+/// `1.5` for 1500000000 units with 9 decimals.
+pub fn format_amount(amount: u128, decimals: u8) -> String {
+    let decimals = decimals as usize;
+    let digits = format!("{amount:0>width$}", width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        integer.to_string()
+    } else {
+        format!("{integer}.{fraction}")
+    }
+}
+
+/// Table of `balances`, with a column for their value in USD if any price is known.
+pub fn write_coin_balances(balances: &[CoinBalance]) -> Result<String, anyhow::Error> {
+    let with_prices = balances.iter().any(|balance| balance.usd_value().is_some());
+    let mut writer = String::new();
+    write!(
+        writer,
+        " {0: ^80} | {1: ^7} | {2: ^24}",
+        "Coin Type", "Coins", "Balance"
+    )?;
+    if with_prices {
+        write!(writer, " | {0: ^14}", "Value (USD)")?;
+    }
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "{}",
+        "-".repeat(if with_prices { 131 } else { 114 })
+    )?;
+    for balance in balances {
+        write!(
+            writer,
+            " {0: ^80} | {1: ^7} | {2: ^24}",
+            balance.balance.coin_type,
+            balance.balance.coin_object_count,
+            balance.formatted_balance()
+        )?;
+        if with_prices {
+            let value = balance
+                .usd_value()
+                .map(|value| format!("{value:.2}"))
+                .unwrap_or_default();
+            write!(writer, " | {0: ^14}", value)?;
+        }
+        writeln!(writer)?;
+    }
+    Ok(writer)
+}
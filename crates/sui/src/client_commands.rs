@@ -4,21 +4,29 @@
 use core::fmt;
 use std::sync::Arc;
 use std::{
-    collections::BTreeSet,
+    collections::{BTreeMap, BTreeSet},
     fmt::{Debug, Display, Formatter, Write},
     path::{Path, PathBuf},
     time::Instant,
 };
 
-use crate::config::{Config, PersistedConfig, SuiClientConfig, SuiEnv};
-use anyhow::{anyhow, ensure};
+use crate::balance::{
+    get_coin_balances, write_coin_balances, CoinBalance, FilePriceProvider, PriceProvider,
+};
+use crate::config::{AddressOrAlias, Config, PersistedConfig, SuiClientConfig, SuiEnv};
+use crate::output::{OutputFormat, PrintableResult};
+use crate::ptb::{build_programmable_transaction, parse_var, PtbPlan};
+use crate::tx_receipt::{read_committee, TransactionReceipt, VerifiedTransactionReceipt};
+use crate::workspace_publish::{publish_workspace, WorkspacePublishReport};
+use anyhow::{anyhow, bail, ensure};
 use bip32::DerivationPath;
 use clap::*;
 use colored::Colorize;
 use fastcrypto::{
-    encoding::{Base64, Encoding},
+    encoding::{Base64, Encoding, Hex},
     traits::ToFromBytes,
 };
+use inquire::Confirm;
 use move_core_types::language_storage::TypeTag;
 use move_package::BuildConfig as MoveBuildConfig;
 use prettytable::Table;
@@ -38,26 +46,28 @@ use sui_framework_build::compiled_package::{
 };
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    DynamicFieldPage, SuiData, SuiObjectData, SuiObjectDataFilter, SuiObjectResponse,
-    SuiObjectResponseQuery, SuiRawData, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
-    SuiTransactionBlockResponseOptions,
+    DryRunTransactionBlockResponse, DynamicFieldPage, ObjectChange, SuiCallArg, SuiData,
+    SuiObjectArg, SuiObjectData, SuiObjectDataFilter, SuiObjectResponse, SuiObjectResponseQuery,
+    SuiRawData, SuiTransactionBlockDataAPI, SuiTransactionBlockEffectsAPI, SuiTransactionBlockKind,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
 use sui_json_rpc_types::{SuiExecutionStatus, SuiObjectDataOptions};
 use sui_keys::keystore::AccountKeystore;
 use sui_sdk::SuiClient;
-use sui_types::crypto::SignatureScheme;
+use sui_transaction_builder::UpgradeCapRestriction;
+use sui_types::crypto::{Signature, SignatureScheme};
 use sui_types::dynamic_field::DynamicFieldType;
-use sui_types::move_package::UpgradeCap;
+use sui_types::move_package::{UpgradeCap, UpgradePolicy};
 use sui_types::signature::GenericSignature;
 use sui_types::{
     base_types::{ObjectID, ObjectRef, SuiAddress},
     gas_coin::GasCoin,
-    messages::{Transaction, VerifiedTransaction},
+    messages::{Transaction, TransactionData, VerifiedTransaction},
     object::Owner,
     parse_sui_type_tag,
 };
 use tokio::sync::RwLock;
-use tracing::{info, warn};
+use tracing::warn;
 
 #[derive(Parser)]
 #[clap(rename_all = "kebab-case")]
@@ -65,10 +75,10 @@ pub enum SuiClientCommands {
     /// Switch active address and network(e.g., devnet, local rpc server)
     #[clap(name = "switch")]
     Switch {
-        /// An Sui address to be used as the active address for subsequent
+        /// An Sui address, or its alias, to be used as the active address for subsequent
         /// commands.
         #[clap(long)]
-        address: Option<SuiAddress>,
+        address: Option<AddressOrAlias>,
         /// The RPC server URL (e.g., local rpc server, devnet rpc server, etc) to be
         /// used for subsequent commands.
         #[clap(long)]
@@ -87,6 +97,23 @@ pub enum SuiClientCommands {
     /// List all Sui environments
     Envs,
 
+    /// Manage the aliases of addresses: names which the commands of the client accept instead of
+    /// addresses, for keys of the keystore as well as for addresses of other accounts.
+    #[clap(name = "alias")]
+    Alias {
+        #[clap(subcommand)]
+        cmd: AliasCommand,
+    },
+
+    /// Manage watch-only addresses: addresses without keys in the keystore, such as treasury
+    /// accounts kept offline. Their objects, gas and balances can be queried, and their
+    /// transactions dry run, but transactions must be signed offline.
+    #[clap(name = "watch-only")]
+    WatchOnly {
+        #[clap(subcommand)]
+        cmd: WatchOnlyCommand,
+    },
+
     /// Default address used for commands when none specified
     #[clap(name = "active-address")]
     ActiveAddress,
@@ -115,6 +142,35 @@ pub enum SuiClientCommands {
         digest: TransactionDigest,
     },
 
+    /// Export the receipt of a checkpointed transaction block: the signed transaction, its effects
+    /// and events, and the certified checkpoint including it, verifiable offline with
+    /// `verify-receipt`
+    #[clap(name = "tx-receipt")]
+    TransactionReceipt {
+        /// Digest of the transaction block
+        #[clap(name = "digest")]
+        digest: TransactionDigest,
+
+        /// Path of the receipt file to write
+        #[clap(long, parse(from_os_str))]
+        output: PathBuf,
+    },
+
+    /// Verify a receipt exported by `tx-receipt`, without connecting to a node
+    #[clap(name = "verify-receipt")]
+    VerifyReceipt {
+        /// Path of the receipt file
+        #[clap(name = "receipt", parse(from_os_str))]
+        receipt: PathBuf,
+
+        /// Path of a JSON file holding the committee of the epoch of the transaction, as returned
+        /// by `suix_getCommitteeInfo`, from a trusted source. Without it, the receipt is verified
+        /// against the committee it holds, which is only as trustworthy as the node it was
+        /// exported from
+        #[clap(long, parse(from_os_str))]
+        committee: Option<PathBuf>,
+    },
+
     /// Publish Move modules
     #[clap(name = "publish")]
     Publish {
@@ -152,6 +208,42 @@ pub enum SuiClientCommands {
         /// Do not Sign transaction, output Base64-encoded Serialized Output
         #[clap(long)]
         serialize_output: bool,
+
+        /// Only check that the package can be published, by dry running the transaction: report
+        /// the ID the package would be published at and the cost of publishing it, without
+        /// signing or submitting the transaction
+        #[clap(long)]
+        dry_run: bool,
+    },
+
+    /// Publish a workspace of interdependent local Move packages in dependency order. The ID of
+    /// every published package is written to its manifest, as its `published-at` field and as
+    /// its named addresses left at `0x0`, so that the packages depending on it link against it.
+    /// Packages which already have a `published-at` field are skipped, so running the command
+    /// again after a failure resumes from the failed package.
+    #[clap(name = "publish-workspace")]
+    PublishWorkspace {
+        /// Paths to the directories of the Move packages of the workspace
+        #[clap(name = "package_paths", parse(from_os_str), required = true)]
+        package_paths: Vec<PathBuf>,
+
+        /// Package build options
+        #[clap(flatten)]
+        build_config: MoveBuildConfig,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for publishing every package
+        #[clap(long)]
+        gas_budget: u64,
+
+        /// Publish the packages without checking whether compiling dependencies from source
+        /// results in bytecode matching the dependencies found on-chain.
+        #[clap(long)]
+        skip_dependency_verification: bool,
     },
 
     /// Upgrade Move modules
@@ -191,6 +283,45 @@ pub enum SuiClientCommands {
         /// Also publish transitive dependencies that have not already been published.
         #[clap(long)]
         with_unpublished_dependencies: bool,
+
+        /// Policy of the upgrade: `compatible`, `additive` or `dep-only`. It cannot be less
+        /// restrictive than the policy of the upgrade capability, which is the default
+        #[clap(long, parse(try_from_str = parse_upgrade_policy))]
+        policy: Option<u8>,
+
+        /// Digest of the package the upgrade was approved for, in hex. The upgrade is not
+        /// submitted if the digest of the compiled package is different
+        #[clap(long)]
+        expected_digest: Option<String>,
+
+        /// Only check that the package can be upgraded, by dry running the transaction: report
+        /// the ID the upgraded package would be published at and the cost of publishing it,
+        /// without signing or submitting the transaction
+        #[clap(long)]
+        dry_run: bool,
+    },
+
+    /// Restrict the upgrades an upgrade capability allows: `additive` only allows upgrades adding
+    /// code, `dep-only` upgrades changing dependencies, and `immutable` makes the package
+    /// immutable by destroying the capability. Restrictions cannot be undone.
+    #[clap(name = "restrict-upgrade-cap")]
+    RestrictUpgradeCap {
+        /// ID of the upgrade capability to restrict.
+        #[clap(long)]
+        upgrade_capability: ObjectID,
+
+        /// The restriction: `additive`, `dep-only` or `immutable`
+        #[clap(long, parse(try_from_str = parse_upgrade_cap_restriction))]
+        restriction: UpgradeCapRestriction,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: u64,
     },
 
     /// Verify local Move packages against on-chain packages, and optionally their dependencies.
@@ -255,14 +386,43 @@ pub enum SuiClientCommands {
         /// Gas budget for this call
         #[clap(long)]
         gas_budget: u64,
+        /// Submit the transaction without previewing its effects and asking for confirmation
+        #[clap(long)]
+        yes: bool,
+    },
+
+    /// Build a programmable transaction from a plan file, and execute it. The plan is a YAML or
+    /// JSON file listing the commands of the transaction, whose results can be bound to names and
+    /// used by the following commands.
+    #[clap(name = "ptb")]
+    Ptb {
+        /// Path of the plan file
+        #[clap(long)]
+        plan: PathBuf,
+        /// Set a variable of the plan, as `name=value`, overriding its value in the plan
+        #[clap(long = "var", multiple_occurrences = true, parse(try_from_str = parse_var))]
+        vars: Vec<(String, Value)>,
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+        /// Gas budget for this transaction
+        #[clap(long)]
+        gas_budget: u64,
+        /// Dry run the transaction and print its effects, without executing it
+        #[clap(long)]
+        preview: bool,
+        /// Submit the transaction without previewing its effects and asking for confirmation
+        #[clap(long)]
+        yes: bool,
     },
 
     /// Transfer object
     #[clap(name = "transfer")]
     Transfer {
-        /// Recipient address
+        /// Recipient address, or its alias
         #[clap(long)]
-        to: SuiAddress,
+        to: AddressOrAlias,
 
         /// Object to transfer, in 20 bytes Hex string
         #[clap(long)]
@@ -276,15 +436,18 @@ pub enum SuiClientCommands {
         /// Gas budget for this transfer
         #[clap(long)]
         gas_budget: u64,
+        /// Submit the transaction without previewing its effects and asking for confirmation
+        #[clap(long)]
+        yes: bool,
     },
     /// Transfer SUI, and pay gas with the same SUI coin object.
     /// If amount is specified, only the amount is transferred; otherwise the entire object
     /// is transferred.
     #[clap(name = "transfer-sui")]
     TransferSui {
-        /// Recipient address
+        /// Recipient address, or its alias
         #[clap(long)]
-        to: SuiAddress,
+        to: AddressOrAlias,
 
         /// Sui coin object to transfer, ID in 20 bytes Hex string. This is also the gas object.
         #[clap(long)]
@@ -306,9 +469,9 @@ pub enum SuiClientCommands {
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
         input_coins: Vec<ObjectID>,
 
-        /// The recipient addresses, must be of same length as amounts
+        /// The recipient addresses or their aliases, must be of same length as amounts
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
-        recipients: Vec<SuiAddress>,
+        recipients: Vec<AddressOrAlias>,
 
         /// The amounts to be paid, following the order of recipients.
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
@@ -333,9 +496,9 @@ pub enum SuiClientCommands {
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
         input_coins: Vec<ObjectID>,
 
-        /// The recipient addresses, must be of same length as amounts.
+        /// The recipient addresses or their aliases, must be of same length as amounts.
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
-        recipients: Vec<SuiAddress>,
+        recipients: Vec<AddressOrAlias>,
 
         /// The amounts to be paid, following the order of recipients.
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
@@ -354,9 +517,9 @@ pub enum SuiClientCommands {
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
         input_coins: Vec<ObjectID>,
 
-        /// The recipient address.
+        /// The recipient address, or its alias.
         #[clap(long, multiple_occurrences = false)]
-        recipient: SuiAddress,
+        recipient: AddressOrAlias,
 
         /// Gas budget for this transaction
         #[clap(long)]
@@ -381,18 +544,35 @@ pub enum SuiClientCommands {
     /// Obtain all objects owned by the address
     #[clap(name = "objects")]
     Objects {
-        /// Address owning the objects
+        /// Address owning the objects, or its alias
         /// Shows all objects owned by `sui client active-address` if no argument is passed
         #[clap(name = "owner_address")]
-        address: Option<SuiAddress>,
+        address: Option<AddressOrAlias>,
     },
 
     /// Obtain all gas objects owned by the address.
     #[clap(name = "gas")]
     Gas {
-        /// Address owning the objects
+        /// Address owning the objects, or its alias
         #[clap(name = "owner_address")]
-        address: Option<SuiAddress>,
+        address: Option<AddressOrAlias>,
+    },
+
+    /// Obtain the balances of the coins owned by the address, aggregated by coin type and
+    /// formatted with the decimals of the coin types.
+    #[clap(name = "balance")]
+    Balance {
+        /// Address owning the coins, or its alias
+        /// Shows the balances of `sui client active-address` if no argument is passed
+        #[clap(name = "owner_address")]
+        address: Option<AddressOrAlias>,
+        /// Only show the balance of this coin type, e.g. 0x2::sui::SUI
+        #[clap(long)]
+        coin_type: Option<String>,
+        /// YAML or JSON file mapping coin types to their price in USD, to show the value of the
+        /// balances
+        #[clap(long)]
+        prices: Option<PathBuf>,
     },
 
     /// Query a dynamic field by its address.
@@ -450,9 +630,9 @@ pub enum SuiClientCommands {
     /// Serialize a transfer that can be signed. This is useful when user prefers to take the data to sign elsewhere.
     #[clap(name = "serialize-transfer-sui")]
     SerializeTransferSui {
-        /// Recipient address
+        /// Recipient address, or its alias
         #[clap(long)]
-        to: SuiAddress,
+        to: AddressOrAlias,
 
         /// Sui coin object to transfer, ID in 20 bytes Hex string. This is also the gas object.
         #[clap(long)]
@@ -467,6 +647,15 @@ pub enum SuiClientCommands {
         amount: Option<u64>,
     },
 
+    /// Dry run a serialized transaction and print its effects, without signing or executing it.
+    /// This is useful to check a transaction before signing it elsewhere.
+    #[clap(name = "dry-run")]
+    DryRun {
+        /// BCS serialized transaction data bytes without its type tag, as base-64 encoded string.
+        #[clap(long)]
+        tx_bytes: String,
+    },
+
     /// Execute a Signed Transaction. This is useful when the user prefers to sign elsewhere and use this command to execute.
     ExecuteSignedTx {
         /// BCS serialized transaction data bytes without its type tag, as base-64 encoded string.
@@ -479,6 +668,29 @@ pub enum SuiClientCommands {
     },
 }
 
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum AliasCommand {
+    /// Name an address with an alias, made of letters, digits, `-` and `_` and starting with a
+    /// letter
+    Add { alias: String, address: SuiAddress },
+    /// Remove an alias
+    Remove { alias: String },
+    /// List the aliases and their addresses
+    List,
+}
+
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum WatchOnlyCommand {
+    /// Add a watch-only address, or its alias
+    Add { address: AddressOrAlias },
+    /// Remove a watch-only address, or its alias
+    Remove { address: AddressOrAlias },
+    /// List the watch-only addresses
+    List,
+}
+
 impl SuiClientCommands {
     pub async fn execute(
         self,
@@ -493,6 +705,9 @@ impl SuiClientCommands {
                 gas_budget,
                 skip_dependency_verification,
                 with_unpublished_dependencies,
+                policy,
+                expected_digest,
+                dry_run,
             } => {
                 let sender = context.try_get_object_owner(&gas).await?;
                 let sender = sender.unwrap_or(context.active_address()?);
@@ -540,12 +755,32 @@ impl SuiClientCommands {
                     .try_as_move()
                     .ok_or_else(|| anyhow!("Upgrade capability is not a Move Object"))?
                     .deserialize()?;
-                // We keep the existing policy -- no fancy policies or changing the upgrade
-                // policy at the moment. To change the policy you can call a Move function in the
-                // `package` module to change this policy.
-                let upgrade_policy = upgrade_cap.policy;
+                ensure!(
+                    upgrade_cap.package.bytes == package_id,
+                    "Upgrade capability {upgrade_capability} is for package {}, not for package \
+                     {package_id} of the 'published-at' field of the manifest",
+                    upgrade_cap.package.bytes
+                );
+                // The upgrade keeps the policy of the capability, unless a more restrictive one is
+                // requested. `sui client restrict-upgrade-cap` changes the policy of the capability.
+                let upgrade_policy = policy.unwrap_or(upgrade_cap.policy);
+                ensure!(
+                    upgrade_policy >= upgrade_cap.policy,
+                    "Upgrade capability {upgrade_capability} only allows {} upgrades",
+                    upgrade_policy_name(upgrade_cap.policy)
+                );
                 let package_digest =
                     compiled_package.get_package_digest(with_unpublished_dependencies);
+                if let Some(expected_digest) = expected_digest {
+                    let expected = Hex::decode(expected_digest.trim_start_matches("0x"))
+                        .map_err(|e| anyhow!("Invalid digest {expected_digest}: {e}"))?;
+                    ensure!(
+                        expected == package_digest,
+                        "The digest of the compiled package {} is not the approved digest {}",
+                        Hex::encode(package_digest),
+                        Hex::encode(&expected)
+                    );
+                }
 
                 let data = client
                     .transaction_builder()
@@ -561,11 +796,10 @@ impl SuiClientCommands {
                         gas_budget,
                     )
                     .await?;
-                let signature = context.config.keystore.sign_secure(
-                    &sender,
-                    &data,
-                    Intent::sui_transaction(),
-                )?;
+                if dry_run {
+                    return dry_run_publish(&client, data).await;
+                }
+                let signature = context.sign_transaction(&sender, &data)?;
                 let response = context
                     .execute_transaction_block(
                         Transaction::from_data(data, Intent::sui_transaction(), vec![signature])
@@ -583,7 +817,12 @@ impl SuiClientCommands {
                 skip_dependency_verification,
                 with_unpublished_dependencies,
                 serialize_output,
+                dry_run,
             } => {
+                ensure!(
+                    !(dry_run && serialize_output),
+                    "--dry-run and --serialize-output cannot be used together"
+                );
                 let sender = context.try_get_object_owner(&gas).await?;
                 let sender = sender.unwrap_or(context.active_address()?);
 
@@ -612,12 +851,11 @@ impl SuiClientCommands {
                         bcs::to_bytes(&data).unwrap(),
                     )));
                 }
+                if dry_run {
+                    return dry_run_publish(&client, data).await;
+                }
 
-                let signature = context.config.keystore.sign_secure(
-                    &sender,
-                    &data,
-                    Intent::sui_transaction(),
-                )?;
+                let signature = context.sign_transaction(&sender, &data)?;
                 let response = context
                     .execute_transaction_block(
                         Transaction::from_data(data, Intent::sui_transaction(), vec![signature])
@@ -628,6 +866,53 @@ impl SuiClientCommands {
                 SuiClientCommandResult::Publish(response)
             }
 
+            SuiClientCommands::PublishWorkspace {
+                package_paths,
+                build_config,
+                gas,
+                gas_budget,
+                skip_dependency_verification,
+            } => {
+                let report = publish_workspace(
+                    context,
+                    &package_paths,
+                    build_config,
+                    gas,
+                    gas_budget,
+                    skip_dependency_verification,
+                )
+                .await?;
+                if let Some((package, error)) = report.failure() {
+                    return Err(anyhow!(
+                        "{report}Failed to publish package {package} of the workspace: {error}"
+                    ));
+                }
+                SuiClientCommandResult::PublishWorkspace(report)
+            }
+
+            SuiClientCommands::RestrictUpgradeCap {
+                upgrade_capability,
+                restriction,
+                gas,
+                gas_budget,
+            } => {
+                let sender = context.try_get_object_owner(&gas).await?;
+                let sender = sender.unwrap_or(context.active_address()?);
+                let client = context.get_client().await?;
+                let data = client
+                    .transaction_builder()
+                    .restrict_upgrade_cap(sender, upgrade_capability, restriction, gas, gas_budget)
+                    .await?;
+                let signature = context.sign_transaction(&sender, &data)?;
+                let response = context
+                    .execute_transaction_block(
+                        Transaction::from_data(data, Intent::sui_transaction(), vec![signature])
+                            .verify()?,
+                    )
+                    .await?;
+                SuiClientCommandResult::RestrictUpgradeCap(response)
+            }
+
             SuiClientCommands::Object { id, bcs } => {
                 // Fetch the object ref
                 let client = context.get_client().await?;
@@ -658,6 +943,22 @@ impl SuiClientCommands {
                 SuiClientCommandResult::TransactionBlock(tx_read)
             }
 
+            SuiClientCommands::TransactionReceipt { digest, output } => {
+                let client = context.get_client().await?;
+                let receipt = TransactionReceipt::fetch(&client, digest).await?;
+                // The receipt is only written once it verifies, against the committee reported by
+                // the node.
+                receipt.verify(None)?;
+                receipt.write(&output)?;
+                SuiClientCommandResult::TransactionReceipt(digest, output)
+            }
+
+            SuiClientCommands::VerifyReceipt { receipt, committee } => {
+                let receipt = TransactionReceipt::read(&receipt)?;
+                let committee = committee.as_deref().map(read_committee).transpose()?;
+                SuiClientCommandResult::VerifyReceipt(receipt.verify(committee.as_ref())?)
+            }
+
             SuiClientCommands::DynamicFieldQuery { id, cursor, limit } => {
                 let client = context.get_client().await?;
                 let df_read = client
@@ -675,33 +976,76 @@ impl SuiClientCommands {
                 gas,
                 gas_budget,
                 args,
+                yes,
             } => {
                 let response = call_move(
-                    package, &module, &function, type_args, gas, gas_budget, args, context,
+                    package, &module, &function, type_args, gas, gas_budget, args, yes, context,
                 )
                 .await?;
                 SuiClientCommandResult::Call(response)
             }
 
+            SuiClientCommands::Ptb {
+                plan,
+                vars,
+                gas,
+                gas_budget,
+                preview,
+                yes,
+            } => {
+                let plan =
+                    PtbPlan::from_yaml(&std::fs::read_to_string(&plan).map_err(|e| {
+                        anyhow!("Cannot read the plan file {}: {e}", plan.display())
+                    })?)?;
+                let commands = plan.expand(&vars)?;
+
+                let gas_owner = context.try_get_object_owner(&gas).await?;
+                let signer = gas_owner.unwrap_or(context.active_address()?);
+                let client = context.get_client().await?;
+                let pt = build_programmable_transaction(&client, commands).await?;
+                let data = client
+                    .transaction_builder()
+                    .finish_programmable_transaction(signer, pt, gas, gas_budget)
+                    .await?;
+
+                if preview {
+                    let response = client.read_api().dry_run_transaction_block(data).await?;
+                    SuiClientCommandResult::PtbPreview(response)
+                } else {
+                    let signature = context.sign_transaction(&signer, &data)?;
+                    confirm_transaction(&client, &data, yes).await?;
+                    let response = context
+                        .execute_transaction_block(
+                            Transaction::from_data(
+                                data,
+                                Intent::sui_transaction(),
+                                vec![signature],
+                            )
+                            .verify()?,
+                        )
+                        .await?;
+                    SuiClientCommandResult::Ptb(response)
+                }
+            }
+
             SuiClientCommands::Transfer {
                 to,
                 object_id,
                 gas,
                 gas_budget,
+                yes,
             } => {
+                let to = context.config.resolve_address(&to)?;
                 let from = context.get_object_owner(&object_id).await?;
-                let time_start = Instant::now();
-
                 let client = context.get_client().await?;
                 let data = client
                     .transaction_builder()
                     .transfer_object(from, object_id, gas, gas_budget, to)
                     .await?;
-                let signature =
-                    context
-                        .config
-                        .keystore
-                        .sign_secure(&from, &data, Intent::sui_transaction())?;
+                let signature = context.sign_transaction(&from, &data)?;
+                confirm_transaction(&client, &data, yes).await?;
+
+                let time_start = Instant::now();
                 let response = context
                     .execute_transaction_block(
                         Transaction::from_data(data, Intent::sui_transaction(), vec![signature])
@@ -727,6 +1071,7 @@ impl SuiClientCommands {
                 gas_budget,
                 amount,
             } => {
+                let to = context.config.resolve_address(&to)?;
                 let from = context.get_object_owner(&object_id).await?;
 
                 let client = context.get_client().await?;
@@ -734,11 +1079,7 @@ impl SuiClientCommands {
                     .transaction_builder()
                     .transfer_sui(from, object_id, gas_budget, to, amount)
                     .await?;
-                let signature =
-                    context
-                        .config
-                        .keystore
-                        .sign_secure(&from, &data, Intent::sui_transaction())?;
+                let signature = context.sign_transaction(&from, &data)?;
                 let response = context
                     .execute_transaction_block(
                         Transaction::from_data(data, Intent::sui_transaction(), vec![signature])
@@ -777,17 +1118,17 @@ impl SuiClientCommands {
                         amounts.len()
                     ),
                 );
+                let recipients = recipients
+                    .iter()
+                    .map(|recipient| context.config.resolve_address(recipient))
+                    .collect::<Result<Vec<_>, _>>()?;
                 let from = context.get_object_owner(&input_coins[0]).await?;
                 let client = context.get_client().await?;
                 let data = client
                     .transaction_builder()
                     .pay(from, input_coins, recipients, amounts, gas, gas_budget)
                     .await?;
-                let signature =
-                    context
-                        .config
-                        .keystore
-                        .sign_secure(&from, &data, Intent::sui_transaction())?;
+                let signature = context.sign_transaction(&from, &data)?;
                 let response = context
                     .execute_transaction_block(
                         Transaction::from_data(data, Intent::sui_transaction(), vec![signature])
@@ -828,17 +1169,17 @@ impl SuiClientCommands {
                         amounts.len()
                     ),
                 );
+                let recipients = recipients
+                    .iter()
+                    .map(|recipient| context.config.resolve_address(recipient))
+                    .collect::<Result<Vec<_>, _>>()?;
                 let signer = context.get_object_owner(&input_coins[0]).await?;
                 let client = context.get_client().await?;
                 let data = client
                     .transaction_builder()
                     .pay_sui(signer, input_coins, recipients, amounts, gas_budget)
                     .await?;
-                let signature = context.config.keystore.sign_secure(
-                    &signer,
-                    &data,
-                    Intent::sui_transaction(),
-                )?;
+                let signature = context.sign_transaction(&signer, &data)?;
                 let response = context
                     .execute_transaction_block(
                         Transaction::from_data(data, Intent::sui_transaction(), vec![signature])
@@ -866,6 +1207,7 @@ impl SuiClientCommands {
                     !input_coins.is_empty(),
                     "PayAllSui transaction requires a non-empty list of input coins"
                 );
+                let recipient = context.config.resolve_address(&recipient)?;
                 let signer = context.get_object_owner(&input_coins[0]).await?;
                 let client = context.get_client().await?;
                 let data = client
@@ -873,11 +1215,7 @@ impl SuiClientCommands {
                     .pay_all_sui(signer, input_coins, recipient, gas_budget)
                     .await?;
 
-                let signature = context.config.keystore.sign_secure(
-                    &signer,
-                    &data,
-                    Intent::sui_transaction(),
-                )?;
+                let signature = context.sign_transaction(&signer, &data)?;
                 let response = context
                     .execute_transaction_block(
                         Transaction::from_data(data, Intent::sui_transaction(), vec![signature])
@@ -902,7 +1240,7 @@ impl SuiClientCommands {
             ),
 
             SuiClientCommands::Objects { address } => {
-                let address = address.unwrap_or(context.active_address()?);
+                let address = context.resolve_address_or_active(address)?;
                 let client = context.get_client().await?;
                 let mut objects: Vec<SuiObjectResponse> = Vec::new();
                 let mut cursor = None;
@@ -941,8 +1279,27 @@ impl SuiClientCommands {
                 )?;
                 SuiClientCommandResult::NewAddress((address, phrase, scheme))
             }
+            SuiClientCommands::Balance {
+                address,
+                coin_type,
+                prices,
+            } => {
+                let address = context.resolve_address_or_active(address)?;
+                let prices = prices
+                    .map(|path| FilePriceProvider::from_file(&path))
+                    .transpose()?;
+                let client = context.get_client().await?;
+                let balances = get_coin_balances(
+                    &client,
+                    address,
+                    coin_type,
+                    prices.as_ref().map(|prices| prices as &dyn PriceProvider),
+                )
+                .await?;
+                SuiClientCommandResult::Balance(balances)
+            }
             SuiClientCommands::Gas { address } => {
-                let address = address.unwrap_or(context.active_address()?);
+                let address = context.resolve_address_or_active(address)?;
                 let coins = context
                     .gas_objects(address)
                     .await?
@@ -981,11 +1338,7 @@ impl SuiClientCommands {
                         return Err(anyhow!("Exactly one of `count` and `amounts` must be present for split-coin command."));
                     }
                 };
-                let signature = context.config.keystore.sign_secure(
-                    &signer,
-                    &data,
-                    Intent::sui_transaction(),
-                )?;
+                let signature = context.sign_transaction(&signer, &data)?;
                 let response = context
                     .execute_transaction_block(
                         Transaction::from_data(data, Intent::sui_transaction(), vec![signature])
@@ -1006,11 +1359,7 @@ impl SuiClientCommands {
                     .transaction_builder()
                     .merge_coins(signer, primary_coin, coin_to_merge, gas, gas_budget)
                     .await?;
-                let signature = context.config.keystore.sign_secure(
-                    &signer,
-                    &data,
-                    Intent::sui_transaction(),
-                )?;
+                let signature = context.sign_transaction(&signer, &data)?;
                 let response = context
                     .execute_transaction_block(
                         Transaction::from_data(data, Intent::sui_transaction(), vec![signature])
@@ -1021,12 +1370,17 @@ impl SuiClientCommands {
                 SuiClientCommandResult::MergeCoin(response)
             }
             SuiClientCommands::Switch { address, env } => {
+                let address = address
+                    .map(|address| context.config.resolve_address(&address))
+                    .transpose()?;
                 match (address, &env) {
                     (None, Some(env)) => {
                         Self::switch_env(&mut context.config, env)?;
                     }
                     (Some(addr), None) => {
-                        if !context.config.keystore.addresses().contains(&addr) {
+                        if !context.config.keystore.addresses().contains(&addr)
+                            && !context.config.watch_only.contains(&addr)
+                        {
                             return Err(anyhow!("Address {} not managed by wallet", addr));
                         }
                         context.config.active_address = Some(addr);
@@ -1046,6 +1400,7 @@ impl SuiClientCommands {
                 gas_budget,
                 amount,
             } => {
+                let to = context.config.resolve_address(&to)?;
                 let from = context.get_object_owner(&object_id).await?;
                 let client = context.get_client().await?;
                 let data = client
@@ -1057,6 +1412,18 @@ impl SuiClientCommands {
                 ))
             }
 
+            SuiClientCommands::DryRun { tx_bytes } => {
+                let data: TransactionData = bcs::from_bytes(
+                    &Base64::try_from(tx_bytes)
+                        .map_err(|e| anyhow!(e))?
+                        .to_vec()
+                        .map_err(|e| anyhow!(e))?,
+                )?;
+                let client = context.get_client().await?;
+                let response = client.read_api().dry_run_transaction_block(data).await?;
+                SuiClientCommandResult::DryRun(response)
+            }
+
             SuiClientCommands::ExecuteSignedTx {
                 tx_bytes,
                 signatures,
@@ -1108,6 +1475,52 @@ impl SuiClientCommands {
                 context.config.envs.clone(),
                 context.config.active_env.clone(),
             ),
+            SuiClientCommands::Alias { cmd } => match cmd {
+                AliasCommand::Add { alias, address } => {
+                    context.config.add_alias(alias.clone(), address)?;
+                    context.config.save()?;
+                    SuiClientCommandResult::NewAlias(alias, address)
+                }
+                AliasCommand::Remove { alias } => {
+                    let address = context
+                        .config
+                        .aliases
+                        .remove(&alias)
+                        .ok_or_else(|| anyhow!("Alias [{alias}] not found"))?;
+                    context.config.save()?;
+                    SuiClientCommandResult::RemovedAlias(alias, address)
+                }
+                AliasCommand::List => {
+                    SuiClientCommandResult::Aliases(context.config.aliases.clone())
+                }
+            },
+            SuiClientCommands::WatchOnly { cmd } => match cmd {
+                WatchOnlyCommand::Add { address } => {
+                    let address = context.config.resolve_address(&address)?;
+                    ensure!(
+                        !context.config.keystore.addresses().contains(&address),
+                        "Address {address} has a key in the keystore"
+                    );
+                    context.config.watch_only.insert(address);
+                    context.config.save()?;
+                    SuiClientCommandResult::WatchOnly(context.config.watch_only.clone())
+                }
+                WatchOnlyCommand::Remove { address } => {
+                    let address = context.config.resolve_address(&address)?;
+                    ensure!(
+                        context.config.watch_only.remove(&address),
+                        "Address {address} is not watch-only"
+                    );
+                    if context.config.active_address == Some(address) {
+                        context.config.active_address = None;
+                    }
+                    context.config.save()?;
+                    SuiClientCommandResult::WatchOnly(context.config.watch_only.clone())
+                }
+                WatchOnlyCommand::List => {
+                    SuiClientCommandResult::WatchOnly(context.config.watch_only.clone())
+                }
+            },
             SuiClientCommands::VerifySource {
                 package_path,
                 build_config,
@@ -1161,7 +1574,7 @@ impl SuiClientCommands {
     }
 }
 
-async fn compile_package(
+pub(crate) async fn compile_package(
     client: &SuiClient,
     build_config: MoveBuildConfig,
     package_path: PathBuf,
@@ -1276,21 +1689,53 @@ impl WalletContext {
     }
 
     pub fn active_address(&mut self) -> Result<SuiAddress, anyhow::Error> {
-        if self.config.keystore.addresses().is_empty() {
+        // Set it if not exists, preferring the addresses of the keystore to watch-only ones
+        let first_address = self
+            .config
+            .keystore
+            .addresses()
+            .first()
+            .or_else(|| self.config.watch_only.iter().next())
+            .copied();
+        let Some(address) = self.config.active_address.or(first_address) else {
             return Err(anyhow!(
                 "No managed addresses. Create new address with `new-address` command."
             ));
-        }
+        };
+        self.config.active_address = Some(address);
+        Ok(address)
+    }
 
-        // Ok to unwrap because we checked that config addresses not empty
-        // Set it if not exists
-        self.config.active_address = Some(
-            self.config
-                .active_address
-                .unwrap_or(*self.config.keystore.addresses().get(0).unwrap()),
+    /// Signs `data` for `signer`, with its key in the keystore. Watch-only addresses have no key.
+    pub fn sign_transaction(
+        &self,
+        signer: &SuiAddress,
+        data: &TransactionData,
+    ) -> Result<Signature, anyhow::Error> {
+        ensure!(
+            !self.config.watch_only.contains(signer),
+            "Address {signer} is watch-only, the client has no key to sign its transactions. \
+             Sign them offline instead: serialize the transaction (e.g. with \
+             `sui client serialize-transfer-sui`), check it with `sui client dry-run`, sign it \
+             with `sui keytool sign` where the key is kept, and execute it with \
+             `sui client execute-signed-tx`."
         );
+        Ok(self
+            .config
+            .keystore
+            .sign_secure(signer, data, Intent::sui_transaction())?)
+    }
 
-        Ok(self.config.active_address.unwrap())
+    /// The address of `address` (see [SuiClientConfig::resolve_address]), or the active address
+    /// if `address` is not given.
+    pub fn resolve_address_or_active(
+        &mut self,
+        address: Option<AddressOrAlias>,
+    ) -> Result<SuiAddress, anyhow::Error> {
+        match address {
+            Some(address) => self.config.resolve_address(&address),
+            None => self.active_address(),
+        }
     }
 
     /// Get the latest object reference given a object id
@@ -1455,9 +1900,31 @@ impl Display for SuiClientCommandResult {
                 };
                 writeln!(writer, "{}", raw_object)?;
             }
-            SuiClientCommandResult::Call(response) => {
+            SuiClientCommandResult::Call(response)
+            | SuiClientCommandResult::Ptb(response)
+            | SuiClientCommandResult::RestrictUpgradeCap(response) => {
                 write!(writer, "{}", write_transaction_response(response)?)?;
             }
+            SuiClientCommandResult::PtbPreview(response)
+            | SuiClientCommandResult::DryRun(response) => {
+                write!(writer, "{}", write_dry_run_response(response)?)?;
+            }
+            SuiClientCommandResult::PublishDryRun(response) => {
+                write!(writer, "{}", write_publish_dry_run_response(response)?)?;
+            }
+            SuiClientCommandResult::PublishWorkspace(report) => {
+                write!(writer, "{report}")?;
+            }
+            SuiClientCommandResult::TransactionReceipt(digest, path) => {
+                writeln!(
+                    writer,
+                    "Receipt of transaction {digest} written to {}",
+                    path.display()
+                )?;
+            }
+            SuiClientCommandResult::VerifyReceipt(receipt) => {
+                write!(writer, "{receipt}")?;
+            }
             SuiClientCommandResult::Transfer(time_elapsed, response) => {
                 writeln!(writer, "Transfer confirmed after {} us", time_elapsed)?;
                 write!(writer, "{}", write_transaction_response(response)?)?;
@@ -1570,6 +2037,9 @@ impl Display for SuiClientCommandResult {
                     writeln!(writer, " {0: ^66} | {1: ^11}", gas.id(), gas.value())?;
                 }
             }
+            SuiClientCommandResult::Balance(balances) => {
+                write!(writer, "{}", write_coin_balances(balances)?)?;
+            }
             SuiClientCommandResult::SplitCoin(response) => {
                 write!(writer, "{}", write_transaction_response(response)?)?;
             }
@@ -1612,11 +2082,29 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::VerifySource => {
                 writeln!(writer, "Source verification succeeded!")?;
             }
+            SuiClientCommandResult::NewAlias(alias, address) => {
+                writeln!(writer, "Added alias [{alias}] for address {address}.")?;
+            }
+            SuiClientCommandResult::RemovedAlias(alias, address) => {
+                writeln!(writer, "Removed alias [{alias}] of address {address}.")?;
+            }
+            SuiClientCommandResult::Aliases(aliases) => {
+                for (alias, address) in aliases {
+                    writeln!(writer, "{alias} => {address}")?;
+                }
+            }
+            SuiClientCommandResult::WatchOnly(addresses) => {
+                writeln!(writer, "Showing {} watch-only addresses.", addresses.len())?;
+                for address in addresses {
+                    writeln!(writer, "{address}")?;
+                }
+            }
         }
         write!(f, "{}", writer.trim_end_matches('\n'))
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn call_move(
     package: ObjectID,
     module: &str,
@@ -1625,6 +2113,7 @@ pub async fn call_move(
     gas: Option<ObjectID>,
     gas_budget: u64,
     args: Vec<SuiJsonValue>,
+    yes: bool,
     context: &mut WalletContext,
 ) -> Result<SuiTransactionBlockResponse, anyhow::Error> {
     // Convert all numeric input to String, this will allow number input from the CLI without failing SuiJSON's checks.
@@ -1653,11 +2142,8 @@ pub async fn call_move(
             gas_budget,
         )
         .await?;
-    let signature =
-        context
-            .config
-            .keystore
-            .sign_secure(&sender, &data, Intent::sui_transaction())?;
+    let signature = context.sign_transaction(&sender, &data)?;
+    confirm_transaction(&client, &data, yes).await?;
     let transaction =
         Transaction::from_data(data, Intent::sui_transaction(), vec![signature]).verify()?;
 
@@ -1672,7 +2158,7 @@ pub async fn call_move(
     Ok(response)
 }
 
-fn convert_number_to_string(value: Value) -> Value {
+pub(crate) fn convert_number_to_string(value: Value) -> Value {
     match value {
         Value::Number(n) => Value::String(n.to_string()),
         Value::Array(a) => Value::Array(a.into_iter().map(convert_number_to_string).collect()),
@@ -1719,20 +2205,241 @@ pub fn write_transaction_response(
     Ok(writer)
 }
 
+pub fn write_dry_run_response(
+    response: &DryRunTransactionBlockResponse,
+) -> Result<String, fmt::Error> {
+    let mut writer = String::new();
+    writeln!(writer, "{}", "----- Transaction Data ----".bold())?;
+    writeln!(writer, "{}", response.input)?;
+
+    writeln!(
+        writer,
+        "{}",
+        "----- Transaction Effects (dry run) ----".bold()
+    )?;
+    writeln!(writer, "{}", response.effects)?;
+
+    writeln!(writer, "{}", "----- Events ----".bold())?;
+    writeln!(writer, "{:#?}", json!(response.events))?;
+
+    writeln!(writer, "{}", "----- Object changes ----".bold())?;
+    writeln!(writer, "{:#?}", json!(response.object_changes))?;
+
+    writeln!(writer, "{}", "----- Balance changes ----".bold())?;
+    writeln!(writer, "{:#?}", json!(response.balance_changes))?;
+    Ok(writer)
+}
+
+fn parse_upgrade_policy(s: &str) -> Result<u8, anyhow::Error> {
+    Ok(match s {
+        "compatible" => UpgradePolicy::COMPATIBLE,
+        "additive" => UpgradePolicy::ADDITIVE,
+        "dep-only" => UpgradePolicy::DEP_ONLY,
+        _ => bail!("Expected `compatible`, `additive` or `dep-only`, found `{s}`"),
+    })
+}
+
+fn parse_upgrade_cap_restriction(s: &str) -> Result<UpgradeCapRestriction, anyhow::Error> {
+    Ok(match s {
+        "additive" => UpgradeCapRestriction::Additive,
+        "dep-only" => UpgradeCapRestriction::DepOnly,
+        "immutable" => UpgradeCapRestriction::Immutable,
+        _ => bail!("Expected `additive`, `dep-only` or `immutable`, found `{s}`"),
+    })
+}
+
+fn upgrade_policy_name(policy: u8) -> String {
+    UpgradePolicy::try_from(policy)
+        .map(|policy| policy.to_string())
+        .unwrap_or_else(|_| policy.to_string())
+}
+
+/// Dry runs the publish or upgrade transaction `data`, which fails if the package cannot be
+/// published, e.g. because it does not pass the verifiers or its linkage is invalid.
+async fn dry_run_publish(
+    client: &SuiClient,
+    data: TransactionData,
+) -> Result<SuiClientCommandResult, anyhow::Error> {
+    let response = client.read_api().dry_run_transaction_block(data).await?;
+    if let SuiExecutionStatus::Failure { error } = response.effects.status() {
+        return Err(anyhow!("The package cannot be published: {error}"));
+    }
+    Ok(SuiClientCommandResult::PublishDryRun(response))
+}
+
+/// The package a publish or upgrade transaction would publish, from its dry run, and its cost.
+pub fn write_publish_dry_run_response(
+    response: &DryRunTransactionBlockResponse,
+) -> Result<String, fmt::Error> {
+    let mut writer = String::new();
+    writeln!(writer, "{}", "----- Publish (dry run) ----".bold())?;
+    for change in &response.object_changes {
+        if let ObjectChange::Published {
+            package_id,
+            version,
+            modules,
+            ..
+        } = change
+        {
+            writeln!(writer, "Package ID: {package_id}")?;
+            writeln!(writer, "Version: {}", version.value())?;
+            writeln!(writer, "Modules: {}", modules.join(", "))?;
+        }
+    }
+    let gas = response.effects.gas_cost_summary();
+    writeln!(writer, "Computation cost: {} MIST", gas.computation_cost)?;
+    writeln!(writer, "Storage cost: {} MIST", gas.storage_cost)?;
+    writeln!(writer, "Storage rebate: {} MIST", gas.storage_rebate)?;
+    writeln!(
+        writer,
+        "The package ID is derived from the transaction, which changes if its gas object is used \
+         by another transaction before the package is published."
+    )?;
+    Ok(writer)
+}
+
+/// Prints a summary of the effects of `data` from a dry run, and asks for confirmation before
+/// it is signed and submitted, unless `yes` is set.
+async fn confirm_transaction(
+    client: &SuiClient,
+    data: &TransactionData,
+    yes: bool,
+) -> Result<(), anyhow::Error> {
+    if yes {
+        return Ok(());
+    }
+    let response = client
+        .read_api()
+        .dry_run_transaction_block(data.clone())
+        .await?;
+    println!("{}", write_transaction_preview(&response)?);
+
+    let confirmed = Confirm::new("Sign and submit this transaction?")
+        .with_default(false)
+        .prompt()
+        .map_err(|e| {
+            anyhow!(
+                "Cannot ask for confirmation ({e}), use --yes to submit the transaction without it"
+            )
+        })?;
+    ensure!(confirmed, "Transaction cancelled");
+    Ok(())
+}
+
+/// A summary of what a transaction does, from its dry run: the balances and objects it changes,
+/// the shared objects it touches, and the gas it can cost.
+pub fn write_transaction_preview(
+    response: &DryRunTransactionBlockResponse,
+) -> Result<String, fmt::Error> {
+    let mut writer = String::new();
+    writeln!(writer, "{}", "----- Transaction Preview ----".bold())?;
+    match response.effects.status() {
+        SuiExecutionStatus::Success => writeln!(writer, "Status: success")?,
+        SuiExecutionStatus::Failure { error } => {
+            writeln!(writer, "{}", format!("Status: failure, {error}").red())?
+        }
+    }
+
+    writeln!(writer, "Balance changes:")?;
+    if response.balance_changes.is_empty() {
+        writeln!(writer, "  none")?;
+    }
+    for change in &response.balance_changes {
+        writeln!(
+            writer,
+            "  {}: {:+} {}",
+            change.owner, change.amount, change.coin_type
+        )?;
+    }
+
+    writeln!(writer, "Object changes:")?;
+    if response.object_changes.is_empty() {
+        writeln!(writer, "  none")?;
+    }
+    for change in &response.object_changes {
+        match change {
+            ObjectChange::Published { package_id, .. } => {
+                writeln!(writer, "  Published package {package_id}")?
+            }
+            ObjectChange::Transferred {
+                recipient,
+                object_type,
+                object_id,
+                ..
+            } => writeln!(
+                writer,
+                "  Transferred {object_id} ({object_type}) to {recipient}"
+            )?,
+            ObjectChange::Mutated {
+                owner,
+                object_type,
+                object_id,
+                ..
+            } => writeln!(
+                writer,
+                "  Mutated {object_id} ({object_type}), owned by {owner}"
+            )?,
+            ObjectChange::Deleted {
+                object_type,
+                object_id,
+                ..
+            } => writeln!(writer, "  Deleted {object_id} ({object_type})")?,
+            ObjectChange::Wrapped {
+                object_type,
+                object_id,
+                ..
+            } => writeln!(writer, "  Wrapped {object_id} ({object_type})")?,
+            ObjectChange::Created {
+                owner,
+                object_type,
+                object_id,
+                ..
+            } => writeln!(
+                writer,
+                "  Created {object_id} ({object_type}), owned by {owner}"
+            )?,
+        }
+    }
+
+    writeln!(writer, "Shared objects:")?;
+    let shared_objects: Vec<_> = match response.input.transaction() {
+        SuiTransactionBlockKind::ProgrammableTransaction(pt) => pt
+            .inputs
+            .iter()
+            .filter_map(|input| match input {
+                SuiCallArg::Object(SuiObjectArg::SharedObject {
+                    object_id, mutable, ..
+                }) => Some((object_id, mutable)),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    };
+    if shared_objects.is_empty() {
+        writeln!(writer, "  none")?;
+    }
+    for (object_id, mutable) in shared_objects {
+        let access = if *mutable { "mutable" } else { "immutable" };
+        writeln!(writer, "  {object_id} ({access})")?;
+    }
+
+    let gas_data = response.input.gas_data();
+    let gas_cost = response.effects.gas_cost_summary();
+    writeln!(
+        writer,
+        "Gas: {} MIST estimated ({} computation, {} storage, {} rebate), at most {} MIST (budget)",
+        gas_cost.net_gas_usage(),
+        gas_cost.computation_cost,
+        gas_cost.storage_cost,
+        gas_cost.storage_rebate,
+        gas_data.budget
+    )?;
+    Ok(writer)
+}
+
 impl Debug for SuiClientCommandResult {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let s = unwrap_err_to_string(|| match self {
-            SuiClientCommandResult::Object(object_read) => {
-                let object = object_read.object()?;
-                Ok(serde_json::to_string_pretty(&object)?)
-            }
-            SuiClientCommandResult::RawObject(raw_object_read) => {
-                let raw_object = raw_object_read.object()?;
-                Ok(serde_json::to_string_pretty(&raw_object)?)
-            }
-            _ => Ok(serde_json::to_string_pretty(self)?),
-        });
-        write!(f, "{}", s)
+        write!(f, "{}", self.render(OutputFormat::Json))
     }
 }
 
@@ -1743,19 +2450,15 @@ fn unwrap_err_to_string<T: Display, F: FnOnce() -> Result<T, anyhow::Error>>(fun
     }
 }
 
-impl SuiClientCommandResult {
-    pub fn print(&self, pretty: bool) {
-        let line = if pretty {
-            format!("{self}")
-        } else {
-            format!("{:?}", self)
-        };
-        // Log line by line
-        for line in line.lines() {
-            // Logs write to a file on the side.  Print to stdout and also log to file, for tests to pass.
-            println!("{line}");
-            info!("{line}")
-        }
+impl PrintableResult for SuiClientCommandResult {
+    fn structured(&self) -> Result<Value, anyhow::Error> {
+        Ok(match self {
+            SuiClientCommandResult::Object(object_read)
+            | SuiClientCommandResult::RawObject(object_read) => {
+                serde_json::to_value(object_read.object()?)?
+            }
+            _ => serde_json::to_value(self)?,
+        })
     }
 }
 
@@ -1764,11 +2467,19 @@ impl SuiClientCommandResult {
 pub enum SuiClientCommandResult {
     Upgrade(SuiTransactionBlockResponse),
     Publish(SuiTransactionBlockResponse),
+    PublishDryRun(DryRunTransactionBlockResponse),
+    PublishWorkspace(WorkspacePublishReport),
+    RestrictUpgradeCap(SuiTransactionBlockResponse),
     VerifySource,
     Object(SuiObjectResponse),
     RawObject(SuiObjectResponse),
     TransactionBlock(SuiTransactionBlockResponse),
+    TransactionReceipt(TransactionDigest, PathBuf),
+    VerifyReceipt(VerifiedTransactionReceipt),
     Call(SuiTransactionBlockResponse),
+    Ptb(SuiTransactionBlockResponse),
+    PtbPreview(DryRunTransactionBlockResponse),
+    DryRun(DryRunTransactionBlockResponse),
     Transfer(
         // Skipping serialisation for elapsed time.
         #[serde(skip)] u128,
@@ -1784,12 +2495,17 @@ pub enum SuiClientCommandResult {
     SyncClientState,
     NewAddress((SuiAddress, String, SignatureScheme)),
     Gas(Vec<GasCoin>),
+    Balance(Vec<CoinBalance>),
     SplitCoin(SuiTransactionBlockResponse),
     MergeCoin(SuiTransactionBlockResponse),
     Switch(SwitchResponse),
     ActiveAddress(Option<SuiAddress>),
     ActiveEnv(Option<String>),
     Envs(Vec<SuiEnv>, Option<String>),
+    NewAlias(String, SuiAddress),
+    RemovedAlias(String, SuiAddress),
+    Aliases(BTreeMap<String, SuiAddress>),
+    WatchOnly(BTreeSet<SuiAddress>),
     SerializeTransferSui(String),
     SerializePublish(String),
     ExecuteSignedTx(SuiTransactionBlockResponse),
@@ -0,0 +1,198 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Shell completion scripts of the CLI, generated from its clap definition by `sui completion`.
+//!
+//! The scripts of bash, zsh and fish also complete the values of the options taking addresses,
+//! with the aliases and addresses of the client config, and of the options taking object IDs,
+//! with the objects owned by the active address. They get these values by running the hidden
+//! `sui completion-values` command, so that they are current at the time of the completion.
+
+use std::io::Write;
+
+use clap::{Command, ValueEnum};
+use clap_complete::{generate, Shell};
+use sui_json_rpc_types::{SuiObjectDataOptions, SuiObjectResponseQuery};
+use sui_types::base_types::ObjectID;
+
+use crate::client_commands::WalletContext;
+use crate::config::SuiClientConfig;
+
+#[path = "unit_tests/completion_tests.rs"]
+#[cfg(test)]
+mod completion_tests;
+
+/// Long names of the options taking addresses or their aliases.
+pub const ADDRESS_OPTIONS: &[&str] = &["address", "to", "recipient", "recipients"];
+
+/// Long names of the options taking IDs of objects owned by the sender.
+pub const OBJECT_OPTIONS: &[&str] = &[
+    "gas",
+    "object-id",
+    "sui-coin-object-id",
+    "input-coins",
+    "coin-id",
+    "primary-coin",
+    "coin-to-merge",
+    "upgrade-capability",
+];
+
+/// Values printed by `sui completion-values`, one per line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CompletionValues {
+    /// The aliases and the addresses of the client config.
+    Addresses,
+    /// The IDs of the objects owned by the active address.
+    Objects,
+}
+
+/// Writes the completion script of `app`, the command `bin_name`, for `shell`.
+pub fn write_completion_script(
+    shell: Shell,
+    app: &mut Command,
+    bin_name: &str,
+    out: &mut dyn Write,
+) -> Result<(), anyhow::Error> {
+    generate(shell, app, bin_name, out);
+    let dynamic = match shell {
+        Shell::Bash => bash_dynamic_completion(bin_name),
+        Shell::Zsh => zsh_dynamic_completion(bin_name),
+        Shell::Fish => fish_dynamic_completion(bin_name),
+        _ => return Ok(()),
+    };
+    writeln!(out)?;
+    write!(out, "{dynamic}")?;
+    Ok(())
+}
+
+/// The aliases of the client config, followed by the addresses of its keystore and its
+/// watch-only addresses.
+pub fn address_values(config: &SuiClientConfig) -> Vec<String> {
+    config
+        .aliases
+        .keys()
+        .cloned()
+        .chain(config.keystore.addresses().iter().map(|a| a.to_string()))
+        .chain(config.watch_only.iter().map(|a| a.to_string()))
+        .collect()
+}
+
+/// The IDs of the objects owned by the active address.
+pub async fn owned_object_ids(context: &mut WalletContext) -> Result<Vec<ObjectID>, anyhow::Error> {
+    let address = context.active_address()?;
+    let client = context.get_client().await?;
+    let mut ids = Vec::new();
+    let mut cursor = None;
+    loop {
+        let response = client
+            .read_api()
+            .get_owned_objects(
+                address,
+                Some(SuiObjectResponseQuery::new_with_options(
+                    SuiObjectDataOptions::new(),
+                )),
+                cursor,
+                None,
+            )
+            .await?;
+        ids.extend(response.data.iter().filter_map(|o| o.object_id().ok()));
+        if response.has_next_page {
+            cursor = response.next_cursor;
+        } else {
+            break;
+        }
+    }
+    Ok(ids)
+}
+
+/// Writes the `values` of the completions, one per line.
+pub async fn write_completion_values(
+    values: CompletionValues,
+    context: &mut WalletContext,
+    out: &mut dyn Write,
+) -> Result<(), anyhow::Error> {
+    let values = match values {
+        CompletionValues::Addresses => address_values(&context.config),
+        CompletionValues::Objects => owned_object_ids(context)
+            .await?
+            .iter()
+            .map(|id| id.to_string())
+            .collect(),
+    };
+    for value in values {
+        writeln!(out, "{value}")?;
+    }
+    Ok(())
+}
+
+fn options(names: &[&str], separator: &str) -> String {
+    names
+        .iter()
+        .map(|name| format!("--{name}"))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn bash_dynamic_completion(bin_name: &str) -> String {
+    format!(
+        r#"_{bin_name}_dynamic() {{
+    local values
+    case "${{COMP_WORDS[COMP_CWORD-1]}}" in
+        {addresses})
+            values="$({bin_name} completion-values addresses 2>/dev/null)"
+            ;;
+        {objects})
+            values="$({bin_name} completion-values objects 2>/dev/null)"
+            ;;
+        *)
+            _{bin_name} "$@"
+            return
+            ;;
+    esac
+    COMPREPLY=($(compgen -W "${{values}}" -- "${{COMP_WORDS[COMP_CWORD]}}"))
+}}
+
+complete -F _{bin_name}_dynamic -o bashdefault -o default {bin_name}
+"#,
+        addresses = options(ADDRESS_OPTIONS, "|"),
+        objects = options(OBJECT_OPTIONS, "|"),
+    )
+}
+
+fn zsh_dynamic_completion(bin_name: &str) -> String {
+    format!(
+        r#"_{bin_name}_dynamic() {{
+    case "${{words[CURRENT-1]}}" in
+        {addresses})
+            compadd -- ${{(f)"$({bin_name} completion-values addresses 2>/dev/null)"}}
+            ;;
+        {objects})
+            compadd -- ${{(f)"$({bin_name} completion-values objects 2>/dev/null)"}}
+            ;;
+        *)
+            _{bin_name} "$@"
+            ;;
+    esac
+}}
+
+compdef _{bin_name}_dynamic {bin_name}
+"#,
+        addresses = options(ADDRESS_OPTIONS, "|"),
+        objects = options(OBJECT_OPTIONS, "|"),
+    )
+}
+
+fn fish_dynamic_completion(bin_name: &str) -> String {
+    let complete = |names: &[&str], values: &str| {
+        names
+            .iter()
+            .map(|name| {
+                format!(
+                    "complete -c {bin_name} -l {name} -xa \
+                     '({bin_name} completion-values {values} 2>/dev/null)'\n"
+                )
+            })
+            .collect::<String>()
+    };
+    complete(ADDRESS_OPTIONS, "addresses") + &complete(OBJECT_OPTIONS, "objects")
+}
@@ -2,9 +2,11 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::{Display, Formatter, Write};
+use std::str::FromStr;
 
-use anyhow::anyhow;
+use anyhow::{anyhow, ensure};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
@@ -24,6 +26,12 @@ pub struct SuiClientConfig {
     pub envs: Vec<SuiEnv>,
     pub active_env: Option<String>,
     pub active_address: Option<SuiAddress>,
+    /// Names of addresses, accepted by the commands of the client instead of the addresses.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, SuiAddress>,
+    /// Addresses without keys in the keystore, which the client can query but not sign for.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub watch_only: BTreeSet<SuiAddress>,
 }
 
 impl SuiClientConfig {
@@ -33,9 +41,36 @@ impl SuiClientConfig {
             envs: vec![],
             active_env: None,
             active_address: None,
+            aliases: BTreeMap::new(),
+            watch_only: BTreeSet::new(),
         }
     }
 
+    /// The address of `address`, looked up in the aliases of the config if it is an alias.
+    pub fn resolve_address(&self, address: &AddressOrAlias) -> Result<SuiAddress, anyhow::Error> {
+        match address {
+            AddressOrAlias::Address(address) => Ok(*address),
+            AddressOrAlias::Alias(alias) => {
+                self.aliases.get(alias).copied().ok_or_else(|| {
+                    anyhow!("Alias [{alias}] not found, see `sui client alias list`")
+                })
+            }
+        }
+    }
+
+    /// Names `address` with `alias`. Fails if the alias is already used, for another address.
+    pub fn add_alias(&mut self, alias: String, address: SuiAddress) -> Result<(), anyhow::Error> {
+        validate_alias(&alias)?;
+        if let Some(existing) = self.aliases.get(&alias) {
+            ensure!(
+                *existing == address,
+                "Alias [{alias}] already exists for address {existing}"
+            );
+        }
+        self.aliases.insert(alias, address);
+        Ok(())
+    }
+
     pub fn get_env(&self, alias: &Option<String>) -> Option<&SuiEnv> {
         if let Some(alias) = alias {
             self.envs.iter().find(|env| &env.alias == alias)
@@ -64,6 +99,59 @@ impl SuiClientConfig {
     }
 }
 
+/// An address argument of a command, given as an address or as an alias of the client config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddressOrAlias {
+    Address(SuiAddress),
+    Alias(String),
+}
+
+impl FromStr for AddressOrAlias {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(address) = SuiAddress::from_str(s) {
+            return Ok(AddressOrAlias::Address(address));
+        }
+        validate_alias(s).map_err(|e| anyhow!("Invalid address or alias [{s}]: {e}"))?;
+        Ok(AddressOrAlias::Alias(s.to_string()))
+    }
+}
+
+impl From<SuiAddress> for AddressOrAlias {
+    fn from(address: SuiAddress) -> Self {
+        AddressOrAlias::Address(address)
+    }
+}
+
+impl Display for AddressOrAlias {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AddressOrAlias::Address(address) => write!(f, "{address}"),
+            AddressOrAlias::Alias(alias) => write!(f, "{alias}"),
+        }
+    }
+}
+
+/// Aliases start with a letter, followed by letters, digits, `-` or `_`, so that they can't be
+/// mistaken for addresses.
+fn validate_alias(alias: &str) -> Result<(), anyhow::Error> {
+    let mut chars = alias.chars();
+    ensure!(
+        chars.next().map_or(false, |c| c.is_ascii_alphabetic()),
+        "Aliases must start with a letter"
+    );
+    ensure!(
+        chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+        "Aliases may only contain letters, digits, `-` and `_`"
+    );
+    ensure!(
+        SuiAddress::from_str(alias).is_err(),
+        "Alias [{alias}] is a valid address"
+    );
+    Ok(())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SuiEnv {
     pub alias: String,
@@ -1,18 +1,28 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::env;
 use std::io::{stderr, Write};
 use std::ops::Deref;
 
+use anyhow::anyhow;
 use async_trait::async_trait;
 use clap::Command;
 use clap::CommandFactory;
 use clap::FromArgMatches;
 use clap::Parser;
+use clap::Subcommand;
 use colored::Colorize;
+use fastcrypto::encoding::{Base64, Encoding};
+use sui_types::crypto::EncodeDecodeBase64;
+use sui_types::digests::TransactionDigest;
+use sui_types::messages::TransactionData;
 
 use crate::client_commands::SwitchResponse;
 use crate::client_commands::{SuiClientCommandResult, SuiClientCommands, WalletContext};
+use crate::completion::{address_values, owned_object_ids, ADDRESS_OPTIONS, OBJECT_OPTIONS};
+use crate::config::AddressOrAlias;
+use crate::output::{OutputFormat, PrintableResult};
 use crate::shell::{
     install_shell_plugins, AsyncHandler, CacheKey, CommandStructure, CompletionCache, Shell,
 };
@@ -23,22 +33,54 @@ const SUI: &str = "   _____       _    ______                       __
  ___/ / /_/ / /  / /___/ /_/ / / / (__  ) /_/ / /  __/
 /____/\\__,_/_/   \\____/\\____/_/ /_/____/\\____/_/\\___/";
 
+/// Variables set by the console for the next commands, which refer to them as `$TX_BYTES`, ...:
+/// the transaction data serialized by the last command serializing one, the signature of the
+/// last `sign` command and the digest of the last transaction executed.
+const TX_BYTES_VARIABLE: &str = "TX_BYTES";
+const SIGNATURE_VARIABLE: &str = "SIGNATURE";
+const DIGEST_VARIABLE: &str = "DIGEST";
+
+const HISTORY_FILE: &str = "console_history";
+
 #[derive(Parser)]
 #[clap(name = "", rename_all = "kebab-case", no_binary_name = true)]
 pub struct ConsoleOpts {
     #[clap(subcommand)]
-    pub command: SuiClientCommands,
-    /// Returns command outputs in JSON format.
+    pub command: ConsoleCommand,
+    /// Format of the command outputs (defaults to table).
+    #[clap(long, global = true, value_enum)]
+    pub output: Option<OutputFormat>,
+    /// Returns command outputs in JSON format, same as `--output json`.
     #[clap(long, global = true)]
     pub json: bool,
 }
 
+/// The commands of the client, and the signing of serialized transactions with its keystore, so
+/// that transactions can be serialized, checked, signed and executed in separate steps.
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum ConsoleCommand {
+    #[clap(flatten)]
+    Client(SuiClientCommands),
+    /// Sign serialized transaction data with the key of an address in the keystore, and print
+    /// the serialized signature `flag || signature || pubkey`, in Base64, for
+    /// `execute-signed-tx`.
+    Sign {
+        /// Address signing the transaction, or its alias
+        #[clap(long)]
+        address: AddressOrAlias,
+        /// BCS serialized transaction data bytes without its type tag, as base-64 encoded string.
+        #[clap(long)]
+        data: String,
+    },
+}
+
 pub async fn start_console(
-    context: WalletContext,
+    mut context: WalletContext,
     out: &mut (dyn Write + Send),
     err: &mut (dyn Write + Send),
 ) -> Result<(), anyhow::Error> {
-    let app: Command = SuiClientCommands::command();
+    let app: Command = ConsoleOpts::command();
     writeln!(out, "{}", SUI.cyan().bold())?;
     let mut version = env!("CARGO_PKG_VERSION").to_owned();
     if let Some(git_rev) = std::option_env!("GIT_REVISION") {
@@ -75,13 +117,28 @@ pub async fn start_console(
 
     writeln!(out)?;
     writeln!(out, "Welcome to the Sui interactive console.")?;
+    writeln!(
+        out,
+        "Commands set ${TX_BYTES_VARIABLE}, ${SIGNATURE_VARIABLE} and ${DIGEST_VARIABLE} to \
+         the transaction they serialize, sign or execute, for the next commands."
+    )?;
     writeln!(out)?;
 
+    let addresses = address_values(&context.config);
+    let objects = owned_object_ids(&mut context).await.unwrap_or_default();
+    let history_file = context.config.path().with_file_name(HISTORY_FILE);
     let mut shell = Shell::new(
         "sui>-$ ",
         context,
         ClientCommandHandler,
         CommandStructure::from_clap(&install_shell_plugins(app)),
+    )
+    .with_history_file(history_file);
+    let completion_cache = shell.completion_cache();
+    cache_addresses(&completion_cache, addresses);
+    cache_objects(
+        &completion_cache,
+        objects.iter().map(|id| id.to_string()).collect(),
     );
 
     shell.run_async(out, err).await
@@ -96,8 +153,9 @@ impl AsyncHandler<WalletContext> for ClientCommandHandler {
         args: Vec<String>,
         context: &mut WalletContext,
         completion_cache: CompletionCache,
+        out: &mut (dyn Write + Send),
     ) -> bool {
-        match handle_command(get_command(args), context, completion_cache).await {
+        match handle_command(get_command(args), context, completion_cache, out).await {
             Err(e) => {
                 let _err = writeln!(stderr(), "{}", e.to_string().red());
                 false
@@ -118,35 +176,60 @@ async fn handle_command(
     wallet_opts: Result<ConsoleOpts, anyhow::Error>,
     context: &mut WalletContext,
     completion_cache: CompletionCache,
+    out: &mut (dyn Write + Send),
 ) -> Result<bool, anyhow::Error> {
     let wallet_opts = wallet_opts?;
-    let result = wallet_opts.command.execute(context).await?;
+    let command = match wallet_opts.command {
+        ConsoleCommand::Client(command) => command,
+        ConsoleCommand::Sign { address, data } => {
+            let signature = sign(context, &address, &data)?;
+            env::set_var(SIGNATURE_VARIABLE, &signature);
+            writeln!(out, "{signature}")?;
+            return Ok(false);
+        }
+    };
+    let result = command.execute(context).await?;
+
+    match &result {
+        SuiClientCommandResult::SerializeTransferSui(tx_bytes)
+        | SuiClientCommandResult::SerializePublish(tx_bytes) => {
+            env::set_var(TX_BYTES_VARIABLE, tx_bytes)
+        }
+        _ => {}
+    }
+    if let Some(digest) = executed_digest(&result) {
+        env::set_var(DIGEST_VARIABLE, digest.to_string());
+    }
 
     // Update completion cache
     // TODO: Completion data are keyed by strings, are there ways to make it more error proof?
-    if let Ok(mut cache) = completion_cache.write() {
-        match result {
-            SuiClientCommandResult::Addresses(ref addresses, _) => {
-                let addresses = addresses
-                    .iter()
-                    .map(|addr| format!("{addr}"))
-                    .collect::<Vec<_>>();
-                cache.insert(CacheKey::flag("--address"), addresses.clone());
-                cache.insert(CacheKey::flag("--to"), addresses);
-            }
-            SuiClientCommandResult::Objects(ref objects) => {
-                let objects = objects
-                    .iter()
-                    .map(|oref| format!("{}", oref.clone().into_object().unwrap().object_id))
-                    .collect::<Vec<_>>();
-                cache.insert(CacheKey::new("object", "--id"), objects.clone());
-                cache.insert(CacheKey::flag("--gas"), objects.clone());
-                cache.insert(CacheKey::flag("--coin-object-id"), objects);
+    match &result {
+        SuiClientCommandResult::Addresses(..) | SuiClientCommandResult::NewAddress(_) => {
+            cache_addresses(&completion_cache, address_values(&context.config));
+        }
+        SuiClientCommandResult::Objects(objects) => {
+            let objects = objects
+                .iter()
+                .filter_map(|object| object.object_id().ok())
+                .map(|id| id.to_string())
+                .collect();
+            cache_objects(&completion_cache, objects);
+        }
+        // The objects owned by the active address change with the transactions it executes.
+        _ if executed_digest(&result).is_some() => {
+            if let Ok(objects) = owned_object_ids(context).await {
+                cache_objects(
+                    &completion_cache,
+                    objects.iter().map(|id| id.to_string()).collect(),
+                );
             }
-            _ => {}
         }
+        _ => {}
     }
-    result.print(!wallet_opts.json);
+    result.print(OutputFormat::from_flags(
+        wallet_opts.output,
+        wallet_opts.json,
+    ));
 
     // Quit shell after RPC switch
     if matches!(
@@ -158,3 +241,53 @@ async fn handle_command(
     }
     Ok(false)
 }
+
+fn sign(
+    context: &WalletContext,
+    address: &AddressOrAlias,
+    data: &str,
+) -> Result<String, anyhow::Error> {
+    let signer = context.config.resolve_address(address)?;
+    let data: TransactionData = bcs::from_bytes(
+        &Base64::decode(data)
+            .map_err(|e| anyhow!("Cannot deserialize data as TransactionData {:?}", e))?,
+    )?;
+    Ok(context.sign_transaction(&signer, &data)?.encode_base64())
+}
+
+/// The digest of the transaction executed by the command of `result`, if it executed one.
+fn executed_digest(result: &SuiClientCommandResult) -> Option<TransactionDigest> {
+    match result {
+        SuiClientCommandResult::Upgrade(response)
+        | SuiClientCommandResult::Publish(response)
+        | SuiClientCommandResult::RestrictUpgradeCap(response)
+        | SuiClientCommandResult::Call(response)
+        | SuiClientCommandResult::Ptb(response)
+        | SuiClientCommandResult::Transfer(_, response)
+        | SuiClientCommandResult::TransferSui(response)
+        | SuiClientCommandResult::Pay(response)
+        | SuiClientCommandResult::PaySui(response)
+        | SuiClientCommandResult::PayAllSui(response)
+        | SuiClientCommandResult::SplitCoin(response)
+        | SuiClientCommandResult::MergeCoin(response)
+        | SuiClientCommandResult::ExecuteSignedTx(response) => Some(response.digest),
+        _ => None,
+    }
+}
+
+fn cache_addresses(completion_cache: &CompletionCache, addresses: Vec<String>) {
+    if let Ok(mut cache) = completion_cache.write() {
+        for option in ADDRESS_OPTIONS {
+            cache.insert(CacheKey::flag(&format!("--{option}")), addresses.clone());
+        }
+    }
+}
+
+fn cache_objects(completion_cache: &CompletionCache, objects: Vec<String>) {
+    if let Ok(mut cache) = completion_cache.write() {
+        cache.insert(CacheKey::new("object", "--id"), objects.clone());
+        for option in OBJECT_OPTIONS {
+            cache.insert(CacheKey::flag(&format!("--{option}")), objects.clone());
+        }
+    }
+}
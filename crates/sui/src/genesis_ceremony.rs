@@ -184,7 +184,8 @@ pub fn run(cmd: Ceremony) -> Result<()> {
 
         CeremonyCommand::BuildUnsignedCheckpoint => {
             let mut builder = Builder::load(&dir)?;
-            let UnsignedGenesis { checkpoint, .. } = builder.build_unsigned_genesis_checkpoint();
+            let UnsignedGenesis { checkpoint, .. } =
+                builder.try_build_unsigned_genesis_checkpoint()?;
             println!(
                 "Successfully built unsigned checkpoint: {}",
                 checkpoint.digest()
@@ -233,7 +234,7 @@ pub fn run(cmd: Ceremony) -> Result<()> {
             let builder = Builder::load(&dir)?;
             check_protocol_version(&builder, protocol_version)?;
 
-            let genesis = builder.build();
+            let genesis = builder.try_build()?;
 
             genesis.save(dir.join(SUI_GENESIS_FILENAME))?;
 
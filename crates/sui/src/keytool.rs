@@ -6,10 +6,12 @@ use clap::*;
 use fastcrypto::encoding::{decode_bytes_hex, Base64, Encoding};
 use fastcrypto::hash::HashFunction;
 use fastcrypto::traits::KeyPair;
+use serde::Serialize;
 use shared_crypto::intent::{Intent, IntentMessage};
+use std::fmt::{self, Display, Formatter};
 use std::fs;
-use std::path::{Path, PathBuf};
-use sui_keys::key_derive::generate_new_key;
+use std::path::PathBuf;
+use sui_keys::key_derive::{generate_new_key, generate_new_keys};
 use sui_keys::keypair_file::{
     read_authority_keypair_from_file, read_keypair_from_file, write_authority_keypair_to_file,
     write_keypair_to_file,
@@ -22,6 +24,9 @@ use sui_types::messages::TransactionData;
 use sui_types::multisig::{MultiSig, MultiSigPublicKey, ThresholdUnit, WeightUnit};
 use sui_types::signature::GenericSignature;
 use tracing::info;
+
+use crate::output::PrintableResult;
+
 #[cfg(test)]
 #[path = "unit_tests/keytool_tests.rs"]
 mod keytool_tests;
@@ -39,10 +44,19 @@ pub enum KeyToolCommand {
     /// The keypair file is output to the current directory. The content of the file is
     /// a Base64 encoded string of 33-byte `flag || privkey`. Note: To generate and add keypair
     /// to sui.keystore, use `sui client new-address`), see more at [enum SuiClientCommands].
+    ///
+    /// With `--count N`, N keypairs are derived from the same mnemonic at consecutive indexes
+    /// of the last derivation path level, one file each, and a JSON manifest including the
+    /// mnemonic is printed. `--custom-derivation-path` accepts any BIP-32 path that is valid for
+    /// the key scheme instead of requiring the Sui purpose and coin type.
     Generate {
         key_scheme: SignatureScheme,
         word_length: Option<String>,
         derivation_path: Option<DerivationPath>,
+        #[clap(long)]
+        count: Option<u32>,
+        #[clap(long)]
+        custom_derivation_path: bool,
     },
     /// This reads the content at the provided file path. The accepted format can be
     /// [enum SuiKeyPair] (Base64 encoded of 33-byte `flag || privkey`) or `type AuthorityKeyPair`
@@ -76,10 +90,18 @@ pub enum KeyToolCommand {
     /// Add a new key to sui.key based on the input mnemonic phrase, the key scheme flag {ed25519 | secp256k1 | secp256r1}
     /// and an optional derivation path, default to m/44'/784'/0'/0'/0' for ed25519 or m/54'/784'/0'/0/0 for secp256k1
     /// or m/74'/784'/0'/0/0 for secp256r1. Supports mnemonic phrase of word length 12, 15, 18`, 21, 24.
+    ///
+    /// With `--count N`, N keys are imported at consecutive indexes of the last derivation path
+    /// level and a JSON manifest of the imported addresses is printed. `--custom-derivation-path`
+    /// accepts any BIP-32 path that is valid for the key scheme.
     Import {
         mnemonic_phrase: String,
         key_scheme: SignatureScheme,
         derivation_path: Option<DerivationPath>,
+        #[clap(long)]
+        count: Option<u32>,
+        #[clap(long)]
+        custom_derivation_path: bool,
     },
     /// This reads the content at the provided file path. The accepted format can be
     /// [enum SuiKeyPair] (Base64 encoded of 33-byte `flag || privkey`) or `type AuthorityKeyPair`
@@ -120,119 +142,173 @@ pub enum KeyToolCommand {
 }
 
 impl KeyToolCommand {
-    pub fn execute(self, keystore: &mut Keystore) -> Result<(), anyhow::Error> {
-        match self {
+    pub fn execute(self, keystore: &mut Keystore) -> Result<KeyToolCommandResult, anyhow::Error> {
+        Ok(match self {
             KeyToolCommand::Generate {
                 key_scheme,
                 derivation_path,
                 word_length,
+                count,
+                custom_derivation_path,
             } => {
                 if "bls12381" == key_scheme.to_string() {
                     // Generate BLS12381 key for authority without key derivation.
                     // The saved keypair is encoded `privkey || pubkey` without the scheme flag.
                     let (address, keypair) = get_authority_key_pair();
-                    let file_name = format!("bls-{address}.key");
-                    write_authority_keypair_to_file(&keypair, file_name)?;
+                    let file = format!("bls-{address}.key");
+                    write_authority_keypair_to_file(&keypair, &file)?;
+                    KeyToolCommandResult::Generate {
+                        file,
+                        key_scheme: SignatureScheme::BLS12381.to_string(),
+                    }
+                } else if count.is_some() || custom_derivation_path {
+                    let (keys, scheme, phrase) = generate_new_keys(
+                        key_scheme,
+                        derivation_path,
+                        word_length,
+                        count.unwrap_or(1),
+                        custom_derivation_path,
+                    )?;
+                    let mut entries = Vec::with_capacity(keys.len());
+                    for (address, kp, path) in keys {
+                        let file = format!("{address}.key");
+                        write_keypair_to_file(&kp, &file)?;
+                        entries.push(DerivedKeyEntry {
+                            address,
+                            derivation_path: path.to_string(),
+                            public_key: kp.public().encode_base64(),
+                            file: Some(file),
+                        });
+                    }
+                    KeyToolCommandResult::DerivedKeys(DerivedKeyManifest {
+                        key_scheme: scheme.to_string(),
+                        mnemonic: Some(phrase),
+                        keys: entries,
+                    })
                 } else {
                     let (address, kp, scheme, _) =
                         generate_new_key(key_scheme, derivation_path, word_length)?;
                     let file = format!("{address}.key");
                     write_keypair_to_file(&kp, &file)?;
-                    println!(
-                        "Keypair wrote to file path: {:?} with scheme: {:?}",
-                        file, scheme
-                    );
+                    KeyToolCommandResult::Generate {
+                        file,
+                        key_scheme: scheme.to_string(),
+                    }
                 }
             }
-            KeyToolCommand::Show { file } => {
-                let res = read_keypair_from_file(&file);
-                match res {
-                    Ok(keypair) => {
-                        println!("Public Key: {}", keypair.public().encode_base64());
-                        println!("Flag: {}", keypair.public().flag());
-                        if let PublicKey::Ed25519(public_key) = keypair.public() {
-                            let peer_id = anemo::PeerId(public_key.0.into());
-                            println!("PeerId: {}", peer_id);
-                        }
-                    }
-                    Err(_) => {
-                        let res = read_authority_keypair_from_file(&file);
-                        match res {
-                            Ok(keypair) => {
-                                println!("Public Key: {}", keypair.public().encode_base64());
-                                println!("Flag: {}", SignatureScheme::BLS12381);
-                            }
-                            Err(e) => {
-                                println!("Failed to read keypair at path {:?} err: {:?}", file, e)
-                            }
+            KeyToolCommand::Show { file } => match read_keypair_from_file(&file) {
+                Ok(keypair) => KeyToolCommandResult::Show {
+                    public_key: keypair.public().encode_base64(),
+                    flag: keypair.public().flag(),
+                    peer_id: match keypair.public() {
+                        PublicKey::Ed25519(public_key) => {
+                            Some(anemo::PeerId(public_key.0.into()).to_string())
                         }
+                        _ => None,
+                    },
+                },
+                Err(_) => {
+                    let keypair = read_authority_keypair_from_file(&file).map_err(|e| {
+                        anyhow!("Failed to read keypair at path {:?} err: {:?}", file, e)
+                    })?;
+                    KeyToolCommandResult::Show {
+                        public_key: keypair.public().encode_base64(),
+                        flag: SignatureScheme::BLS12381.flag(),
+                        peer_id: None,
                     }
                 }
-            }
+            },
 
             KeyToolCommand::Unpack { keypair } => {
-                store_and_print_keypair((&keypair.public()).into(), keypair)
-            }
-            KeyToolCommand::List => {
-                println!(
-                    " {0: ^42} | {1: ^45} | {2: ^6}",
-                    "Sui Address", "Public Key (Base64)", "Scheme"
-                );
-                println!("{}", ["-"; 100].join(""));
-                for pub_key in keystore.keys() {
-                    println!(
-                        " {0: ^42} | {1: ^45} | {2: ^6}",
-                        Into::<SuiAddress>::into(&pub_key),
-                        pub_key.encode_base64(),
-                        pub_key.scheme().to_string()
-                    );
-                }
+                let file = store_keypair((&keypair.public()).into(), keypair)?;
+                KeyToolCommandResult::Unpack { file }
             }
+            KeyToolCommand::List => KeyToolCommandResult::List(
+                keystore
+                    .keys()
+                    .into_iter()
+                    .map(|pub_key| KeyEntry {
+                        sui_address: (&pub_key).into(),
+                        public_key: pub_key.encode_base64(),
+                        scheme: pub_key.scheme().to_string(),
+                    })
+                    .collect(),
+            ),
             KeyToolCommand::Sign {
                 address,
                 data,
                 intent,
             } => {
-                println!("Signer address: {}", address);
-                println!("Raw tx_bytes to execute: {}", data);
                 let intent = intent.unwrap_or_else(Intent::sui_transaction);
-                println!("Intent: {:?}", intent);
                 let msg: TransactionData =
                     bcs::from_bytes(&Base64::decode(&data).map_err(|e| {
                         anyhow!("Cannot deserialize data as TransactionData {:?}", e)
                     })?)?;
-                let intent_msg = IntentMessage::new(intent, msg);
-                println!(
-                    "Raw intent message: {:?}",
-                    Base64::encode(bcs::to_bytes(&intent_msg)?)
-                );
+                let intent_msg = IntentMessage::new(intent.clone(), msg);
+                let raw_intent_msg = Base64::encode(bcs::to_bytes(&intent_msg)?);
                 let mut hasher = DefaultHash::default();
                 hasher.update(bcs::to_bytes(&intent_msg)?);
                 let digest = hasher.finalize().digest;
-                println!("Digest to sign: {:?}", Base64::encode(digest));
                 let sui_signature =
                     keystore.sign_secure(&address, &intent_msg.value, intent_msg.intent)?;
-                println!(
-                    "Serialized signature (`flag || sig || pk` in Base64): {:?}",
-                    sui_signature.encode_base64()
-                );
+                KeyToolCommandResult::Sign(SignData {
+                    sui_address: address,
+                    raw_tx_data: data,
+                    intent,
+                    raw_intent_msg,
+                    digest: Base64::encode(digest),
+                    sui_signature: sui_signature.encode_base64(),
+                })
             }
 
             KeyToolCommand::Import {
                 mnemonic_phrase,
                 key_scheme,
                 derivation_path,
+                count,
+                custom_derivation_path,
             } => {
-                let address =
-                    keystore.import_from_mnemonic(&mnemonic_phrase, key_scheme, derivation_path)?;
-                info!("Key imported for address [{address}]");
+                if count.is_none() && !custom_derivation_path {
+                    let address = keystore.import_from_mnemonic(
+                        &mnemonic_phrase,
+                        key_scheme,
+                        derivation_path,
+                    )?;
+                    info!("Key imported for address [{address}]");
+                    KeyToolCommandResult::Import { address }
+                } else {
+                    let scheme = key_scheme.to_string();
+                    let imported = keystore.import_batch_from_mnemonic(
+                        &mnemonic_phrase,
+                        key_scheme,
+                        derivation_path,
+                        count.unwrap_or(1),
+                        custom_derivation_path,
+                    )?;
+                    let mut entries = Vec::with_capacity(imported.len());
+                    for (address, path) in imported {
+                        info!("Key imported for address [{address}]");
+                        entries.push(DerivedKeyEntry {
+                            address,
+                            derivation_path: path.to_string(),
+                            public_key: keystore.get_key(&address)?.public().encode_base64(),
+                            file: None,
+                        });
+                    }
+                    KeyToolCommandResult::DerivedKeys(DerivedKeyManifest {
+                        key_scheme: scheme,
+                        mnemonic: None,
+                        keys: entries,
+                    })
+                }
             }
 
             KeyToolCommand::Base64PubKeyToAddress { base64_key } => {
                 let pk = PublicKey::decode_base64(&base64_key)
                     .map_err(|e| anyhow!("Invalid base64 key: {:?}", e))?;
-                let address = SuiAddress::from(&pk);
-                println!("Address {:?}", address);
+                KeyToolCommandResult::Address {
+                    address: SuiAddress::from(&pk),
+                }
             }
 
             KeyToolCommand::LoadKeypair { file } => {
@@ -240,20 +316,28 @@ impl KeyToolCommand {
                     Ok(keypair) => {
                         // Account keypair is encoded with the key scheme flag {},
                         // and network and worker keypair are not.
-                        println!("Account Keypair: {}", keypair.encode_base64());
-                        if let SuiKeyPair::Ed25519(kp) = keypair {
-                            println!("Network Keypair: {}", kp.encode_base64());
-                            println!("Worker Keypair: {}", kp.encode_base64());
+                        let network_keypair = match &keypair {
+                            SuiKeyPair::Ed25519(kp) => Some(kp.encode_base64()),
+                            _ => None,
                         };
+                        KeyToolCommandResult::LoadKeypair(KeypairData {
+                            account_keypair: Some(keypair.encode_base64()),
+                            worker_keypair: network_keypair.clone(),
+                            network_keypair,
+                            protocol_keypair: None,
+                        })
                     }
                     Err(_) => {
                         // Authority keypair file is not stored with the flag, it will try read as BLS keypair..
-                        match read_authority_keypair_from_file(&file) {
-                            Ok(kp) => println!("Protocol Keypair: {}", kp.encode_base64()),
-                            Err(e) => {
-                                println!("Failed to read keypair at path {:?} err: {:?}", file, e)
-                            }
-                        }
+                        let kp = read_authority_keypair_from_file(&file).map_err(|e| {
+                            anyhow!("Failed to read keypair at path {:?} err: {:?}", file, e)
+                        })?;
+                        KeyToolCommandResult::LoadKeypair(KeypairData {
+                            account_keypair: None,
+                            network_keypair: None,
+                            worker_keypair: None,
+                            protocol_keypair: Some(kp.encode_base64()),
+                        })
                     }
                 }
             }
@@ -264,21 +348,17 @@ impl KeyToolCommand {
             } => {
                 let multisig_pk = MultiSigPublicKey::new(pks.clone(), weights.clone(), threshold)?;
                 let address: SuiAddress = multisig_pk.into();
-                println!("MultiSig address: {address}");
-
-                println!("Participating parties:");
-                println!(
-                    " {0: ^42} | {1: ^50} | {2: ^6}",
-                    "Sui Address", "Public Key (Base64)", "Weight"
-                );
-                println!("{}", ["-"; 100].join(""));
-                for (pk, w) in pks.into_iter().zip(weights.into_iter()) {
-                    println!(
-                        " {0: ^42} | {1: ^45} | {2: ^6}",
-                        Into::<SuiAddress>::into(&pk),
-                        pk.encode_base64(),
-                        w
-                    );
+                KeyToolCommandResult::MultiSigAddress {
+                    multisig_address: address,
+                    participants: pks
+                        .into_iter()
+                        .zip(weights.into_iter())
+                        .map(|(pk, weight)| MultiSigParticipant {
+                            sui_address: (&pk).into(),
+                            public_key: pk.encode_base64(),
+                            weight,
+                        })
+                        .collect(),
                 }
             }
             KeyToolCommand::MultiSigCombinePartialSig {
@@ -291,26 +371,239 @@ impl KeyToolCommand {
                 let address: SuiAddress = multisig_pk.clone().into();
                 let multisig = MultiSig::combine(sigs, multisig_pk)?;
                 let generic_sig: GenericSignature = multisig.into();
-                println!("MultiSig address: {address}");
-                println!("MultiSig parsed: {:?}", generic_sig);
-                println!("MultiSig serialized: {:?}", generic_sig.encode_base64());
+                KeyToolCommandResult::MultiSigCombinePartialSig {
+                    multisig_address: address,
+                    multisig_parsed: format!("{:?}", generic_sig),
+                    multisig_serialized: generic_sig.encode_base64(),
+                }
+            }
+        })
+    }
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum KeyToolCommandResult {
+    #[serde(rename_all = "camelCase")]
+    Generate {
+        file: String,
+        key_scheme: String,
+    },
+    /// Keys derived from one mnemonic, by `generate` and `import` with `--count`.
+    DerivedKeys(DerivedKeyManifest),
+    #[serde(rename_all = "camelCase")]
+    Show {
+        public_key: String,
+        flag: u8,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        peer_id: Option<String>,
+    },
+    Unpack {
+        file: String,
+    },
+    List(Vec<KeyEntry>),
+    Sign(SignData),
+    Import {
+        address: SuiAddress,
+    },
+    Address {
+        address: SuiAddress,
+    },
+    LoadKeypair(KeypairData),
+    #[serde(rename_all = "camelCase")]
+    MultiSigAddress {
+        multisig_address: SuiAddress,
+        participants: Vec<MultiSigParticipant>,
+    },
+    #[serde(rename_all = "camelCase")]
+    MultiSigCombinePartialSig {
+        multisig_address: SuiAddress,
+        multisig_parsed: String,
+        multisig_serialized: String,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyEntry {
+    sui_address: SuiAddress,
+    public_key: String,
+    scheme: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignData {
+    sui_address: SuiAddress,
+    raw_tx_data: String,
+    intent: Intent,
+    raw_intent_msg: String,
+    digest: String,
+    sui_signature: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeypairData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account_keypair: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    network_keypair: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    worker_keypair: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    protocol_keypair: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigParticipant {
+    sui_address: SuiAddress,
+    public_key: String,
+    weight: WeightUnit,
+}
+
+/// JSON manifest printed when deriving a batch of keys from one mnemonic.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DerivedKeyManifest {
+    key_scheme: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mnemonic: Option<String>,
+    keys: Vec<DerivedKeyEntry>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DerivedKeyEntry {
+    address: SuiAddress,
+    derivation_path: String,
+    public_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+}
+
+impl Display for KeyToolCommandResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyToolCommandResult::Generate { file, key_scheme } => write!(
+                f,
+                "Keypair wrote to file path: {:?} with scheme: {}",
+                file, key_scheme
+            ),
+            KeyToolCommandResult::DerivedKeys(manifest) => write!(
+                f,
+                "{}",
+                serde_json::to_string_pretty(manifest).map_err(|_| fmt::Error)?
+            ),
+            KeyToolCommandResult::Show {
+                public_key,
+                flag,
+                peer_id,
+            } => {
+                writeln!(f, "Public Key: {}", public_key)?;
+                write!(f, "Flag: {}", flag)?;
+                if let Some(peer_id) = peer_id {
+                    write!(f, "\nPeerId: {}", peer_id)?;
+                }
+                Ok(())
+            }
+            KeyToolCommandResult::Unpack { file } => {
+                write!(f, "Address, keypair and key scheme written to {}", file)
+            }
+            KeyToolCommandResult::List(keys) => {
+                writeln!(
+                    f,
+                    " {0: ^42} | {1: ^45} | {2: ^6}",
+                    "Sui Address", "Public Key (Base64)", "Scheme"
+                )?;
+                write!(f, "{}", ["-"; 100].join(""))?;
+                for key in keys {
+                    write!(
+                        f,
+                        "\n {0: ^42} | {1: ^45} | {2: ^6}",
+                        key.sui_address, key.public_key, key.scheme
+                    )?;
+                }
+                Ok(())
+            }
+            KeyToolCommandResult::Sign(data) => {
+                writeln!(f, "Signer address: {}", data.sui_address)?;
+                writeln!(f, "Raw tx_bytes to execute: {}", data.raw_tx_data)?;
+                writeln!(f, "Intent: {:?}", data.intent)?;
+                writeln!(f, "Raw intent message: {:?}", data.raw_intent_msg)?;
+                writeln!(f, "Digest to sign: {:?}", data.digest)?;
+                write!(
+                    f,
+                    "Serialized signature (`flag || sig || pk` in Base64): {:?}",
+                    data.sui_signature
+                )
+            }
+            KeyToolCommandResult::Import { address } => {
+                write!(f, "Key imported for address [{address}]")
+            }
+            KeyToolCommandResult::Address { address } => write!(f, "Address {:?}", address),
+            KeyToolCommandResult::LoadKeypair(data) => {
+                let keypairs = [
+                    ("Account Keypair", &data.account_keypair),
+                    ("Network Keypair", &data.network_keypair),
+                    ("Worker Keypair", &data.worker_keypair),
+                    ("Protocol Keypair", &data.protocol_keypair),
+                ];
+                let lines: Vec<_> = keypairs
+                    .iter()
+                    .filter_map(|(name, kp)| kp.as_ref().map(|kp| format!("{name}: {kp}")))
+                    .collect();
+                write!(f, "{}", lines.join("\n"))
+            }
+            KeyToolCommandResult::MultiSigAddress {
+                multisig_address,
+                participants,
+            } => {
+                writeln!(f, "MultiSig address: {multisig_address}")?;
+                writeln!(f, "Participating parties:")?;
+                writeln!(
+                    f,
+                    " {0: ^42} | {1: ^50} | {2: ^6}",
+                    "Sui Address", "Public Key (Base64)", "Weight"
+                )?;
+                write!(f, "{}", ["-"; 100].join(""))?;
+                for participant in participants {
+                    write!(
+                        f,
+                        "\n {0: ^42} | {1: ^45} | {2: ^6}",
+                        participant.sui_address, participant.public_key, participant.weight
+                    )?;
+                }
+                Ok(())
+            }
+            KeyToolCommandResult::MultiSigCombinePartialSig {
+                multisig_address,
+                multisig_parsed,
+                multisig_serialized,
+            } => {
+                writeln!(f, "MultiSig address: {multisig_address}")?;
+                writeln!(f, "MultiSig parsed: {}", multisig_parsed)?;
+                write!(f, "MultiSig serialized: {:?}", multisig_serialized)
             }
         }
+    }
+}
 
-        Ok(())
+impl PrintableResult for KeyToolCommandResult {
+    fn structured(&self) -> Result<serde_json::Value, anyhow::Error> {
+        Ok(serde_json::to_value(self)?)
     }
 }
 
-fn store_and_print_keypair(address: SuiAddress, keypair: SuiKeyPair) {
-    let path_str = format!("{}.key", address).to_lowercase();
-    let path = Path::new(&path_str);
+/// Writes `keypair` to a file named after `address` in the current directory, and returns the
+/// path of the file.
+fn store_keypair(address: SuiAddress, keypair: SuiKeyPair) -> Result<String, anyhow::Error> {
+    let path = format!("{}.key", address).to_lowercase();
     let address = format!("{}", address);
     let kp = keypair.encode_base64();
     let flag = keypair.public().flag();
     let out_str = format!("address: {}\nkeypair: {}\nflag: {}", address, kp, flag);
-    fs::write(path, out_str).unwrap();
-    println!(
-        "Address, keypair and key scheme written to {}",
-        path.to_str().unwrap()
-    );
+    fs::write(&path, out_str)?;
+    Ok(path)
 }
@@ -2,14 +2,21 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod balance;
 pub mod client_commands;
+pub mod completion;
 pub mod config;
 pub mod console;
 pub mod fire_drill;
 pub mod keytool;
+pub mod localnet_admin;
+pub mod output;
+pub mod ptb;
 pub mod shell;
 pub mod sui_commands;
+pub mod tx_receipt;
 pub mod validator_commands;
+pub mod workspace_publish;
 
 pub mod genesis_ceremony;
 pub mod genesis_inspector;
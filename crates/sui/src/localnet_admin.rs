@@ -0,0 +1,282 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Admin server of the local networks started by `sui start --admin-port <port>`, which controls
+//! the epochs and the clock of the network, so that tests of epoch boundary and time based logic
+//! don't have to wait out real epoch durations.
+//!
+//! Example commands:
+//!
+//! Close the current epoch on every validator, and wait for the network to reach the next one:
+//!
+//!   $ curl -X POST 'http://127.0.0.1:9124/advance-epoch'
+//!
+//! Set the gas price quote of every validator to 2000 MIST, which becomes the reference gas price
+//! of the network from the next epoch:
+//!
+//!   $ curl -X POST 'http://127.0.0.1:9124/set-reference-gas-price?price=2000'
+//!
+//! Move the clock of the network forward by an hour, which the `Clock` object reflects from the
+//! next consensus commit:
+//!
+//!   $ curl -X POST 'http://127.0.0.1:9124/advance-clock?duration_ms=3600000'
+
+use anyhow::{anyhow, Context, Result};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    routing::post,
+    Router,
+};
+use fastcrypto::traits::KeyPair;
+use move_core_types::ident_str;
+use serde::Deserialize;
+use shared_crypto::intent::Intent;
+use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use sui_config::node::KeyPairWithPath;
+use sui_json_rpc_types::{
+    SuiExecutionStatus, SuiObjectDataOptions, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockResponseOptions,
+};
+use sui_node::SuiNodeHandle;
+use sui_sdk::SuiClientBuilder;
+use sui_swarm::memory::Swarm;
+use sui_types::base_types::SuiAddress;
+use sui_types::committee::EpochId;
+use sui_types::crypto::SuiKeyPair;
+use sui_types::messages::{
+    CallArg, ExecuteTransactionRequestType, GasData, ObjectArg, Transaction, TransactionData,
+    TransactionKind,
+};
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::sui_system_state::SuiSystemStateTrait;
+use sui_types::{SUI_SYSTEM_OBJ_CALL_ARG, SUI_SYSTEM_PACKAGE_ID};
+use tokio::time::timeout;
+use tracing::info;
+
+#[cfg(test)]
+#[path = "unit_tests/localnet_admin_tests.rs"]
+mod localnet_admin_tests;
+
+const ADVANCE_EPOCH_ROUTE: &str = "/advance-epoch";
+const SET_REFERENCE_GAS_PRICE_ROUTE: &str = "/set-reference-gas-price";
+const ADVANCE_CLOCK_ROUTE: &str = "/advance-clock";
+
+const EPOCH_CHANGE_TIMEOUT: Duration = Duration::from_secs(60);
+const GAS_BUDGET: u64 = 50_000_000;
+
+struct AppState {
+    validators: Vec<SuiNodeHandle>,
+    /// The node observing the epoch changes of the network, the fullnode if there is one.
+    observer: SuiNodeHandle,
+    fullnode_rpc_url: Option<String>,
+    /// Account keys of the validators, which own their operation caps.
+    validator_account_keys: BTreeMap<SuiAddress, KeyPairWithPath>,
+    /// Genesis account paying for the transactions of the validators, which have no gas coins.
+    sponsor: KeyPairWithPath,
+}
+
+pub fn start_localnet_admin_server(swarm: &Swarm, port: u16) -> Result<()> {
+    let app = router(swarm)?;
+    let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
+    info!(address =% socket_address, "starting localnet admin server");
+    tokio::spawn(async move {
+        axum::Server::bind(&socket_address)
+            .serve(app.into_make_service())
+            .await
+            .unwrap();
+    });
+    Ok(())
+}
+
+fn router(swarm: &Swarm) -> Result<Router> {
+    let validators = swarm.validator_node_handles();
+    let fullnode = swarm.fullnodes().next();
+    let observer = match fullnode {
+        Some(fullnode) => fullnode.get_node_handle(),
+        None => validators.first().cloned(),
+    }
+    .ok_or_else(|| anyhow!("The network has no running node"))?;
+    let validator_account_keys = swarm
+        .config()
+        .validator_configs()
+        .iter()
+        .map(|config| {
+            let key = config.account_key_pair.clone();
+            (SuiAddress::from(&key.keypair().public()), key)
+        })
+        .collect();
+    let sponsor = swarm
+        .config()
+        .account_keys
+        .first()
+        .ok_or_else(|| anyhow!("The network has no genesis account"))?;
+
+    let app_state = AppState {
+        validators,
+        observer,
+        fullnode_rpc_url: fullnode.map(|node| format!("http://{}", node.json_rpc_address())),
+        validator_account_keys,
+        sponsor: KeyPairWithPath::new(SuiKeyPair::Ed25519(sponsor.copy())),
+    };
+
+    Ok(Router::new()
+        .route(ADVANCE_EPOCH_ROUTE, post(advance_epoch))
+        .route(SET_REFERENCE_GAS_PRICE_ROUTE, post(set_reference_gas_price))
+        .route(ADVANCE_CLOCK_ROUTE, post(advance_clock))
+        .with_state(Arc::new(app_state)))
+}
+
+async fn advance_epoch(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    match state.advance_epoch().await {
+        Ok(epoch) => (StatusCode::OK, format!("Advanced to epoch {epoch}\n")),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+struct GasPrice {
+    price: u64,
+}
+
+async fn set_reference_gas_price(
+    State(state): State<Arc<AppState>>,
+    gas_price: Query<GasPrice>,
+) -> (StatusCode, String) {
+    let Query(GasPrice { price }) = gas_price;
+    match state.set_gas_price_quotes(price).await {
+        Ok(()) => (
+            StatusCode::OK,
+            format!("The reference gas price is {price} from the next epoch\n"),
+        ),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+struct ClockAdvance {
+    duration_ms: u64,
+}
+
+async fn advance_clock(clock_advance: Query<ClockAdvance>) -> (StatusCode, String) {
+    let Query(ClockAdvance { duration_ms }) = clock_advance;
+    let offset = narwhal_types::advance_clock(Duration::from_millis(duration_ms));
+    info!(
+        ?offset,
+        "Advanced the clock of the network by {duration_ms}ms"
+    );
+    (
+        StatusCode::OK,
+        format!(
+            "The clock of the network is {}ms ahead of the system time\n",
+            offset.as_millis()
+        ),
+    )
+}
+
+impl AppState {
+    async fn advance_epoch(&self) -> Result<EpochId> {
+        let mut epoch_rx = self.observer.with(|node| node.subscribe_to_epoch_change());
+        let epoch = self.observer.with(|node| node.current_epoch_for_testing());
+        for validator in &self.validators {
+            validator
+                .with_async(|node| async move { node.close_epoch_for_testing().await })
+                .await?;
+        }
+        timeout(EPOCH_CHANGE_TIMEOUT, async move {
+            loop {
+                let system_state = epoch_rx.recv().await?;
+                if system_state.epoch() > epoch {
+                    return Ok::<_, anyhow::Error>(system_state.epoch());
+                }
+            }
+        })
+        .await
+        .context("Timed out waiting for the next epoch")?
+    }
+
+    /// Sets the gas price quote of every active validator for the next epoch, which are all the
+    /// same so that the reference gas price of the next epoch is `price`.
+    async fn set_gas_price_quotes(&self, price: u64) -> Result<()> {
+        let rpc_url = self.fullnode_rpc_url.as_ref().ok_or_else(|| {
+            anyhow!("Setting the reference gas price requires a network with a fullnode")
+        })?;
+        let client = SuiClientBuilder::default().build(rpc_url).await?;
+        let system_state = client
+            .governance_api()
+            .get_latest_sui_system_state()
+            .await?;
+        let sponsor = SuiAddress::from(&self.sponsor.keypair().public());
+
+        for validator in &system_state.active_validators {
+            let account_key = self
+                .validator_account_keys
+                .get(&validator.sui_address)
+                .ok_or_else(|| anyhow!("Unknown validator {}", validator.sui_address))?;
+            let operation_cap = client
+                .read_api()
+                .get_object_with_options(validator.operation_cap_id, SuiObjectDataOptions::new())
+                .await?
+                .object_ref_if_exists()
+                .ok_or_else(|| anyhow!("Missing operation cap of {}", validator.sui_address))?;
+            // Refreshed for every transaction, as each one changes the version of the coin.
+            let gas = crate::fire_drill::get_gas_obj_ref(sponsor, &client, GAS_BUDGET).await?;
+
+            let pt = {
+                let mut builder = ProgrammableTransactionBuilder::new();
+                builder.move_call(
+                    SUI_SYSTEM_PACKAGE_ID,
+                    ident_str!("sui_system").to_owned(),
+                    ident_str!("request_set_gas_price").to_owned(),
+                    vec![],
+                    vec![
+                        SUI_SYSTEM_OBJ_CALL_ARG,
+                        CallArg::Object(ObjectArg::ImmOrOwnedObject(operation_cap)),
+                        CallArg::Pure(bcs::to_bytes(&price)?),
+                    ],
+                )?;
+                builder.finish()
+            };
+            let tx_data = TransactionData::new_with_gas_data(
+                TransactionKind::ProgrammableTransaction(pt),
+                validator.sui_address,
+                GasData {
+                    payment: vec![gas],
+                    owner: sponsor,
+                    price: system_state.reference_gas_price,
+                    budget: GAS_BUDGET,
+                },
+            );
+            let tx = Transaction::from_data_and_signer(
+                tx_data,
+                Intent::sui_transaction(),
+                vec![account_key.keypair(), self.sponsor.keypair()],
+            )
+            .verify()?;
+            let response = client
+                .quorum_driver()
+                .execute_transaction_block(
+                    tx,
+                    SuiTransactionBlockResponseOptions::new().with_effects(),
+                    Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+                )
+                .await?;
+            let status = response
+                .effects
+                .ok_or_else(|| anyhow!("Missing effects"))?
+                .status()
+                .clone();
+            if status != SuiExecutionStatus::Success {
+                return Err(anyhow!(
+                    "Failed to set the gas price of {}: {status:?}",
+                    validator.sui_address
+                ));
+            }
+        }
+        info!("Set the gas price quote of every validator to {price}");
+        Ok(())
+    }
+}
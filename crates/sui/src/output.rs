@@ -0,0 +1,67 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Output of the commands of the CLI. Commands return a response struct, which is printed as
+//! human-readable text (tables, for most commands), or as JSON or YAML for scripts.
+
+use std::fmt::Display;
+
+use clap::ValueEnum;
+use colored::Colorize;
+use serde_json::Value;
+use tracing::info;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Json,
+    Yaml,
+    Table,
+}
+
+impl OutputFormat {
+    /// The format selected by the `--output` and `--json` flags of a command, which prints
+    /// tables by default.
+    pub fn from_flags(output: Option<OutputFormat>, json: bool) -> Self {
+        match output {
+            Some(output) => output,
+            None if json => OutputFormat::Json,
+            None => OutputFormat::Table,
+        }
+    }
+}
+
+/// The response of a command, printed in any [OutputFormat].
+pub trait PrintableResult: Display {
+    /// The response as a structured value, printed for the JSON and YAML formats.
+    fn structured(&self) -> Result<Value, anyhow::Error>;
+
+    /// Responses which have nothing to print.
+    fn is_empty(&self) -> bool {
+        false
+    }
+
+    fn render(&self, format: OutputFormat) -> String {
+        let rendered = match format {
+            OutputFormat::Table => Ok(format!("{self}")),
+            OutputFormat::Json => self
+                .structured()
+                .and_then(|value| Ok(serde_json::to_string_pretty(&value)?)),
+            OutputFormat::Yaml => self
+                .structured()
+                .and_then(|value| Ok(serde_yaml::to_string(&value)?)),
+        };
+        rendered.unwrap_or_else(|err| format!("{err}").red().to_string())
+    }
+
+    fn print(&self, format: OutputFormat) {
+        if self.is_empty() {
+            return;
+        }
+        // Log line by line
+        for line in self.render(format).lines() {
+            // Logs write to a file on the side.  Print to stdout and also log to file, for tests to pass.
+            println!("{line}");
+            info!("{line}")
+        }
+    }
+}
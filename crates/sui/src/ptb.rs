@@ -0,0 +1,507 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Plans of programmable transactions, built by `sui client ptb`.
+//!
+//! A plan is a YAML (or JSON) file listing the commands of the transaction, whose results can be
+//! bound to names and used by the following commands:
+//!
+//! ```yaml
+//! vars:
+//!   recipient: "0x..."
+//!   amount: 1000
+//! commands:
+//!   - split_coins:
+//!       coin: $gas
+//!       amounts: ["${amount}", "${amount}"]
+//!     result: coins
+//!   - repeat:
+//!       times: 2
+//!       var: i
+//!       commands:
+//!         - transfer_objects:
+//!             objects: ["$coins.${i}"]
+//!             address: "${recipient}"
+//! ```
+//!
+//! In strings, `${name}` is replaced by the value of the variable `name`, which is kept as is
+//! (e.g. a number) when it is the whole string. Then, a string starting with `$` refers to an
+//! argument of the transaction: `$gas` is the gas coin, `$name` the result bound to `name`, and
+//! `$name.N` the N-th result of a command returning several values. `$$` escapes a leading `$`.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, ensure};
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use sui_json::SuiJsonValue;
+use sui_json_rpc_types::SuiTypeTag;
+use sui_sdk::SuiClient;
+use sui_transaction_builder::PTBArg;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::messages::{Argument, Command, ProgrammableTransaction};
+use sui_types::parse_sui_type_tag;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+
+use crate::client_commands::convert_number_to_string;
+
+#[cfg(test)]
+#[path = "unit_tests/ptb_tests.rs"]
+mod ptb_tests;
+
+/// Maximum number of commands of a plan, once its loops are unrolled.
+pub const MAX_PLAN_COMMANDS: usize = 1024;
+
+#[derive(Debug, Deserialize)]
+pub struct PtbPlan {
+    /// Default values of the variables, which can be overridden from the command line.
+    #[serde(default)]
+    pub vars: BTreeMap<String, JsonValue>,
+    pub commands: Vec<PlanCommand>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PlanCommand {
+    #[serde(flatten)]
+    pub kind: PlanCommandKind,
+    /// Name bound to the result of the command.
+    #[serde(default)]
+    pub result: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanCommandKind {
+    MoveCall {
+        package: String,
+        module: String,
+        function: String,
+        #[serde(default)]
+        type_args: Vec<String>,
+        #[serde(default)]
+        args: Vec<JsonValue>,
+    },
+    TransferObjects {
+        objects: Vec<JsonValue>,
+        address: JsonValue,
+    },
+    SplitCoins {
+        coin: JsonValue,
+        amounts: Vec<JsonValue>,
+    },
+    MergeCoins {
+        coin: JsonValue,
+        coins: Vec<JsonValue>,
+    },
+    MakeMoveVec {
+        #[serde(default, rename = "type")]
+        type_: Option<String>,
+        elements: Vec<JsonValue>,
+    },
+    /// Repeats `commands` `times` times, with the variable `var` set to the iteration, from 0.
+    /// `times` is at least 1 and at most [MAX_PLAN_COMMANDS], and `commands` is not empty, so that
+    /// every iteration adds commands to the plan.
+    Repeat {
+        times: u64,
+        #[serde(default)]
+        var: Option<String>,
+        commands: Vec<PlanCommand>,
+    },
+}
+
+/// A value of a command, once the variables are substituted.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlanValue {
+    Gas,
+    Result { name: String, index: Option<u16> },
+    Value(JsonValue),
+}
+
+/// A command of a plan, once the variables are substituted and the loops unrolled.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpandedCommand {
+    MoveCall {
+        package: ObjectID,
+        module: String,
+        function: String,
+        type_args: Vec<SuiTypeTag>,
+        args: Vec<PlanValue>,
+    },
+    TransferObjects {
+        objects: Vec<PlanValue>,
+        address: PlanValue,
+    },
+    SplitCoins {
+        coin: PlanValue,
+        amounts: Vec<PlanValue>,
+    },
+    MergeCoins {
+        coin: PlanValue,
+        coins: Vec<PlanValue>,
+    },
+    MakeMoveVec {
+        type_: Option<SuiTypeTag>,
+        elements: Vec<PlanValue>,
+    },
+}
+
+impl PtbPlan {
+    pub fn from_yaml(content: &str) -> Result<Self, anyhow::Error> {
+        // JSON is a subset of YAML.
+        serde_yaml::from_str(content).map_err(|e| anyhow!("Invalid plan: {e}"))
+    }
+
+    /// Substitutes the variables, `overrides` taking precedence over the variables of the plan,
+    /// and unrolls the loops. Returns the commands with the names of their results.
+    pub fn expand(
+        &self,
+        overrides: &[(String, JsonValue)],
+    ) -> Result<Vec<(ExpandedCommand, Option<String>)>, anyhow::Error> {
+        let mut vars = self.vars.clone();
+        vars.extend(overrides.iter().cloned());
+        let mut expanded = vec![];
+        expand_commands(&self.commands, &mut vars, &mut expanded)?;
+        ensure!(!expanded.is_empty(), "The plan has no commands");
+        Ok(expanded)
+    }
+}
+
+fn expand_commands(
+    commands: &[PlanCommand],
+    vars: &mut BTreeMap<String, JsonValue>,
+    expanded: &mut Vec<(ExpandedCommand, Option<String>)>,
+) -> Result<(), anyhow::Error> {
+    for command in commands {
+        let result = command
+            .result
+            .as_deref()
+            .map(|name| substitute_str(name, vars))
+            .transpose()?;
+        let values = |values: &[JsonValue], vars: &BTreeMap<String, JsonValue>| {
+            values
+                .iter()
+                .map(|value| plan_value(value, vars))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let expanded_command = match &command.kind {
+            PlanCommandKind::Repeat {
+                times,
+                var,
+                commands,
+            } => {
+                ensure!(result.is_none(), "A repeat has no result to bind");
+                ensure!(!commands.is_empty(), "A repeat has no commands");
+                ensure!(
+                    (1..=MAX_PLAN_COMMANDS as u64).contains(times),
+                    "A repeat must run between 1 and {MAX_PLAN_COMMANDS} times, not {times}"
+                );
+                for i in 0..*times {
+                    if let Some(var) = var {
+                        vars.insert(var.clone(), JsonValue::from(i));
+                    }
+                    expand_commands(commands, vars, expanded)?;
+                }
+                if let Some(var) = var {
+                    vars.remove(var);
+                }
+                continue;
+            }
+            PlanCommandKind::MoveCall {
+                package,
+                module,
+                function,
+                type_args,
+                args,
+            } => ExpandedCommand::MoveCall {
+                package: ObjectID::from_hex_literal(&substitute_str(package, vars)?)?,
+                module: substitute_str(module, vars)?,
+                function: substitute_str(function, vars)?,
+                type_args: type_args
+                    .iter()
+                    .map(|ty| parse_type_tag(&substitute_str(ty, vars)?))
+                    .collect::<Result<_, _>>()?,
+                args: values(args, vars)?,
+            },
+            PlanCommandKind::TransferObjects { objects, address } => {
+                ExpandedCommand::TransferObjects {
+                    objects: values(objects, vars)?,
+                    address: plan_value(address, vars)?,
+                }
+            }
+            PlanCommandKind::SplitCoins { coin, amounts } => ExpandedCommand::SplitCoins {
+                coin: plan_value(coin, vars)?,
+                amounts: values(amounts, vars)?,
+            },
+            PlanCommandKind::MergeCoins { coin, coins } => ExpandedCommand::MergeCoins {
+                coin: plan_value(coin, vars)?,
+                coins: values(coins, vars)?,
+            },
+            PlanCommandKind::MakeMoveVec { type_, elements } => ExpandedCommand::MakeMoveVec {
+                type_: type_
+                    .as_deref()
+                    .map(|ty| parse_type_tag(&substitute_str(ty, vars)?))
+                    .transpose()?,
+                elements: values(elements, vars)?,
+            },
+        };
+        ensure!(
+            expanded.len() < MAX_PLAN_COMMANDS,
+            "The plan has more than {MAX_PLAN_COMMANDS} commands"
+        );
+        expanded.push((expanded_command, result));
+    }
+    Ok(())
+}
+
+fn parse_type_tag(s: &str) -> Result<SuiTypeTag, anyhow::Error> {
+    Ok(SuiTypeTag::from(parse_sui_type_tag(s)?))
+}
+
+/// Substitutes the variables of `s`, which must result in a string.
+fn substitute_str(s: &str, vars: &BTreeMap<String, JsonValue>) -> Result<String, anyhow::Error> {
+    match substitute(s, vars)? {
+        JsonValue::String(s) => Ok(s),
+        value => Ok(value.to_string()),
+    }
+}
+
+/// Substitutes the variables of `s`. A string which is a single variable takes the value of the
+/// variable, so that variables can hold numbers or arrays.
+fn substitute(s: &str, vars: &BTreeMap<String, JsonValue>) -> Result<JsonValue, anyhow::Error> {
+    let lookup = |name: &str| {
+        vars.get(name)
+            .ok_or_else(|| anyhow!("Undefined variable `{name}`"))
+    };
+    if let Some(name) = s.strip_prefix("${").and_then(|s| s.strip_suffix('}')) {
+        if !name.contains(['$', '{', '}']) {
+            return Ok(lookup(name)?.clone());
+        }
+    }
+
+    let mut result = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| anyhow!("Unterminated variable in `{s}`"))?;
+        match lookup(&rest[start + 2..start + end])? {
+            JsonValue::String(value) => result.push_str(value),
+            value => result.push_str(&value.to_string()),
+        }
+        rest = &rest[start + end + 1..];
+    }
+    result.push_str(rest);
+    Ok(JsonValue::String(result))
+}
+
+/// Substitutes the variables of `value`, and parses the references to arguments of the
+/// transaction.
+fn plan_value(
+    value: &JsonValue,
+    vars: &BTreeMap<String, JsonValue>,
+) -> Result<PlanValue, anyhow::Error> {
+    Ok(match substitute_json(value, vars)? {
+        JsonValue::String(s) if s.starts_with("$$") => {
+            PlanValue::Value(JsonValue::String(s[1..].to_string()))
+        }
+        JsonValue::String(s) if s == "$gas" => PlanValue::Gas,
+        JsonValue::String(s) if s.starts_with('$') => {
+            let (name, index) = match s[1..].split_once('.') {
+                Some((name, index)) => (
+                    name,
+                    Some(
+                        index
+                            .parse()
+                            .map_err(|_| anyhow!("Invalid result index in `{s}`"))?,
+                    ),
+                ),
+                None => (&s[1..], None),
+            };
+            ensure!(!name.is_empty(), "Missing result name in `{s}`");
+            PlanValue::Result {
+                name: name.to_string(),
+                index,
+            }
+        }
+        value => PlanValue::Value(value),
+    })
+}
+
+fn substitute_json(
+    value: &JsonValue,
+    vars: &BTreeMap<String, JsonValue>,
+) -> Result<JsonValue, anyhow::Error> {
+    Ok(match value {
+        JsonValue::String(s) => substitute(s, vars)?,
+        JsonValue::Array(values) => JsonValue::Array(
+            values
+                .iter()
+                .map(|value| substitute_json(value, vars))
+                .collect::<Result<_, _>>()?,
+        ),
+        JsonValue::Object(fields) => JsonValue::Object(
+            fields
+                .iter()
+                .map(|(name, value)| Ok((name.clone(), substitute_json(value, vars)?)))
+                .collect::<Result<_, anyhow::Error>>()?,
+        ),
+        value => value.clone(),
+    })
+}
+
+/// Builds the programmable transaction of the commands of a plan.
+pub async fn build_programmable_transaction(
+    client: &SuiClient,
+    commands: Vec<(ExpandedCommand, Option<String>)>,
+) -> Result<ProgrammableTransaction, anyhow::Error> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let mut results: BTreeMap<String, Argument> = BTreeMap::new();
+    for (command, result) in commands {
+        let arg = match command {
+            ExpandedCommand::MoveCall {
+                package,
+                module,
+                function,
+                type_args,
+                args,
+            } => {
+                let args = args
+                    .into_iter()
+                    .map(|value| {
+                        Ok(match value {
+                            // Numbers are passed as strings, as they are from the command line.
+                            PlanValue::Value(value) => {
+                                PTBArg::Json(SuiJsonValue::new(convert_number_to_string(value))?)
+                            }
+                            value => PTBArg::Argument(resolve_reference(&results, &value)?),
+                        })
+                    })
+                    .collect::<Result<Vec<_>, anyhow::Error>>()?;
+                client
+                    .transaction_builder()
+                    .programmable_move_call(
+                        &mut builder,
+                        package,
+                        &module,
+                        &function,
+                        type_args,
+                        args,
+                    )
+                    .await?
+            }
+            ExpandedCommand::TransferObjects { objects, address } => {
+                let objects = object_args(client, &mut builder, &results, objects).await?;
+                let address = match address {
+                    PlanValue::Value(value) => builder.pure(json_to_address(&value)?)?,
+                    value => resolve_reference(&results, &value)?,
+                };
+                builder.command(Command::TransferObjects(objects, address))
+            }
+            ExpandedCommand::SplitCoins { coin, amounts } => {
+                let coin = object_arg(client, &mut builder, &results, coin).await?;
+                let amounts = amounts
+                    .into_iter()
+                    .map(|amount| match amount {
+                        PlanValue::Value(value) => builder.pure(json_to_u64(&value)?),
+                        value => resolve_reference(&results, &value),
+                    })
+                    .collect::<Result<_, _>>()?;
+                builder.command(Command::SplitCoins(coin, amounts))
+            }
+            ExpandedCommand::MergeCoins { coin, coins } => {
+                let coin = object_arg(client, &mut builder, &results, coin).await?;
+                let coins = object_args(client, &mut builder, &results, coins).await?;
+                builder.command(Command::MergeCoins(coin, coins))
+            }
+            ExpandedCommand::MakeMoveVec { type_, elements } => {
+                let type_ = type_.map(|ty| ty.try_into()).transpose()?;
+                let elements = object_args(client, &mut builder, &results, elements).await?;
+                builder.command(Command::MakeMoveVec(type_, elements))
+            }
+        };
+        if let Some(name) = result {
+            results.insert(name, arg);
+        }
+    }
+    Ok(builder.finish())
+}
+
+fn resolve_reference(
+    results: &BTreeMap<String, Argument>,
+    value: &PlanValue,
+) -> Result<Argument, anyhow::Error> {
+    match value {
+        PlanValue::Gas => Ok(Argument::GasCoin),
+        PlanValue::Result { name, index } => {
+            let arg = results
+                .get(name)
+                .ok_or_else(|| anyhow!("No result named `{name}` before its use"))?;
+            match (arg, index) {
+                (arg, None) => Ok(*arg),
+                (Argument::Result(i), Some(j)) => Ok(Argument::NestedResult(*i, *j)),
+                _ => bail!("`{name}` is not the result of a command"),
+            }
+        }
+        PlanValue::Value(value) => bail!("{value} is not a reference to an argument"),
+    }
+}
+
+async fn object_arg(
+    client: &SuiClient,
+    builder: &mut ProgrammableTransactionBuilder,
+    results: &BTreeMap<String, Argument>,
+    value: PlanValue,
+) -> Result<Argument, anyhow::Error> {
+    match value {
+        PlanValue::Value(JsonValue::String(id)) => {
+            client
+                .transaction_builder()
+                .input_object(builder, ObjectID::from_hex_literal(&id)?)
+                .await
+        }
+        PlanValue::Value(value) => bail!("Expected an object ID, found {value}"),
+        value => resolve_reference(results, &value),
+    }
+}
+
+async fn object_args(
+    client: &SuiClient,
+    builder: &mut ProgrammableTransactionBuilder,
+    results: &BTreeMap<String, Argument>,
+    values: Vec<PlanValue>,
+) -> Result<Vec<Argument>, anyhow::Error> {
+    let mut args = vec![];
+    for value in values {
+        args.push(object_arg(client, builder, results, value).await?);
+    }
+    Ok(args)
+}
+
+fn json_to_address(value: &JsonValue) -> Result<SuiAddress, anyhow::Error> {
+    match value {
+        JsonValue::String(s) => Ok(SuiAddress::from_str(s)?),
+        value => bail!("Expected an address, found {value}"),
+    }
+}
+
+fn json_to_u64(value: &JsonValue) -> Result<u64, anyhow::Error> {
+    match value {
+        JsonValue::Number(n) => n
+            .as_u64()
+            .ok_or_else(|| anyhow!("Expected a u64, found {n}")),
+        JsonValue::String(s) => Ok(s.parse()?),
+        value => bail!("Expected a u64, found {value}"),
+    }
+}
+
+/// Parses a variable set on the command line as `name=value`. The value is a JSON value if it
+/// parses as one, a string otherwise.
+pub fn parse_var(s: &str) -> Result<(String, JsonValue), anyhow::Error> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| anyhow!("Expected `name=value`, found `{s}`"))?;
+    let value = serde_json::from_str(value).unwrap_or_else(|_| JsonValue::from(value));
+    Ok((name.to_string(), value))
+}
@@ -9,6 +9,7 @@ use std::collections::BTreeMap;
 use std::env;
 use std::fmt::Display;
 use std::io::Write;
+use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 
 use async_trait::async_trait;
@@ -33,6 +34,8 @@ pub struct Shell<P: Display, S, H> {
     state: S,
     handler: H,
     command: CommandStructure,
+    completion_cache: CompletionCache,
+    history_file: Option<PathBuf>,
 }
 
 impl<P: Display, S: Send, H: AsyncHandler<S>> Shell<P, S, H> {
@@ -51,9 +54,22 @@ impl<P: Display, S: Send, H: AsyncHandler<S>> Shell<P, S, H> {
             state,
             handler,
             command,
+            completion_cache: Default::default(),
+            history_file: None,
         }
     }
 
+    /// Loads the history of the shell from `path` when it starts, and saves it there when it exits.
+    pub fn with_history_file(mut self, path: PathBuf) -> Self {
+        self.history_file = Some(path);
+        self
+    }
+
+    /// The completion cache of the shell, to fill before it runs.
+    pub fn completion_cache(&self) -> CompletionCache {
+        self.completion_cache.clone()
+    }
+
     pub async fn run_async(
         &mut self,
         out: &mut (dyn Write + Send),
@@ -66,8 +82,12 @@ impl<P: Display, S: Send, H: AsyncHandler<S>> Shell<P, S, H> {
             .build();
 
         let mut rl = Editor::with_config(config);
+        if let Some(history_file) = &self.history_file {
+            // The file is missing the first time the shell runs.
+            let _ = rl.load_history(history_file);
+        }
 
-        let completion_cache = Arc::new(RwLock::new(BTreeMap::new()));
+        let completion_cache = self.completion_cache.clone();
 
         rl.set_helper(Some(ShellHelper {
             command: self.command.clone(),
@@ -126,7 +146,7 @@ impl<P: Display, S: Send, H: AsyncHandler<S>> Shell<P, S, H> {
 
                     if self
                         .handler
-                        .handle_async(line, &mut self.state, completion_cache.clone())
+                        .handle_async(line, &mut self.state, completion_cache.clone(), out)
                         .await
                     {
                         break;
@@ -135,6 +155,11 @@ impl<P: Display, S: Send, H: AsyncHandler<S>> Shell<P, S, H> {
                 Err(e) => writeln!(err, "{}", e.to_string().red())?,
             }
         }
+        if let Some(history_file) = &self.history_file {
+            if let Err(e) = rl.save_history(history_file) {
+                writeln!(err, "Cannot save history to {history_file:?}: {e}")?;
+            }
+        }
         Ok(())
     }
 }
@@ -319,6 +344,7 @@ pub trait AsyncHandler<T: Send> {
         args: Vec<String>,
         state: &mut T,
         completion_cache: CompletionCache,
+        out: &mut (dyn Write + Send),
     ) -> bool;
 }
 
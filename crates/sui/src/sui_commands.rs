@@ -8,10 +8,17 @@ use std::{fs, io};
 
 use anyhow::{anyhow, bail};
 use clap::*;
+use clap_complete::Shell;
 use fastcrypto::traits::KeyPair;
+use move_binary_format::CompiledModule;
 use move_package::BuildConfig;
 use sui_config::genesis_config::DEFAULT_NUMBER_OF_AUTHORITIES;
-use sui_framework_build::compiled_package::SuiPackageHooks;
+use sui_framework::build_move_package;
+use sui_framework_build::compiled_package::{BuildConfig as SuiBuildConfig, SuiPackageHooks};
+use sui_json_rpc_types::{SuiObjectDataOptions, SuiRawData};
+use sui_move::build::resolve_lock_file_path;
+use sui_move::disassemble::Disassemble;
+use sui_source_validation::BytecodeSourceVerifier;
 use tracing::info;
 
 use sui_config::{
@@ -28,11 +35,14 @@ use sui_swarm::memory::Swarm;
 use sui_types::crypto::{SignatureScheme, SuiKeyPair};
 
 use crate::client_commands::{SuiClientCommands, WalletContext};
+use crate::completion::{write_completion_script, write_completion_values, CompletionValues};
 use crate::config::{SuiClientConfig, SuiEnv};
 use crate::console::start_console;
 use crate::fire_drill::{run_fire_drill, FireDrill};
 use crate::genesis_ceremony::{run, Ceremony};
 use crate::keytool::KeyToolCommand;
+use crate::localnet_admin::start_localnet_admin_server;
+use crate::output::{OutputFormat, PrintableResult};
 use crate::validator_commands::SuiValidatorCommand;
 use sui_move::{self, execute_move_command};
 
@@ -47,6 +57,10 @@ pub enum SuiCommand {
         config: Option<PathBuf>,
         #[clap(long = "no-full-node")]
         no_full_node: bool,
+        /// Serve the admin endpoints advancing the epoch, setting the reference gas price and
+        /// moving the clock of the network on this port of localhost.
+        #[clap(long = "admin-port")]
+        admin_port: Option<u16>,
     },
     #[clap(name = "network")]
     Network {
@@ -90,6 +104,12 @@ pub enum SuiCommand {
         /// Subcommands.
         #[clap(subcommand)]
         cmd: KeyToolCommand,
+        /// Format of the command outputs (defaults to table).
+        #[clap(long, global = true, value_enum)]
+        output: Option<OutputFormat>,
+        /// Return command outputs in json format, same as `--output json`.
+        #[clap(long, global = true)]
+        json: bool,
     },
     /// Start Sui interactive console.
     #[clap(name = "console")]
@@ -98,6 +118,26 @@ pub enum SuiCommand {
         #[clap(long = "client.config")]
         config: Option<PathBuf>,
     },
+    /// Generate the completion script of the CLI for a shell. With bash, zsh and fish, options
+    /// taking addresses also complete with the aliases and addresses of the client config, and
+    /// options taking object IDs with the objects owned by the active address.
+    ///
+    /// For example, add `source <(sui completion bash)` to `~/.bashrc`, or run
+    /// `sui completion fish | source` from `~/.config/fish/config.fish`.
+    #[clap(name = "completion")]
+    Completion {
+        #[clap(value_enum)]
+        shell: Shell,
+    },
+    /// Print the values completing the options of the CLI, one per line, for its completion
+    /// scripts.
+    #[clap(name = "completion-values", hide = true)]
+    CompletionValues {
+        #[clap(long = "client.config")]
+        config: Option<PathBuf>,
+        #[clap(value_enum)]
+        values: CompletionValues,
+    },
     /// Client for interacting with the Sui network.
     #[clap(name = "client")]
     Client {
@@ -106,7 +146,10 @@ pub enum SuiCommand {
         config: Option<PathBuf>,
         #[clap(subcommand)]
         cmd: Option<SuiClientCommands>,
-        /// Return command outputs in json format.
+        /// Format of the command outputs (defaults to table).
+        #[clap(long, global = true, value_enum)]
+        output: Option<OutputFormat>,
+        /// Return command outputs in json format, same as `--output json`.
         #[clap(long, global = true)]
         json: bool,
         #[clap(short = 'y', long = "yes")]
@@ -120,7 +163,10 @@ pub enum SuiCommand {
         config: Option<PathBuf>,
         #[clap(subcommand)]
         cmd: Option<SuiValidatorCommand>,
-        /// Return command outputs in json format.
+        /// Format of the command outputs (defaults to table).
+        #[clap(long, global = true, value_enum)]
+        output: Option<OutputFormat>,
+        /// Return command outputs in json format, same as `--output json`.
         #[clap(long, global = true)]
         json: bool,
         #[clap(short = 'y', long = "yes")]
@@ -155,6 +201,7 @@ impl SuiCommand {
             SuiCommand::Start {
                 config,
                 no_full_node,
+                admin_port,
             } => {
                 // Auto genesis if path is none and sui directory doesn't exists.
                 if config.is_none() && !sui_config_dir()?.join(SUI_NETWORK_CONFIG).exists() {
@@ -184,6 +231,10 @@ impl SuiCommand {
 
                 swarm.launch().await?;
 
+                if let Some(admin_port) = admin_port {
+                    start_localnet_admin_server(&swarm, admin_port)?;
+                }
+
                 let mut interval = tokio::time::interval(std::time::Duration::from_secs(3));
                 let mut unhealthy_cnt = 0;
                 loop {
@@ -247,11 +298,18 @@ impl SuiCommand {
                 .await
             }
             SuiCommand::GenesisCeremony(cmd) => run(cmd),
-            SuiCommand::KeyTool { keystore_path, cmd } => {
+            SuiCommand::KeyTool {
+                keystore_path,
+                cmd,
+                output,
+                json,
+            } => {
                 let keystore_path =
                     keystore_path.unwrap_or(sui_config_dir()?.join(SUI_KEYSTORE_FILENAME));
                 let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
-                cmd.execute(&mut keystore)
+                cmd.execute(&mut keystore)?
+                    .print(OutputFormat::from_flags(output, json));
+                Ok(())
             }
             SuiCommand::Console { config } => {
                 let config = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
@@ -259,9 +317,20 @@ impl SuiCommand {
                 let context = WalletContext::new(&config, None).await?;
                 start_console(context, &mut stdout(), &mut stderr()).await
             }
+            SuiCommand::Completion { shell } => {
+                let mut app: Command = SuiCommand::command();
+                write_completion_script(shell, &mut app, "sui", &mut stdout())
+            }
+            SuiCommand::CompletionValues { config, values } => {
+                // Completion scripts run this in the background, it must not prompt.
+                let config = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
+                let mut context = WalletContext::new(&config, None).await?;
+                write_completion_values(values, &mut context, &mut stdout()).await
+            }
             SuiCommand::Client {
                 config,
                 cmd,
+                output,
                 json,
                 accept_defaults,
             } => {
@@ -269,7 +338,9 @@ impl SuiCommand {
                 prompt_if_no_config(&config_path, accept_defaults).await?;
                 let mut context = WalletContext::new(&config_path, None).await?;
                 if let Some(cmd) = cmd {
-                    cmd.execute(&mut context).await?.print(!json);
+                    cmd.execute(&mut context)
+                        .await?
+                        .print(OutputFormat::from_flags(output, json));
                 } else {
                     // Print help
                     let mut app: Command = SuiCommand::command();
@@ -281,6 +352,7 @@ impl SuiCommand {
             SuiCommand::Validator {
                 config,
                 cmd,
+                output,
                 json,
                 accept_defaults,
             } => {
@@ -288,7 +360,9 @@ impl SuiCommand {
                 prompt_if_no_config(&config_path, accept_defaults).await?;
                 let mut context = WalletContext::new(&config_path, None).await?;
                 if let Some(cmd) = cmd {
-                    cmd.execute(&mut context).await?.print(!json);
+                    cmd.execute(&mut context)
+                        .await?
+                        .print(OutputFormat::from_flags(output, json));
                 } else {
                     // Print help
                     let mut app: Command = SuiCommand::command();
@@ -301,7 +375,12 @@ impl SuiCommand {
                 package_path,
                 build_config,
                 cmd,
-            } => execute_move_command(package_path, build_config, cmd),
+            } => match cmd {
+                sui_move::Command::Disassemble(disassemble) if disassemble.package_id.is_some() => {
+                    disassemble_on_chain_module(package_path, build_config, disassemble).await
+                }
+                cmd => execute_move_command(package_path, build_config, cmd),
+            },
             SuiCommand::FireDrill { fire_drill } => run_fire_drill(fire_drill).await,
         }
     }
@@ -422,7 +501,7 @@ async fn genesis(
         builder
             .initial_accounts_config(genesis_conf)
             .with_validators(validators)
-            .build()
+            .try_build()?
     } else {
         builder
             .committee_size(
@@ -434,7 +513,7 @@ async fn genesis(
                 .unwrap(),
             )
             .initial_accounts_config(genesis_conf)
-            .build()
+            .try_build()?
     };
 
     let mut keystore = FileBasedKeystore::new(&keystore_path)?;
@@ -580,6 +659,8 @@ async fn prompt_if_no_config(
                 envs: vec![env],
                 active_address: Some(new_address),
                 active_env: Some(alias),
+                aliases: Default::default(),
+                watch_only: Default::default(),
             }
             .persisted(wallet_conf_path)
             .save()?;
@@ -588,6 +669,73 @@ async fn prompt_if_no_config(
     Ok(())
 }
 
+/// Fetches a module of an on-chain package with the client config, and disassembles it. The
+/// source lines are interleaved with the bytecode if the package at `package_path` is verified
+/// to be the source of the on-chain package.
+async fn disassemble_on_chain_module(
+    package_path: Option<PathBuf>,
+    build_config: BuildConfig,
+    disassemble: Disassemble,
+) -> Result<(), anyhow::Error> {
+    let package_id = disassemble
+        .package_id
+        .ok_or_else(|| anyhow!("Missing --package-id"))?;
+    let module_name = disassemble
+        .module
+        .clone()
+        .ok_or_else(|| anyhow!("--package-id requires --module"))?;
+
+    let context = WalletContext::new(&sui_config_dir()?.join(SUI_CLIENT_CONFIG), None).await?;
+    let client = context.get_client().await?;
+    let object = client
+        .read_api()
+        .get_object_with_options(package_id, SuiObjectDataOptions::new().with_bcs())
+        .await?
+        .into_object()?;
+    let Some(SuiRawData::Package(package)) = object.bcs else {
+        bail!("{package_id} is not a Move package");
+    };
+    let bytes = package
+        .module_map
+        .get(&module_name)
+        .ok_or_else(|| anyhow!("Package {package_id} has no module {module_name}"))?;
+    let module = CompiledModule::deserialize(bytes)?;
+
+    let package_path = match package_path {
+        Some(path) => path,
+        None => std::env::current_dir()?,
+    };
+    let mut source = None;
+    if package_path.join("Move.toml").exists() {
+        let build_config = resolve_lock_file_path(build_config, Some(package_path.clone()))?;
+        let compiled_package = build_move_package(
+            &package_path,
+            SuiBuildConfig {
+                config: build_config,
+                run_bytecode_verifier: true,
+                print_diags_to_stderr: true,
+            },
+        )?;
+        match BytecodeSourceVerifier::new(client.read_api())
+            .verify_package_root(&compiled_package, package_id.into())
+            .await
+        {
+            Ok(()) => {
+                let unit = compiled_package
+                    .package
+                    .get_module_by_name_from_root(&module_name)?;
+                source = Some((unit.unit.source_map().clone(), unit.source_path.clone()));
+            }
+            Err(err) => eprintln!(
+                "The package at {} is not the source of {package_id}, disassembling without \
+                 source: {err}",
+                package_path.display()
+            ),
+        }
+    }
+    disassemble.print_disassembly(&module, source)
+}
+
 fn read_line() -> Result<String, anyhow::Error> {
     let mut s = String::new();
     let _ = stdout().flush();
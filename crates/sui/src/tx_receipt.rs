@@ -0,0 +1,157 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Receipts of executed transactions, for `sui client tx-receipt` and `sui client verify-receipt`.
+//!
+//! A receipt is a self-contained JSON file holding the proof of a transaction: the signed
+//! transaction, its effects and events, the contents of the checkpoint including it, which prove
+//! its inclusion, and the checkpoint summary with the signatures of the committee. It is verified
+//! offline, without a connection to a node.
+//!
+//! The receipt also holds the committee of the epoch of the checkpoint, as reported by the node
+//! it was exported from. It is only as trustworthy as that node, so a receipt is verified against
+//! a committee obtained from a trusted source when one is available.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, ensure, Context};
+use serde::{Deserialize, Serialize};
+use sui_json_rpc_types::{SuiCommittee, SuiTransactionProof};
+use sui_sdk::SuiClient;
+use sui_types::base_types::TransactionDigest;
+use sui_types::committee::{Committee, EpochId, TOTAL_VOTING_POWER};
+use sui_types::messages::TransactionEffectsAPI;
+use sui_types::messages_checkpoint::{CheckpointSequenceNumber, CheckpointTimestamp};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransactionReceipt {
+    pub proof: SuiTransactionProof,
+    /// The committee of the epoch of the checkpoint, as reported by the node the receipt was
+    /// exported from.
+    pub committee: SuiCommittee,
+}
+
+impl TransactionReceipt {
+    /// Fetches the receipt of the transaction `digest`, which must be included in a checkpoint.
+    pub async fn fetch(
+        client: &SuiClient,
+        digest: TransactionDigest,
+    ) -> Result<Self, anyhow::Error> {
+        let proof = client.read_api().get_transaction_proof(digest).await?;
+        let committee = client
+            .read_api()
+            .get_committee_info(Some(proof.epoch.into()))
+            .await?;
+        Ok(Self { proof, committee })
+    }
+
+    pub fn read(path: &Path) -> Result<Self, anyhow::Error> {
+        let receipt = fs::read_to_string(path)
+            .with_context(|| format!("Cannot read the receipt {}", path.display()))?;
+        Ok(serde_json::from_str(&receipt)?)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), anyhow::Error> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Cannot write the receipt to {}", path.display()))
+    }
+
+    /// Verifies the receipt against `committee`, which must come from a trusted source, or
+    /// against the committee of the receipt if `committee` is `None`.
+    pub fn verify(
+        &self,
+        committee: Option<&SuiCommittee>,
+    ) -> Result<VerifiedTransactionReceipt, anyhow::Error> {
+        let trusted_committee = committee.is_some();
+        let committee = to_committee(committee.unwrap_or(&self.committee))?;
+        ensure!(
+            committee.epoch == self.proof.epoch,
+            "The committee is of epoch {}, the transaction is certified by the committee of epoch {}",
+            committee.epoch,
+            self.proof.epoch
+        );
+        let proof = self.proof.verify(&committee)?;
+        Ok(VerifiedTransactionReceipt {
+            digest: self.proof.digest,
+            epoch: proof.checkpoint.epoch,
+            checkpoint: proof.checkpoint.sequence_number,
+            timestamp_ms: proof.checkpoint.timestamp_ms,
+            success: proof.effects.status().is_ok(),
+            events: proof.events.map_or(0, |events| events.data.len()),
+            trusted_committee,
+        })
+    }
+}
+
+/// The facts proved by a receipt.
+#[derive(Debug, Serialize)]
+pub struct VerifiedTransactionReceipt {
+    pub digest: TransactionDigest,
+    pub epoch: EpochId,
+    pub checkpoint: CheckpointSequenceNumber,
+    pub timestamp_ms: CheckpointTimestamp,
+    /// Whether the transaction executed successfully.
+    pub success: bool,
+    pub events: usize,
+    /// Whether the receipt was verified against a committee from a trusted source, rather than
+    /// against the committee of the receipt.
+    pub trusted_committee: bool,
+}
+
+impl Display for VerifiedTransactionReceipt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "----- Transaction receipt ----")?;
+        writeln!(f, "Transaction: {}", self.digest)?;
+        writeln!(
+            f,
+            "Status: {}",
+            if self.success { "success" } else { "failure" }
+        )?;
+        writeln!(f, "Events: {}", self.events)?;
+        writeln!(
+            f,
+            "Checkpoint: {} of epoch {}, at {} ms",
+            self.checkpoint, self.epoch, self.timestamp_ms
+        )?;
+        if self.trusted_committee {
+            writeln!(f, "Verified against the given committee.")
+        } else {
+            writeln!(
+                f,
+                "Verified against the committee of the receipt, pass a trusted committee with \
+                 --committee to verify that it is the committee of the network."
+            )
+        }
+    }
+}
+
+/// Reads a committee in the JSON format of `suix_getCommitteeInfo`.
+pub fn read_committee(path: &Path) -> Result<SuiCommittee, anyhow::Error> {
+    let committee = fs::read_to_string(path)
+        .with_context(|| format!("Cannot read the committee {}", path.display()))?;
+    Ok(serde_json::from_str(&committee)?)
+}
+
+/// Checks the voting rights of `committee`, which `Committee::new` asserts.
+fn to_committee(committee: &SuiCommittee) -> Result<Committee, anyhow::Error> {
+    let voting_rights: BTreeMap<_, _> = committee.validators.iter().cloned().collect();
+    if voting_rights.len() != committee.validators.len() {
+        return Err(anyhow!(
+            "Invalid committee of epoch {}: duplicate validators",
+            committee.epoch
+        ));
+    }
+    let total_votes = voting_rights
+        .values()
+        .try_fold(0u64, |total, votes| total.checked_add(*votes));
+    if total_votes != Some(TOTAL_VOTING_POWER) {
+        return Err(anyhow!(
+            "Invalid committee of epoch {}: total voting power is not {TOTAL_VOTING_POWER}",
+            committee.epoch
+        ));
+    }
+    Ok(Committee::new(committee.epoch, voting_rights))
+}
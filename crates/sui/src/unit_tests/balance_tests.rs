@@ -0,0 +1,87 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use sui_json_rpc_types::{Balance, SuiCoinMetadata};
+use sui_types::parse_sui_type_tag;
+
+use super::{format_amount, write_coin_balances, CoinBalance, FilePriceProvider, PriceProvider};
+
+fn coin_balance(
+    coin_type: &str,
+    total_balance: u128,
+    decimals: Option<u8>,
+    usd_price: Option<f64>,
+) -> CoinBalance {
+    CoinBalance {
+        balance: Balance {
+            coin_type: coin_type.to_string(),
+            coin_object_count: 2,
+            total_balance,
+            locked_balance: HashMap::new(),
+        },
+        metadata: decimals.map(|decimals| SuiCoinMetadata {
+            decimals,
+            name: "Sui".to_string(),
+            symbol: "SUI".to_string(),
+            description: String::new(),
+            icon_url: None,
+            id: None,
+        }),
+        usd_price,
+    }
+}
+
+#[test]
+fn test_format_amount() {
+    assert_eq!(format_amount(1_500_000_000, 9), "1.5");
+    assert_eq!(format_amount(1_000_000_000, 9), "1");
+    assert_eq!(format_amount(10_000, 9), "0.00001");
+    assert_eq!(format_amount(0, 9), "0");
+    assert_eq!(format_amount(42, 0), "42");
+    assert_eq!(format_amount(u128::MAX, 255).len(), 257);
+}
+
+#[test]
+fn test_coin_balance_values() {
+    let balance = coin_balance("0x2::sui::SUI", 2_500_000_000, Some(9), Some(2.0));
+    assert_eq!(balance.formatted_balance(), "2.5 SUI");
+    assert_eq!(balance.usd_value(), Some(5.0));
+
+    // Without metadata, balances are in units of the coin, and have no value.
+    let balance = coin_balance("0x42::coin::COIN", 2_500, None, Some(2.0));
+    assert_eq!(balance.formatted_balance(), "2500");
+    assert_eq!(balance.usd_value(), None);
+
+    // The value column is only shown when a price is known.
+    let table = write_coin_balances(&[coin_balance("0x2::sui::SUI", 1, Some(9), None)]).unwrap();
+    assert!(!table.contains("Value (USD)"), "{table}");
+    let table = write_coin_balances(&[
+        coin_balance("0x2::sui::SUI", 2_500_000_000, Some(9), Some(2.0)),
+        coin_balance("0x42::coin::COIN", 2_500, None, None),
+    ])
+    .unwrap();
+    assert!(table.contains("Value (USD)"), "{table}");
+    assert!(table.contains("5.00"), "{table}");
+}
+
+#[tokio::test]
+async fn test_file_price_provider() -> Result<(), anyhow::Error> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("prices.yaml");
+    std::fs::write(&path, "0x2::sui::SUI: 1.25\n")?;
+    let prices = FilePriceProvider::from_file(&path)?;
+
+    // Coin types are matched whatever the format of their addresses.
+    let sui = parse_sui_type_tag(
+        "0x0000000000000000000000000000000000000000000000000000000000000002::sui::SUI",
+    )?;
+    assert_eq!(prices.usd_price(&sui).await?, Some(1.25));
+    let other = parse_sui_type_tag("0x42::coin::COIN")?;
+    assert_eq!(prices.usd_price(&other).await?, None);
+
+    std::fs::write(&path, "not a coin type: 1.0\n")?;
+    assert!(FilePriceProvider::from_file(&path).is_err());
+    Ok(())
+}
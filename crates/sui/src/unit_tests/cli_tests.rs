@@ -3,17 +3,29 @@
 
 use std::io::Read;
 use std::os::unix::prelude::FileExt;
-use std::{fmt::Write, fs::read_dir, path::PathBuf, str, thread, time::Duration};
+use std::{
+    fmt::Write,
+    fs::read_dir,
+    path::PathBuf,
+    str::{self, FromStr},
+    thread,
+    time::Duration,
+};
 
 use expect_test::expect;
 use serde_json::json;
 use sui_types::object::Owner;
 use tokio::time::sleep;
 
-use sui::client_commands::SwitchResponse;
+use sui::client_commands::{
+    write_transaction_preview, AliasCommand, SwitchResponse, WatchOnlyCommand,
+};
+use sui::output::{OutputFormat, PrintableResult};
+use sui::tx_receipt::TransactionReceipt;
+use sui::workspace_publish::PublishStatus;
 use sui::{
     client_commands::{SuiClientCommandResult, SuiClientCommands, WalletContext},
-    config::SuiClientConfig,
+    config::{AddressOrAlias, SuiClientConfig},
     sui_commands::SuiCommand,
 };
 use sui_config::{
@@ -27,16 +39,19 @@ use sui_config::{
 use sui_framework_build::compiled_package::{BuildConfig, SuiPackageHooks};
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    OwnedObjectRef, SuiObjectData, SuiObjectDataFilter, SuiObjectDataOptions, SuiObjectResponse,
-    SuiObjectResponseQuery, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI,
+    ObjectChange, OwnedObjectRef, SuiCommittee, SuiData, SuiObjectData, SuiObjectDataFilter,
+    SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery, SuiTransactionBlockEffects,
+    SuiTransactionBlockEffectsAPI,
 };
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore};
 use sui_macros::sim_test;
-use sui_types::base_types::SuiAddress;
+use sui_transaction_builder::UpgradeCapRestriction;
+use sui_types::base_types::{SuiAddress, TransactionDigest};
 use sui_types::crypto::{
     Ed25519SuiSignature, Secp256k1SuiSignature, SignatureScheme, SuiKeyPair, SuiSignatureInner,
 };
 use sui_types::error::SuiObjectResponseError;
+use sui_types::move_package::{UpgradeCap, UpgradePolicy};
 use sui_types::{base_types::ObjectID, crypto::get_key_pair, gas_coin::GasCoin};
 use test_utils::messages::make_transactions_with_wallet_context;
 use test_utils::network::TestClusterBuilder;
@@ -53,6 +68,7 @@ async fn test_genesis() -> Result<(), anyhow::Error> {
     let start = SuiCommand::Start {
         config: Some(config),
         no_full_node: false,
+        admin_port: None,
     }
     .execute()
     .await;
@@ -187,11 +203,24 @@ async fn test_addresses_command() -> Result<(), anyhow::Error> {
     }
 
     // Print all addresses
-    SuiClientCommands::Addresses
+    let resp = SuiClientCommands::Addresses
         .execute(&mut context)
         .await
-        .unwrap()
-        .print(true);
+        .unwrap();
+    resp.print(OutputFormat::Table);
+
+    // The JSON and YAML outputs hold the same addresses, and parse back to the same value.
+    let SuiClientCommandResult::Addresses(addresses, active) = &resp else {
+        panic!("Expected an addresses response, got {resp}");
+    };
+    let expected = json!([addresses, active]);
+    let parsed: serde_json::Value = serde_json::from_str(&resp.render(OutputFormat::Json))?;
+    assert_eq!(parsed, expected);
+    let parsed: serde_json::Value = serde_yaml::from_str(&resp.render(OutputFormat::Yaml))?;
+    assert_eq!(parsed, expected);
+    assert!(resp
+        .render(OutputFormat::Table)
+        .contains(&addresses[0].to_string()));
 
     Ok(())
 }
@@ -204,11 +233,11 @@ async fn test_objects_command() -> Result<(), anyhow::Error> {
 
     // Print objects owned by `address`
     SuiClientCommands::Objects {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(context)
     .await?
-    .print(true);
+    .print(OutputFormat::Table);
     let client = context.get_client().await?;
     let _object_refs = client
         .read_api()
@@ -236,7 +265,7 @@ async fn test_regression_6546() -> Result<(), anyhow::Error> {
     let context = &mut test_cluster.wallet;
 
     let SuiClientCommandResult::Objects(coins) = SuiClientCommands::Objects {
-        address: Some(address),
+        address: Some(address.into()),
     }
         .execute(context)
         .await? else{
@@ -260,6 +289,7 @@ async fn test_regression_6546() -> Result<(), anyhow::Error> {
         &test_cluster.get_address_1().to_string(),
         "--gas-budget",
         "100000000",
+        "--yes",
     ])
     .await
 }
@@ -286,11 +316,11 @@ async fn test_custom_genesis() -> Result<(), anyhow::Error> {
 
     // Print objects owned by `address`
     SuiClientCommands::Objects {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(context)
     .await?
-    .print(true);
+    .print(OutputFormat::Table);
 
     Ok(())
 }
@@ -325,7 +355,7 @@ async fn test_object_info_get_command() -> Result<(), anyhow::Error> {
     }
     .execute(context)
     .await?
-    .print(true);
+    .print(OutputFormat::Table);
 
     SuiClientCommands::Object {
         id: object_id,
@@ -333,7 +363,7 @@ async fn test_object_info_get_command() -> Result<(), anyhow::Error> {
     }
     .execute(context)
     .await?
-    .print(true);
+    .print(OutputFormat::Table);
 
     Ok(())
 }
@@ -367,31 +397,32 @@ async fn test_gas_command() -> Result<(), anyhow::Error> {
     let object_to_send = object_refs.data.get(1).unwrap().object().unwrap().object_id;
 
     SuiClientCommands::Gas {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(context)
     .await?
-    .print(true);
+    .print(OutputFormat::Table);
 
     tokio::time::sleep(Duration::from_millis(100)).await;
 
     // Send an object
     SuiClientCommands::Transfer {
-        to: SuiAddress::random_for_testing_only(),
+        to: SuiAddress::random_for_testing_only().into(),
         object_id: object_to_send,
         gas: Some(object_id),
         gas_budget: 50000000,
+        yes: true,
     }
     .execute(context)
     .await?;
 
     // Fetch gas again
     SuiClientCommands::Gas {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(context)
     .await?
-    .print(true);
+    .print(OutputFormat::Table);
 
     Ok(())
 }
@@ -430,6 +461,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
         serialize_output: false,
+        dry_run: false,
     }
     .execute(context)
     .await?;
@@ -455,11 +487,11 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
 
     // Print objects owned by `address1`
     SuiClientCommands::Objects {
-        address: Some(address1),
+        address: Some(address1.into()),
     }
     .execute(context)
     .await?
-    .print(true);
+    .print(OutputFormat::Table);
     tokio::time::sleep(Duration::from_millis(2000)).await;
     let client = context.get_client().await?;
     let object_refs = client
@@ -504,10 +536,11 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args,
         gas: None,
         gas_budget: 1_000_000_000,
+        yes: true,
     }
     .execute(context)
     .await?;
-    resp.print(true);
+    resp.print(OutputFormat::Table);
 
     // Get the created object
     let created_obj: ObjectID = if let SuiClientCommandResult::Call(resp) = resp {
@@ -541,6 +574,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args: args.to_vec(),
         gas: Some(gas),
         gas_budget: 1_000_000_000,
+        yes: true,
     }
     .execute(context)
     .await;
@@ -565,6 +599,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args: args.to_vec(),
         gas: Some(gas),
         gas_budget: 1_000_000_000,
+        yes: true,
     }
     .execute(context)
     .await;
@@ -591,6 +626,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args: args.to_vec(),
         gas: Some(gas),
         gas_budget: 1_000_000,
+        yes: true,
     }
     .execute(context)
     .await?;
@@ -636,12 +672,13 @@ async fn test_package_publish_command() -> Result<(), anyhow::Error> {
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
         serialize_output: false,
+        dry_run: false,
     }
     .execute(context)
     .await?;
 
     // Print it out to CLI/logs
-    resp.print(true);
+    resp.print(OutputFormat::Table);
 
     let obj_ids = if let SuiClientCommandResult::Publish(response) = resp {
         response
@@ -664,6 +701,72 @@ async fn test_package_publish_command() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_package_publish_dry_run() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let context = &mut test_cluster.wallet;
+
+    let client = context.get_client().await?;
+    let object_refs = client
+        .read_api()
+        .get_owned_objects(
+            address,
+            Some(SuiObjectResponseQuery::new_with_options(
+                SuiObjectDataOptions::new(),
+            )),
+            None,
+            None,
+        )
+        .await?
+        .data;
+    let gas_obj_id = object_refs.first().unwrap().object().unwrap().object_id;
+
+    let mut package_path = PathBuf::from(TEST_DATA_DIR);
+    package_path.push("dummy_modules_publish");
+    let publish = |dry_run| SuiClientCommands::Publish {
+        package_path: package_path.clone(),
+        build_config: BuildConfig::new_for_testing().config,
+        gas: Some(gas_obj_id),
+        gas_budget: 1_000_000_000,
+        skip_dependency_verification: false,
+        with_unpublished_dependencies: false,
+        serialize_output: false,
+        dry_run,
+    };
+
+    let resp = publish(true).execute(context).await?;
+    resp.print(OutputFormat::Table);
+    let SuiClientCommandResult::PublishDryRun(response) = resp else {
+        unreachable!("Invalid response");
+    };
+    let predicted_id = response
+        .object_changes
+        .iter()
+        .find_map(|change| match change {
+            ObjectChange::Published { package_id, .. } => Some(*package_id),
+            _ => None,
+        })
+        .unwrap();
+
+    // The dry run does not publish the package, and predicts the ID it is published at.
+    let SuiClientCommandResult::Publish(response) = publish(false).execute(context).await? else {
+        unreachable!("Invalid response");
+    };
+    let package_id = response
+        .effects
+        .unwrap()
+        .created()
+        .iter()
+        .find(|refe| matches!(refe.owner, Owner::Immutable))
+        .unwrap()
+        .reference
+        .object_id;
+    assert_eq!(package_id, predicted_id);
+
+    Ok(())
+}
+
 #[sim_test]
 async fn test_package_publish_command_with_unpublished_dependency_succeeds(
 ) -> Result<(), anyhow::Error> {
@@ -703,12 +806,13 @@ async fn test_package_publish_command_with_unpublished_dependency_succeeds(
         skip_dependency_verification: false,
         with_unpublished_dependencies,
         serialize_output: false,
+        dry_run: false,
     }
     .execute(context)
     .await?;
 
     // Print it out to CLI/logs
-    resp.print(true);
+    resp.print(OutputFormat::Table);
 
     let obj_ids = if let SuiClientCommandResult::Publish(response) = resp {
         response
@@ -770,6 +874,7 @@ async fn test_package_publish_command_with_unpublished_dependency_fails(
         skip_dependency_verification: false,
         with_unpublished_dependencies,
         serialize_output: false,
+        dry_run: false,
     }
     .execute(context)
     .await;
@@ -814,6 +919,7 @@ async fn test_package_publish_command_non_zero_unpublished_dep_fails() -> Result
         skip_dependency_verification: false,
         with_unpublished_dependencies,
         serialize_output: false,
+        dry_run: false,
     }
     .execute(context)
     .await;
@@ -867,6 +973,7 @@ async fn test_package_publish_command_failure_invalid() -> Result<(), anyhow::Er
         skip_dependency_verification: false,
         with_unpublished_dependencies,
         serialize_output: false,
+        dry_run: false,
     }
     .execute(context)
     .await;
@@ -908,6 +1015,7 @@ async fn test_package_publish_nonexistent_dependency() -> Result<(), anyhow::Err
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
         serialize_output: false,
+        dry_run: false,
     }
     .execute(context)
     .await;
@@ -963,12 +1071,13 @@ async fn test_package_upgrade_command() -> Result<(), anyhow::Error> {
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
         serialize_output: false,
+        dry_run: false,
     }
     .execute(context)
     .await?;
 
     // Print it out to CLI/logs
-    resp.print(true);
+    resp.print(OutputFormat::Table);
 
     let SuiClientCommandResult::Publish(response) = resp else {
         unreachable!("Invalid response");
@@ -1033,11 +1142,14 @@ async fn test_package_upgrade_command() -> Result<(), anyhow::Error> {
         gas_budget: 20_000_000,
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
+        policy: None,
+        expected_digest: None,
+        dry_run: false,
     }
     .execute(context)
     .await?;
 
-    resp.print(true);
+    resp.print(OutputFormat::Table);
 
     let SuiClientCommandResult::Upgrade(response) = resp else {
         unreachable!("Invalid upgrade response");
@@ -1060,6 +1172,214 @@ async fn test_package_upgrade_command() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_restrict_upgrade_cap() -> Result<(), anyhow::Error> {
+    move_package::package_hooks::register_package_hooks(Box::new(SuiPackageHooks));
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let context = &mut test_cluster.wallet;
+
+    let mut package_path = PathBuf::from(TEST_DATA_DIR);
+    package_path.push("dummy_modules_upgrade");
+    let resp = SuiClientCommands::Publish {
+        package_path,
+        build_config: BuildConfig::new_for_testing().config,
+        gas: None,
+        gas_budget: 20_000_000,
+        skip_dependency_verification: false,
+        with_unpublished_dependencies: false,
+        serialize_output: false,
+        dry_run: false,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::Publish(response) = resp else {
+        unreachable!("Invalid response");
+    };
+    let cap = response
+        .effects
+        .unwrap()
+        .created()
+        .iter()
+        .find(|refe| matches!(refe.owner, Owner::AddressOwner(_)))
+        .unwrap()
+        .reference
+        .object_id;
+
+    for restriction in [
+        UpgradeCapRestriction::Additive,
+        UpgradeCapRestriction::Immutable,
+    ] {
+        let resp = SuiClientCommands::RestrictUpgradeCap {
+            upgrade_capability: cap,
+            restriction,
+            gas: None,
+            gas_budget: 20_000_000,
+        }
+        .execute(context)
+        .await?;
+        resp.print(OutputFormat::Table);
+        let SuiClientCommandResult::RestrictUpgradeCap(response) = resp else {
+            unreachable!("Invalid response");
+        };
+        assert!(response.effects.unwrap().status().is_ok());
+
+        let client = context.get_client().await?;
+        let cap_object = client
+            .read_api()
+            .get_object_with_options(cap, SuiObjectDataOptions::new().with_bcs())
+            .await?;
+        if restriction == UpgradeCapRestriction::Immutable {
+            // Making the package immutable destroys its upgrade capability.
+            assert!(cap_object.data.is_none());
+        } else {
+            let upgrade_cap: UpgradeCap = cap_object
+                .data
+                .unwrap()
+                .bcs
+                .unwrap()
+                .try_as_move()
+                .unwrap()
+                .deserialize()?;
+            assert_eq!(upgrade_cap.policy, UpgradePolicy::ADDITIVE);
+        }
+    }
+
+    Ok(())
+}
+
+#[sim_test]
+async fn test_publish_workspace() -> Result<(), anyhow::Error> {
+    move_package::package_hooks::register_package_hooks(Box::new(SuiPackageHooks));
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let context = &mut test_cluster.wallet;
+
+    // The manifests of the published packages are updated, so publish a copy of the workspace.
+    let tmp_dir = tempfile::tempdir().unwrap();
+    fs_extra::dir::copy(
+        PathBuf::from(TEST_DATA_DIR).join("workspace"),
+        tmp_dir.path(),
+        &fs_extra::dir::CopyOptions::default(),
+    )
+    .unwrap();
+    let workspace = tmp_dir.path().join("workspace");
+
+    // `App` depends on `Base`, which is published first.
+    let resp = SuiClientCommands::PublishWorkspace {
+        package_paths: vec![workspace.join("app"), workspace.join("base")],
+        build_config: BuildConfig::new_for_testing().config,
+        gas: None,
+        gas_budget: 20_000_000,
+        skip_dependency_verification: false,
+    }
+    .execute(context)
+    .await?;
+    resp.print(OutputFormat::Table);
+    let SuiClientCommandResult::PublishWorkspace(report) = resp else {
+        unreachable!("Invalid response");
+    };
+    let published: Vec<_> = report
+        .packages
+        .iter()
+        .map(|(package, status)| match status {
+            PublishStatus::Published { package_id, .. } => (package.name.as_str(), *package_id),
+            _ => panic!("{} was not published", package.name),
+        })
+        .collect();
+    assert_eq!(published[0].0, "Base");
+    assert_eq!(published[1].0, "App");
+
+    let manifest = std::fs::read_to_string(workspace.join("base").join("Move.toml"))?;
+    let base_id = published[0].1.to_hex_uncompressed();
+    assert!(manifest.contains(&format!("published-at = \"{base_id}\"")));
+    assert!(manifest.contains(&format!("base = \"{base_id}\"")));
+
+    // Published packages are skipped when the workspace is published again.
+    let resp = SuiClientCommands::PublishWorkspace {
+        package_paths: vec![workspace.join("app"), workspace.join("base")],
+        build_config: BuildConfig::new_for_testing().config,
+        gas: None,
+        gas_budget: 20_000_000,
+        skip_dependency_verification: false,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::PublishWorkspace(report) = resp else {
+        unreachable!("Invalid response");
+    };
+    assert!(report
+        .packages
+        .iter()
+        .all(|(_, status)| matches!(status, PublishStatus::AlreadyPublished)));
+
+    Ok(())
+}
+
+#[sim_test]
+async fn test_transaction_receipt() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let committee = test_cluster.swarm.config().genesis.committee()?;
+    let context = &mut test_cluster.wallet;
+    let client = context.get_client().await?;
+    let objects = client
+        .read_api()
+        .get_owned_objects(
+            address,
+            Some(SuiObjectResponseQuery::new_with_options(
+                SuiObjectDataOptions::new().with_previous_transaction(),
+            )),
+            None,
+            None,
+        )
+        .await?
+        .data;
+    // The gas objects are written by the genesis transaction, in the first checkpoint.
+    let digest = objects[0].object().unwrap().previous_transaction.unwrap();
+
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let receipt = tmp_dir.path().join("receipt.json");
+    SuiClientCommands::TransactionReceipt {
+        digest,
+        output: receipt.clone(),
+    }
+    .execute(context)
+    .await?
+    .print(OutputFormat::Table);
+
+    let committee_path = tmp_dir.path().join("committee.json");
+    std::fs::write(
+        &committee_path,
+        serde_json::to_string(&SuiCommittee::from(committee))?,
+    )?;
+    let resp = SuiClientCommands::VerifyReceipt {
+        receipt: receipt.clone(),
+        committee: Some(committee_path),
+    }
+    .execute(context)
+    .await?;
+    resp.print(OutputFormat::Table);
+    let SuiClientCommandResult::VerifyReceipt(verified) = resp else {
+        unreachable!("Invalid response");
+    };
+    assert_eq!(verified.digest, digest);
+    assert_eq!(verified.checkpoint, 0);
+    assert!(verified.trusted_committee);
+
+    // A receipt whose proof does not match its transaction is rejected.
+    let mut forged: TransactionReceipt = serde_json::from_str(&std::fs::read_to_string(&receipt)?)?;
+    forged.proof.digest = TransactionDigest::random();
+    std::fs::write(&receipt, serde_json::to_string(&forged)?)?;
+    let resp = SuiClientCommands::VerifyReceipt {
+        receipt,
+        committee: None,
+    }
+    .execute(context)
+    .await;
+    assert!(resp.is_err());
+
+    Ok(())
+}
+
 #[sim_test]
 async fn test_native_transfer() -> Result<(), anyhow::Error> {
     let mut test_cluster = TestClusterBuilder::new().build().await?;
@@ -1090,15 +1410,16 @@ async fn test_native_transfer() -> Result<(), anyhow::Error> {
 
     let resp = SuiClientCommands::Transfer {
         gas: Some(gas_obj_id),
-        to: recipient,
+        to: recipient.into(),
         object_id: obj_id,
         gas_budget: 50000000,
+        yes: true,
     }
     .execute(context)
     .await?;
 
     // Print it out to CLI/logs
-    resp.print(true);
+    resp.print(OutputFormat::Table);
 
     // Get the mutated objects
     let (mut_obj1, mut_obj2) = if let SuiClientCommandResult::Transfer(_, response) = resp {
@@ -1188,15 +1509,16 @@ async fn test_native_transfer() -> Result<(), anyhow::Error> {
 
     let resp = SuiClientCommands::Transfer {
         gas: None,
-        to: recipient,
+        to: recipient.into(),
         object_id: obj_id,
         gas_budget: 50000000,
+        yes: true,
     }
     .execute(context)
     .await?;
 
     // Print it out to CLI/logs
-    resp.print(true);
+    resp.print(OutputFormat::Table);
 
     // Get the mutated objects
     let (_mut_obj1, _mut_obj2) = if let SuiClientCommandResult::Transfer(_, response) = resp {
@@ -1282,7 +1604,7 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
 
     // Switch the address
     let resp = SuiClientCommands::Switch {
-        address: Some(addr2),
+        address: Some(addr2.into()),
         env: None,
     }
     .execute(context)
@@ -1320,7 +1642,7 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
     // Check that we can switch to this address
     // Switch the address
     let resp = SuiClientCommands::Switch {
-        address: Some(new_addr),
+        address: Some(new_addr.into()),
         env: None,
     }
     .execute(context)
@@ -1399,7 +1721,7 @@ async fn test_active_address_command() -> Result<(), anyhow::Error> {
 
     let addr2 = context.config.keystore.addresses().get(1).cloned().unwrap();
     let resp = SuiClientCommands::Switch {
-        address: Some(addr2),
+        address: Some(addr2.into()),
         env: None,
     }
     .execute(context)
@@ -1759,6 +2081,107 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_ptb_plan() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let recipient = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+    let coin = context
+        .gas_objects(address)
+        .await?
+        .first()
+        .unwrap()
+        .1
+        .object_id;
+    let orig_value = get_gas_value(&get_object(coin, context).await.unwrap());
+
+    let dir = tempfile::tempdir()?;
+    let plan = dir.path().join("plan.yaml");
+    std::fs::write(
+        &plan,
+        format!(
+            r#"
+vars:
+  amount: 1000
+commands:
+  - split_coins:
+      coin: "{coin}"
+      amounts: ["${{amount}}", "${{amount}}", 10]
+    result: coins
+  - repeat:
+      times: 2
+      var: i
+      commands:
+        - transfer_objects:
+            objects: ["$coins.${{i}}"]
+            address: "${{recipient}}"
+  - transfer_objects:
+      objects: ["$coins.2"]
+      address: "{address}"
+"#
+        ),
+    )?;
+
+    // The preview doesn't execute the transaction.
+    let resp = SuiClientCommands::Ptb {
+        plan: plan.clone(),
+        vars: vec![("recipient".to_string(), json!(recipient.to_string()))],
+        gas: None,
+        gas_budget: 200_000_000,
+        preview: true,
+        yes: true,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::PtbPreview(preview) = resp else {
+        panic!("Expected a preview, got {resp:?}");
+    };
+    assert!(preview.effects.status().is_ok());
+    assert_eq!(preview.effects.created().len(), 3);
+    let summary = write_transaction_preview(&preview)?;
+    assert!(summary.contains("Status: success"), "{summary}");
+    assert!(
+        summary.contains(&format!("{}: +2000", Owner::AddressOwner(recipient))),
+        "{summary}"
+    );
+    assert!(summary.contains(&format!("Mutated {coin}")), "{summary}");
+    assert_eq!(
+        get_gas_value(&get_object(coin, context).await.unwrap()),
+        orig_value
+    );
+
+    let resp = SuiClientCommands::Ptb {
+        plan,
+        vars: vec![("recipient".to_string(), json!(recipient.to_string()))],
+        gas: None,
+        gas_budget: 200_000_000,
+        preview: false,
+        yes: true,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::Ptb(resp) = resp else {
+        panic!("Expected a transaction response, got {resp:?}");
+    };
+    let effects = resp.effects.unwrap();
+    assert!(effects.status().is_ok());
+    let created = effects.created();
+    assert_eq!(created.len(), 3);
+    assert_eq!(
+        created
+            .iter()
+            .filter(|o| o.owner == Owner::AddressOwner(recipient))
+            .count(),
+        2
+    );
+    assert_eq!(
+        get_gas_value(&get_object(coin, context).await.unwrap()),
+        orig_value - 2010
+    );
+    Ok(())
+}
+
 #[sim_test]
 async fn test_signature_flag() -> Result<(), anyhow::Error> {
     let res = SignatureScheme::from_flag("0");
@@ -1820,7 +2243,7 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
     let coin = object_refs.get(1).unwrap().object().unwrap().object_id;
 
     SuiClientCommands::SerializeTransferSui {
-        to: address1,
+        to: address1.into(),
         sui_coin_object_id: coin,
         gas_budget: 1000,
         amount: Some(1),
@@ -1830,6 +2253,274 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_alias_command() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let context = &mut test_cluster.wallet;
+    let contact = SuiAddress::random_for_testing_only();
+
+    // Aliases name keys of the keystore as well as addresses of other accounts.
+    for (alias, address) in [("me", address), ("contact", contact)] {
+        SuiClientCommands::Alias {
+            cmd: AliasCommand::Add {
+                alias: alias.to_string(),
+                address,
+            },
+        }
+        .execute(context)
+        .await?;
+    }
+    // An alias names a single address.
+    assert!(SuiClientCommands::Alias {
+        cmd: AliasCommand::Add {
+            alias: "me".to_string(),
+            address: contact,
+        },
+    }
+    .execute(context)
+    .await
+    .is_err());
+
+    // Aliases can't be mistaken for addresses.
+    assert_eq!(
+        AddressOrAlias::from_str(&contact.to_string())?,
+        AddressOrAlias::Address(contact)
+    );
+    assert_eq!(
+        AddressOrAlias::from_str("my-contact_2")?,
+        AddressOrAlias::Alias("my-contact_2".to_string())
+    );
+    assert!(AddressOrAlias::from_str("2contact").is_err());
+    assert!(AddressOrAlias::from_str("contact.sui").is_err());
+
+    // The aliases are saved in the client config.
+    let config = SuiClientConfig::load(context.config.path())?;
+    assert_eq!(config.aliases.get("me"), Some(&address));
+    assert_eq!(config.aliases.get("contact"), Some(&contact));
+
+    let resp = SuiClientCommands::Alias {
+        cmd: AliasCommand::List,
+    }
+    .execute(context)
+    .await?;
+    assert_eq!(
+        format!("{resp}"),
+        format!("contact => {contact}\nme => {address}")
+    );
+
+    // Commands accept the aliases instead of the addresses.
+    let resp = SuiClientCommands::Gas {
+        address: Some(AddressOrAlias::Alias("me".to_string())),
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::Gas(coins) = resp else {
+        panic!("Expected gas coins, got {resp}");
+    };
+    let coin = coins.first().unwrap().id();
+    let resp = SuiClientCommands::TransferSui {
+        to: AddressOrAlias::Alias("contact".to_string()),
+        sui_coin_object_id: *coin,
+        gas_budget: 1000,
+        amount: Some(1),
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::TransferSui(response) = resp else {
+        panic!("Expected a transfer, got {resp}");
+    };
+    let balance_changes = response.balance_changes.unwrap();
+    assert!(balance_changes
+        .iter()
+        .any(|change| change.owner == Owner::AddressOwner(contact) && change.amount == 1));
+
+    // Removed aliases are not accepted anymore.
+    SuiClientCommands::Alias {
+        cmd: AliasCommand::Remove {
+            alias: "contact".to_string(),
+        },
+    }
+    .execute(context)
+    .await?;
+    assert!(SuiClientCommands::Objects {
+        address: Some(AddressOrAlias::Alias("contact".to_string())),
+    }
+    .execute(context)
+    .await
+    .is_err());
+    assert!(!SuiClientConfig::load(context.config.path())?
+        .aliases
+        .contains_key("contact"));
+    Ok(())
+}
+
+#[sim_test]
+async fn test_balance_command() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let context = &mut test_cluster.wallet;
+    let gas_coins = context.gas_objects(address).await?;
+    let total: u64 = gas_coins.iter().map(|(value, _)| value).sum();
+
+    let prices_dir = tempfile::tempdir()?;
+    let prices = prices_dir.path().join("prices.yaml");
+    std::fs::write(&prices, "0x2::sui::SUI: 2.0\n")?;
+
+    // The gas coins are aggregated in a single balance, with the decimals of SUI.
+    let resp = SuiClientCommands::Balance {
+        address: Some(address.into()),
+        coin_type: None,
+        prices: Some(prices),
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::Balance(balances) = &resp else {
+        panic!("Expected balances, got {resp}");
+    };
+    assert_eq!(balances.len(), 1);
+    let balance = &balances[0];
+    assert_eq!(balance.balance.coin_object_count, gas_coins.len());
+    assert_eq!(balance.balance.total_balance, total as u128);
+    assert_eq!(balance.metadata.as_ref().unwrap().decimals, 9);
+    assert_eq!(balance.usd_price, Some(2.0));
+    assert_eq!(balance.usd_value(), Some(total as f64 / 1e9 * 2.0));
+    let table = format!("{resp}");
+    assert!(table.contains(&balance.formatted_balance()), "{table}");
+    assert!(table.contains("Value (USD)"), "{table}");
+
+    let resp = SuiClientCommands::Balance {
+        address: Some(address.into()),
+        coin_type: Some("0x2::sui::SUI".to_string()),
+        prices: None,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::Balance(balances) = resp else {
+        panic!("Expected balances, got {resp}");
+    };
+    assert_eq!(balances[0].balance.total_balance, total as u128);
+    assert_eq!(balances[0].usd_price, None);
+    Ok(())
+}
+
+#[sim_test]
+async fn test_watch_only_command() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let context = &mut test_cluster.wallet;
+    let treasury = SuiAddress::random_for_testing_only();
+
+    // Addresses with keys in the keystore are not watch-only.
+    assert!(SuiClientCommands::WatchOnly {
+        cmd: WatchOnlyCommand::Add {
+            address: address.into(),
+        },
+    }
+    .execute(context)
+    .await
+    .is_err());
+
+    // Fund the treasury, which has no key.
+    let coin = context.gas_objects(address).await?[0].1.object_id;
+    SuiClientCommands::TransferSui {
+        to: treasury.into(),
+        sui_coin_object_id: coin,
+        gas_budget: 1000,
+        amount: Some(10000),
+    }
+    .execute(context)
+    .await?;
+    SuiClientCommands::WatchOnly {
+        cmd: WatchOnlyCommand::Add {
+            address: treasury.into(),
+        },
+    }
+    .execute(context)
+    .await?;
+    let config = SuiClientConfig::load(context.config.path())?;
+    assert!(config.watch_only.contains(&treasury));
+
+    // The coins and balances of watch-only addresses can be queried.
+    let resp = SuiClientCommands::Gas {
+        address: Some(treasury.into()),
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::Gas(coins) = resp else {
+        panic!("Expected gas coins, got {resp}");
+    };
+    assert_eq!(coins.len(), 1);
+    assert_eq!(coins[0].value(), 10000);
+    let treasury_coin = *coins[0].id();
+
+    let resp = SuiClientCommands::Balance {
+        address: Some(treasury.into()),
+        coin_type: None,
+        prices: None,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::Balance(balances) = resp else {
+        panic!("Expected balances, got {resp}");
+    };
+    assert_eq!(balances.len(), 1);
+    assert_eq!(balances[0].balance.coin_object_count, 1);
+    assert_eq!(balances[0].balance.total_balance, 10000);
+
+    // Their transactions can be dry run, but not signed.
+    let resp = SuiClientCommands::SerializeTransferSui {
+        to: address.into(),
+        sui_coin_object_id: treasury_coin,
+        gas_budget: 1000,
+        amount: Some(1),
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::SerializeTransferSui(tx_bytes) = resp else {
+        panic!("Expected a serialized transaction, got {resp}");
+    };
+    let resp = SuiClientCommands::DryRun { tx_bytes }
+        .execute(context)
+        .await?;
+    let SuiClientCommandResult::DryRun(response) = resp else {
+        panic!("Expected a dry run, got {resp}");
+    };
+    assert!(response.effects.status().is_ok());
+
+    let err = SuiClientCommands::TransferSui {
+        to: address.into(),
+        sui_coin_object_id: treasury_coin,
+        gas_budget: 1000,
+        amount: Some(1),
+    }
+    .execute(context)
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("is watch-only"), "{err}");
+
+    // A watch-only address can be the active address, until it is removed.
+    SuiClientCommands::Switch {
+        address: Some(treasury.into()),
+        env: None,
+    }
+    .execute(context)
+    .await?;
+    assert_eq!(context.active_address()?, treasury);
+    SuiClientCommands::WatchOnly {
+        cmd: WatchOnlyCommand::Remove {
+            address: treasury.into(),
+        },
+    }
+    .execute(context)
+    .await?;
+    assert_ne!(context.active_address()?, treasury);
+    assert!(SuiClientConfig::load(context.config.path())?
+        .watch_only
+        .is_empty());
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_stake_with_none_amount() -> Result<(), anyhow::Error> {
     let mut test_cluster = TestClusterBuilder::new().build().await?;
@@ -1869,6 +2560,7 @@ async fn test_stake_with_none_amount() -> Result<(), anyhow::Error> {
         &validator_addr.to_string(),
         "--gas-budget",
         "1000000000",
+        "--yes",
     ])
     .await?;
 
@@ -1921,6 +2613,7 @@ async fn test_stake_with_u64_amount() -> Result<(), anyhow::Error> {
         &validator_addr.to_string(),
         "--gas-budget",
         "1000000000",
+        "--yes",
     ])
     .await?;
 
@@ -0,0 +1,45 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeSet;
+
+use clap::{Command, CommandFactory};
+use clap_complete::Shell;
+
+use crate::completion::{write_completion_script, ADDRESS_OPTIONS, OBJECT_OPTIONS};
+use crate::sui_commands::SuiCommand;
+
+fn long_options(app: &Command, options: &mut BTreeSet<String>) {
+    options.extend(
+        app.get_arguments()
+            .filter_map(|arg| arg.get_long())
+            .map(str::to_string),
+    );
+    for subcommand in app.get_subcommands() {
+        long_options(subcommand, options);
+    }
+}
+
+#[test]
+fn test_dynamic_options_exist() {
+    let mut options = BTreeSet::new();
+    long_options(&SuiCommand::command(), &mut options);
+    for option in ADDRESS_OPTIONS.iter().chain(OBJECT_OPTIONS) {
+        assert!(options.contains(*option), "No option --{option}");
+    }
+}
+
+#[test]
+fn test_completion_scripts() {
+    for (shell, dynamic) in [
+        (Shell::Bash, "complete -F _sui_dynamic"),
+        (Shell::Zsh, "compdef _sui_dynamic sui"),
+        (Shell::Fish, "complete -c sui -l gas -xa"),
+    ] {
+        let mut script = Vec::new();
+        write_completion_script(shell, &mut SuiCommand::command(), "sui", &mut script).unwrap();
+        let script = String::from_utf8(script).unwrap();
+        assert!(script.contains("completion-values objects"), "{shell}");
+        assert!(script.contains(dynamic), "{shell}");
+    }
+}
@@ -0,0 +1,6 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module app::app {
+    public fun value(): u64 { base::base::value() }
+}
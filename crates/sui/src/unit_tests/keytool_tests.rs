@@ -8,10 +8,13 @@ use crate::keytool::read_keypair_from_file;
 
 use super::write_keypair_to_file;
 use super::KeyToolCommand;
+use super::KeyToolCommandResult;
+use crate::output::{OutputFormat, PrintableResult};
 use fastcrypto::encoding::Base64;
 use fastcrypto::encoding::Encoding;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
+use serde_json::json;
 use shared_crypto::intent::Intent;
 use shared_crypto::intent::IntentScope;
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
@@ -46,7 +49,25 @@ fn test_addresses_command() -> Result<(), anyhow::Error> {
     }
 
     // List all addresses with flag
-    KeyToolCommand::List.execute(&mut keystore).unwrap();
+    let result = KeyToolCommand::List.execute(&mut keystore).unwrap();
+    let KeyToolCommandResult::List(keys) = &result else {
+        panic!("Expected a key list, got {result}");
+    };
+    assert_eq!(keys.len(), 6);
+
+    // Each key is listed in the table, and as an object of the JSON output.
+    let table = result.render(OutputFormat::Table);
+    let json: serde_json::Value = serde_json::from_str(&result.render(OutputFormat::Json))?;
+    let entries = json.as_array().unwrap();
+    assert_eq!(entries.len(), 6);
+    for (entry, address) in entries.iter().zip(keystore.addresses()) {
+        assert!(table.contains(&address.to_string()));
+        assert_eq!(entry["suiAddress"], json!(address));
+        assert!(matches!(
+            entry["scheme"].as_str(),
+            Some("ed25519" | "secp256k1")
+        ));
+    }
     Ok(())
 }
 
@@ -205,6 +226,8 @@ fn test_mnemonics_ed25519() -> Result<(), anyhow::Error> {
             mnemonic_phrase: t[0].to_string(),
             key_scheme: SignatureScheme::ED25519,
             derivation_path: None,
+            count: None,
+            custom_derivation_path: false,
         }
         .execute(&mut keystore)?;
         let kp = SuiKeyPair::decode_base64(t[1]).unwrap();
@@ -228,6 +251,8 @@ fn test_mnemonics_secp256k1() -> Result<(), anyhow::Error> {
             mnemonic_phrase: t[0].to_string(),
             key_scheme: SignatureScheme::Secp256k1,
             derivation_path: None,
+            count: None,
+            custom_derivation_path: false,
         }
         .execute(&mut keystore)?;
         let kp = SuiKeyPair::decode_base64(t[1]).unwrap();
@@ -245,6 +270,8 @@ fn test_invalid_derivation_path() -> Result<(), anyhow::Error> {
         mnemonic_phrase: TEST_MNEMONIC.to_string(),
         key_scheme: SignatureScheme::ED25519,
         derivation_path: Some("m/44'/1'/0'/0/0".parse().unwrap()),
+        count: None,
+        custom_derivation_path: false,
     }
     .execute(&mut keystore)
     .is_err());
@@ -253,6 +280,8 @@ fn test_invalid_derivation_path() -> Result<(), anyhow::Error> {
         mnemonic_phrase: TEST_MNEMONIC.to_string(),
         key_scheme: SignatureScheme::ED25519,
         derivation_path: Some("m/0'/784'/0'/0/0".parse().unwrap()),
+        count: None,
+        custom_derivation_path: false,
     }
     .execute(&mut keystore)
     .is_err());
@@ -261,6 +290,8 @@ fn test_invalid_derivation_path() -> Result<(), anyhow::Error> {
         mnemonic_phrase: TEST_MNEMONIC.to_string(),
         key_scheme: SignatureScheme::ED25519,
         derivation_path: Some("m/54'/784'/0'/0/0".parse().unwrap()),
+        count: None,
+        custom_derivation_path: false,
     }
     .execute(&mut keystore)
     .is_err());
@@ -269,6 +300,8 @@ fn test_invalid_derivation_path() -> Result<(), anyhow::Error> {
         mnemonic_phrase: TEST_MNEMONIC.to_string(),
         key_scheme: SignatureScheme::Secp256k1,
         derivation_path: Some("m/54'/784'/0'/0'/0'".parse().unwrap()),
+        count: None,
+        custom_derivation_path: false,
     }
     .execute(&mut keystore)
     .is_err());
@@ -277,6 +310,8 @@ fn test_invalid_derivation_path() -> Result<(), anyhow::Error> {
         mnemonic_phrase: TEST_MNEMONIC.to_string(),
         key_scheme: SignatureScheme::Secp256k1,
         derivation_path: Some("m/44'/784'/0'/0/0".parse().unwrap()),
+        count: None,
+        custom_derivation_path: false,
     }
     .execute(&mut keystore)
     .is_err());
@@ -291,6 +326,8 @@ fn test_valid_derivation_path() -> Result<(), anyhow::Error> {
         mnemonic_phrase: TEST_MNEMONIC.to_string(),
         key_scheme: SignatureScheme::ED25519,
         derivation_path: Some("m/44'/784'/0'/0'/0'".parse().unwrap()),
+        count: None,
+        custom_derivation_path: false,
     }
     .execute(&mut keystore)
     .is_ok());
@@ -299,6 +336,8 @@ fn test_valid_derivation_path() -> Result<(), anyhow::Error> {
         mnemonic_phrase: TEST_MNEMONIC.to_string(),
         key_scheme: SignatureScheme::ED25519,
         derivation_path: Some("m/44'/784'/0'/0'/1'".parse().unwrap()),
+        count: None,
+        custom_derivation_path: false,
     }
     .execute(&mut keystore)
     .is_ok());
@@ -307,6 +346,8 @@ fn test_valid_derivation_path() -> Result<(), anyhow::Error> {
         mnemonic_phrase: TEST_MNEMONIC.to_string(),
         key_scheme: SignatureScheme::ED25519,
         derivation_path: Some("m/44'/784'/1'/0'/1'".parse().unwrap()),
+        count: None,
+        custom_derivation_path: false,
     }
     .execute(&mut keystore)
     .is_ok());
@@ -315,6 +356,8 @@ fn test_valid_derivation_path() -> Result<(), anyhow::Error> {
         mnemonic_phrase: TEST_MNEMONIC.to_string(),
         key_scheme: SignatureScheme::Secp256k1,
         derivation_path: Some("m/54'/784'/0'/0/1".parse().unwrap()),
+        count: None,
+        custom_derivation_path: false,
     }
     .execute(&mut keystore)
     .is_ok());
@@ -323,12 +366,97 @@ fn test_valid_derivation_path() -> Result<(), anyhow::Error> {
         mnemonic_phrase: TEST_MNEMONIC.to_string(),
         key_scheme: SignatureScheme::Secp256k1,
         derivation_path: Some("m/54'/784'/1'/0/1".parse().unwrap()),
+        count: None,
+        custom_derivation_path: false,
     }
     .execute(&mut keystore)
     .is_ok());
     Ok(())
 }
 
+#[test]
+fn test_import_batch_of_keys() -> Result<(), anyhow::Error> {
+    let mut keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(0));
+    KeyToolCommand::Import {
+        mnemonic_phrase: TEST_MNEMONIC.to_string(),
+        key_scheme: SignatureScheme::ED25519,
+        derivation_path: None,
+        count: Some(3),
+        custom_derivation_path: false,
+    }
+    .execute(&mut keystore)?;
+    assert_eq!(keystore.addresses().len(), 3);
+
+    // Each derived key matches a single import at the corresponding index.
+    for index in 0..3 {
+        let mut single = Keystore::from(InMemKeystore::new_insecure_for_tests(0));
+        let address = single.import_from_mnemonic(
+            TEST_MNEMONIC,
+            SignatureScheme::ED25519,
+            Some(format!("m/44'/784'/0'/0'/{index}'").parse().unwrap()),
+        )?;
+        assert!(keystore.addresses().contains(&address));
+    }
+
+    assert!(KeyToolCommand::Import {
+        mnemonic_phrase: TEST_MNEMONIC.to_string(),
+        key_scheme: SignatureScheme::ED25519,
+        derivation_path: None,
+        count: Some(0),
+        custom_derivation_path: false,
+    }
+    .execute(&mut keystore)
+    .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_custom_derivation_path() -> Result<(), anyhow::Error> {
+    let mut keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(0));
+    // Non-Sui purpose and coin type are only accepted with the custom path flag.
+    assert!(KeyToolCommand::Import {
+        mnemonic_phrase: TEST_MNEMONIC.to_string(),
+        key_scheme: SignatureScheme::Secp256k1,
+        derivation_path: Some("m/44'/60'/0'/0/0".parse().unwrap()),
+        count: None,
+        custom_derivation_path: false,
+    }
+    .execute(&mut keystore)
+    .is_err());
+
+    KeyToolCommand::Import {
+        mnemonic_phrase: TEST_MNEMONIC.to_string(),
+        key_scheme: SignatureScheme::Secp256k1,
+        derivation_path: Some("m/44'/60'/0'/0/0".parse().unwrap()),
+        count: Some(2),
+        custom_derivation_path: true,
+    }
+    .execute(&mut keystore)?;
+    assert_eq!(keystore.addresses().len(), 2);
+
+    // Ed25519 derivation still requires every level to be hardened.
+    assert!(KeyToolCommand::Import {
+        mnemonic_phrase: TEST_MNEMONIC.to_string(),
+        key_scheme: SignatureScheme::ED25519,
+        derivation_path: Some("m/44'/1'/0'/0/0".parse().unwrap()),
+        count: None,
+        custom_derivation_path: true,
+    }
+    .execute(&mut keystore)
+    .is_err());
+
+    KeyToolCommand::Import {
+        mnemonic_phrase: TEST_MNEMONIC.to_string(),
+        key_scheme: SignatureScheme::ED25519,
+        derivation_path: Some("m/44'/1'/0'".parse().unwrap()),
+        count: None,
+        custom_derivation_path: true,
+    }
+    .execute(&mut keystore)?;
+    assert_eq!(keystore.addresses().len(), 3);
+    Ok(())
+}
+
 #[test]
 fn test_keytool_bls12381() -> Result<(), anyhow::Error> {
     let mut keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(0));
@@ -336,6 +464,8 @@ fn test_keytool_bls12381() -> Result<(), anyhow::Error> {
         key_scheme: SignatureScheme::BLS12381,
         derivation_path: None,
         word_length: None,
+        count: None,
+        custom_derivation_path: false,
     }
     .execute(&mut keystore)?;
     Ok(())
@@ -0,0 +1,81 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use axum::body::Body;
+use axum::http::Request;
+use std::num::NonZeroUsize;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tower::ServiceExt;
+
+async fn start_swarm(fullnode_count: usize) -> Swarm {
+    let mut swarm = Swarm::builder()
+        .committee_size(NonZeroUsize::new(4).unwrap())
+        .with_fullnode_count(fullnode_count)
+        .build();
+    swarm.launch().await.unwrap();
+    swarm
+}
+
+async fn post(app: &Router, uri: &str) -> StatusCode {
+    let request = Request::post(uri).body(Body::empty()).unwrap();
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_advance_epoch() {
+    let swarm = start_swarm(0).await;
+    let app = router(&swarm).unwrap();
+    let observer = swarm.validator_node_handles().remove(0);
+    assert_eq!(observer.with(|node| node.current_epoch_for_testing()), 0);
+
+    assert_eq!(post(&app, ADVANCE_EPOCH_ROUTE).await, StatusCode::OK);
+    assert_eq!(observer.with(|node| node.current_epoch_for_testing()), 1);
+}
+
+#[tokio::test]
+async fn test_set_reference_gas_price() {
+    let swarm = start_swarm(1).await;
+    let app = router(&swarm).unwrap();
+    let uri = format!("{SET_REFERENCE_GAS_PRICE_ROUTE}?price=2000");
+    assert_eq!(post(&app, &uri).await, StatusCode::OK);
+    assert_eq!(post(&app, ADVANCE_EPOCH_ROUTE).await, StatusCode::OK);
+
+    let fullnode = swarm.fullnodes().next().unwrap();
+    let client = SuiClientBuilder::default()
+        .build(format!("http://{}", fullnode.json_rpc_address()))
+        .await
+        .unwrap();
+    let system_state = client
+        .governance_api()
+        .get_latest_sui_system_state()
+        .await
+        .unwrap();
+    assert_eq!(system_state.reference_gas_price, 2000);
+}
+
+#[tokio::test]
+async fn test_set_reference_gas_price_requires_fullnode() {
+    let swarm = start_swarm(0).await;
+    let app = router(&swarm).unwrap();
+    let uri = format!("{SET_REFERENCE_GAS_PRICE_ROUTE}?price=2000");
+    assert_eq!(post(&app, &uri).await, StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[tokio::test]
+async fn test_advance_clock() {
+    let swarm = start_swarm(0).await;
+    let app = router(&swarm).unwrap();
+    let uri = format!("{ADVANCE_CLOCK_ROUTE}?duration_ms=60000");
+    assert_eq!(post(&app, &uri).await, StatusCode::OK);
+
+    let system_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+    assert!(narwhal_types::now() >= system_time + 60_000);
+
+    // A malformed duration is rejected.
+    let uri = format!("{ADVANCE_CLOCK_ROUTE}?duration_ms=-1");
+    assert_eq!(post(&app, &uri).await, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,211 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use serde_json::json;
+use sui_types::base_types::ObjectID;
+
+use super::{parse_var, ExpandedCommand, PlanValue, PtbPlan, MAX_PLAN_COMMANDS};
+
+fn result(name: &str, index: Option<u16>) -> PlanValue {
+    PlanValue::Result {
+        name: name.to_string(),
+        index,
+    }
+}
+
+#[test]
+fn test_expand_plan() -> Result<(), anyhow::Error> {
+    let plan = PtbPlan::from_yaml(
+        r#"
+vars:
+  recipient: "0x42"
+  amount: 1000
+commands:
+  - split_coins:
+      coin: $gas
+      amounts: ["${amount}", "${amount}"]
+    result: coins
+  - repeat:
+      times: 2
+      var: i
+      commands:
+        - transfer_objects:
+            objects: ["$coins.${i}"]
+            address: "${recipient}"
+  - move_call:
+      package: "0x2"
+      module: coin
+      function: value
+      type_args: ["0x2::sui::SUI"]
+      args: ["$$escaped", "amount_${amount}"]
+"#,
+    )?;
+    let commands = plan.expand(&[])?;
+    assert_eq!(commands.len(), 4);
+
+    assert_eq!(
+        commands[0],
+        (
+            ExpandedCommand::SplitCoins {
+                coin: PlanValue::Gas,
+                amounts: vec![PlanValue::Value(json!(1000)), PlanValue::Value(json!(1000))],
+            },
+            Some("coins".to_string())
+        )
+    );
+    for i in 0..2 {
+        assert_eq!(
+            commands[1 + i],
+            (
+                ExpandedCommand::TransferObjects {
+                    objects: vec![result("coins", Some(i as u16))],
+                    address: PlanValue::Value(json!("0x42")),
+                },
+                None
+            )
+        );
+    }
+    let ExpandedCommand::MoveCall { package, args, type_args, .. } = &commands[3].0 else {
+        panic!("Expected a move call, got {:?}", commands[3]);
+    };
+    assert_eq!(*package, ObjectID::from_hex_literal("0x2")?);
+    assert_eq!(type_args.len(), 1);
+    assert_eq!(
+        *args,
+        vec![
+            PlanValue::Value(json!("$escaped")),
+            PlanValue::Value(json!("amount_1000")),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_expand_plan_from_json() -> Result<(), anyhow::Error> {
+    let plan = PtbPlan::from_yaml(
+        r#"{
+  "commands": [
+    { "merge_coins": { "coin": "0x5", "coins": ["0x6", "$split"] } }
+  ]
+}"#,
+    )?;
+    assert_eq!(
+        plan.expand(&[])?,
+        vec![(
+            ExpandedCommand::MergeCoins {
+                coin: PlanValue::Value(json!("0x5")),
+                coins: vec![PlanValue::Value(json!("0x6")), result("split", None)],
+            },
+            None
+        )]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_expand_plan_variables() -> Result<(), anyhow::Error> {
+    let plan = PtbPlan::from_yaml(
+        r#"
+vars:
+  count: 1
+commands:
+  - repeat:
+      times: 3
+      var: i
+      commands:
+        - split_coins:
+            coin: $gas
+            amounts: ["${count}"]
+          result: coin_${i}
+        - transfer_objects:
+            objects: ["$coin_${i}"]
+            address: "${to}"
+"#,
+    )?;
+
+    // Variables which are not defined by the plan must be set from the command line.
+    let err = plan.expand(&[]).unwrap_err();
+    assert!(err.to_string().contains("Undefined variable `to`"), "{err}");
+
+    let commands = plan.expand(&[parse_var("to=0x42")?, parse_var("count=7")?])?;
+    assert_eq!(commands.len(), 6);
+    assert_eq!(
+        commands[4],
+        (
+            ExpandedCommand::SplitCoins {
+                coin: PlanValue::Gas,
+                amounts: vec![PlanValue::Value(json!(7))],
+            },
+            Some("coin_2".to_string())
+        )
+    );
+    assert_eq!(
+        commands[5].0,
+        ExpandedCommand::TransferObjects {
+            objects: vec![result("coin_2", None)],
+            address: PlanValue::Value(json!("0x42")),
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_invalid_plans() {
+    let plan = PtbPlan::from_yaml("commands: []").unwrap();
+    assert!(plan.expand(&[]).is_err());
+
+    assert!(PtbPlan::from_yaml("commands:\n  - unknown_command: {}").is_err());
+
+    let plan = PtbPlan::from_yaml(&format!(
+        r#"
+commands:
+  - repeat:
+      times: {}
+      commands:
+        - split_coins:
+            coin: $gas
+            amounts: [1]
+"#,
+        MAX_PLAN_COMMANDS + 1
+    ))
+    .unwrap();
+    assert!(plan.expand(&[]).is_err());
+
+    // Repeats without commands, or running zero or too many times, are rejected before they are
+    // unrolled.
+    for (times, commands) in [
+        (u64::MAX, "[]"),
+        (0, "[{split_coins: {coin: $gas, amounts: [1]}}]"),
+        (2, "[{repeat: {times: 1, commands: []}}]"),
+    ] {
+        let plan = PtbPlan::from_yaml(&format!(
+            "commands:\n  - repeat: {{times: {times}, commands: {commands}}}"
+        ))
+        .unwrap();
+        let err = plan.expand(&[]).unwrap_err();
+        assert!(err.to_string().starts_with("A repeat"), "{err}");
+    }
+
+    let plan = PtbPlan::from_yaml(
+        r#"
+commands:
+  - transfer_objects:
+      objects: ["$coins.first"]
+      address: "0x42"
+"#,
+    )
+    .unwrap();
+    assert!(plan.expand(&[]).is_err());
+}
+
+#[test]
+fn test_parse_var() -> Result<(), anyhow::Error> {
+    assert_eq!(parse_var("amount=10")?, ("amount".to_string(), json!(10)));
+    assert_eq!(parse_var("to=0x42")?, ("to".to_string(), json!("0x42")));
+    assert_eq!(
+        parse_var("list=[1,2]")?,
+        ("list".to_string(), json!([1, 2]))
+    );
+    assert!(parse_var("no_value").is_err());
+    Ok(())
+}
@@ -26,6 +26,7 @@ use tap::tap::TapOptional;
 
 use crate::client_commands::WalletContext;
 use crate::fire_drill::get_gas_obj_ref;
+use crate::output::{OutputFormat, PrintableResult};
 use clap::*;
 use colored::Colorize;
 use fastcrypto::traits::ToFromBytes;
@@ -574,11 +575,7 @@ async fn call_0x5(
         rgp,
     )
     .unwrap();
-    let signature =
-        context
-            .config
-            .keystore
-            .sign_secure(&sender, &tx_data, Intent::sui_transaction())?;
+    let signature = context.sign_transaction(&sender, &tx_data)?;
     let transaction =
         Transaction::from_data(tx_data, Intent::sui_transaction(), vec![signature]).verify()?;
     sui_client
@@ -649,33 +646,22 @@ pub fn write_transaction_response(
 
 impl Debug for SuiValidatorCommandResponse {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let string = serde_json::to_string_pretty(self);
-        let s = match string {
-            Ok(s) => s,
-            Err(err) => format!("{err}").red().to_string(),
-        };
-        write!(f, "{}", s)
+        write!(f, "{}", self.render(OutputFormat::Json))
     }
 }
 
-impl SuiValidatorCommandResponse {
-    pub fn print(&self, pretty: bool) {
-        match self {
-            // Don't print empty responses
+impl PrintableResult for SuiValidatorCommandResponse {
+    fn structured(&self) -> Result<serde_json::Value, anyhow::Error> {
+        Ok(serde_json::to_value(self)?)
+    }
+
+    fn is_empty(&self) -> bool {
+        // Don't print empty responses
+        matches!(
+            self,
             SuiValidatorCommandResponse::MakeValidatorInfo
-            | SuiValidatorCommandResponse::DisplayMetadata => {}
-            other => {
-                let line = if pretty {
-                    format!("{other}")
-                } else {
-                    format!("{:?}", other)
-                };
-                // Log line by line
-                for line in line.lines() {
-                    println!("{line}");
-                }
-            }
-        }
+                | SuiValidatorCommandResponse::DisplayMetadata
+        )
     }
 }
 
@@ -0,0 +1,339 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Publishing of a workspace of interdependent local Move packages, for
+//! `sui client publish-workspace`.
+//!
+//! The packages are published in dependency order. Once a package is published, its ID is written
+//! to its manifest, as its `published-at` field and as the value of its named addresses left at
+//! `0x0`, so that the packages depending on it link against the published package.
+//!
+//! Publishing stops at the first package failing to publish. The packages published before it
+//! stay published, and their manifests hold their IDs, so the packages of the workspace which
+//! already have a `published-at` field are skipped, and running the command again resumes from
+//! the failed package.
+
+use crate::client_commands::{compile_package, WalletContext};
+use anyhow::{anyhow, bail, Context};
+use move_package::source_package::{
+    layout::SourcePackageLayout,
+    manifest_parser::parse_move_manifest_from_file,
+    parsed_manifest::{Dependency, DependencyKind},
+};
+use move_package::BuildConfig as MoveBuildConfig;
+use serde::Serialize;
+use shared_crypto::intent::Intent;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+use sui_json_rpc_types::{ObjectChange, SuiExecutionStatus, SuiTransactionBlockEffectsAPI};
+use sui_types::base_types::{ObjectID, TransactionDigest};
+use sui_types::messages::Transaction;
+use sui_types::move_package::UpgradeCap;
+use toml_edit::{value, Document};
+
+const PUBLISHED_AT: &str = "published-at";
+
+/// A package of the workspace.
+#[derive(Debug, Serialize)]
+pub struct WorkspacePackage {
+    pub name: String,
+    pub path: PathBuf,
+    /// The ID of the package, if its manifest has a `published-at` field.
+    pub published_at: Option<ObjectID>,
+    /// The packages of the workspace it depends on, by name.
+    pub dependencies: BTreeSet<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub enum PublishStatus {
+    /// The package was published before this run.
+    AlreadyPublished,
+    Published {
+        package_id: ObjectID,
+        upgrade_cap: Option<ObjectID>,
+        digest: TransactionDigest,
+    },
+    Failed(String),
+    /// The package was not published as an earlier package failed to publish.
+    Skipped,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WorkspacePublishReport {
+    pub packages: Vec<(WorkspacePackage, PublishStatus)>,
+}
+
+impl WorkspacePublishReport {
+    /// The package which failed to publish, and its error.
+    pub fn failure(&self) -> Option<(&str, &str)> {
+        self.packages
+            .iter()
+            .find_map(|(package, status)| match status {
+                PublishStatus::Failed(error) => Some((package.name.as_str(), error.as_str())),
+                _ => None,
+            })
+    }
+}
+
+impl Display for WorkspacePublishReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "----- Workspace publish ----")?;
+        for (package, status) in &self.packages {
+            match status {
+                PublishStatus::AlreadyPublished => writeln!(
+                    f,
+                    "{}: already published at {}",
+                    package.name,
+                    package
+                        .published_at
+                        .map_or_else(String::new, |id| id.to_string())
+                )?,
+                PublishStatus::Published {
+                    package_id,
+                    upgrade_cap,
+                    digest,
+                } => {
+                    writeln!(
+                        f,
+                        "{}: published at {package_id} by transaction {digest}",
+                        package.name
+                    )?;
+                    if let Some(upgrade_cap) = upgrade_cap {
+                        writeln!(f, "  upgrade capability: {upgrade_cap}")?;
+                    }
+                }
+                PublishStatus::Failed(error) => {
+                    writeln!(f, "{}: failed to publish, {error}", package.name)?
+                }
+                PublishStatus::Skipped => writeln!(f, "{}: not published", package.name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads the manifests of the packages at `package_paths`, and orders them so that every package
+/// comes after the packages of the workspace it depends on.
+pub fn workspace_publish_order(
+    package_paths: &[PathBuf],
+) -> Result<Vec<WorkspacePackage>, anyhow::Error> {
+    struct Manifest {
+        name: String,
+        path: PathBuf,
+        published_at: Option<ObjectID>,
+        local_dependencies: Vec<PathBuf>,
+    }
+
+    let mut manifests = vec![];
+    for path in package_paths {
+        let path = fs::canonicalize(path)
+            .with_context(|| format!("Cannot find the package at {}", path.display()))?;
+        let manifest =
+            parse_move_manifest_from_file(&path.join(SourcePackageLayout::Manifest.path()))?;
+        let published_at = manifest
+            .package
+            .custom_properties
+            .iter()
+            .find(|(property, _)| property.as_str() == PUBLISHED_AT)
+            .map(|(_, id)| {
+                ObjectID::from_hex_literal(id)
+                    .map_err(|e| anyhow!("Invalid '{PUBLISHED_AT}' field {id}: {e}"))
+            })
+            .transpose()?;
+        let local_dependencies = manifest
+            .dependencies
+            .values()
+            .filter_map(|dependency| match dependency {
+                Dependency::Internal(dependency) => match &dependency.kind {
+                    DependencyKind::Local(local) => Some(path.join(local)),
+                    _ => None,
+                },
+                Dependency::External(_) => None,
+            })
+            .filter_map(|dependency| fs::canonicalize(dependency).ok())
+            .collect();
+        manifests.push(Manifest {
+            name: manifest.package.name.to_string(),
+            path,
+            published_at,
+            local_dependencies,
+        });
+    }
+
+    let names: BTreeMap<_, _> = manifests
+        .iter()
+        .map(|manifest| (manifest.path.clone(), manifest.name.clone()))
+        .collect();
+    let mut remaining: Vec<_> = manifests
+        .into_iter()
+        .map(|manifest| WorkspacePackage {
+            dependencies: manifest
+                .local_dependencies
+                .iter()
+                .filter_map(|dependency| names.get(dependency).cloned())
+                .collect(),
+            name: manifest.name,
+            path: manifest.path,
+            published_at: manifest.published_at,
+        })
+        .collect();
+
+    let mut ordered: Vec<WorkspacePackage> = vec![];
+    while !remaining.is_empty() {
+        let ordered_names: BTreeSet<_> = ordered.iter().map(|p| p.name.clone()).collect();
+        let Some(next) = remaining
+            .iter()
+            .position(|package| package.dependencies.is_subset(&ordered_names)) else {
+            let names: Vec<_> = remaining.iter().map(|p| p.name.as_str()).collect();
+            bail!(
+                "The packages {} of the workspace depend on each other in a cycle",
+                names.join(", ")
+            );
+        };
+        ordered.push(remaining.remove(next));
+    }
+    Ok(ordered)
+}
+
+/// Publishes the packages at `package_paths` in dependency order, stopping at the first one
+/// failing to publish, see the [module documentation](self).
+pub async fn publish_workspace(
+    context: &mut WalletContext,
+    package_paths: &[PathBuf],
+    build_config: MoveBuildConfig,
+    gas: Option<ObjectID>,
+    gas_budget: u64,
+    skip_dependency_verification: bool,
+) -> Result<WorkspacePublishReport, anyhow::Error> {
+    let packages = workspace_publish_order(package_paths)?;
+    let mut report = WorkspacePublishReport { packages: vec![] };
+    for package in packages {
+        let status = if report.failure().is_some() {
+            PublishStatus::Skipped
+        } else if package.published_at.is_some() {
+            PublishStatus::AlreadyPublished
+        } else {
+            match publish_package(
+                context,
+                &package,
+                build_config.clone(),
+                gas,
+                gas_budget,
+                skip_dependency_verification,
+            )
+            .await
+            {
+                Ok((digest, package_id, upgrade_cap)) => {
+                    match record_published_id(&package.path, package_id) {
+                        Ok(()) => PublishStatus::Published {
+                            package_id,
+                            upgrade_cap,
+                            digest,
+                        },
+                        // The dependents cannot be published without the ID of the package.
+                        Err(e) => PublishStatus::Failed(format!(
+                            "published at {package_id} by transaction {digest}, but failed to \
+                             write its ID to its manifest: {e}"
+                        )),
+                    }
+                }
+                Err(e) => PublishStatus::Failed(e.to_string()),
+            }
+        };
+        report.packages.push((package, status));
+    }
+    Ok(report)
+}
+
+/// Publishes `package`, and returns the digest of the transaction, and the IDs of the package and
+/// of its upgrade capability.
+async fn publish_package(
+    context: &mut WalletContext,
+    package: &WorkspacePackage,
+    build_config: MoveBuildConfig,
+    gas: Option<ObjectID>,
+    gas_budget: u64,
+    skip_dependency_verification: bool,
+) -> Result<(TransactionDigest, ObjectID, Option<ObjectID>), anyhow::Error> {
+    let sender = context.try_get_object_owner(&gas).await?;
+    let sender = sender.unwrap_or(context.active_address()?);
+    let client = context.get_client().await?;
+    let (dependencies, compiled_modules, _, _) = compile_package(
+        &client,
+        build_config,
+        package.path.clone(),
+        false,
+        skip_dependency_verification,
+    )
+    .await?;
+    let data = client
+        .transaction_builder()
+        .publish(
+            sender,
+            compiled_modules,
+            dependencies.published.into_values().collect(),
+            gas,
+            gas_budget,
+        )
+        .await?;
+    let signature = context.sign_transaction(&sender, &data)?;
+    let response = context
+        .execute_transaction_block(
+            Transaction::from_data(data, Intent::sui_transaction(), vec![signature]).verify()?,
+        )
+        .await?;
+
+    if let Some(effects) = &response.effects {
+        if let SuiExecutionStatus::Failure { error } = effects.status() {
+            bail!("{error}");
+        }
+    }
+    let changes = response.object_changes.as_deref().unwrap_or_default();
+    let package_id = changes
+        .iter()
+        .find_map(|change| match change {
+            ObjectChange::Published { package_id, .. } => Some(*package_id),
+            _ => None,
+        })
+        .ok_or_else(|| anyhow!("The transaction {} published no package", response.digest))?;
+    let upgrade_cap = changes.iter().find_map(|change| match change {
+        ObjectChange::Created {
+            object_type,
+            object_id,
+            ..
+        } if *object_type == UpgradeCap::type_() => Some(*object_id),
+        _ => None,
+    });
+    Ok((response.digest, package_id, upgrade_cap))
+}
+
+/// Writes `package_id` to the manifest of the package at `package_path`, as its `published-at`
+/// field and as the value of its named addresses left at `0x0`.
+pub fn record_published_id(package_path: &Path, package_id: ObjectID) -> Result<(), anyhow::Error> {
+    let manifest_path = package_path.join(SourcePackageLayout::Manifest.path());
+    let mut manifest: Document = fs::read_to_string(&manifest_path)?.parse()?;
+    let id = package_id.to_hex_uncompressed();
+    manifest["package"][PUBLISHED_AT] = value(id.clone());
+    if let Some(addresses) = manifest
+        .get_mut("addresses")
+        .and_then(|addresses| addresses.as_table_like_mut())
+    {
+        let unassigned: Vec<_> = addresses
+            .iter()
+            .filter(|(_, address)| {
+                address
+                    .as_str()
+                    .and_then(|address| ObjectID::from_hex_literal(address).ok())
+                    == Some(ObjectID::ZERO)
+            })
+            .map(|(name, _)| name.to_owned())
+            .collect();
+        for name in unassigned {
+            addresses.insert(&name, value(id.clone()));
+        }
+    }
+    fs::write(&manifest_path, manifest.to_string())?;
+    Ok(())
+}
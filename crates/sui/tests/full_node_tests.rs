@@ -15,12 +15,13 @@ use serde_json::json;
 use sui::client_commands::{SuiClientCommandResult, SuiClientCommands, WalletContext};
 use sui_json_rpc_types::EventFilter;
 use sui_json_rpc_types::{
-    type_and_fields_from_move_struct, SuiEvent, SuiExecutionStatus, SuiTransactionBlockEffectsAPI,
-    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    type_and_fields_from_move_struct, DynamicFieldExportPage, ObjectChange, OwnedObjectChange,
+    SuiEvent, SuiExecutionStatus, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions, SuiTransactionBlockStatus,
 };
 use sui_keys::keystore::AccountKeystore;
 use sui_macros::*;
-use sui_node::SuiNode;
+use sui_node::{ConfigReload, SuiNode};
 use sui_tool::restore_from_db_checkpoint;
 use sui_types::base_types::{ObjectRef, SequenceNumber};
 use sui_types::crypto::{get_key_pair, SuiKeyPair};
@@ -34,6 +35,7 @@ use sui_types::object::{Object, ObjectRead, Owner, PastObjectRead};
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::query::TransactionFilter;
 use sui_types::utils::to_sender_signed_transaction_with_multi_signers;
+use sui_types::SUI_SYSTEM_STATE_OBJECT_ID;
 use sui_types::{base_types::ObjectID, messages::TransactionInfoRequest};
 use test_utils::authority::test_and_configure_authority_configs;
 use test_utils::messages::make_transactions_with_wallet_context;
@@ -645,6 +647,147 @@ async fn test_full_node_sub_and_query_move_event_ok() -> Result<(), anyhow::Erro
     Ok(())
 }
 
+#[sim_test]
+async fn test_full_node_sub_owned_object_changes_ok() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new()
+        .enable_fullnode_events()
+        .build()
+        .await?;
+
+    let fullnode = start_fullnode_from_config(
+        test_cluster
+            .fullnode_config_builder()
+            .with_event_store()
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    let node = fullnode.sui_node;
+    let ws_client = fullnode.ws_client;
+
+    let context = &mut test_cluster.wallet;
+    let receiver = context.config.keystore.addresses().get(1).cloned().unwrap();
+    let mut sub: Subscription<OwnedObjectChange> = ws_client
+        .subscribe(
+            "suix_subscribeOwnedObjectChanges",
+            rpc_params![receiver],
+            "suix_unsubscribeOwnedObjectChanges",
+        )
+        .await
+        .unwrap();
+
+    let (transferred_object, sender, receiver_, digest, _, _) = transfer_coin(context).await?;
+    assert_eq!(receiver, receiver_);
+    wait_for_tx(digest, node.state().clone()).await;
+
+    // Only the coin received is notified to the receiver, not the gas coin of the sender
+    match timeout(Duration::from_secs(5), sub.next()).await {
+        Ok(Some(Ok(OwnedObjectChange {
+            address,
+            transaction_digest,
+            change:
+                ObjectChange::Transferred {
+                    sender: sender_,
+                    recipient,
+                    object_id,
+                    ..
+                },
+        }))) => {
+            assert_eq!(address, receiver);
+            assert_eq!(transaction_digest, digest);
+            assert_eq!(sender_, sender);
+            assert_eq!(recipient, Owner::AddressOwner(receiver));
+            assert_eq!(object_id, transferred_object);
+        }
+        other => panic!("Failed to get OwnedObjectChange, but {:?}", other),
+    };
+    match timeout(Duration::from_secs(5), sub.next()).await {
+        Err(_) => (),
+        other => panic!("Expect to time out because no new changes are coming in. Got {other:?}"),
+    }
+    Ok(())
+}
+
+#[sim_test]
+async fn test_full_node_sub_transaction_status_ok() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+
+    let fullnode =
+        start_fullnode_from_config(test_cluster.fullnode_config_builder().build().unwrap())
+            .await
+            .unwrap();
+    let node = fullnode.sui_node;
+    let ws_client = fullnode.ws_client;
+
+    let context = &mut test_cluster.wallet;
+    let (_, _, _, digest, _, _) = transfer_coin(context).await?;
+    wait_for_tx(digest, node.state().clone()).await;
+
+    let mut sub: Subscription<SuiTransactionBlockStatus> = ws_client
+        .subscribe(
+            "suix_subscribeTransactionStatus",
+            rpc_params![digest],
+            "suix_unsubscribeTransactionStatus",
+        )
+        .await
+        .unwrap();
+
+    // The transaction is executed already, the statuses left are pushed until it is finalized
+    loop {
+        match timeout(Duration::from_secs(30), sub.next()).await {
+            Ok(Some(Ok(SuiTransactionBlockStatus::Finalized { checkpoint }))) => {
+                let (_, expected) = node
+                    .state()
+                    .get_transaction_checkpoint_sequence(&digest)?
+                    .unwrap();
+                assert_eq!(checkpoint, expected);
+                break;
+            }
+            Ok(Some(Ok(SuiTransactionBlockStatus::Seen))) => {
+                panic!("Executed transaction reported as only seen")
+            }
+            Ok(Some(Ok(_))) => (),
+            other => panic!("Failed to get SuiTransactionBlockStatus, but {:?}", other),
+        }
+    }
+    Ok(())
+}
+
+#[sim_test]
+async fn test_full_node_export_dynamic_fields_ok() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await?;
+    let fullnode =
+        start_fullnode_from_config(test_cluster.fullnode_config_builder().build().unwrap())
+            .await
+            .unwrap();
+    let ws_client = fullnode.ws_client;
+
+    // The system state object holds its inner state in a dynamic field.
+    let mut sub: Subscription<DynamicFieldExportPage> = ws_client
+        .subscribe(
+            "suix_exportDynamicFields",
+            rpc_params![SUI_SYSTEM_STATE_OBJECT_ID, None::<ObjectID>, 1],
+            "suix_unsubscribeExportDynamicFields",
+        )
+        .await
+        .unwrap();
+    let page = match timeout(Duration::from_secs(30), sub.next()).await {
+        Ok(Some(Ok(page))) => page,
+        other => panic!("Failed to get DynamicFieldExportPage, but {:?}", other),
+    };
+    assert_eq!(page.data.len(), 1);
+    assert!(!page.has_next_page);
+    assert!(page.data[0].value.get("epoch").is_some());
+
+    // The subscription is closed after the last page.
+    match timeout(Duration::from_secs(30), sub.next()).await {
+        Ok(None) => (),
+        other => panic!("Expect the export to be complete, but got {:?}", other),
+    }
+    Ok(())
+}
+
 // Test fullnode has event read jsonrpc endpoints working
 #[sim_test]
 #[ignore]
@@ -1086,3 +1229,56 @@ async fn test_full_node_bootstrap_from_snapshot() -> Result<(), anyhow::Error> {
     wait_for_tx(digest_after_restore, node.state().clone()).await;
     Ok(())
 }
+
+#[sim_test]
+async fn test_full_node_reload_config() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await?;
+    let config = test_cluster.fullnode_config_builder().build()?;
+    let node = start_fullnode_from_config(config.clone()).await?.sui_node;
+
+    let reload = node.reload_config(config.clone()).await?;
+    assert_eq!(reload, ConfigReload::default());
+
+    // Changes of the reloadable fields are applied without a restart.
+    let mut new_config = config.clone();
+    new_config.rpc_limits.max_concurrent_requests = Some(10);
+    new_config.p2p_config.allowlisted_peers = vec![anemo::PeerId([1; 32])];
+    let reload = node.reload_config(new_config.clone()).await?;
+    assert_eq!(
+        reload.applied,
+        vec!["rpc-limits", "p2p-config.allowlisted-peers"]
+    );
+    assert!(!reload.requires_restart);
+
+    // Changes of the other fields are not applied, and require a restart.
+    new_config
+        .json_rpc_address
+        .set_port(new_config.json_rpc_address.port() + 1);
+    let reload = node.reload_config(new_config).await?;
+    assert!(reload.applied.is_empty());
+    assert!(reload.requires_restart);
+    Ok(())
+}
+
+#[sim_test]
+async fn test_full_node_health_checks() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let node = test_cluster.fullnode_handle.sui_node.clone();
+    let (_transferred_object, _, _, digest, ..) = transfer_coin(&mut test_cluster.wallet).await?;
+    wait_for_tx(digest, node.state().clone()).await;
+
+    let liveness = node.liveness();
+    assert!(liveness.healthy, "{liveness:?}");
+    let checks: Vec<_> = liveness.checks.iter().map(|check| check.name).collect();
+    assert_eq!(checks, vec!["db"]);
+
+    let readiness = node.readiness();
+    assert!(readiness.healthy, "{readiness:?}");
+    let checks: Vec<_> = readiness.checks.iter().map(|check| check.name).collect();
+    assert_eq!(checks, vec!["db", "checkpoint_lag", "json_rpc"]);
+    // The lag is measured against the checkpoints known from the validators.
+    assert!(readiness.checks[1]
+        .detail
+        .starts_with("highest known checkpoint Some("));
+    Ok(())
+}
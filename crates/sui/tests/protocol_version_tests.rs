@@ -404,6 +404,7 @@ mod sim_only_tests {
                 Base64::from_bytes(&bcs::to_bytes(&txn).unwrap()),
                 /* gas_price */ None,
                 /* epoch_id */ None,
+                /* clock_timestamp_ms */ None,
             )
             .await
             .unwrap();
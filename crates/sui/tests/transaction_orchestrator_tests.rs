@@ -2,13 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use prometheus::Registry;
+use std::collections::BTreeSet;
+use sui_config::node::TransactionPolicyConfig;
 use sui_core::authority_client::NetworkAuthorityClient;
 use sui_core::transaction_orchestrator::TransactiondOrchestrator;
 use sui_macros::sim_test;
 use sui_types::crypto::{get_key_pair, AccountKeyPair};
 use sui_types::messages::{
     ExecuteTransactionRequest, ExecuteTransactionRequestType, ExecuteTransactionResponse,
-    FinalizedEffects, TransactionData, VerifiedTransaction,
+    FinalizedEffects, TransactionData, TransactionDataAPI, VerifiedTransaction,
 };
 use sui_types::object::generate_test_gas_objects_with_owner;
 use sui_types::quorum_driver_types::QuorumDriverError;
@@ -36,6 +38,8 @@ async fn test_blocking_execution() -> Result<(), anyhow::Error> {
         reconfig_channel,
         temp_dir.path(),
         &Registry::new(),
+        &Default::default(),
+        &Default::default(),
     )
     .await
     .unwrap();
@@ -84,6 +88,59 @@ async fn test_blocking_execution() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_transaction_policy() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let context = &mut test_cluster.wallet;
+    let node = &test_cluster.fullnode_handle.sui_node;
+
+    let mut txns = make_transactions_with_wallet_context(context, 1).await;
+    let txn = txns.swap_remove(0);
+    let sender = txn.data().transaction_data().sender();
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let orchestrator = TransactiondOrchestrator::new_with_network_clients(
+        node.state(),
+        node.subscribe_to_epoch_change(),
+        temp_dir.path(),
+        &Registry::new(),
+        &Default::default(),
+        &TransactionPolicyConfig {
+            deny_senders: BTreeSet::from([sender]),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let res = execute_with_orchestrator(
+        &orchestrator,
+        txn.clone(),
+        ExecuteTransactionRequestType::WaitForEffectsCert,
+    )
+    .await;
+    assert!(matches!(
+        res,
+        Err(QuorumDriverError::TransactionDeniedByPolicy { .. })
+    ));
+
+    // The transaction goes through once its sender is allowed instead.
+    orchestrator
+        .transaction_policy()
+        .update_config(TransactionPolicyConfig {
+            allow_senders: Some(BTreeSet::from([sender])),
+            ..Default::default()
+        });
+    execute_with_orchestrator(
+        &orchestrator,
+        txn,
+        ExecuteTransactionRequestType::WaitForEffectsCert,
+    )
+    .await
+    .unwrap();
+    Ok(())
+}
+
 #[sim_test]
 async fn test_fullnode_wal_log() -> Result<(), anyhow::Error> {
     telemetry_subscribers::init_for_testing();
@@ -102,6 +159,8 @@ async fn test_fullnode_wal_log() -> Result<(), anyhow::Error> {
         reconfig_channel,
         temp_dir.path(),
         &Registry::new(),
+        &Default::default(),
+        &Default::default(),
     )
     .await
     .unwrap();
@@ -34,6 +34,7 @@
 //! ## Features
 //! - `json` - Bunyan formatter - JSON log output, optional
 //! - `tokio-console` - [Tokio-console](https://github.com/tokio-rs/console) subscriber, optional
+//! - `otlp` - Export spans to an [OpenTelemetry](https://opentelemetry.io) collector, optional
 //!
 //! ### Stdout vs file output
 //!
@@ -64,6 +65,13 @@
 //! separate from `RUST_LOG`, so that you can separately control the logging verbosity from the level of
 //! spans that are to be recorded and traced.
 //!
+//! ### Distributed tracing
+//!
+//! With the `otlp` feature, spans are exported over OTLP/gRPC to the collector at `otlp_endpoint`
+//! (or the `OTEL_EXPORTER_OTLP_ENDPOINT` env var if using config `with_env()` method), subject to
+//! the same `span_level` as span latencies. Use [trace_context] to continue the traces of callers
+//! sending a W3C `traceparent`, and to propagate them to downstream services.
+//!
 //! ### Live async inspection / Tokio Console
 //!
 //! [Tokio-console](https://github.com/tokio-rs/console) is an awesome CLI tool designed to analyze and help debug Rust apps using Tokio, in real time!  It relies on a special subscriber.
@@ -99,6 +107,7 @@ use tracing_subscriber::{filter, fmt, layer::SubscriberExt, reload, EnvFilter, L
 use crossterm::tty::IsTty;
 
 pub mod span_latency_prom;
+pub mod trace_context;
 
 /// Alias for a type-erased error type.
 pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
@@ -127,6 +136,9 @@ pub struct TelemetryConfig {
     pub crash_on_panic: bool,
     /// Optional Prometheus registry - if present, all enabled span latencies are measured
     pub prom_registry: Option<prometheus::Registry>,
+    /// If defined, export spans to the OpenTelemetry collector at this endpoint, e.g.
+    /// `http://localhost:4317`. Requires the `otlp` feature.
+    pub otlp_endpoint: Option<String>,
 }
 
 #[must_use]
@@ -212,6 +224,7 @@ impl TelemetryConfig {
             panic_hook: true,
             crash_on_panic: false,
             prom_registry: None,
+            otlp_endpoint: None,
         }
     }
 
@@ -240,6 +253,11 @@ impl TelemetryConfig {
         self
     }
 
+    pub fn with_otlp_endpoint(mut self, endpoint: &str) -> Self {
+        self.otlp_endpoint = Some(endpoint.to_owned());
+        self
+    }
+
     pub fn with_env(mut self) -> Self {
         if env::var("CRASH_ON_PANIC").is_ok() {
             self.crash_on_panic = true
@@ -262,6 +280,10 @@ impl TelemetryConfig {
             self.log_file = Some(filepath);
         }
 
+        if let Ok(endpoint) = env::var("OTEL_EXPORTER_OTLP_ENDPOINT") {
+            self.otlp_endpoint = Some(endpoint);
+        }
+
         self
     }
 
@@ -302,6 +324,30 @@ impl TelemetryConfig {
             layers.push(span_lat_layer.with_filter(span_filter.clone()).boxed());
         }
 
+        // OpenTelemetry layer, exporting spans in batches from a tokio task.
+        #[cfg(feature = "otlp")]
+        if let Some(endpoint) = &config.otlp_endpoint {
+            use opentelemetry_otlp::WithExportConfig;
+
+            let tracer = opentelemetry_otlp::new_pipeline()
+                .tracing()
+                .with_exporter(
+                    opentelemetry_otlp::new_exporter()
+                        .tonic()
+                        .with_endpoint(endpoint),
+                )
+                .install_batch(opentelemetry::runtime::Tokio)
+                .expect("Could not initialize OTLP exporter");
+            let otlp_layer = tracing_opentelemetry::layer().with_tracer(tracer);
+            layers.push(otlp_layer.with_filter(span_filter.clone()).boxed());
+        }
+        #[cfg(not(feature = "otlp"))]
+        if config.otlp_endpoint.is_some() {
+            eprintln!(
+                "otlp_endpoint is set, but telemetry-subscribers is built without the otlp feature"
+            );
+        }
+
         let (nb_output, worker_guard) = get_output(config.log_file.clone());
         if config.json_log_output {
             // Output to file or to stderr in a newline-delimited JSON format
@@ -0,0 +1,163 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! [W3C trace context](https://www.w3.org/TR/trace-context/) propagation.
+//!
+//! Services receiving a `traceparent` header (or gRPC metadata entry) make their request span a
+//! child of the remote span with [set_remote_parent], and send [current_traceparent] to the
+//! services they call, so that the spans of all services handling a request form one trace.
+//!
+//! The trace id is recorded in the `trace_id` field of the span if it declares one, so that logs
+//! can be correlated even without exporting spans. Spans are only linked to their remote parent
+//! when the `otlp` feature is enabled and an OTLP endpoint is configured.
+
+use std::fmt;
+use std::str::FromStr;
+
+use tracing::Span;
+
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+pub const TRACESTATE_HEADER: &str = "tracestate";
+
+/// Name of the span field recording the trace id of the remote parent.
+pub const TRACE_ID_FIELD: &str = "trace_id";
+
+/// A `traceparent` value of version `00`: `00-<trace id>-<parent span id>-<trace flags>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TraceParent {
+    pub trace_id: u128,
+    pub parent_id: u64,
+    pub flags: u8,
+}
+
+impl TraceParent {
+    pub fn is_sampled(&self) -> bool {
+        self.flags & 0x01 == 0x01
+    }
+
+    pub fn trace_id_hex(&self) -> String {
+        format!("{:032x}", self.trace_id)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidTraceParent(String);
+
+impl fmt::Display for InvalidTraceParent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid traceparent {:?}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidTraceParent {}
+
+impl FromStr for TraceParent {
+    type Err = InvalidTraceParent;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidTraceParent(s.to_string());
+        let parts: Vec<_> = s.trim().split('-').collect();
+        let [version, trace_id, parent_id, flags] = parts.as_slice() else {
+            return Err(invalid());
+        };
+        let is_hex = |part: &str, len: usize| {
+            part.len() == len && part.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+        };
+        // Only version 00 is supported.
+        if *version != "00" || !is_hex(trace_id, 32) || !is_hex(parent_id, 16) || !is_hex(flags, 2)
+        {
+            return Err(invalid());
+        }
+        let trace_parent = TraceParent {
+            trace_id: u128::from_str_radix(trace_id, 16).map_err(|_| invalid())?,
+            parent_id: u64::from_str_radix(parent_id, 16).map_err(|_| invalid())?,
+            flags: u8::from_str_radix(flags, 16).map_err(|_| invalid())?,
+        };
+        // All zero ids are invalid.
+        if trace_parent.trace_id == 0 || trace_parent.parent_id == 0 {
+            return Err(invalid());
+        }
+        Ok(trace_parent)
+    }
+}
+
+impl fmt::Display for TraceParent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "00-{:032x}-{:016x}-{:02x}",
+            self.trace_id, self.parent_id, self.flags
+        )
+    }
+}
+
+/// Make `span` a child of the remote span identified by `traceparent`, continuing the trace of
+/// the caller.
+pub fn set_remote_parent(span: &Span, traceparent: &TraceParent, tracestate: Option<&str>) {
+    span.record(TRACE_ID_FIELD, traceparent.trace_id_hex().as_str());
+
+    #[cfg(feature = "otlp")]
+    {
+        use opentelemetry::propagation::TextMapPropagator;
+        use opentelemetry::sdk::propagation::TraceContextPropagator;
+        use std::collections::HashMap;
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+        let mut carrier = HashMap::new();
+        carrier.insert(TRACEPARENT_HEADER.to_string(), traceparent.to_string());
+        if let Some(tracestate) = tracestate {
+            carrier.insert(TRACESTATE_HEADER.to_string(), tracestate.to_string());
+        }
+        span.set_parent(TraceContextPropagator::new().extract(&carrier));
+    }
+    #[cfg(not(feature = "otlp"))]
+    let _ = tracestate;
+}
+
+/// The `traceparent` of the current span, to send to the services called while handling the
+/// request. None if spans are not exported.
+pub fn current_traceparent() -> Option<String> {
+    #[cfg(feature = "otlp")]
+    {
+        use opentelemetry::propagation::TextMapPropagator;
+        use opentelemetry::sdk::propagation::TraceContextPropagator;
+        use std::collections::HashMap;
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+        let mut carrier = HashMap::new();
+        TraceContextPropagator::new().inject_context(&Span::current().context(), &mut carrier);
+        carrier.remove(TRACEPARENT_HEADER)
+    }
+    #[cfg(not(feature = "otlp"))]
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_traceparent() {
+        let value = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let traceparent: TraceParent = value.parse().unwrap();
+        assert_eq!(
+            traceparent.trace_id_hex(),
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+        assert_eq!(traceparent.parent_id, 0x00f067aa0ba902b7);
+        assert!(traceparent.is_sampled());
+        assert_eq!(traceparent.to_string(), value);
+
+        for invalid in [
+            "",
+            "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01",
+        ] {
+            assert!(invalid.parse::<TraceParent>().is_err(), "{invalid}");
+        }
+    }
+}
@@ -4,4 +4,6 @@
 pub mod authority;
 pub mod messages;
 pub mod network;
+#[cfg(msim)]
+pub mod sim;
 pub mod transaction;
@@ -431,6 +431,8 @@ impl TestClusterBuilder {
             envs: Default::default(),
             active_address,
             active_env: Default::default(),
+            aliases: Default::default(),
+            watch_only: Default::default(),
         }
         .save(wallet_path)?;
 
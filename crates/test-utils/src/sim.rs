@@ -0,0 +1,153 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fault injection for simulation tests, which run the validators of a [TestCluster] on simulated
+//! nodes (see `crates/sui-simulator/README.md`). Faults are injected at the fail points of the
+//! validators, registered with `sui_macros`, and are random but reproducible: a test failing with
+//! a given `MSIM_TEST_SEED` fails again with that seed.
+//!
+//! [TestCluster]: crate::network::TestCluster
+
+use rand::{distributions::uniform::SampleRange, thread_rng, Rng};
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use sui_macros::{register_fail_point_async, register_fail_points};
+use sui_simulator::task::NodeId;
+use tracing::{error, info};
+
+/// Fail points around the writes of the validator databases.
+pub const STORAGE_FAIL_POINTS: &[&str] = &[
+    "batch-write-before",
+    "batch-write-after",
+    "put-cf-before",
+    "put-cf-after",
+    "delete-cf-before",
+    "delete-cf-after",
+    "transaction-commit",
+    "highest-executed-checkpoint",
+];
+
+/// Fail points of Narwhal, the consensus of the validators.
+pub const NARWHAL_FAIL_POINTS: &[&str] = &[
+    "narwhal-rpc-response",
+    "narwhal-store-before-write",
+    "narwhal-store-after-write",
+];
+
+struct DeadNode {
+    node_id: NodeId,
+    dead_until: Instant,
+}
+
+/// Crashes the nodes hitting fail points, and restarts them after a random delay. Only one node
+/// is crashed at a time, so that the network keeps a quorum, and the node running the test is
+/// never crashed. Clones share the crashed node.
+#[derive(Clone)]
+pub struct CrashInjector {
+    dead_node: Arc<Mutex<Option<DeadNode>>>,
+    client_node: NodeId,
+    restart_after_ms: Range<u64>,
+}
+
+impl CrashInjector {
+    /// Must be created by the node running the test.
+    pub fn new() -> Self {
+        Self {
+            dead_node: Default::default(),
+            client_node: sui_simulator::current_simnode_id(),
+            restart_after_ms: 10000..20000,
+        }
+    }
+
+    /// Restart crashed nodes after a delay sampled from `range` (10 to 20 seconds by default).
+    pub fn with_restart_after_ms(mut self, range: Range<u64>) -> Self {
+        self.restart_after_ms = range;
+        self
+    }
+
+    /// Crash the nodes hitting one of `fail_points` with `probability` (from 0.0 to 1.0).
+    pub fn crash_on(&self, fail_points: &[&'static str], probability: f64) {
+        let injector = self.clone();
+        register_fail_points(fail_points, move || injector.maybe_crash(probability));
+    }
+
+    /// Crash the nodes hitting the async fail point `fail_point` with `probability`.
+    pub fn crash_on_async(&self, fail_point: &'static str, probability: f64) {
+        let injector = self.clone();
+        register_fail_point_async(fail_point, move || {
+            let injector = injector.clone();
+            async move { injector.maybe_crash(probability) }
+        });
+    }
+
+    fn maybe_crash(&self, probability: f64) {
+        let mut dead_node = self.dead_node.lock().unwrap();
+        let cur_node = sui_simulator::current_simnode_id();
+
+        // never kill the client node (which is running the test)
+        if cur_node == self.client_node {
+            return;
+        }
+
+        // do not fail multiple nodes at a time.
+        if let Some(dead) = &*dead_node {
+            if dead.node_id != cur_node && dead.dead_until > Instant::now() {
+                return;
+            }
+        }
+
+        // otherwise, possibly fail the current node
+        let mut rng = thread_rng();
+        if rng.gen_range(0.0..1.0) < probability {
+            error!("Matched probability threshold for failpoint. Failing...");
+            let restart_after = Duration::from_millis(rng.gen_range(self.restart_after_ms.clone()));
+
+            *dead_node = Some(DeadNode {
+                node_id: cur_node,
+                dead_until: Instant::now() + restart_after,
+            });
+
+            // must manually release lock before calling kill_current_node, which panics
+            // and would poison the lock.
+            drop(dead_node);
+
+            sui_simulator::task::kill_current_node(Some(restart_after));
+        }
+    }
+}
+
+impl Default for CrashInjector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Delay the nodes hitting the async fail point `fail_point` with `probability`, by a duration
+/// sampled from `range_ms`.
+pub fn delay_on<R>(fail_point: &'static str, range_ms: R, probability: f64)
+where
+    R: SampleRange<u64> + Clone + Send + Sync + 'static,
+{
+    register_fail_point_async(fail_point, move || {
+        delay_failpoint(range_ms.clone(), probability)
+    });
+}
+
+async fn delay_failpoint<R>(range_ms: R, probability: f64)
+where
+    R: SampleRange<u64>,
+{
+    let duration = {
+        let mut rng = thread_rng();
+        if rng.gen_range(0.0..1.0) < probability {
+            info!("Matched probability threshold for delay failpoint. Delaying...");
+            Some(Duration::from_millis(rng.gen_range(range_ms)))
+        } else {
+            None
+        }
+    };
+    if let Some(duration) = duration {
+        tokio::time::sleep(duration).await;
+    }
+}
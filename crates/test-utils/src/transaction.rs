@@ -375,6 +375,7 @@ pub async fn create_devnet_nft(
         args,
         gas: Some(*gas_object),
         gas_budget: GAS_BUDGET_IN_UNIT * gas_price,
+        yes: true,
     }
     .execute(context)
     .await?;
@@ -418,7 +419,7 @@ pub async fn transfer_sui(
         .unwrap();
 
     let res = SuiClientCommands::TransferSui {
-        to: receiver,
+        to: receiver.into(),
         amount: None,
         sui_coin_object_id: gas_ref.0,
         gas_budget: GAS_BUDGET_IN_UNIT * gas_price,
@@ -478,10 +479,11 @@ pub async fn transfer_coin(
         object_to_send, sender, receiver
     );
     let res = SuiClientCommands::Transfer {
-        to: receiver,
+        to: receiver.into(),
         object_id: object_to_send,
         gas: None,
         gas_budget: GAS_BUDGET_IN_UNIT * gas_price,
+        yes: true,
     }
     .execute(context)
     .await?;
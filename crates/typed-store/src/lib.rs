@@ -8,13 +8,28 @@
     rust_2021_compatibility
 )]
 
+#[cfg(not(target_arch = "wasm32"))]
 pub mod traits;
+#[cfg(not(target_arch = "wasm32"))]
 pub use traits::Map;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod metrics;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod rocks;
 pub use rocks::TypedStoreError;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod sally;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod test_db;
+#[cfg(not(target_arch = "wasm32"))]
 pub use metrics::DBMetrics;
 
+/// Only the errors of the store build for wasm32 targets, which RocksDB does not build for, so
+/// that types embedding them, such as `SuiError`, have the same layout there.
+#[cfg(target_arch = "wasm32")]
+pub mod rocks {
+    pub mod errors;
+    pub use errors::TypedStoreError;
+}
+
 pub type StoreError = rocks::TypedStoreError;
@@ -3,9 +3,10 @@
 
 use bincode::ErrorKind as BincodeErrorKind;
 
+#[cfg(not(target_arch = "wasm32"))]
 use rocksdb::Error as RocksError;
 use serde::{Deserialize, Serialize};
-use std::{fmt, fmt::Display};
+use std::fmt;
 use thiserror::Error;
 
 #[non_exhaustive]
@@ -25,11 +26,13 @@ pub enum TypedStoreError {
     RetryableTransactionError,
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug, Error)]
 pub(crate) struct RocksErrorDef {
     message: String,
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl From<RocksError> for RocksErrorDef {
     fn from(err: RocksError) -> Self {
         RocksErrorDef {
@@ -38,13 +41,15 @@ impl From<RocksError> for RocksErrorDef {
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl From<RocksError> for TypedStoreError {
     fn from(err: RocksError) -> Self {
         TypedStoreError::RocksDBError(format!("{err}"))
     }
 }
 
-impl Display for RocksErrorDef {
+#[cfg(not(target_arch = "wasm32"))]
+impl fmt::Display for RocksErrorDef {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         self.message.fmt(formatter)
     }
@@ -411,6 +411,26 @@ impl RocksDB {
         delegate_call!(self.flush_cf(cf))
     }
 
+    pub fn flush_wal(&self, sync: bool) -> Result<(), rocksdb::Error> {
+        delegate_call!(self.flush_wal(sync))
+    }
+
+    /// Flush the memtables of the column families `cf_names` and sync the write-ahead log, so
+    /// that nothing needs to be recovered from the write-ahead log when the db is opened again.
+    pub fn flush_cfs<'a>(
+        &self,
+        cf_names: impl IntoIterator<Item = &'a str>,
+    ) -> Result<(), TypedStoreError> {
+        for cf_name in cf_names {
+            let cf = self
+                .cf_handle(cf_name)
+                .ok_or_else(|| TypedStoreError::UnregisteredColumn(cf_name.to_string()))?;
+            self.flush_cf(&cf)?;
+        }
+        self.flush_wal(/* sync */ true)?;
+        Ok(())
+    }
+
     pub fn set_options_cf(
         &self,
         cf: &impl AsColumnFamilyRef,
@@ -419,6 +439,10 @@ impl RocksDB {
         delegate_call!(self.set_options_cf(cf, opts))
     }
 
+    pub fn set_options(&self, opts: &[(&str, &str)]) -> Result<(), rocksdb::Error> {
+        delegate_call!(self.set_options(opts))
+    }
+
     pub fn read_sampling_interval(&self) -> SamplingInterval {
         match self {
             Self::DBWithThreadMode(d) => d.metric_conf.read_sample_interval.clone(),
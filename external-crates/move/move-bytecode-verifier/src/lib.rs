@@ -33,6 +33,7 @@ pub use script_signature::{
     legacy_script_signature_checks, no_additional_script_signature_checks, FnCheckScriptSignature,
 };
 pub use signature::SignatureChecker;
+pub use stack_usage_verifier::{stack_usage, StackUsage};
 pub use struct_defs::RecursiveStructDefChecker;
 pub use verifier::{
     verify_module, verify_module_with_config, verify_module_with_config_for_test, verify_script,
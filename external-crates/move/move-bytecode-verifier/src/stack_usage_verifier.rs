@@ -18,6 +18,45 @@ use move_binary_format::{
 };
 use move_core_types::vm_status::StatusCode;
 
+/// The largest use of the evaluation stack by the basic blocks of a function, which the verifier
+/// bounds by `max_value_stack_size` and `max_push_size`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StackUsage {
+    /// Largest height of the stack within a basic block, relative to its start.
+    pub max_stack_height: u64,
+    /// Largest number of values pushed by the instructions of a basic block.
+    pub max_block_pushes: u64,
+}
+
+/// Computes the stack usage of a function, e.g. to tell how close it is to the limits of the
+/// verifier. The function is expected to use the stack in a balanced manner.
+pub fn stack_usage<'a>(
+    resolver: &'a BinaryIndexedView<'a>,
+    function_view: &'a FunctionView,
+) -> PartialVMResult<StackUsage> {
+    let verifier = StackUsageVerifier {
+        resolver,
+        current_function: function_view.index(),
+        code: function_view.code(),
+        return_: function_view.return_(),
+    };
+    let cfg = function_view.cfg();
+    let mut usage = StackUsage::default();
+    for block_id in cfg.blocks() {
+        let mut height: u64 = 0;
+        let mut pushes: u64 = 0;
+        for i in cfg.block_start(block_id)..=cfg.block_end(block_id) {
+            let (num_pops, num_pushes) =
+                verifier.instruction_effect(&verifier.code.code[i as usize])?;
+            pushes = pushes.saturating_add(num_pushes);
+            height = height.saturating_sub(num_pops).saturating_add(num_pushes);
+            usage.max_stack_height = usage.max_stack_height.max(height);
+        }
+        usage.max_block_pushes = usage.max_block_pushes.max(pushes);
+    }
+    Ok(usage)
+}
+
 pub(crate) struct StackUsageVerifier<'a> {
     resolver: &'a BinaryIndexedView<'a>,
     current_function: Option<FunctionDefinitionIndex>,
@@ -2365,6 +2365,7 @@ impl<'env> ModuleEnv<'env> {
                 print_code: true,
                 print_basic_blocks: true,
                 print_locals: true,
+                print_source: false,
             },
         );
         disas
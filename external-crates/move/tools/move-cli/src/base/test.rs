@@ -3,11 +3,12 @@
 
 use super::reroot_path;
 use crate::NativeFunctionRecord;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use clap::*;
 use move_command_line_common::files::{FileHash, MOVE_COVERAGE_MAP_EXTENSION};
 use move_compiler::{
-    diagnostics::{self, codes::Severity},
+    compiled_unit::AnnotatedCompiledUnit,
+    diagnostics::{self, codes::Severity, Diagnostics, FilesSourceText},
     shared::{NumberFormat, NumericalAddress},
     unit_test::{plan_builder::construct_test_plan, TestPlan},
     PASS_CFGIR,
@@ -148,6 +149,31 @@ pub enum UnitTestResult {
 }
 
 pub fn run_move_unit_tests<W: Write + Send>(
+    pkg_path: &Path,
+    build_config: move_package::BuildConfig,
+    unit_test_config: UnitTestingConfig,
+    natives: Vec<NativeFunctionRecord>,
+    cost_table: Option<CostTable>,
+    compute_coverage: bool,
+    writer: &mut W,
+) -> Result<UnitTestResult> {
+    run_move_unit_tests_with_hook(
+        pkg_path,
+        build_config,
+        unit_test_config,
+        natives,
+        cost_table,
+        compute_coverage,
+        writer,
+        |_, _, _| Ok(()),
+    )
+}
+
+/// Like [run_move_unit_tests], but calls `compiled_units_hook` with the units compiled from the
+/// package and its dependencies before running the tests, e.g. to verify the units or to select
+/// the tests to run from them. An error of the hook aborts the run.
+#[allow(clippy::too_many_arguments)]
+pub fn run_move_unit_tests_with_hook<W: Write + Send>(
     pkg_path: &Path,
     mut build_config: move_package::BuildConfig,
     mut unit_test_config: UnitTestingConfig,
@@ -155,6 +181,11 @@ pub fn run_move_unit_tests<W: Write + Send>(
     cost_table: Option<CostTable>,
     compute_coverage: bool,
     writer: &mut W,
+    mut compiled_units_hook: impl FnMut(
+        &FilesSourceText,
+        &[AnnotatedCompiledUnit],
+        &mut UnitTestingConfig,
+    ) -> Result<()>,
 ) -> Result<UnitTestResult> {
     let mut test_plan = None;
     build_config.test_mode = true;
@@ -201,24 +232,23 @@ pub fn run_move_unit_tests<W: Write + Send>(
     // control back to the Move package system.
     build_plan.compile_with_driver(writer, |compiler| {
         let (files, comments_and_compiler_res) = compiler.run::<PASS_CFGIR>().unwrap();
-        let (_, compiler) =
-            diagnostics::unwrap_or_report_diagnostics(&files, comments_and_compiler_res);
+        let (_, compiler) = unwrap_or_report_error(&files, comments_and_compiler_res)?;
         let (mut compiler, cfgir) = compiler.into_ast();
         let compilation_env = compiler.compilation_env();
         let built_test_plan = construct_test_plan(compilation_env, Some(root_package), &cfgir);
-        if let Err(diags) = compilation_env.check_diags_at_or_above_severity(
+        let diags_result = compilation_env.check_diags_at_or_above_severity(
             if unit_test_config.ignore_compile_warnings {
                 Severity::NonblockingError
             } else {
                 Severity::Warning
             },
-        ) {
-            diagnostics::report_diagnostics(&files, diags);
-        }
+        );
+        unwrap_or_report_error(&files, diags_result)?;
 
         let compilation_result = compiler.at_cfgir(cfgir).build();
 
-        let (units, _) = diagnostics::unwrap_or_report_diagnostics(&files, compilation_result);
+        let (units, _) = unwrap_or_report_error(&files, compilation_result)?;
+        compiled_units_hook(&files, &units, &mut unit_test_config)?;
         test_plan = Some((built_test_plan, files.clone(), units.clone()));
         Ok((files, units))
     })?;
@@ -266,6 +296,18 @@ pub fn run_move_unit_tests<W: Write + Send>(
     Ok(UnitTestResult::Success)
 }
 
+/// Like [diagnostics::unwrap_or_report_diagnostics], but returns an error instead of exiting the
+/// process, so that a caller rerunning the tests can wait for the errors to be fixed.
+fn unwrap_or_report_error<T>(files: &FilesSourceText, result: Result<T, Diagnostics>) -> Result<T> {
+    result.map_err(|diags| {
+        let diags_buf = diagnostics::report_diagnostics_to_color_buffer(files, diags);
+        match std::io::stderr().write_all(&diags_buf) {
+            Ok(()) => anyhow!("Compilation error"),
+            Err(err) => anyhow!("Cannot output compiler diagnostics: {}", err),
+        }
+    })
+}
+
 impl From<UnitTestResult> for ExitStatus {
     fn from(result: UnitTestResult) -> Self {
         match result {
@@ -11,7 +11,7 @@ use move_binary_format::{
     binary_views::BinaryIndexedView,
     control_flow_graph::{ControlFlowGraph, VMControlFlowGraph},
     file_format::{
-        Ability, AbilitySet, Bytecode, CodeUnit, FieldHandleIndex, FunctionDefinition,
+        Ability, AbilitySet, Bytecode, CodeOffset, CodeUnit, FieldHandleIndex, FunctionDefinition,
         FunctionDefinitionIndex, FunctionHandle, ModuleHandle, Signature, SignatureIndex,
         SignatureToken, StructDefinition, StructDefinitionIndex, StructFieldInformation,
         StructTypeParameter, TableIndex, TypeSignature, Visibility,
@@ -44,6 +44,10 @@ pub struct DisassemblerOptions {
     /// Print the locals inside each function body.
     #[clap(long = "print-locals")]
     pub print_locals: bool,
+
+    /// Print the source line of the instructions before them, when the source code is available.
+    #[clap(long = "print-source")]
+    pub print_source: bool,
 }
 
 impl DisassemblerOptions {
@@ -53,6 +57,7 @@ impl DisassemblerOptions {
             print_code: true,
             print_basic_blocks: true,
             print_locals: true,
+            print_source: false,
         }
     }
 }
@@ -958,15 +963,20 @@ impl<'a> Disassembler<'a> {
             })
             .collect::<Result<Vec<String>>>()?;
 
+        let source_lines = self.source_lines(function_source_map, code.code.len());
         let mut instrs: Vec<String> = instrs
             .into_iter()
             .enumerate()
             .map(|(instr_index, dis_instr)| {
-                self.format_with_instruction_coverage(
+                let instr = self.format_with_instruction_coverage(
                     instr_index,
                     function_code_coverage_map,
                     dis_instr,
-                )
+                );
+                match source_lines.get(instr_index).and_then(Option::as_ref) {
+                    Some(source_line) => format!("{}\n{}", source_line, instr),
+                    None => instr,
+                }
             })
             .collect();
 
@@ -983,6 +993,38 @@ impl<'a> Disassembler<'a> {
         Ok(instrs)
     }
 
+    /// The source line of every instruction of a function starting a new source line, as a
+    /// comment, if `print_source` is set and the source code is available.
+    fn source_lines(
+        &self,
+        function_source_map: &FunctionSourceMap,
+        code_len: usize,
+    ) -> Vec<Option<String>> {
+        let source = match &self.source_mapper.source_code {
+            Some((_, source)) if self.options.print_source => source,
+            _ => return vec![],
+        };
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        let mut previous_line = None;
+        (0..code_len)
+            .map(|pc| {
+                let loc = function_source_map.get_code_location(pc as CodeOffset)?;
+                let line = line_starts.partition_point(|start| *start <= loc.start() as usize) - 1;
+                if previous_line == Some(line) {
+                    return None;
+                }
+                previous_line = Some(line);
+                let text = source[line_starts[line]..]
+                    .lines()
+                    .next()
+                    .unwrap_or_default();
+                Some(format!("\t// {}: {}", line + 1, text.trim()))
+            })
+            .collect()
+    }
+
     fn disassemble_struct_type_formals(
         source_map_ty_params: &[SourceName],
         type_parameters: &[StructTypeParameter],
@@ -21,7 +21,7 @@ use move_core_types::language_storage::ModuleId;
 use move_vm_runtime::native_functions::NativeFunctionTable;
 use move_vm_test_utils::gas_schedule::CostTable;
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     io::{Result, Write},
     marker::Send,
     sync::Mutex,
@@ -41,6 +41,11 @@ pub struct UnitTestingConfig {
     #[clap(name = "filter", short = 'f', long = "filter")]
     pub filter: Option<String>,
 
+    /// Only run the tests of the modules with these names, on top of `filter`, e.g. to rerun the
+    /// tests affected by a change
+    #[clap(skip)]
+    pub module_filter: Option<BTreeSet<String>>,
+
     /// List all tests
     #[clap(name = "list", short = 'l', long = "list")]
     pub list: bool,
@@ -136,6 +141,7 @@ impl UnitTestingConfig {
         Self {
             gas_limit: bound.or(Some(DEFAULT_EXECUTION_BOUND)),
             filter: None,
+            module_filter: None,
             num_threads: 8,
             report_statistics: None,
             report_storage_on_error: false,
@@ -260,6 +266,9 @@ impl UnitTestingConfig {
         if let Some(filter_str) = &self.filter {
             test_runner.filter(filter_str)
         }
+        if let Some(modules) = &self.module_filter {
+            test_runner.filter_modules(modules)
+        }
 
         let test_results = test_runner.run(&shared_writer).unwrap();
         if let Some(report_type) = &self.report_statistics {
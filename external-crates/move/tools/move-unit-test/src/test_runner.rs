@@ -44,7 +44,13 @@ use move_vm_test_utils::{
     InMemoryStorage,
 };
 use rayon::prelude::*;
-use std::{collections::BTreeMap, io::Write, marker::Send, sync::Mutex, time::Instant};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::Write,
+    marker::Send,
+    sync::Mutex,
+    time::Instant,
+};
 
 use move_vm_runtime::native_extensions::NativeContextExtensions;
 #[cfg(feature = "evm-backend")]
@@ -201,6 +207,15 @@ impl TestRunner {
             })
     }
 
+    /// Drops the tests of the modules whose name is not in `module_names`.
+    pub fn filter_modules(&mut self, module_names: &BTreeSet<String>) {
+        for (module_id, module_test) in self.tests.module_tests.iter_mut() {
+            if !module_names.contains(module_id.name().as_str()) {
+                module_test.tests.clear();
+            }
+        }
+    }
+
     pub fn filter(&mut self, test_name_slice: &str) {
         for (module_id, module_test) in self.tests.module_tests.iter_mut() {
             if module_id.name().as_str().contains(test_name_slice) {
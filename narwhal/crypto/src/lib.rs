@@ -12,8 +12,9 @@ use fastcrypto::{
     bls12381, ed25519,
     error::FastCryptoError,
     hash::{Blake2b256, HashFunction},
-    traits::{AggregateAuthenticator, Signer, VerifyingKey},
+    traits::{AggregateAuthenticator, KeyPair as _, Signer, VerifyingKey},
 };
+use std::sync::Arc;
 
 // This re-export allows using the trait-defined APIs
 pub use fastcrypto::traits;
@@ -118,3 +119,33 @@ impl NarwhalAuthorityAggregateSignature for AggregateSignature {
 pub fn to_intent_message<T>(value: T) -> IntentMessage<T> {
     IntentMessage::new(Intent::narwhal_app(IntentScope::HeaderDigest), value)
 }
+
+/// Signs with the protocol key of this authority. The key is either held in memory, or by an
+/// external signer, e.g. an HSM, in which case only its public key is known to the node.
+#[derive(Clone)]
+pub struct ProtocolSigner {
+    public: PublicKey,
+    signer: Arc<dyn Signer<Signature> + Send + Sync>,
+}
+
+impl ProtocolSigner {
+    pub fn new(public: PublicKey, signer: Arc<dyn Signer<Signature> + Send + Sync>) -> Self {
+        Self { public, signer }
+    }
+
+    pub fn public(&self) -> &PublicKey {
+        &self.public
+    }
+}
+
+impl From<KeyPair> for ProtocolSigner {
+    fn from(keypair: KeyPair) -> Self {
+        Self::new(keypair.public().clone(), Arc::new(keypair))
+    }
+}
+
+impl Signer<Signature> for ProtocolSigner {
+    fn sign(&self, msg: &[u8]) -> Signature {
+        self.signer.sign(msg)
+    }
+}
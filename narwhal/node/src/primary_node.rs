@@ -9,7 +9,7 @@ use consensus::consensus::ConsensusRound;
 use consensus::dag::Dag;
 use consensus::metrics::{ChannelMetrics, ConsensusMetrics};
 use consensus::Consensus;
-use crypto::{KeyPair, NetworkKeyPair, PublicKey};
+use crypto::{NetworkKeyPair, ProtocolSigner, PublicKey};
 use executor::{get_restored_consensus_output, ExecutionState, Executor, SubscriberResult};
 use fastcrypto::traits::{KeyPair as _, VerifyingKey};
 use mysten_metrics::{RegistryID, RegistryService};
@@ -62,7 +62,7 @@ impl PrimaryNodeInner {
     #[instrument(level = "info", skip_all)]
     async fn start<State>(
         &mut self, // The private-public key pair of this authority.
-        keypair: KeyPair,
+        keypair: impl Into<ProtocolSigner>,
         // The private-public network key pair of this authority.
         network_keypair: NetworkKeyPair,
         // The committee information.
@@ -184,7 +184,7 @@ impl PrimaryNodeInner {
     /// Spawn a new primary. Optionally also spawn the consensus and a client executing transactions.
     pub async fn spawn_primary<State>(
         // The private-public key pair of this authority.
-        keypair: KeyPair,
+        keypair: impl Into<ProtocolSigner>,
         // The private-public network key pair of this authority.
         network_keypair: NetworkKeyPair,
         // The committee information.
@@ -232,6 +232,7 @@ impl PrimaryNodeInner {
             metered_channel::channel(Self::CHANNEL_CAPACITY, &committed_certificates_counter);
 
         // Compute the public key of this authority.
+        let keypair = keypair.into();
         let name = keypair.public().clone();
 
         // Figure out the id for this authority
@@ -422,7 +423,7 @@ impl PrimaryNode {
 
     pub async fn start<State>(
         &self, // The private-public key pair of this authority.
-        keypair: KeyPair,
+        keypair: impl Into<ProtocolSigner>,
         // The private-public network key pair of this authority.
         network_keypair: NetworkKeyPair,
         // The committee information.
@@ -30,7 +30,7 @@ use config::{Authority, AuthorityIdentifier, Committee, Parameters, WorkerCache}
 use consensus::consensus::ConsensusRound;
 use consensus::dag::Dag;
 use crypto::traits::EncodeDecodeBase64;
-use crypto::{KeyPair, NetworkKeyPair, NetworkPublicKey, Signature};
+use crypto::{NetworkKeyPair, NetworkPublicKey, ProtocolSigner, Signature};
 use fastcrypto::{
     hash::Hash,
     signature_service::SignatureService,
@@ -101,7 +101,7 @@ impl Primary {
     #[allow(clippy::too_many_arguments)]
     pub fn spawn(
         authority: Authority,
-        signer: KeyPair,
+        signer: impl Into<ProtocolSigner>,
         network_signer: NetworkKeyPair,
         committee: Committee,
         worker_cache: WorkerCache,
@@ -204,7 +204,7 @@ impl Primary {
             node_metrics.clone(),
         ));
 
-        let signature_service = SignatureService::new(signer);
+        let signature_service = SignatureService::new(signer.into());
 
         // Spawn the network receiver listening to messages from the other primaries.
         let address = authority.primary_address();
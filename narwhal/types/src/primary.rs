@@ -28,6 +28,8 @@ use proptest_derive::Arbitrary;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
+#[cfg(feature = "clock-control")]
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::{
     collections::{BTreeMap, BTreeSet, VecDeque},
     fmt,
@@ -60,11 +62,43 @@ impl Timestamp for TimestampMs {
 // timestamp in milliseconds
 pub fn now() -> TimestampMs {
     match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
-        Ok(n) => n.as_millis() as TimestampMs,
+        Ok(n) => (n.as_millis() as TimestampMs).saturating_add(clock_offset_ms()),
         Err(_) => panic!("SystemTime before UNIX EPOCH!"),
     }
 }
 
+/// Offset added to the system time by [now], see [advance_clock].
+#[cfg(feature = "clock-control")]
+static CLOCK_OFFSET_MS: AtomicU64 = AtomicU64::new(0);
+
+#[cfg(feature = "clock-control")]
+fn clock_offset_ms() -> u64 {
+    CLOCK_OFFSET_MS.load(Ordering::Relaxed)
+}
+
+#[cfg(not(feature = "clock-control"))]
+fn clock_offset_ms() -> u64 {
+    0
+}
+
+/// Moves the clock of the primaries of this process forward by `duration`, and with it the
+/// timestamps of the headers they propose and of the sub dags they commit. Returns the total
+/// offset of the clock, which saturates at `u64::MAX` milliseconds.
+///
+/// Only meant for local networks whose primaries all run in one process, as primaries of other
+/// processes reject headers from more than a second in the future. It is therefore only built
+/// with the `clock-control` feature, which only the `sui` CLI enables, for `sui start`.
+#[cfg(feature = "clock-control")]
+pub fn advance_clock(duration: Duration) -> Duration {
+    let millis = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+    let previous = CLOCK_OFFSET_MS
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |offset| {
+            Some(offset.saturating_add(millis))
+        })
+        .expect("The offset is always updated");
+    Duration::from_millis(previous.saturating_add(millis))
+}
+
 // Additional metadata information for an entity.
 //
 // The structure as a whole is not signed. As a result this data